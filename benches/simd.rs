@@ -0,0 +1,39 @@
+use bencher::{benchmark_group, benchmark_main, black_box, Bencher};
+use ta_panther::indicators::{
+    HighestHighValue, LowestLowValue, SimpleMovingAverage, StandardDeviation, WeightedMovingAverage,
+};
+use ta_panther::testing::random_series;
+use ta_panther::{simd, NextBatch};
+
+const ITEMS_COUNT: usize = 100_000;
+const PERIOD: usize = 50;
+
+macro_rules! bench_kernels {
+    ($($scalar:ident, $vectorized:ident => $indicator:ident, $kernel:path;)*) => {
+        $(
+            fn $scalar(bench: &mut Bencher) {
+                let input = random_series(42, ITEMS_COUNT);
+                let indicator = $indicator::new(PERIOD).unwrap();
+
+                bench.iter(|| black_box(indicator.clone().next_batch(&input)))
+            }
+
+            fn $vectorized(bench: &mut Bencher) {
+                let input = random_series(42, ITEMS_COUNT);
+
+                bench.iter(|| black_box($kernel(&input, PERIOD).unwrap()))
+            }
+        )*
+
+        benchmark_group!(benches, $($scalar, $vectorized,)*);
+        benchmark_main!(benches);
+    }
+}
+
+bench_kernels!(
+    sma_scalar, sma_simd => SimpleMovingAverage, simd::sma;
+    wma_scalar, wma_simd => WeightedMovingAverage, simd::wma;
+    std_dev_scalar, std_dev_simd => StandardDeviation, simd::std_dev;
+    rolling_max_scalar, rolling_max_simd => HighestHighValue, simd::rolling_max;
+    rolling_min_scalar, rolling_min_simd => LowestLowValue, simd::rolling_min;
+);
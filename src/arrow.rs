@@ -0,0 +1,535 @@
+//! Apache Arrow integration.
+//!
+//! Available with the `arrow` feature.
+//!
+//! # Columnar computation
+//!
+//! [compute] and [compute_ohlcv] run an indicator over whole Arrow columns and return its
+//! outputs as a column of the same length, so dataframe pipelines (e.g. Polars, whose series
+//! convert to and from Arrow arrays without copying) don't go through per-row loops. Null
+//! inputs give null outputs and are not fed into the indicator, and the outputs of the warm-up
+//! period are null as well.
+//!
+//! ```
+//! use arrow_array::{Array, Float64Array};
+//! use ta_panther::arrow::compute;
+//! use ta_panther::indicators::SimpleMovingAverage;
+//!
+//! let close = Float64Array::from(vec![Some(1.0), Some(2.0), None, Some(4.0), Some(6.0)]);
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//!
+//! let output = compute(&mut sma, &close, 1);
+//! assert_eq!(output, Float64Array::from(vec![None, Some(1.5), None, Some(3.0), Some(5.0)]));
+//! ```
+//!
+//! # Live outputs
+//!
+//! [OutputTable] keeps the latest output of every (symbol, indicator) pair and turns it into a
+//! [RecordBatch] with the columns `symbol`, `indicator`, `value` and `sequence`. The `sequence`
+//! column is a counter which grows with every update, so consumers can detect stale rows.
+//! Record batches can be written in the Arrow IPC stream format to any [Write], and
+//! [serve_ipc] answers TCP clients with snapshots of a shared table.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::arrow::OutputTable;
+//!
+//! let mut table = OutputTable::new();
+//! table.update("AAPL", "EMA(9)", 151.2);
+//! table.update("AAPL", "RSI(14)", 61.5);
+//! table.update("MSFT", "EMA(9)", 402.7);
+//!
+//! assert_eq!(table.record_batch().num_rows(), 3);
+//! assert_eq!(table.query(Some("AAPL"), None).num_rows(), 2);
+//!
+//! let mut buffer = Vec::new();
+//! table.write_ipc(&mut buffer).unwrap();
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+
+use crate::pool::IndicatorPool;
+use crate::{Close, High, Low, Next, Open, Volume};
+
+/// Computes an indicator over a column of values.
+///
+/// Null values give null outputs and are not fed into the indicator. The outputs of the first
+/// `warm_up` values which are not null are null as well, e.g. `period - 1` for a moving
+/// average, or the [bars_remaining](crate::IsReady::bars_remaining) of a fresh indicator minus
+/// one.
+pub fn compute<N>(indicator: &mut N, column: &Float64Array, warm_up: usize) -> Float64Array
+where
+    N: Next<f64, Output = f64> + ?Sized,
+{
+    let mut fed = 0;
+    column
+        .iter()
+        .map(|value| {
+            let output = indicator.next(value?);
+            fed += 1;
+            (fed > warm_up).then_some(output)
+        })
+        .collect()
+}
+
+/// Values of a bar, read from [OhlcvColumns].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OhlcvRow {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl Open for OhlcvRow {
+    fn open(&self) -> f64 {
+        self.open
+    }
+}
+
+impl High for OhlcvRow {
+    fn high(&self) -> f64 {
+        self.high
+    }
+}
+
+impl Low for OhlcvRow {
+    fn low(&self) -> f64 {
+        self.low
+    }
+}
+
+impl Close for OhlcvRow {
+    fn close(&self) -> f64 {
+        self.close
+    }
+}
+
+impl Volume for OhlcvRow {
+    fn volume(&self) -> f64 {
+        self.volume
+    }
+}
+
+/// Columns of open, high, low and close prices and volumes, all of the same length.
+#[derive(Debug, Clone, Copy)]
+pub struct OhlcvColumns<'a> {
+    open: &'a Float64Array,
+    high: &'a Float64Array,
+    low: &'a Float64Array,
+    close: &'a Float64Array,
+    volume: &'a Float64Array,
+}
+
+impl<'a> OhlcvColumns<'a> {
+    /// Returns an error if the columns have different lengths.
+    pub fn new(
+        open: &'a Float64Array,
+        high: &'a Float64Array,
+        low: &'a Float64Array,
+        close: &'a Float64Array,
+        volume: &'a Float64Array,
+    ) -> Result<Self, ArrowError> {
+        let len = open.len();
+        if [high, low, close, volume]
+            .iter()
+            .any(|column| column.len() != len)
+        {
+            return Err(ArrowError::InvalidArgumentError(
+                "OHLCV columns have different lengths".to_string(),
+            ));
+        }
+        Ok(Self {
+            open,
+            high,
+            low,
+            close,
+            volume,
+        })
+    }
+
+    /// Reads the columns `open`, `high`, `low`, `close` and `volume` of a record batch.
+    ///
+    /// Returns an error if a column is missing or is not of type `Float64`.
+    pub fn from_record_batch(batch: &'a RecordBatch) -> Result<Self, ArrowError> {
+        let column = |name: &str| -> Result<&'a Float64Array, ArrowError> {
+            batch
+                .column_by_name(name)
+                .ok_or_else(|| ArrowError::SchemaError(format!("missing column {name}")))?
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .ok_or_else(|| ArrowError::SchemaError(format!("column {name} is not Float64")))
+        };
+        Self::new(
+            column("open")?,
+            column("high")?,
+            column("low")?,
+            column("close")?,
+            column("volume")?,
+        )
+    }
+
+    /// Returns the number of bars.
+    pub fn len(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Returns `true` if there are no bars.
+    pub fn is_empty(&self) -> bool {
+        self.open.is_empty()
+    }
+
+    /// Returns the bar at `index`, or `None` if one of its values is null.
+    pub fn row(&self, index: usize) -> Option<OhlcvRow> {
+        let value = |column: &Float64Array| column.is_valid(index).then(|| column.value(index));
+        Some(OhlcvRow {
+            open: value(self.open)?,
+            high: value(self.high)?,
+            low: value(self.low)?,
+            close: value(self.close)?,
+            volume: value(self.volume)?,
+        })
+    }
+}
+
+/// Computes an indicator over columns of bars.
+///
+/// Bars with a null value give null outputs and are not fed into the indicator. The outputs of
+/// the first `warm_up` bars without nulls are null as well, see [compute].
+pub fn compute_ohlcv<N>(indicator: &mut N, columns: &OhlcvColumns, warm_up: usize) -> Float64Array
+where
+    N: for<'b> Next<&'b OhlcvRow, Output = f64> + ?Sized,
+{
+    let mut fed = 0;
+    (0..columns.len())
+        .map(|index| {
+            let output = indicator.next(&columns.row(index)?);
+            fed += 1;
+            (fed > warm_up).then_some(output)
+        })
+        .collect()
+}
+
+/// Latest outputs of indicators, keyed by symbol and indicator name.
+#[derive(Debug, Clone, Default)]
+pub struct OutputTable {
+    rows: BTreeMap<(String, String), (f64, u64)>,
+    sequence: u64,
+}
+
+impl OutputTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the schema of the record batches produced by the table.
+    pub fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("symbol", DataType::Utf8, false),
+            Field::new("indicator", DataType::Utf8, false),
+            Field::new("value", DataType::Float64, false),
+            Field::new("sequence", DataType::UInt64, false),
+        ]))
+    }
+
+    /// Stores the latest `value` of `indicator` for `symbol`.
+    pub fn update(&mut self, symbol: &str, indicator: &str, value: f64) {
+        self.sequence += 1;
+        self.rows.insert(
+            (symbol.to_string(), indicator.to_string()),
+            (value, self.sequence),
+        );
+    }
+
+    /// Stores the latest outputs of all symbols of `pool`, using the pool's prototype as
+    /// indicator name.
+    pub fn update_pool<K, I>(&mut self, pool: &IndicatorPool<K, I, f64>)
+    where
+        K: Eq + Hash + Display,
+        I: Clone + Display,
+    {
+        let indicator = pool.prototype().to_string();
+        for (symbol, &value) in pool.iter() {
+            self.update(&symbol.to_string(), &indicator, value);
+        }
+    }
+
+    /// Removes all the rows of `symbol`.
+    pub fn remove_symbol(&mut self, symbol: &str) {
+        self.rows.retain(|(s, _), _| s != symbol);
+    }
+
+    /// Returns the number of rows in the table.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns all the rows as a record batch, ordered by symbol and indicator.
+    pub fn record_batch(&self) -> RecordBatch {
+        self.query(None, None)
+    }
+
+    /// Returns the rows matching the given symbol and indicator as a record batch.
+    ///
+    /// `None` matches any value.
+    pub fn query(&self, symbol: Option<&str>, indicator: Option<&str>) -> RecordBatch {
+        let rows: Vec<_> = self
+            .rows
+            .iter()
+            .filter(|((s, i), _)| {
+                symbol.is_none_or(|symbol| symbol == s)
+                    && indicator.is_none_or(|indicator| indicator == i)
+            })
+            .collect();
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|((s, _), _)| s.as_str()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|((_, i), _)| i.as_str()),
+            )),
+            Arc::new(Float64Array::from_iter_values(
+                rows.iter().map(|(_, (value, _))| *value),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                rows.iter().map(|(_, (_, sequence))| *sequence),
+            )),
+        ];
+
+        RecordBatch::try_new(Self::schema(), columns).expect("columns always match the schema")
+    }
+
+    /// Writes all the rows to `writer` in the Arrow IPC stream format.
+    pub fn write_ipc<W: Write>(&self, writer: W) -> Result<(), ArrowError> {
+        write_batch(writer, &self.record_batch())
+    }
+}
+
+fn write_batch<W: Write>(writer: W, batch: &RecordBatch) -> Result<(), ArrowError> {
+    let mut writer = StreamWriter::try_new(writer, &batch.schema())?;
+    writer.write(batch)?;
+    writer.finish()
+}
+
+/// Serves snapshots of a shared [OutputTable] over TCP in the Arrow IPC stream format.
+///
+/// Every client sends a single query line and receives one IPC stream in response, after which
+/// the connection is closed. An empty line requests the whole table, `SYMBOL` requests the
+/// rows of a single symbol and `SYMBOL INDICATOR` a single row. Use `*` as a symbol to match
+/// any symbol.
+///
+/// The function blocks, handling every client on its own thread, and only returns if accepting
+/// a connection fails. Errors of individual connections are ignored.
+pub fn serve_ipc(listener: TcpListener, table: Arc<RwLock<OutputTable>>) -> std::io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept()?;
+        let table = Arc::clone(&table);
+        thread::spawn(move || {
+            let mut line = String::new();
+            if BufReader::new(&stream).read_line(&mut line).is_err() {
+                return;
+            }
+
+            let mut parts = line.split_whitespace();
+            let symbol = parts.next().filter(|&symbol| symbol != "*");
+            let indicator = parts.next();
+            let batch = match table.read() {
+                Ok(table) => table.query(symbol, indicator),
+                Err(poisoned) => poisoned.into_inner().query(symbol, indicator),
+            };
+            let _ = write_batch(&stream, &batch);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{AverageTrueRange, SimpleMovingAverage};
+    use crate::test_helper::*;
+    use crate::Reset;
+    use arrow_ipc::reader::StreamReader;
+
+    fn values(batch: &RecordBatch) -> Vec<f64> {
+        let column = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        (0..column.len()).map(|i| column.value(i)).collect()
+    }
+
+    #[test]
+    fn test_update_and_query() {
+        let mut table = OutputTable::new();
+        table.update("B", "SMA(3)", 2.0);
+        table.update("A", "SMA(3)", 1.0);
+        table.update("A", "RSI(14)", 50.0);
+        table.update("A", "SMA(3)", 1.5);
+
+        assert_eq!(table.len(), 3);
+        assert_eq!(values(&table.record_batch()), vec![50.0, 1.5, 2.0]);
+        assert_eq!(values(&table.query(Some("A"), Some("SMA(3)"))), vec![1.5]);
+        assert_eq!(values(&table.query(None, Some("SMA(3)"))), vec![1.5, 2.0]);
+        assert_eq!(table.query(Some("C"), None).num_rows(), 0);
+
+        table.remove_symbol("A");
+        assert_eq!(values(&table.record_batch()), vec![2.0]);
+    }
+
+    #[test]
+    fn test_update_pool() {
+        let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+        pool.next(("A", 2.0));
+        pool.next(("A", 4.0));
+        pool.next(("B", 8.0));
+
+        let mut table = OutputTable::new();
+        table.update_pool(&pool);
+        assert_eq!(values(&table.query(None, Some("SMA(2)"))), vec![3.0, 8.0]);
+    }
+
+    #[test]
+    fn test_write_ipc() {
+        let mut table = OutputTable::new();
+        table.update("A", "SMA(3)", 1.0);
+        table.update("B", "SMA(3)", 2.0);
+
+        let mut buffer = Vec::new();
+        table.write_ipc(&mut buffer).unwrap();
+
+        let reader = StreamReader::try_new(buffer.as_slice(), None).unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0], table.record_batch());
+    }
+
+    #[test]
+    fn test_serve_ipc() {
+        let mut table = OutputTable::new();
+        table.update("A", "SMA(3)", 1.0);
+        table.update("B", "SMA(3)", 2.0);
+        let table = Arc::new(RwLock::new(table));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let shared = Arc::clone(&table);
+        std::thread::spawn(move || serve_ipc(listener, shared));
+
+        let query = |stream: &mut std::net::TcpStream, line: &[u8]| {
+            stream.write_all(line).unwrap();
+            let reader = StreamReader::try_new(stream, None).unwrap();
+            let batches: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+            values(&batches[0])
+        };
+
+        // a client which hasn't sent its query yet doesn't block the others
+        let mut idle = std::net::TcpStream::connect(addr).unwrap();
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        assert_eq!(query(&mut stream, b"B\n"), vec![2.0]);
+        assert_eq!(query(&mut idle, b"* SMA(3)\n"), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_compute() {
+        let column = Float64Array::from(vec![Some(2.0), None, Some(4.0), Some(9.0), None]);
+
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        let output = compute(&mut sma, &column, 0);
+        assert_eq!(
+            output,
+            Float64Array::from(vec![Some(2.0), None, Some(3.0), Some(6.5), None])
+        );
+
+        sma.reset();
+        let output = compute(&mut sma, &column, 2);
+        assert_eq!(
+            output,
+            Float64Array::from(vec![None, None, None, Some(6.5), None])
+        );
+    }
+
+    #[test]
+    fn test_compute_ohlcv() {
+        let bars = [
+            Bar::new().high(10).low(7.5).close(9),
+            Bar::new().high(11).low(9).close(9.5),
+            Bar::new().high(9).low(5).close(8),
+        ];
+        let column = |value: fn(&Bar) -> f64, null: Option<usize>| {
+            Float64Array::from_iter(
+                bars.iter()
+                    .enumerate()
+                    .map(|(index, bar)| (null != Some(index)).then(|| value(bar))),
+            )
+        };
+        let open = column(<Bar as Open>::open, None);
+        let high = column(<Bar as High>::high, None);
+        let low = column(<Bar as Low>::low, Some(1));
+        let close = column(<Bar as Close>::close, None);
+        let volume = column(<Bar as Volume>::volume, None);
+        let columns = OhlcvColumns::new(&open, &high, &low, &close, &volume).unwrap();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns.row(1), None);
+
+        let mut atr = AverageTrueRange::new(3).unwrap();
+        let output = compute_ohlcv(&mut atr, &columns, 0);
+        let mut expected = AverageTrueRange::new(3).unwrap();
+        assert_eq!(
+            output,
+            Float64Array::from(vec![
+                Some(expected.next(&bars[0])),
+                None,
+                Some(expected.next(&bars[2])),
+            ])
+        );
+
+        let short = Float64Array::from(vec![1.0]);
+        assert!(OhlcvColumns::new(&open, &high, &low, &close, &short).is_err());
+    }
+
+    #[test]
+    fn test_ohlcv_from_record_batch() {
+        let column = |value: f64| Arc::new(Float64Array::from(vec![value])) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([
+            ("open", column(1.0)),
+            ("high", column(3.0)),
+            ("low", column(0.5)),
+            ("close", column(2.0)),
+            ("volume", column(100.0)),
+        ])
+        .unwrap();
+        let columns = OhlcvColumns::from_record_batch(&batch).unwrap();
+        assert_eq!(
+            columns.row(0),
+            Some(OhlcvRow {
+                open: 1.0,
+                high: 3.0,
+                low: 0.5,
+                close: 2.0,
+                volume: 100.0,
+            })
+        );
+
+        let batch = RecordBatch::try_from_iter([("close", column(2.0))]).unwrap();
+        assert!(OhlcvColumns::from_record_batch(&batch).is_err());
+    }
+}
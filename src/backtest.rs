@@ -0,0 +1,479 @@
+//! Backtests of indicator signals.
+//!
+//! A [Backtest] trades a single position on a series of bars, driven by entry and exit
+//! signals, e.g. the outputs of [CrossAbove](crate::indicators::CrossAbove) and
+//! [CrossBelow](crate::indicators::CrossBelow). It records the equity curve and the closed
+//! trades, and reports the usual statistics of a strategy: the win rate, the maximum drawdown
+//! and the profit factor.
+//!
+//! The engine is deliberately minimal. Signals are computed on the close of a bar, so they are
+//! filled at the open of the next bar to avoid trading on prices not known yet. The whole
+//! equity is invested in every trade, in fractional units, and a commission is charged on the
+//! value of every fill. There is no slippage, no leverage and no pyramiding.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::backtest::Backtest;
+//! use ta_panther::indicators::{CrossAbove, CrossBelow};
+//! use ta_panther::{DataItem, Next};
+//!
+//! let mut entry = CrossAbove::new(10.0).unwrap();
+//! let mut exit = CrossBelow::new(10.0).unwrap();
+//! let mut backtest = Backtest::new(1000.0).unwrap();
+//!
+//! for &(open, close) in [(9.0, 9.0), (9.0, 11.0), (11.0, 12.0), (12.0, 9.0), (10.0, 9.0)].iter() {
+//!     let bar = DataItem::builder()
+//!         .open(open)
+//!         .high(open.max(close))
+//!         .low(open.min(close))
+//!         .close(close)
+//!         .volume(100.0)
+//!         .build()
+//!         .unwrap();
+//!     backtest.next((&bar, entry.next(close), exit.next(close)));
+//! }
+//!
+//! // bought at 11 and sold at 10
+//! assert_eq!(backtest.trades().len(), 1);
+//! assert_eq!(backtest.win_rate(), 0.0);
+//! assert_eq!(backtest.equity().round(), 909.0);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::MaxDrawdown;
+use crate::stops::Direction;
+use crate::{Close, Next, Open, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Closed trade of a [Backtest].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trade {
+    /// Direction of the position.
+    pub direction: Direction,
+    /// Index of the bar the position was opened on.
+    pub entry_index: usize,
+    /// Index of the bar the position was closed on.
+    pub exit_index: usize,
+    /// Fill price of the entry.
+    pub entry_price: f64,
+    /// Fill price of the exit.
+    pub exit_price: f64,
+    /// Number of units traded.
+    pub quantity: f64,
+    /// Profit or loss, net of the commissions of both fills.
+    pub pnl: f64,
+    /// Profit or loss relative to the equity at the entry.
+    pub return_: f64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Position {
+    direction: Direction,
+    entry_index: usize,
+    entry_price: f64,
+    quantity: f64,
+    equity: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Order {
+    Enter,
+    Exit,
+}
+
+/// Single position backtest.
+///
+/// The inputs are triples `(bar, entry, exit)`. An entry signal while there is no position
+/// opens one, an exit signal while there is one closes it, both at the open of the next bar.
+/// Other signals are ignored. Returns the equity at the close of the bar.
+///
+/// # Parameters
+///
+/// * _initial_equity_ - equity at the start (greater than 0).
+/// * _commission_ - share of the value of a fill charged as commission (at least 0, less than
+///   1), set by [with_commission](Backtest::with_commission). Default is 0.
+/// * _direction_ - direction of the positions, set by
+///   [with_direction](Backtest::with_direction). Default is [Long](Direction::Long).
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Backtest {
+    initial_equity: f64,
+    commission: f64,
+    direction: Direction,
+    index: usize,
+    cash: f64,
+    position: Option<Position>,
+    order: Option<Order>,
+    equity_curve: Vec<f64>,
+    trades: Vec<Trade>,
+    drawdown: MaxDrawdown,
+    max_drawdown: f64,
+}
+
+impl Backtest {
+    pub fn new(initial_equity: f64) -> Result<Self> {
+        Self::with_commission(initial_equity, 0.0)
+    }
+
+    pub fn with_commission(initial_equity: f64, commission: f64) -> Result<Self> {
+        Self::with_direction(initial_equity, commission, Direction::Long)
+    }
+
+    pub fn with_direction(
+        initial_equity: f64,
+        commission: f64,
+        direction: Direction,
+    ) -> Result<Self> {
+        if !(initial_equity > 0.0 && initial_equity.is_finite() && (0.0..1.0).contains(&commission))
+        {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            initial_equity,
+            commission,
+            direction,
+            index: 0,
+            cash: initial_equity,
+            position: None,
+            order: None,
+            equity_curve: Vec::new(),
+            trades: Vec::new(),
+            drawdown: MaxDrawdown::new(),
+            max_drawdown: 0.0,
+        })
+    }
+
+    pub fn initial_equity(&self) -> f64 {
+        self.initial_equity
+    }
+
+    pub fn commission(&self) -> f64 {
+        self.commission
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns whether a position is open.
+    pub fn in_position(&self) -> bool {
+        self.position.is_some()
+    }
+
+    /// Returns the equity at the close of the latest bar.
+    pub fn equity(&self) -> f64 {
+        self.equity_curve
+            .last()
+            .copied()
+            .unwrap_or(self.initial_equity)
+    }
+
+    /// Returns the equity at the close of every bar.
+    pub fn equity_curve(&self) -> &[f64] {
+        &self.equity_curve
+    }
+
+    /// Returns the closed trades. A position still open is not included.
+    pub fn trades(&self) -> &[Trade] {
+        &self.trades
+    }
+
+    /// Returns the share of the closed trades with a profit, 0 before the first trade.
+    pub fn win_rate(&self) -> f64 {
+        if self.trades.is_empty() {
+            return 0.0;
+        }
+        let wins = self.trades.iter().filter(|trade| trade.pnl > 0.0).count();
+        wins as f64 / self.trades.len() as f64
+    }
+
+    /// Returns the largest drawdown of the equity curve, as a fraction of its peak.
+    pub fn max_drawdown(&self) -> f64 {
+        self.max_drawdown
+    }
+
+    /// Returns the gross profit of the closed trades divided by their gross loss. Infinite if
+    /// no trade lost, 0 before the first profitable trade.
+    pub fn profit_factor(&self) -> f64 {
+        let (profit, loss) = self
+            .trades
+            .iter()
+            .fold((0.0, 0.0), |(profit, loss), trade| {
+                if trade.pnl > 0.0 {
+                    (profit + trade.pnl, loss)
+                } else {
+                    (profit, loss - trade.pnl)
+                }
+            });
+        if profit == 0.0 {
+            0.0
+        } else if loss == 0.0 {
+            f64::INFINITY
+        } else {
+            profit / loss
+        }
+    }
+
+    fn sign(direction: Direction) -> f64 {
+        match direction {
+            Direction::Long => 1.0,
+            Direction::Short => -1.0,
+        }
+    }
+
+    fn fill(&mut self, order: Order, price: f64) {
+        match (order, self.position.take()) {
+            (Order::Enter, None) => {
+                let equity = self.cash;
+                let quantity = equity / (price * (1.0 + self.commission));
+                let value = quantity * price;
+                self.cash -= Self::sign(self.direction) * value + value * self.commission;
+                self.position = Some(Position {
+                    direction: self.direction,
+                    entry_index: self.index,
+                    entry_price: price,
+                    quantity,
+                    equity,
+                });
+            }
+            (Order::Exit, Some(position)) => {
+                let value = position.quantity * price;
+                self.cash += Self::sign(position.direction) * value - value * self.commission;
+                let pnl = self.cash - position.equity;
+                self.trades.push(Trade {
+                    direction: position.direction,
+                    entry_index: position.entry_index,
+                    exit_index: self.index,
+                    entry_price: position.entry_price,
+                    exit_price: price,
+                    quantity: position.quantity,
+                    pnl,
+                    return_: pnl / position.equity,
+                });
+            }
+            (_, position) => self.position = position,
+        }
+    }
+}
+
+impl<T: Open + Close> Next<(&T, bool, bool)> for Backtest {
+    type Output = f64;
+
+    fn next(&mut self, (bar, entry, exit): (&T, bool, bool)) -> Self::Output {
+        if let Some(order) = self.order.take() {
+            self.fill(order, bar.open());
+        }
+
+        let equity = match &self.position {
+            Some(position) => {
+                self.cash + Self::sign(position.direction) * position.quantity * bar.close()
+            }
+            None => self.cash,
+        };
+        self.equity_curve.push(equity);
+        self.max_drawdown = self.drawdown.next(equity).max_drawdown;
+
+        self.order = match self.position {
+            Some(_) if exit => Some(Order::Exit),
+            None if entry => Some(Order::Enter),
+            _ => None,
+        };
+        self.index += 1;
+        equity
+    }
+}
+
+impl Reset for Backtest {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.cash = self.initial_equity;
+        self.position = None;
+        self.order = None;
+        self.equity_curve.clear();
+        self.trades.clear();
+        self.drawdown.reset();
+        self.max_drawdown = 0.0;
+    }
+}
+
+impl Default for Backtest {
+    fn default() -> Self {
+        Self::new(10_000.0).unwrap()
+    }
+}
+
+impl fmt::Display for Backtest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BACKTEST({}", self.initial_equity)?;
+        if self.commission != 0.0 || self.direction != Direction::Long {
+            write!(f, ", {}", self.commission)?;
+        }
+        if self.direction == Direction::Short {
+            write!(f, ", SHORT")?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn run(backtest: &mut Backtest, bars: &[(f64, f64, bool, bool)]) -> Vec<f64> {
+        bars.iter()
+            .map(|&(open, close, entry, exit)| {
+                let bar = Bar::new().open(open).close(close);
+                round(backtest.next((&bar, entry, exit)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Backtest::new(0.0).is_err());
+        assert!(Backtest::new(f64::INFINITY).is_err());
+        assert!(Backtest::with_commission(100.0, -0.1).is_err());
+        assert!(Backtest::with_commission(100.0, 1.0).is_err());
+        assert!(Backtest::with_commission(100.0, 0.001).is_ok());
+    }
+
+    #[test]
+    fn test_long() {
+        let mut backtest = Backtest::new(100.0).unwrap();
+        let equity = run(
+            &mut backtest,
+            &[
+                (10.0, 10.0, true, false),
+                (10.0, 12.0, false, false),
+                (12.0, 15.0, false, true),
+                (16.0, 14.0, false, false),
+            ],
+        );
+        assert_eq!(equity, vec![100.0, 120.0, 150.0, 160.0]);
+        assert!(!backtest.in_position());
+
+        let trade = &backtest.trades()[0];
+        assert_eq!((trade.entry_index, trade.exit_index), (1, 3));
+        assert_eq!((trade.entry_price, trade.exit_price), (10.0, 16.0));
+        assert_eq!(trade.quantity, 10.0);
+        assert_eq!(trade.pnl, 60.0);
+        assert_eq!(trade.return_, 0.6);
+        assert_eq!(backtest.win_rate(), 1.0);
+        assert_eq!(backtest.profit_factor(), f64::INFINITY);
+        assert_eq!(backtest.max_drawdown(), 0.0);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut backtest = Backtest::new(100.0).unwrap();
+        run(
+            &mut backtest,
+            &[
+                (10.0, 10.0, true, false),
+                (10.0, 5.0, false, true),
+                (5.0, 5.0, true, false),
+                (5.0, 6.0, false, true),
+                (6.0, 6.0, false, false),
+            ],
+        );
+        // lost 50 then gained 10
+        let pnls: Vec<f64> = backtest.trades().iter().map(|trade| trade.pnl).collect();
+        assert_eq!(pnls, vec![-50.0, 10.0]);
+        assert_eq!(backtest.win_rate(), 0.5);
+        assert_eq!(backtest.profit_factor(), 0.2);
+        assert_eq!(backtest.max_drawdown(), 0.5);
+        assert_eq!(backtest.equity(), 60.0);
+    }
+
+    #[test]
+    fn test_short() {
+        let mut backtest = Backtest::with_direction(100.0, 0.0, Direction::Short).unwrap();
+        let equity = run(
+            &mut backtest,
+            &[
+                (10.0, 10.0, true, false),
+                (10.0, 8.0, false, true),
+                (9.0, 9.0, false, false),
+            ],
+        );
+        assert_eq!(equity, vec![100.0, 120.0, 110.0]);
+        assert_eq!(backtest.trades()[0].pnl, 10.0);
+    }
+
+    #[test]
+    fn test_commission() {
+        let mut backtest = Backtest::with_commission(101.0, 0.01).unwrap();
+        run(
+            &mut backtest,
+            &[
+                (10.0, 10.0, true, false),
+                (10.0, 10.0, false, true),
+                (10.0, 10.0, false, false),
+            ],
+        );
+        // 10 units bought for 100 plus 1 of commission, sold for 100 less 1
+        let trade = &backtest.trades()[0];
+        assert_eq!(trade.quantity, 10.0);
+        assert_eq!(trade.pnl, -2.0);
+        assert_eq!(backtest.equity(), 99.0);
+    }
+
+    #[test]
+    fn test_ignored_signals() {
+        let mut backtest = Backtest::new(100.0).unwrap();
+        run(
+            &mut backtest,
+            &[
+                (10.0, 10.0, false, true),
+                (10.0, 10.0, true, false),
+                (10.0, 10.0, true, false),
+                (10.0, 10.0, false, false),
+            ],
+        );
+        assert!(backtest.in_position());
+        assert!(backtest.trades().is_empty());
+        assert_eq!(backtest.win_rate(), 0.0);
+        assert_eq!(backtest.profit_factor(), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut backtest = Backtest::new(100.0).unwrap();
+        run(
+            &mut backtest,
+            &[(10.0, 10.0, true, false), (10.0, 5.0, false, true)],
+        );
+
+        backtest.reset();
+        assert!(!backtest.in_position());
+        assert!(backtest.equity_curve().is_empty());
+        assert_eq!(backtest.equity(), 100.0);
+        assert_eq!(backtest.max_drawdown(), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Backtest::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", Backtest::new(1000.0).unwrap()),
+            "BACKTEST(1000)"
+        );
+        let backtest = Backtest::with_direction(1000.0, 0.001, Direction::Short).unwrap();
+        assert_eq!(format!("{}", backtest), "BACKTEST(1000, 0.001, SHORT)");
+    }
+}
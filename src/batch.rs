@@ -0,0 +1,211 @@
+//! Utilities for working with indicator outputs computed over whole series.
+
+use alloc::{vec, vec::Vec};
+
+use crate::errors::{Result, TaError};
+#[cfg(feature = "rayon")]
+use crate::NextBatch;
+
+/// Aligns outputs of several indicators to their common valid range.
+///
+/// Every output vector is expected to be computed over the same input series and to be aligned
+/// to its end, i.e. the last element of every vector corresponds to the last input. The first
+/// `lookbacks[i]` elements of `outputs[i]` are treated as warm-up values and discarded. Vectors
+/// which have already been trimmed can simply be passed with a lookback of `0`.
+///
+/// The returned vectors all have the same length and element `j` of every vector corresponds to
+/// the same input.
+///
+/// Returns an error if the number of lookbacks does not match the number of outputs.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::batch::align;
+/// use ta_panther::indicators::{SimpleMovingAverage, RateOfChange};
+/// use ta_panther::Next;
+///
+/// let input = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// let mut roc = RateOfChange::new(1).unwrap();
+/// let sma: Vec<f64> = input.iter().map(|&x| sma.next(x)).collect();
+/// let roc: Vec<f64> = input.iter().map(|&x| roc.next(x)).collect();
+///
+/// let aligned = align(&[&sma, &roc], &[2, 1]).unwrap();
+/// assert_eq!(aligned[0], vec![2.0, 3.0, 4.0]);
+/// assert_eq!(aligned[1].len(), 3);
+/// assert_eq!(aligned[1][0], 50.0);
+/// ```
+pub fn align<T: Clone>(outputs: &[&[T]], lookbacks: &[usize]) -> Result<Vec<Vec<T>>> {
+    if outputs.len() != lookbacks.len() {
+        return Err(TaError::InvalidParameter);
+    }
+
+    let common = outputs
+        .iter()
+        .zip(lookbacks)
+        .map(|(output, &lookback)| output.len().saturating_sub(lookback))
+        .min()
+        .unwrap_or(0);
+
+    Ok(outputs
+        .iter()
+        .map(|output| output[output.len() - common..].to_vec())
+        .collect())
+}
+
+/// Aligns outputs of several indicators, padding warm-up values with `None`.
+///
+/// Follows the same conventions as [align], but instead of trimming the outputs to the common
+/// valid range, all vectors are extended to the length of the longest one. Warm-up values and
+/// positions before the beginning of a shorter vector are set to `None`.
+///
+/// Returns an error if the number of lookbacks does not match the number of outputs.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::batch::align_padded;
+///
+/// let a = [1.0, 2.0, 3.0, 4.0];
+/// let b = [20.0, 30.0];
+///
+/// let aligned = align_padded(&[&a[..], &b[..]], &[1, 0]).unwrap();
+/// assert_eq!(aligned[0], vec![None, Some(2.0), Some(3.0), Some(4.0)]);
+/// assert_eq!(aligned[1], vec![None, None, Some(20.0), Some(30.0)]);
+/// ```
+pub fn align_padded<T: Clone>(
+    outputs: &[&[T]],
+    lookbacks: &[usize],
+) -> Result<Vec<Vec<Option<T>>>> {
+    if outputs.len() != lookbacks.len() {
+        return Err(TaError::InvalidParameter);
+    }
+
+    let len = outputs.iter().map(|output| output.len()).max().unwrap_or(0);
+
+    Ok(outputs
+        .iter()
+        .zip(lookbacks)
+        .map(|(output, &lookback)| {
+            let offset = len - output.len();
+            let mut aligned = vec![None; offset];
+            aligned.extend(output.iter().enumerate().map(|(i, value)| {
+                if i < lookback {
+                    None
+                } else {
+                    Some(value.clone())
+                }
+            }));
+            aligned
+        })
+        .collect())
+}
+
+/// Computes an indicator over many independent series in parallel.
+///
+/// Every series is computed by a fresh clone of `indicator`, with
+/// [next_batch](NextBatch::next_batch), and the outputs are returned in the order of the
+/// series. The clones run on the rayon thread pool, so scanning thousands of tickers uses all
+/// the cores. Requires the `rayon` feature.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::batch::compute_parallel;
+/// use ta_panther::indicators::SimpleMovingAverage;
+///
+/// let a = [1.0, 2.0, 3.0];
+/// let b = [10.0, 20.0];
+///
+/// let sma = SimpleMovingAverage::new(2).unwrap();
+/// let outputs = compute_parallel(&sma, &[&a[..], &b[..]]);
+/// assert_eq!(outputs, vec![vec![1.0, 1.5, 2.5], vec![10.0, 15.0]]);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn compute_parallel<N>(indicator: &N, series: &[&[f64]]) -> Vec<Vec<N::Output>>
+where
+    N: NextBatch + Clone + Sync,
+    N::Output: Send,
+{
+    use rayon::prelude::*;
+
+    series
+        .par_iter()
+        .map(|input| indicator.clone().next_batch(input))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [10.0, 20.0, 30.0, 40.0, 50.0];
+        let c = [300.0, 400.0, 500.0];
+
+        let aligned = align(&[&a[..], &b[..], &c[..]], &[1, 3, 0]).unwrap();
+        assert_eq!(aligned[0], vec![4.0, 5.0]);
+        assert_eq!(aligned[1], vec![40.0, 50.0]);
+        assert_eq!(aligned[2], vec![400.0, 500.0]);
+    }
+
+    #[test]
+    fn test_align_lookback_exceeds_len() {
+        let a = [1.0, 2.0];
+        let b = [1.0, 2.0, 3.0];
+
+        let aligned = align(&[&a[..], &b[..]], &[5, 0]).unwrap();
+        assert!(aligned.iter().all(|output| output.is_empty()));
+    }
+
+    #[test]
+    fn test_align_empty() {
+        let aligned: Vec<Vec<f64>> = align(&[], &[]).unwrap();
+        assert!(aligned.is_empty());
+    }
+
+    #[test]
+    fn test_align_padded() {
+        let a = [1.0, 2.0, 3.0];
+        let b = [10.0, 20.0, 30.0];
+
+        let aligned = align_padded(&[&a[..], &b[..]], &[0, 2]).unwrap();
+        assert_eq!(aligned[0], vec![Some(1.0), Some(2.0), Some(3.0)]);
+        assert_eq!(aligned[1], vec![None, None, Some(30.0)]);
+    }
+
+    #[test]
+    fn test_invalid_lookbacks() {
+        let a = [1.0, 2.0];
+        assert_eq!(align(&[&a[..]], &[]), Err(TaError::InvalidParameter));
+        assert_eq!(
+            align_padded(&[&a[..]], &[1, 2]),
+            Err(TaError::InvalidParameter)
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_compute_parallel() {
+        use crate::indicators::ExponentialMovingAverage;
+        use crate::Next;
+
+        let series: Vec<Vec<f64>> = (0..20)
+            .map(|i| (0..50).map(|j| ((i * j) as f64).sin()).collect())
+            .collect();
+        let slices: Vec<&[f64]> = series.iter().map(Vec::as_slice).collect();
+        let ema = ExponentialMovingAverage::new(5).unwrap();
+
+        let outputs = compute_parallel(&ema, &slices);
+        assert_eq!(outputs.len(), 20);
+        for (input, output) in series.iter().zip(&outputs) {
+            let mut expected = ema.clone();
+            let expected: Vec<f64> = input.iter().map(|&x| expected.next(x)).collect();
+            assert_eq!(output, &expected);
+        }
+        assert!(compute_parallel(&ema, &[]).is_empty());
+    }
+}
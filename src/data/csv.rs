@@ -0,0 +1,341 @@
+//! Reader of OHLCV bars from CSV files.
+//!
+//! Available with the `csv` feature.
+//!
+//! A [CsvReader] maps the columns of a CSV file with a header row to the fields of
+//! [DataItem]s and yields the bars one by one. By default it reads the columns `date`, `open`,
+//! `high`, `low`, `close` and `volume`, as exported by most data vendors, matching the names
+//! case-insensitively, and parses the dates with [parse_iso8601](crate::data::parse_iso8601). Every column can be renamed
+//! and the dates can be parsed with a custom function.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::data::csv::CsvReader;
+//! use ta_panther::data::DateFormat;
+//! use ta_panther::indicators::ExponentialMovingAverage;
+//! use ta_panther::{Next, Timestamp};
+//!
+//! let data = "\
+//! time;o;h;l;c;v
+//! 1600000000;10.0;12.0;9.5;11.0;1500
+//! 1600000060;11.0;11.5;10.0;10.5;900
+//! ";
+//!
+//! let reader = CsvReader::new()
+//!     .delimiter(b';')
+//!     .timestamp_column("time")
+//!     .date_format(DateFormat::UnixSeconds)
+//!     .open_column("o")
+//!     .high_column("h")
+//!     .low_column("l")
+//!     .close_column("c")
+//!     .volume_column("v");
+//!
+//! let mut ema = ExponentialMovingAverage::new(3).unwrap();
+//! for bar in reader.read(data.as_bytes()).unwrap() {
+//!     let bar = bar.unwrap();
+//!     println!("{:?}: {:.2}", bar.timestamp(), ema.next(&bar));
+//! }
+//! ```
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use csv::{StringRecord, StringRecordsIntoIter};
+
+use crate::data::{DataError, DateFormat, Result};
+use crate::DataItem;
+
+/// Configuration of a CSV reader, see the [module](self) documentation.
+#[derive(Debug, Clone)]
+pub struct CsvReader {
+    delimiter: u8,
+    timestamp: Option<String>,
+    date_format: DateFormat,
+    open: String,
+    high: String,
+    low: String,
+    close: String,
+    volume: String,
+}
+
+impl CsvReader {
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            timestamp: Some("date".to_string()),
+            date_format: DateFormat::Iso8601,
+            open: "open".to_string(),
+            high: "high".to_string(),
+            low: "low".to_string(),
+            close: "close".to_string(),
+            volume: "volume".to_string(),
+        }
+    }
+
+    /// Sets the field delimiter, `,` by default.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the name of the timestamp column, `date` by default.
+    pub fn timestamp_column(mut self, name: &str) -> Self {
+        self.timestamp = Some(name.to_string());
+        self
+    }
+
+    /// Reads the bars without timestamps.
+    pub fn without_timestamp(mut self) -> Self {
+        self.timestamp = None;
+        self
+    }
+
+    /// Sets the format of the timestamp column, [DateFormat::Iso8601] by default.
+    pub fn date_format(mut self, date_format: DateFormat) -> Self {
+        self.date_format = date_format;
+        self
+    }
+
+    /// Sets the name of the open price column, `open` by default.
+    pub fn open_column(mut self, name: &str) -> Self {
+        self.open = name.to_string();
+        self
+    }
+
+    /// Sets the name of the high price column, `high` by default.
+    pub fn high_column(mut self, name: &str) -> Self {
+        self.high = name.to_string();
+        self
+    }
+
+    /// Sets the name of the low price column, `low` by default.
+    pub fn low_column(mut self, name: &str) -> Self {
+        self.low = name.to_string();
+        self
+    }
+
+    /// Sets the name of the close price column, `close` by default.
+    pub fn close_column(mut self, name: &str) -> Self {
+        self.close = name.to_string();
+        self
+    }
+
+    /// Sets the name of the volume column, `volume` by default.
+    pub fn volume_column(mut self, name: &str) -> Self {
+        self.volume = name.to_string();
+        self
+    }
+
+    /// Opens a CSV file and reads its header.
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<Bars<File>> {
+        self.read(File::open(path)?)
+    }
+
+    /// Reads the header of CSV data.
+    ///
+    /// Returns an error if a configured column is missing.
+    pub fn read<R: Read>(&self, reader: R) -> Result<Bars<R>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let header = reader.headers().map_err(error)?;
+        let index = |name: &str| {
+            header
+                .iter()
+                .position(|column| column.eq_ignore_ascii_case(name))
+                .ok_or_else(|| DataError::MissingColumn(name.to_string()))
+        };
+
+        Ok(Bars {
+            timestamp: self.timestamp.as_deref().map(index).transpose()?,
+            date_format: self.date_format,
+            columns: [
+                index(&self.open)?,
+                index(&self.high)?,
+                index(&self.low)?,
+                index(&self.close)?,
+                index(&self.volume)?,
+            ],
+            records: reader.into_records(),
+        })
+    }
+}
+
+impl Default for CsvReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn error(err: csv::Error) -> DataError {
+    let line = err.position().map_or(0, |position| position.line());
+    let message = err.to_string();
+    match err.into_kind() {
+        csv::ErrorKind::Io(err) => DataError::Io(err),
+        _ => DataError::Parse { line, message },
+    }
+}
+
+/// Iterator over the bars of CSV data, created by a [CsvReader].
+pub struct Bars<R> {
+    timestamp: Option<usize>,
+    date_format: DateFormat,
+    columns: [usize; 5],
+    records: StringRecordsIntoIter<R>,
+}
+
+impl<R: Read> Bars<R> {
+    fn bar(&self, record: &StringRecord) -> Result<DataItem> {
+        let line = record.position().map_or(0, |position| position.line());
+        let field = |index: usize| record.get(index).unwrap_or("");
+        let [open, high, low, close, volume] = self.columns.map(|index| {
+            field(index).parse::<f64>().map_err(|_| DataError::Parse {
+                line,
+                message: format!("invalid number {:?}", field(index)),
+            })
+        });
+
+        let mut builder = DataItem::builder()
+            .open(open?)
+            .high(high?)
+            .low(low?)
+            .close(close?)
+            .volume(volume?);
+        if let Some(index) = self.timestamp {
+            let timestamp =
+                self.date_format
+                    .parse(field(index))
+                    .ok_or_else(|| DataError::Parse {
+                        line,
+                        message: format!("invalid date {:?}", field(index)),
+                    })?;
+            builder = builder.timestamp(timestamp);
+        }
+        builder
+            .build()
+            .map_err(|error| DataError::Invalid { line, error })
+    }
+}
+
+impl<R: Read> Iterator for Bars<R> {
+    type Item = Result<DataItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.records.next()?;
+        Some(record.map_err(error).and_then(|record| self.bar(&record)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::TaError;
+    use crate::{Close, Timestamp, Volume};
+
+    const DATA: &str = "\
+Date,Open,High,Low,Close,Volume
+2017-01-03,757.92,758.76,747.70,753.67,3521100
+2017-01-04,758.39,759.68,754.20,757.18,2510500
+";
+
+    #[test]
+    fn test_read() {
+        let bars: Vec<DataItem> = CsvReader::new()
+            .read(DATA.as_bytes())
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].close(), 753.67);
+        assert_eq!(bars[1].volume(), 2510500.0);
+        assert_eq!(bars[1].timestamp(), Some(1_483_488_000_000));
+    }
+
+    #[test]
+    fn test_column_mapping() {
+        let data = "ts,c,h,l,o,v\n1500,2.0,3.0,1.0,2.5,10\n";
+        let reader = CsvReader::new()
+            .timestamp_column("ts")
+            .date_format(DateFormat::UnixMillis)
+            .open_column("o")
+            .high_column("h")
+            .low_column("l")
+            .close_column("c")
+            .volume_column("v");
+        let bar = reader
+            .read(data.as_bytes())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(bar.close(), 2.0);
+        assert_eq!(bar.timestamp(), Some(1500));
+
+        let bar = reader
+            .clone()
+            .date_format(DateFormat::Custom(|date| {
+                date.parse::<i64>().ok().map(|t| t * 2)
+            }))
+            .read(data.as_bytes())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(bar.timestamp(), Some(3000));
+
+        let bar = reader
+            .without_timestamp()
+            .read(data.as_bytes())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(bar.timestamp(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let missing = CsvReader::new().read("date,open,high,low,close\n".as_bytes());
+        assert!(matches!(missing, Err(DataError::MissingColumn(column)) if column == "volume"));
+
+        let data = "\
+date,open,high,low,close,volume
+2017-01-03,1,2,1,x,100
+2017-13-03,1,2,1,1,100
+2017-01-05,1,2,3,1,100
+2017-01-06,1,2,1,1,100
+";
+        let results: Vec<Result<DataItem>> =
+            CsvReader::new().read(data.as_bytes()).unwrap().collect();
+        assert!(matches!(results[0], Err(DataError::Parse { line: 2, .. })));
+        assert!(matches!(results[1], Err(DataError::Parse { line: 3, .. })));
+        assert!(matches!(
+            results[2],
+            Err(DataError::Invalid {
+                line: 4,
+                error: TaError::DataItemInvalid
+            })
+        ));
+        assert!(results[3].is_ok());
+    }
+
+    #[test]
+    fn test_from_path() {
+        let bars = CsvReader::new()
+            .open("./examples/data/AMZN.csv")
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert!(!bars.is_empty());
+        assert_eq!(bars[0].timestamp(), Some(1_483_401_600_000));
+
+        assert!(matches!(
+            CsvReader::new().open("./examples/data/missing.csv"),
+            Err(DataError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+//! Reader of bars and ticks from newline-delimited JSON.
+//!
+//! Available with the `json` feature.
+//!
+//! Recorded exchange feeds are usually stored as one JSON object per line, with field names
+//! which differ from one exchange to the other. A [JsonReader] tolerates these differences: it
+//! looks up every field of a [DataItem] under a list of common names, first exactly and then
+//! case-insensitively, and accepts numbers as well as numeric strings.
+//!
+//! | Field     | Names                                                          |
+//! |-----------|----------------------------------------------------------------|
+//! | timestamp | `timestamp`, `time`, `ts`, `t`, `date`                         |
+//! | open      | `open`, `o`                                                    |
+//! | high      | `high`, `h`                                                    |
+//! | low       | `low`, `l`                                                     |
+//! | close     | `close`, `c`, `price`, `p`, `last`                             |
+//! | volume    | `volume`, `v`, `vol`, `quantity`, `qty`, `q`, `size`, `amount` |
+//!
+//! Only the close price is required. Ticks, which have a single price, become bars whose open,
+//! high and low prices are the close price, and a missing volume is 0. Objects nested in an
+//! envelope, e.g. `{"stream": "...", "data": {...}}`, are found with a
+//! [JSON pointer](JsonReader::pointer).
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::data::json::JsonReader;
+//! use ta_panther::indicators::SimpleMovingAverage;
+//! use ta_panther::{Close, Next};
+//!
+//! let feed = r#"
+//! {"time": 1600000000000, "o": 10, "h": 12, "l": 9.5, "c": 11, "v": 1500}
+//! {"time": 1600000060000, "price": "10.5", "qty": "0.3"}
+//! "#;
+//!
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! for bar in JsonReader::new().read(feed.as_bytes()) {
+//!     let bar = bar.unwrap();
+//!     sma.next(bar.close());
+//! }
+//! assert_eq!(sma.next(11.5), 11.0);
+//! ```
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::data::{DataError, DateFormat, Result};
+use crate::DataItem;
+
+const TIMESTAMP: &[&str] = &["timestamp", "time", "ts", "t", "date"];
+const OPEN: &[&str] = &["open", "o"];
+const HIGH: &[&str] = &["high", "h"];
+const LOW: &[&str] = &["low", "l"];
+const CLOSE: &[&str] = &["close", "c", "price", "p", "last"];
+const VOLUME: &[&str] = &[
+    "volume", "v", "vol", "quantity", "qty", "q", "size", "amount",
+];
+
+/// Names under which a field is looked up.
+#[derive(Debug, Clone)]
+struct Names {
+    custom: Vec<String>,
+    defaults: &'static [&'static str],
+}
+
+impl Names {
+    fn new(defaults: &'static [&'static str]) -> Self {
+        Self {
+            custom: Vec::new(),
+            defaults,
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        self.custom
+            .iter()
+            .map(String::as_str)
+            .chain(self.defaults.iter().copied())
+    }
+
+    fn find<'a>(&self, object: &'a Map<String, Value>) -> Option<&'a Value> {
+        self.iter().find_map(|name| object.get(name)).or_else(|| {
+            self.iter().find_map(|name| {
+                object
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value)
+            })
+        })
+    }
+}
+
+/// Configuration of a JSON reader, see the [module](self) documentation.
+#[derive(Debug, Clone)]
+pub struct JsonReader {
+    pointer: Option<String>,
+    date_format: DateFormat,
+    timestamp: Names,
+    open: Names,
+    high: Names,
+    low: Names,
+    close: Names,
+    volume: Names,
+}
+
+impl JsonReader {
+    pub fn new() -> Self {
+        Self {
+            pointer: None,
+            date_format: DateFormat::UnixMillis,
+            timestamp: Names::new(TIMESTAMP),
+            open: Names::new(OPEN),
+            high: Names::new(HIGH),
+            low: Names::new(LOW),
+            close: Names::new(CLOSE),
+            volume: Names::new(VOLUME),
+        }
+    }
+
+    /// Reads the bars from the object at a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901)
+    /// of every line, e.g. `/data` or `/data/k`.
+    pub fn pointer(mut self, pointer: &str) -> Self {
+        self.pointer = Some(pointer.to_string());
+        self
+    }
+
+    /// Sets the format of the timestamps, [DateFormat::UnixMillis] by default.
+    ///
+    /// Timestamps can be numbers or strings.
+    pub fn date_format(mut self, date_format: DateFormat) -> Self {
+        self.date_format = date_format;
+        self
+    }
+
+    /// Adds a name of the timestamp field, looked up before the default names.
+    pub fn timestamp_field(mut self, name: &str) -> Self {
+        self.timestamp.custom.push(name.to_string());
+        self
+    }
+
+    /// Adds a name of the open price field, looked up before the default names.
+    pub fn open_field(mut self, name: &str) -> Self {
+        self.open.custom.push(name.to_string());
+        self
+    }
+
+    /// Adds a name of the high price field, looked up before the default names.
+    pub fn high_field(mut self, name: &str) -> Self {
+        self.high.custom.push(name.to_string());
+        self
+    }
+
+    /// Adds a name of the low price field, looked up before the default names.
+    pub fn low_field(mut self, name: &str) -> Self {
+        self.low.custom.push(name.to_string());
+        self
+    }
+
+    /// Adds a name of the close price field, looked up before the default names.
+    pub fn close_field(mut self, name: &str) -> Self {
+        self.close.custom.push(name.to_string());
+        self
+    }
+
+    /// Adds a name of the volume field, looked up before the default names.
+    pub fn volume_field(mut self, name: &str) -> Self {
+        self.volume.custom.push(name.to_string());
+        self
+    }
+
+    /// Opens a file of newline-delimited JSON.
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<Bars<BufReader<File>>> {
+        Ok(self.read(BufReader::new(File::open(path)?)))
+    }
+
+    /// Reads newline-delimited JSON, skipping empty lines.
+    pub fn read<R: BufRead>(&self, reader: R) -> Bars<R> {
+        Bars {
+            reader: self.clone(),
+            lines: reader.lines(),
+            line: 0,
+        }
+    }
+
+    /// Parses a single JSON object, e.g. a message received from a websocket.
+    pub fn parse(&self, json: &str) -> Result<DataItem> {
+        self.bar(json, 1)
+    }
+
+    fn bar(&self, json: &str, line: u64) -> Result<DataItem> {
+        let parse_error = |message: String| DataError::Parse { line, message };
+        let value: Value =
+            serde_json::from_str(json).map_err(|err| parse_error(err.to_string()))?;
+        let value = match &self.pointer {
+            Some(pointer) => value
+                .pointer(pointer)
+                .ok_or_else(|| parse_error(format!("missing {}", pointer)))?,
+            None => &value,
+        };
+        let object = value
+            .as_object()
+            .ok_or_else(|| parse_error("expected an object".to_string()))?;
+
+        let number = |names: &Names, field: &str| -> Result<Option<f64>> {
+            names
+                .find(object)
+                .map(|value| {
+                    let number = match value {
+                        Value::Number(number) => number.as_f64(),
+                        Value::String(text) => text.trim().parse().ok(),
+                        _ => None,
+                    };
+                    number.ok_or_else(|| parse_error(format!("invalid {} {}", field, value)))
+                })
+                .transpose()
+        };
+        let close = number(&self.close, "close")?
+            .ok_or_else(|| parse_error("missing close".to_string()))?;
+        let mut builder = DataItem::builder()
+            .open(number(&self.open, "open")?.unwrap_or(close))
+            .high(number(&self.high, "high")?.unwrap_or(close))
+            .low(number(&self.low, "low")?.unwrap_or(close))
+            .close(close)
+            .volume(number(&self.volume, "volume")?.unwrap_or(0.0));
+
+        if let Some(value) = self.timestamp.find(object) {
+            let timestamp = match value {
+                Value::Number(number) => self.date_format.parse(&number.to_string()),
+                Value::String(text) => self.date_format.parse(text),
+                _ => None,
+            };
+            let timestamp =
+                timestamp.ok_or_else(|| parse_error(format!("invalid timestamp {}", value)))?;
+            builder = builder.timestamp(timestamp);
+        }
+
+        builder
+            .build()
+            .map_err(|error| DataError::Invalid { line, error })
+    }
+}
+
+impl Default for JsonReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the bars of newline-delimited JSON, created by a [JsonReader].
+pub struct Bars<R> {
+    reader: JsonReader,
+    lines: Lines<R>,
+    line: u64,
+}
+
+impl<R: BufRead> Iterator for Bars<R> {
+    type Item = Result<DataItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+            match self.lines.next()? {
+                Ok(text) if text.trim().is_empty() => continue,
+                Ok(text) => return Some(self.reader.bar(&text, self.line)),
+                Err(err) => return Some(Err(DataError::Io(err))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::TaError;
+    use crate::{Close, High, Low, Open, Timestamp, Volume};
+
+    #[test]
+    fn test_bar() {
+        let bar = JsonReader::new()
+            .parse(r#"{"Timestamp": "1600000000000", "Open": 2, "HIGH": 4, "low": 1, "close": "3", "Volume": 10}"#)
+            .unwrap();
+        assert_eq!(bar.timestamp(), Some(1_600_000_000_000));
+        assert_eq!(
+            (bar.open(), bar.high(), bar.low(), bar.close(), bar.volume()),
+            (2.0, 4.0, 1.0, 3.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn test_tick() {
+        let tick = JsonReader::new().parse(r#"{"p": "101.5"}"#).unwrap();
+        assert_eq!(
+            (
+                tick.open(),
+                tick.high(),
+                tick.low(),
+                tick.close(),
+                tick.volume()
+            ),
+            (101.5, 101.5, 101.5, 101.5, 0.0)
+        );
+        assert_eq!(tick.timestamp(), None);
+    }
+
+    #[test]
+    fn test_custom_fields() {
+        let reader = JsonReader::new()
+            .pointer("/data")
+            .timestamp_field("T")
+            .close_field("mark")
+            .date_format(DateFormat::UnixSeconds);
+        let tick = reader
+            .parse(r#"{"stream": "x", "data": {"t": 7, "T": 1600000000, "price": 1, "mark": 2}}"#)
+            .unwrap();
+        assert_eq!(tick.timestamp(), Some(1_600_000_000_000));
+        assert_eq!(tick.close(), 2.0);
+
+        let date = JsonReader::new()
+            .date_format(DateFormat::Iso8601)
+            .parse(r#"{"date": "1970-01-02", "c": 1}"#)
+            .unwrap();
+        assert_eq!(date.timestamp(), Some(86_400_000));
+    }
+
+    #[test]
+    fn test_read() {
+        let feed = "{\"c\": 1}\n\n{\"c\": 2, \"v\": -1}\n[1]\n{\"o\": 1}\n{\"c\": true}\nnope\n{\"c\": 3}\n";
+        let results: Vec<Result<DataItem>> = JsonReader::new().read(feed.as_bytes()).collect();
+        assert_eq!(results.len(), 7);
+        assert_eq!(results[0].as_ref().unwrap().close(), 1.0);
+        assert!(matches!(
+            results[1],
+            Err(DataError::Invalid {
+                line: 3,
+                error: TaError::DataItemInvalid
+            })
+        ));
+        for (result, line) in results[2..6].iter().zip(4..) {
+            assert!(matches!(result, Err(DataError::Parse { line: l, .. }) if *l == line));
+        }
+        assert_eq!(results[6].as_ref().unwrap().close(), 3.0);
+    }
+}
@@ -0,0 +1,208 @@
+//! Readers of recorded market data.
+//!
+//! The readers turn recorded bars into [DataItem](crate::DataItem)s, so indicators and
+//! strategies can be run against real data:
+//!
+//! * [csv] reads OHLCV bars from CSV files, available with the `csv` feature.
+//! * [json] reads bars and ticks from newline-delimited JSON, available with the `json` feature.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::errors::TaError;
+
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "json")]
+pub mod json;
+
+/// Error returned by the readers.
+#[derive(Debug)]
+pub enum DataError {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// A column required by the reader is missing.
+    MissingColumn(String),
+    /// A record could not be parsed.
+    Parse { line: u64, message: String },
+    /// A record is not a valid bar, e.g. its high is below its low.
+    Invalid { line: u64, error: TaError },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataError::Io(err) => write!(f, "I/O error: {}", err),
+            DataError::MissingColumn(column) => write!(f, "missing column {}", column),
+            DataError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            DataError::Invalid { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl Error for DataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DataError::Io(err) => Some(err),
+            DataError::Invalid { error, .. } => Some(error),
+            DataError::MissingColumn(_) | DataError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for DataError {
+    fn from(err: io::Error) -> Self {
+        DataError::Io(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, DataError>;
+
+/// Format of timestamps.
+#[derive(Debug, Clone, Copy)]
+pub enum DateFormat {
+    /// Dates and times parsed with [parse_iso8601].
+    Iso8601,
+    /// Seconds since the Unix epoch.
+    UnixSeconds,
+    /// Milliseconds since the Unix epoch.
+    UnixMillis,
+    /// Custom function returning the milliseconds since the Unix epoch, or `None` if the date
+    /// is invalid.
+    Custom(fn(&str) -> Option<i64>),
+}
+
+impl DateFormat {
+    /// Parses a timestamp, returning the milliseconds since the Unix epoch.
+    pub fn parse(&self, date: &str) -> Option<i64> {
+        match self {
+            DateFormat::Iso8601 => parse_iso8601(date),
+            DateFormat::UnixSeconds => date.trim().parse::<i64>().ok()?.checked_mul(1000),
+            DateFormat::UnixMillis => date.trim().parse().ok(),
+            DateFormat::Custom(parse) => parse(date),
+        }
+    }
+}
+
+/// Parses a date in one of the formats `YYYY-MM-DD`, `YYYY-MM-DD HH:MM`,
+/// `YYYY-MM-DDTHH:MM:SS` or `YYYY-MM-DDTHH:MM:SS.fff`, optionally followed by `Z`, as UTC.
+///
+/// Returns the milliseconds since the Unix epoch, or `None` if the date is invalid.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::data::parse_iso8601;
+///
+/// assert_eq!(parse_iso8601("1970-01-02"), Some(86_400_000));
+/// assert_eq!(parse_iso8601("2020-09-13T12:26:40.5Z"), Some(1_600_000_000_500));
+/// assert_eq!(parse_iso8601("2020-02-30"), None);
+/// ```
+pub fn parse_iso8601(date: &str) -> Option<i64> {
+    let date = date.trim();
+    let date = date.strip_suffix('Z').unwrap_or(date);
+    let (day, time) = match date.split_once(['T', ' ']) {
+        Some((day, time)) => (day, Some(time)),
+        None => (date, None),
+    };
+
+    let mut parts = day.splitn(3, '-');
+    let year: i64 = number(parts.next()?, 4)?;
+    let month: i64 = number(parts.next()?, 2)?;
+    let day: i64 = number(parts.next()?, 2)?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let mut millis = 0;
+    if let Some(time) = time {
+        let (time, fraction) = match time.split_once('.') {
+            Some((time, fraction)) => (time, Some(fraction)),
+            None => (time, None),
+        };
+        let mut parts = time.splitn(3, ':');
+        let hour: i64 = number(parts.next()?, 2)?;
+        let minute: i64 = number(parts.next()?, 2)?;
+        let second: i64 = parts.next().map_or(Some(0), |second| number(second, 2))?;
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+        millis = ((hour * 60 + minute) * 60 + second) * 1000;
+        if let Some(fraction) = fraction {
+            if fraction.is_empty() || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            let digits = format!("{:0<3}", &fraction[..fraction.len().min(3)]);
+            millis += digits.parse::<i64>().ok()?;
+        }
+    }
+
+    Some(days_from_civil(year, month, day) * 86_400_000 + millis)
+}
+
+fn number(text: &str, digits: usize) -> Option<i64> {
+    if text.len() == digits && text.bytes().all(|byte| byte.is_ascii_digit()) {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 of a date of the proleptic Gregorian calendar, see
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = (month + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iso8601() {
+        assert_eq!(parse_iso8601("1970-01-01"), Some(0));
+        assert_eq!(parse_iso8601("1969-12-31"), Some(-86_400_000));
+        assert_eq!(parse_iso8601("2000-03-01"), Some(951_868_800_000));
+        assert_eq!(parse_iso8601("2024-02-29 09:30"), Some(1_709_199_000_000));
+        assert_eq!(
+            parse_iso8601("2017-01-03T14:30:15.25Z"),
+            Some(1_483_453_815_250)
+        );
+
+        assert_eq!(parse_iso8601("2023-02-29"), None);
+        assert_eq!(parse_iso8601("2023-13-01"), None);
+        assert_eq!(parse_iso8601("2023-1-01"), None);
+        assert_eq!(parse_iso8601("2023-01-01T24:00"), None);
+        assert_eq!(parse_iso8601("2023-01-01T12:00."), None);
+        assert_eq!(parse_iso8601("01/02/2023"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let err = DataError::Parse {
+            line: 3,
+            message: "invalid close".to_string(),
+        };
+        assert_eq!(err.to_string(), "line 3: invalid close");
+        assert_eq!(
+            DataError::MissingColumn("close".to_string()).to_string(),
+            "missing column close"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::errors::*;
-use crate::{Close, High, Low, Open, Volume};
+use crate::{Close, High, Low, Open, OpenInterest, Timestamp, Volume};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Data item is used as an input for indicators.
@@ -27,19 +28,63 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(item.volume(), 7500.0);
 /// ```
 ///
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Bars can also carry a timestamp, the open interest and the number of trades, e.g. for
+/// session based indicators. These fields are optional and are `None` unless set:
+///
+/// ```
+/// use ta_panther::DataItem;
+/// use ta_panther::{OpenInterest, Timestamp};
+///
+/// let item = DataItem::builder()
+///     .open(20.0)
+///     .high(25.0)
+///     .low(15.0)
+///     .close(21.0)
+///     .volume(7500.0)
+///     .timestamp(1_600_000_000_000)
+///     .trade_count(42)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(item.timestamp(), Some(1_600_000_000_000));
+/// assert_eq!(item.trade_count(), Some(42));
+/// assert_eq!(item.open_interest(), None);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DataItem {
     open: f64,
     high: f64,
     low: f64,
     close: f64,
     volume: f64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    timestamp: Option<i64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    open_interest: Option<f64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    trade_count: Option<u64>,
 }
 
 impl DataItem {
     pub fn builder() -> DataItemBuilder {
         DataItemBuilder::new()
     }
+
+    /// Number of trades during the period, if known.
+    pub fn trade_count(&self) -> Option<u64> {
+        self.trade_count
+    }
 }
 
 impl Open for DataItem {
@@ -72,12 +117,27 @@ impl Volume for DataItem {
     }
 }
 
+impl Timestamp for DataItem {
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+impl OpenInterest for DataItem {
+    fn open_interest(&self) -> Option<f64> {
+        self.open_interest
+    }
+}
+
 pub struct DataItemBuilder {
     open: Option<f64>,
     high: Option<f64>,
     low: Option<f64>,
     close: Option<f64>,
     volume: Option<f64>,
+    timestamp: Option<i64>,
+    open_interest: Option<f64>,
+    trade_count: Option<u64>,
 }
 
 impl DataItemBuilder {
@@ -88,6 +148,9 @@ impl DataItemBuilder {
             low: None,
             close: None,
             volume: None,
+            timestamp: None,
+            open_interest: None,
+            trade_count: None,
         }
     }
 
@@ -116,6 +179,22 @@ impl DataItemBuilder {
         self
     }
 
+    /// Sets the start time of the period, in milliseconds since the Unix epoch.
+    pub fn timestamp(mut self, val: i64) -> Self {
+        self.timestamp = Some(val);
+        self
+    }
+
+    pub fn open_interest(mut self, val: f64) -> Self {
+        self.open_interest = Some(val);
+        self
+    }
+
+    pub fn trade_count(mut self, val: u64) -> Self {
+        self.trade_count = Some(val);
+        self
+    }
+
     pub fn build(self) -> Result<DataItem> {
         if let (Some(open), Some(high), Some(low), Some(close), Some(volume)) =
             (self.open, self.high, self.low, self.close, self.volume)
@@ -127,6 +206,7 @@ impl DataItemBuilder {
                 && high >= open
                 && high >= close
                 && volume >= 0.0
+                && self.open_interest.is_none_or(|oi| oi >= 0.0)
             {
                 let item = DataItem {
                     open,
@@ -134,6 +214,9 @@ impl DataItemBuilder {
                     low,
                     close,
                     volume,
+                    timestamp: self.timestamp,
+                    open_interest: self.open_interest,
+                    trade_count: self.trade_count,
                 };
                 Ok(item)
             } else {
@@ -148,6 +231,7 @@ impl DataItemBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_builder() {
@@ -200,4 +284,42 @@ mod tests {
             assert_invalid(record)
         }
     }
+    #[test]
+    fn test_metadata() {
+        let builder = || {
+            DataItem::builder()
+                .open(20.0)
+                .high(25.0)
+                .low(15.0)
+                .close(21.0)
+                .volume(7500.0)
+        };
+
+        let item = builder().build().unwrap();
+        assert_eq!(item.timestamp(), None);
+        assert_eq!(item.open_interest(), None);
+        assert_eq!(item.trade_count(), None);
+
+        let item = builder()
+            .timestamp(-1000)
+            .open_interest(350.0)
+            .trade_count(12)
+            .build()
+            .unwrap();
+        assert_eq!(item.timestamp(), Some(-1000));
+        assert_eq!(item.open_interest(), Some(350.0));
+        assert_eq!(item.trade_count(), Some(12));
+
+        let result = builder().open_interest(-1.0).build();
+        assert_eq!(result, Err(TaError::DataItemInvalid));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_deserialize_without_metadata() {
+        let json = r#"{"open":20.0,"high":25.0,"low":15.0,"close":21.0,"volume":7500.0}"#;
+        let item: DataItem = serde_json::from_str(json).unwrap();
+        assert_eq!(item.timestamp(), None);
+        assert_eq!(serde_json::to_string(&item).unwrap(), json);
+    }
 }
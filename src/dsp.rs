@@ -0,0 +1,1225 @@
+//! Digital signal processing indicators.
+//!
+//! [SecondOrderFilter] is a standard low-pass, high-pass or band-pass filter, e.g. a Butterworth
+//! filter, with its cutoff given in bars.
+//!
+//! The other indicators were designed by John Ehlers. [SuperSmoother], [RoofingFilter] and
+//! [Decycler] are recursive filters, which smooth the price with far less lag than moving
+//! averages of the same period, or isolate its cycles from its trend.
+//!
+//! [MesaAdaptiveMovingAverage] and [DominantCycle] share a Hilbert transform of the price, which
+//! splits the detrended price into its in-phase and quadrature components. A homodyne
+//! discriminator then measures the dominant cycle period of the market, from 6 to 50 bars,
+//! which adapts the [MesaAdaptiveMovingAverage] and gives the period and phase of the
+//! [DominantCycle]. The Hilbert transform needs about 32 bars to settle, during which their
+//! outputs are unreliable.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::dsp::DominantCycle;
+//! use ta_panther::Next;
+//!
+//! let mut cycle = DominantCycle::new();
+//! let mut output = cycle.next(0.0);
+//! for i in 1..200 {
+//!     let angle = 2.0 * std::f64::consts::PI * i as f64 / 20.0;
+//!     output = cycle.next(100.0 + angle.sin());
+//! }
+//! assert!((output.period - 20.0).abs() < 1.0);
+//! ```
+//!
+//! # Links
+//!
+//! * [MESA Adaptive Moving Averages, John Ehlers](https://www.mesasoftware.com/papers/MAMA.pdf)
+
+use core::f64::consts::{FRAC_1_SQRT_2, PI, SQRT_2};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::rolling::RollingWindow;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of bars the filters need to settle.
+const WARMUP: usize = 32;
+
+const MIN_PERIOD: f64 = 6.0;
+const MAX_PERIOD: f64 = 50.0;
+
+fn atan_degrees(x: f64) -> f64 {
+    x.atan().to_degrees()
+}
+
+/// Pushes `value` at the front of the history, dropping the oldest value.
+fn shift<const N: usize>(history: &mut [f64; N], value: f64) {
+    history.copy_within(..N - 1, 1);
+    history[0] = value;
+}
+
+/// Hilbert transform FIR filter over the latest 7 values, scaled by the dominant period.
+fn hilbert(history: &[f64; 7], scale: f64) -> f64 {
+    (0.0962 * history[0] + 0.5769 * history[2] - 0.5769 * history[4] - 0.0962 * history[6]) * scale
+}
+
+/// Hilbert transform and homodyne discriminator of the price.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct HilbertTransform {
+    count: usize,
+    prices: [f64; 4],
+    smooth: [f64; 7],
+    detrender: [f64; 7],
+    in_phase: [f64; 7],
+    quadrature: [f64; 7],
+    i2: f64,
+    q2: f64,
+    re: f64,
+    im: f64,
+    period: f64,
+    smooth_period: f64,
+}
+
+impl HilbertTransform {
+    fn next(&mut self, price: f64) {
+        self.count += 1;
+        shift(&mut self.prices, price);
+        let p = &self.prices;
+        let smooth = (4.0 * p[0] + 3.0 * p[1] + 2.0 * p[2] + p[3]) / 10.0;
+        shift(&mut self.smooth, smooth);
+
+        let scale = 0.075 * self.period + 0.54;
+        let detrender = hilbert(&self.smooth, scale);
+        shift(&mut self.detrender, detrender);
+        let q1 = hilbert(&self.detrender, scale);
+        let i1 = self.detrender[3];
+        shift(&mut self.in_phase, i1);
+        shift(&mut self.quadrature, q1);
+
+        // advance the phase of the components by 90 degrees
+        let ji = hilbert(&self.in_phase, scale);
+        let jq = hilbert(&self.quadrature, scale);
+
+        let i2 = 0.2 * (i1 - jq) + 0.8 * self.i2;
+        let q2 = 0.2 * (q1 + ji) + 0.8 * self.q2;
+        let re = i2 * self.i2 + q2 * self.q2;
+        let im = i2 * self.q2 - q2 * self.i2;
+        self.i2 = i2;
+        self.q2 = q2;
+        self.re = 0.2 * re + 0.8 * self.re;
+        self.im = 0.2 * im + 0.8 * self.im;
+
+        let previous = self.period;
+        let mut period = previous;
+        if self.im != 0.0 && self.re != 0.0 {
+            period = 360.0 / atan_degrees(self.im / self.re);
+        }
+        period = period
+            .min(1.5 * previous)
+            .max(0.67 * previous)
+            .clamp(MIN_PERIOD, MAX_PERIOD);
+        self.period = 0.2 * period + 0.8 * previous;
+        self.smooth_period = 0.33 * self.period + 0.67 * self.smooth_period;
+    }
+
+    /// Returns the latest smoothed price.
+    fn smooth(&self) -> f64 {
+        self.smooth[0]
+    }
+
+    fn in_phase(&self) -> f64 {
+        self.in_phase[0]
+    }
+
+    fn quadrature(&self) -> f64 {
+        self.quadrature[0]
+    }
+
+    fn bars_remaining(&self) -> usize {
+        WARMUP.saturating_sub(self.count)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MesaAdaptiveMovingAverageOutput {
+    pub mama: f64,
+    pub fama: f64,
+}
+
+impl From<MesaAdaptiveMovingAverageOutput> for (f64, f64) {
+    fn from(output: MesaAdaptiveMovingAverageOutput) -> Self {
+        (output.mama, output.fama)
+    }
+}
+
+/// MESA adaptive moving average (MAMA) and following adaptive moving average (FAMA).
+///
+/// Exponential moving average whose smoothing factor adapts to the rate of change of the phase
+/// of the dominant cycle: it follows the price quickly when the phase changes slowly, i.e. in
+/// trends, and slowly in cycling markets. The FAMA follows the MAMA with half its smoothing
+/// factor, and their crossovers are trading signals.
+///
+/// Both averages start at the first input.
+///
+/// # Formula
+///
+/// Phase = arctan(Q1 / I1)
+///
+/// α = max(fast_limit / max(Phase<sub>t-1</sub> - Phase<sub>t</sub>, 1), slow_limit)
+///
+/// MAMA = α × Price + (1 - α) × MAMA<sub>t-1</sub>
+///
+/// FAMA = α / 2 × MAMA + (1 - α / 2) × FAMA<sub>t-1</sub>
+///
+/// Where _I1_ and _Q1_ are the in-phase and quadrature components of the Hilbert transform,
+/// see the [module](self) documentation, and the phase is in degrees.
+///
+/// # Parameters
+///
+/// * _fast_limit_ - maximum smoothing factor (between 0.0 and 1.0). Default is 0.5.
+/// * _slow_limit_ - minimum smoothing factor (between 0.0 and _fast_limit_). Default is 0.05.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::dsp::MesaAdaptiveMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut mama = MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap();
+/// assert_eq!(mama.next(10.0).mama, 10.0);
+/// let output = mama.next(12.0);
+/// assert!(output.mama > output.fama);
+/// ```
+#[doc(alias = "MAMA")]
+#[doc(alias = "FAMA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MesaAdaptiveMovingAverage {
+    hilbert: HilbertTransform,
+    fast_limit: f64,
+    slow_limit: f64,
+    phase: f64,
+    output: Option<MesaAdaptiveMovingAverageOutput>,
+}
+
+impl MesaAdaptiveMovingAverage {
+    pub fn new(fast_limit: f64, slow_limit: f64) -> Result<Self> {
+        if !(slow_limit > 0.0 && slow_limit <= fast_limit && fast_limit <= 1.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            hilbert: HilbertTransform::default(),
+            fast_limit,
+            slow_limit,
+            phase: 0.0,
+            output: None,
+        })
+    }
+
+    pub fn fast_limit(&self) -> f64 {
+        self.fast_limit
+    }
+
+    pub fn slow_limit(&self) -> f64 {
+        self.slow_limit
+    }
+}
+
+impl IsReady for MesaAdaptiveMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.hilbert.bars_remaining()
+    }
+}
+
+impl Next<f64> for MesaAdaptiveMovingAverage {
+    type Output = MesaAdaptiveMovingAverageOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.hilbert.next(input);
+
+        let previous_phase = self.phase;
+        let in_phase = self.hilbert.in_phase();
+        if in_phase != 0.0 {
+            self.phase = atan_degrees(self.hilbert.quadrature() / in_phase);
+        }
+        let delta_phase = (previous_phase - self.phase).max(1.0);
+        let alpha = (self.fast_limit / delta_phase).max(self.slow_limit);
+
+        let output = match self.output {
+            Some(previous) => {
+                let mama = alpha * input + (1.0 - alpha) * previous.mama;
+                let fama = 0.5 * alpha * mama + (1.0 - 0.5 * alpha) * previous.fama;
+                MesaAdaptiveMovingAverageOutput { mama, fama }
+            }
+            None => MesaAdaptiveMovingAverageOutput {
+                mama: input,
+                fama: input,
+            },
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for MesaAdaptiveMovingAverage {
+    type Output = MesaAdaptiveMovingAverageOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for MesaAdaptiveMovingAverage {
+    fn reset(&mut self) {
+        self.hilbert = HilbertTransform::default();
+        self.phase = 0.0;
+        self.output = None;
+    }
+}
+
+impl Default for MesaAdaptiveMovingAverage {
+    fn default() -> Self {
+        Self::new(0.5, 0.05).unwrap()
+    }
+}
+
+impl fmt::Display for MesaAdaptiveMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MAMA({}, {})", self.fast_limit, self.slow_limit)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DominantCycleOutput {
+    /// Smoothed dominant cycle period, in bars.
+    pub period: f64,
+    /// Phase of the dominant cycle, in degrees from -45 to 315.
+    pub phase: f64,
+}
+
+impl From<DominantCycleOutput> for (f64, f64) {
+    fn from(output: DominantCycleOutput) -> Self {
+        (output.period, output.phase)
+    }
+}
+
+/// Hilbert transform dominant cycle period and phase (HT_DCPERIOD and HT_DCPHASE).
+///
+/// The period is measured by the homodyne discriminator, see the [module](self) documentation,
+/// and the phase is the angle of the smoothed prices of the last dominant cycle correlated with
+/// a sine and a cosine of the same period.
+///
+/// # Formula
+///
+/// Real = Σ<sub>k < n</sub> sin(360 k / n) × Smooth<sub>t-k</sub>
+///
+/// Imaginary = Σ<sub>k < n</sub> cos(360 k / n) × Smooth<sub>t-k</sub>
+///
+/// Phase = arctan(Real / Imaginary) + 90 + 360 / Period, plus 180 if Imaginary is negative
+///
+/// Where _Period_ is the smoothed dominant cycle period, _n_ is the period rounded to the
+/// nearest integer and _Smooth_ is the 4 bar weighted moving average of the price. The phase
+/// is wrapped to stay below 315 degrees.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::dsp::DominantCycle;
+/// use ta_panther::Next;
+///
+/// let mut cycle = DominantCycle::new();
+/// let output = cycle.next(10.0);
+/// assert!(output.period > 0.0);
+/// ```
+#[doc(alias = "HT_DCPERIOD")]
+#[doc(alias = "HT_DCPHASE")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DominantCycle {
+    hilbert: HilbertTransform,
+    smooth: RollingWindow<f64>,
+    phase: f64,
+}
+
+impl DominantCycle {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::default(),
+            smooth: RollingWindow::new(MAX_PERIOD as usize).unwrap(),
+            phase: 0.0,
+        }
+    }
+
+    fn update_phase(&mut self) {
+        let period = self.hilbert.smooth_period;
+        let cycle = ((period + 0.5) as usize).min(self.smooth.len());
+        let (mut real, mut imaginary) = (0.0, 0.0);
+        // the newest smoothed price comes first
+        for (k, smooth) in self.smooth.iter().rev().take(cycle).enumerate() {
+            let angle = (360.0 * k as f64 / cycle as f64).to_radians();
+            real += angle.sin() * smooth;
+            imaginary += angle.cos() * smooth;
+        }
+
+        let mut phase = self.phase;
+        if imaginary.abs() > 0.0 {
+            phase = atan_degrees(real / imaginary);
+        }
+        if imaginary.abs() <= 0.001 {
+            phase += 90.0 * real.signum();
+        }
+        phase += 90.0;
+        // compensates the lag of one bar of the weighted moving average
+        phase += 360.0 / period.max(MIN_PERIOD);
+        if imaginary < 0.0 {
+            phase += 180.0;
+        }
+        if phase > 315.0 {
+            phase -= 360.0;
+        }
+        self.phase = phase;
+    }
+}
+
+impl IsReady for DominantCycle {
+    fn bars_remaining(&self) -> usize {
+        self.hilbert.bars_remaining()
+    }
+}
+
+impl Next<f64> for DominantCycle {
+    type Output = DominantCycleOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.hilbert.next(input);
+        self.smooth.push(self.hilbert.smooth());
+        self.update_phase();
+        DominantCycleOutput {
+            period: self.hilbert.smooth_period,
+            phase: self.phase,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for DominantCycle {
+    type Output = DominantCycleOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for DominantCycle {
+    fn reset(&mut self) {
+        self.hilbert = HilbertTransform::default();
+        self.smooth.clear();
+        self.phase = 0.0;
+    }
+}
+
+impl Default for DominantCycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for DominantCycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("HT_DC")
+    }
+}
+
+/// Coefficients of the two-pole Super Smoother of `period` bars.
+fn super_smoother_coefficients(period: f64) -> (f64, f64, f64) {
+    let a1 = (-SQRT_2 * PI / period).exp();
+    let c2 = 2.0 * a1 * (SQRT_2 * PI / period).cos();
+    let c3 = -a1 * a1;
+    (1.0 - c2 - c3, c2, c3)
+}
+
+/// Ehlers' two-pole Super Smoother filter (SSF).
+///
+/// Low-pass Butterworth filter averaging the last two inputs, which removes the aliasing noise
+/// of the bars: cycles shorter than _period_ are strongly attenuated while the lag stays much
+/// smaller than the lag of a moving average of the same period. The first two outputs are the
+/// inputs.
+///
+/// # Formula
+///
+/// a<sub>1</sub> = exp(-√2 π / period)
+///
+/// c<sub>2</sub> = 2 a<sub>1</sub> cos(√2 π / period), c<sub>3</sub> = -a<sub>1</sub>²,
+/// c<sub>1</sub> = 1 - c<sub>2</sub> - c<sub>3</sub>
+///
+/// SSF<sub>t</sub> = c<sub>1</sub> (Price<sub>t</sub> + Price<sub>t-1</sub>) / 2 +
+/// c<sub>2</sub> SSF<sub>t-1</sub> + c<sub>3</sub> SSF<sub>t-2</sub>
+///
+/// # Parameters
+///
+/// * _period_ - critical period, in bars, below which cycles are attenuated (integer greater
+///   than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::dsp::SuperSmoother;
+/// use ta_panther::Next;
+///
+/// let mut ssf = SuperSmoother::new(10).unwrap();
+/// assert_eq!(ssf.next(10.0), 10.0);
+/// let mut output = 0.0;
+/// for i in 0..50 {
+///     output = ssf.next(if i % 2 == 0 { 10.0 } else { 12.0 });
+/// }
+/// // the noise of the alternating inputs is removed
+/// assert!((output - 11.0).abs() < 0.01);
+/// ```
+#[doc(alias = "SSF")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SuperSmoother {
+    period: usize,
+    c1: f64,
+    c2: f64,
+    c3: f64,
+    count: usize,
+    input: f64,
+    outputs: [f64; 2],
+}
+
+impl SuperSmoother {
+    pub fn new(period: usize) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        let (c1, c2, c3) = super_smoother_coefficients(period as f64);
+        Ok(Self {
+            period,
+            c1,
+            c2,
+            c3,
+            count: 0,
+            input: 0.0,
+            outputs: [0.0; 2],
+        })
+    }
+}
+
+impl Period for SuperSmoother {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for SuperSmoother {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = if self.count < 2 {
+            self.count += 1;
+            input
+        } else {
+            self.c1 * (input + self.input) / 2.0
+                + self.c2 * self.outputs[0]
+                + self.c3 * self.outputs[1]
+        };
+        self.input = input;
+        shift(&mut self.outputs, output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for SuperSmoother {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for SuperSmoother {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.input = 0.0;
+        self.outputs = [0.0; 2];
+    }
+}
+
+impl Default for SuperSmoother {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for SuperSmoother {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SSF({})", self.period)
+    }
+}
+
+/// Ehlers' roofing filter (ROOF).
+///
+/// Band-pass filter keeping the cycles between the two periods: a two-pole high-pass filter
+/// removes the trend, i.e. the cycles longer than _high_pass_period_, and a [SuperSmoother]
+/// removes the noise shorter than _low_pass_period_. The output oscillates around 0, and is 0
+/// for the first two inputs.
+///
+/// # Formula
+///
+/// α = (cos(2π / √2 / hp) + sin(2π / √2 / hp) - 1) / cos(2π / √2 / hp)
+///
+/// HP<sub>t</sub> = (1 - α / 2)² (Price<sub>t</sub> - 2 Price<sub>t-1</sub> +
+/// Price<sub>t-2</sub>) + 2 (1 - α) HP<sub>t-1</sub> - (1 - α)² HP<sub>t-2</sub>
+///
+/// ROOF = SSF(lp) of HP
+///
+/// Where _hp_ and _lp_ are the high-pass and low-pass periods.
+///
+/// # Parameters
+///
+/// * _high_pass_period_ - period above which cycles are removed (integer greater than 2).
+///   Default is 48.
+/// * _low_pass_period_ - period below which cycles are removed (integer greater than 0).
+///   Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::dsp::RoofingFilter;
+/// use ta_panther::Next;
+///
+/// let mut roof = RoofingFilter::new(48, 10).unwrap();
+/// // a linear trend is removed entirely
+/// for i in 0..10 {
+///     assert_eq!(roof.next(100.0 + i as f64), 0.0);
+/// }
+/// ```
+#[doc(alias = "ROOF")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoofingFilter {
+    high_pass_period: usize,
+    alpha: f64,
+    count: usize,
+    inputs: [f64; 2],
+    high_pass: [f64; 2],
+    smoother: SuperSmoother,
+}
+
+impl RoofingFilter {
+    pub fn new(high_pass_period: usize, low_pass_period: usize) -> Result<Self> {
+        if high_pass_period <= 2 {
+            return Err(TaError::InvalidParameter);
+        }
+        let angle = 2.0 * PI / SQRT_2 / high_pass_period as f64;
+        Ok(Self {
+            high_pass_period,
+            alpha: (angle.cos() + angle.sin() - 1.0) / angle.cos(),
+            count: 0,
+            inputs: [0.0; 2],
+            high_pass: [0.0; 2],
+            smoother: SuperSmoother::new(low_pass_period)?,
+        })
+    }
+
+    pub fn high_pass_period(&self) -> usize {
+        self.high_pass_period
+    }
+
+    pub fn low_pass_period(&self) -> usize {
+        self.smoother.period()
+    }
+}
+
+impl Next<f64> for RoofingFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let high_pass = if self.count < 2 {
+            self.count += 1;
+            0.0
+        } else {
+            let (a, x, hp) = (self.alpha, &self.inputs, &self.high_pass);
+            (1.0 - a / 2.0).powi(2) * (input - 2.0 * x[0] + x[1]) + 2.0 * (1.0 - a) * hp[0]
+                - (1.0 - a).powi(2) * hp[1]
+        };
+        shift(&mut self.inputs, input);
+        shift(&mut self.high_pass, high_pass);
+        self.smoother.next(high_pass)
+    }
+}
+
+impl<T: Close> Next<&T> for RoofingFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for RoofingFilter {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.inputs = [0.0; 2];
+        self.high_pass = [0.0; 2];
+        self.smoother.reset();
+    }
+}
+
+impl Default for RoofingFilter {
+    fn default() -> Self {
+        Self::new(48, 10).unwrap()
+    }
+}
+
+impl fmt::Display for RoofingFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ROOF({}, {})",
+            self.high_pass_period,
+            self.low_pass_period()
+        )
+    }
+}
+
+/// Ehlers' decycler (DECYCLER).
+///
+/// Trend of the price left after a one-pole high-pass filter removes the cycles shorter than
+/// _period_, computed directly as the complementary low-pass filter. It follows the trend with
+/// little lag, and the price crossing it signals changes of the trend. The first output is the
+/// input.
+///
+/// # Formula
+///
+/// α = (cos(2π / period) + sin(2π / period) - 1) / cos(2π / period)
+///
+/// Decycler<sub>t</sub> = α / 2 (Price<sub>t</sub> + Price<sub>t-1</sub>) + (1 - α)
+/// Decycler<sub>t-1</sub>
+///
+/// # Parameters
+///
+/// * _period_ - period below which cycles are removed (integer greater than 4). Default is 60.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::dsp::Decycler;
+/// use ta_panther::Next;
+///
+/// let mut decycler = Decycler::new(20).unwrap();
+/// assert_eq!(decycler.next(10.0), 10.0);
+/// let trend = decycler.next(12.0);
+/// assert!(10.0 < trend && trend < 12.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Decycler {
+    period: usize,
+    alpha: f64,
+    previous: Option<(f64, f64)>,
+}
+
+impl Decycler {
+    pub fn new(period: usize) -> Result<Self> {
+        if period <= 4 {
+            return Err(TaError::InvalidParameter);
+        }
+        let angle = 2.0 * PI / period as f64;
+        Ok(Self {
+            period,
+            alpha: (angle.cos() + angle.sin() - 1.0) / angle.cos(),
+            previous: None,
+        })
+    }
+}
+
+impl Period for Decycler {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for Decycler {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = match self.previous {
+            Some((previous_input, previous_output)) => {
+                self.alpha / 2.0 * (input + previous_input) + (1.0 - self.alpha) * previous_output
+            }
+            None => input,
+        };
+        self.previous = Some((input, output));
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for Decycler {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for Decycler {
+    fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+impl Default for Decycler {
+    fn default() -> Self {
+        Self::new(60).unwrap()
+    }
+}
+
+impl fmt::Display for Decycler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DECYCLER({})", self.period)
+    }
+}
+
+/// Response of a [SecondOrderFilter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FilterType {
+    /// Keeps the cycles longer than the period, e.g. to denoise the price.
+    #[default]
+    LowPass,
+    /// Keeps the cycles shorter than the period, e.g. to detrend the price.
+    HighPass,
+    /// Keeps the cycles close to the period.
+    BandPass,
+}
+
+impl fmt::Display for FilterType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FilterType::LowPass => f.write_str("LOWPASS"),
+            FilterType::HighPass => f.write_str("HIGHPASS"),
+            FilterType::BandPass => f.write_str("BANDPASS"),
+        }
+    }
+}
+
+/// Second-order IIR digital filter (biquad).
+///
+/// Standard low-pass, high-pass or band-pass filter, whose cutoff is given as a period in bars.
+/// With the default quality factor of 1/√2, the low-pass and high-pass filters are Butterworth
+/// filters, whose response is as flat as possible in the pass band and halves the power of the
+/// cycles of _period_ bars. Higher quality factors give a resonant peak at the period, and
+/// narrower band-pass filters, whose gain is 1 at the period.
+///
+/// The filter starts in the steady state of the first input, so the low-pass filter returns
+/// the first input and the other filters return 0.
+///
+/// # Formula
+///
+/// ω = 2π / period, α = sin(ω) / (2 Q)
+///
+/// y<sub>t</sub> = (b<sub>0</sub> x<sub>t</sub> + b<sub>1</sub> x<sub>t-1</sub> +
+/// b<sub>2</sub> x<sub>t-2</sub> - a<sub>1</sub> y<sub>t-1</sub> - a<sub>2</sub>
+/// y<sub>t-2</sub>) / a<sub>0</sub>
+///
+/// Where a<sub>0</sub> = 1 + α, a<sub>1</sub> = -2 cos(ω), a<sub>2</sub> = 1 - α, and:
+///
+/// * low-pass: b<sub>0</sub> = b<sub>2</sub> = (1 - cos(ω)) / 2, b<sub>1</sub> = 1 - cos(ω)
+/// * high-pass: b<sub>0</sub> = b<sub>2</sub> = (1 + cos(ω)) / 2, b<sub>1</sub> = -(1 +
+///   cos(ω))
+/// * band-pass: b<sub>0</sub> = α, b<sub>1</sub> = 0, b<sub>2</sub> = -α
+///
+/// # Parameters
+///
+/// * _filter_type_ - response of the filter, see [FilterType]. Default is
+///   [LowPass](FilterType::LowPass).
+/// * _period_ - cutoff, or center of the band, in bars (integer greater than 2). Default is 20.
+/// * _q_ - quality factor (greater than 0.0). Default is 1/√2, see [with_q](Self::with_q).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::dsp::{FilterType, SecondOrderFilter};
+/// use ta_panther::Next;
+///
+/// let mut low_pass = SecondOrderFilter::new(FilterType::LowPass, 10).unwrap();
+/// let mut high_pass = SecondOrderFilter::new(FilterType::HighPass, 10).unwrap();
+/// assert_eq!(low_pass.next(10.0), 10.0);
+/// assert_eq!(high_pass.next(10.0), 0.0);
+///
+/// // a step is followed smoothly by the low-pass filter, and fades out of the high-pass one
+/// let (mut low, mut high) = (0.0, 0.0);
+/// for _ in 0..50 {
+///     low = low_pass.next(20.0);
+///     high = high_pass.next(20.0);
+/// }
+/// assert!((low - 20.0).abs() < 1e-3);
+/// assert!(high.abs() < 1e-3);
+/// ```
+///
+/// # Links
+///
+/// * [Digital biquad filter, Wikipedia](https://en.wikipedia.org/wiki/Digital_biquad_filter)
+///
+#[doc(alias = "BIQUAD")]
+#[doc(alias = "BUTTERWORTH")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecondOrderFilter {
+    filter_type: FilterType,
+    period: usize,
+    q: f64,
+    b: [f64; 3],
+    a: [f64; 2],
+    inputs: [f64; 2],
+    outputs: [f64; 2],
+    started: bool,
+}
+
+impl SecondOrderFilter {
+    pub fn new(filter_type: FilterType, period: usize) -> Result<Self> {
+        Self::with_q(filter_type, period, FRAC_1_SQRT_2)
+    }
+
+    pub fn with_q(filter_type: FilterType, period: usize, q: f64) -> Result<Self> {
+        if period <= 2 || !(q > 0.0 && q.is_finite()) {
+            return Err(TaError::InvalidParameter);
+        }
+        let omega = 2.0 * PI / period as f64;
+        let (sin, cos) = (omega.sin(), omega.cos());
+        let alpha = sin / (2.0 * q);
+        let b = match filter_type {
+            FilterType::LowPass => [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            FilterType::HighPass => [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            FilterType::BandPass => [alpha, 0.0, -alpha],
+        };
+        let a0 = 1.0 + alpha;
+        Ok(Self {
+            filter_type,
+            period,
+            q,
+            b: [b[0] / a0, b[1] / a0, b[2] / a0],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+            inputs: [0.0; 2],
+            outputs: [0.0; 2],
+            started: false,
+        })
+    }
+
+    pub fn filter_type(&self) -> FilterType {
+        self.filter_type
+    }
+
+    pub fn q(&self) -> f64 {
+        self.q
+    }
+}
+
+impl Period for SecondOrderFilter {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for SecondOrderFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.started {
+            // steady state of a constant input
+            let gain = match self.filter_type {
+                FilterType::LowPass => 1.0,
+                FilterType::HighPass | FilterType::BandPass => 0.0,
+            };
+            self.inputs = [input; 2];
+            self.outputs = [gain * input; 2];
+            self.started = true;
+            return gain * input;
+        }
+        let (b, a) = (&self.b, &self.a);
+        let output = b[0] * input + b[1] * self.inputs[0] + b[2] * self.inputs[1]
+            - a[0] * self.outputs[0]
+            - a[1] * self.outputs[1];
+        shift(&mut self.inputs, input);
+        shift(&mut self.outputs, output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for SecondOrderFilter {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for SecondOrderFilter {
+    fn reset(&mut self) {
+        self.inputs = [0.0; 2];
+        self.outputs = [0.0; 2];
+        self.started = false;
+    }
+}
+
+impl Default for SecondOrderFilter {
+    fn default() -> Self {
+        Self::new(FilterType::LowPass, 20).unwrap()
+    }
+}
+
+impl fmt::Display for SecondOrderFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.q == FRAC_1_SQRT_2 {
+            write!(f, "{}({})", self.filter_type, self.period)
+        } else {
+            write!(f, "{}({}, {})", self.filter_type, self.period, self.q)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use alloc::string::ToString;
+
+    fn sine(period: f64) -> impl Iterator<Item = f64> {
+        (0..).map(move |i| 100.0 + 5.0 * (2.0 * PI * i as f64 / period).sin())
+    }
+
+    /// Returns the amplitude of the outputs of the filter on a sine of `period` bars.
+    fn amplitude(filter: &mut impl Next<f64, Output = f64>, period: f64) -> f64 {
+        let outputs: Vec<f64> = sine(period).take(600).map(|x| filter.next(x)).collect();
+        let settled = &outputs[400..];
+        let mean = settled.iter().sum::<f64>() / settled.len() as f64;
+        let variance =
+            settled.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / settled.len() as f64;
+        (2.0 * variance).sqrt()
+    }
+
+    #[test]
+    fn test_super_smoother() {
+        assert!(SuperSmoother::new(0).is_err());
+        let (c1, c2, c3) = super_smoother_coefficients(10.0);
+        assert_eq!(round(c1 + c2 + c3), 1.0);
+
+        let mut ssf = SuperSmoother::new(10).unwrap();
+        assert_eq!(ssf.period(), 10);
+        for _ in 0..20 {
+            assert_eq!(round(ssf.next(&Bar::new().close(5.0))), 5.0);
+        }
+        // cycles of 2 bars cancel out, long cycles pass
+        assert!(amplitude(&mut SuperSmoother::new(10).unwrap(), 2.0) < 1e-9);
+        assert!(amplitude(&mut SuperSmoother::new(10).unwrap(), 50.0) > 4.5);
+
+        ssf.reset();
+        assert_eq!(ssf.next(7.0), 7.0);
+    }
+
+    #[test]
+    fn test_roofing_filter() {
+        assert!(RoofingFilter::new(2, 10).is_err());
+        assert!(RoofingFilter::new(48, 0).is_err());
+
+        let mut roof = RoofingFilter::new(48, 10).unwrap();
+        assert_eq!((roof.high_pass_period(), roof.low_pass_period()), (48, 10));
+        for i in 0..20 {
+            assert_eq!(round(roof.next(&Bar::new().close(i))), 0.0);
+        }
+        // cycles within the band pass, shorter and longer ones are attenuated
+        let amplitude_of = |period| amplitude(&mut RoofingFilter::new(48, 10).unwrap(), period);
+        let band = amplitude_of(20.0);
+        assert!(band > 3.0);
+        assert!(amplitude_of(4.0) < 0.2 * band);
+        assert!(amplitude_of(200.0) < 0.2 * band);
+
+        roof.reset();
+        assert_eq!(roof.next(100.0), 0.0);
+    }
+
+    #[test]
+    fn test_decycler() {
+        assert!(Decycler::new(4).is_err());
+
+        let mut decycler = Decycler::new(20).unwrap();
+        assert_eq!(decycler.period(), 20);
+        for _ in 0..20 {
+            assert_eq!(round(decycler.next(&Bar::new().close(5.0))), 5.0);
+        }
+        assert!(amplitude(&mut Decycler::new(20).unwrap(), 4.0) < 1.0);
+        assert!(amplitude(&mut Decycler::new(20).unwrap(), 100.0) > 4.5);
+
+        decycler.reset();
+        assert_eq!(decycler.next(7.0), 7.0);
+    }
+
+    #[test]
+    fn test_second_order_filter_new() {
+        assert!(SecondOrderFilter::new(FilterType::LowPass, 2).is_err());
+        assert!(SecondOrderFilter::with_q(FilterType::BandPass, 20, 0.0).is_err());
+        assert!(SecondOrderFilter::with_q(FilterType::BandPass, 20, f64::INFINITY).is_err());
+
+        let filter = SecondOrderFilter::with_q(FilterType::HighPass, 20, 2.0).unwrap();
+        assert_eq!(filter.filter_type(), FilterType::HighPass);
+        assert_eq!((filter.period(), filter.q()), (20, 2.0));
+    }
+
+    #[test]
+    fn test_second_order_filter_response() {
+        let gain = |filter_type, period: usize, cycle: f64| {
+            let mut filter = SecondOrderFilter::new(filter_type, period).unwrap();
+            amplitude(&mut filter, cycle) / 5.0
+        };
+        // Butterworth filters halve the power at the cutoff
+        assert!((gain(FilterType::LowPass, 20, 20.0) - FRAC_1_SQRT_2).abs() < 0.01);
+        assert!((gain(FilterType::HighPass, 20, 20.0) - FRAC_1_SQRT_2).abs() < 0.01);
+        assert!(gain(FilterType::LowPass, 20, 100.0) > 0.99);
+        assert!(gain(FilterType::LowPass, 20, 4.0) < 0.1);
+        assert!(gain(FilterType::HighPass, 20, 4.0) > 0.99);
+        assert!(gain(FilterType::HighPass, 20, 100.0) < 0.1);
+
+        assert!((gain(FilterType::BandPass, 20, 20.0) - 1.0).abs() < 0.01);
+        let mut narrow = SecondOrderFilter::with_q(FilterType::BandPass, 20, 5.0).unwrap();
+        assert!(amplitude(&mut narrow, 10.0) < 0.2 * 5.0);
+    }
+
+    #[test]
+    fn test_second_order_filter_next() {
+        let mut low_pass = SecondOrderFilter::default();
+        let mut band_pass = SecondOrderFilter::new(FilterType::BandPass, 20).unwrap();
+        for _ in 0..20 {
+            assert_eq!(round(low_pass.next(&Bar::new().close(5.0))), 5.0);
+            assert_eq!(round(band_pass.next(5.0)), 0.0);
+        }
+
+        low_pass.reset();
+        let outputs: Vec<f64> = [5.0, 100.0, 50.0]
+            .iter()
+            .map(|&x| low_pass.next(x))
+            .collect();
+        low_pass.reset();
+        assert_eq!(low_pass.next(7.0), 7.0);
+        low_pass.reset();
+        for (&input, &output) in [5.0, 100.0, 50.0].iter().zip(&outputs) {
+            assert_eq!(low_pass.next(input), output);
+        }
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut history = [1.0, 2.0, 3.0];
+        shift(&mut history, 0.0);
+        assert_eq!(history, [0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_mama_new() {
+        assert!(MesaAdaptiveMovingAverage::new(0.5, 0.0).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(0.05, 0.5).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(1.5, 0.05).is_err());
+        assert!(MesaAdaptiveMovingAverage::new(f64::NAN, 0.05).is_err());
+        let mama = MesaAdaptiveMovingAverage::new(0.5, 0.5).unwrap();
+        assert_eq!((mama.fast_limit(), mama.slow_limit()), (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_mama_next() {
+        let mut mama = MesaAdaptiveMovingAverage::default();
+        for _ in 0..50 {
+            let (mama_value, fama_value) = mama.next(10.0).into();
+            assert_eq!((round(mama_value), round(fama_value)), (10.0, 10.0));
+        }
+
+        // the averages follow a trend, the FAMA lagging the MAMA
+        let mut mama = MesaAdaptiveMovingAverage::default();
+        let mut output = mama.next(&Bar::new().close(100));
+        for i in 1..100 {
+            output = mama.next(100.0 + i as f64);
+        }
+        assert!(mama.is_ready());
+        let (mama_value, fama_value) = output.into();
+        assert!(fama_value < mama_value && mama_value < 199.0);
+        assert!(mama_value > 190.0);
+    }
+
+    #[test]
+    fn test_mama_limits() {
+        // a constant smoothing factor is a plain exponential moving average
+        let mut mama = MesaAdaptiveMovingAverage::new(0.2, 0.2).unwrap();
+        mama.next(10.0);
+        assert_eq!(mama.next(20.0).mama, 12.0);
+        assert_eq!(
+            mama.next(20.0).fama,
+            0.9 * (0.9 * 10.0 + 0.1 * 12.0) + 0.1 * 13.6
+        );
+    }
+
+    #[test]
+    fn test_dominant_cycle_period() {
+        for period in [10.0, 20.0, 30.0] {
+            let mut cycle = DominantCycle::new();
+            let output = sine(period)
+                .take(300)
+                .map(|x| cycle.next(x))
+                .last()
+                .unwrap();
+            assert!((output.period - period).abs() < 0.1 * period);
+        }
+    }
+
+    #[test]
+    fn test_dominant_cycle_phase() {
+        let mut cycle = DominantCycle::new();
+        assert_eq!(cycle.bars_remaining(), WARMUP);
+        let outputs: Vec<_> = sine(20.0).take(300).map(|x| cycle.next(x)).collect();
+        assert!(cycle.is_ready());
+        assert!(outputs
+            .iter()
+            .all(|output| (-45.0..=315.0).contains(&output.phase)));
+        // the phase advances by about 18 degrees per bar
+        let steps: Vec<f64> = outputs[200..]
+            .windows(2)
+            .map(|pair| (pair[1].phase - pair[0].phase).rem_euclid(360.0))
+            .collect();
+        let mean = steps.iter().sum::<f64>() / steps.len() as f64;
+        assert!((mean - 18.0).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mama = MesaAdaptiveMovingAverage::default();
+        let mut cycle = DominantCycle::new();
+        let first = (mama.next(5.0), cycle.next(5.0));
+        for x in sine(15.0).take(40) {
+            mama.next(x);
+            cycle.next(x);
+        }
+        mama.reset();
+        cycle.reset();
+        assert_eq!((mama.next(5.0), cycle.next(5.0)), first);
+        assert!(!cycle.is_ready());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            MesaAdaptiveMovingAverage::default().to_string(),
+            "MAMA(0.5, 0.05)"
+        );
+        assert_eq!(DominantCycle::default().to_string(), "HT_DC");
+        assert_eq!(SuperSmoother::default().to_string(), "SSF(10)");
+        assert_eq!(RoofingFilter::default().to_string(), "ROOF(48, 10)");
+        assert_eq!(Decycler::default().to_string(), "DECYCLER(60)");
+        assert_eq!(SecondOrderFilter::default().to_string(), "LOWPASS(20)");
+        let band_pass = SecondOrderFilter::with_q(FilterType::BandPass, 30, 2.0).unwrap();
+        assert_eq!(band_pass.to_string(), "BANDPASS(30, 2)");
+    }
+}
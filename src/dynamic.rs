@@ -0,0 +1,200 @@
+//! Indicators with different outputs behind a single trait object.
+//!
+//! [IndicatorDyn] erases the output type of an indicator into an [OutputValue], so indicators
+//! returning numbers, flags or several values at once can be kept in one collection and driven
+//! from a single event loop. It is implemented for every indicator whose output converts into
+//! an [OutputValue], for any input type the indicator accepts.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::dynamic::{IndicatorDyn, OutputValue};
+//! use ta_panther::indicators::{BollingerBands, CrossAbove, SimpleMovingAverage};
+//!
+//! let mut indicators: Vec<Box<dyn IndicatorDyn<f64>>> = vec![
+//!     Box::new(SimpleMovingAverage::new(2).unwrap()),
+//!     Box::new(CrossAbove::new(3.0).unwrap()),
+//!     Box::new(BollingerBands::new(2, 2.0).unwrap()),
+//! ];
+//!
+//! for &price in [2.0, 4.0].iter() {
+//!     for indicator in indicators.iter_mut() {
+//!         indicator.next_value(price);
+//!     }
+//! }
+//! let outputs: Vec<OutputValue> = indicators.iter_mut().map(|i| i.next_value(6.0)).collect();
+//! assert_eq!(
+//!     outputs,
+//!     vec![
+//!         OutputValue::Float(5.0),
+//!         OutputValue::Bool(false),
+//!         OutputValue::Multi(vec![5.0, 7.0, 3.0]),
+//!     ]
+//! );
+//! ```
+
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::indicators::{
+    AverageDirectionalIndexOutput, BollingerBandsOutput, ChandelierExitOutput,
+    ConditionalMeanOutput, KalmanFilterOutput, KeltnerChannelOutput, MaxDrawdownOutput,
+    MovingAverageConvergenceDivergenceOutput, PercentagePriceOscillatorOutput,
+    RegressionChannelOutput, StochasticOscillatorOutput,
+};
+use crate::{Next, Reset};
+
+/// Output of an [IndicatorDyn].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OutputValue {
+    /// Output of indicators returning a single number, e.g. an SMA.
+    Float(f64),
+    /// Output of indicators returning a flag, e.g. a cross detector.
+    Bool(bool),
+    /// Output of indicators returning several numbers, in the order of the fields of their
+    /// output, e.g. `[average, upper, lower]` for Bollinger Bands.
+    Multi(Vec<f64>),
+}
+
+impl OutputValue {
+    /// Returns the number of a [Float](OutputValue::Float) output.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            OutputValue::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the flag of a [Bool](OutputValue::Bool) output.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            OutputValue::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<f64> for OutputValue {
+    fn from(value: f64) -> Self {
+        OutputValue::Float(value)
+    }
+}
+
+impl From<bool> for OutputValue {
+    fn from(value: bool) -> Self {
+        OutputValue::Bool(value)
+    }
+}
+
+macro_rules! multi_output {
+    ($($output:ident { $($field:ident),* }),* $(,)?) => {
+        $(impl From<$output> for OutputValue {
+            fn from(output: $output) -> Self {
+                OutputValue::Multi(vec![$(output.$field as f64),*])
+            }
+        })*
+    };
+}
+
+multi_output! {
+    AverageDirectionalIndexOutput { adx, plus_di, minus_di },
+    BollingerBandsOutput { average, upper, lower },
+    ChandelierExitOutput { long, short },
+    ConditionalMeanOutput { mean, count },
+    KalmanFilterOutput { value, velocity },
+    KeltnerChannelOutput { average, upper, lower },
+    MaxDrawdownOutput { drawdown, max_drawdown, duration },
+    MovingAverageConvergenceDivergenceOutput { macd, signal, histogram },
+    PercentagePriceOscillatorOutput { ppo, signal, histogram },
+    RegressionChannelOutput { average, upper, lower },
+    StochasticOscillatorOutput { k, d },
+}
+
+/// Object-safe indicator consuming inputs of type `I`, with the output erased into an
+/// [OutputValue].
+///
+/// `Box<dyn IndicatorDyn<f64>>` holds any indicator of `f64` inputs and
+/// `Box<dyn for<'a> IndicatorDyn<&'a DataItem>>` any indicator of [DataItem](crate::DataItem)s.
+/// Boxed indicators can still be reset and displayed like the indicators they hold.
+pub trait IndicatorDyn<I>: Reset + fmt::Display + Send {
+    /// Feeds the next input, returning the erased output.
+    fn next_value(&mut self, input: I) -> OutputValue;
+}
+
+impl<I, T> IndicatorDyn<I> for T
+where
+    T: Next<I> + Reset + fmt::Display + Send,
+    T::Output: Into<OutputValue>,
+{
+    fn next_value(&mut self, input: I) -> OutputValue {
+        self.next(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ChandelierExit, MaxDrawdown, MoneyFlowIndex, OnBalanceVolume, SimpleMovingAverage,
+    };
+    use crate::DataItem;
+    use alloc::boxed::Box;
+    use alloc::string::{String, ToString};
+
+    fn item(high: f64, low: f64, close: f64, volume: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_data_items() {
+        let mut indicators: Vec<Box<dyn for<'a> IndicatorDyn<&'a DataItem>>> = vec![
+            Box::new(SimpleMovingAverage::new(2).unwrap()),
+            Box::new(OnBalanceVolume::new()),
+            Box::new(MoneyFlowIndex::new(2).unwrap()),
+            Box::new(ChandelierExit::new(2, 1.0).unwrap()),
+        ];
+        let names: Vec<String> = indicators.iter().map(|i| i.to_string()).collect();
+        assert_eq!(names, vec!["SMA(2)", "OBV", "MFI(2)", "CE(2, 1)"]);
+
+        for indicator in indicators.iter_mut() {
+            indicator.next_value(&item(11.0, 9.0, 10.0, 100.0));
+        }
+        let outputs: Vec<OutputValue> = indicators
+            .iter_mut()
+            .map(|i| i.next_value(&item(13.0, 11.0, 12.0, 50.0)))
+            .collect();
+        assert_eq!(outputs[0], OutputValue::Float(11.0));
+        assert_eq!(outputs[1], OutputValue::Float(150.0));
+        assert!(outputs[2].as_f64().is_some());
+        assert!(matches!(outputs[3], OutputValue::Multi(ref values) if values.len() == 2));
+    }
+
+    #[test]
+    fn test_multi() {
+        let mut mdd: Box<dyn IndicatorDyn<f64>> = Box::new(MaxDrawdown::new());
+        mdd.next_value(8.0);
+        assert_eq!(
+            mdd.next_value(6.0),
+            OutputValue::Multi(vec![0.25, 0.25, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_accessors() {
+        assert_eq!(OutputValue::Float(1.5).as_f64(), Some(1.5));
+        assert_eq!(OutputValue::Float(1.5).as_bool(), None);
+        assert_eq!(OutputValue::Bool(true).as_bool(), Some(true));
+        assert_eq!(OutputValue::Multi(vec![1.0]).as_f64(), None);
+    }
+}
@@ -0,0 +1,513 @@
+//! Indicators of many symbols.
+//!
+//! An [Engine] computes the same set of indicators for every symbol of a market. Ticks of
+//! different symbols can be fed interleaved, each one tagged with its symbol, and the engine
+//! keeps the latest outputs of every symbol, so a screener can scan the whole market after every
+//! tick or batch of ticks.
+//!
+//! Batches of ticks passed to [update](Engine::update) are grouped by symbol and, with the
+//! `rayon` feature, the symbols are computed in parallel.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::engine::Engine;
+//!
+//! let mut engine = Engine::new()
+//!     .with_indicator("fast", "SMA", &[2.0])
+//!     .unwrap()
+//!     .with_indicator("slow", "SMA", &[3.0])
+//!     .unwrap();
+//!
+//! engine.update(&[
+//!     ("AAPL", 10.0),
+//!     ("MSFT", 30.0),
+//!     ("AAPL", 12.0),
+//!     ("MSFT", 27.0),
+//!     ("AAPL", 14.0),
+//!     ("MSFT", 24.0),
+//! ]);
+//!
+//! assert_eq!(engine.latest(&"AAPL"), Some(&[13.0, 12.0][..]));
+//! assert_eq!(engine.value(&"MSFT", "slow"), Some(27.0));
+//!
+//! // symbols whose fast average is above the slow one
+//! let rising: Vec<&&str> = engine
+//!     .iter()
+//!     .filter(|(_, outputs)| outputs[0] > outputs[1])
+//!     .map(|(symbol, _)| symbol)
+//!     .collect();
+//! assert_eq!(rising, vec![&"AAPL"]);
+//! ```
+//!
+//! # Worker
+//!
+//! A [Worker] runs an engine on its own thread. Any number of threads send ticks to it through
+//! a channel, and every subscriber receives the outputs of every tick, so live indicator values
+//! can be shared across a multithreaded application without locking the engine:
+//!
+//! ```
+//! use ta_panther::engine::{Engine, Worker};
+//!
+//! let engine = Engine::new().with_indicator("sma", "SMA", &[2.0]).unwrap();
+//! let worker = Worker::spawn(engine);
+//! let updates = worker.subscribe();
+//!
+//! let sender = worker.sender();
+//! std::thread::spawn(move || {
+//!     sender.send(("AAPL", 10.0)).unwrap();
+//!     sender.send(("AAPL", 12.0)).unwrap();
+//! });
+//!
+//! assert_eq!(updates.recv().unwrap().outputs, vec![10.0]);
+//! assert_eq!(updates.recv().unwrap().outputs, vec![11.0]);
+//!
+//! let engine = worker.join();
+//! assert_eq!(engine.value(&"AAPL", "sma"), Some(11.0));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::errors::{Result, TaError};
+use crate::factory::{self, NextF64};
+use crate::{Next, Reset};
+
+type Constructor = Box<dyn Fn() -> Box<dyn NextF64> + Send + Sync>;
+
+/// Indicators and latest outputs of a symbol.
+struct Entry {
+    indicators: Vec<Box<dyn NextF64>>,
+    latest: Option<Vec<f64>>,
+}
+
+impl Entry {
+    fn next(&mut self, input: f64) -> &[f64] {
+        let outputs = self.latest.get_or_insert_with(Vec::new);
+        outputs.clear();
+        outputs.extend(
+            self.indicators
+                .iter_mut()
+                .map(|indicator| indicator.next(input)),
+        );
+        outputs
+    }
+}
+
+/// Set of indicators computed for every symbol.
+///
+/// Indicators are added with a label, either by the name and parameters of the
+/// [factory] or by a custom constructor, and their outputs are ordered as they
+/// were added. Symbols are registered on their first tick, or in advance with
+/// [register](Engine::register).
+pub struct Engine<K> {
+    labels: Vec<String>,
+    constructors: Vec<Constructor>,
+    symbols: HashMap<K, usize>,
+    entries: Vec<Entry>,
+}
+
+impl<K: Eq + Hash + Clone> Engine<K> {
+    pub fn new() -> Self {
+        Self {
+            labels: Vec::new(),
+            constructors: Vec::new(),
+            symbols: HashMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds a built-in indicator by its name and parameters, see [factory::indicator].
+    ///
+    /// Returns an error if the label is already used, or the name or the parameters are invalid.
+    pub fn with_indicator(self, label: &str, name: &str, params: &[f64]) -> Result<Self> {
+        factory::indicator(name, params)?;
+        let (name, params) = (name.to_string(), params.to_vec());
+        self.with_constructor(label, move || {
+            factory::indicator(&name, &params).expect("parameters were validated")
+        })
+    }
+
+    /// Adds an indicator created by `constructor` for every symbol.
+    ///
+    /// Returns an error if the label is already used.
+    pub fn with_constructor<F>(mut self, label: &str, constructor: F) -> Result<Self>
+    where
+        F: Fn() -> Box<dyn NextF64> + Send + Sync + 'static,
+    {
+        if self.labels.iter().any(|existing| existing == label) {
+            return Err(TaError::InvalidParameter);
+        }
+        for entry in self.entries.iter_mut() {
+            entry.indicators.push(constructor());
+            if let Some(latest) = entry.latest.as_mut() {
+                latest.push(f64::NAN);
+            }
+        }
+        self.labels.push(label.to_string());
+        self.constructors.push(Box::new(constructor));
+        Ok(self)
+    }
+
+    /// Registers a symbol, creating its indicators. Registering a symbol again has no effect.
+    pub fn register(&mut self, symbol: K) {
+        self.index(symbol);
+    }
+
+    fn index(&mut self, symbol: K) -> usize {
+        let Self {
+            constructors,
+            symbols,
+            entries,
+            ..
+        } = self;
+        *symbols.entry(symbol).or_insert_with(|| {
+            entries.push(Entry {
+                indicators: constructors
+                    .iter()
+                    .map(|constructor| constructor())
+                    .collect(),
+                latest: None,
+            });
+            entries.len() - 1
+        })
+    }
+
+    /// Feeds a batch of ticks, tagged with their symbols.
+    ///
+    /// The ticks of every symbol are fed in the order they appear in the batch, but the
+    /// symbols are computed independently of each other, in parallel with the `rayon` feature.
+    /// The outputs are read with [latest](Engine::latest) or [iter](Engine::iter).
+    pub fn update(&mut self, ticks: &[(K, f64)]) {
+        let mut pending: Vec<Vec<f64>> = vec![Vec::new(); self.entries.len()];
+        for (symbol, input) in ticks {
+            let index = self.index(symbol.clone());
+            if index == pending.len() {
+                pending.push(Vec::new());
+            }
+            pending[index].push(*input);
+        }
+
+        let feed = |(entry, inputs): (&mut Entry, &Vec<f64>)| {
+            for &input in inputs {
+                entry.next(input);
+            }
+        };
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            self.entries.par_iter_mut().zip(&pending).for_each(feed);
+        }
+        #[cfg(not(feature = "rayon"))]
+        self.entries.iter_mut().zip(&pending).for_each(feed);
+    }
+
+    /// Returns the latest outputs of a symbol, in the order the indicators were added.
+    pub fn latest(&self, symbol: &K) -> Option<&[f64]> {
+        self.entry(symbol).and_then(|entry| entry.latest.as_deref())
+    }
+
+    /// Returns the latest output of the indicator with `label` for a symbol.
+    pub fn value(&self, symbol: &K, label: &str) -> Option<f64> {
+        let position = self.labels.iter().position(|existing| existing == label)?;
+        self.latest(symbol).map(|outputs| outputs[position])
+    }
+
+    fn entry(&self, symbol: &K) -> Option<&Entry> {
+        self.symbols.get(symbol).map(|&index| &self.entries[index])
+    }
+
+    /// Iterates over the symbols and their latest outputs.
+    ///
+    /// Symbols which have been registered, but have not received a tick yet, are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &[f64])> {
+        self.symbols.iter().filter_map(|(symbol, &index)| {
+            self.entries[index]
+                .latest
+                .as_deref()
+                .map(|outputs| (symbol, outputs))
+        })
+    }
+
+    /// Returns the labels of the indicators, in the order they were added.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Iterates over the registered symbols.
+    pub fn symbols(&self) -> impl Iterator<Item = &K> {
+        self.symbols.keys()
+    }
+
+    /// Returns the number of registered symbols.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no symbol is registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K: Eq + Hash + Clone> Next<(K, f64)> for Engine<K> {
+    type Output = Vec<f64>;
+
+    fn next(&mut self, (symbol, input): (K, f64)) -> Self::Output {
+        let index = self.index(symbol);
+        self.entries[index].next(input).to_vec()
+    }
+}
+
+impl<K> Reset for Engine<K> {
+    fn reset(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry
+                .indicators
+                .iter_mut()
+                .for_each(|indicator| indicator.reset());
+            entry.latest = None;
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> Default for Engine<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> fmt::Debug for Engine<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Engine")
+            .field("labels", &self.labels)
+            .field("symbols", &self.entries.len())
+            .finish()
+    }
+}
+
+/// Outputs of a tick processed by a [Worker].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update<K> {
+    pub symbol: K,
+    /// Outputs of the indicators, in the order they were added to the engine.
+    pub outputs: Vec<f64>,
+}
+
+type Subscribers<K> = Arc<Mutex<Vec<Sender<Update<K>>>>>;
+
+/// Engine running on its own thread, fed through a channel and publishing its outputs to
+/// subscribers.
+///
+/// The thread stops once the worker and all the senders returned by [sender](Worker::sender)
+/// have been dropped, or when [join](Worker::join) is called and the other senders are gone.
+pub struct Worker<K> {
+    labels: Vec<String>,
+    sender: Sender<(K, f64)>,
+    subscribers: Subscribers<K>,
+    handle: JoinHandle<Engine<K>>,
+}
+
+impl<K> Worker<K>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+{
+    /// Moves `engine` to a new thread processing the ticks sent to the worker.
+    pub fn spawn(mut engine: Engine<K>) -> Self {
+        let labels = engine.labels().to_vec();
+        let (sender, receiver) = mpsc::channel::<(K, f64)>();
+        let subscribers: Subscribers<K> = Arc::default();
+        let published = Arc::clone(&subscribers);
+        let handle = thread::spawn(move || {
+            for (symbol, input) in receiver {
+                let outputs = engine.next((symbol.clone(), input));
+                let update = Update { symbol, outputs };
+                let mut subscribers = match published.lock() {
+                    Ok(subscribers) => subscribers,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                subscribers.retain(|subscriber| subscriber.send(update.clone()).is_ok());
+            }
+            engine
+        });
+
+        Self {
+            labels,
+            sender,
+            subscribers,
+            handle,
+        }
+    }
+
+    /// Returns a sender of ticks, tagged with their symbols, to the worker.
+    pub fn sender(&self) -> Sender<(K, f64)> {
+        self.sender.clone()
+    }
+
+    /// Returns a receiver of the [Update]s of all the ticks processed from now on.
+    ///
+    /// Subscribers which have been dropped are removed on the next tick.
+    pub fn subscribe(&self) -> Receiver<Update<K>> {
+        let (sender, receiver) = mpsc::channel();
+        match self.subscribers.lock() {
+            Ok(mut subscribers) => subscribers.push(sender),
+            Err(poisoned) => poisoned.into_inner().push(sender),
+        }
+        receiver
+    }
+
+    /// Returns the labels of the indicators, in the order of the outputs of the updates.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Waits until all the senders are dropped and the pending ticks are processed, and returns
+    /// the engine.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an indicator panicked on the worker thread.
+    pub fn join(self) -> Engine<K> {
+        drop(self.sender);
+        match self.handle.join() {
+            Ok(engine) => engine,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl<K> fmt::Debug for Worker<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Worker")
+            .field("labels", &self.labels)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+
+    fn engine() -> Engine<&'static str> {
+        Engine::new()
+            .with_indicator("sma", "SMA", &[2.0])
+            .unwrap()
+            .with_indicator("ema", "ema", &[3.0])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_with_indicator() {
+        assert!(engine().with_indicator("sma", "RSI", &[14.0]).is_err());
+        assert!(engine().with_indicator("rsi", "NOPE", &[14.0]).is_err());
+        assert!(engine().with_indicator("rsi", "RSI", &[]).is_err());
+        assert_eq!(engine().labels(), &["sma", "ema"]);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut engine = engine();
+        assert_eq!(engine.next(("A", 4.0)), vec![4.0, 4.0]);
+        assert_eq!(engine.next(("B", 10.0)), vec![10.0, 10.0]);
+        assert_eq!(engine.next(("A", 6.0)), vec![5.0, 5.0]);
+        assert_eq!(engine.next(("A", 8.0)), vec![7.0, 6.5]);
+        assert_eq!(engine.latest(&"B"), Some(&[10.0, 10.0][..]));
+        assert_eq!(engine.value(&"A", "ema"), Some(6.5));
+        assert_eq!(engine.value(&"A", "rsi"), None);
+        assert_eq!(engine.value(&"C", "sma"), None);
+        assert_eq!(engine.len(), 2);
+    }
+
+    #[test]
+    fn test_update() {
+        let ticks = [
+            ("A", 4.0),
+            ("B", 10.0),
+            ("A", 6.0),
+            ("C", 1.0),
+            ("B", 20.0),
+            ("A", 8.0),
+        ];
+        let mut batched = engine();
+        batched.update(&ticks);
+        let mut sequential = engine();
+        for tick in ticks {
+            sequential.next(tick);
+        }
+        for symbol in ["A", "B", "C"] {
+            assert_eq!(batched.latest(&symbol), sequential.latest(&symbol));
+        }
+        assert_eq!(batched.latest(&"B"), Some(&[15.0, 15.0][..]));
+    }
+
+    #[test]
+    fn test_register() {
+        let mut engine = engine();
+        engine.register("A");
+        engine.register("A");
+        assert_eq!(engine.len(), 1);
+        assert_eq!(engine.latest(&"A"), None);
+        assert_eq!(engine.iter().count(), 0);
+        engine.next(("A", 1.0));
+        assert_eq!(engine.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_with_constructor() {
+        let mut engine = Engine::new()
+            .with_constructor("sma", || Box::new(SimpleMovingAverage::new(2).unwrap()))
+            .unwrap();
+        engine.next(("A", 2.0));
+        let mut engine = engine
+            .with_constructor("sma4", || Box::new(SimpleMovingAverage::new(4).unwrap()))
+            .unwrap();
+        assert!(engine.latest(&"A").unwrap()[1].is_nan());
+        assert_eq!(engine.next(("A", 4.0)), vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut engine = engine();
+        engine.next(("A", 4.0));
+        engine.reset();
+        assert_eq!(engine.latest(&"A"), None);
+        assert_eq!(engine.next(("A", 8.0)), vec![8.0, 8.0]);
+    }
+
+    #[test]
+    fn test_worker() {
+        let worker = Worker::spawn(engine());
+        assert_eq!(worker.labels(), &["sma", "ema"]);
+        let first = worker.subscribe();
+        let second = worker.subscribe();
+        drop(worker.subscribe());
+
+        let senders: Vec<_> = ["A", "B"]
+            .into_iter()
+            .map(|symbol| {
+                let sender = worker.sender();
+                thread::spawn(move || {
+                    for input in [4.0, 6.0, 8.0] {
+                        sender.send((symbol, input)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for sender in senders {
+            sender.join().unwrap();
+        }
+
+        let engine = worker.join();
+        let updates: Vec<Update<&str>> = first.iter().collect();
+        assert_eq!(updates.len(), 6);
+        assert_eq!(second.iter().collect::<Vec<_>>(), updates);
+        let last_a = updates.iter().rev().find(|update| update.symbol == "A");
+        assert_eq!(last_a.unwrap().outputs, vec![7.0, 6.5]);
+        assert_eq!(engine.latest(&"B"), Some(&[7.0, 6.5][..]));
+    }
+}
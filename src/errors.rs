@@ -1,31 +1,39 @@
-use std::error::Error;
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-pub type Result<T> = std::result::Result<T, TaError>;
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub type Result<T> = core::result::Result<T, TaError>;
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TaError {
     InvalidParameter,
     DataItemIncomplete,
     DataItemInvalid,
+    InvalidInput,
+    InvalidState,
 }
 
 impl Display for TaError {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         match *self {
             TaError::InvalidParameter => write!(f, "invalid parameter"),
             TaError::DataItemIncomplete => write!(f, "data item is incomplete"),
             TaError::DataItemInvalid => write!(f, "data item is invalid"),
+            TaError::InvalidInput => write!(f, "invalid input"),
+            TaError::InvalidState => write!(f, "invalid state"),
         }
     }
 }
 
-impl Error for TaError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
+#[cfg(feature = "std")]
+impl std::error::Error for TaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
             TaError::InvalidParameter => None,
             TaError::DataItemIncomplete => None,
             TaError::DataItemInvalid => None,
+            TaError::InvalidInput => None,
+            TaError::InvalidState => None,
         }
     }
 }
@@ -0,0 +1,696 @@
+//! Streaming pipelines of indicators parsed from formulas.
+//!
+//! Available with the `expr` feature.
+//!
+//! An [Expression] is parsed from a formula such as
+//! `CROSS_ABOVE(EMA(CLOSE, 9), SMA(CLOSE, 21))` and is fed with data items like any other
+//! indicator, which makes it a small screener language on top of the built-in indicators.
+//!
+//! # Syntax
+//!
+//! * Numbers, e.g. `70` or `0.5`.
+//! * Series of the data item: `OPEN`, `HIGH`, `LOW`, `CLOSE` and `VOLUME`.
+//! * Indicators of the [IndicatorFactory], applied to an expression followed by their numeric
+//!   parameters, e.g. `RSI(CLOSE, 14)` or `SMA(HIGH - LOW, 10)`.
+//! * `CROSS_ABOVE(a, b)` and `CROSS_BELOW(a, b)`, true when _a_ crosses above (below) _b_.
+//! * `ABS(a)`.
+//! * Arithmetic `+`, `-`, `*`, `/`, comparisons `>`, `>=`, `<`, `<=`, `==`, `!=` and the logical
+//!   operators `AND` and `OR`, with the usual precedence, and parentheses.
+//!
+//! Names are case-insensitive. Conditions evaluate to 1 when they are true and to 0 otherwise,
+//! and any number except 0 counts as true. All the indicators of an expression are fed on every
+//! input, even when `AND` or `OR` could skip one side, so they never miss an input.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::expr::Expression;
+//! use ta_panther::{DataItem, Next};
+//!
+//! let formula = "CROSS_ABOVE(CLOSE, SMA(CLOSE, 3)) AND VOLUME > 1000";
+//! let mut signal = Expression::parse(formula).unwrap();
+//!
+//! let bars = [(10.0, 1500.0), (9.0, 1500.0), (8.0, 1500.0), (12.0, 2000.0), (13.0, 500.0)];
+//! let outputs: Vec<f64> = bars
+//!     .iter()
+//!     .map(|&(close, volume)| {
+//!         let item = DataItem::builder()
+//!             .open(close)
+//!             .high(close)
+//!             .low(close)
+//!             .close(close)
+//!             .volume(volume)
+//!             .build()
+//!             .unwrap();
+//!         signal.next(&item)
+//!     })
+//!     .collect();
+//! assert_eq!(outputs, vec![0.0, 0.0, 0.0, 1.0, 0.0]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::factory::{IndicatorFactory, NextF64};
+use crate::indicators::{CrossAbove, CrossBelow};
+use crate::{Close, High, Low, Next, Open, Reset, Volume};
+
+/// Error returned when a formula can not be parsed into an [Expression].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    /// The formula is malformed at the given byte offset.
+    Syntax { position: usize, message: String },
+    /// The formula refers to an unknown series or function.
+    UnknownName(String),
+    /// A function is called with a wrong number of arguments or invalid parameters.
+    InvalidArguments(String),
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprError::Syntax { position, message } => {
+                write!(f, "syntax error at {}: {}", position, message)
+            }
+            ExprError::UnknownName(name) => write!(f, "unknown name `{}`", name),
+            ExprError::InvalidArguments(name) => write!(f, "invalid arguments of `{}`", name),
+        }
+    }
+}
+
+impl Error for ExprError {}
+
+/// Streaming pipeline of indicators parsed from a formula.
+///
+/// See the [module documentation](self) for the syntax. It displays as the formula it was
+/// parsed from.
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+impl Expression {
+    /// Parses a formula using the built-in indicators.
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        Self::parse_with(source, IndicatorFactory::builtin())
+    }
+
+    /// Parses a formula using the indicators of `factory`, which may include custom ones.
+    pub fn parse_with(source: &str, factory: &IndicatorFactory) -> Result<Self, ExprError> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            end: source.len(),
+            factory,
+        };
+        let root = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(Self {
+                source: source.trim().to_string(),
+                root,
+            }),
+            Some(_) => Err(parser.error("expected end of formula")),
+        }
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExprError;
+
+    fn from_str(s: &str) -> Result<Self, ExprError> {
+        Self::parse(s)
+    }
+}
+
+impl<T: Open + High + Low + Close + Volume> Next<&T> for Expression {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.root.eval(input)
+    }
+}
+
+impl Reset for Expression {
+    fn reset(&mut self) {
+        self.root.reset();
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl fmt::Debug for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Expression")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Series {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+impl Operator {
+    fn apply(self, a: f64, b: f64) -> f64 {
+        let truth = |condition: bool| if condition { 1.0 } else { 0.0 };
+        match self {
+            Operator::Add => a + b,
+            Operator::Sub => a - b,
+            Operator::Mul => a * b,
+            Operator::Div => a / b,
+            Operator::Gt => truth(a > b),
+            Operator::Ge => truth(a >= b),
+            Operator::Lt => truth(a < b),
+            Operator::Le => truth(a <= b),
+            Operator::Eq => truth(a == b),
+            Operator::Ne => truth(a != b),
+            Operator::And => truth(a != 0.0 && b != 0.0),
+            Operator::Or => truth(a != 0.0 || b != 0.0),
+        }
+    }
+}
+
+enum Node {
+    Number(f64),
+    Series(Series),
+    Neg(Box<Node>),
+    Abs(Box<Node>),
+    Binary(Operator, Box<Node>, Box<Node>),
+    Indicator(Box<dyn NextF64>, Box<Node>),
+    CrossAbove(CrossAbove, Box<Node>, Box<Node>),
+    CrossBelow(CrossBelow, Box<Node>, Box<Node>),
+}
+
+impl Node {
+    fn eval<T: Open + High + Low + Close + Volume>(&mut self, item: &T) -> f64 {
+        match self {
+            Node::Number(value) => *value,
+            Node::Series(Series::Open) => item.open(),
+            Node::Series(Series::High) => item.high(),
+            Node::Series(Series::Low) => item.low(),
+            Node::Series(Series::Close) => item.close(),
+            Node::Series(Series::Volume) => item.volume(),
+            Node::Neg(node) => -node.eval(item),
+            Node::Abs(node) => node.eval(item).abs(),
+            Node::Binary(operator, a, b) => {
+                // both sides are evaluated, so the indicators of each side see every input
+                let a = a.eval(item);
+                let b = b.eval(item);
+                operator.apply(a, b)
+            }
+            Node::Indicator(indicator, node) => indicator.next(node.eval(item)),
+            Node::CrossAbove(cross, a, b) => {
+                let spread = a.eval(item) - b.eval(item);
+                if cross.next(spread) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Node::CrossBelow(cross, a, b) => {
+                let spread = a.eval(item) - b.eval(item);
+                if cross.next(spread) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Node::Number(_) | Node::Series(_) => {}
+            Node::Neg(node) | Node::Abs(node) => node.reset(),
+            Node::Binary(_, a, b) => {
+                a.reset();
+                b.reset();
+            }
+            Node::Indicator(indicator, node) => {
+                indicator.reset();
+                node.reset();
+            }
+            Node::CrossAbove(cross, a, b) => {
+                cross.reset();
+                a.reset();
+                b.reset();
+            }
+            Node::CrossBelow(cross, a, b) => {
+                cross.reset();
+                a.reset();
+                b.reset();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 13] = [
+    ">=", "<=", "==", "!=", ">", "<", "+", "-", "*", "/", "(", ")", ",",
+];
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < source.len() {
+        let rest = &source[position..];
+        let c = rest.chars().next().unwrap_or_default();
+        if c.is_whitespace() {
+            position += c.len_utf8();
+        } else if c.is_ascii_digit() || c == '.' {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let number = rest[..len].parse().map_err(|_| ExprError::Syntax {
+                position,
+                message: format!("invalid number `{}`", &rest[..len]),
+            })?;
+            tokens.push((position, Token::Number(number)));
+            position += len;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push((position, Token::Name(rest[..len].to_uppercase())));
+            position += len;
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(*symbol)) {
+            tokens.push((position, Token::Symbol(symbol)));
+            position += symbol.len();
+        } else {
+            return Err(ExprError::Syntax {
+                position,
+                message: format!("unexpected character `{}`", c),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    end: usize,
+    factory: &'a IndicatorFactory,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn error(&self, message: &str) -> ExprError {
+        ExprError::Syntax {
+            position: self
+                .tokens
+                .get(self.position)
+                .map_or(self.end, |&(position, _)| position),
+            message: message.to_string(),
+        }
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        match self.peek() {
+            Some(Token::Symbol(s)) if *s == symbol => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), ExprError> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", symbol)))
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Name(name)) if name == keyword => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            node = Node::Binary(Operator::Or, Box::new(node), Box::new(self.parse_and()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_comparison()?;
+        while self.eat_keyword("AND") {
+            node = Node::Binary(
+                Operator::And,
+                Box::new(node),
+                Box::new(self.parse_comparison()?),
+            );
+        }
+        Ok(node)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Node, ExprError> {
+        let node = self.parse_sum()?;
+        let operator = match self.peek() {
+            Some(Token::Symbol(">")) => Operator::Gt,
+            Some(Token::Symbol(">=")) => Operator::Ge,
+            Some(Token::Symbol("<")) => Operator::Lt,
+            Some(Token::Symbol("<=")) => Operator::Le,
+            Some(Token::Symbol("==")) => Operator::Eq,
+            Some(Token::Symbol("!=")) => Operator::Ne,
+            _ => return Ok(node),
+        };
+        self.position += 1;
+        Ok(Node::Binary(
+            operator,
+            Box::new(node),
+            Box::new(self.parse_sum()?),
+        ))
+    }
+
+    fn parse_sum(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_product()?;
+        loop {
+            let operator = if self.eat_symbol("+") {
+                Operator::Add
+            } else if self.eat_symbol("-") {
+                Operator::Sub
+            } else {
+                return Ok(node);
+            };
+            node = Node::Binary(operator, Box::new(node), Box::new(self.parse_product()?));
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.parse_unary()?;
+        loop {
+            let operator = if self.eat_symbol("*") {
+                Operator::Mul
+            } else if self.eat_symbol("/") {
+                Operator::Div
+            } else {
+                return Ok(node);
+            };
+            node = Node::Binary(operator, Box::new(node), Box::new(self.parse_unary()?));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, ExprError> {
+        if self.eat_symbol("-") {
+            return Ok(match self.parse_unary()? {
+                Node::Number(value) => Node::Number(-value),
+                node => Node::Neg(Box::new(node)),
+            });
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, ExprError> {
+        if self.eat_symbol("(") {
+            let node = self.parse_or()?;
+            self.expect_symbol(")")?;
+            return Ok(node);
+        }
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.position += 1;
+                Ok(Node::Number(value))
+            }
+            Some(Token::Name(name)) => {
+                self.position += 1;
+                if self.eat_symbol("(") {
+                    let args = self.parse_args()?;
+                    self.call(name, args)
+                } else {
+                    series(&name)
+                        .map(Node::Series)
+                        .ok_or(ExprError::UnknownName(name))
+                }
+            }
+            _ => Err(self.error("expected a number, a series or a function")),
+        }
+    }
+
+    fn parse_args(&mut self) -> Result<Vec<Node>, ExprError> {
+        let mut args = Vec::new();
+        if self.eat_symbol(")") {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_or()?);
+            if self.eat_symbol(")") {
+                return Ok(args);
+            }
+            self.expect_symbol(",")?;
+        }
+    }
+
+    fn call(&mut self, name: String, args: Vec<Node>) -> Result<Node, ExprError> {
+        let mut args = args.into_iter();
+        let (first, second, rest) = (args.next(), args.next(), args.next());
+        match (name.as_str(), first, second, rest) {
+            ("ABS", Some(a), None, None) => Ok(Node::Abs(Box::new(a))),
+            ("CROSS_ABOVE", Some(a), Some(b), None) => Ok(Node::CrossAbove(
+                CrossAbove::default(),
+                Box::new(a),
+                Box::new(b),
+            )),
+            ("CROSS_BELOW", Some(a), Some(b), None) => Ok(Node::CrossBelow(
+                CrossBelow::default(),
+                Box::new(a),
+                Box::new(b),
+            )),
+            ("ABS", ..) | ("CROSS_ABOVE", ..) | ("CROSS_BELOW", ..) => {
+                Err(ExprError::InvalidArguments(name))
+            }
+            (_, source, second, rest) => {
+                if !self.factory.names().contains(&name.as_str()) {
+                    return Err(ExprError::UnknownName(name));
+                }
+                let source = source.ok_or_else(|| ExprError::InvalidArguments(name.clone()))?;
+                let params = second
+                    .into_iter()
+                    .chain(rest)
+                    .chain(args)
+                    .map(|node| match node {
+                        Node::Number(value) => Ok(value),
+                        _ => Err(ExprError::InvalidArguments(name.clone())),
+                    })
+                    .collect::<Result<Vec<f64>, ExprError>>()?;
+                let indicator = self
+                    .factory
+                    .create(&name, &params)
+                    .map_err(|_| ExprError::InvalidArguments(name.clone()))?;
+                Ok(Node::Indicator(indicator, Box::new(source)))
+            }
+        }
+    }
+}
+
+fn series(name: &str) -> Option<Series> {
+    match name {
+        "OPEN" => Some(Series::Open),
+        "HIGH" => Some(Series::High),
+        "LOW" => Some(Series::Low),
+        "CLOSE" => Some(Series::Close),
+        "VOLUME" => Some(Series::Volume),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::DataItem;
+
+    fn item(high: f64, low: f64, close: f64, volume: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    fn eval(source: &str, closes: &[f64]) -> Vec<f64> {
+        let mut expr = Expression::parse(source).unwrap();
+        closes
+            .iter()
+            .map(|&close| expr.next(&item(close, close, close, 0.0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(eval("1 + 2 * 3", &[0.0]), vec![7.0]);
+        assert_eq!(eval("(1 + 2) * 3", &[0.0]), vec![9.0]);
+        assert_eq!(eval("-CLOSE / 4 - -1", &[2.0]), vec![0.5]);
+        assert_eq!(eval("abs(1 - close)", &[3.0]), vec![2.0]);
+
+        let mut range = Expression::parse("HIGH - LOW").unwrap();
+        assert_eq!(range.next(&item(12.0, 9.0, 10.0, 0.0)), 3.0);
+    }
+
+    #[test]
+    fn test_conditions() {
+        assert_eq!(eval("CLOSE > 2", &[1.0, 2.0, 3.0]), vec![0.0, 0.0, 1.0]);
+        assert_eq!(eval("CLOSE >= 2", &[1.0, 2.0, 3.0]), vec![0.0, 1.0, 1.0]);
+        assert_eq!(
+            eval("CLOSE < 2 OR CLOSE == 3", &[1.0, 2.0, 3.0]),
+            vec![1.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            eval("CLOSE != 1 AND CLOSE <= 2", &[1.0, 2.0, 3.0]),
+            vec![0.0, 1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_indicators() {
+        let closes = [10.0, 12.0, 11.0, 14.0, 9.0];
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        let expected: Vec<f64> = closes
+            .iter()
+            .map(|&close| ema.next(close) - sma.next(close * 2.0))
+            .collect();
+        assert_eq!(eval("ema(CLOSE, 3) - SMA(CLOSE * 2, 2)", &closes), expected);
+    }
+
+    #[test]
+    fn test_cross() {
+        let closes = [10.0, 8.0, 12.0, 13.0, 9.0];
+        assert_eq!(
+            eval("CROSS_ABOVE(CLOSE, SMA(CLOSE, 2))", &closes),
+            vec![0.0, 0.0, 1.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            eval("CROSS_BELOW(CLOSE, SMA(CLOSE, 2))", &closes),
+            vec![0.0, 1.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_indicators_see_every_input() {
+        // the right side of AND is evaluated even when the left side is false
+        let closes = [1.0, 2.0, 3.0];
+        assert_eq!(
+            eval("CLOSE > 2 AND SMA(CLOSE, 3) == 2", &closes),
+            vec![0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut expr = Expression::parse("SMA(CLOSE, 2)").unwrap();
+        expr.next(&item(4.0, 4.0, 4.0, 0.0));
+        expr.next(&item(8.0, 8.0, 8.0, 0.0));
+
+        expr.reset();
+        assert_eq!(expr.next(&item(2.0, 2.0, 2.0, 0.0)), 2.0);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Expression::parse("1 +").unwrap_err(),
+            ExprError::Syntax {
+                position: 3,
+                message: "expected a number, a series or a function".to_string()
+            }
+        );
+        assert_eq!(
+            Expression::parse("CLOSE $ 1").unwrap_err(),
+            ExprError::Syntax {
+                position: 6,
+                message: "unexpected character `$`".to_string()
+            }
+        );
+        assert!(matches!(
+            Expression::parse("(CLOSE"),
+            Err(ExprError::Syntax { .. })
+        ));
+        assert!(matches!(
+            Expression::parse("CLOSE CLOSE"),
+            Err(ExprError::Syntax { .. })
+        ));
+        assert_eq!(
+            Expression::parse("PRICE").unwrap_err(),
+            ExprError::UnknownName("PRICE".to_string())
+        );
+        assert_eq!(
+            Expression::parse("NOPE(CLOSE, 3)").unwrap_err(),
+            ExprError::UnknownName("NOPE".to_string())
+        );
+        for source in ["SMA(CLOSE)", "SMA(CLOSE, 0)", "SMA(CLOSE, CLOSE)", "SMA()"] {
+            assert_eq!(
+                Expression::parse(source).unwrap_err(),
+                ExprError::InvalidArguments("SMA".to_string())
+            );
+        }
+        assert_eq!(
+            Expression::parse("CROSS_ABOVE(CLOSE)").unwrap_err(),
+            ExprError::InvalidArguments("CROSS_ABOVE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let mut factory = IndicatorFactory::empty();
+        factory.register("DOUBLE_SMA", |params| {
+            let [period] = crate::factory::periods(params)?;
+            Ok(Box::new(SimpleMovingAverage::new(period * 2)?))
+        });
+        assert!(Expression::parse_with("DOUBLE_SMA(CLOSE, 1)", &factory).is_ok());
+        assert!(Expression::parse_with("SMA(CLOSE, 1)", &factory).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let expr: Expression = " RSI(CLOSE, 14) > 70 ".parse().unwrap();
+        assert_eq!(expr.to_string(), "RSI(CLOSE, 14) > 70");
+    }
+}
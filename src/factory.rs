@@ -0,0 +1,642 @@
+//! Construction of indicators by name.
+//!
+//! The [IndicatorFactory] creates any built-in indicator with a scalar output from its
+//! abbreviation and a list of numeric parameters, so indicators can be chosen in configuration
+//! files or scripts without matching on names in user code. The created indicators are boxed
+//! as [NextF64] and keep their usual [Display](fmt::Display) output, e.g. `RSI(14)`.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::factory::indicator;
+//! use ta_panther::Next;
+//!
+//! let mut sma = indicator("sma", &[2.0]).unwrap();
+//! assert_eq!(sma.next(4.0), 4.0);
+//! assert_eq!(sma.next(8.0), 6.0);
+//! assert_eq!(sma.to_string(), "SMA(2)");
+//!
+//! assert!(indicator("SMA", &[]).is_err());
+//! assert!(indicator("NOPE", &[14.0]).is_err());
+//! ```
+//!
+//! # Configuration files
+//!
+//! An [IndicatorConfig] declares an indicator by its name and parameters, independently of the
+//! serialized state of the indicator, so strategy configuration files can list the indicators
+//! they use. Indicators created with [from_config] return their configuration with
+//! [to_config](ConfiguredIndicator::to_config).
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! use ta_panther::factory::{from_config, IndicatorConfig};
+//! use ta_panther::Next;
+//!
+//! let json = r#"[{"name": "ema", "params": [3]}, {"name": "RETURNS"}]"#;
+//! let configs: Vec<IndicatorConfig> = serde_json::from_str(json).unwrap();
+//!
+//! let mut ema = from_config(&configs[0]).unwrap();
+//! assert_eq!(ema.next(2.0), 2.0);
+//! assert_eq!(ema.next(5.0), 3.5);
+//! assert_eq!(ema.to_config(), IndicatorConfig::new("ema", &[3.0]));
+//! assert_eq!(from_config(&configs[1]).unwrap().to_string(), "RETURNS");
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::errors::{Result, TaError};
+#[cfg(feature = "plugin")]
+use crate::plugin::Plugin;
+use crate::{FromParams, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Indicator consuming `f64` inputs and returning `f64` outputs, which can be boxed.
+///
+/// Implemented for every such indicator which also implements [Reset] and `Display`.
+pub trait NextF64: Next<f64, Output = f64> + Reset + fmt::Display + Send {}
+
+impl<T> NextF64 for T where T: Next<f64, Output = f64> + Reset + fmt::Display + Send {}
+
+type Constructor = Box<dyn Fn(&[f64]) -> Result<Box<dyn NextF64>> + Send + Sync>;
+
+/// Constructors of indicators by name.
+///
+/// The default factory knows all the built-in indicators accepting and returning `f64`, named
+/// after their abbreviation (e.g. `SMA`, `RSI`, `KAMA`). Custom indicators can be added with
+/// [register](IndicatorFactory::register).
+pub struct IndicatorFactory {
+    constructors: HashMap<String, Constructor>,
+}
+
+impl IndicatorFactory {
+    /// Creates a factory without any indicators.
+    pub fn empty() -> Self {
+        Self {
+            constructors: HashMap::new(),
+        }
+    }
+
+    /// Registers a constructor of an indicator under `name`, replacing a previous one.
+    pub fn register<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&[f64]) -> Result<Box<dyn NextF64>> + Send + Sync + 'static,
+    {
+        self.constructors
+            .insert(name.to_uppercase(), Box::new(constructor));
+    }
+
+    /// Registers an indicator type under its [FromParams::NAME].
+    pub fn register_type<T>(&mut self)
+    where
+        T: FromParams + NextF64 + 'static,
+    {
+        self.register(T::NAME, |params| Ok(Box::new(T::from_params(params)?)));
+    }
+
+    /// Registers all the indicators of a plugin under their names.
+    ///
+    /// Available with the `plugin` feature.
+    #[cfg(feature = "plugin")]
+    pub fn register_plugin(&mut self, plugin: &Plugin) {
+        for name in plugin.names() {
+            let plugin = plugin.clone();
+            let indicator = name.to_string();
+            self.register(name, move |params| {
+                Ok(Box::new(plugin.create(&indicator, params)?))
+            });
+        }
+    }
+
+    /// Creates an indicator by its case-insensitive name.
+    ///
+    /// Returns an error if the name is unknown or the parameters are invalid.
+    pub fn create(&self, name: &str, params: &[f64]) -> Result<Box<dyn NextF64>> {
+        match self.constructors.get(&name.to_uppercase()) {
+            Some(constructor) => constructor(params),
+            None => Err(TaError::InvalidParameter),
+        }
+    }
+
+    /// Creates an indicator declared by a configuration, which it returns with
+    /// [to_config](ConfiguredIndicator::to_config).
+    ///
+    /// Returns an error if the name is unknown or the parameters are invalid.
+    pub fn from_config(&self, config: &IndicatorConfig) -> Result<ConfiguredIndicator> {
+        Ok(ConfiguredIndicator {
+            indicator: self.create(&config.name, &config.params)?,
+            config: config.clone(),
+        })
+    }
+
+    /// Returns the default factory, which is built once.
+    pub(crate) fn builtin() -> &'static Self {
+        static FACTORY: OnceLock<IndicatorFactory> = OnceLock::new();
+        FACTORY.get_or_init(IndicatorFactory::default)
+    }
+
+    /// Returns the sorted names of all registered indicators.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.constructors.keys().map(|name| name.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl Default for IndicatorFactory {
+    fn default() -> Self {
+        let mut factory = Self::empty();
+        register_scalar_indicators!(factory);
+        factory
+    }
+}
+
+impl fmt::Debug for IndicatorFactory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IndicatorFactory")
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+/// Creates a built-in indicator by its case-insensitive name, e.g. `indicator("RSI", &[14.0])`.
+///
+/// Shorthand for [IndicatorFactory::create] on a default factory, which is built once.
+pub fn indicator(name: &str, params: &[f64]) -> Result<Box<dyn NextF64>> {
+    IndicatorFactory::builtin().create(name, params)
+}
+
+/// Creates a built-in indicator declared by a configuration.
+///
+/// Shorthand for [IndicatorFactory::from_config] on a default factory, which is built once.
+pub fn from_config(config: &IndicatorConfig) -> Result<ConfiguredIndicator> {
+    IndicatorFactory::builtin().from_config(config)
+}
+
+/// Name and parameters of an indicator created by an [IndicatorFactory], e.g. read from a
+/// configuration file.
+///
+/// The parameters can be omitted for indicators without any.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IndicatorConfig {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub params: Vec<f64>,
+}
+
+impl IndicatorConfig {
+    pub fn new(name: &str, params: &[f64]) -> Self {
+        Self {
+            name: name.to_string(),
+            params: params.to_vec(),
+        }
+    }
+}
+
+/// Indicator created from an [IndicatorConfig], which it keeps.
+pub struct ConfiguredIndicator {
+    config: IndicatorConfig,
+    indicator: Box<dyn NextF64>,
+}
+
+impl ConfiguredIndicator {
+    /// Returns the configuration the indicator was created from.
+    pub fn to_config(&self) -> IndicatorConfig {
+        self.config.clone()
+    }
+
+    /// Returns the created indicator.
+    pub fn into_inner(self) -> Box<dyn NextF64> {
+        self.indicator
+    }
+}
+
+impl Next<f64> for ConfiguredIndicator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.indicator.next(input)
+    }
+}
+
+impl Reset for ConfiguredIndicator {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl fmt::Display for ConfiguredIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+impl fmt::Debug for ConfiguredIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConfiguredIndicator")
+            .field("config", &self.config)
+            .field("indicator", &self.indicator.to_string())
+            .finish()
+    }
+}
+
+/// Registers the built-in indicators with `f64` outputs. Shared with the registry of the
+/// server, which boxes them as a different trait object.
+macro_rules! register_scalar_indicators {
+    ($registry:ident) => {{
+        use $crate::factory::{integer, numbers, periods};
+        use $crate::indicators::*;
+        use $crate::performance::{CalmarRatio, SharpeRatio, SortinoRatio};
+        use $crate::returns::{CumulativeReturns, LogReturns, SimpleReturns};
+        use $crate::volatility::HistoricalVolatility;
+
+        $crate::factory::register_periodic! {
+            $registry,
+            "SMA" => SimpleMovingAverage,
+            "EMA" => ExponentialMovingAverage,
+            "WMA" => WeightedMovingAverage,
+            "TMA" => TriangularMovingAverage,
+            "SWMA" => SineWeightedMovingAverage,
+            "HMA" => HullMovingAverage,
+            "ZLEMA" => ZeroLagExponentialMovingAverage,
+            "RMA" => WildersMovingAverage,
+            "RSI" => RelativeStrengthIndex,
+            "SD" => StandardDeviation,
+            "MAD" => MeanAbsoluteDeviation,
+            "MAX" => Maximum,
+            "MIN" => Minimum,
+            "HHV" => HighestHighValue,
+            "LLV" => LowestLowValue,
+            "ROC" => RateOfChange,
+            "ROCR" => RateOfChangeRatio,
+            "MOM" => Momentum,
+            "LAG" => Lag,
+            "ER" => EfficiencyRatio,
+            "ATR" => AverageTrueRange,
+            "FAST_STOCH" => FastStochastic,
+            "STDERR" => StandardError,
+            "FORECAST" => LinearRegressionPrediction,
+            "FOSC" => ForecastOscillator,
+            "EXP_FORECAST" => ExponentialRegressionForecast,
+            "MEDIAN" => RollingMedian,
+            "SKEW" => Skewness,
+            "KURT" => Kurtosis,
+            "ZSCORE" => ZScore,
+            "GM" => GeometricMean,
+            "HM" => HarmonicMean,
+            "MDU" => RollingMaxDrawup,
+            "LSE" => RollingLogSumExp,
+            "UI" => UlcerIndex,
+            "UPI" => UlcerPerformanceIndex,
+        }
+
+        $registry.register("PERCENTILE", |params| {
+            let [period, percentile] = numbers(params)?;
+            Ok(Box::new(RollingPercentile::new(
+                integer(period)?,
+                percentile,
+            )?))
+        });
+        $registry.register("SLOW_STOCH", |params| {
+            let [stochastic, ema] = periods(params)?;
+            Ok(Box::new(SlowStochastic::new(stochastic, ema)?))
+        });
+        $registry.register("POLY_FORECAST", |params| {
+            let [period, degree] = periods(params)?;
+            Ok(Box::new(PolynomialRegressionForecast::new(period, degree)?))
+        });
+        $registry.register("ENTROPY", |params| {
+            let [period, bins] = periods(params)?;
+            Ok(Box::new(ShannonEntropy::new(period, bins)?))
+        });
+        $registry.register("KAMA", |params| {
+            let [period, fast, slow] = periods(params)?;
+            Ok(Box::new(KaufmanAdaptiveMovingAverage::new(
+                period, fast, slow,
+            )?))
+        });
+        $registry.register("APEN", |params| {
+            let [period, m, tolerance] = numbers(params)?;
+            Ok(Box::new(ApproximateEntropy::new(
+                integer(period)?,
+                integer(m)?,
+                tolerance,
+            )?))
+        });
+        $registry.register("ACF", |params| {
+            let [lag, period] = periods(params)?;
+            Ok(Box::new(Autocorrelation::new(lag, period)?))
+        });
+        $registry.register("RETURNS", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(SimpleReturns::new()))
+        });
+        $registry.register("LOG_RETURNS", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(LogReturns::new()))
+        });
+        $registry.register("CUM_RETURNS", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(CumulativeReturns::new()))
+        });
+        $registry.register("VAR", |params| {
+            let [confidence, period] = numbers(params)?;
+            Ok(Box::new(ValueAtRisk::new(confidence, integer(period)?)?))
+        });
+        $registry.register("ES", |params| {
+            let [confidence, period] = numbers(params)?;
+            Ok(Box::new(ExpectedShortfall::new(
+                confidence,
+                integer(period)?,
+            )?))
+        });
+        $registry.register("HV", |params| {
+            let [period, periods_per_year] = numbers(params)?;
+            Ok(Box::new(HistoricalVolatility::new(
+                integer(period)?,
+                periods_per_year,
+            )?))
+        });
+        $registry.register("SHARPE", |params| {
+            let [period, risk_free, periods_per_year] = numbers(params)?;
+            Ok(Box::new(SharpeRatio::new(
+                integer(period)?,
+                risk_free,
+                periods_per_year,
+            )?))
+        });
+        $registry.register("SORTINO", |params| {
+            let [period, risk_free, periods_per_year] = numbers(params)?;
+            Ok(Box::new(SortinoRatio::new(
+                integer(period)?,
+                risk_free,
+                periods_per_year,
+            )?))
+        });
+        $registry.register("CALMAR", |params| {
+            let [period, periods_per_year] = numbers(params)?;
+            Ok(Box::new(CalmarRatio::new(
+                integer(period)?,
+                periods_per_year,
+            )?))
+        });
+        $registry.register("TR", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(TrueRange::new()))
+        });
+        $registry.register("BBP", |params| {
+            let [period, multiplier] = numbers(params)?;
+            Ok(Box::new(BollingerPercentB::new(
+                integer(period)?,
+                multiplier,
+            )?))
+        });
+        $registry.register("BBW", |params| {
+            let [period, multiplier] = numbers(params)?;
+            Ok(Box::new(BollingerBandwidth::new(
+                integer(period)?,
+                multiplier,
+            )?))
+        });
+    }};
+}
+
+/// Registers the built-in indicators of `f64` inputs with several outputs. Shared by the
+/// registries of the server and the C interface, which box them as different trait objects.
+#[cfg(any(feature = "server", feature = "ffi"))]
+macro_rules! register_multi_output_indicators {
+    ($registry:ident) => {{
+        use $crate::factory::{integer, numbers, periods};
+        use $crate::indicators::*;
+
+        $registry.register("BB", |params| {
+            let [period, multiplier] = numbers(params)?;
+            Ok(Box::new(BollingerBands::new(integer(period)?, multiplier)?))
+        });
+        $registry.register("KC", |params| {
+            let [period, multiplier] = numbers(params)?;
+            Ok(Box::new(KeltnerChannel::new(integer(period)?, multiplier)?))
+        });
+        $registry.register("LRC", |params| {
+            let [period, multiplier] = numbers(params)?;
+            Ok(Box::new(RegressionChannel::new(
+                integer(period)?,
+                multiplier,
+            )?))
+        });
+        $registry.register("MACD", |params| {
+            let [fast, slow, signal] = periods(params)?;
+            Ok(Box::new(MovingAverageConvergenceDivergence::new(
+                fast, slow, signal,
+            )?))
+        });
+        $registry.register("PPO", |params| {
+            let [fast, slow, signal] = periods(params)?;
+            Ok(Box::new(PercentagePriceOscillator::new(
+                fast, slow, signal,
+            )?))
+        });
+        $registry.register("STOCH", |params| {
+            let [k_period, k_smoothing, d_smoothing] = periods(params)?;
+            Ok(Box::new(StochasticOscillator::new(
+                k_period,
+                k_smoothing,
+                d_smoothing,
+            )?))
+        });
+        $registry.register("KALMAN", |params| {
+            let [process_noise, measurement_noise] = numbers(params)?;
+            Ok(Box::new(KalmanFilter::new(
+                process_noise,
+                measurement_noise,
+            )?))
+        });
+        $registry.register("MDD", |params| match params {
+            [] => Ok(Box::new(MaxDrawdown::new())),
+            _ => {
+                let [period] = periods(params)?;
+                Ok(Box::new(MaxDrawdown::rolling(period)?))
+            }
+        });
+    }};
+}
+
+/// Registers indicators created from a single period.
+macro_rules! register_periodic {
+    ($registry:ident, $($name:expr => $indicator:ident),* $(,)?) => {
+        $($registry.register($name, |params| {
+            let [period] = $crate::factory::periods(params)?;
+            Ok(Box::new($indicator::new(period)?))
+        });)*
+    };
+}
+
+#[cfg(any(feature = "server", feature = "ffi"))]
+pub(crate) use register_multi_output_indicators;
+pub(crate) use {register_periodic, register_scalar_indicators};
+
+/// Converts exactly `N` parameters into an array.
+pub(crate) fn numbers<const N: usize>(params: &[f64]) -> Result<[f64; N]> {
+    params.try_into().map_err(|_| TaError::InvalidParameter)
+}
+
+/// Converts exactly `N` parameters into periods, see [integer].
+pub(crate) fn periods<const N: usize>(params: &[f64]) -> Result<[usize; N]> {
+    let mut periods = [0; N];
+    for (period, &param) in periods.iter_mut().zip(numbers::<N>(params)?.iter()) {
+        *period = integer(param)?;
+    }
+    Ok(periods)
+}
+
+/// Converts a parameter into a non-negative integer.
+pub(crate) fn integer(param: f64) -> Result<usize> {
+    if param >= 0.0 && param.fract() == 0.0 && param <= u32::MAX as f64 {
+        Ok(param as usize)
+    } else {
+        Err(TaError::InvalidParameter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{RelativeStrengthIndex, SimpleMovingAverage};
+
+    #[test]
+    fn test_default() {
+        let factory = IndicatorFactory::default();
+        assert!(factory.names().contains(&"RSI"));
+        assert!(!factory.names().contains(&"BB"));
+        assert_eq!(
+            factory.create("rsi", &[14.0]).unwrap().to_string(),
+            "RSI(14)"
+        );
+        assert_eq!(
+            factory
+                .create("KAMA", &[10.0, 2.0, 30.0])
+                .unwrap()
+                .to_string(),
+            "KAMA(10, 2, 30)"
+        );
+        assert_eq!(
+            factory.create("BBP", &[20.0, 2.0]).unwrap().to_string(),
+            "BBP(20, 2)"
+        );
+        assert!(factory.create("SMA", &[]).is_err());
+        assert!(factory.create("SMA", &[2.5]).is_err());
+        assert!(factory.create("SMA", &[0.0]).is_err());
+        assert!(factory.create("NOPE", &[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_indicator() {
+        let mut rsi = indicator("RSI", &[3.0]).unwrap();
+        let mut expected = RelativeStrengthIndex::new(3).unwrap();
+        for &input in [10.0, 10.5, 10.0, 9.5, 11.0].iter() {
+            assert_eq!(rsi.next(input), expected.next(input));
+        }
+
+        rsi.reset();
+        expected.reset();
+        assert_eq!(rsi.next(10.0), expected.next(10.0));
+    }
+
+    #[test]
+    fn test_register() {
+        let mut factory = IndicatorFactory::empty();
+        factory.register("double_sma", |params| {
+            let [period] = periods(params)?;
+            Ok(Box::new(SimpleMovingAverage::new(period * 2)?))
+        });
+        assert_eq!(factory.names(), vec!["DOUBLE_SMA"]);
+        assert_eq!(
+            factory.create("double_sma", &[5.0]).unwrap().to_string(),
+            "SMA(10)"
+        );
+    }
+
+    #[test]
+    fn test_register_type() {
+        struct Double(SimpleMovingAverage);
+
+        impl Next<f64> for Double {
+            type Output = f64;
+
+            fn next(&mut self, input: f64) -> f64 {
+                self.0.next(input) * 2.0
+            }
+        }
+
+        impl Reset for Double {
+            fn reset(&mut self) {
+                self.0.reset();
+            }
+        }
+
+        impl fmt::Display for Double {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "DOUBLE({})", self.0)
+            }
+        }
+
+        impl FromParams for Double {
+            const NAME: &'static str = "DOUBLE";
+
+            fn from_params(params: &[f64]) -> Result<Self> {
+                let [period] = periods(params)?;
+                Ok(Double(SimpleMovingAverage::new(period)?))
+            }
+        }
+
+        let mut factory = IndicatorFactory::empty();
+        factory.register_type::<Double>();
+        let mut double = factory.create("double", &[2.0]).unwrap();
+        assert_eq!(double.to_string(), "DOUBLE(SMA(2))");
+        assert_eq!(double.next(3.0), 6.0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_config() {
+        let config: IndicatorConfig =
+            serde_json::from_str(r#"{"name": "KAMA", "params": [10, 2, 30]}"#).unwrap();
+        assert_eq!(config, IndicatorConfig::new("KAMA", &[10.0, 2.0, 30.0]));
+
+        let mut kama = from_config(&config).unwrap();
+        assert_eq!(kama.to_string(), "KAMA(10, 2, 30)");
+        assert_eq!(kama.next(5.0), 5.0);
+        kama.reset();
+        assert_eq!(kama.next(7.0), 7.0);
+
+        let json = serde_json::to_string(&kama.to_config()).unwrap();
+        assert_eq!(json, r#"{"name":"KAMA","params":[10.0,2.0,30.0]}"#);
+        assert_eq!(
+            serde_json::from_str::<IndicatorConfig>(&json).unwrap(),
+            config
+        );
+        assert_eq!(kama.into_inner().to_string(), "KAMA(10, 2, 30)");
+
+        let returns: IndicatorConfig = serde_json::from_str(r#"{"name": "returns"}"#).unwrap();
+        assert!(returns.params.is_empty());
+        assert!(from_config(&returns).is_ok());
+
+        assert!(from_config(&IndicatorConfig::new("SMA", &[])).is_err());
+        assert!(IndicatorFactory::empty()
+            .from_config(&IndicatorConfig::new("SMA", &[2.0]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(integer(3.0), Ok(3));
+        assert!(integer(-1.0).is_err());
+        assert!(integer(1.5).is_err());
+        assert!(integer(f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,678 @@
+//! C interface.
+//!
+//! Available with the `ffi` feature.
+//!
+//! Exports `extern "C"` functions creating, feeding and destroying indicators by name, so the
+//! crate can be linked into C and C++ trading systems and called from any language with a C
+//! foreign function interface. Indicators are opaque [TaIndicator] handles, owned by the caller
+//! from their creation until they are passed to [ta_panther_destroy]. The functions never
+//! unwind into the caller: panics are caught and reported as errors, like null handles.
+//!
+//! Indicators are created for one kind of inputs:
+//!
+//! * [ta_panther_create] creates the indicators of `double` values, i.e. all the indicators of
+//!   the [factory](crate::factory) and the ones with several outputs, e.g. `BB` or `MACD`.
+//! * [ta_panther_create_bar] creates the indicators of [TaBar]s which need their highs, lows or
+//!   volumes, e.g. `ATR`, `ADX`, `CE`, `MFI`, or `MAX` of the highs.
+//!
+//! [ta_panther_next_values] and [ta_panther_next_bar] write all the outputs of an indicator,
+//! e.g. the average, upper and lower band of `BB`, and flags as 1 or 0.
+//!
+//! The crate has to be built with the crate type `staticlib` or `cdylib`, and declares the
+//! functions for C as:
+//!
+//! ```c
+//! #include <stddef.h>
+//! #include <stdint.h>
+//!
+//! typedef struct TaIndicator TaIndicator;
+//!
+//! typedef struct TaBar {
+//!     double open;
+//!     double high;
+//!     double low;
+//!     double close;
+//!     double volume;
+//! } TaBar;
+//!
+//! uint32_t ta_panther_abi_version(void);
+//! TaIndicator *ta_panther_create(const char *name, const double *params, size_t len);
+//! TaIndicator *ta_panther_create_bar(const char *name, const double *params, size_t len);
+//! double ta_panther_next(TaIndicator *indicator, double input);
+//! size_t ta_panther_next_values(TaIndicator *indicator, double input, double *output, size_t len);
+//! size_t ta_panther_next_bar(TaIndicator *indicator, const TaBar *bar, double *output, size_t len);
+//! void ta_panther_next_batch(TaIndicator *indicator, const double *input, double *output, size_t len);
+//! void ta_panther_reset(TaIndicator *indicator);
+//! size_t ta_panther_display(const TaIndicator *indicator, char *buf, size_t len);
+//! void ta_panther_destroy(TaIndicator *indicator);
+//! ```
+//!
+//! # Example
+//!
+//! ```c
+//! double params[] = {14};
+//! TaIndicator *rsi = ta_panther_create("RSI", params, 1);
+//! if (rsi == NULL) {
+//!     // unknown name or invalid parameters
+//! }
+//! double value = ta_panther_next(rsi, 101.5);
+//! ta_panther_destroy(rsi);
+//!
+//! TaIndicator *adx = ta_panther_create_bar("ADX", params, 1);
+//! TaBar bar = {100.0, 102.0, 99.0, 101.5, 12000.0};
+//! double outputs[3]; // adx, +DI, -DI
+//! size_t count = ta_panther_next_bar(adx, &bar, outputs, 3);
+//! ta_panther_destroy(adx);
+//! ```
+
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::sync::OnceLock;
+
+use crate::dynamic::{IndicatorDyn, OutputValue};
+use crate::errors::{Result, TaError};
+use crate::factory::{register_multi_output_indicators, register_scalar_indicators};
+use crate::DataItem;
+
+/// Version of the C interface, increased on every incompatible change of its functions.
+pub const ABI_VERSION: u32 = 1;
+
+type Values = dyn IndicatorDyn<f64>;
+type Bars = dyn for<'a> IndicatorDyn<&'a DataItem>;
+type Constructor<I> = Box<dyn Fn(&[f64]) -> Result<Box<I>> + Send + Sync>;
+
+/// Constructors of the indicators of one kind of inputs, by name.
+struct Registry<I: ?Sized> {
+    constructors: HashMap<String, Constructor<I>>,
+}
+
+impl<I: ?Sized> Registry<I> {
+    fn empty() -> Self {
+        Self {
+            constructors: HashMap::new(),
+        }
+    }
+
+    fn register<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&[f64]) -> Result<Box<I>> + Send + Sync + 'static,
+    {
+        self.constructors
+            .insert(name.to_uppercase(), Box::new(constructor));
+    }
+
+    fn create(&self, name: &str, params: &[f64]) -> Result<Box<I>> {
+        match self.constructors.get(&name.to_uppercase()) {
+            Some(constructor) => constructor(params),
+            None => Err(TaError::InvalidParameter),
+        }
+    }
+}
+
+fn values() -> &'static Registry<Values> {
+    static REGISTRY: OnceLock<Registry<Values>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry: Registry<Values> = Registry::empty();
+        register_scalar_indicators!(registry);
+        register_multi_output_indicators!(registry);
+        registry
+    })
+}
+
+/// Indicators of the highs, lows or volumes of bars. Indicators of closes only are created by
+/// [ta_panther_create].
+fn bars() -> &'static Registry<Bars> {
+    use crate::factory::{integer, numbers, periods, register_periodic};
+    use crate::indicators::*;
+
+    static REGISTRY: OnceLock<Registry<Bars>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry: Registry<Bars> = Registry::empty();
+        register_periodic! {
+            registry,
+            "MAX" => Maximum,
+            "MIN" => Minimum,
+            "ATR" => AverageTrueRange,
+            "FAST_STOCH" => FastStochastic,
+            "ADX" => AverageDirectionalIndex,
+            "ADXR" => Adxr,
+            "CCI" => CommodityChannelIndex,
+            "CMF" => ChaikinMoneyFlow,
+            "MFI" => MoneyFlowIndex,
+            "OFI" => OrderFlowImbalance,
+            "TMF" => TwiggsMoneyFlow,
+            "VWMA" => WeightedMean,
+            "VWSTD" => WeightedStd,
+        }
+        registry.register("SLOW_STOCH", |params| {
+            let [stochastic, ema] = periods(params)?;
+            Ok(Box::new(SlowStochastic::new(stochastic, ema)?))
+        });
+        registry.register("STOCH", |params| {
+            let [k_period, k_smoothing, d_smoothing] = periods(params)?;
+            Ok(Box::new(StochasticOscillator::new(
+                k_period,
+                k_smoothing,
+                d_smoothing,
+            )?))
+        });
+        registry.register("KC", |params| {
+            let [period, multiplier] = numbers(params)?;
+            Ok(Box::new(KeltnerChannel::new(integer(period)?, multiplier)?))
+        });
+        registry.register("CE", |params| {
+            let [period, multiplier] = numbers(params)?;
+            Ok(Box::new(ChandelierExit::new(integer(period)?, multiplier)?))
+        });
+        registry.register("VPIN", |params| {
+            let [period, bucket_volume] = numbers(params)?;
+            Ok(Box::new(Vpin::new(integer(period)?, bucket_volume)?))
+        });
+        registry.register("TR", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(TrueRange::new()))
+        });
+        registry.register("OBV", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(OnBalanceVolume::new()))
+        });
+        registry.register("MEDIAN_PRICE", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(MedianPrice::new()))
+        });
+        registry.register("TYPICAL_PRICE", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(TypicalPrice::new()))
+        });
+        registry.register("WEIGHTED_CLOSE", |params| {
+            let [] = numbers(params)?;
+            Ok(Box::new(WeightedClose::new()))
+        });
+        registry
+    })
+}
+
+/// Opaque handle of an indicator.
+pub struct TaIndicator {
+    inner: Inner,
+}
+
+enum Inner {
+    Values(Box<Values>),
+    Bars(Box<Bars>),
+}
+
+/// Prices and volume of a period, the input of the indicators created by
+/// [ta_panther_create_bar].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaBar {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl TaBar {
+    fn to_data_item(self) -> Result<DataItem> {
+        DataItem::builder()
+            .open(self.open)
+            .high(self.high)
+            .low(self.low)
+            .close(self.close)
+            .volume(self.volume)
+            .build()
+    }
+}
+
+/// Returns the `len` values at `data`, or `None` if `data` is null and `len` isn't 0.
+unsafe fn slice_or_empty<'a, T>(data: *const T, len: usize) -> Option<&'a [T]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
+
+/// Writes the outputs which fit into `output` and returns the number of outputs.
+fn write_outputs(value: OutputValue, output: &mut [f64]) -> usize {
+    let values = match value {
+        OutputValue::Float(value) => vec![value],
+        OutputValue::Bool(flag) => vec![if flag { 1.0 } else { 0.0 }],
+        OutputValue::Multi(values) => values,
+    };
+    let len = values.len().min(output.len());
+    output[..len].copy_from_slice(&values[..len]);
+    values.len()
+}
+
+/// Returns the first output, or NaN for an empty output.
+fn first_output(value: OutputValue) -> f64 {
+    let mut output = [f64::NAN];
+    write_outputs(value, &mut output);
+    output[0]
+}
+
+unsafe fn create<I: ?Sized>(
+    registry: fn() -> &'static Registry<I>,
+    name: *const c_char,
+    params: *const f64,
+    len: usize,
+    inner: fn(Box<I>) -> Inner,
+) -> *mut TaIndicator {
+    if name.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(name) = CStr::from_ptr(name).to_str() else {
+        return ptr::null_mut();
+    };
+    let Some(params) = slice_or_empty(params, len) else {
+        return ptr::null_mut();
+    };
+    match panic::catch_unwind(|| registry().create(name, params)) {
+        Ok(Ok(indicator)) => Box::into_raw(Box::new(TaIndicator {
+            inner: inner(indicator),
+        })),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Returns [ABI_VERSION].
+#[no_mangle]
+pub extern "C" fn ta_panther_abi_version() -> u32 {
+    ABI_VERSION
+}
+
+/// Creates an indicator of `double` values by its case-insensitive name and `len` parameters,
+/// e.g. `SMA` or `BB`, see [factory::indicator](crate::factory::indicator).
+///
+/// Returns null if the name is not valid UTF-8, or is unknown, or the parameters are invalid.
+///
+/// # Safety
+///
+/// `name` has to point to a zero terminated string and `params` to `len` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_create(
+    name: *const c_char,
+    params: *const f64,
+    len: usize,
+) -> *mut TaIndicator {
+    create(values, name, params, len, Inner::Values)
+}
+
+/// Creates an indicator of [TaBar]s by its case-insensitive name and `len` parameters, e.g.
+/// `ATR` or `ADX`.
+///
+/// Returns null if the name is not valid UTF-8, or is unknown, or the parameters are invalid.
+///
+/// # Safety
+///
+/// `name` has to point to a zero terminated string and `params` to `len` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_create_bar(
+    name: *const c_char,
+    params: *const f64,
+    len: usize,
+) -> *mut TaIndicator {
+    create(bars, name, params, len, Inner::Bars)
+}
+
+/// Feeds the next value into an indicator of values, returning its first output.
+///
+/// Returns NaN if the indicator is null, or is an indicator of bars, or panicked.
+///
+/// # Safety
+///
+/// `indicator` has to be null or a handle returned by [ta_panther_create] and not destroyed
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_next(indicator: *mut TaIndicator, input: f64) -> f64 {
+    let Some(TaIndicator {
+        inner: Inner::Values(indicator),
+    }) = indicator.as_mut()
+    else {
+        return f64::NAN;
+    };
+    panic::catch_unwind(AssertUnwindSafe(|| indicator.next_value(input)))
+        .map_or(f64::NAN, first_output)
+}
+
+/// Feeds the next value into an indicator of values, writing at most `len` of its outputs
+/// into `output`, and returns the number of its outputs.
+///
+/// Returns 0 if the indicator or `output` is null, or the indicator is an indicator of bars,
+/// or panicked.
+///
+/// # Safety
+///
+/// `indicator` has to be null or a handle returned by [ta_panther_create] and not destroyed
+/// yet, and `output` has to point to space for `len` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_next_values(
+    indicator: *mut TaIndicator,
+    input: f64,
+    output: *mut f64,
+    len: usize,
+) -> usize {
+    let Some(TaIndicator {
+        inner: Inner::Values(indicator),
+    }) = indicator.as_mut()
+    else {
+        return 0;
+    };
+    let Some(output) = slice_or_empty(output, len) else {
+        return 0;
+    };
+    let output = slice::from_raw_parts_mut(output.as_ptr().cast_mut(), output.len());
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        write_outputs(indicator.next_value(input), output)
+    }))
+    .unwrap_or(0)
+}
+
+/// Feeds the next bar into an indicator of bars, writing at most `len` of its outputs into
+/// `output`, and returns the number of its outputs.
+///
+/// Returns 0 without feeding the indicator if the bar is invalid, e.g. its low is above its
+/// high. Returns 0 as well if the indicator, `bar` or `output` is null, or the indicator is an
+/// indicator of values, or panicked.
+///
+/// # Safety
+///
+/// `indicator` has to be null or a handle returned by [ta_panther_create_bar] and not
+/// destroyed yet, `bar` has to be null or point to a bar, and `output` has to point to space
+/// for `len` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_next_bar(
+    indicator: *mut TaIndicator,
+    bar: *const TaBar,
+    output: *mut f64,
+    len: usize,
+) -> usize {
+    let Some(TaIndicator {
+        inner: Inner::Bars(indicator),
+    }) = indicator.as_mut()
+    else {
+        return 0;
+    };
+    let Some(Ok(item)) = bar.as_ref().map(|bar| bar.to_data_item()) else {
+        return 0;
+    };
+    let Some(output) = slice_or_empty(output, len) else {
+        return 0;
+    };
+    let output = slice::from_raw_parts_mut(output.as_ptr().cast_mut(), output.len());
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        write_outputs(indicator.next_value(&item), output)
+    }))
+    .unwrap_or(0)
+}
+
+/// Feeds `len` values into an indicator of values, writing their first outputs into `output`.
+///
+/// If the indicator is an indicator of bars or panics, the remaining outputs are set to NaN.
+/// Does nothing if the indicator, `input` or `output` is null.
+///
+/// # Safety
+///
+/// `indicator` has to be null or a handle returned by [ta_panther_create] and not destroyed
+/// yet, `input` has to point to `len` doubles and `output` to space for `len` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_next_batch(
+    indicator: *mut TaIndicator,
+    input: *const f64,
+    output: *mut f64,
+    len: usize,
+) {
+    let (Some(indicator), Some(input)) = (indicator.as_mut(), slice_or_empty(input, len)) else {
+        return;
+    };
+    if len == 0 || output.is_null() {
+        return;
+    }
+    let output = slice::from_raw_parts_mut(output, len);
+    let Inner::Values(indicator) = &mut indicator.inner else {
+        output.fill(f64::NAN);
+        return;
+    };
+    let fed = panic::catch_unwind(AssertUnwindSafe(|| {
+        for (value, out) in input.iter().zip(output.iter_mut()) {
+            *out = first_output(indicator.next_value(*value));
+        }
+    }));
+    if fed.is_err() {
+        output.fill(f64::NAN);
+    }
+}
+
+/// Resets an indicator to its initial state. Resetting null has no effect.
+///
+/// # Safety
+///
+/// `indicator` has to be null or a handle returned by [ta_panther_create] or
+/// [ta_panther_create_bar] and not destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_reset(indicator: *mut TaIndicator) {
+    let Some(indicator) = indicator.as_mut() else {
+        return;
+    };
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| match &mut indicator.inner {
+        Inner::Values(indicator) => indicator.reset(),
+        Inner::Bars(indicator) => indicator.reset(),
+    }));
+}
+
+/// Writes at most `len` bytes of the UTF-8 encoded display name of an indicator (e.g.
+/// `SMA(14)`) into `buf`, followed by a terminating zero if it fits, and returns the length of
+/// the full name without the terminating zero.
+///
+/// Returns 0 without writing anything if the indicator is null or panicked. Only returns the
+/// length if `buf` is null.
+///
+/// # Safety
+///
+/// `indicator` has to be null or a handle returned by [ta_panther_create] or
+/// [ta_panther_create_bar] and not destroyed yet, and `buf` has to be null or point to space
+/// for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_display(
+    indicator: *const TaIndicator,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    let Some(indicator) = indicator.as_ref() else {
+        return 0;
+    };
+    let Ok(name) = panic::catch_unwind(AssertUnwindSafe(|| match &indicator.inner {
+        Inner::Values(indicator) => indicator.to_string(),
+        Inner::Bars(indicator) => indicator.to_string(),
+    })) else {
+        return 0;
+    };
+    if len > 0 && !buf.is_null() {
+        ptr::copy_nonoverlapping(name.as_ptr().cast(), buf, name.len().min(len));
+        if name.len() < len {
+            *buf.add(name.len()) = 0;
+        }
+    }
+    name.len()
+}
+
+/// Destroys an indicator. Destroying null has no effect.
+///
+/// # Safety
+///
+/// `indicator` has to be null or a handle returned by [ta_panther_create] or
+/// [ta_panther_create_bar] and not destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ta_panther_destroy(indicator: *mut TaIndicator) {
+    if !indicator.is_null() {
+        drop(Box::from_raw(indicator));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create(name: &CStr, params: &[f64]) -> *mut TaIndicator {
+        unsafe { ta_panther_create(name.as_ptr(), params.as_ptr(), params.len()) }
+    }
+
+    #[test]
+    fn test_create() {
+        assert!(create(c"NOPE", &[1.0]).is_null());
+        assert!(create(c"SMA", &[]).is_null());
+        assert!(unsafe { ta_panther_create(ptr::null(), ptr::null(), 0) }.is_null());
+
+        let sma = create(c"sma", &[2.0]);
+        assert!(!sma.is_null());
+        unsafe {
+            ta_panther_destroy(sma);
+            ta_panther_destroy(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        let sma = create(c"SMA", &[2.0]);
+        unsafe {
+            assert_eq!(ta_panther_next(sma, 4.0), 4.0);
+            assert_eq!(ta_panther_next(sma, 8.0), 6.0);
+
+            let mut output = [0.0; 2];
+            ta_panther_next_batch(sma, [10.0, 12.0].as_ptr(), output.as_mut_ptr(), 2);
+            assert_eq!(output, [9.0, 11.0]);
+
+            ta_panther_reset(sma);
+            assert_eq!(ta_panther_next(sma, 1.0), 1.0);
+            ta_panther_destroy(sma);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = create(c"SMA", &[14.0]);
+        unsafe {
+            let mut buf = [1 as c_char; 8];
+            assert_eq!(ta_panther_display(sma, buf.as_mut_ptr(), buf.len()), 7);
+            assert_eq!(CStr::from_ptr(buf.as_ptr()), c"SMA(14)");
+
+            let mut short = [1 as c_char; 3];
+            assert_eq!(ta_panther_display(sma, short.as_mut_ptr(), short.len()), 7);
+            assert_eq!(short.map(|byte| byte as u8), *b"SMA");
+            ta_panther_destroy(sma);
+        }
+    }
+
+    #[test]
+    fn test_next_values() {
+        let bb = create(c"BB", &[2.0, 1.0]);
+        let mut output = [f64::NAN; 3];
+        unsafe {
+            assert_eq!(ta_panther_next_values(bb, 4.0, output.as_mut_ptr(), 3), 3);
+            assert_eq!(output, [4.0, 4.0, 4.0]);
+            assert_eq!(ta_panther_next_values(bb, 8.0, output.as_mut_ptr(), 3), 3);
+            assert_eq!(output, [6.0, 8.0, 4.0]);
+
+            // only the outputs which fit are written
+            let mut average = [0.0];
+            assert_eq!(ta_panther_next_values(bb, 6.0, average.as_mut_ptr(), 1), 3);
+            assert_eq!(average, [7.0]);
+            assert_eq!(ta_panther_next_values(bb, 6.0, ptr::null_mut(), 1), 0);
+            assert_eq!(ta_panther_next_values(bb, 6.0, ptr::null_mut(), 0), 3);
+            ta_panther_destroy(bb);
+        }
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let bar = |high, low, close| TaBar {
+            open: close,
+            high,
+            low,
+            close,
+            volume: 1000.0,
+        };
+        unsafe {
+            assert!(ta_panther_create_bar(c"SMA".as_ptr(), [2.0].as_ptr(), 1).is_null());
+            let max = ta_panther_create_bar(c"MAX".as_ptr(), [2.0].as_ptr(), 1);
+            let mut output = [0.0];
+            for (input, expected) in [(bar(12.0, 8.0, 10.0), 12.0), (bar(11.0, 9.0, 9.5), 12.0)] {
+                assert_eq!(ta_panther_next_bar(max, &input, output.as_mut_ptr(), 1), 1);
+                assert_eq!(output, [expected]);
+            }
+
+            // invalid bars are not fed
+            let invalid = bar(8.0, 12.0, 10.0);
+            assert_eq!(
+                ta_panther_next_bar(max, &invalid, output.as_mut_ptr(), 1),
+                0
+            );
+            assert_eq!(
+                ta_panther_next_bar(max, ptr::null(), output.as_mut_ptr(), 1),
+                0
+            );
+            let input = bar(10.0, 9.0, 9.5);
+            assert_eq!(ta_panther_next_bar(max, &input, output.as_mut_ptr(), 1), 1);
+            assert_eq!(output, [11.0]);
+
+            // indicators of bars don't take values and the other way round
+            assert!(ta_panther_next(max, 1.0).is_nan());
+            let sma = create(c"SMA", &[2.0]);
+            assert_eq!(ta_panther_next_bar(sma, &input, output.as_mut_ptr(), 1), 0);
+            ta_panther_destroy(sma);
+            ta_panther_destroy(max);
+
+            let adx = ta_panther_create_bar(c"adx".as_ptr(), [3.0].as_ptr(), 1);
+            let mut output = [f64::NAN; 3];
+            assert_eq!(ta_panther_next_bar(adx, &input, output.as_mut_ptr(), 3), 3);
+            assert!(output.iter().all(|value| value.is_finite()));
+            ta_panther_destroy(adx);
+        }
+    }
+
+    #[test]
+    fn test_null() {
+        let mut output = [1.0; 2];
+        let mut buf = [1 as c_char; 4];
+        let bar = TaBar {
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+        };
+        unsafe {
+            assert!(ta_panther_next(ptr::null_mut(), 1.0).is_nan());
+            let null = ptr::null_mut();
+            assert_eq!(ta_panther_next_values(null, 1.0, output.as_mut_ptr(), 2), 0);
+            assert_eq!(ta_panther_next_bar(null, &bar, output.as_mut_ptr(), 2), 0);
+            ta_panther_next_batch(null, [1.0, 2.0].as_ptr(), output.as_mut_ptr(), 2);
+            assert_eq!(output, [1.0, 1.0]);
+            ta_panther_reset(null);
+            assert_eq!(ta_panther_display(null, buf.as_mut_ptr(), buf.len()), 0);
+            assert_eq!(buf, [1; 4]);
+
+            let sma = create(c"SMA", &[2.0]);
+            ta_panther_next_batch(sma, ptr::null(), output.as_mut_ptr(), 2);
+            ta_panther_next_batch(sma, [1.0, 2.0].as_ptr(), ptr::null_mut(), 2);
+            assert_eq!(ta_panther_next(sma, 4.0), 4.0);
+            assert_eq!(ta_panther_display(sma, ptr::null_mut(), 4), 6);
+            assert!(create(c"SMA", &[]).is_null());
+            assert!(ta_panther_create(c"SMA".as_ptr(), ptr::null(), 1).is_null());
+            ta_panther_destroy(sma);
+        }
+    }
+
+    #[test]
+    fn test_abi_version() {
+        assert_eq!(ta_panther_abi_version(), ABI_VERSION);
+    }
+}
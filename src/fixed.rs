@@ -0,0 +1,448 @@
+//! Indicators with a window fixed at compile time.
+//!
+//! The indicators of this module take their period as a const generic parameter and keep their
+//! window in an inline `[f64; N]` array instead of a boxed slice. They never allocate, can be
+//! copied and stored inline in other structures, which suits latency sensitive code and
+//! embedded targets. A period of 0 is rejected at compile time.
+//!
+//! Their outputs are the same as those of the indicators with a runtime period of the
+//! [indicators](crate::indicators) module, which remain the general purpose choice.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::fixed::{HighestHighValue, SimpleMovingAverage};
+//! use ta_panther::Next;
+//!
+//! let mut sma = SimpleMovingAverage::<3>::new();
+//! let mut hhv = HighestHighValue::<3>::new();
+//! for x in [10.0, 11.0, 12.0, 13.0] {
+//!     sma.next(x);
+//!     hhv.next(x);
+//! }
+//! assert_eq!(sma.next(14.0), 13.0);
+//! assert_eq!(hhv.next(9.0), 13.0);
+//! ```
+//!
+//! An empty window does not compile:
+//!
+//! ```compile_fail
+//! let sma = ta_panther::fixed::SimpleMovingAverage::<0>::new();
+//! ```
+
+use core::fmt;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ring buffer of the last `N` values.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Window<const N: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "array"))]
+    values: [f64; N],
+    index: usize,
+    count: usize,
+}
+
+impl<const N: usize> Window<N> {
+    const fn new() -> Self {
+        const { assert!(N > 0, "the period must be greater than 0") };
+        Self {
+            values: [0.0; N],
+            index: 0,
+            count: 0,
+        }
+    }
+
+    /// Adds a value, returning the value it replaces, 0 until the window is full.
+    fn push(&mut self, value: f64) -> f64 {
+        let old = core::mem::replace(&mut self.values[self.index], value);
+        self.index = if self.index + 1 < N {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < N {
+            self.count += 1;
+        }
+        old
+    }
+
+    fn is_full(&self) -> bool {
+        self.count == N
+    }
+
+    fn values(&self) -> &[f64] {
+        &self.values[..self.count]
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Serializes arrays of any length as sequences, serde only implements arrays up to 32 values.
+#[cfg(feature = "serde")]
+mod array {
+    use alloc::vec::Vec;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        values: &[f64; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[f64; N], D::Error> {
+        let values = Vec::<f64>::deserialize(deserializer)?;
+        let len = values.len();
+        values
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &"as many values as the period"))
+    }
+}
+
+/// Implements the traits shared by all the indicators of the module.
+macro_rules! fixed_indicator {
+    ($name:ident, $($format:tt)*) => {
+        impl<const N: usize> Period for $name<N> {
+            fn period(&self) -> usize {
+                N
+            }
+        }
+
+        impl<const N: usize> IsReady for $name<N> {
+            fn bars_remaining(&self) -> usize {
+                N - self.window.count
+            }
+        }
+
+        impl<T: Close, const N: usize> Next<&T> for $name<N> {
+            type Output = f64;
+
+            fn next(&mut self, input: &T) -> Self::Output {
+                self.next(input.close())
+            }
+        }
+
+        impl<const N: usize> Default for $name<N> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<const N: usize> fmt::Display for $name<N> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, $($format)*, N)
+            }
+        }
+    };
+}
+
+/// Simple moving average (SMA) over `N` periods.
+///
+/// Same as [indicators::SimpleMovingAverage](crate::indicators::SimpleMovingAverage).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::fixed::SimpleMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut sma = SimpleMovingAverage::<3>::new();
+/// assert_eq!(sma.next(10.0), 10.0);
+/// assert_eq!(sma.next(11.0), 10.5);
+/// assert_eq!(sma.next(12.0), 11.0);
+/// assert_eq!(sma.next(13.0), 12.0);
+/// ```
+///
+#[doc(alias = "SMA")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimpleMovingAverage<const N: usize> {
+    window: Window<N>,
+    sum: f64,
+}
+
+impl<const N: usize> SimpleMovingAverage<N> {
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+            sum: 0.0,
+        }
+    }
+}
+
+impl<const N: usize> Next<f64> for SimpleMovingAverage<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let old = self.window.push(input);
+        self.sum = self.sum - old + input;
+        self.sum / self.window.count as f64
+    }
+}
+
+impl<const N: usize> Reset for SimpleMovingAverage<N> {
+    fn reset(&mut self) {
+        self.window.reset();
+        self.sum = 0.0;
+    }
+}
+
+fixed_indicator!(SimpleMovingAverage, "SMA({})");
+
+/// Standard deviation (SD) over `N` periods.
+///
+/// Same as [indicators::StandardDeviation](crate::indicators::StandardDeviation).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::fixed::StandardDeviation;
+/// use ta_panther::Next;
+///
+/// let mut sd = StandardDeviation::<3>::new();
+/// assert_eq!(sd.next(10.0), 0.0);
+/// assert_eq!(sd.next(20.0), 5.0);
+/// ```
+///
+#[doc(alias = "SD")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StandardDeviation<const N: usize> {
+    window: Window<N>,
+    m: f64,
+    m2: f64,
+}
+
+impl<const N: usize> StandardDeviation<N> {
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+            m: 0.0,
+            m2: 0.0,
+        }
+    }
+}
+
+impl<const N: usize> Next<f64> for StandardDeviation<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let was_full = self.window.is_full();
+        let old = self.window.push(input);
+        let count = self.window.count as f64;
+
+        if !was_full {
+            let delta = input - self.m;
+            self.m += delta / count;
+            let delta2 = input - self.m;
+            self.m2 += delta * delta2;
+        } else {
+            let delta = input - old;
+            let old_m = self.m;
+            self.m += delta / count;
+            let delta2 = input - self.m + old - old_m;
+            self.m2 += delta * delta2;
+        }
+        if self.m2 < 0.0 {
+            self.m2 = 0.0;
+        }
+
+        (self.m2 / count).sqrt()
+    }
+}
+
+impl<const N: usize> Reset for StandardDeviation<N> {
+    fn reset(&mut self) {
+        self.window.reset();
+        self.m = 0.0;
+        self.m2 = 0.0;
+    }
+}
+
+fixed_indicator!(StandardDeviation, "SD({})");
+
+/// Highest High Value (HHV) over `N` periods.
+///
+/// Same as [indicators::HighestHighValue](crate::indicators::HighestHighValue).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::fixed::HighestHighValue;
+/// use ta_panther::Next;
+///
+/// let mut hhv = HighestHighValue::<3>::new();
+/// assert_eq!(hhv.next(10.0), 10.0);
+/// assert_eq!(hhv.next(12.0), 12.0);
+/// assert_eq!(hhv.next(8.0), 12.0);
+/// assert_eq!(hhv.next(13.0), 13.0);
+/// ```
+///
+#[doc(alias = "HHV")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HighestHighValue<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> HighestHighValue<N> {
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+        }
+    }
+}
+
+impl<const N: usize> Next<f64> for HighestHighValue<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+        self.window
+            .values()
+            .iter()
+            .fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+    }
+}
+
+impl<const N: usize> Reset for HighestHighValue<N> {
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+}
+
+fixed_indicator!(HighestHighValue, "HHV:{}");
+
+/// Lowest Low Value (LLV) over `N` periods.
+///
+/// Same as [indicators::LowestLowValue](crate::indicators::LowestLowValue).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::fixed::LowestLowValue;
+/// use ta_panther::Next;
+///
+/// let mut llv = LowestLowValue::<3>::new();
+/// assert_eq!(llv.next(10.0), 10.0);
+/// assert_eq!(llv.next(8.0), 8.0);
+/// assert_eq!(llv.next(12.0), 8.0);
+/// assert_eq!(llv.next(13.0), 8.0);
+/// assert_eq!(llv.next(14.0), 12.0);
+/// ```
+///
+#[doc(alias = "LLV")]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LowestLowValue<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> LowestLowValue<N> {
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+        }
+    }
+}
+
+impl<const N: usize> Next<f64> for LowestLowValue<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+        self.window
+            .values()
+            .iter()
+            .fold(f64::INFINITY, |a, &b| a.min(b))
+    }
+}
+
+impl<const N: usize> Reset for LowestLowValue<N> {
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+}
+
+fixed_indicator!(LowestLowValue, "LLV:{}");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators;
+    use crate::test_helper::*;
+    use crate::testing::random_series;
+
+    type Sma = SimpleMovingAverage<4>;
+    type Sd = StandardDeviation<4>;
+    type Hhv = HighestHighValue<4>;
+    type Llv = LowestLowValue<4>;
+
+    test_indicator!(Sma);
+
+    #[test]
+    fn test_same_as_runtime_period() {
+        fn check<F, R>(mut fixed: F, mut runtime: R)
+        where
+            F: Next<f64, Output = f64> + IsReady,
+            R: Next<f64, Output = f64> + IsReady,
+        {
+            for x in random_series(3, 200) {
+                assert_eq!(fixed.next(x), runtime.next(x));
+                assert_eq!(fixed.bars_remaining(), runtime.bars_remaining());
+            }
+        }
+
+        check(Sma::new(), indicators::SimpleMovingAverage::new(4).unwrap());
+        check(Sd::new(), indicators::StandardDeviation::new(4).unwrap());
+        check(Hhv::new(), indicators::HighestHighValue::new(4).unwrap());
+        check(Llv::new(), indicators::LowestLowValue::new(4).unwrap());
+        check(
+            SimpleMovingAverage::<1>::new(),
+            indicators::SimpleMovingAverage::new(1).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sd = Sd::new();
+        sd.next(10.0);
+        sd.next(20.0);
+        sd.reset();
+        assert_eq!(sd.next(30.0), 0.0);
+        assert_eq!(sd.bars_remaining(), 3);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Sma::new()), "SMA(4)");
+        assert_eq!(format!("{}", Sd::new()), "SD(4)");
+        assert_eq!(format!("{}", Hhv::new()), "HHV:4");
+        assert_eq!(format!("{}", Llv::new()), "LLV:4");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let mut sma = SimpleMovingAverage::<40>::new();
+        sma.next(4.0);
+        sma.next(8.0);
+        let json = serde_json::to_string(&sma).unwrap();
+        let mut restored: SimpleMovingAverage<40> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.next(12.0), 8.0);
+        assert!(serde_json::from_str::<SimpleMovingAverage<3>>(&json).is_err());
+    }
+}
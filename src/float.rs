@@ -0,0 +1,60 @@
+//! Floating point functions of `f64` which are only provided by `std`, implemented with libm
+//! for `no_std` builds.
+//!
+//! The methods have the names of their `std` counterparts, so importing [Float] is the only
+//! change needed in the code calling them.
+
+pub(crate) trait Float {
+    fn sqrt(self) -> Self;
+    fn ln(self) -> Self;
+    fn log2(self) -> Self;
+    fn exp(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn floor(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan(self) -> Self;
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn log2(self) -> f64 {
+        libm::log2(self)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn atan(self) -> f64 {
+        libm::atan(self)
+    }
+}
@@ -1,16 +1,622 @@
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+
+use crate::errors::{Result, TaError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+
+/// Validates the state of a ring buffer of `period` values, of which `count` have been
+/// filled (at most `max_count`), with `index` pointing to the next slot.
+pub fn validate_ring(
+    period: usize,
+    buffer: &[f64],
+    index: usize,
+    count: usize,
+    max_count: usize,
+) -> Result<()> {
+    if period > 0 && buffer.len() == period && index < period && count <= max_count {
+        Ok(())
+    } else {
+        Err(TaError::InvalidState)
+    }
+}
+
+/// Allocates a ring buffer of `len` copies of `value`.
+///
+/// Returns [InvalidParameter](TaError::InvalidParameter) instead of panicking or aborting if
+/// the buffer can't be allocated, e.g. for a period close to `usize::MAX`.
+pub fn ring<T: Clone>(value: T, len: usize) -> Result<Box<[T]>> {
+    let mut ring = Vec::new();
+    ring.try_reserve_exact(len)
+        .map_err(|_| TaError::InvalidParameter)?;
+    ring.resize(len, value);
+    Ok(ring.into_boxed_slice())
+}
+
+/// Allocates an empty deque with room for `len` values, see [ring].
+pub fn deque<T>(len: usize) -> Result<VecDeque<T>> {
+    let mut deque = VecDeque::new();
+    deque
+        .try_reserve_exact(len)
+        .map_err(|_| TaError::InvalidParameter)?;
+    Ok(deque)
+}
+
 /// Returns the largest of 3 given numbers.
 pub fn max3(a: f64, b: f64, c: f64) -> f64 {
     a.max(b).max(c)
 }
 
+/// Fits a least squares line through `values` placed at `x = 1, 2, ..., n`.
+///
+/// Returns `(slope, intercept)`. The slope is `0.0` when less than two values are given.
+pub fn linear_regression<I>(values: I) -> (f64, f64)
+where
+    I: IntoIterator<Item = f64>,
+    I::IntoIter: Clone,
+{
+    let values = values.into_iter();
+    let (n, sum_y) = values
+        .clone()
+        .fold((0usize, 0.0), |(n, sum), y| (n + 1, sum + y));
+    if n == 0 {
+        return (0.0, 0.0);
+    }
+
+    let n_f = n as f64;
+    let mean_x = (n_f + 1.0) / 2.0;
+    let mean_y = sum_y / n_f;
+    // sum of (x - mean_x)^2 for x = 1..=n
+    let var_x = n_f * (n_f * n_f - 1.0) / 12.0;
+    if var_x == 0.0 {
+        return (0.0, mean_y);
+    }
+
+    let cov_xy: f64 = values
+        .enumerate()
+        .map(|(i, y)| (i as f64 + 1.0 - mean_x) * (y - mean_y))
+        .sum();
+    let slope = cov_xy / var_x;
+    (slope, mean_y - slope * mean_x)
+}
+
+/// Returns the standard error of a regression line fitted by [linear_regression].
+///
+/// It is `0.0` when less than three values are given.
+pub fn regression_standard_error<I>(values: I, slope: f64, intercept: f64) -> f64
+where
+    I: IntoIterator<Item = f64>,
+{
+    let (n, sum_sq) = values
+        .into_iter()
+        .enumerate()
+        .fold((0usize, 0.0), |(_, sum), (i, y)| {
+            let residual = y - (slope * (i as f64 + 1.0) + intercept);
+            (i + 1, sum + residual * residual)
+        });
+    if n < 3 {
+        return 0.0;
+    }
+    (sum_sq / (n - 2) as f64).sqrt()
+}
+
+/// Fits a least squares polynomial of the given degree through `values` placed at
+/// `x = 1, 2, ..., n` and evaluates it at `at`.
+///
+/// The fit uses polynomials orthogonal over the sample points (Forsythe's three-term
+/// recurrence), which avoids the ill-conditioned normal equations of the monomial basis.
+/// If there are not enough values for the requested degree, the degree is lowered to `n - 1`.
+pub fn polynomial_forecast(values: &[f64], degree: usize, at: f64) -> f64 {
+    let n = values.len();
+    if n == 0 {
+        return 0.0;
+    }
+    let degree = degree.min(n - 1);
+
+    // centering x improves the conditioning of the recurrence
+    let center = (n as f64 + 1.0) / 2.0;
+    let xs: Vec<f64> = (1..=n).map(|x| x as f64 - center).collect();
+    let at = at - center;
+
+    let mut prev = vec![0.0; n];
+    let mut curr = vec![1.0; n];
+    let mut prev_norm = 1.0;
+    let (mut prev_at, mut curr_at) = (0.0, 1.0);
+    let mut result = 0.0;
+
+    for k in 0..=degree {
+        let norm: f64 = curr.iter().map(|p| p * p).sum();
+        if norm == 0.0 {
+            break;
+        }
+        let coefficient: f64 = curr.iter().zip(values).map(|(p, y)| p * y).sum::<f64>() / norm;
+        result += coefficient * curr_at;
+
+        if k == degree {
+            break;
+        }
+        let alpha: f64 = xs.iter().zip(&curr).map(|(x, p)| x * p * p).sum::<f64>() / norm;
+        let beta = if k == 0 { 0.0 } else { norm / prev_norm };
+        let next: Vec<f64> = xs
+            .iter()
+            .zip(curr.iter().zip(&prev))
+            .map(|(x, (p, q))| (x - alpha) * p - beta * q)
+            .collect();
+        let next_at = (at - alpha) * curr_at - beta * prev_at;
+
+        prev = core::mem::replace(&mut curr, next);
+        prev_at = core::mem::replace(&mut curr_at, next_at);
+        prev_norm = norm;
+    }
+
+    result
+}
+
+/// Returns the average of the values of a ring buffer weighted by `kernel`.
+///
+/// The newest value gets the last weight of the kernel. Until the buffer is full, the kernel is
+/// truncated to its last `count` weights and renormalized.
+pub fn kernel_average(kernel: &[f64], deque: &[f64], index: usize, count: usize) -> f64 {
+    let weights = &kernel[kernel.len() - count..];
+    let (older, newer) = if count < deque.len() {
+        (&deque[..0], &deque[..count])
+    } else {
+        (&deque[index..], &deque[..index])
+    };
+    let sum: f64 = older
+        .iter()
+        .chain(newer)
+        .zip(weights)
+        .map(|(value, weight)| value * weight)
+        .sum();
+    sum / weights.iter().sum::<f64>()
+}
+
+/// Central moments of a window of values, updated in O(1) as values enter and leave.
+///
+/// Uses the one-pass update formulas of Welford and Terriberry, which avoid the cancellation of
+/// the naive sums of powers.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Moments {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+}
+
+impl Moments {
+    /// Adds a value.
+    pub fn push(&mut self, x: f64) {
+        let n0 = self.count as f64;
+        self.count += 1;
+        let n = self.count as f64;
+
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * n0;
+
+        self.mean += delta_n;
+        self.m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+    }
+
+    /// Removes a value which has been added before, reversing [push](Moments::push).
+    pub fn pop(&mut self, x: f64) {
+        if self.count <= 1 {
+            *self = Self::default();
+            return;
+        }
+
+        let n = self.count as f64;
+        let n0 = n - 1.0;
+        let mean = (n * self.mean - x) / n0;
+
+        let delta = x - mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * n0;
+
+        self.count -= 1;
+        self.mean = mean;
+        self.m2 = (self.m2 - term).max(0.0);
+        self.m3 -= term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m4 = (self.m4 - term * delta_n2 * (n * n - 3.0 * n + 3.0) - 6.0 * delta_n2 * self.m2
+            + 4.0 * delta_n * self.m3)
+            .max(0.0);
+    }
+
+    /// Returns the number of values.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the mean, or `0.0` if there are no values.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the sample variance, or `0.0` if there are less than two values.
+    pub fn sample_variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+
+    /// Returns the population skewness, or `0.0` if all the values are equal.
+    pub fn skewness(&self) -> f64 {
+        if self.is_degenerate() {
+            0.0
+        } else {
+            (self.count as f64).sqrt() * self.m3 / self.m2.powf(1.5)
+        }
+    }
+
+    /// Returns the population excess kurtosis, or `0.0` if all the values are equal.
+    pub fn kurtosis(&self) -> f64 {
+        if self.is_degenerate() {
+            0.0
+        } else {
+            self.count as f64 * self.m4 / (self.m2 * self.m2) - 3.0
+        }
+    }
+
+    /// Returns `true` if the spread of the values is indistinguishable from rounding errors.
+    fn is_degenerate(&self) -> bool {
+        self.count < 2
+            || self.m2 <= 1e-12 * self.count as f64 * self.mean * self.mean
+            || self.m2 <= f64::MIN_POSITIVE
+    }
+}
+
+/// Means, variances and covariance of a window of pairs of values, updated in O(1) as pairs
+/// enter and leave.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoMoments {
+    count: usize,
+    mean_x: f64,
+    mean_y: f64,
+    m2_x: f64,
+    m2_y: f64,
+    c_xy: f64,
+}
+
+impl CoMoments {
+    /// Adds a pair.
+    pub fn push(&mut self, x: f64, y: f64) {
+        self.count += 1;
+        let n = self.count as f64;
+
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.mean_x += dx / n;
+        self.mean_y += dy / n;
+
+        self.m2_x += dx * (x - self.mean_x);
+        self.m2_y += dy * (y - self.mean_y);
+        self.c_xy += dx * (y - self.mean_y);
+    }
+
+    /// Removes a pair which has been added before, reversing [push](CoMoments::push).
+    pub fn pop(&mut self, x: f64, y: f64) {
+        if self.count <= 1 {
+            *self = Self::default();
+            return;
+        }
+
+        let n = self.count as f64;
+        let mean_x = (n * self.mean_x - x) / (n - 1.0);
+        let mean_y = (n * self.mean_y - y) / (n - 1.0);
+
+        self.m2_x = (self.m2_x - (x - mean_x) * (x - self.mean_x)).max(0.0);
+        self.m2_y = (self.m2_y - (y - mean_y) * (y - self.mean_y)).max(0.0);
+        self.c_xy -= (x - mean_x) * (y - self.mean_y);
+
+        self.count -= 1;
+        self.mean_x = mean_x;
+        self.mean_y = mean_y;
+    }
+
+    /// Returns the number of pairs.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the population covariance.
+    pub fn covariance(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.c_xy / self.count as f64
+        }
+    }
+
+    /// Returns the Pearson correlation, or `0.0` if either of the series is constant.
+    pub fn correlation(&self) -> f64 {
+        if self.is_degenerate_x() || self.is_degenerate_y() {
+            0.0
+        } else {
+            (self.c_xy / (self.m2_x * self.m2_y).sqrt()).clamp(-1.0, 1.0)
+        }
+    }
+
+    /// Returns the slope of the regression of `x` on `y`, or `0.0` if `y` is constant.
+    pub fn beta(&self) -> f64 {
+        if self.is_degenerate_y() {
+            0.0
+        } else {
+            self.c_xy / self.m2_y
+        }
+    }
+
+    fn is_degenerate_x(&self) -> bool {
+        self.count < 2 || self.m2_x <= 1e-12 * self.count as f64 * self.mean_x * self.mean_x
+    }
+
+    fn is_degenerate_y(&self) -> bool {
+        self.count < 2 || self.m2_y <= 1e-12 * self.count as f64 * self.mean_y * self.mean_y
+    }
+}
+
+/// Weighted mean and variance of a window of values, updated in O(1) as values enter and leave.
+///
+/// Uses the weighted one-pass update of West. Values with a zero weight are ignored.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedMoments {
+    count: usize,
+    weight: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl WeightedMoments {
+    /// Adds a value with the given weight.
+    pub fn push(&mut self, x: f64, w: f64) {
+        if w == 0.0 {
+            return;
+        }
+        self.count += 1;
+        self.weight += w;
+
+        let delta = x - self.mean;
+        self.mean += delta * w / self.weight;
+        self.m2 += w * delta * (x - self.mean);
+    }
+
+    /// Removes a value which has been added before, reversing [push](WeightedMoments::push).
+    pub fn pop(&mut self, x: f64, w: f64) {
+        if w == 0.0 {
+            return;
+        }
+        if self.count <= 1 {
+            *self = Self::default();
+            return;
+        }
+
+        let weight = self.weight - w;
+        let mean = (self.weight * self.mean - w * x) / weight;
+        self.m2 = (self.m2 - w * (x - mean) * (x - self.mean)).max(0.0);
+
+        self.count -= 1;
+        self.weight = weight;
+        self.mean = mean;
+    }
+
+    /// Returns the weighted mean, or NaN if the weights sum up to zero.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+
+    /// Returns the weighted population standard deviation, or NaN if the weights sum up to zero.
+    pub fn std_dev(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            (self.m2 / self.weight).sqrt()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn naive_moments(values: &[f64]) -> (f64, f64) {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let moment = |k| values.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / n;
+        let (m2, m3, m4) = (moment(2), moment(3), moment(4));
+        (m3 / m2.powf(1.5), m4 / (m2 * m2) - 3.0)
+    }
+
+    #[test]
+    fn test_ring() {
+        assert_eq!(&*ring(1.5, 3).unwrap(), &[1.5, 1.5, 1.5]);
+        assert!(ring(0.0, 0).unwrap().is_empty());
+        assert!(ring(0.0, usize::MAX).is_err());
+        assert!(ring((0.0, 0.0), usize::MAX / 8).is_err());
+
+        let empty = deque::<f64>(3).unwrap();
+        assert!(empty.is_empty() && empty.capacity() >= 3);
+        assert!(deque::<f64>(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_moments() {
+        let values = [2.0, 8.0, 3.0, 3.5, 10.0, 1.0, 4.0, 7.0, 2.5];
+        let period = 4;
+        let mut moments = Moments::default();
+        for (i, &x) in values.iter().enumerate() {
+            if i >= period {
+                moments.pop(values[i - period]);
+            }
+            moments.push(x);
+
+            let window = &values[(i + 1).saturating_sub(period)..=i];
+            if window.len() > 1 {
+                let (skewness, kurtosis) = naive_moments(window);
+                let mean = window.iter().sum::<f64>() / window.len() as f64;
+                let variance = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+                    / (window.len() - 1) as f64;
+                assert!((moments.sample_variance() - variance).abs() < 1e-9);
+                assert!((moments.skewness() - skewness).abs() < 1e-9);
+                assert!((moments.kurtosis() - kurtosis).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_moments_degenerate() {
+        let mut moments = Moments::default();
+        assert_eq!(moments.skewness(), 0.0);
+        moments.push(3.0);
+        assert_eq!(moments.skewness(), 0.0);
+        moments.push(3.0);
+        moments.push(3.0);
+        assert_eq!(moments.kurtosis(), 0.0);
+        moments.pop(3.0);
+        moments.pop(3.0);
+        moments.pop(3.0);
+        moments.push(1.0);
+        moments.push(2.0);
+        assert_eq!(moments.kurtosis(), -2.0);
+    }
+
+    #[test]
+    fn test_co_moments() {
+        let xs = [1.0, 3.0, 2.0, 5.0, 4.0, 8.0, 6.0];
+        let ys = [2.0, 1.0, 4.0, 3.0, 7.0, 5.0, 9.0];
+        let period = 4;
+        let mut moments = CoMoments::default();
+        for i in 0..xs.len() {
+            if i >= period {
+                moments.pop(xs[i - period], ys[i - period]);
+            }
+            moments.push(xs[i], ys[i]);
+
+            let start = (i + 1).saturating_sub(period);
+            let (x, y) = (&xs[start..=i], &ys[start..=i]);
+            let n = x.len() as f64;
+            let mx = x.iter().sum::<f64>() / n;
+            let my = y.iter().sum::<f64>() / n;
+            let cov = x
+                .iter()
+                .zip(y)
+                .map(|(a, b)| (a - mx) * (b - my))
+                .sum::<f64>()
+                / n;
+            let var_x = x.iter().map(|a| (a - mx).powi(2)).sum::<f64>() / n;
+            let var_y = y.iter().map(|b| (b - my).powi(2)).sum::<f64>() / n;
+
+            assert!((moments.covariance() - cov).abs() < 1e-9);
+            if i > 0 {
+                assert!((moments.beta() - cov / var_y).abs() < 1e-9);
+                assert!((moments.correlation() - cov / (var_x * var_y).sqrt()).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weighted_moments() {
+        let values = [
+            (2.0, 1.0),
+            (4.0, 3.0),
+            (6.0, 0.0),
+            (1.0, 2.0),
+            (5.0, 0.5),
+            (3.0, 4.0),
+        ];
+        let period = 3;
+        let mut moments = WeightedMoments::default();
+        for i in 0..values.len() {
+            if i >= period {
+                let (x, w) = values[i - period];
+                moments.pop(x, w);
+            }
+            let (x, w) = values[i];
+            moments.push(x, w);
+
+            let window = &values[(i + 1).saturating_sub(period)..=i];
+            let weight: f64 = window.iter().map(|(_, w)| w).sum();
+            let mean = window.iter().map(|(x, w)| x * w).sum::<f64>() / weight;
+            let variance = window
+                .iter()
+                .map(|(x, w)| w * (x - mean).powi(2))
+                .sum::<f64>()
+                / weight;
+            assert!((moments.mean() - mean).abs() < 1e-9);
+            assert!((moments.std_dev() - variance.sqrt()).abs() < 1e-9);
+        }
+
+        let mut moments = WeightedMoments::default();
+        assert!(moments.mean().is_nan());
+        moments.push(3.0, 0.0);
+        assert!(moments.std_dev().is_nan());
+    }
+
     #[test]
     fn test_max3() {
         assert_eq!(max3(3.0, 2.0, 1.0), 3.0);
         assert_eq!(max3(2.0, 3.0, 1.0), 3.0);
         assert_eq!(max3(2.0, 1.0, 3.0), 3.0);
     }
+
+    #[test]
+    fn test_polynomial_forecast() {
+        assert_eq!(polynomial_forecast(&[], 2, 1.0), 0.0);
+        assert_eq!(polynomial_forecast(&[3.0], 2, 2.0), 3.0);
+
+        // y = x^2
+        let squares = [1.0, 4.0, 9.0, 16.0, 25.0];
+        assert!((polynomial_forecast(&squares, 2, 6.0) - 36.0).abs() < 1e-9);
+
+        // y = x^3 - 2x
+        let cubic: Vec<f64> = (1..=8).map(|x| (x * x * x - 2 * x) as f64).collect();
+        assert!((polynomial_forecast(&cubic, 3, 9.0) - 711.0).abs() < 1e-9);
+
+        // degree 1 matches the linear regression
+        let values = [1.0, 3.0, 2.0, 4.0];
+        let (slope, intercept) = linear_regression(values.to_vec());
+        let expected = slope * 5.0 + intercept;
+        assert!((polynomial_forecast(&values, 1, 5.0) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_linear_regression() {
+        assert_eq!(linear_regression(vec![]), (0.0, 0.0));
+        assert_eq!(linear_regression(vec![5.0]), (0.0, 5.0));
+        assert_eq!(linear_regression(vec![1.0, 3.0]), (2.0, -1.0));
+        assert_eq!(linear_regression(vec![2.0, 4.0, 6.0, 8.0]), (2.0, 0.0));
+    }
+
+    #[test]
+    fn test_regression_standard_error() {
+        assert_eq!(regression_standard_error(vec![1.0, 3.0], 2.0, -1.0), 0.0);
+        assert_eq!(
+            regression_standard_error(vec![2.0, 4.0, 6.0], 2.0, 0.0),
+            0.0
+        );
+
+        let values = vec![1.0, 3.0, 2.0, 4.0];
+        let (slope, intercept) = linear_regression(values.clone());
+        let se = regression_standard_error(values, slope, intercept);
+        assert!((se - 0.9486833).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,208 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::Result;
+use crate::helpers::deque;
+use crate::indicators::{AverageDirectionalIndex, Smoothing};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Average directional movement index rating (ADXR).
+///
+/// Average of the current [ADX](crate::indicators::AverageDirectionalIndex) and the ADX
+/// _period - 1_ bars ago, a smoother and more lagging measure of the strength of the trend.
+/// The oldest ADX is used while fewer bars have been seen.
+///
+/// # Formula
+///
+/// ADXR<sub>t</sub> = (ADX<sub>t</sub> + ADX<sub>t-(period-1)</sub>) / 2
+///
+/// # Parameters
+///
+/// * _period_ - period of the ADX and of the lag (integer greater than 0). Default is 14.
+/// * _smoothing_ - smoothing of the ADX. Default is [Smoothing::Wilder].
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Adxr;
+/// use ta_panther::{DataItem, Next};
+///
+/// let mut adxr = Adxr::new(14).unwrap();
+/// let bar = |high, low, close| {
+///     DataItem::builder()
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .open(close)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+/// assert_eq!(adxr.next(&bar(10.0, 9.0, 10.0)), 0.0);
+/// assert_eq!(adxr.next(&bar(11.0, 10.0, 10.5)), 50.0);
+/// ```
+///
+/// # Links
+///
+/// * [Average directional movement index, Wikipedia](https://en.wikipedia.org/wiki/Average_directional_movement_index)
+///
+#[doc(alias = "ADXR")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Adxr {
+    adx: AverageDirectionalIndex,
+    history: VecDeque<f64>,
+    // inputs seen, up to the warm-up
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
+}
+
+impl Adxr {
+    pub fn new(period: usize) -> Result<Self> {
+        Self::with_smoothing(period, Smoothing::Wilder)
+    }
+
+    /// Creates an ADXR of an ADX with the given smoothing.
+    pub fn with_smoothing(period: usize, smoothing: Smoothing) -> Result<Self> {
+        Ok(Self {
+            adx: AverageDirectionalIndex::with_smoothing(period, smoothing)?,
+            history: deque(period)?,
+            count: 0,
+        })
+    }
+
+    pub fn smoothing(&self) -> Smoothing {
+        self.adx.smoothing()
+    }
+}
+
+impl Period for Adxr {
+    fn period(&self) -> usize {
+        self.adx.period()
+    }
+}
+
+impl IsReady for Adxr {
+    /// The lagged ADX is _period - 1_ inputs older than the current one, so it is ready
+    /// that many inputs after the ADX.
+    fn bars_remaining(&self) -> usize {
+        let warm_up = 3 * self.period() - 1;
+        warm_up.saturating_sub(self.count)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for Adxr {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let adx = self.adx.next(input).adx;
+        if self.history.len() == self.period() {
+            self.history.pop_front();
+        }
+        self.history.push_back(adx);
+        let lagged = self.history.front().copied().unwrap_or(adx);
+        (adx + lagged) / 2.0
+    }
+}
+
+impl Reset for Adxr {
+    fn reset(&mut self) {
+        self.adx.reset();
+        self.history.clear();
+        self.count = 0;
+    }
+}
+
+impl Default for Adxr {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for Adxr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.smoothing() {
+            Smoothing::Wilder => write!(f, "ADXR({})", self.period()),
+            smoothing => write!(f, "ADXR({}, {})", self.period(), smoothing),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Adxr::new(0).is_err());
+        assert!(Adxr::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let bars = [
+            Bar::new().high(10).low(8).close(9),
+            Bar::new().high(12).low(9).close(11),
+            Bar::new().high(11).low(7).close(8),
+            Bar::new().high(9).low(6).close(6.5),
+        ];
+        // ADX: 0, 100, 50, 41.667
+        let mut adxr = Adxr::new(2).unwrap();
+        let values: Vec<f64> = bars.iter().map(|bar| round(adxr.next(bar))).collect();
+        assert_eq!(values, vec![0.0, 50.0, 75.0, 45.833]);
+
+        let mut adxr = Adxr::new(1).unwrap();
+        let values: Vec<f64> = bars.iter().map(|bar| round(adxr.next(bar))).collect();
+        assert_eq!(values, vec![0.0, 100.0, 100.0, 100.0]);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(10).low(8).close(9);
+        let mut adxr = Adxr::new(3).unwrap();
+        assert_eq!(adxr.bars_remaining(), 8);
+        for _ in 0..6 {
+            adxr.next(&bar);
+        }
+        assert!(adxr.adx.is_ready());
+        assert_eq!(adxr.bars_remaining(), 2);
+        adxr.next(&bar);
+        adxr.next(&bar);
+        assert!(adxr.is_ready());
+
+        adxr.reset();
+        assert_eq!(adxr.bars_remaining(), 8);
+        assert_eq!(Adxr::new(1).unwrap().bars_remaining(), 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut adxr = Adxr::new(2).unwrap();
+        adxr.next(&Bar::new().high(10).low(8).close(9));
+        adxr.next(&Bar::new().high(12).low(9).close(11));
+
+        adxr.reset();
+        assert_eq!(adxr.next(&Bar::new().high(12).low(9).close(11)), 0.0);
+        assert_eq!(adxr.next(&Bar::new().high(11).low(7).close(8)), 50.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Adxr::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let adxr = Adxr::new(14).unwrap();
+        assert_eq!(format!("{}", adxr), "ADXR(14)");
+        let adxr = Adxr::with_smoothing(14, Smoothing::Sma).unwrap();
+        assert_eq!(format!("{}", adxr), "ADXR(14, SMA)");
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Close, High, Low, Next, Reset, Volume};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Anchored volume weighted average price (AVWAP).
+///
+/// Volume weighted average of the typical price since each of several anchors, e.g. the start of
+/// a session, an earnings release or the swing lows of a trend. Anchors are added with
+/// [add_anchor](AnchoredVwap::add_anchor) and cover the inputs from the next one on, until they
+/// are removed with [remove_anchor](AnchoredVwap::remove_anchor).
+///
+/// Returns the identifier and the VWAP of every active anchor, in the order they were added.
+/// While no volume has been traded since an anchor, its VWAP is the latest price.
+///
+/// The input is a bar, or a `(price, volume)` tuple of a trade.
+///
+/// # Formula
+///
+/// Typical Price(TP) = (High + Low + Close) / 3
+///
+/// AVWAP = Σ TP x Volume / Σ Volume, for the bars since the anchor
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::AnchoredVwap;
+/// use ta_panther::Next;
+///
+/// let mut avwap = AnchoredVwap::new();
+/// let session = avwap.add_anchor();
+/// assert_eq!(avwap.next((10.0, 100.0)), vec![(session, 10.0)]);
+///
+/// let swing_low = avwap.add_anchor();
+/// assert_eq!(avwap.next((13.0, 100.0)), vec![(session, 11.5), (swing_low, 13.0)]);
+///
+/// avwap.remove_anchor(session);
+/// assert_eq!(avwap.next((15.0, 300.0)), vec![(swing_low, 14.5)]);
+/// assert_eq!(avwap.vwap(swing_low), Some(14.5));
+/// ```
+///
+/// # Links
+///
+/// * [Volume-weighted average price, Wikipedia](https://en.wikipedia.org/wiki/Volume-weighted_average_price)
+///
+#[doc(alias = "AVWAP")]
+#[doc(alias = "VWAP")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnchoredVwap {
+    next_id: usize,
+    anchors: Vec<Anchor>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Anchor {
+    id: usize,
+    price_volume: f64,
+    volume: f64,
+    vwap: f64,
+}
+
+impl AnchoredVwap {
+    pub fn new() -> Self {
+        Self {
+            next_id: 0,
+            anchors: Vec::new(),
+        }
+    }
+
+    /// Adds an anchor at the next input, returning its identifier.
+    pub fn add_anchor(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.anchors.push(Anchor {
+            id,
+            price_volume: 0.0,
+            volume: 0.0,
+            vwap: f64::NAN,
+        });
+        id
+    }
+
+    /// Removes an anchor, returning whether it was active.
+    pub fn remove_anchor(&mut self, id: usize) -> bool {
+        let len = self.anchors.len();
+        self.anchors.retain(|anchor| anchor.id != id);
+        self.anchors.len() < len
+    }
+
+    /// Returns the identifiers of the active anchors.
+    pub fn anchors(&self) -> impl Iterator<Item = usize> + '_ {
+        self.anchors.iter().map(|anchor| anchor.id)
+    }
+
+    /// Returns the latest VWAP of an anchor, or `None` if it is not active. The VWAP is NaN
+    /// before the first input since the anchor.
+    pub fn vwap(&self, id: usize) -> Option<f64> {
+        self.anchors
+            .iter()
+            .find(|anchor| anchor.id == id)
+            .map(|anchor| anchor.vwap)
+    }
+}
+
+impl Next<(f64, f64)> for AnchoredVwap {
+    type Output = Vec<(usize, f64)>;
+
+    fn next(&mut self, (price, volume): (f64, f64)) -> Self::Output {
+        self.anchors
+            .iter_mut()
+            .map(|anchor| {
+                anchor.price_volume += price * volume;
+                anchor.volume += volume;
+                anchor.vwap = if anchor.volume > 0.0 {
+                    anchor.price_volume / anchor.volume
+                } else {
+                    price
+                };
+                (anchor.id, anchor.vwap)
+            })
+            .collect()
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for AnchoredVwap {
+    type Output = Vec<(usize, f64)>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+        self.next((typical_price, input.volume()))
+    }
+}
+
+impl Reset for AnchoredVwap {
+    fn reset(&mut self) {
+        self.next_id = 0;
+        self.anchors.clear();
+    }
+}
+
+impl Default for AnchoredVwap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AnchoredVwap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AVWAP")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_no_anchors() {
+        let mut avwap = AnchoredVwap::new();
+        assert_eq!(avwap.next((10.0, 100.0)), vec![]);
+    }
+
+    #[test]
+    fn test_anchors() {
+        let mut avwap = AnchoredVwap::new();
+        let first = avwap.add_anchor();
+        let second = avwap.add_anchor();
+        assert_eq!(avwap.vwap(first).map(f64::is_nan), Some(true));
+
+        avwap.next((4.0, 0.0));
+        assert_eq!(avwap.next((2.0, 300.0)), vec![(first, 2.0), (second, 2.0)]);
+
+        let third = avwap.add_anchor();
+        assert_eq!(
+            avwap.next((6.0, 100.0)),
+            vec![(first, 3.0), (second, 3.0), (third, 6.0)]
+        );
+
+        assert!(avwap.remove_anchor(second));
+        assert!(!avwap.remove_anchor(second));
+        assert_eq!(avwap.anchors().collect::<Vec<_>>(), vec![first, third]);
+        assert_eq!(avwap.vwap(second), None);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut avwap = AnchoredVwap::new();
+        let anchor = avwap.add_anchor();
+        avwap.next(&Bar::new().high(4).low(1).close(4).volume(100.0));
+        assert_eq!(
+            avwap.next(&Bar::new().high(6).low(5).close(7).volume(100.0)),
+            vec![(anchor, 4.5)]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut avwap = AnchoredVwap::new();
+        avwap.add_anchor();
+        avwap.next((4.0, 10.0));
+
+        avwap.reset();
+        assert_eq!(avwap.anchors().count(), 0);
+        assert_eq!(avwap.add_anchor(), 0);
+        assert_eq!(avwap.next((2.0, 10.0)), vec![(0, 2.0)]);
+    }
+
+    #[test]
+    fn test_default() {
+        AnchoredVwap::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let avwap = AnchoredVwap::new();
+        assert_eq!(format!("{}", avwap), "AVWAP");
+    }
+}
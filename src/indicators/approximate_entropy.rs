@@ -0,0 +1,285 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::rolling::RollingWindow;
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use crate::state::ring_values;
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+/// Approximate entropy (ApEn).
+///
+/// Quantifies how unpredictable the last _period_ inputs are: it measures how often patterns of
+/// _m_ consecutive values which are close to each other stay close when extended by one more
+/// value. Regular series, like a trend or an oscillation, have an approximate entropy close to
+/// 0, while random series have larger values.
+///
+/// Two values are close if they differ by at most the _tolerance_ times the standard deviation of
+/// the window. The estimate is biased for short windows and may even be slightly negative, so
+/// the window should hold at least a few dozen values. Returns 0 until the window is full.
+///
+/// Every update compares all pairs of patterns in the window and takes _O(period²)_ time.
+///
+/// # Formula
+///
+/// _ApEn = Φ<sub>m</sub>(r) - Φ<sub>m+1</sub>(r)_
+///
+/// Where:
+///
+/// * _Φ<sub>m</sub>(r)_ - average natural logarithm of the fraction of patterns of length _m_
+///   within the distance _r_ of each pattern
+/// * _r_ - _tolerance_ × standard deviation of the window
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than _m_ + 1). Default is 50.
+/// * _m_ - length of the compared patterns (integer greater than 0). Default is 2.
+/// * _tolerance_ - distance of close values in standard deviations (greater than 0.0).
+///   Default is 0.2.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ApproximateEntropy;
+/// use ta_panther::Next;
+///
+/// let mut regular = ApproximateEntropy::new(10, 1, 0.2).unwrap();
+/// let mut irregular = ApproximateEntropy::new(10, 1, 0.2).unwrap();
+/// let (mut a, mut b) = (0.0, 0.0);
+/// for &(x, y) in [(1.0, 3.0), (2.0, 1.0), (1.0, 4.0), (2.0, 1.0), (1.0, 5.0),
+///                 (2.0, 9.0), (1.0, 2.0), (2.0, 6.0), (1.0, 5.0), (2.0, 3.0)].iter() {
+///     a = regular.next(x);
+///     b = irregular.next(y);
+/// }
+/// assert!(a.abs() < 0.01);
+/// assert!(b > 0.3);
+/// ```
+///
+/// # Links
+///
+/// * [Approximate entropy, Wikipedia](https://en.wikipedia.org/wiki/Approximate_entropy)
+///
+#[doc(alias = "APEN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApproximateEntropy {
+    m: usize,
+    tolerance: f64,
+    window: RollingWindow<f64>,
+}
+
+impl ApproximateEntropy {
+    pub fn new(period: usize, m: usize, tolerance: f64) -> Result<Self> {
+        if m == 0 || period <= m.saturating_add(1) || !(tolerance > 0.0 && tolerance.is_finite()) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            m,
+            tolerance,
+            window: RollingWindow::new(period)?,
+        })
+    }
+}
+
+/// Returns the average logarithm of the fraction of patterns of length `m` within the distance
+/// `r` of each pattern.
+fn phi(values: &[f64], m: usize, r: f64) -> f64 {
+    let n = values.len() - m + 1;
+    let sum: f64 = (0..n)
+        .map(|i| {
+            let matches = (0..n)
+                .filter(|&j| (0..m).all(|k| (values[i + k] - values[j + k]).abs() <= r))
+                .count();
+            (matches as f64 / n as f64).ln()
+        })
+        .sum();
+    sum / n as f64
+}
+
+impl Period for ApproximateEntropy {
+    fn period(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+impl Validate for ApproximateEntropy {
+    /// The window is stored as a [RollingWindow] since version 2.
+    const STATE_VERSION: u32 = 2;
+
+    fn validate(&self) -> Result<()> {
+        self.window.validate()
+    }
+
+    #[cfg(feature = "serde")]
+    fn deserialize_version<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        if version >= 2 {
+            return Self::deserialize(deserializer);
+        }
+        let state = StateV1::deserialize(deserializer)?;
+        let values = ring_values(state.period, state.index, state.count, &state.deque)
+            .map_err(D::Error::custom)?;
+        let mut apen =
+            Self::new(state.period, state.m, state.tolerance).map_err(D::Error::custom)?;
+        apen.window.extend(values);
+        Ok(apen)
+    }
+}
+
+/// State of version 1, a ring buffer of the inputs.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StateV1 {
+    period: usize,
+    m: usize,
+    tolerance: f64,
+    index: usize,
+    count: usize,
+    deque: Vec<f64>,
+}
+
+impl IsReady for ApproximateEntropy {
+    fn bars_remaining(&self) -> usize {
+        self.period() - self.window.len()
+    }
+}
+
+impl Next<f64> for ApproximateEntropy {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+        if !self.window.is_full() {
+            return 0.0;
+        }
+
+        let values = self.window.to_vec();
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let r = self.tolerance * variance.sqrt();
+
+        phi(&values, self.m, r) - phi(&values, self.m + 1, r)
+    }
+}
+
+impl<T: Close> Next<&T> for ApproximateEntropy {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ApproximateEntropy {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Default for ApproximateEntropy {
+    fn default() -> Self {
+        Self::new(50, 2, 0.2).unwrap()
+    }
+}
+
+impl fmt::Display for ApproximateEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "APEN({}, {}, {})", self.period(), self.m, self.tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ApproximateEntropy);
+
+    #[test]
+    fn test_new() {
+        assert!(ApproximateEntropy::new(10, 0, 0.2).is_err());
+        assert!(ApproximateEntropy::new(3, 2, 0.2).is_err());
+        assert!(ApproximateEntropy::new(10, 2, 0.0).is_err());
+        assert!(ApproximateEntropy::new(10, 2, f64::NAN).is_err());
+        assert!(ApproximateEntropy::new(4, 2, 0.2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut apen = ApproximateEntropy::new(6, 1, 0.5).unwrap();
+        for &value in &[1.0, 2.0, 1.0, 2.0, 1.0] {
+            assert_eq!(apen.next(value), 0.0);
+        }
+        assert_eq!(round(apen.next(2.0)), -0.02);
+        assert_eq!(round(apen.next(3.0)), 0.044);
+        assert_eq!(round(apen.next(1.0)), 0.321);
+        assert_eq!(round(apen.next(4.0)), 0.28);
+    }
+
+    #[test]
+    fn test_constant() {
+        let mut apen = ApproximateEntropy::new(5, 2, 0.2).unwrap();
+        for _ in 0..10 {
+            assert_eq!(apen.next(3.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut apen = ApproximateEntropy::new(3, 1, 0.2).unwrap();
+        apen.next(&Bar::new().close(2));
+        apen.next(&Bar::new().close(2));
+        assert_eq!(apen.next(&Bar::new().close(2)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut apen = ApproximateEntropy::new(3, 1, 0.2).unwrap();
+        apen.next(1.0);
+        apen.next(5.0);
+        apen.next(2.0);
+
+        apen.reset();
+        assert_eq!(apen.next(1.0), 0.0);
+        assert_eq!(apen.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ApproximateEntropy::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let apen = ApproximateEntropy::new(30, 2, 0.25).unwrap();
+        assert_eq!(format!("{}", apen), "APEN(30, 2, 0.25)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_migrate() {
+        use crate::state::Versioned;
+
+        let json = r#"{"version":1,"state":{"period":4,"m":1,"tolerance":0.5,"index":2,"count":2,"deque":[3.0,1.0,0.0,0.0]}}"#;
+        let Versioned(mut restored) =
+            serde_json::from_str::<Versioned<ApproximateEntropy>>(json).unwrap();
+        assert_eq!(restored.to_string(), "APEN(4, 1, 0.5)");
+        assert_eq!(restored.bars_remaining(), 2);
+
+        let mut apen = ApproximateEntropy::new(4, 1, 0.5).unwrap();
+        apen.next(3.0);
+        apen.next(1.0);
+        for input in [2.0, 4.0, 1.0] {
+            assert_eq!(restored.next(input), apen.next(input));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, CoMoments};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling autocorrelation.
+///
+/// Returns the correlation between the inputs and the inputs _lag_ periods earlier, over the last
+/// _period_ such pairs. Positive values indicate persistence (trending), negative values indicate
+/// mean reversion. Usually applied to returns rather than prices. Returns 0 until two pairs have
+/// been seen, or while the window is constant.
+///
+/// The co-moments are updated incrementally as pairs enter and leave the window, so every update
+/// is O(1).
+///
+/// # Formula
+///
+/// _ACF = corr(x<sub>t</sub>, x<sub>t-lag</sub>)_
+///
+/// # Parameters
+///
+/// * _lag_ - distance of the correlated inputs (integer greater than 0). Default is 1.
+/// * _period_ - number of pairs (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Autocorrelation;
+/// use ta_panther::Next;
+///
+/// let mut lag1 = Autocorrelation::new(1, 5).unwrap();
+/// let mut lag2 = Autocorrelation::new(2, 5).unwrap();
+/// for &value in [1.0, 3.0, 1.0, 3.0, 1.0, 3.0].iter() {
+///     lag1.next(value);
+///     lag2.next(value);
+/// }
+/// assert_eq!(lag1.next(1.0), -1.0);
+/// assert_eq!(lag2.next(1.0), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Autocorrelation, Wikipedia](https://en.wikipedia.org/wiki/Autocorrelation)
+///
+#[doc(alias = "ACF")]
+#[doc(alias = "AUTOCORR")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Autocorrelation {
+    lag: usize,
+    period: usize,
+    index: usize,
+    count: usize,
+    moments: CoMoments,
+    deque: Box<[(f64, f64)]>,
+    lag_index: usize,
+    lag_count: usize,
+    lagged: Box<[f64]>,
+}
+
+impl Autocorrelation {
+    pub fn new(lag: usize, period: usize) -> Result<Self> {
+        if lag == 0 || period == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            lag,
+            period,
+            index: 0,
+            count: 0,
+            moments: CoMoments::default(),
+            deque: ring((0.0, 0.0), period)?,
+            lag_index: 0,
+            lag_count: 0,
+            lagged: ring(0.0, lag)?,
+        })
+    }
+
+    pub fn lag(&self) -> usize {
+        self.lag
+    }
+}
+
+impl Period for Autocorrelation {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for Autocorrelation {
+    fn bars_remaining(&self) -> usize {
+        self.lag.saturating_sub(self.lag_count) + self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for Autocorrelation {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.lag_count < self.lag {
+            self.lag_count += 1;
+        } else {
+            let lagged = self.lagged[self.lag_index];
+            if self.count < self.period {
+                self.count += 1;
+            } else {
+                let (old, old_lagged) = self.deque[self.index];
+                self.moments.pop(old, old_lagged);
+            }
+            self.moments.push(input, lagged);
+
+            self.deque[self.index] = (input, lagged);
+            self.index = if self.index + 1 < self.period {
+                self.index + 1
+            } else {
+                0
+            };
+        }
+
+        self.lagged[self.lag_index] = input;
+        self.lag_index = if self.lag_index + 1 < self.lag {
+            self.lag_index + 1
+        } else {
+            0
+        };
+
+        self.moments.correlation()
+    }
+}
+
+impl<T: Close> Next<&T> for Autocorrelation {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for Autocorrelation {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.moments = CoMoments::default();
+        for i in 0..self.period {
+            self.deque[i] = (0.0, 0.0);
+        }
+        self.lag_index = 0;
+        self.lag_count = 0;
+        for i in 0..self.lag {
+            self.lagged[i] = 0.0;
+        }
+    }
+}
+
+impl Default for Autocorrelation {
+    fn default() -> Self {
+        Self::new(1, 20).unwrap()
+    }
+}
+
+impl fmt::Display for Autocorrelation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ACF({}, {})", self.lag, self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Autocorrelation);
+
+    #[test]
+    fn test_new() {
+        assert!(Autocorrelation::new(0, 5).is_err());
+        assert!(Autocorrelation::new(1, 0).is_err());
+        assert!(Autocorrelation::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut acf = Autocorrelation::new(1, 4).unwrap();
+        assert_eq!(acf.next(1.0), 0.0);
+        assert_eq!(acf.next(3.0), 0.0);
+        assert_eq!(round(acf.next(2.0)), -1.0);
+        assert_eq!(round(acf.next(5.0)), -0.327);
+        assert_eq!(round(acf.next(4.0)), 0.076);
+        assert_eq!(round(acf.next(6.0)), 0.076);
+        assert_eq!(round(acf.next(5.0)), -0.239);
+        assert_eq!(round(acf.next(8.0)), -0.239);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut acf = Autocorrelation::new(1, 3).unwrap();
+        acf.next(&Bar::new().close(1));
+        acf.next(&Bar::new().close(2));
+        assert_eq!(acf.next(&Bar::new().close(3)), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut acf = Autocorrelation::new(2, 3).unwrap();
+        acf.next(1.0);
+        acf.next(2.0);
+        acf.next(4.0);
+        acf.next(3.0);
+
+        acf.reset();
+        assert_eq!(acf.next(5.0), 0.0);
+        assert_eq!(acf.next(6.0), 0.0);
+        assert_eq!(acf.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Autocorrelation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let acf = Autocorrelation::new(2, 30).unwrap();
+        assert_eq!(format!("{}", acf), "ACF(2, 30)");
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut acf = Autocorrelation::new(1, 3).unwrap();
+        assert_eq!(acf.bars_remaining(), 4);
+        for &x in [1.0, 2.0, 3.0].iter() {
+            acf.next(x);
+        }
+        assert_eq!(acf.bars_remaining(), 1);
+        acf.next(4.0);
+        assert!(acf.is_ready());
+    }
+}
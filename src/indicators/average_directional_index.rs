@@ -0,0 +1,302 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::smoothing::Smoother;
+use crate::indicators::{Smoothing, TrueRange};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Average directional index (ADX).
+///
+/// Trend strength indicator developed by J. Welles Wilder. It ranges from 0 to 100 regardless
+/// of the direction of the trend, values above 25 are commonly read as a trending market.
+///
+/// Returns the ADX along with the positive and negative directional indicators (+DI and −DI)
+/// it is derived from, whose crossovers signal the direction of the trend.
+///
+/// # Formula
+///
+/// +DM = High - Previous High, if it is greater than Previous Low - Low and than 0, else 0
+///
+/// −DM = Previous Low - Low, if it is greater than High - Previous High and than 0, else 0
+///
+/// +DI = 100 x Smoothed(+DM) / Smoothed(TR)
+///
+/// −DI = 100 x Smoothed(−DM) / Smoothed(TR)
+///
+/// DX = 100 x |+DI - −DI| / (+DI + −DI)
+///
+/// ADX = Smoothed(DX)
+///
+/// Where _TR_ is the [true range](crate::indicators::TrueRange). The smoothing is Wilder's by
+/// default, [with_smoothing](Self::with_smoothing) selects another [Smoothing]. The first bar
+/// has no directional movement and returns 0 for every output.
+///
+/// # Parameters
+///
+/// * _period_ - smoothing period (integer greater than 0). Default is 14.
+/// * _smoothing_ - smoothing of the averages. Default is [Smoothing::Wilder].
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::AverageDirectionalIndex;
+/// use ta_panther::{DataItem, Next};
+///
+/// let mut adx = AverageDirectionalIndex::new(14).unwrap();
+/// let bar = |high, low, close| {
+///     DataItem::builder()
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .open(close)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+/// assert_eq!(adx.next(&bar(10.0, 9.0, 10.0)).adx, 0.0);
+///
+/// let out = adx.next(&bar(11.0, 10.0, 10.5));
+/// assert_eq!(out.adx, 100.0);
+/// assert_eq!(out.plus_di, 100.0);
+/// assert_eq!(out.minus_di, 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Average directional movement index, Wikipedia](https://en.wikipedia.org/wiki/Average_directional_movement_index)
+///
+#[doc(alias = "ADX")]
+#[doc(alias = "DMI")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AverageDirectionalIndex {
+    true_range: TrueRange,
+    plus_dm: Smoother,
+    minus_dm: Smoother,
+    tr: Smoother,
+    dx: Smoother,
+    previous: Option<(f64, f64)>,
+    // inputs seen, up to the warm-up
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AverageDirectionalIndexOutput {
+    pub adx: f64,
+    pub plus_di: f64,
+    pub minus_di: f64,
+}
+
+impl From<AverageDirectionalIndexOutput> for (f64, f64, f64) {
+    fn from(ao: AverageDirectionalIndexOutput) -> Self {
+        (ao.adx, ao.plus_di, ao.minus_di)
+    }
+}
+
+impl AverageDirectionalIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        Self::with_smoothing(period, Smoothing::Wilder)
+    }
+
+    /// Creates an ADX averaging the directional movement, the true range and the DX with the
+    /// given smoothing.
+    pub fn with_smoothing(period: usize, smoothing: Smoothing) -> Result<Self> {
+        Ok(Self {
+            true_range: TrueRange::new(),
+            plus_dm: Smoother::new(smoothing, period)?,
+            minus_dm: Smoother::new(smoothing, period)?,
+            tr: Smoother::new(smoothing, period)?,
+            dx: Smoother::new(smoothing, period)?,
+            previous: None,
+            count: 0,
+        })
+    }
+
+    pub fn smoothing(&self) -> Smoothing {
+        self.dx.smoothing()
+    }
+}
+
+impl Period for AverageDirectionalIndex {
+    fn period(&self) -> usize {
+        self.dx.period()
+    }
+}
+
+impl IsReady for AverageDirectionalIndex {
+    /// The directional indicators are smoothed over _period_ movements, which start at the
+    /// second input, and the DX over another _period_.
+    fn bars_remaining(&self) -> usize {
+        (2 * self.period()).saturating_sub(self.count)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for AverageDirectionalIndex {
+    type Output = AverageDirectionalIndexOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let true_range = self.true_range.next(input);
+        let previous = self.previous.replace((input.high(), input.low()));
+        let (previous_high, previous_low) = match previous {
+            Some(previous) => previous,
+            None => {
+                return AverageDirectionalIndexOutput {
+                    adx: 0.0,
+                    plus_di: 0.0,
+                    minus_di: 0.0,
+                }
+            }
+        };
+
+        let up = input.high() - previous_high;
+        let down = previous_low - input.low();
+        let plus_dm = if up > down && up > 0.0 { up } else { 0.0 };
+        let minus_dm = if down > up && down > 0.0 { down } else { 0.0 };
+
+        let plus_dm = self.plus_dm.next(plus_dm);
+        let minus_dm = self.minus_dm.next(minus_dm);
+        let true_range = self.tr.next(true_range);
+        let (plus_di, minus_di) = if true_range > 0.0 {
+            (100.0 * plus_dm / true_range, 100.0 * minus_dm / true_range)
+        } else {
+            (0.0, 0.0)
+        };
+
+        let dx = if plus_di + minus_di > 0.0 {
+            100.0 * (plus_di - minus_di).abs() / (plus_di + minus_di)
+        } else {
+            0.0
+        };
+        AverageDirectionalIndexOutput {
+            adx: self.dx.next(dx),
+            plus_di,
+            minus_di,
+        }
+    }
+}
+
+impl Reset for AverageDirectionalIndex {
+    fn reset(&mut self) {
+        self.true_range.reset();
+        self.plus_dm.reset();
+        self.minus_dm.reset();
+        self.tr.reset();
+        self.dx.reset();
+        self.previous = None;
+        self.count = 0;
+    }
+}
+
+impl Default for AverageDirectionalIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for AverageDirectionalIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.smoothing() {
+            Smoothing::Wilder => write!(f, "ADX({})", self.period()),
+            smoothing => write!(f, "ADX({}, {})", self.period(), smoothing),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn round3(nums: (f64, f64, f64)) -> (f64, f64, f64) {
+        (round(nums.0), round(nums.1), round(nums.2))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(AverageDirectionalIndex::new(0).is_err());
+        assert!(AverageDirectionalIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut adx = AverageDirectionalIndex::new(2).unwrap();
+        let out = adx.next(&Bar::new().high(10).low(8).close(9));
+        assert_eq!(round3(out.into()), (0.0, 0.0, 0.0));
+        let out = adx.next(&Bar::new().high(12).low(9).close(11));
+        assert_eq!(round3(out.into()), (100.0, 66.667, 0.0));
+        let out = adx.next(&Bar::new().high(11).low(7).close(8));
+        assert_eq!(round3(out.into()), (50.0, 28.571, 28.571));
+        let out = adx.next(&Bar::new().high(9).low(6).close(6.5));
+        assert_eq!(round3(out.into()), (41.667, 15.385, 30.769));
+    }
+
+    #[test]
+    fn test_smoothing() {
+        let mut adx = AverageDirectionalIndex::with_smoothing(2, Smoothing::Sma).unwrap();
+        assert_eq!(adx.smoothing(), Smoothing::Sma);
+        adx.next(&Bar::new().high(10).low(8).close(9));
+        adx.next(&Bar::new().high(12).low(9).close(11));
+        adx.next(&Bar::new().high(11).low(7).close(8));
+        assert_eq!(
+            round(adx.next(&Bar::new().high(9).low(6).close(6.5)).adx),
+            50.0
+        );
+    }
+
+    #[test]
+    fn test_no_range() {
+        let mut adx = AverageDirectionalIndex::new(2).unwrap();
+        adx.next(&Bar::new().high(10).low(10).close(10));
+        let out = adx.next(&Bar::new().high(10).low(10).close(10));
+        assert_eq!(round3(out.into()), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(10).low(8).close(9);
+        let mut adx = AverageDirectionalIndex::new(3).unwrap();
+        assert_eq!(adx.bars_remaining(), 6);
+        for _ in 0..5 {
+            adx.next(&bar);
+        }
+        assert_eq!(adx.bars_remaining(), 1);
+        adx.next(&bar);
+        assert!(adx.is_ready());
+
+        adx.reset();
+        assert_eq!(adx.bars_remaining(), 6);
+        assert_eq!(AverageDirectionalIndex::new(1).unwrap().bars_remaining(), 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut adx = AverageDirectionalIndex::new(2).unwrap();
+        adx.next(&Bar::new().high(10).low(8).close(9));
+        adx.next(&Bar::new().high(12).low(9).close(11));
+
+        adx.reset();
+        assert_eq!(adx.next(&Bar::new().high(12).low(9).close(11)).adx, 0.0);
+        assert_eq!(adx.next(&Bar::new().high(11).low(7).close(8)).adx, 100.0);
+    }
+
+    #[test]
+    fn test_default() {
+        AverageDirectionalIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let adx = AverageDirectionalIndex::new(14).unwrap();
+        assert_eq!(format!("{}", adx), "ADX(14)");
+        let adx = AverageDirectionalIndex::with_smoothing(14, Smoothing::Ema).unwrap();
+        assert_eq!(format!("{}", adx), "ADX(14, EMA)");
+    }
+}
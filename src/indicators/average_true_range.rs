@@ -1,16 +1,20 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
-use crate::indicators::{ExponentialMovingAverage, TrueRange};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::indicators::smoothing::Smoother;
+use crate::indicators::{Smoothing, TrueRange};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Average true range (ATR).
 ///
 /// A technical analysis volatility indicator, originally developed by J. Welles Wilder.
 /// The average true range is an N-day smoothed moving average of the true range values.
-/// This implementation uses exponential moving average.
+/// This implementation uses exponential moving average by default,
+/// [with_smoothing](Self::with_smoothing) selects another [Smoothing], e.g. Wilder's original
+/// smoothing.
 ///
 /// # Formula
 ///
@@ -24,6 +28,7 @@ use serde::{Deserialize, Serialize};
 /// # Parameters
 ///
 /// * _period_ - smoothing period of EMA (integer greater than 0)
+/// * _smoothing_ - smoothing of the true range. Default is [Smoothing::Ema].
 ///
 /// # Example
 ///
@@ -56,24 +61,40 @@ use serde::{Deserialize, Serialize};
 ///     }
 /// }
 #[doc(alias = "ATR")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AverageTrueRange {
     true_range: TrueRange,
-    ema: ExponentialMovingAverage,
+    average: Smoother,
 }
 
 impl AverageTrueRange {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_smoothing(period, Smoothing::Ema)
+    }
+
+    /// Creates an ATR averaging the true range with the given smoothing.
+    pub fn with_smoothing(period: usize, smoothing: Smoothing) -> Result<Self> {
         Ok(Self {
             true_range: TrueRange::new(),
-            ema: ExponentialMovingAverage::new(period)?,
+            average: Smoother::new(smoothing, period)?,
         })
     }
+
+    pub fn smoothing(&self) -> Smoothing {
+        self.average.smoothing()
+    }
 }
 
 impl Period for AverageTrueRange {
     fn period(&self) -> usize {
-        self.ema.period()
+        self.average.period()
+    }
+}
+
+impl IsReady for AverageTrueRange {
+    fn bars_remaining(&self) -> usize {
+        self.average.bars_remaining()
     }
 }
 
@@ -81,7 +102,7 @@ impl Next<f64> for AverageTrueRange {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        self.ema.next(self.true_range.next(input))
+        self.average.next(self.true_range.next(input))
     }
 }
 
@@ -89,14 +110,14 @@ impl<T: High + Low + Close> Next<&T> for AverageTrueRange {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.ema.next(self.true_range.next(input))
+        self.average.next(self.true_range.next(input))
     }
 }
 
 impl Reset for AverageTrueRange {
     fn reset(&mut self) {
         self.true_range.reset();
-        self.ema.reset();
+        self.average.reset();
     }
 }
 
@@ -108,7 +129,10 @@ impl Default for AverageTrueRange {
 
 impl fmt::Display for AverageTrueRange {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ATR({})", self.ema.period())
+        match self.smoothing() {
+            Smoothing::Ema => write!(f, "ATR({})", self.average.period()),
+            smoothing => write!(f, "ATR({}, {})", self.average.period(), smoothing),
+        }
     }
 }
 
@@ -137,6 +161,43 @@ mod tests {
         assert_eq!(atr.next(&bar3), 3.375);
     }
 
+    #[test]
+    fn test_smoothing() {
+        let bar1 = Bar::new().high(10).low(7.5).close(9);
+        let bar2 = Bar::new().high(11).low(9).close(9.5);
+        let bar3 = Bar::new().high(9).low(5).close(8);
+        let bar4 = Bar::new().high(9).low(8).close(8.5);
+
+        let mut atr = AverageTrueRange::with_smoothing(3, Smoothing::Wilder).unwrap();
+        assert_eq!(atr.smoothing(), Smoothing::Wilder);
+        assert_eq!(atr.next(&bar1), 2.5);
+        assert_eq!(atr.next(&bar2), 2.25);
+        assert_eq!(atr.next(&bar3), 3.0);
+        assert_eq!(round(atr.next(&bar4)), 2.333);
+
+        let mut atr = AverageTrueRange::with_smoothing(2, Smoothing::Sma).unwrap();
+        atr.next(&bar1);
+        atr.next(&bar2);
+        assert_eq!(atr.next(&bar3), 3.25);
+        assert_eq!(atr.next(&bar4), 2.75);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(10).low(7.5).close(9);
+        for smoothing in Smoothing::ALL {
+            let mut atr = AverageTrueRange::with_smoothing(3, smoothing).unwrap();
+            assert_eq!(atr.bars_remaining(), 3);
+            atr.next(&bar);
+            atr.next(&bar);
+            assert_eq!(atr.bars_remaining(), 1);
+            atr.next(&bar);
+            assert!(atr.is_ready());
+            atr.reset();
+            assert_eq!(atr.bars_remaining(), 3);
+        }
+    }
+
     #[test]
     fn test_reset() {
         let mut atr = AverageTrueRange::new(9).unwrap();
@@ -161,5 +222,7 @@ mod tests {
     fn test_display() {
         let indicator = AverageTrueRange::new(8).unwrap();
         assert_eq!(format!("{}", indicator), "ATR(8)");
+        let indicator = AverageTrueRange::with_smoothing(14, Smoothing::Wilder).unwrap();
+        assert_eq!(format!("{}", indicator), "ATR(14, WILDER)");
     }
 }
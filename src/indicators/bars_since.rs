@@ -0,0 +1,102 @@
+use core::fmt;
+
+use crate::{Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Bars since.
+///
+/// Counts the inputs since a condition was last true, e.g. since the RSI crossed below 30. The
+/// output is 0 on an input where the condition is true and `None` until it has been true once.
+/// Screener rules like "RSI crossed 30 within the last 5 bars" become `bars_since <= Some(4)`.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{BarsSince, CrossBelow};
+/// use ta_panther::Next;
+///
+/// let mut cross = CrossBelow::new(30.0).unwrap();
+/// let mut since = BarsSince::new();
+///
+/// assert_eq!(since.next(cross.next(35.0)), None);
+/// assert_eq!(since.next(cross.next(28.0)), Some(0));
+/// assert_eq!(since.next(cross.next(25.0)), Some(1));
+/// assert_eq!(since.next(cross.next(33.0)), Some(2));
+/// ```
+///
+#[doc(alias = "BARSSINCE")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BarsSince {
+    bars: Option<usize>,
+}
+
+impl BarsSince {
+    pub fn new() -> Self {
+        Self { bars: None }
+    }
+}
+
+impl Next<bool> for BarsSince {
+    type Output = Option<usize>;
+
+    fn next(&mut self, condition: bool) -> Self::Output {
+        self.bars = if condition {
+            Some(0)
+        } else {
+            self.bars.map(|bars| bars + 1)
+        };
+        self.bars
+    }
+}
+
+impl Reset for BarsSince {
+    fn reset(&mut self) {
+        self.bars = None;
+    }
+}
+
+impl fmt::Display for BarsSince {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BARS_SINCE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_next() {
+        let mut since = BarsSince::new();
+        assert_eq!(since.next(false), None);
+        assert_eq!(since.next(true), Some(0));
+        assert_eq!(since.next(true), Some(0));
+        assert_eq!(since.next(false), Some(1));
+        assert_eq!(since.next(false), Some(2));
+        assert_eq!(since.next(true), Some(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut since = BarsSince::new();
+        since.next(true);
+        since.next(false);
+
+        since.reset();
+        assert_eq!(since.next(false), None);
+    }
+
+    #[test]
+    fn test_default() {
+        BarsSince::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", BarsSince::new()), "BARS_SINCE");
+    }
+}
@@ -0,0 +1,157 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::rolling::RollingCoMoments;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling beta.
+///
+/// Measures the sensitivity of an asset to a benchmark over the last _period_ pairs of inputs:
+/// a beta of 2 means the asset moved on average twice as much as the benchmark. The inputs are
+/// pairs `(asset, benchmark)`, usually returns rather than prices. The value is also the hedge
+/// ratio which minimizes the variance of `asset - beta * benchmark`. Returns 0 while the
+/// benchmark is constant.
+///
+/// The co-moments are updated incrementally as pairs enter and leave the window, so every update
+/// is O(1).
+///
+/// # Formula
+///
+/// _BETA = cov(asset, benchmark) / var(benchmark)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Beta;
+/// use ta_panther::Next;
+///
+/// let mut beta = Beta::new(3).unwrap();
+/// beta.next((0.02, 0.01));
+/// beta.next((-0.04, -0.02));
+/// assert_eq!(beta.next((0.06, 0.03)), 2.0);
+/// ```
+///
+/// # Links
+///
+/// * [Beta (finance), Wikipedia](https://en.wikipedia.org/wiki/Beta_(finance))
+///
+#[doc(alias = "BETA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Beta {
+    moments: RollingCoMoments,
+}
+
+impl Beta {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            moments: RollingCoMoments::new(period)?,
+        })
+    }
+}
+
+impl Period for Beta {
+    fn period(&self) -> usize {
+        self.moments.capacity()
+    }
+}
+
+impl IsReady for Beta {
+    fn bars_remaining(&self) -> usize {
+        self.period() - self.moments.len()
+    }
+}
+
+impl Next<(f64, f64)> for Beta {
+    type Output = f64;
+
+    fn next(&mut self, (asset, benchmark): (f64, f64)) -> Self::Output {
+        self.moments.push((asset, benchmark));
+        self.moments.beta()
+    }
+}
+
+impl<T: Close> Next<(&T, &T)> for Beta {
+    type Output = f64;
+
+    fn next(&mut self, (asset, benchmark): (&T, &T)) -> Self::Output {
+        self.next((asset.close(), benchmark.close()))
+    }
+}
+
+impl Reset for Beta {
+    fn reset(&mut self) {
+        self.moments.clear();
+    }
+}
+
+impl Default for Beta {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for Beta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BETA({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Beta::new(0).is_err());
+        assert!(Beta::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut beta = Beta::new(3).unwrap();
+        assert_eq!(beta.next((1.0, 5.0)), 0.0);
+        assert_eq!(round(beta.next((2.0, 3.0))), -0.5);
+        assert_eq!(round(beta.next((4.0, 4.0))), -0.5);
+        assert_eq!(round(beta.next((3.0, 6.0))), 0.214);
+        assert_eq!(round(beta.next((3.0, 6.0))), -0.5);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut beta = Beta::new(2).unwrap();
+        beta.next((&Bar::new().close(1), &Bar::new().close(4)));
+        let output = beta.next((&Bar::new().close(7), &Bar::new().close(6)));
+        assert_eq!(round(output), 3.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut beta = Beta::new(3).unwrap();
+        beta.next((1.0, 2.0));
+        beta.next((2.0, 4.0));
+
+        beta.reset();
+        assert_eq!(beta.next((1.0, 1.0)), 0.0);
+        assert_eq!(round(beta.next((0.0, 2.0))), -1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Beta::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let beta = Beta::new(10).unwrap();
+        assert_eq!(format!("{}", beta), "BETA(10)");
+    }
+}
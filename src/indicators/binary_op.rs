@@ -0,0 +1,200 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::{Result, TaError};
+use crate::{Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Operator of a [BinaryOp].
+///
+/// Parsed case-insensitively from its [Display](fmt::Display) name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operator {
+    /// _a + b_
+    #[default]
+    Add,
+    /// _a - b_
+    Sub,
+    /// _a * b_
+    Mul,
+    /// _a / b_, infinite or NaN if _b_ is 0.
+    Div,
+    /// Larger of _a_ and _b_.
+    Max,
+    /// Smaller of _a_ and _b_.
+    Min,
+}
+
+impl Operator {
+    /// All the operators.
+    pub const ALL: [Operator; 6] = [
+        Operator::Add,
+        Operator::Sub,
+        Operator::Mul,
+        Operator::Div,
+        Operator::Max,
+        Operator::Min,
+    ];
+
+    /// Applies the operator to `a` and `b`.
+    pub fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            Operator::Add => a + b,
+            Operator::Sub => a - b,
+            Operator::Mul => a * b,
+            Operator::Div => a / b,
+            Operator::Max | Operator::Min if a.is_nan() || b.is_nan() => f64::NAN,
+            Operator::Max => a.max(b),
+            Operator::Min => a.min(b),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Operator::Add => "ADD",
+            Operator::Sub => "SUB",
+            Operator::Mul => "MUL",
+            Operator::Div => "DIV",
+            Operator::Max => "MAX",
+            Operator::Min => "MIN",
+        }
+    }
+}
+
+impl FromStr for Operator {
+    type Err = TaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Operator::ALL
+            .iter()
+            .copied()
+            .find(|operator| operator.name().eq_ignore_ascii_case(s))
+            .ok_or(TaError::InvalidParameter)
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Binary operation.
+///
+/// Combines two series element by element with an arithmetic [Operator], e.g. the difference of
+/// two moving averages or the larger of two stops. Together with [Compare] and [IfThenElse] it
+/// builds indicator formulas out of the outputs of other indicators.
+///
+/// A NaN operand gives NaN for every operator, including the maximum and the minimum.
+///
+/// [Compare]: crate::indicators::Compare
+/// [IfThenElse]: crate::indicators::IfThenElse
+///
+/// # Parameters
+///
+/// * _operator_ - the [Operator]. Default is [Add](Operator::Add).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{BinaryOp, Operator};
+/// use ta_panther::Next;
+///
+/// let mut spread = BinaryOp::new(Operator::Sub);
+/// assert_eq!(spread.next((12.0, 10.5)), 1.5);
+///
+/// let mut highest = BinaryOp::new(Operator::Max);
+/// assert_eq!(highest.next((12.0, 10.5)), 12.0);
+/// ```
+///
+#[doc(alias = "ADD")]
+#[doc(alias = "SUB")]
+#[doc(alias = "MUL")]
+#[doc(alias = "DIV")]
+#[doc(alias = "MAX")]
+#[doc(alias = "MIN")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BinaryOp {
+    operator: Operator,
+}
+
+impl BinaryOp {
+    pub fn new(operator: Operator) -> Self {
+        Self { operator }
+    }
+
+    pub fn operator(&self) -> Operator {
+        self.operator
+    }
+}
+
+impl Next<(f64, f64)> for BinaryOp {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        self.operator.apply(a, b)
+    }
+}
+
+impl Reset for BinaryOp {
+    fn reset(&mut self) {}
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.operator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_next() {
+        let cases = [
+            (Operator::Add, 7.0),
+            (Operator::Sub, 3.0),
+            (Operator::Mul, 10.0),
+            (Operator::Div, 2.5),
+            (Operator::Max, 5.0),
+            (Operator::Min, 2.0),
+        ];
+        for &(operator, expected) in cases.iter() {
+            let mut op = BinaryOp::new(operator);
+            assert_eq!(op.next((5.0, 2.0)), expected);
+        }
+    }
+
+    #[test]
+    fn test_nan_and_zero() {
+        assert!(BinaryOp::new(Operator::Max).next((f64::NAN, 1.0)).is_nan());
+        assert!(BinaryOp::new(Operator::Min).next((1.0, f64::NAN)).is_nan());
+        assert_eq!(BinaryOp::new(Operator::Div).next((1.0, 0.0)), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_operator_from_str() {
+        for operator in Operator::ALL.iter() {
+            assert_eq!(operator.to_string().parse::<Operator>(), Ok(*operator));
+        }
+        assert_eq!("max".parse::<Operator>(), Ok(Operator::Max));
+        assert!("pow".parse::<Operator>().is_err());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(BinaryOp::default().operator(), Operator::Add);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", BinaryOp::new(Operator::Div)), "DIV");
+    }
+}
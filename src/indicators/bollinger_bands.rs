@@ -1,10 +1,37 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
-use crate::indicators::StandardDeviation as Sd;
-use crate::{Close, Next, Period, Reset};
+use crate::indicators::{MeanAbsoluteDeviation as Mad, StandardDeviation as Sd};
+use crate::{Close, IsReady, Next, Period, Reset};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// Measure of the deviation of [BollingerBands].
+///
+/// Charting platforms differ in the deviation used for the width of the bands, choosing the
+/// same one is necessary to reproduce their values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeviationType {
+    /// Population standard deviation, dividing by _n_, as originally defined by Bollinger.
+    #[default]
+    Population,
+    /// Sample standard deviation, dividing by _n - 1_.
+    Sample,
+    /// [Mean absolute deviation](crate::indicators::MeanAbsoluteDeviation) from the average.
+    MeanAbsolute,
+}
+
+impl fmt::Display for DeviationType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeviationType::Population => f.write_str("POPULATION"),
+            DeviationType::Sample => f.write_str("SAMPLE"),
+            DeviationType::MeanAbsolute => f.write_str("MAD"),
+        }
+    }
+}
+
 /// A Bollinger Bands (BB).
 /// (BB).
 /// It is a type of infinite impulse response filter that calculates Bollinger Bands using Exponential Moving Average.
@@ -20,6 +47,11 @@ use serde::{Deserialize, Serialize};
 ///  * _BB<sub>Upper Band</sub>_ = SMA + SD of observation * multipler (usually 2.0)
 ///  * _BB<sub>Lower Band</sub>_ = SMA - SD of observation * multipler (usually 2.0)
 ///
+/// The SD is the population standard deviation by default, [with_deviation](Self::with_deviation)
+/// selects another [DeviationType]. See [BollingerPercentB](crate::indicators::BollingerPercentB)
+/// and [BollingerBandwidth](crate::indicators::BollingerBandwidth) for the position of the input
+/// within the bands and their width.
+///
 /// # Example
 ///
 ///```
@@ -45,14 +77,20 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Bollinger Bands, Wikipedia](https://en.wikipedia.org/wiki/Bollinger_Bands)
 #[doc(alias = "BB")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BollingerBands {
     period: usize,
     multiplier: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    deviation: DeviationType,
     sd: Sd,
+    #[cfg_attr(feature = "serde", serde(default))]
+    mad: Option<Mad>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BollingerBandsOutput {
     pub average: f64,
     pub upper: f64,
@@ -61,16 +99,45 @@ pub struct BollingerBandsOutput {
 
 impl BollingerBands {
     pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        Self::with_deviation(period, multiplier, DeviationType::Population)
+    }
+
+    /// Creates Bollinger Bands `multiplier` times the given measure of deviation away from the
+    /// average.
+    pub fn with_deviation(
+        period: usize,
+        multiplier: f64,
+        deviation: DeviationType,
+    ) -> Result<Self> {
+        let mad = match deviation {
+            DeviationType::MeanAbsolute => Some(Mad::new(period)?),
+            _ => None,
+        };
         Ok(Self {
             period,
             multiplier,
+            deviation,
             sd: Sd::new(period)?,
+            mad,
         })
     }
 
     pub fn multiplier(&self) -> f64 {
         self.multiplier
     }
+
+    pub fn deviation(&self) -> DeviationType {
+        self.deviation
+    }
+
+    /// Writes the parameters, e.g. `(20, 2)`, for the displays of the derived indicators.
+    pub(super) fn fmt_params(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}", self.period, self.multiplier)?;
+        if self.deviation != DeviationType::Population {
+            write!(f, ", {}", self.deviation)?;
+        }
+        write!(f, ")")
+    }
 }
 
 impl Period for BollingerBands {
@@ -79,12 +146,23 @@ impl Period for BollingerBands {
     }
 }
 
+impl IsReady for BollingerBands {
+    fn bars_remaining(&self) -> usize {
+        self.sd.bars_remaining()
+    }
+}
+
 impl Next<f64> for BollingerBands {
     type Output = BollingerBandsOutput;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        let sd = self.sd.next(input);
+        let population = self.sd.next(input);
         let mean = self.sd.mean();
+        let sd = match (self.deviation, self.mad.as_mut()) {
+            (DeviationType::Sample, _) => self.sd.sample(),
+            (DeviationType::MeanAbsolute, Some(mad)) => mad.next(input),
+            _ => population,
+        };
 
         Self::Output {
             average: mean,
@@ -105,6 +183,9 @@ impl<T: Close> Next<&T> for BollingerBands {
 impl Reset for BollingerBands {
     fn reset(&mut self) {
         self.sd.reset();
+        if let Some(mad) = self.mad.as_mut() {
+            mad.reset();
+        }
     }
 }
 
@@ -116,7 +197,8 @@ impl Default for BollingerBands {
 
 impl fmt::Display for BollingerBands {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "BB({}, {})", self.period, self.multiplier)
+        write!(f, "BB")?;
+        self.fmt_params(f)
     }
 }
 
@@ -159,6 +241,34 @@ mod tests {
         assert_eq!(round(d.lower), -0.395);
     }
 
+    #[test]
+    fn test_deviation() {
+        let mut sample = BollingerBands::with_deviation(3, 1.0, DeviationType::Sample).unwrap();
+        let mut mad = BollingerBands::with_deviation(3, 1.0, DeviationType::MeanAbsolute).unwrap();
+        assert_eq!(sample.deviation(), DeviationType::Sample);
+
+        assert_eq!(sample.next(2.0).upper, 2.0);
+        assert_eq!(mad.next(2.0).upper, 2.0);
+
+        let out = sample.next(4.0);
+        assert_eq!(round(out.upper), 4.414);
+        assert_eq!(round(out.lower), 1.586);
+        let out = mad.next(4.0);
+        assert_eq!((out.average, out.upper, out.lower), (3.0, 4.0, 2.0));
+
+        let out = sample.next(9.0);
+        assert_eq!(out.average, 5.0);
+        assert_eq!(round(out.upper), 8.606);
+        assert_eq!(round(out.lower), 1.394);
+        let out = mad.next(9.0);
+        assert_eq!(round(out.upper), 7.667);
+
+        sample.reset();
+        mad.reset();
+        assert_eq!(sample.next(3.0).lower, 3.0);
+        assert_eq!(mad.next(3.0).lower, 3.0);
+    }
+
     #[test]
     fn test_reset() {
         let mut bb = BollingerBands::new(5, 2.0_f64).unwrap();
@@ -195,5 +305,16 @@ mod tests {
     fn test_display() {
         let bb = BollingerBands::new(10, 3.0_f64).unwrap();
         assert_eq!(format!("{}", bb), "BB(10, 3)");
+        let bb = BollingerBands::with_deviation(10, 3.0, DeviationType::Sample).unwrap();
+        assert_eq!(format!("{}", bb), "BB(10, 3, SAMPLE)");
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut bb = BollingerBands::new(2, 2.0).unwrap();
+        bb.next(1.0);
+        assert!(!bb.is_ready());
+        bb.next(2.0);
+        assert!(bb.is_ready());
     }
 }
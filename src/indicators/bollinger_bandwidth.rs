@@ -0,0 +1,178 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{BollingerBands, DeviationType};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Bollinger Bandwidth (BBW).
+///
+/// Width of the [Bollinger Bands](crate::indicators::BollingerBands) relative to their middle
+/// band. Low values mark a squeeze, a period of low volatility which often precedes a strong
+/// move. Returns 0 while the average is 0.
+///
+/// # Formula
+///
+/// Bandwidth = (BB<sub>Upper Band</sub> - BB<sub>Lower Band</sub>) / BB<sub>Middle Band</sub>
+///
+/// # Parameters
+///
+/// * _period_ - number of periods of the bands (integer greater than 0). Default is 20.
+/// * _multiplier_ - width of the bands in deviations. Default is 2.0.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::BollingerBandwidth;
+/// use ta_panther::Next;
+///
+/// let mut bandwidth = BollingerBandwidth::new(3, 2.0).unwrap();
+/// assert_eq!(bandwidth.next(2.0), 0.0);
+/// assert_eq!(bandwidth.next(6.0), 2.0);
+/// ```
+///
+/// # Links
+///
+/// * [Bollinger Bands, Wikipedia](https://en.wikipedia.org/wiki/Bollinger_Bands#Indicators_derived_from_Bollinger_Bands)
+///
+#[doc(alias = "BBW")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BollingerBandwidth {
+    bb: BollingerBands,
+}
+
+impl BollingerBandwidth {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        Ok(Self {
+            bb: BollingerBands::new(period, multiplier)?,
+        })
+    }
+
+    /// Creates the bandwidth of bands using the given measure of deviation.
+    pub fn with_deviation(
+        period: usize,
+        multiplier: f64,
+        deviation: DeviationType,
+    ) -> Result<Self> {
+        Ok(Self {
+            bb: BollingerBands::with_deviation(period, multiplier, deviation)?,
+        })
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.bb.multiplier()
+    }
+
+    pub fn deviation(&self) -> DeviationType {
+        self.bb.deviation()
+    }
+}
+
+impl Period for BollingerBandwidth {
+    fn period(&self) -> usize {
+        self.bb.period()
+    }
+}
+
+impl IsReady for BollingerBandwidth {
+    fn bars_remaining(&self) -> usize {
+        self.bb.bars_remaining()
+    }
+}
+
+impl Next<f64> for BollingerBandwidth {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let bands = self.bb.next(input);
+        if bands.average != 0.0 {
+            (bands.upper - bands.lower) / bands.average
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for BollingerBandwidth {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for BollingerBandwidth {
+    fn reset(&mut self) {
+        self.bb.reset();
+    }
+}
+
+impl Default for BollingerBandwidth {
+    fn default() -> Self {
+        Self::new(20, 2.0).unwrap()
+    }
+}
+
+impl fmt::Display for BollingerBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BBW")?;
+        self.bb.fmt_params(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(BollingerBandwidth);
+
+    #[test]
+    fn test_new() {
+        assert!(BollingerBandwidth::new(0, 2.0).is_err());
+        assert!(BollingerBandwidth::new(1, 2.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bandwidth = BollingerBandwidth::new(3, 1.0).unwrap();
+        assert_eq!(bandwidth.next(2.0), 0.0);
+        assert_eq!(round(bandwidth.next(4.0)), 0.667);
+        assert_eq!(round(bandwidth.next(9.0)), 1.178);
+    }
+
+    #[test]
+    fn test_zero_average() {
+        let mut bandwidth = BollingerBandwidth::new(2, 1.0).unwrap();
+        bandwidth.next(2.0);
+        assert_eq!(bandwidth.next(-2.0), 0.0);
+    }
+
+    #[test]
+    fn test_with_deviation() {
+        let mut bandwidth =
+            BollingerBandwidth::with_deviation(3, 1.0, DeviationType::MeanAbsolute).unwrap();
+        bandwidth.next(2.0);
+        bandwidth.next(4.0);
+        assert_eq!(round(bandwidth.next(9.0)), 1.067);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bandwidth = BollingerBandwidth::new(3, 1.0).unwrap();
+        bandwidth.next(2.0);
+        bandwidth.next(4.0);
+
+        bandwidth.reset();
+        assert_eq!(bandwidth.next(9.0), 0.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let bandwidth = BollingerBandwidth::new(10, 3.0).unwrap();
+        assert_eq!(format!("{}", bandwidth), "BBW(10, 3)");
+    }
+}
@@ -0,0 +1,177 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{BollingerBands, DeviationType};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Bollinger Band %B (BBP).
+///
+/// Position of the input within the [Bollinger Bands](crate::indicators::BollingerBands): 0 at
+/// the lower band, 1 at the upper band, below 0 or above 1 outside of the bands. Returns 0.5
+/// while the bands have no width.
+///
+/// # Formula
+///
+/// %B = (Price - BB<sub>Lower Band</sub>) / (BB<sub>Upper Band</sub> - BB<sub>Lower Band</sub>)
+///
+/// # Parameters
+///
+/// * _period_ - number of periods of the bands (integer greater than 0). Default is 20.
+/// * _multiplier_ - width of the bands in deviations. Default is 2.0.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::BollingerPercentB;
+/// use ta_panther::Next;
+///
+/// let mut percent_b = BollingerPercentB::new(3, 2.0).unwrap();
+/// assert_eq!(percent_b.next(2.0), 0.5);
+/// assert_eq!(percent_b.next(5.0), 0.75);
+/// ```
+///
+/// # Links
+///
+/// * [Bollinger Bands, Wikipedia](https://en.wikipedia.org/wiki/Bollinger_Bands#Indicators_derived_from_Bollinger_Bands)
+///
+#[doc(alias = "BBP")]
+#[doc(alias = "%B")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BollingerPercentB {
+    bb: BollingerBands,
+}
+
+impl BollingerPercentB {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        Ok(Self {
+            bb: BollingerBands::new(period, multiplier)?,
+        })
+    }
+
+    /// Creates %B of bands using the given measure of deviation.
+    pub fn with_deviation(
+        period: usize,
+        multiplier: f64,
+        deviation: DeviationType,
+    ) -> Result<Self> {
+        Ok(Self {
+            bb: BollingerBands::with_deviation(period, multiplier, deviation)?,
+        })
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.bb.multiplier()
+    }
+
+    pub fn deviation(&self) -> DeviationType {
+        self.bb.deviation()
+    }
+}
+
+impl Period for BollingerPercentB {
+    fn period(&self) -> usize {
+        self.bb.period()
+    }
+}
+
+impl IsReady for BollingerPercentB {
+    fn bars_remaining(&self) -> usize {
+        self.bb.bars_remaining()
+    }
+}
+
+impl Next<f64> for BollingerPercentB {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let bands = self.bb.next(input);
+        let width = bands.upper - bands.lower;
+        if width > 0.0 {
+            (input - bands.lower) / width
+        } else {
+            0.5
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for BollingerPercentB {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for BollingerPercentB {
+    fn reset(&mut self) {
+        self.bb.reset();
+    }
+}
+
+impl Default for BollingerPercentB {
+    fn default() -> Self {
+        Self::new(20, 2.0).unwrap()
+    }
+}
+
+impl fmt::Display for BollingerPercentB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BBP")?;
+        self.bb.fmt_params(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(BollingerPercentB);
+
+    #[test]
+    fn test_new() {
+        assert!(BollingerPercentB::new(0, 2.0).is_err());
+        assert!(BollingerPercentB::new(1, 2.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut percent_b = BollingerPercentB::new(3, 1.0).unwrap();
+        assert_eq!(percent_b.next(2.0), 0.5);
+        assert_eq!(percent_b.next(4.0), 1.0);
+        assert_eq!(round(percent_b.next(9.0)), 1.179);
+        assert_eq!(round(percent_b.next(1.0)), -0.056);
+    }
+
+    #[test]
+    fn test_with_deviation() {
+        let mut percent_b =
+            BollingerPercentB::with_deviation(3, 1.0, DeviationType::Sample).unwrap();
+        percent_b.next(2.0);
+        assert_eq!(round(percent_b.next(4.0)), 0.854);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut percent_b = BollingerPercentB::new(3, 1.0).unwrap();
+        percent_b.next(2.0);
+        percent_b.next(4.0);
+
+        percent_b.reset();
+        assert_eq!(percent_b.next(9.0), 0.5);
+        assert_eq!(percent_b.next(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let percent_b = BollingerPercentB::new(10, 3.0).unwrap();
+        assert_eq!(format!("{}", percent_b), "BBP(10, 3)");
+        let percent_b =
+            BollingerPercentB::with_deviation(10, 3.0, DeviationType::MeanAbsolute).unwrap();
+        assert_eq!(format!("{}", percent_b), "BBP(10, 3, MAD)");
+    }
+}
@@ -0,0 +1,218 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::ring;
+use crate::{Close, High, IsReady, Low, Next, Period, Reset, Volume};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Chaikin Money Flow (CMF).
+///
+/// Volume-weighted average of the position of the close within the range of the bar over the
+/// last _period_ bars. Ranges from -1, when every bar closed at its low, to 1, when every bar
+/// closed at its high. Returns 0 while no volume has been traded.
+///
+/// # Formula
+///
+/// Money Flow Multiplier(MFM) = ((Close - Low) - (High - Close)) / (High - Low)
+///
+/// Money Flow Volume(MFV) = MFM x Volume
+///
+/// CMF = Σ MFV / Σ Volume
+///
+/// The multiplier of bars without a range is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ChaikinMoneyFlow;
+/// use ta_panther::{DataItem, Next};
+///
+/// let mut cmf = ChaikinMoneyFlow::new(2).unwrap();
+/// let di = DataItem::builder()
+///             .high(4.0)
+///             .low(2.0)
+///             .close(3.5)
+///             .open(3.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+/// assert_eq!(cmf.next(&di), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Chaikin Money Flow, stockcharts](https://school.stockcharts.com/doku.php?id=technical_indicators:chaikin_money_flow_cmf)
+///
+#[doc(alias = "CMF")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChaikinMoneyFlow {
+    period: usize,
+    index: usize,
+    count: usize,
+    money_flow_volume: f64,
+    volume: f64,
+    deque: Box<[(f64, f64)]>,
+}
+
+impl ChaikinMoneyFlow {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                money_flow_volume: 0.0,
+                volume: 0.0,
+                deque: ring((0.0, 0.0), period)?,
+            }),
+        }
+    }
+}
+
+/// Position of `close` within the range from `low` to `high`, from -1 at the low to 1 at the
+/// high. Returns 0 if the range is empty.
+pub(crate) fn money_flow_multiplier(high: f64, low: f64, close: f64) -> f64 {
+    let range = high - low;
+    if range > 0.0 {
+        ((close - low) - (high - close)) / range
+    } else {
+        0.0
+    }
+}
+
+impl Period for ChaikinMoneyFlow {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for ChaikinMoneyFlow {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for ChaikinMoneyFlow {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> f64 {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let (old_money_flow_volume, old_volume) = self.deque[self.index];
+            self.money_flow_volume -= old_money_flow_volume;
+            self.volume -= old_volume;
+        }
+
+        let money_flow_volume =
+            money_flow_multiplier(input.high(), input.low(), input.close()) * input.volume();
+        self.money_flow_volume += money_flow_volume;
+        self.volume += input.volume();
+
+        self.deque[self.index] = (money_flow_volume, input.volume());
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.volume > 0.0 {
+            self.money_flow_volume / self.volume
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for ChaikinMoneyFlow {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for ChaikinMoneyFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CMF({})", self.period)
+    }
+}
+
+impl Reset for ChaikinMoneyFlow {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.money_flow_volume = 0.0;
+        self.volume = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = (0.0, 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ChaikinMoneyFlow::new(0).is_err());
+        assert!(ChaikinMoneyFlow::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut cmf = ChaikinMoneyFlow::new(2).unwrap();
+
+        let bar1 = Bar::new().high(4).low(2).close(4).volume(100.0);
+        assert_eq!(cmf.next(&bar1), 1.0);
+
+        let bar2 = Bar::new().high(4).low(2).close(2).volume(300.0);
+        assert_eq!(cmf.next(&bar2), -0.5);
+
+        let bar3 = Bar::new().high(3).low(3).close(3).volume(100.0);
+        assert_eq!(cmf.next(&bar3), -0.75);
+
+        let bar4 = Bar::new().high(3).low(3).close(3).volume(0.0);
+        assert_eq!(cmf.next(&bar4), 0.0);
+        assert!(cmf.is_ready());
+    }
+
+    #[test]
+    fn test_no_volume() {
+        let mut cmf = ChaikinMoneyFlow::new(2).unwrap();
+        assert_eq!(cmf.next(&Bar::new().high(4).low(2).close(4)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cmf = ChaikinMoneyFlow::new(2).unwrap();
+        let bar1 = Bar::new().high(4).low(2).close(4).volume(100.0);
+        let bar2 = Bar::new().high(4).low(2).close(2).volume(300.0);
+
+        cmf.next(&bar1);
+        cmf.next(&bar2);
+
+        cmf.reset();
+        assert_eq!(cmf.next(&bar2), -1.0);
+        assert_eq!(cmf.next(&bar1), -0.5);
+    }
+
+    #[test]
+    fn test_default() {
+        ChaikinMoneyFlow::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cmf = ChaikinMoneyFlow::new(10).unwrap();
+        assert_eq!(format!("{}", cmf), "CMF(10)");
+    }
+}
@@ -1,11 +1,11 @@
-use std::fmt;
-
+use core::fmt;
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::errors::Result;
 use crate::indicators::{AverageTrueRange, Maximum, Minimum};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
 
 /// Chandelier Exit (CE).
 ///
@@ -51,7 +51,8 @@ use crate::{Close, High, Low, Next, Period, Reset};
 /// * [Chandelier Exit, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:chandelier_exit)
 ///
 #[doc(alias = "CE")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChandelierExit {
     atr: AverageTrueRange,
     min: Minimum,
@@ -75,6 +76,7 @@ impl ChandelierExit {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChandelierExitOutput {
     pub long: f64,
     pub short: f64,
@@ -92,6 +94,15 @@ impl Period for ChandelierExit {
     }
 }
 
+impl IsReady for ChandelierExit {
+    fn bars_remaining(&self) -> usize {
+        self.atr
+            .bars_remaining()
+            .max(self.min.bars_remaining())
+            .max(self.max.bars_remaining())
+    }
+}
+
 impl<T: Low + High + Close> Next<&T> for ChandelierExit {
     type Output = ChandelierExitOutput;
 
@@ -171,6 +182,20 @@ mod tests {
         assert_eq!(round(ce.next(&bar6).into()), (2.92, 7.08));
     }
 
+    #[test]
+    fn test_is_ready() {
+        let mut ce = Ce::new(3, 2.0).unwrap();
+        let bar = Bar::new().high(5).low(3).close(4);
+        assert_eq!(ce.bars_remaining(), 3);
+        ce.next(&bar);
+        ce.next(&bar);
+        assert_eq!(ce.bars_remaining(), 1);
+        ce.next(&bar);
+        assert!(ce.is_ready());
+        ce.reset();
+        assert_eq!(ce.bars_remaining(), 3);
+    }
+
     #[test]
     fn test_reset() {
         let mut ce = Ce::new(5, 2.0).unwrap();
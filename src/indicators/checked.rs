@@ -0,0 +1,179 @@
+use core::fmt;
+
+use crate::{IsReady, Next, NextChecked, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Checked warm-up.
+///
+/// Wraps an indicator and returns `None` until it is [ready](IsReady), and `Some` output
+/// afterwards. Many indicators return values over a partial window during their warm-up (e.g.
+/// the maximum of the inputs seen so far, or the input itself), which are easily mistaken for
+/// real signals; the wrapper treats all of them the same way. The warm-up is the one of the
+/// indicator, e.g. _period_ + 1 inputs for a [RateOfChange](crate::indicators::RateOfChange).
+///
+/// The wrapped indicator is fed with every input, including the ones during the warm-up. The
+/// wrapper is an indicator itself, e.g. for registries; indicators can also be called directly
+/// with [next_checked](NextChecked::next_checked).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{Checked, Maximum};
+/// use ta_panther::Next;
+///
+/// let mut max = Checked::new(Maximum::new(3).unwrap());
+/// assert_eq!(max.next(4.0), None);
+/// assert_eq!(max.next(7.0), None);
+/// assert_eq!(max.next(5.0), Some(7.0));
+/// assert_eq!(max.next(2.0), Some(7.0));
+/// ```
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Checked<N> {
+    indicator: N,
+}
+
+impl<N: IsReady> Checked<N> {
+    pub fn new(indicator: N) -> Self {
+        Self { indicator }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn inner(&self) -> &N {
+        &self.indicator
+    }
+}
+
+impl<N: Period> Period for Checked<N> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<N: IsReady> IsReady for Checked<N> {
+    fn bars_remaining(&self) -> usize {
+        self.indicator.bars_remaining()
+    }
+}
+
+impl<N: Next<T> + IsReady, T> Next<T> for Checked<N> {
+    type Output = Option<N::Output>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.indicator.next_checked(input)
+    }
+}
+
+impl<N: Reset> Reset for Checked<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<N: IsReady + Default> Default for Checked<N> {
+    fn default() -> Self {
+        Self::new(N::default())
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for Checked<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CHECKED({})", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage as Ema, LinearRegressionPrediction, Momentum, RateOfChange,
+        SimpleMovingAverage as Sma,
+    };
+    use crate::test_helper::*;
+
+    type CheckedSma = Checked<Sma>;
+
+    test_indicator!(CheckedSma);
+
+    #[test]
+    fn test_next() {
+        let mut sma = Checked::new(Sma::new(3).unwrap());
+        assert_eq!(sma.next(3.0), None);
+        assert!(!sma.is_ready());
+        assert_eq!(sma.next(6.0), None);
+        assert_eq!(sma.next(9.0), Some(6.0));
+        assert!(sma.is_ready());
+        assert_eq!(sma.next(12.0), Some(9.0));
+
+        let mut forecast = Checked::new(LinearRegressionPrediction::new(2).unwrap());
+        assert_eq!(forecast.next(1.0), None);
+        assert_eq!(forecast.next(2.0), Some(3.0));
+    }
+
+    #[test]
+    fn test_warm_up_of_indicator() {
+        // the change over 3 bars needs 4 inputs
+        let mut roc = Checked::new(RateOfChange::new(3).unwrap());
+        assert_eq!(roc.bars_remaining(), 4);
+        assert_eq!(roc.next(10.0), None);
+        assert_eq!(roc.next(11.0), None);
+        assert_eq!(roc.next(12.0), None);
+        assert_eq!(roc.next(15.0), Some(50.0));
+
+        let mut momentum = Checked::new(Momentum::new(2).unwrap());
+        assert_eq!(momentum.next(10.0), None);
+        assert_eq!(momentum.next(11.0), None);
+        assert_eq!(momentum.next(13.0), Some(3.0));
+
+        let mut ema = Checked::new(Ema::new(3).unwrap());
+        assert_eq!(ema.next(2.0), None);
+        assert_eq!(ema.next(5.0), None);
+        assert_eq!(ema.next(1.0), Some(2.25));
+        assert!(ema.is_ready());
+    }
+
+    #[test]
+    fn test_next_checked() {
+        let mut roc = RateOfChange::new(1).unwrap();
+        assert_eq!(roc.next_checked(10.0), None);
+        assert_eq!(roc.next_checked(12.0), Some(20.0));
+
+        let mut sma = Sma::new(2).unwrap();
+        assert_eq!(sma.next_checked(&Bar::new().close(4)), None);
+        assert_eq!(sma.next_checked(&Bar::new().close(8)), Some(6.0));
+    }
+
+    #[test]
+    fn test_period_of_one() {
+        let mut sma = Checked::new(Sma::new(1).unwrap());
+        assert_eq!(sma.next(3.0), Some(3.0));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut sma = Checked::new(Sma::new(2).unwrap());
+        assert_eq!(sma.next(&Bar::new().close(4)), None);
+        assert_eq!(sma.next(&Bar::new().close(8)), Some(6.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = Checked::new(Sma::new(2).unwrap());
+        sma.next(4.0);
+        sma.next(8.0);
+
+        sma.reset();
+        assert_eq!(sma.next(1.0), None);
+        assert_eq!(sma.next(3.0), Some(2.0));
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = Checked::new(Sma::new(7).unwrap());
+        assert_eq!(format!("{}", sma), "CHECKED(SMA(7))");
+        assert_eq!(sma.inner().period(), 7);
+    }
+}
@@ -1,10 +1,12 @@
-use std::fmt;
+use core::fmt;
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::errors::Result;
-use crate::indicators::{MeanAbsoluteDeviation, SimpleMovingAverage};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::errors::TaError;
+use crate::indicators::{MeanAbsoluteDeviation, PriceSource, SimpleMovingAverage};
+use crate::{Close, High, IsReady, Low, Next, Open, Period, Reset};
 
 /// Commodity Channel Index (CCI)
 ///
@@ -16,30 +18,78 @@ use crate::{Close, High, Low, Next, Period, Reset};
 ///
 /// # Formula
 ///
-/// CCI(_period_) = (TP - SMA(_period_) of TP) / (MAD(_period_) * 0.015)
+/// CCI(_period_) = (TP - SMA(_period_) of TP) / (MAD(_mad_period_) of TP * _constant_)
+///
+/// The typical price (TP) is _(High + Low + Close) / 3_ by default,
+/// [with_params](Self::with_params) selects another [PriceSource].
 ///
 /// # Parameters
 ///
 /// * _period_ - number of periods (integer greater than 0). Default is 20.
+/// * _constant_ - scaling constant (greater than 0), chosen by Lambert so that most values fall
+///   between -100 and 100. Default is 0.015.
+/// * _mad_period_ - number of periods of the mean absolute deviation (integer greater than 0).
+///   Default is _period_.
+/// * _source_ - price of the bars. Default is [PriceSource::Hlc3].
 ///
 /// # Links
 ///
 /// * [Commodity Channel Index, Wikipedia](https://en.wikipedia.org/wiki/Commodity_channel_index)
 /// * [Commodity Channel Index, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:commodity_channel_index_cci)
 ///
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CommodityChannelIndex {
     sma: SimpleMovingAverage,
     mad: MeanAbsoluteDeviation,
+    #[cfg_attr(feature = "serde", serde(default = "default_constant"))]
+    constant: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_source"))]
+    source: PriceSource,
+}
+
+fn default_constant() -> f64 {
+    0.015
+}
+
+fn default_source() -> PriceSource {
+    PriceSource::Hlc3
 }
 
 impl CommodityChannelIndex {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_params(period, default_constant(), period, default_source())
+    }
+
+    /// Creates a CCI with a custom scaling constant, deviation window and price source.
+    pub fn with_params(
+        period: usize,
+        constant: f64,
+        mad_period: usize,
+        source: PriceSource,
+    ) -> Result<Self> {
+        if !(constant.is_finite() && constant > 0.0) {
+            return Err(TaError::InvalidParameter);
+        }
         Ok(Self {
             sma: SimpleMovingAverage::new(period)?,
-            mad: MeanAbsoluteDeviation::new(period)?,
+            mad: MeanAbsoluteDeviation::new(mad_period)?,
+            constant,
+            source,
         })
     }
+
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+
+    pub fn mad_period(&self) -> usize {
+        self.mad.period()
+    }
+
+    pub fn source(&self) -> PriceSource {
+        self.source
+    }
 }
 
 impl Period for CommodityChannelIndex {
@@ -48,19 +98,25 @@ impl Period for CommodityChannelIndex {
     }
 }
 
-impl<T: Close + High + Low> Next<&T> for CommodityChannelIndex {
+impl IsReady for CommodityChannelIndex {
+    fn bars_remaining(&self) -> usize {
+        self.sma.bars_remaining().max(self.mad.bars_remaining())
+    }
+}
+
+impl<T: Open + Close + High + Low> Next<&T> for CommodityChannelIndex {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        let tp = (input.close() + input.high() + input.low()) / 3.0;
+        let tp = self.source.price(input);
         let sma = self.sma.next(tp);
-        let mad = self.mad.next(input);
+        let mad = self.mad.next(tp);
 
         if mad == 0.0 {
             return 0.0;
         }
 
-        (tp - sma) / (mad * 0.015)
+        (tp - sma) / (mad * self.constant)
     }
 }
 
@@ -79,7 +135,20 @@ impl Default for CommodityChannelIndex {
 
 impl fmt::Display for CommodityChannelIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "CCI({})", self.sma.period())
+        write!(f, "CCI({}", self.sma.period())?;
+        if self.constant != default_constant()
+            || self.mad.period() != self.sma.period()
+            || self.source != default_source()
+        {
+            write!(
+                f,
+                ", {}, {}, {}",
+                self.constant,
+                self.mad.period(),
+                self.source
+            )?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -117,6 +186,28 @@ mod tests {
         assert_eq!(round(cci.next(&bar6)), -126.126);
     }
 
+    #[test]
+    fn test_with_params() {
+        assert!(CommodityChannelIndex::with_params(5, 0.0, 5, PriceSource::Hlc3).is_err());
+        assert!(CommodityChannelIndex::with_params(5, 0.015, 0, PriceSource::Hlc3).is_err());
+
+        let mut cci = CommodityChannelIndex::with_params(2, 0.01, 3, PriceSource::Close).unwrap();
+        assert_eq!(cci.constant(), 0.01);
+        assert_eq!(cci.mad_period(), 3);
+        assert_eq!(cci.source(), PriceSource::Close);
+
+        assert_eq!(cci.next(&Bar::new().high(9).low(1).close(2)), 0.0);
+        // SMA of 2 and 4 is 3, MAD of 2 and 4 is 1
+        assert_eq!(cci.next(&Bar::new().high(9).low(1).close(4)), 100.0);
+        // SMA of 4 and 6 is 5, MAD of 2, 4 and 6 is 4 / 3
+        assert_eq!(round(cci.next(&Bar::new().high(9).low(1).close(6))), 75.0);
+
+        let mut cci = CommodityChannelIndex::with_params(2, 0.015, 2, PriceSource::Ohlc4).unwrap();
+        cci.next(&Bar::new().open(2).high(3).low(1).close(2));
+        let value = cci.next(&Bar::new().open(6).high(7).low(5).close(6));
+        assert_eq!(round(value), 66.667);
+    }
+
     #[test]
     fn test_reset() {
         let mut cci = CommodityChannelIndex::new(5).unwrap();
@@ -142,5 +233,7 @@ mod tests {
     fn test_display() {
         let indicator = CommodityChannelIndex::new(10).unwrap();
         assert_eq!(format!("{}", indicator), "CCI(10)");
+        let indicator = CommodityChannelIndex::with_params(10, 0.02, 5, PriceSource::Hl2).unwrap();
+        assert_eq!(format!("{}", indicator), "CCI(10, 0.02, 5, HL2)");
     }
 }
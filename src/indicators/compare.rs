@@ -0,0 +1,174 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::{Result, TaError};
+use crate::{Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Comparison of a [Compare].
+///
+/// Parsed case-insensitively from its [Display](fmt::Display) name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Comparison {
+    /// _a > b_
+    #[default]
+    Greater,
+    /// _a < b_
+    Less,
+    /// _a == b_
+    Equal,
+}
+
+impl Comparison {
+    /// All the comparisons.
+    pub const ALL: [Comparison; 3] = [Comparison::Greater, Comparison::Less, Comparison::Equal];
+
+    /// Compares `a` with `b`.
+    pub fn apply(self, a: f64, b: f64) -> bool {
+        match self {
+            Comparison::Greater => a > b,
+            Comparison::Less => a < b,
+            Comparison::Equal => a == b,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Comparison::Greater => "GT",
+            Comparison::Less => "LT",
+            Comparison::Equal => "EQ",
+        }
+    }
+}
+
+impl FromStr for Comparison {
+    type Err = TaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Comparison::ALL
+            .iter()
+            .copied()
+            .find(|comparison| comparison.name().eq_ignore_ascii_case(s))
+            .ok_or(TaError::InvalidParameter)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Comparison of two series.
+///
+/// Compares two series element by element, e.g. the close with a moving average, giving the
+/// condition of an [IfThenElse](crate::indicators::IfThenElse). Unlike
+/// [CrossAbove](crate::indicators::CrossAbove) it holds for as long as the relation does, not
+/// just on the input where it starts.
+///
+/// Every comparison with a NaN operand is false.
+///
+/// # Parameters
+///
+/// * _comparison_ - the [Comparison]. Default is [Greater](Comparison::Greater).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{Compare, Comparison};
+/// use ta_panther::Next;
+///
+/// let mut above = Compare::new(Comparison::Greater);
+/// assert_eq!(above.next((12.0, 10.5)), true);
+/// assert_eq!(above.next((10.0, 10.5)), false);
+/// ```
+///
+#[doc(alias = "GT")]
+#[doc(alias = "LT")]
+#[doc(alias = "EQ")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Compare {
+    comparison: Comparison,
+}
+
+impl Compare {
+    pub fn new(comparison: Comparison) -> Self {
+        Self { comparison }
+    }
+
+    pub fn comparison(&self) -> Comparison {
+        self.comparison
+    }
+}
+
+impl Next<(f64, f64)> for Compare {
+    type Output = bool;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        self.comparison.apply(a, b)
+    }
+}
+
+impl Reset for Compare {
+    fn reset(&mut self) {}
+}
+
+impl fmt::Display for Compare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.comparison)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_next() {
+        let mut greater = Compare::new(Comparison::Greater);
+        let mut less = Compare::new(Comparison::Less);
+        let mut equal = Compare::new(Comparison::Equal);
+        for &(a, b) in [(2.0, 1.0), (1.0, 2.0), (1.5, 1.5)].iter() {
+            assert_eq!(greater.next((a, b)), a > b);
+            assert_eq!(less.next((a, b)), a < b);
+            assert_eq!(equal.next((a, b)), a == b);
+        }
+    }
+
+    #[test]
+    fn test_nan() {
+        for comparison in Comparison::ALL.iter() {
+            let mut compare = Compare::new(*comparison);
+            assert!(!compare.next((f64::NAN, 1.0)));
+            assert!(!compare.next((f64::NAN, f64::NAN)));
+        }
+    }
+
+    #[test]
+    fn test_comparison_from_str() {
+        for comparison in Comparison::ALL.iter() {
+            assert_eq!(
+                comparison.to_string().parse::<Comparison>(),
+                Ok(*comparison)
+            );
+        }
+        assert_eq!("lt".parse::<Comparison>(), Ok(Comparison::Less));
+        assert!(">".parse::<Comparison>().is_err());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Compare::default().comparison(), Comparison::Greater);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Compare::new(Comparison::Equal)), "EQ");
+    }
+}
@@ -0,0 +1,231 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::ring;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Conditional mean.
+///
+/// Averages only the values of the last _period_ inputs for which the condition is true, e.g.
+/// the average volume on up days. The inputs are pairs `(value, condition)`. The output also
+/// reports how many values of the window met the condition, since an average over a handful of
+/// values is less meaningful than one over the whole window. While no value in the window meets
+/// the condition, the mean is NaN.
+///
+/// # Formula
+///
+/// _CMEAN = Σ value<sub>i</sub> · c<sub>i</sub> / Σ c<sub>i</sub>_
+///
+/// Where _c<sub>i</sub>_ is 1 if the condition of the i-th input is true and 0 otherwise.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ConditionalMean;
+/// use ta_panther::Next;
+///
+/// let mut up_volume = ConditionalMean::new(3).unwrap();
+/// up_volume.next((100.0, true));
+/// up_volume.next((500.0, false));
+/// let output = up_volume.next((200.0, true));
+/// assert_eq!(output.mean, 150.0);
+/// assert_eq!(output.count, 2);
+/// ```
+///
+#[doc(alias = "CMEAN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConditionalMean {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum: f64,
+    matched: usize,
+    deque: Box<[(f64, bool)]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConditionalMeanOutput {
+    pub mean: f64,
+    pub count: usize,
+}
+
+impl ConditionalMean {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum: 0.0,
+                matched: 0,
+                deque: ring((0.0, false), period)?,
+            }),
+        }
+    }
+}
+
+impl Period for ConditionalMean {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for ConditionalMean {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<(f64, bool)> for ConditionalMean {
+    type Output = ConditionalMeanOutput;
+
+    fn next(&mut self, (value, condition): (f64, bool)) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let (old_value, old_condition) = self.deque[self.index];
+            if old_condition {
+                self.sum -= old_value;
+                self.matched -= 1;
+            }
+        }
+        if condition {
+            self.sum += value;
+            self.matched += 1;
+        }
+        if self.matched == 0 {
+            // avoid drifting away from zero once the window holds no matching value
+            self.sum = 0.0;
+        }
+
+        self.deque[self.index] = (value, condition);
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        ConditionalMeanOutput {
+            mean: if self.matched == 0 {
+                f64::NAN
+            } else {
+                self.sum / self.matched as f64
+            },
+            count: self.matched,
+        }
+    }
+}
+
+impl<T: Close> Next<(&T, bool)> for ConditionalMean {
+    type Output = ConditionalMeanOutput;
+
+    fn next(&mut self, (input, condition): (&T, bool)) -> Self::Output {
+        self.next((input.close(), condition))
+    }
+}
+
+impl Reset for ConditionalMean {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        self.matched = 0;
+        for i in 0..self.period {
+            self.deque[i] = (0.0, false);
+        }
+    }
+}
+
+impl Default for ConditionalMean {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ConditionalMean {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CMEAN({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ConditionalMean::new(0).is_err());
+        assert!(ConditionalMean::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cmean = ConditionalMean::new(3).unwrap();
+
+        let output = cmean.next((4.0, false));
+        assert!(output.mean.is_nan());
+        assert_eq!(output.count, 0);
+
+        let output = cmean.next((2.0, true));
+        assert_eq!((output.mean, output.count), (2.0, 1));
+
+        let output = cmean.next((7.0, true));
+        assert_eq!((output.mean, output.count), (4.5, 2));
+
+        let output = cmean.next((9.0, false));
+        assert_eq!((output.mean, output.count), (4.5, 2));
+
+        let output = cmean.next((1.0, true));
+        assert_eq!((output.mean, output.count), (4.0, 2));
+
+        let output = cmean.next((3.0, false));
+        assert_eq!((output.mean, output.count), (1.0, 1));
+
+        cmean.next((5.0, false));
+        let output = cmean.next((6.0, false));
+        assert!(output.mean.is_nan());
+        assert_eq!(output.count, 0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut cmean = ConditionalMean::new(2).unwrap();
+        cmean.next((&Bar::new().close(3), true));
+        let output = cmean.next((&Bar::new().close(6), true));
+        assert_eq!(output.mean, 4.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cmean = ConditionalMean::new(3).unwrap();
+        cmean.next((4.0, true));
+        cmean.next((8.0, true));
+
+        cmean.reset();
+        let output = cmean.next((1.0, true));
+        assert_eq!((output.mean, output.count), (1.0, 1));
+    }
+
+    #[test]
+    fn test_default() {
+        ConditionalMean::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cmean = ConditionalMean::new(10).unwrap();
+        assert_eq!(format!("{}", cmean), "CMEAN(10)");
+    }
+}
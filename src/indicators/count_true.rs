@@ -0,0 +1,172 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::ring;
+use crate::{IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Count of true conditions.
+///
+/// Counts the inputs of the last _period_ ones for which a condition is true, e.g. the number
+/// of up days of the last month or whether a cross happened within the last bars.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::CountTrue;
+/// use ta_panther::Next;
+///
+/// let mut count = CountTrue::new(3).unwrap();
+/// assert_eq!(count.next(true), 1);
+/// assert_eq!(count.next(false), 1);
+/// assert_eq!(count.next(true), 2);
+/// assert_eq!(count.next(false), 1);
+/// ```
+///
+#[doc(alias = "COUNT")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CountTrue {
+    period: usize,
+    index: usize,
+    count: usize,
+    matched: usize,
+    deque: Box<[bool]>,
+}
+
+impl CountTrue {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                matched: 0,
+                deque: ring(false, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for CountTrue {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for CountTrue {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<bool> for CountTrue {
+    type Output = usize;
+
+    fn next(&mut self, condition: bool) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else if self.deque[self.index] {
+            self.matched -= 1;
+        }
+
+        if condition {
+            self.matched += 1;
+        }
+        self.deque[self.index] = condition;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.matched
+    }
+}
+
+impl Reset for CountTrue {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.matched = 0;
+        for i in 0..self.period {
+            self.deque[i] = false;
+        }
+    }
+}
+
+impl Default for CountTrue {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for CountTrue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "COUNT_TRUE({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_new() {
+        assert!(CountTrue::new(0).is_err());
+        assert!(CountTrue::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut count = CountTrue::new(3).unwrap();
+        assert_eq!(count.next(true), 1);
+        assert_eq!(count.next(true), 2);
+        assert!(!count.is_ready());
+        assert_eq!(count.next(true), 3);
+        assert!(count.is_ready());
+        assert_eq!(count.next(false), 2);
+        assert_eq!(count.next(false), 1);
+        assert_eq!(count.next(false), 0);
+        assert_eq!(count.next(true), 1);
+    }
+
+    #[test]
+    fn test_period_of_one() {
+        let mut count = CountTrue::new(1).unwrap();
+        assert_eq!(count.next(true), 1);
+        assert_eq!(count.next(false), 0);
+        assert_eq!(count.next(true), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut count = CountTrue::new(3).unwrap();
+        count.next(true);
+        count.next(true);
+
+        count.reset();
+        assert_eq!(count.next(false), 0);
+        assert_eq!(count.bars_remaining(), 2);
+    }
+
+    #[test]
+    fn test_default() {
+        CountTrue::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let count = CountTrue::new(5).unwrap();
+        assert_eq!(format!("{}", count), "COUNT_TRUE(5)");
+    }
+}
@@ -1,10 +1,10 @@
-use std::collections::VecDeque;
-use std::fmt;
+use alloc::collections::VecDeque;
+use core::fmt;
 
-
-use serde::{Deserialize, Serialize};
-use crate::errors::{Result};
+use crate::errors::Result;
 use crate::{Next, Period, Reset, State};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Cross Above Indicator.
 ///
@@ -43,7 +43,8 @@ use crate::{Next, Period, Reset, State};
 /// # Links
 ///
 #[doc(alias = "CROSS_ABOVE")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CrossAbove {
     threshold: f64,
     deque: VecDeque<f64>,
@@ -62,10 +63,7 @@ impl CrossAbove {
             deque.pop_front();
         }
 
-        Ok(Self {
-            threshold,
-            deque
-        })
+        Ok(Self { threshold, deque })
     }
 }
 
@@ -94,11 +92,15 @@ impl Next<f64> for CrossAbove {
     }
 }
 
+/// The threshold, the previous input and the latest input, which are `None` until enough inputs
+/// have been fed.
 impl State for CrossAbove {
-    type Output = (f64, f64, f64);
+    type Output = (f64, Option<f64>, Option<f64>);
 
     fn state(&self) -> Self::Output {
-        (self.threshold, self.deque[0], self.deque[1])
+        let mut inputs = self.deque.iter().rev().copied();
+        let current = inputs.next();
+        (self.threshold, inputs.next(), current)
     }
 }
 
@@ -118,4 +120,33 @@ impl fmt::Display for CrossAbove {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "CROSS_ABOVE({})", self.threshold)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn test_next() {
+        let mut cross = CrossAbove::new(10.0).unwrap();
+        let outputs: Vec<bool> = [11.0, 9.0, 11.0, 12.0, 10.0, 10.5]
+            .iter()
+            .map(|&input| cross.next(input))
+            .collect();
+        assert_eq!(outputs, vec![false, false, true, false, false, true]);
+    }
+
+    #[test]
+    fn test_state() {
+        let mut cross = CrossAbove::new(10.0).unwrap();
+        assert_eq!(cross.state(), (10.0, None, None));
+        cross.next(9.0);
+        assert_eq!(cross.state(), (10.0, None, Some(9.0)));
+        cross.next(11.0);
+        assert_eq!(cross.state(), (10.0, Some(9.0), Some(11.0)));
+
+        cross.reset();
+        assert_eq!(cross.state(), (10.0, None, None));
+    }
+}
@@ -0,0 +1,193 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Cross with hysteresis.
+///
+/// Detects when a series crosses above an upper level, like
+/// [CrossAbove](crate::indicators::CrossAbove), but only fires again after the series has
+/// fallen below a lower level. A series oscillating around a single threshold makes a cross
+/// detector fire on every swing, the band between the two levels absorbs those swings.
+///
+/// Returns `true` on the input where the series rises above the upper level after having been
+/// below the lower one. The first input only sets the state: it is above if the input is above
+/// the upper level and below otherwise, so it never fires.
+///
+/// # Parameters
+///
+/// * _upper_ - level to cross to fire
+/// * _lower_ - level to fall below to fire again (at most _upper_)
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::CrossWithHysteresis;
+/// use ta_panther::Next;
+///
+/// let mut cross = CrossWithHysteresis::new(70.0, 60.0).unwrap();
+/// assert_eq!(cross.next(65.0), false);
+/// assert_eq!(cross.next(72.0), true);
+/// assert_eq!(cross.next(68.0), false);
+/// assert_eq!(cross.next(71.0), false); // did not fall below 60
+/// assert_eq!(cross.next(58.0), false);
+/// assert_eq!(cross.next(75.0), true);
+/// ```
+///
+/// # Links
+///
+/// * [Hysteresis, Wikipedia](https://en.wikipedia.org/wiki/Schmitt_trigger)
+///
+#[doc(alias = "SCHMITT_TRIGGER")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrossWithHysteresis {
+    upper: f64,
+    lower: f64,
+    above: Option<bool>,
+}
+
+impl CrossWithHysteresis {
+    pub fn new(upper: f64, lower: f64) -> Result<Self> {
+        if !(upper.is_finite() && lower.is_finite() && lower <= upper) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            upper,
+            lower,
+            above: None,
+        })
+    }
+
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns whether the series is above, i.e. it crossed the upper level and has not fallen
+    /// below the lower one since, or `None` before the first input.
+    pub fn is_above(&self) -> Option<bool> {
+        self.above
+    }
+}
+
+impl Next<f64> for CrossWithHysteresis {
+    type Output = bool;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self.above {
+            None => {
+                self.above = Some(input > self.upper);
+                false
+            }
+            Some(false) if input > self.upper => {
+                self.above = Some(true);
+                true
+            }
+            Some(true) if input < self.lower => {
+                self.above = Some(false);
+                false
+            }
+            Some(_) => false,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for CrossWithHysteresis {
+    type Output = bool;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for CrossWithHysteresis {
+    fn reset(&mut self) {
+        self.above = None;
+    }
+}
+
+impl Default for CrossWithHysteresis {
+    fn default() -> Self {
+        Self::new(0.0, 0.0).unwrap()
+    }
+}
+
+impl fmt::Display for CrossWithHysteresis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CROSS_HYSTERESIS({}, {})", self.upper, self.lower)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(CrossWithHysteresis::new(1.0, 2.0).is_err());
+        assert!(CrossWithHysteresis::new(f64::NAN, 0.0).is_err());
+        assert!(CrossWithHysteresis::new(1.0, 1.0).is_ok());
+        assert!(CrossWithHysteresis::new(2.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cross = CrossWithHysteresis::new(2.0, 1.0).unwrap();
+        let outputs: Vec<bool> = [0.0, 3.0, 1.5, 3.0, 1.0, 3.0, 0.5, 2.0, 2.5]
+            .iter()
+            .map(|&input| cross.next(input))
+            .collect();
+        // touching a level does not cross it
+        assert_eq!(
+            outputs,
+            vec![false, true, false, false, false, false, false, false, true]
+        );
+        assert_eq!(cross.is_above(), Some(true));
+    }
+
+    #[test]
+    fn test_first_input_above() {
+        let mut cross = CrossWithHysteresis::new(2.0, 1.0).unwrap();
+        assert!(!cross.next(3.0));
+        assert!(!cross.next(4.0));
+        assert!(!cross.next(0.0));
+        assert!(cross.next(4.0));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut cross = CrossWithHysteresis::new(2.0, 1.0).unwrap();
+        cross.next(&Bar::new().close(0));
+        assert!(cross.next(&Bar::new().close(3)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cross = CrossWithHysteresis::new(2.0, 1.0).unwrap();
+        cross.next(0.0);
+        cross.next(3.0);
+
+        cross.reset();
+        assert_eq!(cross.is_above(), None);
+        assert!(!cross.next(3.0));
+    }
+
+    #[test]
+    fn test_default() {
+        CrossWithHysteresis::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cross = CrossWithHysteresis::new(70.0, 60.0).unwrap();
+        assert_eq!(format!("{}", cross), "CROSS_HYSTERESIS(70, 60)");
+    }
+}
@@ -0,0 +1,185 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Debounced condition.
+///
+/// Wraps an indicator returning a condition and returns true only once the condition has held
+/// for the last _bars_ inputs, and false as soon as it fails. Requiring a condition to persist
+/// filters out the whipsaws of a price oscillating around a level.
+///
+/// A cross detector is true on a single input, so debounce the underlying condition instead,
+/// e.g. a [Compare](crate::indicators::Compare) of the price with the level, to require the
+/// price to stay above the level for _bars_ inputs. To keep a single fired signal true for a
+/// while, use [Hold](crate::indicators::Hold).
+///
+/// # Parameters
+///
+/// * _bars_ - number of consecutive inputs the condition must hold for (integer greater than
+///   0).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{Compare, Comparison, Debounce};
+/// use ta_panther::Next;
+///
+/// let level = 10.0;
+/// let mut above = Debounce::new(Compare::new(Comparison::Greater), 2).unwrap();
+/// assert_eq!(above.next((11.0, level)), false);
+/// assert_eq!(above.next((9.0, level)), false);
+/// assert_eq!(above.next((11.0, level)), false);
+/// assert_eq!(above.next((12.0, level)), true);
+/// assert_eq!(above.next((13.0, level)), true);
+/// ```
+///
+#[doc(alias = "CONFIRM")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Debounce<N> {
+    indicator: N,
+    bars: usize,
+    count: usize,
+    consecutive: usize,
+}
+
+impl<N> Debounce<N> {
+    pub fn new(indicator: N, bars: usize) -> Result<Self> {
+        match bars {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                indicator,
+                bars,
+                count: 0,
+                consecutive: 0,
+            }),
+        }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn inner(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn bars(&self) -> usize {
+        self.bars
+    }
+}
+
+impl<N: Period> Period for Debounce<N> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<N: Period> IsReady for Debounce<N> {
+    fn bars_remaining(&self) -> usize {
+        (self.indicator.period().saturating_add(self.bars) - 1).saturating_sub(self.count)
+    }
+}
+
+impl<N: Next<T, Output = bool>, T> Next<T> for Debounce<N> {
+    type Output = bool;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        self.consecutive = if self.indicator.next(input) {
+            self.consecutive.saturating_add(1)
+        } else {
+            0
+        };
+        self.consecutive >= self.bars
+    }
+}
+
+impl<N: Reset> Reset for Debounce<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.count = 0;
+        self.consecutive = 0;
+    }
+}
+
+impl<N: Default> Default for Debounce<N> {
+    fn default() -> Self {
+        Self::new(N::default(), 1).unwrap()
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for Debounce<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DEBOUNCE({}, {})", self.indicator, self.bars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Compare, Comparison, CrossAbove};
+    use alloc::format;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn test_new() {
+        assert!(Debounce::new(CrossAbove::new(1.0).unwrap(), 0).is_err());
+        assert!(Debounce::new(CrossAbove::new(1.0).unwrap(), 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut above = Debounce::new(Compare::new(Comparison::Greater), 3).unwrap();
+        let outputs: Vec<bool> = [2.0, 2.0, 0.0, 2.0, 2.0, 2.0, 2.0, 0.0]
+            .iter()
+            .map(|&price| above.next((price, 1.0)))
+            .collect();
+        assert_eq!(
+            outputs,
+            vec![false, false, false, false, false, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_one_bar() {
+        let mut cross = Debounce::new(CrossAbove::new(1.0).unwrap(), 1).unwrap();
+        assert!(!cross.next(0.0));
+        assert!(cross.next(2.0));
+        assert!(!cross.next(3.0));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut cross = Debounce::new(CrossAbove::new(1.0).unwrap(), 2).unwrap();
+        assert_eq!(cross.bars_remaining(), 3);
+        cross.next(0.0);
+        cross.next(2.0);
+        assert!(!cross.is_ready());
+        cross.next(2.0);
+        assert!(cross.is_ready());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut above = Debounce::new(Compare::new(Comparison::Greater), 2).unwrap();
+        above.next((2.0, 1.0));
+        above.next((2.0, 1.0));
+
+        above.reset();
+        assert!(!above.next((2.0, 1.0)));
+        assert!(above.next((2.0, 1.0)));
+    }
+
+    #[test]
+    fn test_default() {
+        Debounce::<Compare>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cross = Debounce::new(CrossAbove::new(30.0).unwrap(), 3).unwrap();
+        assert_eq!(format!("{}", cross), "DEBOUNCE(CROSS_ABOVE(30), 3)");
+    }
+}
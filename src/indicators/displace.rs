@@ -0,0 +1,196 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::deque;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Displacement of the output of an indicator.
+///
+/// Wraps an indicator and shifts its output forward by _bars_ periods, so each input returns
+/// the output of _bars_ inputs ago, like a displaced moving average or `REF(MA(X, n), bars)` of
+/// TDX and MetaStock formulas. Until _bars_ periods have passed, the first output is returned,
+/// like [Lag](crate::indicators::Lag) does. Works with any output, e.g. the flags of cross
+/// detectors.
+///
+/// The wrapped indicator is fed with every input, the output is ready _bars_ periods after the
+/// first _period_ inputs. A backward displacement would need future inputs and cannot be
+/// computed on a stream; displace the series compared with instead.
+///
+/// # Parameters
+///
+/// * _bars_ - number of periods to shift the output by (integer greater than 0).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{Displace, SimpleMovingAverage};
+/// use ta_panther::Next;
+///
+/// let mut dma = Displace::new(SimpleMovingAverage::new(2).unwrap(), 1).unwrap();
+/// assert_eq!(dma.next(2.0), 2.0);
+/// assert_eq!(dma.next(4.0), 2.0);
+/// assert_eq!(dma.next(6.0), 3.0);
+/// assert_eq!(dma.next(8.0), 5.0);
+/// ```
+///
+#[doc(alias = "DISPLACED")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "N: Serialize, N::Output: Serialize",
+        deserialize = "N: Deserialize<'de>, N::Output: Deserialize<'de>"
+    ))
+)]
+pub struct Displace<N: Next<f64>> {
+    indicator: N,
+    bars: usize,
+    count: usize,
+    outputs: VecDeque<N::Output>,
+}
+
+impl<N: Next<f64>> Displace<N> {
+    pub fn new(indicator: N, bars: usize) -> Result<Self> {
+        match bars {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                indicator,
+                bars,
+                count: 0,
+                outputs: deque(bars.saturating_add(1))?,
+            }),
+        }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn inner(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn bars(&self) -> usize {
+        self.bars
+    }
+}
+
+impl<N: Next<f64> + Period> Period for Displace<N> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<N: Next<f64> + Period> IsReady for Displace<N> {
+    fn bars_remaining(&self) -> usize {
+        (self.indicator.period() + self.bars).saturating_sub(self.count)
+    }
+}
+
+impl<N: Next<f64>> Next<f64> for Displace<N>
+where
+    N::Output: Clone,
+{
+    type Output = N::Output;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = self.indicator.next(input);
+        self.count = self.count.saturating_add(1);
+        if self.outputs.len() > self.bars {
+            self.outputs.pop_front();
+        }
+        self.outputs.push_back(output.clone());
+        self.outputs.front().cloned().unwrap_or(output)
+    }
+}
+
+impl<N: Next<f64>, T: Close> Next<&T> for Displace<N>
+where
+    N::Output: Clone,
+{
+    type Output = N::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<N: Next<f64> + Reset> Reset for Displace<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.count = 0;
+        self.outputs.clear();
+    }
+}
+
+impl<N: Next<f64> + Default> Default for Displace<N> {
+    fn default() -> Self {
+        Self::new(N::default(), 1).unwrap()
+    }
+}
+
+impl<N: Next<f64> + fmt::Display> fmt::Display for Displace<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DISPLACE({}, {})", self.indicator, self.bars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{CrossAbove, ExponentialMovingAverage as Ema, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    type DisplacedEma = Displace<Ema>;
+
+    test_indicator!(DisplacedEma);
+
+    #[test]
+    fn test_new() {
+        assert!(Displace::new(Ema::new(3).unwrap(), 0).is_err());
+        assert!(Displace::new(Ema::new(3).unwrap(), 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dma = Displace::new(SimpleMovingAverage::new(2).unwrap(), 2).unwrap();
+        assert_eq!(dma.bars_remaining(), 4);
+        let outputs: Vec<f64> = [2.0, 4.0, 6.0, 8.0, 10.0]
+            .iter()
+            .map(|&input| dma.next(input))
+            .collect();
+        assert_eq!(outputs, vec![2.0, 2.0, 2.0, 3.0, 5.0]);
+        assert!(dma.is_ready());
+        assert_eq!(dma.next(&Bar::new().close(12)), 7.0);
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut cross = Displace::new(CrossAbove::new(5.0).unwrap(), 1).unwrap();
+        assert!(!cross.next(4.0));
+        assert!(!cross.next(6.0));
+        assert!(cross.next(7.0));
+        assert!(!cross.next(8.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dma = Displace::new(SimpleMovingAverage::new(2).unwrap(), 1).unwrap();
+        dma.next(2.0);
+        dma.next(4.0);
+
+        dma.reset();
+        assert_eq!(dma.next(10.0), 10.0);
+        assert_eq!(dma.next(20.0), 10.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let dma = Displace::new(SimpleMovingAverage::new(9).unwrap(), 3).unwrap();
+        assert_eq!(format!("{}", dma), "DISPLACE(SMA(9), 3)");
+        assert_eq!(dma.bars(), 3);
+        assert_eq!(dma.inner().period(), 9);
+    }
+}
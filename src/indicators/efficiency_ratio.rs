@@ -1,8 +1,11 @@
-use std::fmt;
+use alloc::boxed::Box;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Kaufman's Efficiency Ratio (ER).
@@ -28,9 +31,9 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(er.next(18.0), 0.8);
 /// assert_eq!(er.next(19.0), 0.75);
 /// ```
-
 #[doc(alias = "ER")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EfficiencyRatio {
     period: usize,
     index: usize,
@@ -46,7 +49,7 @@ impl EfficiencyRatio {
                 period,
                 index: 0,
                 count: 0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                deque: ring(0.0, period)?,
             }),
         }
     }
@@ -58,6 +61,24 @@ impl Period for EfficiencyRatio {
     }
 }
 
+impl Validate for EfficiencyRatio {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for EfficiencyRatio {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
 impl Next<f64> for EfficiencyRatio {
     type Output = f64;
 
@@ -0,0 +1,197 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::Result;
+use crate::helpers::ring;
+use crate::indicators::ValueAtRisk;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Historical expected shortfall (ES), also known as conditional value at risk (CVaR).
+///
+/// Returns the average loss of the returns of the last _period_ inputs which are at least as bad
+/// as the [ValueAtRisk] at the same confidence level. Unlike the VaR it takes the size of the
+/// losses beyond the threshold into account, so it is never lower than the VaR.
+///
+/// The inputs are returns, e.g. `0.01` for a gain of 1%. The tail is averaged by scanning the
+/// window, so every update is O(period).
+///
+/// # Formula
+///
+/// _ES = -mean(r<sub>i</sub> : r<sub>i</sub> ≤ -VaR)_
+///
+/// # Parameters
+///
+/// * _confidence_ - confidence level (between 0.0 and 1.0, exclusive). Default is 0.95.
+/// * _period_ - number of returns (integer greater than 0). Default is 100.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ExpectedShortfall;
+/// use ta_panther::Next;
+///
+/// let mut es = ExpectedShortfall::new(0.75, 5).unwrap();
+/// for &r in [0.01, -0.02, 0.03, -0.04].iter() {
+///     es.next(r);
+/// }
+/// assert_eq!(es.next(0.0), 0.03);
+/// ```
+///
+/// # Links
+///
+/// * [Expected shortfall, Wikipedia](https://en.wikipedia.org/wiki/Expected_shortfall)
+///
+#[doc(alias = "ES")]
+#[doc(alias = "CVAR")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpectedShortfall {
+    var: ValueAtRisk,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+}
+
+impl ExpectedShortfall {
+    pub fn new(confidence: f64, period: usize) -> Result<Self> {
+        Ok(Self {
+            var: ValueAtRisk::new(confidence, period)?,
+            index: 0,
+            count: 0,
+            deque: ring(0.0, period)?,
+        })
+    }
+
+    pub fn confidence(&self) -> f64 {
+        self.var.confidence()
+    }
+}
+
+impl Period for ExpectedShortfall {
+    fn period(&self) -> usize {
+        self.var.period()
+    }
+}
+
+impl IsReady for ExpectedShortfall {
+    fn bars_remaining(&self) -> usize {
+        self.period()
+            .saturating_sub(self.count)
+            .max(self.var.bars_remaining())
+    }
+}
+
+impl Next<f64> for ExpectedShortfall {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let threshold = -self.var.next(input);
+
+        let period = self.period();
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < period {
+            self.count += 1;
+        }
+
+        // the minimum of the window is never above the threshold, so the tail is not empty
+        let (sum, n) = self.deque[..self.count]
+            .iter()
+            .filter(|&&r| r <= threshold)
+            .fold((0.0, 0), |(sum, n), &r| (sum + r, n + 1));
+        -sum / n as f64
+    }
+}
+
+impl<T: Close> Next<&T> for ExpectedShortfall {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ExpectedShortfall {
+    fn reset(&mut self) {
+        self.var.reset();
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.deque.len() {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for ExpectedShortfall {
+    fn default() -> Self {
+        Self::new(0.95, 100).unwrap()
+    }
+}
+
+impl fmt::Display for ExpectedShortfall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ES({}, {})", self.confidence(), self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ExpectedShortfall);
+
+    #[test]
+    fn test_new() {
+        assert!(ExpectedShortfall::new(0.95, 0).is_err());
+        assert!(ExpectedShortfall::new(0.0, 10).is_err());
+        assert!(ExpectedShortfall::new(1.5, 10).is_err());
+        assert!(ExpectedShortfall::new(0.99, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut es = ExpectedShortfall::new(0.8, 5).unwrap();
+        assert_eq!(round(es.next(0.01)), -0.01);
+        assert_eq!(round(es.next(-0.02)), 0.02);
+        assert_eq!(round(es.next(0.03)), 0.02);
+        assert_eq!(round(es.next(-0.05)), 0.05);
+        assert_eq!(round(es.next(0.0)), 0.05);
+        assert_eq!(round(es.next(0.02)), 0.05);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut es = ExpectedShortfall::new(0.5, 2).unwrap();
+        es.next(&Bar::new().close(-0.01));
+        assert_eq!(round(es.next(&Bar::new().close(-0.03))), 0.03);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut es = ExpectedShortfall::new(0.9, 3).unwrap();
+        es.next(-0.1);
+        es.next(0.1);
+
+        es.reset();
+        assert_eq!(es.next(0.05), -0.05);
+    }
+
+    #[test]
+    fn test_default() {
+        ExpectedShortfall::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let es = ExpectedShortfall::new(0.975, 250).unwrap();
+        assert_eq!(format!("{}", es), "ES(0.975, 250)");
+    }
+}
@@ -1,8 +1,9 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, State, Period, Reset};
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset, State, Validate};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// An exponential moving average (EMA), also known as an exponentially weighted moving average
@@ -51,14 +52,16 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Exponential moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Exponential_moving_average)
 ///
-
 #[doc(alias = "EMA")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExponentialMovingAverage {
     period: usize,
     k: f64,
     current: f64,
     is_new: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
 }
 
 impl ExponentialMovingAverage {
@@ -67,9 +70,10 @@ impl ExponentialMovingAverage {
             0 => Err(TaError::InvalidParameter),
             _ => Ok(Self {
                 period,
-                k: 2.0 / (period + 1) as f64,
+                k: 2.0 / (period as f64 + 1.0),
                 current: 0.0,
                 is_new: true,
+                count: 0,
             }),
         }
     }
@@ -79,9 +83,10 @@ impl ExponentialMovingAverage {
             0 => Err(TaError::InvalidParameter),
             _ => Ok(Self {
                 period,
-                k: 2.0 / (period + 1) as f64,
+                k: 2.0 / (period as f64 + 1.0),
                 current,
                 is_new: false,
+                count: period,
             }),
         }
     }
@@ -93,10 +98,32 @@ impl Period for ExponentialMovingAverage {
     }
 }
 
+impl Validate for ExponentialMovingAverage {
+    fn validate(&self) -> Result<()> {
+        let consistent = self.period > 0
+            && self.k == 2.0 / (self.period as f64 + 1.0)
+            && self.count <= self.period
+            && !(self.is_new && self.count > 0);
+        match consistent {
+            true => Ok(()),
+            false => Err(TaError::InvalidState),
+        }
+    }
+}
+
+impl IsReady for ExponentialMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
 impl Next<f64> for ExponentialMovingAverage {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        }
         if self.is_new {
             self.is_new = false;
             self.current = input;
@@ -123,10 +150,13 @@ impl<T: Close> Next<&T> for ExponentialMovingAverage {
     }
 }
 
+impl NextBatch for ExponentialMovingAverage {}
+
 impl Reset for ExponentialMovingAverage {
     fn reset(&mut self) {
         self.current = 0.0;
         self.is_new = true;
+        self.count = 0;
     }
 }
 
@@ -195,4 +225,21 @@ mod tests {
         let ema = ExponentialMovingAverage::new(7).unwrap();
         assert_eq!(format!("{}", ema), "EMA(7)");
     }
+
+    #[test]
+    fn test_is_ready() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        assert_eq!(ema.bars_remaining(), 3);
+        ema.next(1.0);
+        ema.next(2.0);
+        assert_eq!(ema.bars_remaining(), 1);
+        ema.next(3.0);
+        assert!(ema.is_ready());
+
+        ema.reset();
+        assert_eq!(ema.bars_remaining(), 3);
+        assert!(ExponentialMovingAverage::from_state(3, 2.0)
+            .unwrap()
+            .is_ready());
+    }
 }
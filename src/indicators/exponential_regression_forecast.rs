@@ -0,0 +1,179 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{deque, linear_regression};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Exponential Regression Forecast.
+///
+/// Fits an exponential curve through the last `period` values and extrapolates it one bar
+/// ahead. It suits series growing or decaying at a roughly constant rate, where
+/// [LinearRegressionPrediction](crate::indicators::LinearRegressionPrediction) lags behind.
+///
+/// # Formula
+///
+/// y = e<sup>a + bx</sup>, where _a_ and _b_ are found by a linear regression of
+/// ln(p<sub>i</sub>) over _x = 1, 2, ..., n_. The forecast is the curve evaluated at
+/// _x = n + 1_.
+///
+/// The inputs must be positive, otherwise the output is `NaN` until the non-positive value
+/// leaves the window.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ExponentialRegressionForecast;
+/// use ta_panther::Next;
+///
+/// let mut erf = ExponentialRegressionForecast::new(3).unwrap();
+/// erf.next(1.0);
+/// erf.next(2.0);
+/// assert_eq!(erf.next(4.0).round(), 8.0);
+/// ```
+///
+/// # Links
+///
+/// * [Exponential regression, Wikipedia](https://en.wikipedia.org/wiki/Nonlinear_regression#Transformation)
+///
+#[doc(alias = "EXP_FORECAST")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExponentialRegressionForecast {
+    period: usize,
+    deque: VecDeque<f64>,
+}
+
+impl ExponentialRegressionForecast {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                deque: deque(period)?,
+            }),
+        }
+    }
+}
+
+impl Period for ExponentialRegressionForecast {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for ExponentialRegressionForecast {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.deque.len())
+    }
+}
+
+impl Next<f64> for ExponentialRegressionForecast {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.deque.len() == self.period {
+            self.deque.pop_front();
+        }
+        self.deque.push_back(input.ln());
+
+        let (slope, intercept) = linear_regression(self.deque.iter().copied());
+        (slope * (self.deque.len() as f64 + 1.0) + intercept).exp()
+    }
+}
+
+impl<T: Close> Next<&T> for ExponentialRegressionForecast {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ExponentialRegressionForecast {
+    fn reset(&mut self) {
+        self.deque.clear();
+    }
+}
+
+impl Default for ExponentialRegressionForecast {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ExponentialRegressionForecast {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EXP_FORECAST({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ExponentialRegressionForecast);
+
+    #[test]
+    fn test_new() {
+        assert!(ExponentialRegressionForecast::new(0).is_err());
+        assert!(ExponentialRegressionForecast::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut erf = ExponentialRegressionForecast::new(3).unwrap();
+        assert_eq!(round(erf.next(2.0)), 2.0);
+        assert_eq!(round(erf.next(6.0)), 18.0);
+        assert_eq!(round(erf.next(18.0)), 54.0);
+        assert_eq!(round(erf.next(54.0)), 162.0);
+        assert_eq!(round(erf.next(54.0)), 112.325);
+    }
+
+    #[test]
+    fn test_non_positive_input() {
+        let mut erf = ExponentialRegressionForecast::new(2).unwrap();
+        erf.next(1.0);
+        assert!(erf.next(-1.0).is_nan());
+        assert!(erf.next(2.0).is_nan());
+        assert!(!erf.next(4.0).is_nan());
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut erf = ExponentialRegressionForecast::new(3).unwrap();
+        erf.next(&Bar::new().close(1));
+        assert_eq!(round(erf.next(&Bar::new().close(3))), 9.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut erf = ExponentialRegressionForecast::new(3).unwrap();
+        erf.next(1.0);
+        erf.next(5.0);
+
+        erf.reset();
+        assert_eq!(round(erf.next(7.0)), 7.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ExponentialRegressionForecast::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let erf = ExponentialRegressionForecast::new(10).unwrap();
+        assert_eq!(format!("{}", erf), "EXP_FORECAST(10)");
+    }
+}
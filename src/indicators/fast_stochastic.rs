@@ -1,9 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
-use crate::indicators::{Maximum, Minimum};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::rolling::RollingMinMax;
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Fast stochastic oscillator.
@@ -40,26 +41,29 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(stoch.next(35.0), 75.0);
 /// assert_eq!(stoch.next(15.0), 0.0);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FastStochastic {
-    period: usize,
-    minimum: Minimum,
-    maximum: Maximum,
+    range: RollingMinMax,
 }
 
 impl FastStochastic {
     pub fn new(period: usize) -> Result<Self> {
         Ok(Self {
-            period,
-            minimum: Minimum::new(period)?,
-            maximum: Maximum::new(period)?,
+            range: RollingMinMax::new(period)?,
         })
     }
 }
 
 impl Period for FastStochastic {
     fn period(&self) -> usize {
-        self.period
+        self.range.capacity()
+    }
+}
+
+impl IsReady for FastStochastic {
+    fn bars_remaining(&self) -> usize {
+        self.range.capacity() - self.range.len()
     }
 }
 
@@ -67,8 +71,7 @@ impl Next<f64> for FastStochastic {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        let min = self.minimum.next(input);
-        let max = self.maximum.next(input);
+        let (min, max) = self.range.push(input);
 
         if min == max {
             // When only 1 input was given, than min and max are the same,
@@ -84,8 +87,7 @@ impl<T: High + Low + Close> Next<&T> for FastStochastic {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        let highest = self.maximum.next(input.high());
-        let lowest = self.minimum.next(input.low());
+        let (lowest, highest) = self.range.push_range(input.low(), input.high());
         let close = input.close();
 
         if highest == lowest {
@@ -99,8 +101,7 @@ impl<T: High + Low + Close> Next<&T> for FastStochastic {
 
 impl Reset for FastStochastic {
     fn reset(&mut self) {
-        self.minimum.reset();
-        self.maximum.reset();
+        self.range.clear();
     }
 }
 
@@ -112,7 +113,7 @@ impl Default for FastStochastic {
 
 impl fmt::Display for FastStochastic {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "FAST_STOCH({})", self.period)
+        write!(f, "FAST_STOCH({})", self.period())
     }
 }
 
@@ -0,0 +1,168 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::LinearRegressionPrediction;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Forecast Oscillator (FOSC).
+///
+/// Compares the current value with the value which was forecast for it on the previous bar by
+/// the [LinearRegressionPrediction](crate::indicators::LinearRegressionPrediction) (FORECAST).
+/// A positive oscillator means the price is above its forecast, a negative one means it is below.
+///
+/// # Formula
+///
+/// FOSC<sub>t</sub> = (p<sub>t</sub> - FORECAST<sub>t-1</sub>) / p<sub>t</sub> * 100
+///
+/// Where:
+///
+/// * _p<sub>t</sub>_ - input value at a point of time _t_
+/// * _FORECAST<sub>t-1</sub>_ - value forecast for _t_ at a point of time _t-1_
+///
+/// The first output is `0.0`, since there is no forecast yet. The output is also `0.0` when the
+/// input is `0.0`.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods used by the regression (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ForecastOscillator;
+/// use ta_panther::Next;
+///
+/// let mut fosc = ForecastOscillator::new(3).unwrap();
+/// assert_eq!(fosc.next(1.0), 0.0);
+/// assert_eq!(fosc.next(2.0), 50.0);
+/// assert_eq!(fosc.next(4.0), 25.0);
+/// ```
+///
+/// # Links
+///
+/// * [Forecast Oscillator, Fidelity](https://www.fidelity.com/learning-center/trading-investing/technical-analysis/technical-indicator-guide/fosc)
+///
+#[doc(alias = "FOSC")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ForecastOscillator {
+    forecast: LinearRegressionPrediction,
+    previous: Option<f64>,
+}
+
+impl ForecastOscillator {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            forecast: LinearRegressionPrediction::new(period)?,
+            previous: None,
+        })
+    }
+}
+
+impl Period for ForecastOscillator {
+    fn period(&self) -> usize {
+        self.forecast.period()
+    }
+}
+
+impl IsReady for ForecastOscillator {
+    fn bars_remaining(&self) -> usize {
+        self.forecast.bars_remaining()
+    }
+}
+
+impl Next<f64> for ForecastOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let oscillator = match self.previous {
+            Some(forecast) if input != 0.0 => (input - forecast) / input * 100.0,
+            _ => 0.0,
+        };
+        self.previous = Some(self.forecast.next(input));
+        oscillator
+    }
+}
+
+impl<T: Close> Next<&T> for ForecastOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ForecastOscillator {
+    fn reset(&mut self) {
+        self.forecast.reset();
+        self.previous = None;
+    }
+}
+
+impl Default for ForecastOscillator {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ForecastOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FOSC({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ForecastOscillator);
+
+    #[test]
+    fn test_new() {
+        assert!(ForecastOscillator::new(0).is_err());
+        assert!(ForecastOscillator::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut fosc = ForecastOscillator::new(3).unwrap();
+        assert_eq!(fosc.next(1.0), 0.0);
+        assert_eq!(fosc.next(2.0), 50.0);
+        // forecast of 3 on the line through the first two inputs
+        assert_eq!(fosc.next(2.0), -50.0);
+        assert_eq!(round(fosc.next(4.0)), 33.333);
+        assert_eq!(fosc.next(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut fosc = ForecastOscillator::new(3).unwrap();
+        assert_eq!(fosc.next(&Bar::new().close(1)), 0.0);
+        assert_eq!(fosc.next(&Bar::new().close(2)), 50.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut fosc = ForecastOscillator::new(3).unwrap();
+        fosc.next(1.0);
+        fosc.next(2.0);
+
+        fosc.reset();
+        assert_eq!(fosc.next(2.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ForecastOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let fosc = ForecastOscillator::new(5).unwrap();
+        assert_eq!(format!("{}", fosc), "FOSC(5)");
+    }
+}
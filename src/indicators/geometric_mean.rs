@@ -0,0 +1,220 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling geometric mean.
+///
+/// Returns the _n_-th root of the product of the last _period_ inputs. It is the appropriate
+/// average of growth factors and price ratios, which the arithmetic mean overestimates: e.g. the
+/// average growth of returns is the geometric mean of the factors _1 + r_ minus 1.
+///
+/// The inputs must be positive. While the window holds a value which is not, the output is NaN.
+/// A rolling sum of logarithms is kept, so every update is O(1).
+///
+/// # Formula
+///
+/// _GM = exp(Σ ln(x<sub>i</sub>) / n)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::GeometricMean;
+/// use ta_panther::Next;
+///
+/// let mut gm = GeometricMean::new(2).unwrap();
+/// assert_eq!(gm.next(2.0), 2.0);
+/// assert_eq!(gm.next(8.0), 4.0);
+/// ```
+///
+/// # Links
+///
+/// * [Geometric mean, Wikipedia](https://en.wikipedia.org/wiki/Geometric_mean)
+///
+#[doc(alias = "GM")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeometricMean {
+    period: usize,
+    index: usize,
+    count: usize,
+    invalid: usize,
+    sum: f64,
+    deque: Box<[f64]>,
+}
+
+impl GeometricMean {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                invalid: 0,
+                sum: 0.0,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for GeometricMean {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for GeometricMean {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for GeometricMean {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for GeometricMean {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let old = self.deque[self.index];
+            if old.is_nan() {
+                self.invalid -= 1;
+            } else {
+                self.sum -= old;
+            }
+        }
+
+        // invalid inputs are kept out of the sum, so it recovers once they leave the window
+        let log = if input > 0.0 { input.ln() } else { f64::NAN };
+        if log.is_nan() {
+            self.invalid += 1;
+        } else {
+            self.sum += log;
+        }
+
+        self.deque[self.index] = log;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.invalid > 0 {
+            f64::NAN
+        } else {
+            (self.sum / self.count as f64).exp()
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for GeometricMean {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for GeometricMean {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.invalid = 0;
+        self.sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for GeometricMean {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for GeometricMean {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GM({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(GeometricMean);
+
+    #[test]
+    fn test_new() {
+        assert!(GeometricMean::new(0).is_err());
+        assert!(GeometricMean::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut gm = GeometricMean::new(3).unwrap();
+        assert_eq!(round(gm.next(1.0)), 1.0);
+        assert_eq!(round(gm.next(4.0)), 2.0);
+        assert_eq!(round(gm.next(2.0)), 2.0);
+        assert_eq!(round(gm.next(8.0)), 4.0);
+        assert!(gm.next(0.0).is_nan());
+        assert!(gm.next(3.0).is_nan());
+        assert!(gm.next(9.0).is_nan());
+        assert_eq!(round(gm.next(1.0)), 3.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut gm = GeometricMean::new(2).unwrap();
+        gm.next(&Bar::new().close(3));
+        assert_eq!(round(gm.next(&Bar::new().close(12))), 6.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut gm = GeometricMean::new(3).unwrap();
+        gm.next(-1.0);
+        gm.next(10.0);
+
+        gm.reset();
+        assert_eq!(round(gm.next(5.0)), 5.0);
+    }
+
+    #[test]
+    fn test_default() {
+        GeometricMean::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let gm = GeometricMean::new(10).unwrap();
+        assert_eq!(format!("{}", gm), "GM(10)");
+    }
+}
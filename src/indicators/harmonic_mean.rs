@@ -0,0 +1,218 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling harmonic mean.
+///
+/// Returns the reciprocal of the mean of the reciprocals of the last _period_ inputs. It is the
+/// appropriate average of rates and ratios with a fixed numerator, e.g. the average price paid
+/// when investing the same amount at every period, or the average of price/earnings ratios.
+///
+/// The inputs must be positive. While the window holds a value which is not, the output is NaN.
+/// A rolling sum of reciprocals is kept, so every update is O(1).
+///
+/// # Formula
+///
+/// _HM = n / Σ (1 / x<sub>i</sub>)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::HarmonicMean;
+/// use ta_panther::Next;
+///
+/// let mut hm = HarmonicMean::new(2).unwrap();
+/// assert_eq!(hm.next(2.0), 2.0);
+/// assert_eq!(hm.next(6.0), 3.0);
+/// ```
+///
+/// # Links
+///
+/// * [Harmonic mean, Wikipedia](https://en.wikipedia.org/wiki/Harmonic_mean)
+///
+#[doc(alias = "HM")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HarmonicMean {
+    period: usize,
+    index: usize,
+    count: usize,
+    invalid: usize,
+    sum: f64,
+    deque: Box<[f64]>,
+}
+
+impl HarmonicMean {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                invalid: 0,
+                sum: 0.0,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for HarmonicMean {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for HarmonicMean {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for HarmonicMean {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for HarmonicMean {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let old = self.deque[self.index];
+            if old.is_nan() {
+                self.invalid -= 1;
+            } else {
+                self.sum -= old;
+            }
+        }
+
+        // invalid inputs are kept out of the sum, so it recovers once they leave the window
+        let reciprocal = if input > 0.0 { 1.0 / input } else { f64::NAN };
+        if reciprocal.is_nan() {
+            self.invalid += 1;
+        } else {
+            self.sum += reciprocal;
+        }
+
+        self.deque[self.index] = reciprocal;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.invalid > 0 {
+            f64::NAN
+        } else {
+            self.count as f64 / self.sum
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for HarmonicMean {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for HarmonicMean {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.invalid = 0;
+        self.sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for HarmonicMean {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for HarmonicMean {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HM({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(HarmonicMean);
+
+    #[test]
+    fn test_new() {
+        assert!(HarmonicMean::new(0).is_err());
+        assert!(HarmonicMean::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut hm = HarmonicMean::new(3).unwrap();
+        assert_eq!(round(hm.next(1.0)), 1.0);
+        assert_eq!(round(hm.next(4.0)), 1.6);
+        assert_eq!(round(hm.next(2.0)), 1.714);
+        assert_eq!(round(hm.next(8.0)), 3.429);
+        assert!(hm.next(0.0).is_nan());
+        assert!(hm.next(3.0).is_nan());
+        assert!(hm.next(6.0).is_nan());
+        assert_eq!(round(hm.next(2.0)), 3.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut hm = HarmonicMean::new(2).unwrap();
+        hm.next(&Bar::new().close(3));
+        assert_eq!(round(hm.next(&Bar::new().close(6))), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hm = HarmonicMean::new(3).unwrap();
+        hm.next(-1.0);
+        hm.next(10.0);
+
+        hm.reset();
+        assert_eq!(round(hm.next(5.0)), 5.0);
+    }
+
+    #[test]
+    fn test_default() {
+        HarmonicMean::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let hm = HarmonicMean::new(10).unwrap();
+        assert_eq!(format!("{}", hm), "HM(10)");
+    }
+}
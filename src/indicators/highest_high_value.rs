@@ -1,8 +1,15 @@
-
-use serde::{Deserialize, Serialize};
-use std::fmt;
-use crate::errors::{Result, TaError};
-use crate::{Next, Period, Reset};
+use crate::errors::Result;
+use crate::rolling::RollingMinMax;
+#[cfg(feature = "serde")]
+use crate::rolling::RollingWindow;
+#[cfg(feature = "serde")]
+use crate::state::Ring;
+use crate::{IsReady, Next, NextBatch, Period, Reset, Validate};
+#[cfg(feature = "simd")]
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
 
 /// Highest High Value (HHV).
 ///
@@ -28,31 +35,69 @@ use crate::{Next, Period, Reset};
 /// # Links
 ///
 #[doc(alias = "HHV")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HighestHighValue {
-    period: usize,
-    index: usize,
-    count: usize,
-    deque: Box<[f64]>,
+    range: RollingMinMax,
 }
 
 impl HighestHighValue {
     pub fn new(period: usize) -> Result<Self> {
-        match period {
-            0 => Err(TaError::InvalidParameter),
-            _ => Ok(Self {
-                period,
-                index: 0,
-                count: 0,
-                deque: vec![f64::NEG_INFINITY; period].into_boxed_slice(),
-            }),
-        }
+        Ok(Self {
+            range: RollingMinMax::new(period)?,
+        })
     }
 }
 
 impl Period for HighestHighValue {
     fn period(&self) -> usize {
-        self.period
+        self.range.capacity()
+    }
+}
+
+impl Validate for HighestHighValue {
+    /// The window is tracked by a [RollingMinMax] since version 3.
+    const STATE_VERSION: u32 = 3;
+
+    fn validate(&self) -> Result<()> {
+        self.range.validate()
+    }
+
+    #[cfg(feature = "serde")]
+    fn deserialize_version<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        let (period, values) = match version {
+            1 => {
+                let ring = Ring::deserialize(deserializer)?;
+                (ring.period, ring.values().map_err(D::Error::custom)?)
+            }
+            2 => {
+                let window = StateV2::deserialize(deserializer)?.window;
+                window.validate().map_err(D::Error::custom)?;
+                (window.capacity(), window.to_vec())
+            }
+            _ => return Self::deserialize(deserializer),
+        };
+        let mut hhv = Self::new(period).map_err(D::Error::custom)?;
+        for value in values {
+            hhv.range.push(value);
+        }
+        Ok(hhv)
+    }
+}
+
+/// State of version 2, a [RollingWindow] of the inputs. Version 1 was a [Ring].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StateV2 {
+    window: RollingWindow<f64>,
+}
+
+impl IsReady for HighestHighValue {
+    fn bars_remaining(&self) -> usize {
+        self.range.capacity() - self.range.len()
     }
 }
 
@@ -60,28 +105,21 @@ impl Next<f64> for HighestHighValue {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        self.deque[self.index] = input;
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
-        if self.count < self.period {
-            self.count += 1;
-        }
-        self.deque[..self.count]
-            .iter()
-            .fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+        self.range.push(input).1
+    }
+}
+
+impl NextBatch for HighestHighValue {
+    /// Runs the [rolling_max](crate::simd::rolling_max) kernel if no input was seen yet.
+    #[cfg(feature = "simd")]
+    fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+        crate::simd::next_batch(self, input, crate::simd::rolling_max)
     }
 }
 
 impl Reset for HighestHighValue {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
-        for i in 0..self.period {
-            self.deque[i] = f64::NEG_INFINITY;
-        }
+        self.range.clear();
     }
 }
 
@@ -93,6 +131,6 @@ impl Default for HighestHighValue {
 
 impl fmt::Display for HighestHighValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "HHV:{}", self.period)
+        write!(f, "HHV:{}", self.period())
     }
 }
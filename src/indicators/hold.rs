@@ -0,0 +1,163 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Held signal.
+///
+/// Wraps an indicator returning a signal and keeps it true for _bars_ inputs after it fired,
+/// including the input it fired on. A signal firing again while held starts over. Holding the
+/// single input signal of a cross detector allows combining it with conditions that are met a
+/// few inputs later, e.g. a cross followed by a rise of the volume.
+///
+/// # Parameters
+///
+/// * _bars_ - number of inputs to hold the signal for (integer greater than 0).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{CrossAbove, Hold};
+/// use ta_panther::Next;
+///
+/// let mut cross = Hold::new(CrossAbove::new(10.0).unwrap(), 2).unwrap();
+/// assert_eq!(cross.next(9.0), false);
+/// assert_eq!(cross.next(11.0), true);
+/// assert_eq!(cross.next(12.0), true);
+/// assert_eq!(cross.next(13.0), false);
+/// ```
+///
+#[doc(alias = "LATCH")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hold<N> {
+    indicator: N,
+    bars: usize,
+    remaining: usize,
+}
+
+impl<N> Hold<N> {
+    pub fn new(indicator: N, bars: usize) -> Result<Self> {
+        match bars {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                indicator,
+                bars,
+                remaining: 0,
+            }),
+        }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn inner(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn bars(&self) -> usize {
+        self.bars
+    }
+}
+
+impl<N: Period> Period for Hold<N> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<N: IsReady> IsReady for Hold<N> {
+    fn bars_remaining(&self) -> usize {
+        self.indicator.bars_remaining()
+    }
+}
+
+impl<N: Next<T, Output = bool>, T> Next<T> for Hold<N> {
+    type Output = bool;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.indicator.next(input) {
+            self.remaining = self.bars;
+        }
+        let held = self.remaining > 0;
+        self.remaining = self.remaining.saturating_sub(1);
+        held
+    }
+}
+
+impl<N: Reset> Reset for Hold<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.remaining = 0;
+    }
+}
+
+impl<N: Default> Default for Hold<N> {
+    fn default() -> Self {
+        Self::new(N::default(), 1).unwrap()
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for Hold<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HOLD({}, {})", self.indicator, self.bars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Compare, CrossAbove};
+    use alloc::format;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn test_new() {
+        assert!(Hold::new(CrossAbove::new(1.0).unwrap(), 0).is_err());
+        assert!(Hold::new(CrossAbove::new(1.0).unwrap(), 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cross = Hold::new(CrossAbove::new(1.0).unwrap(), 3).unwrap();
+        let outputs: Vec<bool> = [0.0, 2.0, 2.0, 0.0, 2.0, 3.0, 3.0, 3.0, 3.0]
+            .iter()
+            .map(|&price| cross.next(price))
+            .collect();
+        // the second cross starts the hold over
+        assert_eq!(
+            outputs,
+            vec![false, true, true, true, true, true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_one_bar() {
+        let mut cross = Hold::new(CrossAbove::new(1.0).unwrap(), 1).unwrap();
+        assert!(!cross.next(0.0));
+        assert!(cross.next(2.0));
+        assert!(!cross.next(3.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cross = Hold::new(CrossAbove::new(1.0).unwrap(), 3).unwrap();
+        cross.next(0.0);
+        cross.next(2.0);
+
+        cross.reset();
+        assert!(!cross.next(3.0));
+    }
+
+    #[test]
+    fn test_default() {
+        Hold::<Compare>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let cross = Hold::new(CrossAbove::new(30.0).unwrap(), 3).unwrap();
+        assert_eq!(format!("{}", cross), "HOLD(CROSS_ABOVE(30), 3)");
+    }
+}
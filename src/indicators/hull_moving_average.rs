@@ -0,0 +1,214 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::indicators::WeightedMovingAverage;
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Hull moving average (HMA).
+///
+/// A moving average developed by Alan Hull which almost eliminates the lag while staying smooth.
+/// The difference of a [WMA](crate::indicators::WeightedMovingAverage) of half of the period and
+/// a WMA of the full period extrapolates the trend, and a WMA of the square root of the period
+/// smooths the result. It follows a linear trend without lag once _period_ inputs have been seen.
+///
+/// # Formula
+///
+/// _HMA = WMA<sub>√n</sub>(2 × WMA<sub>n/2</sub>(p) - WMA<sub>n</sub>(p))_
+///
+/// Where _n/2_ and _√n_ are rounded down, but not below 1.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::HullMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut hma = HullMovingAverage::new(4).unwrap();
+/// for &price in [10.0, 11.0, 12.0, 13.0].iter() {
+///     hma.next(price);
+/// }
+/// assert_eq!(hma.next(14.0), 14.0);
+/// assert_eq!(hma.next(15.0), 15.0);
+/// ```
+///
+/// # Links
+///
+/// * [Hull moving average, Alan Hull](https://alanhull.com/hull-moving-average)
+///
+#[doc(alias = "HMA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HullMovingAverage {
+    period: usize,
+    half: WeightedMovingAverage,
+    full: WeightedMovingAverage,
+    smooth: WeightedMovingAverage,
+    // inputs seen, up to the warm-up
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
+}
+
+impl HullMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                half: WeightedMovingAverage::new((period / 2).max(1))?,
+                full: WeightedMovingAverage::new(period)?,
+                smooth: WeightedMovingAverage::new(((period as f64).sqrt() as usize).max(1))?,
+                count: 0,
+            }),
+        }
+    }
+}
+
+impl Period for HullMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for HullMovingAverage {
+    /// The smoothing WMA is full of differences of full windows after _period + √n - 1_
+    /// inputs.
+    fn bars_remaining(&self) -> usize {
+        let warm_up = self.full.period() + self.smooth.period() - 1;
+        warm_up.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for HullMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        let raw = 2.0 * self.half.next(input) - self.full.next(input);
+        self.smooth.next(raw)
+    }
+}
+
+impl<T: Close> Next<&T> for HullMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl NextBatch for HullMovingAverage {}
+
+impl Reset for HullMovingAverage {
+    fn reset(&mut self) {
+        self.half.reset();
+        self.full.reset();
+        self.smooth.reset();
+        self.count = 0;
+    }
+}
+
+impl Default for HullMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for HullMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(HullMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(HullMovingAverage::new(0).is_err());
+        assert!(HullMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut hma = HullMovingAverage::new(4).unwrap();
+        assert_eq!(hma.next(10.0), 10.0);
+        assert_eq!(round(hma.next(12.0)), 10.889);
+        assert_eq!(round(hma.next(11.0)), 11.444);
+        assert_eq!(round(hma.next(14.0)), 12.967);
+        assert_eq!(round(hma.next(15.0)), 15.056);
+        assert_eq!(round(hma.next(13.0)), 14.4);
+        assert_eq!(round(hma.next(16.0)), 14.778);
+
+        let mut hma = HullMovingAverage::new(9).unwrap();
+        let outputs: Vec<f64> = [10.0, 12.0, 11.0, 14.0, 15.0, 13.0, 16.0]
+            .iter()
+            .map(|&x| round(hma.next(x)))
+            .collect();
+        assert_eq!(
+            outputs,
+            vec![10.0, 10.889, 11.028, 11.761, 12.961, 13.745, 14.79]
+        );
+    }
+
+    #[test]
+    fn test_is_ready() {
+        // WMAs of 2, 4 and 2 inputs
+        let mut hma = HullMovingAverage::new(4).unwrap();
+        assert_eq!(hma.bars_remaining(), 5);
+        for input in [10.0, 12.0, 11.0, 14.0] {
+            hma.next(input);
+        }
+        assert_eq!(hma.bars_remaining(), 1);
+        hma.next(15.0);
+        assert!(hma.is_ready());
+        hma.next(13.0);
+        assert!(hma.is_ready());
+
+        hma.reset();
+        assert_eq!(hma.bars_remaining(), 5);
+        assert_eq!(HullMovingAverage::new(1).unwrap().bars_remaining(), 1);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut hma = HullMovingAverage::new(1).unwrap();
+        assert_eq!(hma.next(&Bar::new().close(4)), 4.0);
+        assert_eq!(hma.next(&Bar::new().close(8)), 8.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hma = HullMovingAverage::new(4).unwrap();
+        hma.next(4.0);
+        hma.next(10.0);
+
+        hma.reset();
+        assert_eq!(hma.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        HullMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let hma = HullMovingAverage::new(16).unwrap();
+        assert_eq!(format!("{}", hma), "HMA(16)");
+    }
+}
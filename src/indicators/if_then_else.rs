@@ -0,0 +1,87 @@
+use core::fmt;
+
+use crate::{Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// If-then-else selection.
+///
+/// Returns the second element of a `(condition, then, else)` input if the condition holds and
+/// the third one otherwise. The condition is usually the output of a
+/// [Compare](crate::indicators::Compare) or of a cross detector, the values outputs of other
+/// indicators or constants.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{Compare, Comparison, IfThenElse};
+/// use ta_panther::Next;
+///
+/// // the larger of the close and the open, written as a formula
+/// let mut above = Compare::new(Comparison::Greater);
+/// let mut select = IfThenElse::new();
+///
+/// let (open, close) = (10.0, 12.0);
+/// assert_eq!(select.next((above.next((close, open)), close, open)), 12.0);
+///
+/// let (open, close) = (12.0, 11.0);
+/// assert_eq!(select.next((above.next((close, open)), close, open)), 12.0);
+/// ```
+///
+#[doc(alias = "IF")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IfThenElse {}
+
+impl IfThenElse {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Next<(bool, f64, f64)> for IfThenElse {
+    type Output = f64;
+
+    fn next(&mut self, (condition, then, otherwise): (bool, f64, f64)) -> Self::Output {
+        if condition {
+            then
+        } else {
+            otherwise
+        }
+    }
+}
+
+impl Reset for IfThenElse {
+    fn reset(&mut self) {}
+}
+
+impl fmt::Display for IfThenElse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IF")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_next() {
+        let mut select = IfThenElse::new();
+        assert_eq!(select.next((true, 1.0, 2.0)), 1.0);
+        assert_eq!(select.next((false, 1.0, 2.0)), 2.0);
+        assert!(select.next((true, f64::NAN, 2.0)).is_nan());
+    }
+
+    #[test]
+    fn test_default() {
+        IfThenElse::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", IfThenElse::new()), "IF");
+    }
+}
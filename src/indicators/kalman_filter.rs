@@ -0,0 +1,261 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kalman Filter smoother.
+///
+/// A low-lag price smoother based on a constant-velocity model: the price is assumed to move
+/// with a velocity which changes only by random acceleration, and every input is a noisy
+/// measurement of the price. On every input the filter predicts the next state and corrects it
+/// by the measurement, weighting both by their uncertainty.
+///
+/// Compared to an EMA, the filter tracks trends without a systematic lag, because the estimated
+/// velocity is part of the prediction.
+///
+/// # Formula
+///
+/// State _x = [level, velocity]_, transition _F = [[1, 1], [0, 1]]_, measurement
+/// _H = [1, 0]_.
+///
+/// Predict:
+///
+/// * _x = F x_
+/// * _P = F P F<sup>T</sup> + Q_, where _Q = q [[1/4, 1/2], [1/2, 1]]_
+///
+/// Update with the input _z_:
+///
+/// * _K = P H<sup>T</sup> / (H P H<sup>T</sup> + r)_
+/// * _x = x + K (z - H x)_
+/// * _P = (I - K H) P_
+///
+/// The first input initializes the level, with zero velocity.
+///
+/// # Parameters
+///
+/// * _process_noise_ - variance _q_ of the random acceleration (non-negative).
+///   Larger values make the filter react faster. Default is 0.01.
+/// * _measurement_noise_ - variance _r_ of the input noise (greater than 0).
+///   Larger values make the output smoother. Default is 1.0.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::KalmanFilter;
+/// use ta_panther::Next;
+///
+/// let mut kf = KalmanFilter::new(0.01, 1.0).unwrap();
+/// let out = kf.next(10.0);
+/// assert_eq!(out.value, 10.0);
+/// assert_eq!(out.velocity, 0.0);
+///
+/// let out = kf.next(11.0);
+/// assert!(out.value > 10.0 && out.value < 11.0);
+/// assert!(out.velocity > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Kalman filter, Wikipedia](https://en.wikipedia.org/wiki/Kalman_filter)
+///
+#[doc(alias = "KALMAN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KalmanFilter {
+    process_noise: f64,
+    measurement_noise: f64,
+    level: f64,
+    velocity: f64,
+    covariance: [[f64; 2]; 2],
+    is_new: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KalmanFilterOutput {
+    pub value: f64,
+    pub velocity: f64,
+}
+
+impl KalmanFilter {
+    pub fn new(process_noise: f64, measurement_noise: f64) -> Result<Self> {
+        if !(process_noise >= 0.0 && measurement_noise > 0.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            process_noise,
+            measurement_noise,
+            level: 0.0,
+            velocity: 0.0,
+            covariance: [[measurement_noise, 0.0], [0.0, measurement_noise]],
+            is_new: true,
+        })
+    }
+
+    pub fn process_noise(&self) -> f64 {
+        self.process_noise
+    }
+
+    pub fn measurement_noise(&self) -> f64 {
+        self.measurement_noise
+    }
+}
+
+impl Next<f64> for KalmanFilter {
+    type Output = KalmanFilterOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.is_new {
+            self.is_new = false;
+            self.level = input;
+            self.velocity = 0.0;
+            return KalmanFilterOutput {
+                value: input,
+                velocity: 0.0,
+            };
+        }
+
+        // predict
+        let q = self.process_noise;
+        let [[p00, p01], [p10, p11]] = self.covariance;
+        let level = self.level + self.velocity;
+        let p00 = p00 + p01 + p10 + p11 + q / 4.0;
+        let p01 = p01 + p11 + q / 2.0;
+        let p10 = p10 + p11 + q / 2.0;
+        let p11 = p11 + q;
+
+        // update
+        let s = p00 + self.measurement_noise;
+        let k0 = p00 / s;
+        let k1 = p10 / s;
+        let residual = input - level;
+        self.level = level + k0 * residual;
+        self.velocity += k1 * residual;
+        self.covariance = [
+            [(1.0 - k0) * p00, (1.0 - k0) * p01],
+            [p10 - k1 * p00, p11 - k1 * p01],
+        ];
+
+        KalmanFilterOutput {
+            value: self.level,
+            velocity: self.velocity,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for KalmanFilter {
+    type Output = KalmanFilterOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for KalmanFilter {
+    fn reset(&mut self) {
+        let r = self.measurement_noise;
+        self.level = 0.0;
+        self.velocity = 0.0;
+        self.covariance = [[r, 0.0], [0.0, r]];
+        self.is_new = true;
+    }
+}
+
+impl Default for KalmanFilter {
+    fn default() -> Self {
+        Self::new(0.01, 1.0).unwrap()
+    }
+}
+
+impl fmt::Display for KalmanFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "KALMAN({}, {})",
+            self.process_noise, self.measurement_noise
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(KalmanFilter);
+
+    #[test]
+    fn test_new() {
+        assert!(KalmanFilter::new(-0.1, 1.0).is_err());
+        assert!(KalmanFilter::new(0.1, 0.0).is_err());
+        assert!(KalmanFilter::new(f64::NAN, 1.0).is_err());
+        assert!(KalmanFilter::new(0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kf = KalmanFilter::new(0.01, 1.0).unwrap();
+        assert_eq!(kf.next(10.0).value, 10.0);
+
+        let out = kf.next(11.0);
+        assert_eq!(round(out.value), 10.667);
+        assert_eq!(round(out.velocity), 0.335);
+
+        let out = kf.next(12.0);
+        assert_eq!(round(out.value), 11.669);
+        assert_eq!(round(out.velocity), 0.671);
+    }
+
+    #[test]
+    fn test_constant_input() {
+        let mut kf = KalmanFilter::default();
+        for _ in 0..50 {
+            let out = kf.next(5.0);
+            assert_eq!(round(out.value), 5.0);
+            assert_eq!(round(out.velocity), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_tracks_trend() {
+        let mut kf = KalmanFilter::new(0.1, 1.0).unwrap();
+        let mut out = kf.next(0.0);
+        for i in 1..200 {
+            out = kf.next(i as f64 * 2.0);
+        }
+        assert_eq!(round(out.value), 398.0);
+        assert_eq!(round(out.velocity), 2.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut kf = KalmanFilter::default();
+        assert_eq!(kf.next(&Bar::new().close(7)).value, 7.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kf = KalmanFilter::default();
+        kf.next(10.0);
+        kf.next(20.0);
+
+        kf.reset();
+        let out = kf.next(3.0);
+        assert_eq!(out.value, 3.0);
+        assert_eq!(out.velocity, 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        KalmanFilter::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let kf = KalmanFilter::new(0.5, 2.0).unwrap();
+        assert_eq!(format!("{}", kf), "KALMAN(0.5, 2)");
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::ring;
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kaufman's adaptive moving average (KAMA).
+///
+/// An exponential moving average whose smoothing constant follows the
+/// [Efficiency Ratio](crate::indicators::EfficiencyRatio) of the last _period_ inputs: it
+/// reacts like a fast EMA while the market trends and almost stops moving while it ranges.
+///
+/// Until _period_ changes have been seen, the efficiency ratio of all changes so far is used.
+/// The first output is the first input.
+///
+/// # Formula
+///
+/// * _ER = |p<sub>t</sub> - p<sub>t-n</sub>| / Σ |p<sub>i</sub> - p<sub>i-1</sub>|_
+/// * _SC = (ER × (2 / (fast + 1) - 2 / (slow + 1)) + 2 / (slow + 1))<sup>2</sup>_
+/// * _KAMA<sub>t</sub> = KAMA<sub>t-1</sub> + SC × (p<sub>t</sub> - KAMA<sub>t-1</sub>)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods of the efficiency ratio (integer greater than 0). Default is 10.
+/// * _fast_ - period of the fastest EMA (integer greater than 0). Default is 2.
+/// * _slow_ - period of the slowest EMA (integer greater than _fast_). Default is 30.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::KaufmanAdaptiveMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut kama = KaufmanAdaptiveMovingAverage::new(3, 2, 30).unwrap();
+/// assert_eq!(kama.next(10.0), 10.0);
+/// assert_eq!(kama.next(10.0), 10.0);
+/// assert!(kama.next(12.0) > 10.0);
+/// ```
+///
+/// # Links
+///
+/// * [Kaufman's Adaptive Moving Average, StockCharts](https://chartschool.stockcharts.com/table-of-contents/technical-indicators-and-overlays/technical-overlays/kaufmans-adaptive-moving-average-kama)
+///
+#[doc(alias = "KAMA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KaufmanAdaptiveMovingAverage {
+    period: usize,
+    fast: usize,
+    slow: usize,
+    fast_sc: f64,
+    slow_sc: f64,
+    index: usize,
+    count: usize,
+    current: f64,
+    volatility: f64,
+    prices: Box<[f64]>,
+    changes: Box<[f64]>,
+}
+
+impl KaufmanAdaptiveMovingAverage {
+    pub fn new(period: usize, fast: usize, slow: usize) -> Result<Self> {
+        if period == 0 || fast == 0 || slow <= fast {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            fast,
+            slow,
+            fast_sc: 2.0 / (fast as f64 + 1.0),
+            slow_sc: 2.0 / (slow as f64 + 1.0),
+            index: 0,
+            count: 0,
+            current: 0.0,
+            volatility: 0.0,
+            prices: ring(0.0, period)?,
+            changes: ring(0.0, period)?,
+        })
+    }
+}
+
+impl Period for KaufmanAdaptiveMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for KaufmanAdaptiveMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for KaufmanAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.count == 0 {
+            self.current = input;
+        } else {
+            // the ring of prices holds the last `period` inputs, the oldest at `index` once full
+            let previous = self.prices[(self.index + self.period - 1) % self.period];
+            let oldest = if self.count < self.period {
+                self.prices[0]
+            } else {
+                self.prices[self.index]
+            };
+
+            let change = (input - previous).abs();
+            if self.count >= self.period {
+                self.volatility -= self.changes[self.index];
+            }
+            self.volatility += change;
+            self.changes[self.index] = change;
+
+            let er = if self.volatility > 0.0 {
+                ((input - oldest).abs() / self.volatility).min(1.0)
+            } else {
+                0.0
+            };
+            let sc = (er * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
+            self.current += sc * (input - self.current);
+        }
+
+        self.prices[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        self.current
+    }
+}
+
+impl<T: Close> Next<&T> for KaufmanAdaptiveMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl NextBatch for KaufmanAdaptiveMovingAverage {}
+
+impl Reset for KaufmanAdaptiveMovingAverage {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.current = 0.0;
+        self.volatility = 0.0;
+        for i in 0..self.period {
+            self.prices[i] = 0.0;
+            self.changes[i] = 0.0;
+        }
+    }
+}
+
+impl Default for KaufmanAdaptiveMovingAverage {
+    fn default() -> Self {
+        Self::new(10, 2, 30).unwrap()
+    }
+}
+
+impl fmt::Display for KaufmanAdaptiveMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KAMA({}, {}, {})", self.period, self.fast, self.slow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(KaufmanAdaptiveMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(KaufmanAdaptiveMovingAverage::new(0, 2, 30).is_err());
+        assert!(KaufmanAdaptiveMovingAverage::new(10, 0, 30).is_err());
+        assert!(KaufmanAdaptiveMovingAverage::new(10, 5, 5).is_err());
+        assert!(KaufmanAdaptiveMovingAverage::new(1, 1, 2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kama = KaufmanAdaptiveMovingAverage::new(3, 2, 30).unwrap();
+        assert_eq!(kama.next(10.0), 10.0);
+        assert_eq!(round(kama.next(12.0)), 10.889);
+        assert_eq!(round(kama.next(11.0)), 10.897);
+        assert_eq!(round(kama.next(14.0)), 11.57);
+        assert_eq!(round(kama.next(15.0)), 12.192);
+        assert_eq!(round(kama.next(13.0)), 12.249);
+        assert_eq!(round(kama.next(16.0)), 12.513);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut kama = KaufmanAdaptiveMovingAverage::new(3, 2, 30).unwrap();
+        assert_eq!(kama.next(&Bar::new().close(10)), 10.0);
+        assert_eq!(round(kama.next(&Bar::new().close(12))), 10.889);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kama = KaufmanAdaptiveMovingAverage::new(3, 2, 30).unwrap();
+        kama.next(4.0);
+        kama.next(10.0);
+
+        kama.reset();
+        assert_eq!(kama.next(10.0), 10.0);
+        assert_eq!(round(kama.next(12.0)), 10.889);
+    }
+
+    #[test]
+    fn test_default() {
+        KaufmanAdaptiveMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let kama = KaufmanAdaptiveMovingAverage::new(10, 2, 30).unwrap();
+        assert_eq!(format!("{}", kama), "KAMA(10, 2, 30)");
+    }
+}
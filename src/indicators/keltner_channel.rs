@@ -1,9 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
 use crate::indicators::{AverageTrueRange, ExponentialMovingAverage};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Keltner Channel (KC).
@@ -47,7 +48,8 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Keltner channel, Wikipedia](https://en.wikipedia.org/wiki/Keltner_channel)
 #[doc(alias = "KC")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeltnerChannel {
     period: usize,
     multiplier: f64,
@@ -56,6 +58,7 @@ pub struct KeltnerChannel {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeltnerChannelOutput {
     pub average: f64,
     pub upper: f64,
@@ -83,6 +86,12 @@ impl Period for KeltnerChannel {
     }
 }
 
+impl IsReady for KeltnerChannel {
+    fn bars_remaining(&self) -> usize {
+        self.atr.bars_remaining().max(self.ema.bars_remaining())
+    }
+}
+
 impl Next<f64> for KeltnerChannel {
     type Output = KeltnerChannelOutput;
 
@@ -173,6 +182,19 @@ mod tests {
         assert_eq!(round(d.lower), -3.75);
     }
 
+    #[test]
+    fn test_is_ready() {
+        let mut kc = KeltnerChannel::new(3, 2.0).unwrap();
+        assert_eq!(kc.bars_remaining(), 3);
+        kc.next(2.0);
+        kc.next(&Bar::new().low(1.2).high(1.7).close(1.3));
+        assert_eq!(kc.bars_remaining(), 1);
+        kc.next(5.0);
+        assert!(kc.is_ready());
+        kc.reset();
+        assert_eq!(kc.bars_remaining(), 3);
+    }
+
     #[test]
     fn test_next_with_data_item() {
         let mut kc = KeltnerChannel::new(3, 2.0_f64).unwrap();
@@ -0,0 +1,238 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::rolling::RollingMoments;
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use crate::helpers::Moments;
+#[cfg(feature = "serde")]
+use crate::state::ring_values;
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+/// Rolling excess kurtosis.
+///
+/// Measures how heavy the tails of the distribution of the last _period_ inputs are compared to
+/// the normal distribution. Positive values mean fat tails (extreme moves are more common than
+/// the standard deviation suggests), negative values mean thin tails. Returns 0 until at least
+/// two different inputs have been seen.
+///
+/// The central moments are updated incrementally as values enter and leave the window, so every
+/// update is O(1).
+///
+/// # Formula
+///
+/// _KURT = n m<sub>4</sub> / m<sub>2</sub><sup>2</sup> - 3_
+///
+/// Where:
+///
+/// * _n_ - number of values in the window
+/// * _m<sub>k</sub>_ - sum of _k_-th powers of deviations from the window mean
+///
+/// This is the population (biased) excess kurtosis _g<sub>2</sub>_, which is 0 for normally
+/// distributed values.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Kurtosis;
+/// use ta_panther::Next;
+///
+/// let mut kurt = Kurtosis::new(4).unwrap();
+/// kurt.next(1.0);
+/// kurt.next(2.0);
+/// kurt.next(1.0);
+/// assert_eq!(kurt.next(2.0), -2.0);
+/// ```
+///
+/// # Links
+///
+/// * [Kurtosis, Wikipedia](https://en.wikipedia.org/wiki/Kurtosis)
+///
+#[doc(alias = "KURT")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Kurtosis {
+    moments: RollingMoments,
+}
+
+impl Kurtosis {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            moments: RollingMoments::with_higher_moments(period)?,
+        })
+    }
+}
+
+impl Period for Kurtosis {
+    fn period(&self) -> usize {
+        self.moments.capacity()
+    }
+}
+
+impl Validate for Kurtosis {
+    /// The window is stored as [RollingMoments] since version 2.
+    const STATE_VERSION: u32 = 2;
+
+    fn validate(&self) -> Result<()> {
+        self.moments.validate()
+    }
+
+    #[cfg(feature = "serde")]
+    fn deserialize_version<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        if version >= 2 {
+            return Self::deserialize(deserializer);
+        }
+        let state = StateV1::deserialize(deserializer)?;
+        let values = ring_values(state.period, state.index, state.count, &state.deque)
+            .map_err(D::Error::custom)?;
+        let mut kurt = Self::new(state.period).map_err(D::Error::custom)?;
+        for value in values {
+            kurt.moments.push(value);
+        }
+        Ok(kurt)
+    }
+}
+
+/// State of version 1, a ring buffer of the inputs. The moments are recomputed from the inputs.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StateV1 {
+    period: usize,
+    index: usize,
+    count: usize,
+    #[serde(rename = "moments")]
+    _moments: Moments,
+    deque: Vec<f64>,
+}
+
+impl IsReady for Kurtosis {
+    fn bars_remaining(&self) -> usize {
+        self.period() - self.moments.len()
+    }
+}
+
+impl Next<f64> for Kurtosis {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.moments.push(input);
+        self.moments.kurtosis().unwrap_or(0.0)
+    }
+}
+
+impl<T: Close> Next<&T> for Kurtosis {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for Kurtosis {
+    fn reset(&mut self) {
+        self.moments.clear();
+    }
+}
+
+impl Default for Kurtosis {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for Kurtosis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KURT({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Kurtosis);
+
+    #[test]
+    fn test_new() {
+        assert!(Kurtosis::new(0).is_err());
+        assert!(Kurtosis::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kurt = Kurtosis::new(4).unwrap();
+        assert_eq!(kurt.next(1.0), 0.0);
+        assert_eq!(round(kurt.next(2.0)), -2.0);
+        assert_eq!(round(kurt.next(6.0)), -1.5);
+        assert_eq!(round(kurt.next(3.0)), -1.0);
+        assert_eq!(round(kurt.next(-4.0)), -0.956);
+        assert_eq!(round(kurt.next(3.0)), -0.868);
+    }
+
+    #[test]
+    fn test_constant_input() {
+        let mut kurt = Kurtosis::new(3).unwrap();
+        for &x in &[5.0, 1.0, 7.0, 3.3, 3.3, 3.3, 3.3] {
+            kurt.next(x);
+        }
+        assert_eq!(kurt.next(3.3), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut kurt = Kurtosis::new(3).unwrap();
+        kurt.next(&Bar::new().close(1));
+        assert_eq!(kurt.next(&Bar::new().close(3)), -2.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kurt = Kurtosis::new(3).unwrap();
+        kurt.next(1.0);
+        kurt.next(9.0);
+
+        kurt.reset();
+        assert_eq!(kurt.next(4.0), 0.0);
+        assert_eq!(kurt.next(5.0), -2.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Kurtosis::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let kurt = Kurtosis::new(10).unwrap();
+        assert_eq!(format!("{}", kurt), "KURT(10)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_migrate() {
+        use crate::state::Versioned;
+
+        let json = r#"{"version":1,"state":{"period":4,"index":2,"count":4,"moments":{"count":4,"mean":0.0,"m2":0.0,"m3":0.0,"m4":0.0},"deque":[5.0,1.0,2.0,4.0]}}"#;
+        let Versioned(mut restored) = serde_json::from_str::<Versioned<Kurtosis>>(json).unwrap();
+        let mut kurt = Kurtosis::new(4).unwrap();
+        for input in [2.0, 4.0, 5.0, 1.0] {
+            kurt.next(input);
+        }
+        assert!(restored.is_ready());
+        for input in [3.0, 8.0, 1.0] {
+            assert_eq!(restored.next(input), kurt.next(input));
+        }
+    }
+}
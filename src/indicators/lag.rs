@@ -0,0 +1,156 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::deque;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Lag, also known as REF or shift.
+///
+/// Returns the input of _n_ periods ago, like `REF(X, n)` of TDX and MetaStock formulas. Until
+/// _n_ periods have passed, the first input is returned, like the
+/// [Momentum](crate::indicators::Momentum) does.
+///
+/// To lag the output of another indicator, see [Displace](crate::indicators::Displace).
+///
+/// # Formula
+///
+/// LAG<sub>t</sub> = p<sub>t-n</sub>
+///
+/// # Parameters
+///
+/// * _period_ - number of periods _n_ (integer greater than 0). Default is 1.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Lag;
+/// use ta_panther::Next;
+///
+/// let mut lag = Lag::new(2).unwrap();
+/// assert_eq!(lag.next(10.0), 10.0);
+/// assert_eq!(lag.next(11.0), 10.0);
+/// assert_eq!(lag.next(12.0), 10.0);
+/// assert_eq!(lag.next(13.0), 11.0);
+/// ```
+///
+#[doc(alias = "REF")]
+#[doc(alias = "SHIFT")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Lag {
+    period: usize,
+    count: usize,
+    window: VecDeque<f64>,
+}
+
+impl Lag {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                count: 0,
+                window: deque(period.saturating_add(1))?,
+            }),
+        }
+    }
+}
+
+impl Period for Lag {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for Lag {
+    fn bars_remaining(&self) -> usize {
+        (self.period + 1).saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for Lag {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        self.window.front().copied().unwrap_or(input)
+    }
+}
+
+impl<T: Close> Next<&T> for Lag {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for Lag {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.window.clear();
+    }
+}
+
+impl Default for Lag {
+    fn default() -> Self {
+        Self::new(1).unwrap()
+    }
+}
+
+impl fmt::Display for Lag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LAG({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Lag);
+
+    #[test]
+    fn test_new() {
+        assert!(Lag::new(0).is_err());
+        assert!(Lag::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut lag = Lag::new(1).unwrap();
+        assert_eq!(lag.next(3.0), 3.0);
+        assert!(!lag.is_ready());
+        assert_eq!(lag.next(5.0), 3.0);
+        assert!(lag.is_ready());
+        assert_eq!(lag.next(&Bar::new().close(8)), 5.0);
+        assert_eq!(lag.next(1.0), 8.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut lag = Lag::new(2).unwrap();
+        lag.next(3.0);
+        lag.next(5.0);
+        lag.next(7.0);
+
+        lag.reset();
+        assert_eq!(lag.next(1.0), 1.0);
+        assert_eq!(lag.bars_remaining(), 2);
+    }
+
+    #[test]
+    fn test_display() {
+        let lag = Lag::new(5).unwrap();
+        assert_eq!(format!("{}", lag), "LAG(5)");
+    }
+}
@@ -1,5 +1,11 @@
-use std::collections::VecDeque;
-use std::fmt;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::linear_regression;
+use crate::rolling::RollingWindow;
+use crate::{IsReady, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Linear Regression Prediction (FORECAST).
 ///
@@ -12,18 +18,21 @@ use std::fmt;
 /// The linear regression line is defined as \( y = mx + b \), where:
 /// - \( m \) (slope) = \( \frac{\text{cov}(x, y)}{\text{var}(x)} \)
 /// - \( b \) (intercept) = \( \bar{y} - m \cdot \bar{x} \)
-/// - Predicted value = \( m \cdot (n + 1) + b \)
+/// - Predicted value = \( m \cdot (n + h) + b \)
 ///
 /// Where:
 /// - \( \text{cov}(x, y) \) = covariance between the time steps (\( x \)) and values (\( y \))
 /// - \( \text{var}(x) \) = variance of the time steps (\( x \))
 /// - \( \bar{x} \), \( \bar{y} \) = mean of \( x \) and \( y \)
 /// - \( n \) = number of data points in the current window
+/// - \( h \) = forecast horizon, number of bars to project ahead
 /// - \( x = [1, 2, ..., \text{period}] \) = time steps
 ///
 /// # Parameters
 ///
 /// * `period` - Number of periods (integer greater than 0)
+/// * `horizon` - Number of bars to project ahead (integer greater than 0). Default is 1,
+///   use [with_horizon](LinearRegressionPrediction::with_horizon) to change it.
 ///
 /// # Example
 ///
@@ -33,69 +42,62 @@ use std::fmt;
 ///
 /// let mut lrp = LinearRegressionPrediction::new(3).unwrap();
 /// assert_eq!(lrp.next(1.0), 1.0); // First value
-/// assert_eq!(lrp.next(2.0), 2.0); // Predict next based on [1.0, 2.0]
+/// assert_eq!(lrp.next(2.0), 3.0); // Predict next based on [1.0, 2.0]
 /// assert_eq!(lrp.next(3.0), 4.0); // Predict next based on [1.0, 2.0, 3.0]
 /// assert_eq!(lrp.next(4.0), 5.0); // Predict next based on [2.0, 3.0, 4.0]
 /// assert_eq!(lrp.next(5.0), 6.0); // Predict next based on [3.0, 4.0, 5.0]
+///
+/// // Project 3 bars ahead
+/// let mut lrp = LinearRegressionPrediction::with_horizon(3, 3).unwrap();
+/// lrp.next(1.0);
+/// lrp.next(2.0);
+/// assert_eq!(lrp.next(3.0), 6.0);
 /// ```
 ///
 /// # Links
 ///
-
-use serde::{Deserialize, Serialize};
-use crate::errors::{Result, TaError};
-use crate::{Next, Period, Reset};
-
 #[doc(alias = "FORECAST")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LinearRegressionPrediction {
     period: usize,
-    deque: VecDeque<f64>,
-    x: Vec<f64>, // 缓存自变量 x
-    mean_x: f64, // 缓存 x 的均值
+    window: RollingWindow<f64>,
+    #[cfg_attr(feature = "serde", serde(default = "default_horizon"))]
+    horizon: usize,
+}
+
+fn default_horizon() -> usize {
+    1
 }
 
 impl LinearRegressionPrediction {
     pub fn new(period: usize) -> Result<Self> {
-        if period == 0 {
+        Self::with_horizon(period, default_horizon())
+    }
+
+    pub fn with_horizon(period: usize, horizon: usize) -> Result<Self> {
+        if period == 0 || horizon == 0 {
             return Err(TaError::InvalidParameter);
         }
-        let x: Vec<f64> = (1..=period).map(|x| x as f64).collect();
-        let mean_x = (period as f64 + 1.0) / 2.0;
         Ok(Self {
             period,
-            deque: VecDeque::with_capacity(period),
-            x,
-            mean_x,
+            window: RollingWindow::new(period)?,
+            horizon,
         })
     }
+
+    pub fn horizon(&self) -> usize {
+        self.horizon
+    }
 }
 
 impl Next<f64> for LinearRegressionPrediction {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        let value = input;
-        if self.deque.len() == self.period {
-            self.deque.pop_front();
-        }
-        self.deque.push_back(value);
-        let slice = self.deque.iter().copied().collect::<Vec<_>>();
-        if slice.is_empty() {
-            return 0.0;
-        }
-        let n = slice.len() as f64;
-        let mean_y = slice.iter().sum::<f64>() / n;
-        let mut cov_xy = 0.0;
-        let mut var_x = 0.0;
-        for (xi, &yi) in self.x.iter().zip(slice.iter()) {
-            cov_xy += (xi - self.mean_x) * (yi - mean_y);
-            var_x += (xi - self.mean_x).powi(2);
-        }
-        let slope = if var_x != 0.0 { cov_xy / var_x } else { 0.0 };
-        let intercept = mean_y - slope * self.mean_x;
-        let result = slope * (n + 1.0) + intercept;
-        result
+        self.window.push(input);
+        let (slope, intercept) = linear_regression(self.window.iter().copied());
+        slope * (self.window.len() as f64 + self.horizon as f64) + intercept
     }
 }
 
@@ -105,9 +107,15 @@ impl Period for LinearRegressionPrediction {
     }
 }
 
+impl IsReady for LinearRegressionPrediction {
+    fn bars_remaining(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
 impl Reset for LinearRegressionPrediction {
     fn reset(&mut self) {
-        self.deque.clear();
+        self.window.clear();
     }
 }
 
@@ -119,6 +127,53 @@ impl Default for LinearRegressionPrediction {
 
 impl fmt::Display for LinearRegressionPrediction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "FORECAST:{}", self.period)
+        if self.horizon == 1 {
+            write!(f, "FORECAST:{}", self.period)
+        } else {
+            write!(f, "FORECAST:{}:{}", self.period, self.horizon)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_new() {
+        assert!(LinearRegressionPrediction::new(0).is_err());
+        assert!(LinearRegressionPrediction::new(1).is_ok());
+        assert!(LinearRegressionPrediction::with_horizon(3, 0).is_err());
+        assert!(LinearRegressionPrediction::with_horizon(3, 5).is_ok());
+    }
+
+    #[test]
+    fn test_next_with_horizon() {
+        let mut lrp = LinearRegressionPrediction::with_horizon(4, 2).unwrap();
+        lrp.next(2.0);
+        lrp.next(4.0);
+        lrp.next(6.0);
+        assert_eq!(lrp.next(8.0), 12.0);
+        assert_eq!(lrp.next(10.0), 14.0);
+        assert_eq!(lrp.horizon(), 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut lrp = LinearRegressionPrediction::with_horizon(3, 2).unwrap();
+        lrp.next(1.0);
+        lrp.next(5.0);
+
+        lrp.reset();
+        assert_eq!(lrp.next(3.0), 3.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let lrp = LinearRegressionPrediction::new(9).unwrap();
+        assert_eq!(format!("{}", lrp), "FORECAST:9");
+        let lrp = LinearRegressionPrediction::with_horizon(9, 3).unwrap();
+        assert_eq!(format!("{}", lrp), "FORECAST:9:3");
     }
 }
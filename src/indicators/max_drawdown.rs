@@ -0,0 +1,318 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::ring;
+use crate::{Close, IsReady, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum drawdown (MDD) with the current drawdown and its duration.
+///
+/// Tracks how far the input, usually the value of an equity curve, is below its peak. Drawdowns
+/// are expressed as positive fractions of the peak, so 0.2 means 20% below the peak.
+///
+/// * [MaxDrawdown::new] tracks the all-time peak and maximum drawdown in O(1) per update.
+/// * [MaxDrawdown::rolling] only considers the last _period_ inputs, so old drawdowns are
+///   forgotten. Every update scans the window, so it is O(period).
+///
+/// The inputs should be positive.
+///
+/// # Formula
+///
+/// _DD<sub>t</sub> = 1 - x<sub>t</sub> / max(x<sub>0</sub> ... x<sub>t</sub>)_
+///
+/// _MDD = max(DD<sub>0</sub> ... DD<sub>t</sub>)_
+///
+/// # Output
+///
+/// * _drawdown_ - the current drawdown
+/// * _max_drawdown_ - the largest drawdown seen
+/// * _duration_ - number of inputs since the peak, 0 at a new peak
+///
+/// # Parameters
+///
+/// * _period_ - number of periods of [MaxDrawdown::rolling] (integer greater than 0). By
+///   default the drawdown is not limited to a window.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::MaxDrawdown;
+/// use ta_panther::Next;
+///
+/// let mut mdd = MaxDrawdown::new();
+/// mdd.next(100.0);
+/// mdd.next(50.0);
+/// let out = mdd.next(75.0);
+/// assert_eq!(out.drawdown, 0.25);
+/// assert_eq!(out.max_drawdown, 0.5);
+/// assert_eq!(out.duration, 2);
+/// ```
+///
+/// # Links
+///
+/// * [Drawdown (economics), Wikipedia](https://en.wikipedia.org/wiki/Drawdown_(economics))
+///
+#[doc(alias = "MDD")]
+#[doc(alias = "DRAWDOWN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaxDrawdown {
+    period: Option<usize>,
+    index: usize,
+    count: usize,
+    peak: f64,
+    duration: usize,
+    max_drawdown: f64,
+    deque: Box<[f64]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaxDrawdownOutput {
+    pub drawdown: f64,
+    pub max_drawdown: f64,
+    pub duration: usize,
+}
+
+impl MaxDrawdown {
+    /// Tracks the drawdown from the all-time peak.
+    pub fn new() -> Self {
+        Self {
+            period: None,
+            index: 0,
+            count: 0,
+            peak: f64::NEG_INFINITY,
+            duration: 0,
+            max_drawdown: 0.0,
+            deque: Box::new([]),
+        }
+    }
+
+    /// Tracks the drawdown within the last _period_ inputs.
+    pub fn rolling(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period: Some(period),
+                deque: ring(0.0, period)?,
+                ..Self::new()
+            }),
+        }
+    }
+
+    /// Returns the window size of a rolling drawdown.
+    pub fn period(&self) -> Option<usize> {
+        self.period
+    }
+
+    fn next_running(&mut self, input: f64) -> MaxDrawdownOutput {
+        if input >= self.peak {
+            self.peak = input;
+            self.duration = 0;
+        } else {
+            self.duration += 1;
+        }
+
+        let drawdown = 1.0 - input / self.peak;
+        self.max_drawdown = self.max_drawdown.max(drawdown);
+
+        MaxDrawdownOutput {
+            drawdown,
+            max_drawdown: self.max_drawdown,
+            duration: self.duration,
+        }
+    }
+
+    fn next_rolling(&mut self, period: usize, input: f64) -> MaxDrawdownOutput {
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < period {
+            self.count += 1;
+        }
+
+        // walk the window from the oldest input, tracking the highest peak seen so far
+        let oldest = (self.index + period - self.count) % period;
+        let mut peak = f64::NEG_INFINITY;
+        let mut duration = 0;
+        let mut max_drawdown: f64 = 0.0;
+        for i in 0..self.count {
+            let value = self.deque[(oldest + i) % period];
+            if value >= peak {
+                peak = value;
+                duration = 0;
+            } else {
+                duration += 1;
+            }
+            max_drawdown = max_drawdown.max(1.0 - value / peak);
+        }
+
+        MaxDrawdownOutput {
+            drawdown: 1.0 - input / peak,
+            max_drawdown,
+            duration,
+        }
+    }
+}
+
+impl IsReady for MaxDrawdown {
+    fn bars_remaining(&self) -> usize {
+        self.period
+            .map_or(0, |period| period.saturating_sub(self.count))
+    }
+}
+
+impl Next<f64> for MaxDrawdown {
+    type Output = MaxDrawdownOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self.period {
+            Some(period) => self.next_rolling(period, input),
+            None => self.next_running(input),
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for MaxDrawdown {
+    type Output = MaxDrawdownOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for MaxDrawdown {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.peak = f64::NEG_INFINITY;
+        self.duration = 0;
+        self.max_drawdown = 0.0;
+        for i in 0..self.deque.len() {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for MaxDrawdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for MaxDrawdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.period {
+            Some(period) => write!(f, "MDD({})", period),
+            None => write!(f, "MDD"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(MaxDrawdown);
+
+    fn outputs(mdd: &mut MaxDrawdown, inputs: &[f64]) -> Vec<(f64, f64, usize)> {
+        inputs
+            .iter()
+            .map(|&input| {
+                let out = mdd.next(input);
+                (round(out.drawdown), round(out.max_drawdown), out.duration)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(MaxDrawdown::rolling(0).is_err());
+        assert!(MaxDrawdown::rolling(1).is_ok());
+        assert_eq!(MaxDrawdown::new().period(), None);
+        assert_eq!(MaxDrawdown::rolling(5).unwrap().period(), Some(5));
+    }
+
+    #[test]
+    fn test_next() {
+        let inputs = [100.0, 110.0, 99.0, 105.0, 88.0, 120.0, 114.0];
+
+        let mut mdd = MaxDrawdown::new();
+        assert_eq!(
+            outputs(&mut mdd, &inputs),
+            vec![
+                (0.0, 0.0, 0),
+                (0.0, 0.0, 0),
+                (0.1, 0.1, 1),
+                (0.045, 0.1, 2),
+                (0.2, 0.2, 3),
+                (0.0, 0.2, 0),
+                (0.05, 0.2, 1),
+            ]
+        );
+
+        let mut mdd = MaxDrawdown::rolling(3).unwrap();
+        assert_eq!(
+            outputs(&mut mdd, &inputs),
+            vec![
+                (0.0, 0.0, 0),
+                (0.0, 0.0, 0),
+                (0.1, 0.1, 1),
+                (0.045, 0.1, 2),
+                (0.162, 0.162, 1),
+                (0.0, 0.162, 0),
+                (0.05, 0.05, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut mdd = MaxDrawdown::rolling(2).unwrap();
+        mdd.next(&Bar::new().close(10));
+        let out = mdd.next(&Bar::new().close(5));
+        assert_eq!(out.drawdown, 0.5);
+        assert_eq!(out.duration, 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mdd = MaxDrawdown::rolling(3).unwrap();
+        mdd.next(10.0);
+        mdd.next(5.0);
+
+        mdd.reset();
+        let out = mdd.next(4.0);
+        assert_eq!(out.max_drawdown, 0.0);
+        assert_eq!(out.duration, 0);
+    }
+
+    #[test]
+    fn test_default() {
+        MaxDrawdown::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MaxDrawdown::new()), "MDD");
+        assert_eq!(format!("{}", MaxDrawdown::rolling(10).unwrap()), "MDD(10)");
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(MaxDrawdown::new().is_ready());
+
+        let mut mdd = MaxDrawdown::rolling(2).unwrap();
+        mdd.next(10.0);
+        assert_eq!(mdd.bars_remaining(), 1);
+        mdd.next(8.0);
+        assert!(mdd.is_ready());
+    }
+}
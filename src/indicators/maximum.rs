@@ -1,8 +1,11 @@
-use std::fmt;
+use alloc::boxed::Box;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{High, Next, Period, Reset};
+use crate::helpers::ring;
+use crate::{High, IsReady, Next, Period, Reset, Validate};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Returns the highest value in a given time frame.
@@ -24,11 +27,14 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(max.next(4.0), 5.0);
 /// assert_eq!(max.next(8.0), 8.0);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Maximum {
     period: usize,
     max_index: usize,
     cur_index: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
     deque: Box<[f64]>,
 }
 
@@ -40,7 +46,8 @@ impl Maximum {
                 period,
                 max_index: 0,
                 cur_index: 0,
-                deque: vec![f64::NEG_INFINITY; period].into_boxed_slice(),
+                count: 0,
+                deque: ring(f64::NEG_INFINITY, period)?,
             }),
         }
     }
@@ -66,6 +73,41 @@ impl Period for Maximum {
     }
 }
 
+impl Validate for Maximum {
+    // Version 1 did not store the number of inputs in the window.
+    const STATE_VERSION: u32 = 2;
+
+    fn validate(&self) -> Result<()> {
+        if self.period > 0
+            && self.deque.len() == self.period
+            && self.max_index < self.period
+            && self.cur_index < self.period
+            && self.count <= self.period
+        {
+            Ok(())
+        } else {
+            Err(TaError::InvalidState)
+        }
+    }
+
+    fn migrate(&mut self, version: u32) -> Result<()> {
+        if version < 2 {
+            self.count = self
+                .deque
+                .iter()
+                .filter(|&&value| value != f64::NEG_INFINITY)
+                .count();
+        }
+        Ok(())
+    }
+}
+
+impl IsReady for Maximum {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
 impl Next<f64> for Maximum {
     type Output = f64;
 
@@ -83,6 +125,9 @@ impl Next<f64> for Maximum {
         } else {
             0
         };
+        if self.count < self.period {
+            self.count += 1;
+        }
 
         self.deque[self.max_index]
     }
@@ -98,6 +143,7 @@ impl<T: High> Next<&T> for Maximum {
 
 impl Reset for Maximum {
     fn reset(&mut self) {
+        self.count = 0;
         for i in 0..self.period {
             self.deque[i] = f64::NEG_INFINITY;
         }
@@ -179,4 +225,16 @@ mod tests {
         let indicator = Maximum::new(7).unwrap();
         assert_eq!(format!("{}", indicator), "MAX(7)");
     }
+
+    #[test]
+    fn test_is_ready() {
+        let mut max = Maximum::new(2).unwrap();
+        max.next(4.0);
+        assert_eq!(max.bars_remaining(), 1);
+        max.next(2.0);
+        assert!(max.is_ready());
+
+        max.reset();
+        assert!(!max.is_ready());
+    }
 }
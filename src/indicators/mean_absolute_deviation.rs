@@ -1,10 +1,12 @@
-use std::fmt;
-
+use alloc::boxed::Box;
+use core::fmt;
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
 
 /// Mean Absolute Deviation (MAD)
 ///
@@ -27,7 +29,8 @@ use crate::{Close, Next, Period, Reset};
 ///
 /// * [Mean Absolute Deviation, Wikipedia](https://en.wikipedia.org/wiki/Mean_absolute_deviation)
 ///
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MeanAbsoluteDeviation {
     period: usize,
     index: usize,
@@ -45,7 +48,7 @@ impl MeanAbsoluteDeviation {
                 index: 0,
                 count: 0,
                 sum: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                deque: ring(0.0, period)?,
             }),
         }
     }
@@ -57,12 +60,30 @@ impl Period for MeanAbsoluteDeviation {
     }
 }
 
+impl Validate for MeanAbsoluteDeviation {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for MeanAbsoluteDeviation {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
 impl Next<f64> for MeanAbsoluteDeviation {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
         self.sum = if self.count < self.period {
-            self.count = self.count + 1;
+            self.count += 1;
             self.sum + input
         } else {
             self.sum + input - self.deque[self.index]
@@ -0,0 +1,99 @@
+use core::fmt;
+
+use crate::{Close, High, Low, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Median price (MEDPRICE).
+///
+/// Returns the midpoint of the high and low prices of a bar. Stateless, it transforms bars into the single price consumed by
+/// the indicators of close prices, in front of which it is chained.
+///
+/// # Formula
+///
+/// MP = (High + Low) / 2
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{MedianPrice, SimpleMovingAverage};
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64, close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut mp = MedianPrice::new();
+/// assert_eq!(mp.next(&bar(13.0, 8.0, 12.0)), 10.5);
+///
+/// // chained in front of an SMA
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// sma.next(mp.next(&bar(13.0, 8.0, 12.0)));
+/// assert_eq!(sma.next(mp.next(&bar(15.0, 9.0, 15.0))), 11.25);
+/// ```
+///
+/// # Links
+///
+#[doc(alias = "MEDPRICE")]
+#[doc(alias = "HL2")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MedianPrice {}
+
+impl MedianPrice {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for MedianPrice {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        (input.high() + input.low()) / 2.0
+    }
+}
+
+impl Reset for MedianPrice {
+    fn reset(&mut self) {}
+}
+
+impl fmt::Display for MedianPrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("MEDPRICE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut mp = MedianPrice::new();
+        assert_eq!(mp.next(&Bar::new().high(13).low(8).close(12)), 10.5);
+        assert_eq!(mp.next(&Bar::new().high(4).low(4).close(4)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mp = MedianPrice::default();
+        mp.next(&Bar::new().high(13).low(8).close(12));
+        mp.reset();
+        assert_eq!(mp.next(&Bar::new().high(13).low(8).close(12)), 10.5);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MedianPrice::new()), "MEDPRICE");
+    }
+}
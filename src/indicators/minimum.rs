@@ -1,8 +1,11 @@
-use std::fmt;
+use alloc::boxed::Box;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Low, Next, Period, Reset};
+use crate::helpers::ring;
+use crate::{IsReady, Low, Next, Period, Reset, Validate};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Returns the lowest value in a given time frame.
@@ -23,11 +26,14 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(min.next(12.0), 10.0);
 /// assert_eq!(min.next(13.0), 11.0);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Minimum {
     period: usize,
     min_index: usize,
     cur_index: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
     deque: Box<[f64]>,
 }
 
@@ -39,7 +45,8 @@ impl Minimum {
                 period,
                 min_index: 0,
                 cur_index: 0,
-                deque: vec![f64::INFINITY; period].into_boxed_slice(),
+                count: 0,
+                deque: ring(f64::INFINITY, period)?,
             }),
         }
     }
@@ -65,6 +72,41 @@ impl Period for Minimum {
     }
 }
 
+impl Validate for Minimum {
+    // Version 1 did not store the number of inputs in the window.
+    const STATE_VERSION: u32 = 2;
+
+    fn validate(&self) -> Result<()> {
+        if self.period > 0
+            && self.deque.len() == self.period
+            && self.min_index < self.period
+            && self.cur_index < self.period
+            && self.count <= self.period
+        {
+            Ok(())
+        } else {
+            Err(TaError::InvalidState)
+        }
+    }
+
+    fn migrate(&mut self, version: u32) -> Result<()> {
+        if version < 2 {
+            self.count = self
+                .deque
+                .iter()
+                .filter(|&&value| value != f64::INFINITY)
+                .count();
+        }
+        Ok(())
+    }
+}
+
+impl IsReady for Minimum {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
 impl Next<f64> for Minimum {
     type Output = f64;
 
@@ -82,6 +124,9 @@ impl Next<f64> for Minimum {
         } else {
             0
         };
+        if self.count < self.period {
+            self.count += 1;
+        }
 
         self.deque[self.min_index]
     }
@@ -97,6 +142,7 @@ impl<T: Low> Next<&T> for Minimum {
 
 impl Reset for Minimum {
     fn reset(&mut self) {
+        self.count = 0;
         for i in 0..self.period {
             self.deque[i] = f64::INFINITY;
         }
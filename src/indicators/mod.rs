@@ -1,9 +1,39 @@
 mod exponential_moving_average;
 pub use self::exponential_moving_average::ExponentialMovingAverage;
 
+mod kalman_filter;
+pub use self::kalman_filter::{KalmanFilter, KalmanFilterOutput};
+
 mod weighted_moving_average;
 pub use self::weighted_moving_average::WeightedMovingAverage;
 
+mod triangular_moving_average;
+pub use self::triangular_moving_average::TriangularMovingAverage;
+
+mod sine_weighted_moving_average;
+pub use self::sine_weighted_moving_average::SineWeightedMovingAverage;
+
+mod hull_moving_average;
+pub use self::hull_moving_average::HullMovingAverage;
+
+mod kaufman_adaptive_moving_average;
+pub use self::kaufman_adaptive_moving_average::KaufmanAdaptiveMovingAverage;
+
+mod zero_lag_exponential_moving_average;
+pub use self::zero_lag_exponential_moving_average::ZeroLagExponentialMovingAverage;
+
+mod wilders_moving_average;
+pub use self::wilders_moving_average::WildersMovingAverage;
+
+mod moving_average;
+pub use self::moving_average::{MaType, MovingAverage};
+
+mod price_source;
+pub use self::price_source::PriceSource;
+
+mod smoothing;
+pub use self::smoothing::Smoothing;
+
 mod simple_moving_average;
 pub use self::simple_moving_average::SimpleMovingAverage;
 
@@ -28,12 +58,35 @@ pub use self::fast_stochastic::FastStochastic;
 mod slow_stochastic;
 pub use self::slow_stochastic::SlowStochastic;
 
+mod stochastic_oscillator;
+pub use self::stochastic_oscillator::{StochasticOscillator, StochasticOscillatorOutput};
+
 mod true_range;
 pub use self::true_range::TrueRange;
 
+mod typical_price;
+pub use self::typical_price::TypicalPrice;
+
+mod median_price;
+pub use self::median_price::MedianPrice;
+
+mod weighted_close;
+pub use self::weighted_close::WeightedClose;
+
 mod average_true_range;
 pub use self::average_true_range::AverageTrueRange;
 
+mod average_directional_index;
+pub use self::average_directional_index::{AverageDirectionalIndex, AverageDirectionalIndexOutput};
+
+mod adxr;
+pub use self::adxr::Adxr;
+
+mod regime_classifier;
+pub use self::regime_classifier::{
+    Regime, RegimeClassifier, RegimeClassifierOutput, RegimeThresholds,
+};
+
 mod moving_average_convergence_divergence;
 pub use self::moving_average_convergence_divergence::{
     MovingAverageConvergenceDivergence, MovingAverageConvergenceDivergenceOutput,
@@ -51,7 +104,13 @@ mod efficiency_ratio;
 pub use self::efficiency_ratio::EfficiencyRatio;
 
 mod bollinger_bands;
-pub use self::bollinger_bands::{BollingerBands, BollingerBandsOutput};
+pub use self::bollinger_bands::{BollingerBands, BollingerBandsOutput, DeviationType};
+
+mod bollinger_percent_b;
+pub use self::bollinger_percent_b::BollingerPercentB;
+
+mod bollinger_bandwidth;
+pub use self::bollinger_bandwidth::BollingerBandwidth;
 
 mod chandelier_exit;
 pub use self::chandelier_exit::{ChandelierExit, ChandelierExitOutput};
@@ -62,16 +121,63 @@ pub use self::keltner_channel::{KeltnerChannel, KeltnerChannelOutput};
 mod rate_of_change;
 pub use self::rate_of_change::RateOfChange;
 
+mod rate_of_change_ratio;
+pub use self::rate_of_change_ratio::RateOfChangeRatio;
+
+mod momentum;
+pub use self::momentum::Momentum;
+
+mod lag;
+pub use self::lag::Lag;
+
+mod displace;
+pub use self::displace::Displace;
+
 mod money_flow_index;
 pub use self::money_flow_index::MoneyFlowIndex;
 
 mod on_balance_volume;
 pub use self::on_balance_volume::OnBalanceVolume;
 
+mod chaikin_money_flow;
+pub use self::chaikin_money_flow::ChaikinMoneyFlow;
+
+mod twiggs_money_flow;
+pub use self::twiggs_money_flow::TwiggsMoneyFlow;
+
+mod anchored_vwap;
+pub use self::anchored_vwap::AnchoredVwap;
+
 pub mod cross_above;
 pub use self::cross_above::CrossAbove;
 pub mod cross_below;
 pub use self::cross_below::CrossBelow;
+mod cross_with_hysteresis;
+pub use self::cross_with_hysteresis::CrossWithHysteresis;
+
+mod binary_op;
+pub use self::binary_op::{BinaryOp, Operator};
+
+mod compare;
+pub use self::compare::{Compare, Comparison};
+
+mod if_then_else;
+pub use self::if_then_else::IfThenElse;
+
+mod bars_since;
+pub use self::bars_since::BarsSince;
+
+mod count_true;
+pub use self::count_true::CountTrue;
+
+mod sum_since;
+pub use self::sum_since::SumSince;
+
+mod debounce;
+pub use self::debounce::Debounce;
+
+mod hold;
+pub use self::hold::Hold;
 
 pub mod highest_high_value;
 pub use self::highest_high_value::HighestHighValue;
@@ -79,5 +185,107 @@ pub use self::highest_high_value::HighestHighValue;
 pub mod linear_regression_prediction;
 pub use self::linear_regression_prediction::LinearRegressionPrediction;
 
+mod standard_error;
+pub use self::standard_error::StandardError;
+
+mod forecast_oscillator;
+pub use self::forecast_oscillator::ForecastOscillator;
+
+mod regression_channel;
+pub use self::regression_channel::{RegressionChannel, RegressionChannelOutput};
+
+mod polynomial_regression_forecast;
+pub use self::polynomial_regression_forecast::PolynomialRegressionForecast;
+
+mod exponential_regression_forecast;
+pub use self::exponential_regression_forecast::ExponentialRegressionForecast;
+
+mod rolling_percentile;
+pub use self::rolling_percentile::RollingPercentile;
+
+mod rolling_median;
+pub use self::rolling_median::RollingMedian;
+
+mod skewness;
+pub use self::skewness::Skewness;
+
+mod kurtosis;
+pub use self::kurtosis::Kurtosis;
+
+mod z_score;
+pub use self::z_score::ZScore;
+
+mod rolling_correlation;
+pub use self::rolling_correlation::RollingCorrelation;
+
+mod beta;
+pub use self::beta::Beta;
+
+mod conditional_mean;
+pub use self::conditional_mean::{ConditionalMean, ConditionalMeanOutput};
+
+mod split_by;
+pub use self::split_by::{SplitBy, SplitByOutput};
+
+mod checked;
+pub use self::checked::Checked;
+
+mod nan_guard;
+pub use self::nan_guard::{NanGuard, NanPolicy};
+
+mod with_source;
+pub use self::with_source::WithSource;
+
+mod shannon_entropy;
+pub use self::shannon_entropy::ShannonEntropy;
+
+mod approximate_entropy;
+pub use self::approximate_entropy::ApproximateEntropy;
+
+mod weighted_mean;
+pub use self::weighted_mean::WeightedMean;
+
+mod weighted_std;
+pub use self::weighted_std::WeightedStd;
+
+mod autocorrelation;
+pub use self::autocorrelation::Autocorrelation;
+
+mod geometric_mean;
+pub use self::geometric_mean::GeometricMean;
+
+mod harmonic_mean;
+pub use self::harmonic_mean::HarmonicMean;
+
+mod rolling_max_drawup;
+pub use self::rolling_max_drawup::RollingMaxDrawup;
+
+mod rolling_log_sum_exp;
+pub use self::rolling_log_sum_exp::RollingLogSumExp;
+
+mod ulcer_index;
+pub use self::ulcer_index::UlcerIndex;
+
+mod ulcer_performance_index;
+pub use self::ulcer_performance_index::UlcerPerformanceIndex;
+
+mod max_drawdown;
+pub use self::max_drawdown::{MaxDrawdown, MaxDrawdownOutput};
+
+mod value_at_risk;
+pub use self::value_at_risk::ValueAtRisk;
+
+mod expected_shortfall;
+pub use self::expected_shortfall::ExpectedShortfall;
+
 pub mod lowest_low_value;
-pub use self::lowest_low_value::LowestLowValue;
\ No newline at end of file
+pub use self::lowest_low_value::LowestLowValue;
+
+mod tick_rule;
+pub use self::tick_rule::TickRule;
+
+mod order_flow_imbalance;
+pub use self::order_flow_imbalance::OrderFlowImbalance;
+
+mod vpin;
+pub use self::vpin::Vpin;
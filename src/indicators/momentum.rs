@@ -0,0 +1,213 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Momentum (MOM)
+///
+/// The absolute change of the price over _n_ periods. It is the absolute counterpart of the
+/// [Rate of Change](crate::indicators::RateOfChange): positive while the price rises, negative
+/// while it falls.
+///
+/// Until _n_ periods have passed, the change since the first input is returned, like the ROC
+/// does, so the first output is 0.
+///
+/// # Formula
+///
+/// MOM = Price<sub>t</sub> - Price<sub>t-n</sub>
+///
+/// Where:
+///
+/// * MOM - current value of Momentum indicator
+/// * P<sub>t</sub> - price at the moment
+/// * P<sub>t-n</sub> - price _n_ periods ago
+///
+/// # Parameters
+///
+/// * _period_ - number of periods integer greater than 0. Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Momentum;
+/// use ta_panther::Next;
+///
+/// let mut mom = Momentum::new(2).unwrap();
+/// assert_eq!(mom.next(10.0), 0.0);   //  0
+/// assert_eq!(mom.next(9.5), -0.5);   //  9.5 - 10 = -0.5
+/// assert_eq!(mom.next(20.0), 10.0);  //  20 - 10 = 10
+/// assert_eq!(mom.next(20.0), 10.5);  //  20 - 9.5 = 10.5
+/// ```
+///
+/// # Links
+///
+/// * [Momentum, Wikipedia](https://en.wikipedia.org/wiki/Momentum_(technical_analysis))
+///
+#[doc(alias = "MOM")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Momentum {
+    period: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+}
+
+impl Momentum {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for Momentum {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for Momentum {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period + 1,
+        )
+    }
+}
+
+impl IsReady for Momentum {
+    fn bars_remaining(&self) -> usize {
+        (self.period + 1).saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for Momentum {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> f64 {
+        let previous = if self.count > self.period {
+            self.deque[self.index]
+        } else {
+            self.count += 1;
+            if self.count == 1 {
+                input
+            } else {
+                self.deque[0]
+            }
+        };
+        self.deque[self.index] = input;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        input - previous
+    }
+}
+
+impl<T: Close> Next<&T> for Momentum {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> f64 {
+        self.next(input.close())
+    }
+}
+
+impl Default for Momentum {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for Momentum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MOM({})", self.period)
+    }
+}
+
+impl Reset for Momentum {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Momentum);
+
+    #[test]
+    fn test_new() {
+        assert!(Momentum::new(0).is_err());
+        assert!(Momentum::new(1).is_ok());
+        assert!(Momentum::new(100_000).is_ok());
+    }
+
+    #[test]
+    fn test_next_f64() {
+        let mut mom = Momentum::new(3).unwrap();
+
+        assert_eq!(round(mom.next(10.0)), 0.0);
+        assert_eq!(round(mom.next(10.4)), 0.4);
+        assert_eq!(round(mom.next(10.57)), 0.57);
+        assert_eq!(round(mom.next(10.8)), 0.8);
+        assert_eq!(round(mom.next(10.9)), 0.5);
+        assert_eq!(round(mom.next(10.0)), -0.57);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        fn bar(close: f64) -> Bar {
+            Bar::new().close(close)
+        }
+
+        let mut mom = Momentum::new(3).unwrap();
+
+        assert_eq!(round(mom.next(&bar(10.0))), 0.0);
+        assert_eq!(round(mom.next(&bar(10.4))), 0.4);
+        assert_eq!(round(mom.next(&bar(10.57))), 0.57);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mom = Momentum::new(3).unwrap();
+
+        mom.next(12.3);
+        mom.next(15.0);
+
+        mom.reset();
+
+        assert_eq!(round(mom.next(10.0)), 0.0);
+        assert_eq!(round(mom.next(10.4)), 0.4);
+        assert_eq!(round(mom.next(10.57)), 0.57);
+    }
+
+    #[test]
+    fn test_display() {
+        let mom = Momentum::new(10).unwrap();
+        assert_eq!(format!("{}", mom), "MOM(10)");
+    }
+}
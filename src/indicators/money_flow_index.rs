@@ -1,9 +1,11 @@
-use std::fmt;
+use alloc::boxed::Box;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, High, Low, Next, Period, Reset, Volume};
-
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset, Validate, Volume};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Money Flow Index (MFI).
@@ -27,6 +29,8 @@ use serde::{Deserialize, Serialize};
 ///
 /// Money Flow Index(MFI) = PMF / (PMF + NMF) * 100
 ///
+/// The MFI is 50 while there is no money flow, e.g. for the first bar, or as long as the
+/// typical price or the volume doesn't change.
 ///
 /// # Parameters
 ///
@@ -52,9 +56,9 @@ use serde::{Deserialize, Serialize};
 /// # Links
 /// * [Money Flow Index, Wikipedia](https://en.wikipedia.org/wiki/Money_flow_index)
 /// * [Money Flow Index, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:money_flow_index_mfi)
-
 #[doc(alias = "MFI")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MoneyFlowIndex {
     period: usize,
     index: usize,
@@ -76,7 +80,7 @@ impl MoneyFlowIndex {
                 previous_typical_price: 0.0,
                 total_positive_money_flow: 0.0,
                 total_negative_money_flow: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                deque: ring(0.0, period)?,
             }),
         }
     }
@@ -88,6 +92,24 @@ impl Period for MoneyFlowIndex {
     }
 }
 
+impl Validate for MoneyFlowIndex {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for MoneyFlowIndex {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
 impl<T: High + Low + Close + Volume> Next<&T> for MoneyFlowIndex {
     type Output = f64;
 
@@ -101,7 +123,7 @@ impl<T: High + Low + Close + Volume> Next<&T> for MoneyFlowIndex {
         };
 
         if self.count < self.period {
-            self.count = self.count + 1;
+            self.count += 1;
             if self.count == 1 {
                 self.previous_typical_price = tp;
                 return 50.0;
@@ -128,9 +150,13 @@ impl<T: High + Low + Close + Volume> Next<&T> for MoneyFlowIndex {
         }
         self.previous_typical_price = tp;
 
-        self.total_positive_money_flow
-            / (self.total_positive_money_flow + self.total_negative_money_flow)
-            * 100.0
+        let total_money_flow = self.total_positive_money_flow + self.total_negative_money_flow;
+        if total_money_flow > 0.0 {
+            self.total_positive_money_flow / total_money_flow * 100.0
+        } else {
+            // neither buying nor selling pressure, e.g. the typical price has not changed
+            50.0
+        }
     }
 }
 
@@ -199,6 +225,24 @@ mod tests {
         assert_eq!(round(mfi.next(&bar8)), 60.87);
     }
 
+    #[test]
+    fn test_no_money_flow() {
+        let mut mfi = MoneyFlowIndex::new(2).unwrap();
+
+        let bar = Bar::new().high(3).low(1).close(2).volume(500.0);
+        assert_eq!(mfi.next(&bar), 50.0);
+        assert_eq!(mfi.next(&bar), 50.0);
+        assert_eq!(mfi.next(&bar), 50.0);
+
+        let mut mfi = MoneyFlowIndex::new(1).unwrap();
+        assert_eq!(mfi.next(&bar), 50.0);
+        assert_eq!(mfi.next(&bar.high(4)), 100.0);
+        assert_eq!(
+            mfi.next(&Bar::new().high(4).low(1).close(2).volume(0.0)),
+            50.0
+        );
+    }
+
     #[test]
     fn test_reset() {
         let mut mfi = MoneyFlowIndex::new(3).unwrap();
@@ -0,0 +1,319 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    ExponentialMovingAverage, HullMovingAverage, KaufmanAdaptiveMovingAverage, SimpleMovingAverage,
+    SineWeightedMovingAverage, TriangularMovingAverage, WeightedMovingAverage,
+    ZeroLagExponentialMovingAverage,
+};
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Type of a [MovingAverage].
+///
+/// Parses from and displays as the short name of the moving average, e.g. `SMA`, ignoring the
+/// case when parsing, and serializes the same way, so it can be read from configuration files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+pub enum MaType {
+    /// [SimpleMovingAverage]
+    Sma,
+    /// [ExponentialMovingAverage]
+    Ema,
+    /// [WeightedMovingAverage]
+    Wma,
+    /// [TriangularMovingAverage]
+    Tma,
+    /// [SineWeightedMovingAverage]
+    Swma,
+    /// [HullMovingAverage]
+    Hma,
+    /// [KaufmanAdaptiveMovingAverage] with the default fast and slow periods
+    Kama,
+    /// [ZeroLagExponentialMovingAverage]
+    Zlema,
+}
+
+impl MaType {
+    /// All the types of moving averages.
+    pub const ALL: [MaType; 8] = [
+        MaType::Sma,
+        MaType::Ema,
+        MaType::Wma,
+        MaType::Tma,
+        MaType::Swma,
+        MaType::Hma,
+        MaType::Kama,
+        MaType::Zlema,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            MaType::Sma => "SMA",
+            MaType::Ema => "EMA",
+            MaType::Wma => "WMA",
+            MaType::Tma => "TMA",
+            MaType::Swma => "SWMA",
+            MaType::Hma => "HMA",
+            MaType::Kama => "KAMA",
+            MaType::Zlema => "ZLEMA",
+        }
+    }
+}
+
+impl FromStr for MaType {
+    type Err = TaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        MaType::ALL
+            .iter()
+            .copied()
+            .find(|ma_type| ma_type.name().eq_ignore_ascii_case(s))
+            .ok_or(TaError::InvalidParameter)
+    }
+}
+
+impl fmt::Display for MaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Moving average of a type selected at runtime.
+///
+/// Wraps one of the moving averages of this crate, so strategies and configuration files can
+/// choose the type without generics or boxing. It behaves exactly like the wrapped moving
+/// average, including its [Display](fmt::Display) output.
+///
+/// # Parameters
+///
+/// * _ma_type_ - type of the moving average. Default is [MaType::Ema].
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{MaType, MovingAverage};
+/// use ta_panther::Next;
+///
+/// let ma_type = "wma".parse::<MaType>().unwrap();
+/// let mut ma = MovingAverage::new(ma_type, 3).unwrap();
+/// assert_eq!(ma.next(10.0), 10.0);
+/// assert_eq!(ma.next(13.0), 12.0);
+/// assert_eq!(ma.next(16.0), 14.0);
+/// assert_eq!(ma.to_string(), "WMA(3)");
+/// ```
+///
+#[doc(alias = "MA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MovingAverage {
+    Sma(SimpleMovingAverage),
+    Ema(ExponentialMovingAverage),
+    Wma(WeightedMovingAverage),
+    Tma(TriangularMovingAverage),
+    Swma(SineWeightedMovingAverage),
+    Hma(HullMovingAverage),
+    Kama(KaufmanAdaptiveMovingAverage),
+    Zlema(ZeroLagExponentialMovingAverage),
+}
+
+macro_rules! dispatch {
+    ($self:expr, $ma:ident => $body:expr) => {
+        match $self {
+            MovingAverage::Sma($ma) => $body,
+            MovingAverage::Ema($ma) => $body,
+            MovingAverage::Wma($ma) => $body,
+            MovingAverage::Tma($ma) => $body,
+            MovingAverage::Swma($ma) => $body,
+            MovingAverage::Hma($ma) => $body,
+            MovingAverage::Kama($ma) => $body,
+            MovingAverage::Zlema($ma) => $body,
+        }
+    };
+}
+
+impl MovingAverage {
+    pub fn new(ma_type: MaType, period: usize) -> Result<Self> {
+        Ok(match ma_type {
+            MaType::Sma => MovingAverage::Sma(SimpleMovingAverage::new(period)?),
+            MaType::Ema => MovingAverage::Ema(ExponentialMovingAverage::new(period)?),
+            MaType::Wma => MovingAverage::Wma(WeightedMovingAverage::new(period)?),
+            MaType::Tma => MovingAverage::Tma(TriangularMovingAverage::new(period)?),
+            MaType::Swma => MovingAverage::Swma(SineWeightedMovingAverage::new(period)?),
+            MaType::Hma => MovingAverage::Hma(HullMovingAverage::new(period)?),
+            MaType::Kama => MovingAverage::Kama(KaufmanAdaptiveMovingAverage::new(period, 2, 30)?),
+            MaType::Zlema => MovingAverage::Zlema(ZeroLagExponentialMovingAverage::new(period)?),
+        })
+    }
+
+    pub fn ma_type(&self) -> MaType {
+        match self {
+            MovingAverage::Sma(_) => MaType::Sma,
+            MovingAverage::Ema(_) => MaType::Ema,
+            MovingAverage::Wma(_) => MaType::Wma,
+            MovingAverage::Tma(_) => MaType::Tma,
+            MovingAverage::Swma(_) => MaType::Swma,
+            MovingAverage::Hma(_) => MaType::Hma,
+            MovingAverage::Kama(_) => MaType::Kama,
+            MovingAverage::Zlema(_) => MaType::Zlema,
+        }
+    }
+}
+
+impl Period for MovingAverage {
+    fn period(&self) -> usize {
+        dispatch!(self, ma => ma.period())
+    }
+}
+
+impl IsReady for MovingAverage {
+    fn bars_remaining(&self) -> usize {
+        dispatch!(self, ma => ma.bars_remaining())
+    }
+}
+
+impl Next<f64> for MovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        dispatch!(self, ma => ma.next(input))
+    }
+}
+
+impl<T: Close> Next<&T> for MovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl NextBatch for MovingAverage {
+    fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+        dispatch!(self, ma => ma.next_batch(input))
+    }
+}
+
+impl Reset for MovingAverage {
+    fn reset(&mut self) {
+        dispatch!(self, ma => ma.reset())
+    }
+}
+
+impl Default for MovingAverage {
+    fn default() -> Self {
+        Self::new(MaType::Ema, 9).unwrap()
+    }
+}
+
+impl fmt::Display for MovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        dispatch!(self, ma => ma.fmt(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(MovingAverage);
+
+    #[test]
+    fn test_new() {
+        for &ma_type in MaType::ALL.iter() {
+            assert!(MovingAverage::new(ma_type, 0).is_err());
+            let ma = MovingAverage::new(ma_type, 5).unwrap();
+            assert_eq!(ma.ma_type(), ma_type);
+            assert_eq!(ma.period(), 5);
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        let inputs = [10.0, 12.0, 11.0, 14.0, 15.0, 13.0];
+        for &ma_type in MaType::ALL.iter() {
+            let mut ma = MovingAverage::new(ma_type, 4).unwrap();
+            let mut expected: Box<dyn Next<f64, Output = f64>> = match ma_type {
+                MaType::Sma => Box::new(SimpleMovingAverage::new(4).unwrap()),
+                MaType::Ema => Box::new(ExponentialMovingAverage::new(4).unwrap()),
+                MaType::Wma => Box::new(WeightedMovingAverage::new(4).unwrap()),
+                MaType::Tma => Box::new(TriangularMovingAverage::new(4).unwrap()),
+                MaType::Swma => Box::new(SineWeightedMovingAverage::new(4).unwrap()),
+                MaType::Hma => Box::new(HullMovingAverage::new(4).unwrap()),
+                MaType::Kama => Box::new(KaufmanAdaptiveMovingAverage::new(4, 2, 30).unwrap()),
+                MaType::Zlema => Box::new(ZeroLagExponentialMovingAverage::new(4).unwrap()),
+            };
+            for &input in inputs.iter() {
+                assert_eq!(ma.next(input), expected.next(input));
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_ready() {
+        for &ma_type in MaType::ALL.iter() {
+            let mut ma = MovingAverage::new(ma_type, 4).unwrap();
+            let warm_up = ma.bars_remaining();
+            assert!(warm_up >= 4, "{} warms up over {} inputs", ma, warm_up);
+            for remaining in (1..=warm_up).rev() {
+                assert_eq!(ma.bars_remaining(), remaining);
+                ma.next(10.0);
+            }
+            assert!(ma.is_ready());
+
+            ma.reset();
+            assert_eq!(ma.bars_remaining(), warm_up);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ma = MovingAverage::new(MaType::Sma, 2).unwrap();
+        ma.next(&Bar::new().close(4));
+        assert_eq!(ma.next(&Bar::new().close(8)), 6.0);
+    }
+
+    #[test]
+    fn test_next_batch() {
+        let mut ma = MovingAverage::new(MaType::Sma, 2).unwrap();
+        assert_eq!(ma.next_batch(&[2.0, 4.0, 8.0]), vec![2.0, 3.0, 6.0]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ma = MovingAverage::new(MaType::Hma, 4).unwrap();
+        ma.next(4.0);
+        ma.next(10.0);
+
+        ma.reset();
+        assert_eq!(ma.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        MovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ma = MovingAverage::new(MaType::Kama, 10).unwrap();
+        assert_eq!(format!("{}", ma), "KAMA(10, 2, 30)");
+        assert_eq!(format!("{}", MaType::Swma), "SWMA");
+    }
+
+    #[test]
+    fn test_ma_type_from_str() {
+        for &ma_type in MaType::ALL.iter() {
+            assert_eq!(ma_type.to_string().parse::<MaType>(), Ok(ma_type));
+        }
+        assert_eq!("ema".parse::<MaType>(), Ok(MaType::Ema));
+        assert_eq!("EMA2".parse::<MaType>(), Err(TaError::InvalidParameter));
+    }
+}
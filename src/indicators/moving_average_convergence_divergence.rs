@@ -1,9 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
-use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset};
+use crate::indicators::{MaType, MovingAverage};
+use crate::{Close, IsReady, Next, Period, Reset};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Moving average converge divergence (MACD).
@@ -20,6 +21,10 @@ use serde::{Deserialize, Serialize};
 /// moving average (EMA), and a "slow" (longer period) EMA of the price series.
 /// The average series is an EMA of the MACD series itself.
 ///
+/// Like many charting platforms, [with_ma_types](Self::with_ma_types) allows other
+/// [types](MaType) of moving averages instead of the EMAs, for the fast and slow averages
+/// (the oscillator) and for the signal. The outputs differ materially from the classic MACD.
+///
 /// # Formula
 ///
 /// # Parameters
@@ -27,11 +32,13 @@ use serde::{Deserialize, Serialize};
 /// * _fast_period_ - period for the fast EMA. Default is 12.
 /// * _slow_period_ - period for the slow EMA. Default is 26.
 /// * _signal_period_ - period for the signal EMA. Default is 9.
+/// * _oscillator_ma_ - type of the fast and slow moving averages. Default is [MaType::Ema].
+/// * _signal_ma_ - type of the signal moving average. Default is [MaType::Ema].
 ///
 /// # Example
 ///
 /// ```
-/// use ta_panther::indicators::MovingAverageConvergenceDivergence as Macd;
+/// use ta_panther::indicators::{MaType, MovingAverageConvergenceDivergence as Macd};
 /// use ta_panther::Next;
 ///
 /// let mut macd = Macd::new(3, 6, 4).unwrap();
@@ -49,26 +56,52 @@ use serde::{Deserialize, Serialize};
 ///     let n2 = (nums.2 * 100.0).round() / 100.0;
 ///     (n0, n1, n2)
 /// }
+///
+/// let mut sma_macd = Macd::with_ma_types(2, 4, 2, MaType::Sma, MaType::Sma).unwrap();
+/// assert_eq!(sma_macd.next(2.0).macd, 0.0);
+/// assert_eq!(sma_macd.next(4.0).macd, 0.0);
+/// assert_eq!(sma_macd.next(6.0).macd, 1.0);
+/// assert_eq!(sma_macd.to_string(), "MACD(2, 4, 2, SMA, SMA)");
 /// ```
 #[doc(alias = "MACD")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MovingAverageConvergenceDivergence {
-    fast_ema: Ema,
-    slow_ema: Ema,
-    signal_ema: Ema,
+    fast_ma: MovingAverage,
+    slow_ma: MovingAverage,
+    signal_ma: MovingAverage,
 }
 
 impl MovingAverageConvergenceDivergence {
     pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Result<Self> {
+        Self::with_ma_types(
+            fast_period,
+            slow_period,
+            signal_period,
+            MaType::Ema,
+            MaType::Ema,
+        )
+    }
+
+    /// Creates a MACD with fast and slow moving averages of type `oscillator_ma` and a signal
+    /// moving average of type `signal_ma`.
+    pub fn with_ma_types(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+        oscillator_ma: MaType,
+        signal_ma: MaType,
+    ) -> Result<Self> {
         Ok(Self {
-            fast_ema: Ema::new(fast_period)?,
-            slow_ema: Ema::new(slow_period)?,
-            signal_ema: Ema::new(signal_period)?,
+            fast_ma: MovingAverage::new(oscillator_ma, fast_period)?,
+            slow_ma: MovingAverage::new(oscillator_ma, slow_period)?,
+            signal_ma: MovingAverage::new(signal_ma, signal_period)?,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MovingAverageConvergenceDivergenceOutput {
     pub macd: f64,
     pub signal: f64,
@@ -81,15 +114,24 @@ impl From<MovingAverageConvergenceDivergenceOutput> for (f64, f64, f64) {
     }
 }
 
+impl IsReady for MovingAverageConvergenceDivergence {
+    fn bars_remaining(&self) -> usize {
+        self.fast_ma
+            .bars_remaining()
+            .max(self.slow_ma.bars_remaining())
+            .max(self.signal_ma.bars_remaining())
+    }
+}
+
 impl Next<f64> for MovingAverageConvergenceDivergence {
     type Output = MovingAverageConvergenceDivergenceOutput;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        let fast_val = self.fast_ema.next(input);
-        let slow_val = self.slow_ema.next(input);
+        let fast_val = self.fast_ma.next(input);
+        let slow_val = self.slow_ma.next(input);
 
         let macd = fast_val - slow_val;
-        let signal = self.signal_ema.next(macd);
+        let signal = self.signal_ma.next(macd);
         let histogram = macd - signal;
 
         MovingAverageConvergenceDivergenceOutput {
@@ -110,9 +152,9 @@ impl<T: Close> Next<&T> for MovingAverageConvergenceDivergence {
 
 impl Reset for MovingAverageConvergenceDivergence {
     fn reset(&mut self) {
-        self.fast_ema.reset();
-        self.slow_ema.reset();
-        self.signal_ema.reset();
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+        self.signal_ma.reset();
     }
 }
 
@@ -126,11 +168,17 @@ impl fmt::Display for MovingAverageConvergenceDivergence {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "MACD({}, {}, {})",
-            self.fast_ema.period(),
-            self.slow_ema.period(),
-            self.signal_ema.period()
-        )
+            "MACD({}, {}, {}",
+            self.fast_ma.period(),
+            self.slow_ma.period(),
+            self.signal_ma.period()
+        )?;
+        let oscillator_ma = self.fast_ma.ma_type();
+        let signal_ma = self.signal_ma.ma_type();
+        if oscillator_ma != MaType::Ema || signal_ma != MaType::Ema {
+            write!(f, ", {}, {}", oscillator_ma, signal_ma)?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -138,6 +186,7 @@ impl fmt::Display for MovingAverageConvergenceDivergence {
 mod tests {
     use super::*;
     use crate::test_helper::*;
+    use crate::NextChecked;
     type Macd = MovingAverageConvergenceDivergence;
 
     test_indicator!(Macd);
@@ -155,6 +204,7 @@ mod tests {
         assert!(Macd::new(1, 0, 1).is_err());
         assert!(Macd::new(1, 1, 0).is_err());
         assert!(Macd::new(1, 1, 1).is_ok());
+        assert!(Macd::with_ma_types(1, 1, 0, MaType::Sma, MaType::Wma).is_err());
     }
 
     #[test]
@@ -169,6 +219,47 @@ mod tests {
         assert_eq!(round(macd.next(6.5).into()), (0.94, 0.87, 0.07));
     }
 
+    #[test]
+    fn test_ma_types() {
+        let mut macd = Macd::with_ma_types(2, 3, 2, MaType::Sma, MaType::Wma).unwrap();
+
+        assert_eq!(round(macd.next(6.0).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(macd.next(12.0).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(macd.next(18.0).into()), (3.0, 2.0, 1.0));
+        assert_eq!(round(macd.next(24.0).into()), (3.0, 3.0, 0.0));
+
+        let mut ema = Macd::new(3, 6, 4).unwrap();
+        let mut zlema = Macd::with_ma_types(3, 6, 4, MaType::Zlema, MaType::Ema).unwrap();
+        ema.next(2.0);
+        zlema.next(2.0);
+        assert_ne!(ema.next(3.0), zlema.next(3.0));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut macd = Macd::new(2, 4, 3).unwrap();
+        assert_eq!(macd.bars_remaining(), 4);
+        for input in 1..4 {
+            macd.next(input as f64);
+        }
+        assert_eq!(macd.bars_remaining(), 1);
+        macd.next(4.0);
+        assert!(macd.is_ready());
+
+        macd.reset();
+        assert_eq!(macd.bars_remaining(), 4);
+    }
+
+    #[test]
+    fn test_next_checked() {
+        let mut macd = Macd::new(3, 6, 4).unwrap();
+        for &input in [2.0, 3.0, 4.2, 7.0, 6.7].iter() {
+            assert_eq!(macd.next_checked(input), None);
+        }
+        let output = macd.next_checked(6.5).unwrap();
+        assert_eq!(round(output.into()), (0.94, 0.87, 0.07));
+    }
+
     #[test]
     fn test_reset() {
         let mut macd = Macd::new(3, 6, 4).unwrap();
@@ -191,5 +282,7 @@ mod tests {
     fn test_display() {
         let indicator = Macd::new(13, 30, 10).unwrap();
         assert_eq!(format!("{}", indicator), "MACD(13, 30, 10)");
+        let indicator = Macd::with_ma_types(13, 30, 10, MaType::Ema, MaType::Sma).unwrap();
+        assert_eq!(format!("{}", indicator), "MACD(13, 30, 10, EMA, SMA)");
     }
 }
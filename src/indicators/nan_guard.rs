@@ -0,0 +1,212 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Handling of NaN and infinite inputs.
+///
+/// A single NaN input poisons the state of most indicators for good, e.g. an EMA or a running
+/// sum never recovers from it. The policy decides what a [NanGuard] does with such inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NanPolicy {
+    /// Feeds the input into the indicator anyway, like unguarded indicators do.
+    #[default]
+    Propagate,
+    /// Ignores the input and repeats the latest output, which is NaN before the first valid
+    /// input.
+    Skip,
+    /// Ignores the input and reports an error from [try_next](NanGuard::try_next). [Next]
+    /// returns NaN instead.
+    Error,
+}
+
+impl fmt::Display for NanPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NanPolicy::Propagate => f.write_str("PROPAGATE"),
+            NanPolicy::Skip => f.write_str("SKIP"),
+            NanPolicy::Error => f.write_str("ERROR"),
+        }
+    }
+}
+
+/// NaN guard.
+///
+/// Wraps an indicator and applies a [NanPolicy] to NaN and infinite inputs before they reach
+/// it, so corrupted ticks of a feed do not wreck the state of long-running indicators. With
+/// [try_next](NanGuard::try_next) invalid inputs can be detected by the caller.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{NanGuard, NanPolicy, SimpleMovingAverage};
+/// use ta_panther::Next;
+///
+/// let mut sma = NanGuard::new(SimpleMovingAverage::new(2).unwrap(), NanPolicy::Skip);
+/// assert_eq!(sma.next(2.0), 2.0);
+/// assert_eq!(sma.next(f64::NAN), 2.0);
+/// assert_eq!(sma.next(4.0), 3.0);
+///
+/// let mut sma = NanGuard::new(SimpleMovingAverage::new(2).unwrap(), NanPolicy::Error);
+/// assert_eq!(sma.try_next(2.0), Ok(2.0));
+/// assert!(sma.try_next(f64::INFINITY).is_err());
+/// assert_eq!(sma.try_next(4.0), Ok(3.0));
+/// ```
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NanGuard<N> {
+    indicator: N,
+    policy: NanPolicy,
+    latest: f64,
+}
+
+impl<N> NanGuard<N> {
+    pub fn new(indicator: N, policy: NanPolicy) -> Self {
+        Self {
+            indicator,
+            policy,
+            latest: f64::NAN,
+        }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn inner(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn policy(&self) -> NanPolicy {
+        self.policy
+    }
+}
+
+impl<N: Next<f64, Output = f64>> NanGuard<N> {
+    /// Feeds the next input, returning an error if it is NaN or infinite and the policy is
+    /// [NanPolicy::Error]. The wrapped indicator is left untouched in that case.
+    pub fn try_next(&mut self, input: f64) -> Result<f64> {
+        if !input.is_finite() {
+            match self.policy {
+                NanPolicy::Propagate => {}
+                NanPolicy::Skip => return Ok(self.latest),
+                NanPolicy::Error => return Err(TaError::InvalidInput),
+            }
+        }
+        self.latest = self.indicator.next(input);
+        Ok(self.latest)
+    }
+}
+
+impl<N: Period> Period for NanGuard<N> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<N: IsReady> IsReady for NanGuard<N> {
+    fn bars_remaining(&self) -> usize {
+        self.indicator.bars_remaining()
+    }
+}
+
+impl<N: Next<f64, Output = f64>> Next<f64> for NanGuard<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.try_next(input).unwrap_or(f64::NAN)
+    }
+}
+
+impl<N: Next<f64, Output = f64>, T: Close> Next<&T> for NanGuard<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<N: Reset> Reset for NanGuard<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.latest = f64::NAN;
+    }
+}
+
+impl<N: Default> Default for NanGuard<N> {
+    fn default() -> Self {
+        Self::new(N::default(), NanPolicy::default())
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for NanGuard<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NAN_GUARD({}, {})", self.indicator, self.policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage as Ema, Maximum};
+    use crate::test_helper::*;
+
+    type GuardedEma = NanGuard<Ema>;
+
+    test_indicator!(GuardedEma);
+
+    #[test]
+    fn test_propagate() {
+        let mut ema = NanGuard::new(Ema::new(3).unwrap(), NanPolicy::Propagate);
+        ema.next(2.0);
+        assert!(ema.next(f64::NAN).is_nan());
+        assert!(ema.next(5.0).is_nan());
+    }
+
+    #[test]
+    fn test_skip() {
+        let mut max = NanGuard::new(Maximum::new(2).unwrap(), NanPolicy::Skip);
+        assert!(max.next(f64::NAN).is_nan());
+        assert_eq!(max.next(3.0), 3.0);
+        assert_eq!(max.next(f64::NEG_INFINITY), 3.0);
+        assert_eq!(max.try_next(f64::NAN), Ok(3.0));
+        assert_eq!(max.next(1.0), 3.0);
+        assert_eq!(max.next(2.0), 2.0);
+    }
+
+    #[test]
+    fn test_error() {
+        let mut ema = NanGuard::new(Ema::new(3).unwrap(), NanPolicy::Error);
+        assert_eq!(ema.try_next(2.0), Ok(2.0));
+        assert_eq!(ema.try_next(f64::NAN), Err(TaError::InvalidInput));
+        assert!(ema.next(f64::NAN).is_nan());
+        assert_eq!(ema.try_next(5.0), Ok(3.5));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ema = NanGuard::new(Ema::new(3).unwrap(), NanPolicy::Skip);
+        assert_eq!(ema.next(&Bar::new().close(2)), 2.0);
+        assert_eq!(ema.next(&Bar::new().close(f64::NAN)), 2.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ema = NanGuard::new(Ema::new(3).unwrap(), NanPolicy::Skip);
+        ema.next(4.0);
+
+        ema.reset();
+        assert!(ema.next(f64::NAN).is_nan());
+        assert_eq!(ema.next(2.0), 2.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let ema = NanGuard::new(Ema::new(7).unwrap(), NanPolicy::Skip);
+        assert_eq!(format!("{}", ema), "NAN_GUARD(EMA(7), SKIP)");
+        assert_eq!(ema.policy(), NanPolicy::Skip);
+        assert_eq!(ema.inner().period(), 7);
+    }
+}
@@ -1,7 +1,8 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{Close, Next, Reset, Volume};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// On Balance Volume (OBV).
@@ -57,9 +58,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [On Balance Volume, Wikipedia](https://en.wikipedia.org/wiki/On-balance_volume)
 /// * [On Balance Volume, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:on_balance_volume_obv)
-
 #[doc(alias = "OBV")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OnBalanceVolume {
     obv: f64,
     prev_close: f64,
@@ -79,9 +80,9 @@ impl<T: Close + Volume> Next<&T> for OnBalanceVolume {
 
     fn next(&mut self, input: &T) -> f64 {
         if input.close() > self.prev_close {
-            self.obv = self.obv + input.volume();
+            self.obv += input.volume();
         } else if input.close() < self.prev_close {
-            self.obv = self.obv - input.volume();
+            self.obv -= input.volume();
         }
         self.prev_close = input.close();
         self.obv
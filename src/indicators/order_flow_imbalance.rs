@@ -0,0 +1,214 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::ring;
+use crate::indicators::TickRule;
+use crate::{Close, IsReady, Next, Period, Reset, Volume};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Order flow imbalance (OFI).
+///
+/// Share of the volume of the last _period_ trades which was bought rather than sold, with
+/// trades classified by the [TickRule]. Ranges from -1, when all the volume was sold, to 1,
+/// when all of it was bought. Returns 0 while no volume has been traded.
+///
+/// The input is a `(price, volume)` tuple of a trade, or a bar with its close and volume.
+///
+/// # Formula
+///
+/// _OFI = Σ b<sub>i</sub> * v<sub>i</sub> / Σ v<sub>i</sub>_
+///
+/// Where:
+///
+/// * _b<sub>i</sub>_ - sign of the trade from the tick rule
+/// * _v<sub>i</sub>_ - volume of the trade
+///
+/// # Parameters
+///
+/// * _period_ - number of trades (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::OrderFlowImbalance;
+/// use ta_panther::Next;
+///
+/// let mut ofi = OrderFlowImbalance::new(3).unwrap();
+/// assert_eq!(ofi.next((10.0, 100.0)), 0.0);
+/// assert_eq!(ofi.next((10.5, 100.0)), 0.5);
+/// assert_eq!(ofi.next((10.0, 200.0)), -0.25);
+/// assert_eq!(ofi.next((10.25, 200.0)), 0.2);
+/// ```
+///
+/// # Links
+///
+/// * [Order flow, Wikipedia](https://en.wikipedia.org/wiki/Order_flow)
+///
+#[doc(alias = "OFI")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OrderFlowImbalance {
+    period: usize,
+    index: usize,
+    count: usize,
+    tick_rule: TickRule,
+    signed_sum: f64,
+    volume_sum: f64,
+    deque: Box<[(f64, f64)]>,
+}
+
+impl OrderFlowImbalance {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                tick_rule: TickRule::new(),
+                signed_sum: 0.0,
+                volume_sum: 0.0,
+                deque: ring((0.0, 0.0), period)?,
+            }),
+        }
+    }
+}
+
+impl Period for OrderFlowImbalance {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for OrderFlowImbalance {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<(f64, f64)> for OrderFlowImbalance {
+    type Output = f64;
+
+    fn next(&mut self, (price, volume): (f64, f64)) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let (old_signed, old_volume) = self.deque[self.index];
+            self.signed_sum -= old_signed;
+            self.volume_sum -= old_volume;
+        }
+
+        let signed = self.tick_rule.next(price) * volume;
+        self.signed_sum += signed;
+        self.volume_sum += volume;
+
+        self.deque[self.index] = (signed, volume);
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        if self.volume_sum > 0.0 {
+            self.signed_sum / self.volume_sum
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for OrderFlowImbalance {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.close(), input.volume()))
+    }
+}
+
+impl Reset for OrderFlowImbalance {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.tick_rule.reset();
+        self.signed_sum = 0.0;
+        self.volume_sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = (0.0, 0.0);
+        }
+    }
+}
+
+impl Default for OrderFlowImbalance {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for OrderFlowImbalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OFI({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(OrderFlowImbalance::new(0).is_err());
+        assert!(OrderFlowImbalance::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ofi = OrderFlowImbalance::new(2).unwrap();
+        assert_eq!(ofi.next((5.0, 10.0)), 0.0);
+        assert_eq!(ofi.next((6.0, 30.0)), 0.75);
+        // zero tick keeps the sign of the previous trade
+        assert_eq!(ofi.next((6.0, 10.0)), 1.0);
+        assert_eq!(ofi.next((4.0, 30.0)), -0.5);
+        assert!(ofi.is_ready());
+    }
+
+    #[test]
+    fn test_no_volume() {
+        let mut ofi = OrderFlowImbalance::new(2).unwrap();
+        assert_eq!(ofi.next((5.0, 0.0)), 0.0);
+        assert_eq!(ofi.next((6.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ofi = OrderFlowImbalance::new(2).unwrap();
+        ofi.next(&Bar::new().close(4).volume(10.0));
+        assert_eq!(ofi.next(&Bar::new().close(3).volume(30.0)), -0.75);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ofi = OrderFlowImbalance::new(2).unwrap();
+        ofi.next((5.0, 10.0));
+        ofi.next((6.0, 10.0));
+
+        ofi.reset();
+        assert_eq!(ofi.bars_remaining(), 2);
+        assert_eq!(ofi.next((4.0, 10.0)), 0.0);
+        assert_eq!(ofi.next((3.0, 10.0)), -0.5);
+    }
+
+    #[test]
+    fn test_default() {
+        OrderFlowImbalance::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ofi = OrderFlowImbalance::new(14).unwrap();
+        assert_eq!(format!("{}", ofi), "OFI(14)");
+    }
+}
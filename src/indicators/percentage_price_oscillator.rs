@@ -1,9 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
-use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset};
+use crate::indicators::{MaType, MovingAverage};
+use crate::{Close, IsReady, Next, Period, Reset};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Percentage Price Oscillator (PPO).
@@ -20,13 +21,28 @@ use serde::{Deserialize, Serialize};
 /// moving average (EMA), and a "slow" (longer period) EMA of the price series.
 /// The average series is an EMA of the PPO series itself.
 ///
+/// Unlike the [MACD](crate::indicators::MovingAverageConvergenceDivergence), the difference is
+/// expressed as a percentage of the slow EMA, so the values of instruments with different price
+/// scales can be compared. Like for the MACD, [with_ma_types](Self::with_ma_types) allows other
+/// [types](MaType) of moving averages.
+///
 /// # Formula
 ///
+/// PPO = (EMA<sub>fast</sub> - EMA<sub>slow</sub>) / EMA<sub>slow</sub> x 100
+///
+/// Signal = EMA<sub>signal</sub>(PPO)
+///
+/// Histogram = PPO - Signal
+///
+/// The PPO is 0 while the slow EMA is 0.
+///
 /// # Parameters
 ///
 /// * _fast_period_ - period for the fast EMA. Default is 12.
 /// * _slow_period_ - period for the slow EMA. Default is 26.
 /// * _signal_period_ - period for the signal EMA. Default is 9.
+/// * _oscillator_ma_ - type of the fast and slow moving averages. Default is [MaType::Ema].
+/// * _signal_ma_ - type of the signal moving average. Default is [MaType::Ema].
 ///
 /// # Example
 ///
@@ -51,24 +67,44 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 #[doc(alias = "PPO")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PercentagePriceOscillator {
-    fast_ema: Ema,
-    slow_ema: Ema,
-    signal_ema: Ema,
+    fast_ma: MovingAverage,
+    slow_ma: MovingAverage,
+    signal_ma: MovingAverage,
 }
 
 impl PercentagePriceOscillator {
     pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Result<Self> {
-        Ok(PercentagePriceOscillator {
-            fast_ema: Ema::new(fast_period)?,
-            slow_ema: Ema::new(slow_period)?,
-            signal_ema: Ema::new(signal_period)?,
+        Self::with_ma_types(
+            fast_period,
+            slow_period,
+            signal_period,
+            MaType::Ema,
+            MaType::Ema,
+        )
+    }
+
+    /// Creates a PPO with fast and slow moving averages of type `oscillator_ma` and a signal
+    /// moving average of type `signal_ma`.
+    pub fn with_ma_types(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+        oscillator_ma: MaType,
+        signal_ma: MaType,
+    ) -> Result<Self> {
+        Ok(Self {
+            fast_ma: MovingAverage::new(oscillator_ma, fast_period)?,
+            slow_ma: MovingAverage::new(oscillator_ma, slow_period)?,
+            signal_ma: MovingAverage::new(signal_ma, signal_period)?,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PercentagePriceOscillatorOutput {
     pub ppo: f64,
     pub signal: f64,
@@ -81,15 +117,28 @@ impl From<PercentagePriceOscillatorOutput> for (f64, f64, f64) {
     }
 }
 
+impl IsReady for PercentagePriceOscillator {
+    fn bars_remaining(&self) -> usize {
+        self.fast_ma
+            .bars_remaining()
+            .max(self.slow_ma.bars_remaining())
+            .max(self.signal_ma.bars_remaining())
+    }
+}
+
 impl Next<f64> for PercentagePriceOscillator {
     type Output = PercentagePriceOscillatorOutput;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        let fast_val = self.fast_ema.next(input);
-        let slow_val = self.slow_ema.next(input);
+        let fast_val = self.fast_ma.next(input);
+        let slow_val = self.slow_ma.next(input);
 
-        let ppo = (fast_val - slow_val) / slow_val * 100.0;
-        let signal = self.signal_ema.next(ppo);
+        let ppo = if slow_val != 0.0 {
+            (fast_val - slow_val) / slow_val * 100.0
+        } else {
+            0.0
+        };
+        let signal = self.signal_ma.next(ppo);
         let histogram = ppo - signal;
 
         PercentagePriceOscillatorOutput {
@@ -110,9 +159,9 @@ impl<T: Close> Next<&T> for PercentagePriceOscillator {
 
 impl Reset for PercentagePriceOscillator {
     fn reset(&mut self) {
-        self.fast_ema.reset();
-        self.slow_ema.reset();
-        self.signal_ema.reset();
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+        self.signal_ma.reset();
     }
 }
 
@@ -126,11 +175,17 @@ impl fmt::Display for PercentagePriceOscillator {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "PPO({}, {}, {})",
-            self.fast_ema.period(),
-            self.slow_ema.period(),
-            self.signal_ema.period()
-        )
+            "PPO({}, {}, {}",
+            self.fast_ma.period(),
+            self.slow_ma.period(),
+            self.signal_ma.period()
+        )?;
+        let oscillator_ma = self.fast_ma.ma_type();
+        let signal_ma = self.signal_ma.ma_type();
+        if oscillator_ma != MaType::Ema || signal_ma != MaType::Ema {
+            write!(f, ", {}, {}", oscillator_ma, signal_ma)?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -169,6 +224,36 @@ mod tests {
         assert_eq!(round(ppo.next(6.5).into()), (17.84, 19.08, -1.24));
     }
 
+    #[test]
+    fn test_ma_types() {
+        let mut ppo = Ppo::with_ma_types(2, 4, 2, MaType::Sma, MaType::Sma).unwrap();
+        assert_eq!(round(ppo.next(2.0).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(ppo.next(4.0).into()), (0.0, 0.0, 0.0));
+        // fast 5, slow 4
+        assert_eq!(round(ppo.next(6.0).into()), (25.0, 12.5, 12.5));
+    }
+
+    #[test]
+    fn test_zero_slow_average() {
+        let mut ppo = Ppo::new(3, 6, 4).unwrap();
+        assert_eq!(round(ppo.next(0.0).into()), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut ppo = Ppo::new(2, 4, 3).unwrap();
+        assert_eq!(ppo.bars_remaining(), 4);
+        for input in 1..4 {
+            ppo.next(input as f64);
+        }
+        assert_eq!(ppo.bars_remaining(), 1);
+        ppo.next(4.0);
+        assert!(ppo.is_ready());
+
+        ppo.reset();
+        assert_eq!(ppo.bars_remaining(), 4);
+    }
+
     #[test]
     fn test_reset() {
         let mut ppo = Ppo::new(3, 6, 4).unwrap();
@@ -191,5 +276,7 @@ mod tests {
     fn test_display() {
         let indicator = Ppo::new(13, 30, 10).unwrap();
         assert_eq!(format!("{}", indicator), "PPO(13, 30, 10)");
+        let indicator = Ppo::with_ma_types(13, 30, 10, MaType::Ema, MaType::Sma).unwrap();
+        assert_eq!(format!("{}", indicator), "PPO(13, 30, 10, EMA, SMA)");
     }
 }
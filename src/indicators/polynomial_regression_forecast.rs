@@ -0,0 +1,196 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{deque, polynomial_forecast};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Polynomial Regression Forecast.
+///
+/// A curved counterpart of [LinearRegressionPrediction](crate::indicators::LinearRegressionPrediction).
+/// It fits a least squares polynomial of degree 2 or 3 through the last `period` values and
+/// extrapolates it one bar ahead, which follows series with curvature better than a line.
+///
+/// The polynomial is fitted in a basis of polynomials orthogonal over the window, so the fit
+/// stays numerically stable even for long windows.
+///
+/// # Formula
+///
+/// y = b<sub>0</sub> + b<sub>1</sub>x + ... + b<sub>d</sub>x<sup>d</sup>, fitted over
+/// _x = 1, 2, ..., n_ and evaluated at _x = n + 1_.
+///
+/// Where:
+///
+/// * _d_ - degree of the polynomial
+/// * _n_ - number of values in the window
+///
+/// While the window holds `degree` values or less, the degree is lowered to fit them.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than degree)
+/// * _degree_ - degree of the polynomial (1, 2 or 3)
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::PolynomialRegressionForecast;
+/// use ta_panther::Next;
+///
+/// let mut prf = PolynomialRegressionForecast::new(4, 2).unwrap();
+/// prf.next(1.0);
+/// prf.next(4.0);
+/// prf.next(9.0);
+/// assert_eq!(prf.next(16.0).round(), 25.0);
+/// ```
+///
+/// # Links
+///
+/// * [Polynomial regression, Wikipedia](https://en.wikipedia.org/wiki/Polynomial_regression)
+///
+#[doc(alias = "POLY_FORECAST")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PolynomialRegressionForecast {
+    period: usize,
+    degree: usize,
+    deque: VecDeque<f64>,
+}
+
+impl PolynomialRegressionForecast {
+    pub fn new(period: usize, degree: usize) -> Result<Self> {
+        if !(1..=3).contains(&degree) || period <= degree {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            degree,
+            deque: deque(period)?,
+        })
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+}
+
+impl Period for PolynomialRegressionForecast {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for PolynomialRegressionForecast {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.deque.len())
+    }
+}
+
+impl Next<f64> for PolynomialRegressionForecast {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.deque.len() == self.period {
+            self.deque.pop_front();
+        }
+        self.deque.push_back(input);
+
+        let n = self.deque.len() as f64;
+        polynomial_forecast(self.deque.make_contiguous(), self.degree, n + 1.0)
+    }
+}
+
+impl<T: Close> Next<&T> for PolynomialRegressionForecast {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for PolynomialRegressionForecast {
+    fn reset(&mut self) {
+        self.deque.clear();
+    }
+}
+
+impl Default for PolynomialRegressionForecast {
+    fn default() -> Self {
+        Self::new(14, 2).unwrap()
+    }
+}
+
+impl fmt::Display for PolynomialRegressionForecast {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "POLY_FORECAST({}, {})", self.period, self.degree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(PolynomialRegressionForecast);
+
+    #[test]
+    fn test_new() {
+        assert!(PolynomialRegressionForecast::new(0, 2).is_err());
+        assert!(PolynomialRegressionForecast::new(2, 2).is_err());
+        assert!(PolynomialRegressionForecast::new(5, 0).is_err());
+        assert!(PolynomialRegressionForecast::new(5, 4).is_err());
+        assert!(PolynomialRegressionForecast::new(3, 2).is_ok());
+        assert!(PolynomialRegressionForecast::new(4, 3).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut prf = PolynomialRegressionForecast::new(5, 2).unwrap();
+        assert_eq!(prf.next(1.0), 1.0);
+        assert_eq!(round(prf.next(4.0)), 7.0);
+        assert_eq!(round(prf.next(9.0)), 16.0);
+        assert_eq!(round(prf.next(16.0)), 25.0);
+        assert_eq!(round(prf.next(25.0)), 36.0);
+        assert_eq!(round(prf.next(36.0)), 49.0);
+
+        let mut prf = PolynomialRegressionForecast::new(6, 3).unwrap();
+        let mut last = 0.0;
+        for x in 1..=8 {
+            let x = x as f64;
+            last = prf.next(x * x * x - 2.0 * x);
+        }
+        assert_eq!(round(last), 711.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut prf = PolynomialRegressionForecast::new(3, 2).unwrap();
+        prf.next(&Bar::new().close(1));
+        prf.next(&Bar::new().close(4));
+        assert_eq!(round(prf.next(&Bar::new().close(9))), 16.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut prf = PolynomialRegressionForecast::new(3, 2).unwrap();
+        prf.next(1.0);
+        prf.next(4.0);
+
+        prf.reset();
+        assert_eq!(prf.next(7.0), 7.0);
+    }
+
+    #[test]
+    fn test_default() {
+        PolynomialRegressionForecast::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let prf = PolynomialRegressionForecast::new(10, 3).unwrap();
+        assert_eq!(format!("{}", prf), "POLY_FORECAST(10, 3)");
+    }
+}
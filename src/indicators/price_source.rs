@@ -0,0 +1,130 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, High, Low, Open};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Price of a bar an indicator is computed from, the "source" of charting platforms.
+///
+/// Indicators of a single price read the close of bars, [WithSource] feeds them another price.
+/// Parsed case-insensitively from its [Display](fmt::Display) name.
+///
+/// [WithSource]: crate::indicators::WithSource
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::PriceSource;
+/// use ta_panther::DataItem;
+///
+/// let bar = DataItem::builder()
+///     .open(9.0)
+///     .high(12.0)
+///     .low(8.0)
+///     .close(11.0)
+///     .volume(1000.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(PriceSource::Close.price(&bar), 11.0);
+/// assert_eq!(PriceSource::Hl2.price(&bar), 10.0);
+/// assert_eq!("ohlc4".parse::<PriceSource>().unwrap().price(&bar), 10.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PriceSource {
+    /// Close price.
+    #[default]
+    Close,
+    /// Open price.
+    Open,
+    /// Median price, _(High + Low) / 2_.
+    Hl2,
+    /// Typical price, _(High + Low + Close) / 3_.
+    Hlc3,
+    /// Average price, _(Open + High + Low + Close) / 4_.
+    Ohlc4,
+    /// Weighted close, _(High + Low + 2 x Close) / 4_.
+    Weighted,
+}
+
+impl PriceSource {
+    /// All the price sources.
+    pub const ALL: [PriceSource; 6] = [
+        PriceSource::Close,
+        PriceSource::Open,
+        PriceSource::Hl2,
+        PriceSource::Hlc3,
+        PriceSource::Ohlc4,
+        PriceSource::Weighted,
+    ];
+
+    /// Returns the price of a bar.
+    pub fn price<T: Open + High + Low + Close>(self, bar: &T) -> f64 {
+        match self {
+            PriceSource::Close => bar.close(),
+            PriceSource::Open => bar.open(),
+            PriceSource::Hl2 => (bar.high() + bar.low()) / 2.0,
+            PriceSource::Hlc3 => (bar.high() + bar.low() + bar.close()) / 3.0,
+            PriceSource::Ohlc4 => (bar.open() + bar.high() + bar.low() + bar.close()) / 4.0,
+            PriceSource::Weighted => (bar.high() + bar.low() + 2.0 * bar.close()) / 4.0,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            PriceSource::Close => "CLOSE",
+            PriceSource::Open => "OPEN",
+            PriceSource::Hl2 => "HL2",
+            PriceSource::Hlc3 => "HLC3",
+            PriceSource::Ohlc4 => "OHLC4",
+            PriceSource::Weighted => "WEIGHTED",
+        }
+    }
+}
+
+impl FromStr for PriceSource {
+    type Err = TaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        PriceSource::ALL
+            .iter()
+            .copied()
+            .find(|source| source.name().eq_ignore_ascii_case(s))
+            .ok_or(TaError::InvalidParameter)
+    }
+}
+
+impl fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_price() {
+        let bar = Bar::new().open(9).high(12).low(8).close(13);
+        assert_eq!(PriceSource::Close.price(&bar), 13.0);
+        assert_eq!(PriceSource::Open.price(&bar), 9.0);
+        assert_eq!(PriceSource::Hl2.price(&bar), 10.0);
+        assert_eq!(PriceSource::Hlc3.price(&bar), 11.0);
+        assert_eq!(PriceSource::Ohlc4.price(&bar), 10.5);
+        assert_eq!(PriceSource::Weighted.price(&bar), 11.5);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("hlc3".parse::<PriceSource>().unwrap(), PriceSource::Hlc3);
+        assert!("HLC".parse::<PriceSource>().is_err());
+        for source in PriceSource::ALL.iter() {
+            assert_eq!(source.to_string().parse::<PriceSource>().unwrap(), *source);
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use std::fmt;
+use alloc::boxed::Box;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Rate of Change (ROC)
@@ -39,7 +42,8 @@ use serde::{Deserialize, Serialize};
 /// * [Rate of Change, Wikipedia](https://en.wikipedia.org/wiki/Momentum_(technical_analysis))
 ///
 #[doc(alias = "ROC")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RateOfChange {
     period: usize,
     index: usize,
@@ -55,7 +59,7 @@ impl RateOfChange {
                 period,
                 index: 0,
                 count: 0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                deque: ring(0.0, period)?,
             }),
         }
     }
@@ -67,6 +71,24 @@ impl Period for RateOfChange {
     }
 }
 
+impl Validate for RateOfChange {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period + 1,
+        )
+    }
+}
+
+impl IsReady for RateOfChange {
+    fn bars_remaining(&self) -> usize {
+        (self.period + 1).saturating_sub(self.count)
+    }
+}
+
 impl Next<f64> for RateOfChange {
     type Output = f64;
 
@@ -175,4 +197,15 @@ mod tests {
         assert_eq!(round(roc.next(10.4)), 4.0);
         assert_eq!(round(roc.next(10.57)), 5.7);
     }
+
+    #[test]
+    fn test_is_ready() {
+        let mut roc = RateOfChange::new(2).unwrap();
+        assert_eq!(roc.bars_remaining(), 3);
+        roc.next(10.0);
+        roc.next(11.0);
+        assert!(!roc.is_ready());
+        roc.next(12.0);
+        assert!(roc.is_ready());
+    }
 }
@@ -0,0 +1,214 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rate of Change Ratio (ROCR)
+///
+/// The ratio of the price to the price _n_ periods ago. It carries the same information as the
+/// [Rate of Change](crate::indicators::RateOfChange), but is centered around 1 instead of 0,
+/// which makes it convenient to compound: the product of consecutive ratios is the ratio of the
+/// whole span.
+///
+/// Until _n_ periods have passed, the ratio to the first input is returned, like the ROC does, so
+/// the first output is 1.
+///
+/// # Formula
+///
+/// ROCR = Price<sub>t</sub> / Price<sub>t-n</sub>
+///
+/// Where:
+///
+/// * ROCR - current value of Rate of Change Ratio indicator
+/// * P<sub>t</sub> - price at the moment
+/// * P<sub>t-n</sub> - price _n_ periods ago
+///
+/// # Parameters
+///
+/// * _period_ - number of periods integer greater than 0. Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::RateOfChangeRatio;
+/// use ta_panther::Next;
+///
+/// let mut rocr = RateOfChangeRatio::new(2).unwrap();
+/// assert_eq!(rocr.next(10.0), 1.0);   //  1
+/// assert_eq!(rocr.next(8.0), 0.8);    //  8 / 10 = 0.8
+/// assert_eq!(rocr.next(20.0), 2.0);   //  20 / 10 = 2
+/// assert_eq!(rocr.next(20.0), 2.5);   //  20 / 8 = 2.5
+/// ```
+///
+/// # Links
+///
+/// * [Rate of Change, Wikipedia](https://en.wikipedia.org/wiki/Momentum_(technical_analysis))
+///
+#[doc(alias = "ROCR")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RateOfChangeRatio {
+    period: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+}
+
+impl RateOfChangeRatio {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for RateOfChangeRatio {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for RateOfChangeRatio {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period + 1,
+        )
+    }
+}
+
+impl IsReady for RateOfChangeRatio {
+    fn bars_remaining(&self) -> usize {
+        (self.period + 1).saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for RateOfChangeRatio {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> f64 {
+        let previous = if self.count > self.period {
+            self.deque[self.index]
+        } else {
+            self.count += 1;
+            if self.count == 1 {
+                input
+            } else {
+                self.deque[0]
+            }
+        };
+        self.deque[self.index] = input;
+
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        input / previous
+    }
+}
+
+impl<T: Close> Next<&T> for RateOfChangeRatio {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> f64 {
+        self.next(input.close())
+    }
+}
+
+impl Default for RateOfChangeRatio {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for RateOfChangeRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ROCR({})", self.period)
+    }
+}
+
+impl Reset for RateOfChangeRatio {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RateOfChangeRatio);
+
+    #[test]
+    fn test_new() {
+        assert!(RateOfChangeRatio::new(0).is_err());
+        assert!(RateOfChangeRatio::new(1).is_ok());
+        assert!(RateOfChangeRatio::new(100_000).is_ok());
+    }
+
+    #[test]
+    fn test_next_f64() {
+        let mut rocr = RateOfChangeRatio::new(3).unwrap();
+
+        assert_eq!(round(rocr.next(10.0)), 1.0);
+        assert_eq!(round(rocr.next(10.4)), 1.04);
+        assert_eq!(round(rocr.next(10.57)), 1.057);
+        assert_eq!(round(rocr.next(10.8)), 1.08);
+        assert_eq!(round(rocr.next(10.9)), 1.048);
+        assert_eq!(round(rocr.next(10.0)), 0.946);
+    }
+
+    #[test]
+    fn test_next_bar() {
+        fn bar(close: f64) -> Bar {
+            Bar::new().close(close)
+        }
+
+        let mut rocr = RateOfChangeRatio::new(3).unwrap();
+
+        assert_eq!(round(rocr.next(&bar(10.0))), 1.0);
+        assert_eq!(round(rocr.next(&bar(10.4))), 1.04);
+        assert_eq!(round(rocr.next(&bar(10.57))), 1.057);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rocr = RateOfChangeRatio::new(3).unwrap();
+
+        rocr.next(12.3);
+        rocr.next(15.0);
+
+        rocr.reset();
+
+        assert_eq!(round(rocr.next(10.0)), 1.0);
+        assert_eq!(round(rocr.next(10.4)), 1.04);
+        assert_eq!(round(rocr.next(10.57)), 1.057);
+    }
+
+    #[test]
+    fn test_display() {
+        let rocr = RateOfChangeRatio::new(10).unwrap();
+        assert_eq!(format!("{}", rocr), "ROCR(10)");
+    }
+}
@@ -0,0 +1,381 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{AverageDirectionalIndex, AverageTrueRange, SimpleMovingAverage};
+use crate::rolling::RollingWindow;
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Market regime of a [RegimeClassifier].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Regime {
+    /// Strong trend with a rising moving average.
+    TrendingUp,
+    /// Strong trend with a falling moving average.
+    TrendingDown,
+    /// Neither trending nor volatile.
+    Ranging,
+    /// Volatility in the top percentiles of the lookback, whatever the trend.
+    Volatile,
+}
+
+impl fmt::Display for Regime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Regime::TrendingUp => f.write_str("TRENDING_UP"),
+            Regime::TrendingDown => f.write_str("TRENDING_DOWN"),
+            Regime::Ranging => f.write_str("RANGING"),
+            Regime::Volatile => f.write_str("VOLATILE"),
+        }
+    }
+}
+
+/// Thresholds of a [RegimeClassifier].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeThresholds {
+    /// ADX at or above which the market is trending (between 0.0 and 100.0). Default is 25.0.
+    pub adx: f64,
+    /// Volatility percentile at or above which the market is volatile (between 0.0 and 1.0).
+    /// Default is 0.9.
+    pub volatility: f64,
+    /// Slope of the moving average, in percent per bar, beyond which a trend is up or down
+    /// (0.0 or greater). Default is 0.0.
+    pub slope: f64,
+}
+
+impl RegimeThresholds {
+    fn is_valid(&self) -> bool {
+        (0.0..=100.0).contains(&self.adx)
+            && (0.0..=1.0).contains(&self.volatility)
+            && self.slope >= 0.0
+            && self.slope.is_finite()
+    }
+}
+
+impl Default for RegimeThresholds {
+    fn default() -> Self {
+        Self {
+            adx: 25.0,
+            volatility: 0.9,
+            slope: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeClassifierOutput {
+    pub regime: Regime,
+    pub adx: f64,
+    /// Fraction of the previous volatilities of the lookback below the current one.
+    pub volatility_percentile: f64,
+    /// Change of the moving average since the previous bar, in percent.
+    pub slope: f64,
+}
+
+/// Market regime classifier.
+///
+/// Combines the trend strength, the volatility and the direction of the price into a discrete
+/// [Regime], so strategies can switch rule sets by regime:
+///
+/// * [Volatile](Regime::Volatile) if the volatility percentile reaches its threshold
+/// * else [TrendingUp](Regime::TrendingUp) if the ADX reaches its threshold and the slope is
+///   above its threshold
+/// * else [TrendingDown](Regime::TrendingDown) if the ADX reaches its threshold and the slope
+///   is below the opposite of its threshold
+/// * else [Ranging](Regime::Ranging)
+///
+/// # Formula
+///
+/// Volatility = ATR(period) / Close
+///
+/// Volatility percentile = fraction of the previous _lookback_ volatilities below the current
+/// one, 0 on the first bar
+///
+/// Slope = 100 x (SMA<sub>t</sub> - SMA<sub>t-1</sub>) / SMA<sub>t-1</sub>, 0 on the first bar
+///
+/// Where _ATR_ is the [AverageTrueRange], and the ADX is the [AverageDirectionalIndex] of the
+/// same period.
+///
+/// # Parameters
+///
+/// * _period_ - period of the ADX and the ATR (integer greater than 0). Default is 14.
+/// * _ma_period_ - period of the SMA (integer greater than 0). Default is 50.
+/// * _lookback_ - number of previous volatilities ranked (integer greater than 0). Default
+///   is 100.
+/// * _thresholds_ - see [RegimeThresholds] and [with_thresholds](Self::with_thresholds).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{Regime, RegimeClassifier};
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(close + 1.0)
+///         .low(close - 1.0)
+///         .close(close)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut regime = RegimeClassifier::new(3, 3, 20).unwrap();
+/// let mut output = regime.next(&bar(100.0));
+/// for close in 1..10 {
+///     output = regime.next(&bar(100.0 + 2.0 * close as f64));
+/// }
+/// assert_eq!(output.regime, Regime::TrendingUp);
+/// ```
+#[doc(alias = "REGIME")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeClassifier {
+    adx: AverageDirectionalIndex,
+    atr: AverageTrueRange,
+    ma: SimpleMovingAverage,
+    volatilities: RollingWindow<f64>,
+    previous_ma: Option<f64>,
+    thresholds: RegimeThresholds,
+}
+
+impl RegimeClassifier {
+    pub fn new(period: usize, ma_period: usize, lookback: usize) -> Result<Self> {
+        Self::with_thresholds(period, ma_period, lookback, RegimeThresholds::default())
+    }
+
+    pub fn with_thresholds(
+        period: usize,
+        ma_period: usize,
+        lookback: usize,
+        thresholds: RegimeThresholds,
+    ) -> Result<Self> {
+        if !thresholds.is_valid() {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            adx: AverageDirectionalIndex::new(period)?,
+            atr: AverageTrueRange::new(period)?,
+            ma: SimpleMovingAverage::new(ma_period)?,
+            volatilities: RollingWindow::new(lookback)?,
+            previous_ma: None,
+            thresholds,
+        })
+    }
+
+    pub fn thresholds(&self) -> RegimeThresholds {
+        self.thresholds
+    }
+}
+
+impl Period for RegimeClassifier {
+    fn period(&self) -> usize {
+        self.adx.period()
+    }
+}
+
+impl IsReady for RegimeClassifier {
+    /// Ready once the ADX and the moving average are warm and the lookback of volatilities is
+    /// full.
+    fn bars_remaining(&self) -> usize {
+        let percentile = self.volatilities.capacity() - self.volatilities.len();
+        self.adx
+            .bars_remaining()
+            .max(self.ma.bars_remaining())
+            .max(percentile)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for RegimeClassifier {
+    type Output = RegimeClassifierOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let adx = self.adx.next(input).adx;
+
+        let volatility = self.atr.next(input) / input.close();
+        let volatility_percentile = if self.volatilities.is_empty() {
+            0.0
+        } else {
+            let below = self
+                .volatilities
+                .iter()
+                .filter(|&&v| v < volatility)
+                .count();
+            below as f64 / self.volatilities.len() as f64
+        };
+        self.volatilities.push(volatility);
+
+        let ma = self.ma.next(input.close());
+        let slope = match self.previous_ma.replace(ma) {
+            Some(previous) if previous != 0.0 => 100.0 * (ma - previous) / previous,
+            _ => 0.0,
+        };
+
+        let thresholds = &self.thresholds;
+        let regime = if volatility_percentile >= thresholds.volatility {
+            Regime::Volatile
+        } else if adx >= thresholds.adx && slope > thresholds.slope {
+            Regime::TrendingUp
+        } else if adx >= thresholds.adx && slope < -thresholds.slope {
+            Regime::TrendingDown
+        } else {
+            Regime::Ranging
+        };
+        RegimeClassifierOutput {
+            regime,
+            adx,
+            volatility_percentile,
+            slope,
+        }
+    }
+}
+
+impl Reset for RegimeClassifier {
+    fn reset(&mut self) {
+        self.adx.reset();
+        self.atr.reset();
+        self.ma.reset();
+        self.volatilities.clear();
+        self.previous_ma = None;
+    }
+}
+
+impl Default for RegimeClassifier {
+    fn default() -> Self {
+        Self::new(14, 50, 100).unwrap()
+    }
+}
+
+impl fmt::Display for RegimeClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "REGIME({}, {}, {})",
+            self.adx.period(),
+            self.ma.period(),
+            self.volatilities.capacity()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use alloc::string::ToString;
+
+    fn bar(close: f64, range: f64) -> Bar {
+        Bar::new()
+            .high(close + range)
+            .low(close - range)
+            .close(close)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RegimeClassifier::new(0, 50, 100).is_err());
+        assert!(RegimeClassifier::new(14, 0, 100).is_err());
+        assert!(RegimeClassifier::new(14, 50, 0).is_err());
+
+        let thresholds = |adx, volatility, slope| RegimeThresholds {
+            adx,
+            volatility,
+            slope,
+        };
+        let with_thresholds = |t| RegimeClassifier::with_thresholds(14, 50, 100, t);
+        assert!(with_thresholds(thresholds(101.0, 0.9, 0.0)).is_err());
+        assert!(with_thresholds(thresholds(25.0, 1.5, 0.0)).is_err());
+        assert!(with_thresholds(thresholds(25.0, f64::NAN, 0.0)).is_err());
+        assert!(with_thresholds(thresholds(25.0, 0.9, -0.1)).is_err());
+        let classifier = with_thresholds(thresholds(20.0, 0.8, 0.1)).unwrap();
+        assert_eq!(classifier.thresholds(), thresholds(20.0, 0.8, 0.1));
+    }
+
+    #[test]
+    fn test_next() {
+        let mut regime = RegimeClassifier::new(3, 3, 20).unwrap();
+        let first = regime.next(&bar(100.0, 5.0));
+        assert_eq!(first.regime, Regime::Ranging);
+        assert_eq!((first.volatility_percentile, first.slope), (0.0, 0.0));
+
+        let mut output = first.clone();
+        for _ in 0..10 {
+            output = regime.next(&bar(100.0, 5.0));
+        }
+        assert_eq!(output.regime, Regime::Ranging);
+        for i in 1..10 {
+            output = regime.next(&bar(100.0 - 2.0 * i as f64, 1.0));
+        }
+        assert_eq!(output.regime, Regime::TrendingDown);
+        assert!(output.slope < 0.0);
+
+        // flat closes lose the trend
+        for _ in 0..20 {
+            output = regime.next(&bar(82.0, 1.0));
+        }
+        assert_eq!(output.regime, Regime::Ranging);
+
+        // the widest range of the lookback
+        let output = regime.next(&bar(82.0, 10.0));
+        assert_eq!(output.volatility_percentile, 1.0);
+        assert_eq!(output.regime, Regime::Volatile);
+
+        regime.reset();
+        assert_eq!(regime.next(&bar(100.0, 5.0)), first);
+    }
+
+    #[test]
+    fn test_slope_threshold() {
+        let thresholds = RegimeThresholds {
+            slope: 5.0,
+            ..Default::default()
+        };
+        let mut regime = RegimeClassifier::with_thresholds(3, 3, 10, thresholds).unwrap();
+        let mut output = regime.next(&bar(100.0, 1.0));
+        for i in 1..10 {
+            output = regime.next(&bar(100.0 + 2.0 * i as f64, 1.0));
+        }
+        assert!(output.adx >= 25.0);
+        assert_eq!(output.regime, Regime::Ranging);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut classifier = RegimeClassifier::new(2, 3, 6).unwrap();
+        assert_eq!(classifier.bars_remaining(), 6);
+        for close in 0..5 {
+            classifier.next(&bar(100.0 + close as f64, 2.0));
+        }
+        assert_eq!(classifier.bars_remaining(), 1);
+        classifier.next(&bar(105.0, 2.0));
+        assert!(classifier.is_ready());
+
+        // the ADX takes longest to warm up
+        let mut classifier = RegimeClassifier::new(5, 3, 4).unwrap();
+        for close in 0..4 {
+            classifier.next(&bar(100.0 + close as f64, 2.0));
+        }
+        assert_eq!(classifier.bars_remaining(), 6);
+
+        classifier.reset();
+        assert_eq!(classifier.bars_remaining(), 10);
+    }
+
+    #[test]
+    fn test_default() {
+        RegimeClassifier::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let regime = RegimeClassifier::default();
+        assert_eq!(regime.to_string(), "REGIME(14, 50, 100)");
+        assert_eq!(Regime::TrendingDown.to_string(), "TRENDING_DOWN");
+    }
+}
@@ -0,0 +1,201 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{deque, linear_regression, regression_standard_error};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Linear Regression Channel.
+///
+/// Fits a linear regression line through the last `period` values (see
+/// [LinearRegressionPrediction](crate::indicators::LinearRegressionPrediction)) and surrounds
+/// the current value of the line with bands placed `multiplier` standard errors
+/// (see [StandardError](crate::indicators::StandardError)) away from it.
+///
+/// # Formula
+///
+///  * _Middle_ - value of the regression line at the latest input
+///  * _Upper_ = Middle + STDERR * multiplier
+///  * _Lower_ = Middle - STDERR * multiplier
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+/// * _multiplier_ - number of standard errors between the line and the bands
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{RegressionChannel, RegressionChannelOutput};
+/// use ta_panther::Next;
+///
+/// let mut rc = RegressionChannel::new(3, 2.0).unwrap();
+///
+/// rc.next(1.0);
+/// rc.next(2.0);
+/// let out = rc.next(3.0);
+///
+/// assert_eq!(out.average, 3.0);
+/// assert_eq!(out.upper, 3.0);
+/// assert_eq!(out.lower, 3.0);
+/// ```
+///
+/// # Links
+///
+/// * [Linear Regression Channel, StockCharts](https://chartschool.stockcharts.com/table-of-contents/chart-analysis/chart-types/raff-regression-channel)
+///
+#[doc(alias = "LRC")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegressionChannel {
+    period: usize,
+    multiplier: f64,
+    deque: VecDeque<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegressionChannelOutput {
+    pub average: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+impl RegressionChannel {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                multiplier,
+                deque: deque(period)?,
+            }),
+        }
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+}
+
+impl Period for RegressionChannel {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for RegressionChannel {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.deque.len())
+    }
+}
+
+impl Next<f64> for RegressionChannel {
+    type Output = RegressionChannelOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.deque.len() == self.period {
+            self.deque.pop_front();
+        }
+        self.deque.push_back(input);
+
+        let (slope, intercept) = linear_regression(self.deque.iter().copied());
+        let se = regression_standard_error(self.deque.iter().copied(), slope, intercept);
+        let average = slope * self.deque.len() as f64 + intercept;
+
+        Self::Output {
+            average,
+            upper: average + se * self.multiplier,
+            lower: average - se * self.multiplier,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for RegressionChannel {
+    type Output = RegressionChannelOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for RegressionChannel {
+    fn reset(&mut self) {
+        self.deque.clear();
+    }
+}
+
+impl Default for RegressionChannel {
+    fn default() -> Self {
+        Self::new(14, 2_f64).unwrap()
+    }
+}
+
+impl fmt::Display for RegressionChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LRC({}, {})", self.period, self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RegressionChannel);
+
+    #[test]
+    fn test_new() {
+        assert!(RegressionChannel::new(0, 2_f64).is_err());
+        assert!(RegressionChannel::new(1, 2_f64).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rc = RegressionChannel::new(4, 2.0_f64).unwrap();
+
+        let a = rc.next(1.0);
+        let b = rc.next(3.0);
+        let c = rc.next(2.0);
+        let d = rc.next(4.0);
+
+        assert_eq!(a.average, 1.0);
+        assert_eq!(b.average, 3.0);
+        assert_eq!(round(c.average), 2.5);
+        assert_eq!(round(d.average), 3.7);
+
+        assert_eq!(b.upper, 3.0);
+        assert_eq!(round(c.upper), 4.949);
+        assert_eq!(round(d.upper), 5.597);
+
+        assert_eq!(b.lower, 3.0);
+        assert_eq!(round(c.lower), 0.051);
+        assert_eq!(round(d.lower), 1.803);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rc = RegressionChannel::new(3, 2.0_f64).unwrap();
+        rc.next(1.0);
+        rc.next(5.0);
+
+        rc.reset();
+        let out = rc.next(2.0);
+        assert_eq!(out.average, 2.0);
+        assert_eq!(out.upper, 2.0);
+    }
+
+    #[test]
+    fn test_default() {
+        RegressionChannel::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let rc = RegressionChannel::new(10, 3.0_f64).unwrap();
+        assert_eq!(format!("{}", rc), "LRC(10, 3)");
+    }
+}
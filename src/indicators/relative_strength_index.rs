@@ -1,9 +1,11 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
-use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset, State};
+use crate::indicators::smoothing::Smoother;
+use crate::indicators::Smoothing;
+use crate::{Close, IsReady, Next, Period, Reset, State};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The relative strength index (RSI).
@@ -47,9 +49,15 @@ use serde::{Deserialize, Serialize};
 /// * p<sub>t</sub> - input value in a moment of time _t_
 /// * p<sub>t-1</sub> - input value in a moment of time _t-1_
 ///
+/// [with_smoothing](Self::with_smoothing) replaces the EMA with another [Smoothing], e.g. Wilder's
+/// original smoothing. With Wilder or SMA smoothing the first input returns 50 and the averages
+/// start from the first change, so the values match the published ones once _period_ changes
+/// have been seen.
+///
 /// # Parameters
 ///
 /// * _period_ - number of periods (integer greater than 0). Default value is 14.
+/// * _smoothing_ - smoothing of the averages. Default is [Smoothing::Ema].
 ///
 /// # Example
 ///
@@ -69,31 +77,46 @@ use serde::{Deserialize, Serialize};
 /// * [RSI (Investopedia)](http://www.investopedia.com/terms/r/rsi.asp)
 ///
 #[doc(alias = "RSI")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RelativeStrengthIndex {
     period: usize,
-    up_ema_indicator: Ema,
-    down_ema_indicator: Ema,
+    up_average: Smoother,
+    down_average: Smoother,
     prev_val: f64,
     is_new: bool,
 }
 
 impl RelativeStrengthIndex {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_smoothing(period, Smoothing::Ema)
+    }
+
+    /// Creates an RSI averaging the gains and losses with the given smoothing.
+    pub fn with_smoothing(period: usize, smoothing: Smoothing) -> Result<Self> {
         Ok(Self {
             period,
-            up_ema_indicator: Ema::new(period)?,
-            down_ema_indicator: Ema::new(period)?,
+            up_average: Smoother::new(smoothing, period)?,
+            down_average: Smoother::new(smoothing, period)?,
             prev_val: 0.0,
             is_new: true,
         })
     }
 
-    pub fn from_state(period: usize, up_ema_current: f64, down_ema_current: f64, prev_val: f64) -> Result<Self> {
+    pub fn smoothing(&self) -> Smoothing {
+        self.up_average.smoothing()
+    }
+
+    pub fn from_state(
+        period: usize,
+        up_ema_current: f64,
+        down_ema_current: f64,
+        prev_val: f64,
+    ) -> Result<Self> {
         Ok(Self {
             period,
-            up_ema_indicator: Ema::from_state(period, up_ema_current)?,
-            down_ema_indicator: Ema::from_state(period, down_ema_current)?,
+            up_average: Smoother::from_ema_state(period, up_ema_current)?,
+            down_average: Smoother::from_ema_state(period, down_ema_current)?,
             prev_val,
             is_new: false,
         })
@@ -106,6 +129,14 @@ impl Period for RelativeStrengthIndex {
     }
 }
 
+impl IsReady for RelativeStrengthIndex {
+    /// Without EMA smoothing the first input only seeds the previous value.
+    fn bars_remaining(&self) -> usize {
+        let seed = usize::from(self.is_new && self.smoothing() != Smoothing::Ema);
+        self.up_average.bars_remaining() + seed
+    }
+}
+
 impl Next<f64> for RelativeStrengthIndex {
     type Output = f64;
 
@@ -115,6 +146,10 @@ impl Next<f64> for RelativeStrengthIndex {
 
         if self.is_new {
             self.is_new = false;
+            if self.smoothing() != Smoothing::Ema {
+                self.prev_val = input;
+                return 50.0;
+            }
             // Initialize with some small seed numbers to avoid division by zero
             up = 0.1;
             down = 0.1;
@@ -127,9 +162,13 @@ impl Next<f64> for RelativeStrengthIndex {
         }
 
         self.prev_val = input;
-        let up_ema = self.up_ema_indicator.next(up);
-        let down_ema = self.down_ema_indicator.next(down);
-        100.0 * up_ema / (up_ema + down_ema)
+        let up_average = self.up_average.next(up);
+        let down_average = self.down_average.next(down);
+        if up_average + down_average > 0.0 {
+            100.0 * up_average / (up_average + down_average)
+        } else {
+            50.0
+        }
     }
 }
 
@@ -137,10 +176,12 @@ impl State for RelativeStrengthIndex {
     type Output = (usize, f64, f64, f64);
 
     fn state(&self) -> Self::Output {
-        let (_, up_ema_current) =self.up_ema_indicator.state();
-        let (_, down_ema_current) =self.down_ema_indicator.state();
-
-        (self.period, up_ema_current, down_ema_current, self.prev_val)
+        (
+            self.period,
+            self.up_average.current(),
+            self.down_average.current(),
+            self.prev_val,
+        )
     }
 }
 
@@ -156,8 +197,8 @@ impl Reset for RelativeStrengthIndex {
     fn reset(&mut self) {
         self.is_new = true;
         self.prev_val = 0.0;
-        self.up_ema_indicator.reset();
-        self.down_ema_indicator.reset();
+        self.up_average.reset();
+        self.down_average.reset();
     }
 }
 
@@ -169,7 +210,10 @@ impl Default for RelativeStrengthIndex {
 
 impl fmt::Display for RelativeStrengthIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "RSI({})", self.period)
+        match self.smoothing() {
+            Smoothing::Ema => write!(f, "RSI({})", self.period),
+            smoothing => write!(f, "RSI({}, {})", self.period, smoothing),
+        }
     }
 }
 
@@ -177,6 +221,7 @@ impl fmt::Display for RelativeStrengthIndex {
 mod tests {
     use super::*;
     use crate::test_helper::*;
+    use crate::NextChecked;
 
     test_indicator!(RelativeStrengthIndex);
 
@@ -195,6 +240,76 @@ mod tests {
         assert_eq!(rsi.next(9.5).round(), 16.0);
     }
 
+    #[test]
+    fn test_wilder_smoothing() {
+        // reference values of stockcharts.com
+        let closes = [
+            44.3389, 44.0902, 44.1497, 43.6124, 44.3278, 44.8264, 45.0955, 45.4245, 45.8433,
+            46.0826, 45.8931, 46.0328, 45.6140, 46.2820, 46.2820, 46.0028, 46.0328, 46.4116,
+            46.2222, 45.6439, 46.2122, 46.2521, 45.7137, 46.4515, 45.7835, 45.3548, 44.0288,
+            44.1783, 44.2181, 44.5672, 43.4205, 42.6628, 43.1314,
+        ];
+        let expected = [
+            70.53, 66.32, 66.55, 69.41, 66.36, 57.97, 62.93, 63.26, 56.06, 62.38, 54.71, 50.42,
+            39.99, 41.46, 41.87, 45.46, 37.30, 33.08, 37.77,
+        ];
+
+        let mut rsi = RelativeStrengthIndex::with_smoothing(14, Smoothing::Wilder).unwrap();
+        assert_eq!(rsi.smoothing(), Smoothing::Wilder);
+        let values: Vec<f64> = closes.iter().map(|&close| rsi.next(close)).collect();
+        assert_eq!(values[0], 50.0);
+        for (value, expected) in values[14..].iter().zip(expected.iter()) {
+            assert_eq!((value * 100.0).round() / 100.0, *expected);
+        }
+    }
+
+    #[test]
+    fn test_sma_smoothing() {
+        let mut rsi = RelativeStrengthIndex::with_smoothing(2, Smoothing::Sma).unwrap();
+        assert_eq!(rsi.next(10.0), 50.0);
+        assert_eq!(rsi.next(10.0), 50.0);
+        assert_eq!(rsi.next(12.0), 100.0);
+        assert_eq!(rsi.next(11.0), 66.66666666666667);
+        assert_eq!(rsi.next(10.0), 0.0);
+
+        rsi.reset();
+        assert_eq!(rsi.next(10.0), 50.0);
+        assert_eq!(rsi.next(9.0), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+        assert_eq!(rsi.bars_remaining(), 3);
+        rsi.next(10.0);
+        rsi.next(10.5);
+        assert_eq!(rsi.bars_remaining(), 1);
+        rsi.next(10.0);
+        assert!(rsi.is_ready());
+
+        let mut rsi = RelativeStrengthIndex::with_smoothing(2, Smoothing::Sma).unwrap();
+        assert_eq!(rsi.bars_remaining(), 3);
+        rsi.next(10.0);
+        assert_eq!(rsi.bars_remaining(), 2);
+        rsi.next(11.0);
+        rsi.next(12.0);
+        assert!(rsi.is_ready());
+
+        rsi.reset();
+        assert_eq!(rsi.bars_remaining(), 3);
+    }
+
+    #[test]
+    fn test_next_checked() {
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+        assert_eq!(rsi.next_checked(10.0), None);
+        assert_eq!(rsi.next_checked(10.5), None);
+        assert_eq!(rsi.next_checked(10.0).map(f64::round), Some(35.0));
+
+        rsi.reset();
+        assert_eq!(rsi.next_checked(10.0), None);
+    }
+
     #[test]
     fn test_reset() {
         let mut rsi = RelativeStrengthIndex::new(3).unwrap();
@@ -215,5 +330,7 @@ mod tests {
     fn test_display() {
         let rsi = RelativeStrengthIndex::new(16).unwrap();
         assert_eq!(format!("{}", rsi), "RSI(16)");
+        let rsi = RelativeStrengthIndex::with_smoothing(14, Smoothing::Wilder).unwrap();
+        assert_eq!(format!("{}", rsi), "RSI(14, WILDER)");
     }
 }
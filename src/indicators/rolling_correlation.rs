@@ -0,0 +1,157 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::rolling::RollingCoMoments;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling correlation.
+///
+/// Returns the Pearson correlation coefficient of two series over the last _period_ pairs of
+/// inputs, ranging from -1 (moving in opposite directions) to 1 (moving together). The inputs
+/// are pairs `(a, b)`, e.g. returns of an asset and of a benchmark. Returns 0 while either of the
+/// series is constant.
+///
+/// The co-moments are updated incrementally as pairs enter and leave the window, so every update
+/// is O(1).
+///
+/// # Formula
+///
+/// _CORR = cov(a, b) / (σ<sub>a</sub> σ<sub>b</sub>)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::RollingCorrelation;
+/// use ta_panther::Next;
+///
+/// let mut corr = RollingCorrelation::new(3).unwrap();
+/// corr.next((1.0, 10.0));
+/// corr.next((2.0, 20.0));
+/// assert_eq!(corr.next((3.0, 30.0)), 1.0);
+/// assert_eq!(corr.next((2.0, 40.0)), 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Pearson correlation coefficient, Wikipedia](https://en.wikipedia.org/wiki/Pearson_correlation_coefficient)
+///
+#[doc(alias = "CORR")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingCorrelation {
+    moments: RollingCoMoments,
+}
+
+impl RollingCorrelation {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            moments: RollingCoMoments::new(period)?,
+        })
+    }
+}
+
+impl Period for RollingCorrelation {
+    fn period(&self) -> usize {
+        self.moments.capacity()
+    }
+}
+
+impl IsReady for RollingCorrelation {
+    fn bars_remaining(&self) -> usize {
+        self.period() - self.moments.len()
+    }
+}
+
+impl Next<(f64, f64)> for RollingCorrelation {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        self.moments.push((a, b));
+        self.moments.correlation()
+    }
+}
+
+impl<T: Close> Next<(&T, &T)> for RollingCorrelation {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (&T, &T)) -> Self::Output {
+        self.next((a.close(), b.close()))
+    }
+}
+
+impl Reset for RollingCorrelation {
+    fn reset(&mut self) {
+        self.moments.clear();
+    }
+}
+
+impl Default for RollingCorrelation {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RollingCorrelation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CORR({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RollingCorrelation::new(0).is_err());
+        assert!(RollingCorrelation::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut corr = RollingCorrelation::new(3).unwrap();
+        assert_eq!(corr.next((1.0, 5.0)), 0.0);
+        assert_eq!(round(corr.next((2.0, 3.0))), -1.0);
+        assert_eq!(round(corr.next((4.0, 4.0))), -0.327);
+        assert_eq!(round(corr.next((3.0, 6.0))), 0.327);
+        assert_eq!(round(corr.next((3.0, 6.0))), -1.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut corr = RollingCorrelation::new(2).unwrap();
+        corr.next((&Bar::new().close(1), &Bar::new().close(4)));
+        let output = corr.next((&Bar::new().close(2), &Bar::new().close(2)));
+        assert_eq!(round(output), -1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut corr = RollingCorrelation::new(3).unwrap();
+        corr.next((1.0, 2.0));
+        corr.next((2.0, 4.0));
+
+        corr.reset();
+        assert_eq!(corr.next((1.0, 1.0)), 0.0);
+        assert_eq!(round(corr.next((0.0, 2.0))), -1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingCorrelation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let corr = RollingCorrelation::new(10).unwrap();
+        assert_eq!(format!("{}", corr), "CORR(10)");
+    }
+}
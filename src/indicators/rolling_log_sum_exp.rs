@@ -0,0 +1,199 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling log-sum-exp (LSE).
+///
+/// Returns the logarithm of the sum of the exponentials of the last _period_ inputs, a smooth
+/// approximation of their maximum. It is the building block for combining log-likelihoods or
+/// log-weights, e.g. to normalize them into probabilities.
+///
+/// The largest input is factored out before exponentiating, so the result neither overflows
+/// nor underflows for inputs of any magnitude. Every update scans the window, so it is
+/// O(period).
+///
+/// # Formula
+///
+/// _LSE = m + ln(Σ exp(x<sub>i</sub> - m))_, with _m = max(x<sub>i</sub>)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::RollingLogSumExp;
+/// use ta_panther::Next;
+///
+/// let mut lse = RollingLogSumExp::new(2).unwrap();
+/// assert_eq!(lse.next(1000.0), 1000.0);
+/// assert_eq!(lse.next(1000.0), 1000.0 + 2.0_f64.ln());
+/// ```
+///
+/// # Links
+///
+/// * [LogSumExp, Wikipedia](https://en.wikipedia.org/wiki/LogSumExp)
+///
+#[doc(alias = "LSE")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingLogSumExp {
+    period: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+}
+
+impl RollingLogSumExp {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for RollingLogSumExp {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for RollingLogSumExp {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for RollingLogSumExp {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for RollingLogSumExp {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let window = &self.deque[..self.count];
+        let max = window.iter().fold(f64::NEG_INFINITY, |max, &x| max.max(x));
+        if max.is_infinite() {
+            // all inputs are -inf, or one is +inf
+            return max;
+        }
+        max + window.iter().map(|&x| (x - max).exp()).sum::<f64>().ln()
+    }
+}
+
+impl<T: Close> Next<&T> for RollingLogSumExp {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for RollingLogSumExp {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for RollingLogSumExp {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RollingLogSumExp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LSE({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingLogSumExp);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingLogSumExp::new(0).is_err());
+        assert!(RollingLogSumExp::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut lse = RollingLogSumExp::new(3).unwrap();
+        assert_eq!(lse.next(1.0), 1.0);
+        assert_eq!(round(lse.next(2.0)), 2.313);
+        assert_eq!(round(lse.next(3.0)), 3.408);
+        assert_eq!(lse.next(1000.0), 1000.0);
+        assert_eq!(round(lse.next(1000.0)), 1000.693);
+        assert_eq!(round(lse.next(-1000.0)), 1000.693);
+        assert_eq!(lse.next(f64::INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut lse = RollingLogSumExp::new(2).unwrap();
+        assert_eq!(lse.next(&Bar::new().close(5)), 5.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut lse = RollingLogSumExp::new(3).unwrap();
+        lse.next(4.0);
+        lse.next(10.0);
+
+        lse.reset();
+        assert_eq!(lse.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingLogSumExp::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let lse = RollingLogSumExp::new(10).unwrap();
+        assert_eq!(format!("{}", lse), "LSE(10)");
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling maximum drawup.
+///
+/// Returns the largest relative rise from a trough to a later peak within the last _period_
+/// inputs, the mirror image of the maximum drawdown. A value of 0.5 means that the input rose by
+/// 50% at some point of the window. Returns 0 while the window only falls.
+///
+/// The inputs should be positive, e.g. prices or the value of an equity curve. Every update
+/// scans the window, so it is O(period).
+///
+/// # Formula
+///
+/// _MDU = max<sub>i ≤ j</sub>(x<sub>j</sub> / x<sub>i</sub>) - 1_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::RollingMaxDrawup;
+/// use ta_panther::Next;
+///
+/// let mut mdu = RollingMaxDrawup::new(3).unwrap();
+/// assert_eq!(mdu.next(10.0), 0.0);
+/// assert_eq!(mdu.next(8.0), 0.0);
+/// assert_eq!(mdu.next(12.0), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Drawdown (economics), Wikipedia](https://en.wikipedia.org/wiki/Drawdown_(economics))
+///
+#[doc(alias = "MDU")]
+#[doc(alias = "DRAWUP")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingMaxDrawup {
+    period: usize,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+}
+
+impl RollingMaxDrawup {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for RollingMaxDrawup {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for RollingMaxDrawup {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for RollingMaxDrawup {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for RollingMaxDrawup {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        // walk the window from the oldest input, tracking the lowest trough seen so far
+        let oldest = (self.index + self.period - self.count) % self.period;
+        let mut trough = f64::INFINITY;
+        let mut drawup: f64 = 0.0;
+        for i in 0..self.count {
+            let value = self.deque[(oldest + i) % self.period];
+            trough = trough.min(value);
+            drawup = drawup.max(value / trough - 1.0);
+        }
+        drawup
+    }
+}
+
+impl<T: Close> Next<&T> for RollingMaxDrawup {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for RollingMaxDrawup {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for RollingMaxDrawup {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RollingMaxDrawup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MDU({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingMaxDrawup);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingMaxDrawup::new(0).is_err());
+        assert!(RollingMaxDrawup::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mdu = RollingMaxDrawup::new(4).unwrap();
+        assert_eq!(mdu.next(10.0), 0.0);
+        assert_eq!(mdu.next(8.0), 0.0);
+        assert_eq!(mdu.next(12.0), 0.5);
+        assert_eq!(mdu.next(9.0), 0.5);
+        assert_eq!(mdu.next(15.0), 0.875);
+        assert_eq!(round(mdu.next(14.0)), 0.667);
+        assert_eq!(round(mdu.next(13.0)), 0.667);
+        assert_eq!(mdu.next(12.0), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut mdu = RollingMaxDrawup::new(2).unwrap();
+        mdu.next(&Bar::new().close(4));
+        assert_eq!(mdu.next(&Bar::new().close(5)), 0.25);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mdu = RollingMaxDrawup::new(3).unwrap();
+        mdu.next(1.0);
+        mdu.next(10.0);
+
+        mdu.reset();
+        assert_eq!(mdu.next(10.0), 0.0);
+        assert_eq!(round(mdu.next(11.0)), 0.1);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingMaxDrawup::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let mdu = RollingMaxDrawup::new(10).unwrap();
+        assert_eq!(format!("{}", mdu), "MDU(10)");
+    }
+}
@@ -0,0 +1,154 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::RollingPercentile;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling median.
+///
+/// Returns the middle value of the last _period_ inputs, or the mean of the two middle values if
+/// the window holds an even number of inputs. Unlike moving averages the median ignores single
+/// outliers, which makes it a robust filter for noisy data.
+///
+/// Updates take _O(log period)_ time, see [RollingPercentile].
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::RollingMedian;
+/// use ta_panther::Next;
+///
+/// let mut median = RollingMedian::new(3).unwrap();
+/// assert_eq!(median.next(10.0), 10.0);
+/// assert_eq!(median.next(12.0), 11.0);
+/// assert_eq!(median.next(100.0), 12.0);
+/// assert_eq!(median.next(11.0), 12.0);
+/// assert_eq!(median.next(13.0), 13.0);
+/// ```
+///
+/// # Links
+///
+/// * [Median filter, Wikipedia](https://en.wikipedia.org/wiki/Median_filter)
+///
+#[doc(alias = "MEDIAN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingMedian {
+    percentile: RollingPercentile,
+}
+
+impl RollingMedian {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            percentile: RollingPercentile::new(period, 0.5)?,
+        })
+    }
+}
+
+impl Period for RollingMedian {
+    fn period(&self) -> usize {
+        self.percentile.period()
+    }
+}
+
+impl IsReady for RollingMedian {
+    fn bars_remaining(&self) -> usize {
+        self.percentile.bars_remaining()
+    }
+}
+
+impl Next<f64> for RollingMedian {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.percentile.next(input)
+    }
+}
+
+impl<T: Close> Next<&T> for RollingMedian {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for RollingMedian {
+    fn reset(&mut self) {
+        self.percentile.reset();
+    }
+}
+
+impl Default for RollingMedian {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for RollingMedian {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MEDIAN({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingMedian);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingMedian::new(0).is_err());
+        assert!(RollingMedian::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut median = RollingMedian::new(4).unwrap();
+        assert_eq!(median.next(5.0), 5.0);
+        assert_eq!(median.next(1.0), 3.0);
+        assert_eq!(median.next(9.0), 5.0);
+        assert_eq!(median.next(7.0), 6.0);
+        assert_eq!(median.next(2.0), 4.5);
+        assert_eq!(median.next(2.0), 4.5);
+        assert_eq!(median.next(2.0), 2.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut median = RollingMedian::new(3).unwrap();
+        assert_eq!(median.next(&Bar::new().close(4)), 4.0);
+        assert_eq!(median.next(&Bar::new().close(8)), 6.0);
+        assert_eq!(median.next(&Bar::new().close(5)), 5.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut median = RollingMedian::new(3).unwrap();
+        median.next(4.0);
+        median.next(10.0);
+
+        median.reset();
+        assert_eq!(median.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingMedian::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let median = RollingMedian::new(5).unwrap();
+        assert_eq!(format!("{}", median), "MEDIAN(5)");
+    }
+}
@@ -0,0 +1,398 @@
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeSet as HashSet;
+use alloc::collections::BinaryHeap;
+use core::cmp::{Ordering, Reverse};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::ring;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling percentile.
+///
+/// Returns the value below which the given fraction of the last _period_ inputs falls. Values
+/// between two inputs are linearly interpolated, which matches the default method of most
+/// statistical packages. Until _period_ inputs have been seen, the percentile of all inputs so
+/// far is returned.
+///
+/// The window is kept in two heaps split at the requested rank, so every update takes
+/// _O(log period)_ time.
+///
+/// # Formula
+///
+/// For the sorted window _x<sub>0</sub> ≤ ... ≤ x<sub>n-1</sub>_:
+///
+/// * _r = q (n - 1)_, _k = floor(r)_
+/// * _P = x<sub>k</sub> + (r - k) (x<sub>k+1</sub> - x<sub>k</sub>)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+/// * _quantile_ - requested fraction _q_ of the window (between 0.0 and 1.0). Default is 0.5.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::RollingPercentile;
+/// use ta_panther::Next;
+///
+/// let mut p90 = RollingPercentile::new(5, 0.9).unwrap();
+/// p90.next(1.0);
+/// p90.next(5.0);
+/// p90.next(2.0);
+/// p90.next(4.0);
+/// assert_eq!(p90.next(3.0), 4.6);
+/// assert_eq!(p90.next(0.0), 4.6);
+/// ```
+///
+/// # Links
+///
+/// * [Percentile, Wikipedia](https://en.wikipedia.org/wiki/Percentile)
+///
+#[doc(alias = "PERCENTILE")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingPercentile {
+    period: usize,
+    quantile: f64,
+    index: usize,
+    count: usize,
+    deque: Box<[Item]>,
+    statistics: OrderStatistics,
+}
+
+impl RollingPercentile {
+    pub fn new(period: usize, quantile: f64) -> Result<Self> {
+        if period == 0 || !(0.0..=1.0).contains(&quantile) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            quantile,
+            index: 0,
+            count: 0,
+            deque: ring(Item::default(), period)?,
+            statistics: OrderStatistics::default(),
+        })
+    }
+
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+}
+
+impl Period for RollingPercentile {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for RollingPercentile {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for RollingPercentile {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            self.statistics.remove(self.deque[self.index]);
+        }
+
+        let item = self.statistics.insert(input);
+        self.deque[self.index] = item;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.statistics.percentile(self.quantile)
+    }
+}
+
+impl<T: Close> Next<&T> for RollingPercentile {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for RollingPercentile {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.statistics = OrderStatistics::default();
+    }
+}
+
+impl Default for RollingPercentile {
+    fn default() -> Self {
+        Self::new(14, 0.5).unwrap()
+    }
+}
+
+impl fmt::Display for RollingPercentile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PERCENTILE({}, {})", self.period, self.quantile)
+    }
+}
+
+/// Input value tagged with its arrival number, so that equal values stay distinguishable.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Item {
+    value: f64,
+    id: u64,
+}
+
+impl PartialEq for Item {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Item {}
+
+impl PartialOrd for Item {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value
+            .total_cmp(&other.value)
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+/// Multiset of values supporting insertion, removal and rank queries in _O(log n)_.
+///
+/// The values are split between a max-heap holding the lower part and a min-heap holding the
+/// upper part. Removed values are only marked and dropped once they reach the top of their heap.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct OrderStatistics {
+    lower: BinaryHeap<Item>,
+    upper: BinaryHeap<Reverse<Item>>,
+    lower_len: usize,
+    upper_len: usize,
+    removed: HashSet<u64>,
+    next_id: u64,
+}
+
+impl OrderStatistics {
+    fn insert(&mut self, value: f64) -> Item {
+        let item = Item {
+            value,
+            id: self.next_id,
+        };
+        self.next_id += 1;
+
+        if self.lower.peek().is_some_and(|top| item <= *top) {
+            self.lower.push(item);
+            self.lower_len += 1;
+        } else {
+            self.upper.push(Reverse(item));
+            self.upper_len += 1;
+        }
+        item
+    }
+
+    fn remove(&mut self, item: Item) {
+        self.removed.insert(item.id);
+        if self.lower.peek().is_some_and(|top| item <= *top) {
+            self.lower_len -= 1;
+        } else {
+            self.upper_len -= 1;
+        }
+        self.prune();
+        self.compact();
+    }
+
+    /// Returns the interpolated value at `quantile` of the sorted values.
+    fn percentile(&mut self, quantile: f64) -> f64 {
+        let len = self.lower_len + self.upper_len;
+        if len == 0 {
+            return f64::NAN;
+        }
+
+        let rank = quantile * (len - 1) as f64;
+        let k = rank.floor() as usize;
+        self.balance(k + 1);
+
+        let low = self.lower.peek().map_or(f64::NAN, |item| item.value);
+        let fraction = rank - k as f64;
+        if fraction > 0.0 {
+            let high = self.upper.peek().map_or(f64::NAN, |item| item.0.value);
+            low + fraction * (high - low)
+        } else {
+            low
+        }
+    }
+
+    /// Moves values between the heaps until the lower one holds `lower_len` values.
+    fn balance(&mut self, lower_len: usize) {
+        while self.lower_len > lower_len {
+            let item = self.lower.pop().unwrap();
+            self.upper.push(Reverse(item));
+            self.lower_len -= 1;
+            self.upper_len += 1;
+            self.prune();
+        }
+        while self.lower_len < lower_len {
+            let Reverse(item) = self.upper.pop().unwrap();
+            self.lower.push(item);
+            self.upper_len -= 1;
+            self.lower_len += 1;
+            self.prune();
+        }
+    }
+
+    /// Drops removed values from the tops of both heaps.
+    fn prune(&mut self) {
+        while let Some(top) = self.lower.peek() {
+            if !self.removed.remove(&top.id) {
+                break;
+            }
+            self.lower.pop();
+        }
+        while let Some(Reverse(top)) = self.upper.peek() {
+            if !self.removed.remove(&top.id) {
+                break;
+            }
+            self.upper.pop();
+        }
+    }
+
+    /// Drops removed values buried inside the heaps once they outnumber the live ones.
+    fn compact(&mut self) {
+        if self.removed.len() > self.lower_len + self.upper_len + 1 {
+            let removed = &mut self.removed;
+            self.lower.retain(|item| !removed.remove(&item.id));
+            self.upper.retain(|item| !removed.remove(&item.0.id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::testing::random_series;
+
+    test_indicator!(RollingPercentile);
+
+    fn naive(window: &[f64], quantile: f64) -> f64 {
+        let mut sorted = window.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let rank = quantile * (sorted.len() - 1) as f64;
+        let k = rank.floor() as usize;
+        let fraction = rank - k as f64;
+        if fraction > 0.0 {
+            sorted[k] + fraction * (sorted[k + 1] - sorted[k])
+        } else {
+            sorted[k]
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RollingPercentile::new(0, 0.5).is_err());
+        assert!(RollingPercentile::new(5, -0.1).is_err());
+        assert!(RollingPercentile::new(5, 1.1).is_err());
+        assert!(RollingPercentile::new(5, f64::NAN).is_err());
+        assert!(RollingPercentile::new(1, 0.0).is_ok());
+        assert!(RollingPercentile::new(5, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut p = RollingPercentile::new(4, 0.25).unwrap();
+        assert_eq!(p.next(4.0), 4.0);
+        assert_eq!(p.next(8.0), 5.0);
+        assert_eq!(p.next(2.0), 3.0);
+        assert_eq!(p.next(6.0), 3.5);
+        assert_eq!(p.next(1.0), 1.75);
+        assert_eq!(p.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let data = random_series(3, 400);
+        for period in [1, 2, 3, 10, 33] {
+            for quantile in [0.0, 0.1, 0.5, 0.75, 1.0] {
+                let mut p = RollingPercentile::new(period, quantile).unwrap();
+                for i in 0..data.len() {
+                    let start = (i + 1).saturating_sub(period);
+                    let expected = naive(&data[start..=i], quantile);
+                    assert_eq!(p.next(data[i]), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let data: Vec<f64> = (0..300).map(|i| (i * 7 % 5) as f64).collect();
+        let mut p = RollingPercentile::new(6, 0.3).unwrap();
+        for i in 0..data.len() {
+            let start = (i + 1).saturating_sub(6);
+            assert_eq!(p.next(data[i]), naive(&data[start..=i], 0.3));
+        }
+    }
+
+    #[test]
+    fn test_trending_input_stays_compact() {
+        let mut p = RollingPercentile::new(5, 0.5).unwrap();
+        for i in 0..10_000 {
+            p.next(i as f64);
+        }
+        assert!(p.statistics.lower.len() + p.statistics.upper.len() <= 2 * 5 + 2);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut p = RollingPercentile::new(3, 1.0).unwrap();
+        assert_eq!(p.next(&Bar::new().close(4)), 4.0);
+        assert_eq!(p.next(&Bar::new().close(9)), 9.0);
+        assert_eq!(p.next(&Bar::new().close(2)), 9.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut p = RollingPercentile::new(3, 0.5).unwrap();
+        p.next(4.0);
+        p.next(10.0);
+
+        p.reset();
+        assert_eq!(p.next(1.0), 1.0);
+        assert_eq!(p.next(3.0), 2.0);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingPercentile::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let p = RollingPercentile::new(10, 0.95).unwrap();
+        assert_eq!(format!("{}", p), "PERCENTILE(10, 0.95)");
+    }
+}
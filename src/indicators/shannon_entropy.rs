@@ -0,0 +1,250 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Shannon entropy of returns.
+///
+/// Quantifies how evenly the returns of the last _period_ inputs are spread: the returns are
+/// sorted into _bins_ equally wide bins between the smallest and the largest return of the
+/// window, and the entropy of the resulting distribution is returned in bits. It is 0 if all the
+/// returns fall into a single bin and at most _log<sub>2</sub>(bins)_ if they are spread evenly
+/// over all bins, which indicates a random market.
+///
+/// Returns 0 until two inputs have been seen. Every update takes _O(period)_ time.
+///
+/// # Formula
+///
+/// _H = -Σ p<sub>i</sub> log<sub>2</sub>(p<sub>i</sub>)_
+///
+/// Where _p<sub>i</sub>_ is the fraction of the returns falling into the i-th bin.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0). Default is 20.
+/// * _bins_ - number of bins (integer greater than 1). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ShannonEntropy;
+/// use ta_panther::Next;
+///
+/// let mut entropy = ShannonEntropy::new(4, 2).unwrap();
+/// entropy.next(100.0);
+/// assert_eq!(entropy.next(110.0), 0.0);
+/// assert_eq!(entropy.next(99.0), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Entropy (information theory), Wikipedia](https://en.wikipedia.org/wiki/Entropy_(information_theory))
+///
+#[doc(alias = "ENTROPY")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShannonEntropy {
+    period: usize,
+    bins: usize,
+    index: usize,
+    count: usize,
+    previous: Option<f64>,
+    deque: Box<[f64]>,
+    histogram: Box<[usize]>,
+}
+
+impl ShannonEntropy {
+    pub fn new(period: usize, bins: usize) -> Result<Self> {
+        if period == 0 || bins < 2 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            bins,
+            index: 0,
+            count: 0,
+            previous: None,
+            deque: ring(0.0, period)?,
+            histogram: ring(0, bins)?,
+        })
+    }
+
+    fn entropy(&mut self) -> f64 {
+        let returns = &self.deque[..self.count];
+        let (min, max) = returns
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &r| {
+                (min.min(r), max.max(r))
+            });
+        let width = max - min;
+        if !(width > 0.0 && width.is_finite()) {
+            return 0.0;
+        }
+
+        self.histogram.iter_mut().for_each(|count| *count = 0);
+        for &r in returns {
+            let bin = ((r - min) / width * self.bins as f64) as usize;
+            self.histogram[bin.min(self.bins - 1)] += 1;
+        }
+
+        let n = self.count as f64;
+        -self
+            .histogram
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / n;
+                p * p.log2()
+            })
+            .sum::<f64>()
+    }
+}
+
+impl Period for ShannonEntropy {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for ShannonEntropy {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for ShannonEntropy {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count) + self.previous.is_none() as usize
+    }
+}
+
+impl Next<f64> for ShannonEntropy {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if let Some(previous) = self.previous.replace(input) {
+            if previous != 0.0 {
+                self.deque[self.index] = input / previous - 1.0;
+                self.index = if self.index + 1 < self.period {
+                    self.index + 1
+                } else {
+                    0
+                };
+                if self.count < self.period {
+                    self.count += 1;
+                }
+            }
+        }
+        self.entropy()
+    }
+}
+
+impl<T: Close> Next<&T> for ShannonEntropy {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ShannonEntropy {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.previous = None;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for ShannonEntropy {
+    fn default() -> Self {
+        Self::new(20, 10).unwrap()
+    }
+}
+
+impl fmt::Display for ShannonEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ENTROPY({}, {})", self.period, self.bins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ShannonEntropy);
+
+    #[test]
+    fn test_new() {
+        assert!(ShannonEntropy::new(0, 10).is_err());
+        assert!(ShannonEntropy::new(10, 1).is_err());
+        assert!(ShannonEntropy::new(1, 2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut entropy = ShannonEntropy::new(4, 3).unwrap();
+        assert_eq!(entropy.next(10.0), 0.0);
+        assert_eq!(entropy.next(11.0), 0.0);
+        assert_eq!(round(entropy.next(9.9)), 1.0);
+        assert_eq!(round(entropy.next(10.89)), 0.918);
+        assert_eq!(round(entropy.next(9.801)), 1.0);
+        assert_eq!(round(entropy.next(10.0)), 1.5);
+        assert_eq!(round(entropy.next(10.5)), 1.5);
+    }
+
+    #[test]
+    fn test_constant_returns() {
+        let mut entropy = ShannonEntropy::new(5, 4).unwrap();
+        for _ in 0..10 {
+            assert_eq!(entropy.next(100.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut entropy = ShannonEntropy::new(3, 2).unwrap();
+        entropy.next(&Bar::new().close(10));
+        entropy.next(&Bar::new().close(12));
+        assert_eq!(entropy.next(&Bar::new().close(11)), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut entropy = ShannonEntropy::new(3, 2).unwrap();
+        entropy.next(10.0);
+        entropy.next(12.0);
+        entropy.next(11.0);
+
+        entropy.reset();
+        assert_eq!(entropy.next(11.0), 0.0);
+        assert_eq!(entropy.next(12.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ShannonEntropy::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let entropy = ShannonEntropy::new(30, 8).unwrap();
+        assert_eq!(format!("{}", entropy), "ENTROPY(30, 8)");
+    }
+}
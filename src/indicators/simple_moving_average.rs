@@ -1,9 +1,15 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::errors::Result;
+use crate::rolling::RollingWindow;
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset, Validate};
 
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use crate::state::ring_values;
+#[cfg(any(feature = "serde", feature = "simd"))]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
 
 /// Simple moving average (SMA).
 ///
@@ -39,55 +45,78 @@ use serde::{Deserialize, Serialize};
 /// * [Simple Moving Average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Simple_moving_average)
 ///
 #[doc(alias = "SMA")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SimpleMovingAverage {
-    period: usize,
-    index: usize,
-    count: usize,
     sum: f64,
-    deque: Box<[f64]>,
+    window: RollingWindow<f64>,
 }
 
 impl SimpleMovingAverage {
     pub fn new(period: usize) -> Result<Self> {
-        match period {
-            0 => Err(TaError::InvalidParameter),
-            _ => Ok(Self {
-                period,
-                index: 0,
-                count: 0,
-                sum: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
-            }),
-        }
+        Ok(Self {
+            sum: 0.0,
+            window: RollingWindow::new(period)?,
+        })
     }
 }
 
 impl Period for SimpleMovingAverage {
     fn period(&self) -> usize {
-        self.period
+        self.window.capacity()
     }
 }
 
-impl Next<f64> for SimpleMovingAverage {
-    type Output = f64;
+impl Validate for SimpleMovingAverage {
+    /// The window is stored as a [RollingWindow] since version 2.
+    const STATE_VERSION: u32 = 2;
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        let old_val = self.deque[self.index];
-        self.deque[self.index] = input;
-
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
+    fn validate(&self) -> Result<()> {
+        self.window.validate()
+    }
 
-        if self.count < self.period {
-            self.count += 1;
+    #[cfg(feature = "serde")]
+    fn deserialize_version<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        if version >= 2 {
+            return Self::deserialize(deserializer);
         }
+        let state = StateV1::deserialize(deserializer)?;
+        let values = ring_values(state.period, state.index, state.count, &state.deque)
+            .map_err(D::Error::custom)?;
+        let mut sma = Self::new(state.period).map_err(D::Error::custom)?;
+        sma.window.extend(values);
+        sma.sum = state.sum;
+        Ok(sma)
+    }
+}
 
+/// State of version 1, a ring buffer of the inputs.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StateV1 {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum: f64,
+    deque: Vec<f64>,
+}
+
+impl IsReady for SimpleMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.window.capacity() - self.window.len()
+    }
+}
+
+impl Next<f64> for SimpleMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let old_val = self.window.push(input).unwrap_or(0.0);
         self.sum = self.sum - old_val + input;
-        self.sum / (self.count as f64)
+        self.sum / (self.window.len() as f64)
     }
 }
 
@@ -99,14 +128,18 @@ impl<T: Close> Next<&T> for SimpleMovingAverage {
     }
 }
 
+impl NextBatch for SimpleMovingAverage {
+    /// Runs the [sma](crate::simd::sma) kernel if no input was seen yet.
+    #[cfg(feature = "simd")]
+    fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+        crate::simd::next_batch(self, input, crate::simd::sma)
+    }
+}
+
 impl Reset for SimpleMovingAverage {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
         self.sum = 0.0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.window.clear();
     }
 }
 
@@ -118,7 +151,7 @@ impl Default for SimpleMovingAverage {
 
 impl fmt::Display for SimpleMovingAverage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "SMA({})", self.period)
+        write!(f, "SMA({})", self.period())
     }
 }
 
@@ -181,4 +214,57 @@ mod tests {
         let sma = SimpleMovingAverage::new(5).unwrap();
         assert_eq!(format!("{}", sma), "SMA(5)");
     }
+
+    #[test]
+    fn test_is_ready() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        assert_eq!(sma.bars_remaining(), 3);
+        sma.next(1.0);
+        sma.next(2.0);
+        assert!(!sma.is_ready());
+        assert_eq!(sma.bars_remaining(), 1);
+        sma.next(3.0);
+        assert!(sma.is_ready());
+        sma.next(4.0);
+        assert_eq!(sma.bars_remaining(), 0);
+
+        sma.reset();
+        assert_eq!(sma.bars_remaining(), 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_migrate() {
+        use crate::state::Versioned;
+
+        let json =
+            r#"{"version":1,"state":{"period":2,"index":1,"count":1,"deque":[4.0,0.0],"sum":4.0}}"#;
+        let Versioned(mut sma) =
+            serde_json::from_str::<Versioned<SimpleMovingAverage>>(json).unwrap();
+        assert_eq!(sma.bars_remaining(), 1);
+        assert_eq!(sma.next(8.0), 6.0);
+        assert_eq!(sma.next(2.0), 5.0);
+
+        // full ring, the next input overwrites index 1
+        let json = r#"{"version":1,"state":{"period":3,"index":1,"count":3,"deque":[6.0,2.0,4.0],"sum":12.0}}"#;
+        let Versioned(mut sma) =
+            serde_json::from_str::<Versioned<SimpleMovingAverage>>(json).unwrap();
+        assert!(sma.is_ready());
+        assert_eq!(sma.next(5.0), 5.0);
+        assert_eq!(sma.next(1.0), 4.0);
+
+        let v1 = (1u32, (3usize, 1usize, 3usize, 12.0, vec![6.0, 2.0, 4.0]));
+        let bytes = bincode::serialize(&v1).unwrap();
+        let mut sma: Versioned<SimpleMovingAverage> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(sma.0.next(5.0), 5.0);
+
+        // round trip of the migrated state
+        let json = serde_json::to_string(&Versioned(sma.0.clone())).unwrap();
+        let mut restored = serde_json::from_str::<Versioned<SimpleMovingAverage>>(&json).unwrap();
+        assert_eq!(restored.0.next(3.0), sma.0.next(3.0));
+
+        let invalid =
+            r#"{"version":1,"state":{"period":2,"index":0,"count":1,"deque":[4.0,0.0],"sum":4.0}}"#;
+        assert!(serde_json::from_str::<Versioned<SimpleMovingAverage>>(invalid).is_err());
+    }
 }
@@ -0,0 +1,204 @@
+use alloc::boxed::Box;
+use core::f64::consts::PI;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{kernel_average, ring, validate_ring};
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Sine weighted moving average (SWMA).
+///
+/// A moving average whose weights follow the first half of a sine wave, so the inputs in the
+/// middle of the window weigh most. It is a smoother alternative to the
+/// [TMA](crate::indicators::TriangularMovingAverage) with a similar lag of half of the period.
+///
+/// The weights are computed once on construction. Until _period_ inputs have been seen, the
+/// weights of the newest inputs are used and renormalized.
+///
+/// # Formula
+///
+/// _SWMA = Σ w<sub>i</sub> p<sub>i</sub> / Σ w<sub>i</sub>_, with
+/// _w<sub>i</sub> = sin(π i / (n + 1))_ for _i = 1 ... n_
+///
+/// Where:
+///
+/// * _n_ - the period
+/// * _p<sub>i</sub>_ - the i-th input of the window, from the oldest to the newest
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::SineWeightedMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut swma = SineWeightedMovingAverage::new(4).unwrap();
+/// swma.next(10.0);
+/// swma.next(11.0);
+/// swma.next(12.0);
+/// assert_eq!(swma.next(13.0), 11.5);
+/// assert_eq!(swma.next(14.0), 12.5);
+/// ```
+///
+#[doc(alias = "SWMA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SineWeightedMovingAverage {
+    period: usize,
+    index: usize,
+    count: usize,
+    kernel: Box<[f64]>,
+    deque: Box<[f64]>,
+}
+
+impl SineWeightedMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: ring(0.0, period)?,
+                kernel: (1..=period)
+                    .map(|i| (PI * i as f64 / (period as f64 + 1.0)).sin())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl Period for SineWeightedMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for SineWeightedMovingAverage {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for SineWeightedMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for SineWeightedMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        kernel_average(&self.kernel, &self.deque, self.index, self.count)
+    }
+}
+
+impl<T: Close> Next<&T> for SineWeightedMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl NextBatch for SineWeightedMovingAverage {}
+
+impl Reset for SineWeightedMovingAverage {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for SineWeightedMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for SineWeightedMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SWMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SineWeightedMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(SineWeightedMovingAverage::new(0).is_err());
+        assert!(SineWeightedMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut swma = SineWeightedMovingAverage::new(3).unwrap();
+        assert_eq!(swma.next(1.0), 1.0);
+        assert_eq!(round(swma.next(5.0)), 2.657);
+        assert_eq!(round(swma.next(2.0)), 2.95);
+        assert_eq!(round(swma.next(8.0)), 4.636);
+        assert_eq!(round(swma.next(4.0)), 5.071);
+        assert_eq!(round(swma.next(6.0)), 5.757);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut swma = SineWeightedMovingAverage::new(2).unwrap();
+        assert_eq!(swma.next(&Bar::new().close(4)), 4.0);
+        assert_eq!(round(swma.next(&Bar::new().close(8))), 6.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut swma = SineWeightedMovingAverage::new(3).unwrap();
+        swma.next(4.0);
+        swma.next(10.0);
+
+        swma.reset();
+        assert_eq!(swma.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        SineWeightedMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let swma = SineWeightedMovingAverage::new(10).unwrap();
+        assert_eq!(format!("{}", swma), "SWMA(10)");
+    }
+}
@@ -0,0 +1,237 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::rolling::RollingMoments;
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use crate::helpers::Moments;
+#[cfg(feature = "serde")]
+use crate::state::ring_values;
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+/// Rolling skewness.
+///
+/// Measures the asymmetry of the distribution of the last _period_ inputs. Positive values mean
+/// a longer right tail (rare large gains when applied to returns), negative values a longer left
+/// tail. Returns 0 until at least two different inputs have been seen.
+///
+/// The central moments are updated incrementally as values enter and leave the window, so every
+/// update is O(1).
+///
+/// # Formula
+///
+/// _SKEW = √n m<sub>3</sub> / m<sub>2</sub><sup>3/2</sup>_
+///
+/// Where:
+///
+/// * _n_ - number of values in the window
+/// * _m<sub>k</sub>_ - sum of _k_-th powers of deviations from the window mean
+///
+/// This is the population (biased) skewness _g<sub>1</sub>_.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Skewness;
+/// use ta_panther::Next;
+///
+/// let mut skew = Skewness::new(4).unwrap();
+/// skew.next(1.0);
+/// skew.next(1.0);
+/// skew.next(1.0);
+/// assert!(skew.next(5.0) > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Skewness, Wikipedia](https://en.wikipedia.org/wiki/Skewness)
+///
+#[doc(alias = "SKEW")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Skewness {
+    moments: RollingMoments,
+}
+
+impl Skewness {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            moments: RollingMoments::with_higher_moments(period)?,
+        })
+    }
+}
+
+impl Period for Skewness {
+    fn period(&self) -> usize {
+        self.moments.capacity()
+    }
+}
+
+impl Validate for Skewness {
+    /// The window is stored as [RollingMoments] since version 2.
+    const STATE_VERSION: u32 = 2;
+
+    fn validate(&self) -> Result<()> {
+        self.moments.validate()
+    }
+
+    #[cfg(feature = "serde")]
+    fn deserialize_version<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        if version >= 2 {
+            return Self::deserialize(deserializer);
+        }
+        let state = StateV1::deserialize(deserializer)?;
+        let values = ring_values(state.period, state.index, state.count, &state.deque)
+            .map_err(D::Error::custom)?;
+        let mut skew = Self::new(state.period).map_err(D::Error::custom)?;
+        for value in values {
+            skew.moments.push(value);
+        }
+        Ok(skew)
+    }
+}
+
+/// State of version 1, a ring buffer of the inputs. The moments are recomputed from the inputs.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StateV1 {
+    period: usize,
+    index: usize,
+    count: usize,
+    #[serde(rename = "moments")]
+    _moments: Moments,
+    deque: Vec<f64>,
+}
+
+impl IsReady for Skewness {
+    fn bars_remaining(&self) -> usize {
+        self.period() - self.moments.len()
+    }
+}
+
+impl Next<f64> for Skewness {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.moments.push(input);
+        self.moments.skewness().unwrap_or(0.0)
+    }
+}
+
+impl<T: Close> Next<&T> for Skewness {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for Skewness {
+    fn reset(&mut self) {
+        self.moments.clear();
+    }
+}
+
+impl Default for Skewness {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for Skewness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SKEW({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Skewness);
+
+    #[test]
+    fn test_new() {
+        assert!(Skewness::new(0).is_err());
+        assert!(Skewness::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut skew = Skewness::new(4).unwrap();
+        assert_eq!(skew.next(1.0), 0.0);
+        assert_eq!(skew.next(2.0), 0.0);
+        assert_eq!(round(skew.next(6.0)), 0.595);
+        assert_eq!(round(skew.next(3.0)), 0.687);
+        assert_eq!(round(skew.next(-4.0)), -0.581);
+        assert_eq!(round(skew.next(3.0)), -0.756);
+    }
+
+    #[test]
+    fn test_constant_input() {
+        let mut skew = Skewness::new(3).unwrap();
+        for &x in &[5.0, 1.0, 7.0, 3.3, 3.3, 3.3, 3.3] {
+            skew.next(x);
+        }
+        assert_eq!(skew.next(3.3), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut skew = Skewness::new(3).unwrap();
+        skew.next(&Bar::new().close(1));
+        skew.next(&Bar::new().close(1));
+        assert!(skew.next(&Bar::new().close(10)) > 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut skew = Skewness::new(3).unwrap();
+        skew.next(1.0);
+        skew.next(9.0);
+
+        skew.reset();
+        assert_eq!(skew.next(4.0), 0.0);
+        assert_eq!(skew.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Skewness::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let skew = Skewness::new(10).unwrap();
+        assert_eq!(format!("{}", skew), "SKEW(10)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_migrate() {
+        use crate::state::Versioned;
+
+        let json = r#"{"version":1,"state":{"period":4,"index":2,"count":4,"moments":{"count":4,"mean":0.0,"m2":0.0,"m3":0.0,"m4":0.0},"deque":[5.0,1.0,2.0,4.0]}}"#;
+        let Versioned(mut restored) = serde_json::from_str::<Versioned<Skewness>>(json).unwrap();
+        let mut skew = Skewness::new(4).unwrap();
+        for input in [2.0, 4.0, 5.0, 1.0] {
+            skew.next(input);
+        }
+        assert!(restored.is_ready());
+        for input in [3.0, 8.0, 1.0] {
+            assert_eq!(restored.next(input), skew.next(input));
+        }
+    }
+}
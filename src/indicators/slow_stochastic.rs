@@ -1,9 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
 use crate::indicators::{ExponentialMovingAverage, FastStochastic};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Slow stochastic oscillator.
@@ -29,6 +30,7 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(stoch.next(55.0).round(), 77.0);
 /// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SlowStochastic {
     fast_stochastic: FastStochastic,
     ema: ExponentialMovingAverage,
@@ -43,6 +45,14 @@ impl SlowStochastic {
     }
 }
 
+impl IsReady for SlowStochastic {
+    fn bars_remaining(&self) -> usize {
+        self.fast_stochastic
+            .bars_remaining()
+            .max(self.ema.bars_remaining())
+    }
+}
+
 impl Next<f64> for SlowStochastic {
     type Output = f64;
 
@@ -107,6 +117,21 @@ mod tests {
         assert_eq!(stoch.next(55.0).round(), 77.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        let mut stoch = SlowStochastic::new(3, 5).unwrap();
+        assert_eq!(stoch.bars_remaining(), 5);
+        for input in 0..4 {
+            stoch.next(input as f64);
+        }
+        assert_eq!(stoch.bars_remaining(), 1);
+        stoch.next(4.0);
+        assert!(stoch.is_ready());
+
+        stoch.reset();
+        assert_eq!(stoch.bars_remaining(), 5);
+    }
+
     #[test]
     fn test_next_with_bars() {
         let test_data = vec![
@@ -0,0 +1,197 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    ExponentialMovingAverage as Ema, SimpleMovingAverage as Sma, WildersMovingAverage as Rma,
+};
+use crate::{IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Smoothing of the averages of the [RSI](crate::indicators::RelativeStrengthIndex), the
+/// [ATR](crate::indicators::AverageTrueRange) and the
+/// [ADX](crate::indicators::AverageDirectionalIndex).
+///
+/// Brokers and charting platforms use different conventions, choosing the same one is necessary
+/// to reproduce their values. Parsed case-insensitively from its [Display](fmt::Display) name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Smoothing {
+    /// [ExponentialMovingAverage] with a smoothing factor of _2 / (period + 1)_.
+    ///
+    /// [ExponentialMovingAverage]: crate::indicators::ExponentialMovingAverage
+    #[default]
+    Ema,
+    /// [WildersMovingAverage] with a smoothing factor of _1 / period_, as originally defined by
+    /// Wilder.
+    ///
+    /// [WildersMovingAverage]: crate::indicators::WildersMovingAverage
+    Wilder,
+    /// [SimpleMovingAverage] of the last _period_ values, as used by Cutler's RSI.
+    ///
+    /// [SimpleMovingAverage]: crate::indicators::SimpleMovingAverage
+    Sma,
+}
+
+impl Smoothing {
+    /// All the smoothings.
+    pub const ALL: [Smoothing; 3] = [Smoothing::Ema, Smoothing::Wilder, Smoothing::Sma];
+
+    fn name(self) -> &'static str {
+        match self {
+            Smoothing::Ema => "EMA",
+            Smoothing::Wilder => "WILDER",
+            Smoothing::Sma => "SMA",
+        }
+    }
+}
+
+impl FromStr for Smoothing {
+    type Err = TaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Smoothing::ALL
+            .iter()
+            .copied()
+            .find(|smoothing| smoothing.name().eq_ignore_ascii_case(s))
+            .ok_or(TaError::InvalidParameter)
+    }
+}
+
+impl fmt::Display for Smoothing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Moving average of a [Smoothing], keeping its latest value.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(super) struct Smoother {
+    average: Average,
+    current: f64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Average {
+    Ema(Ema),
+    Wilder(Rma),
+    Sma(Sma),
+}
+
+impl Smoother {
+    pub(super) fn new(smoothing: Smoothing, period: usize) -> Result<Self> {
+        let average = match smoothing {
+            Smoothing::Ema => Average::Ema(Ema::new(period)?),
+            Smoothing::Wilder => Average::Wilder(Rma::new(period)?),
+            Smoothing::Sma => Average::Sma(Sma::new(period)?),
+        };
+        Ok(Self {
+            average,
+            current: 0.0,
+        })
+    }
+
+    /// Restores an exponential smoother, see [Ema::from_state].
+    pub(super) fn from_ema_state(period: usize, current: f64) -> Result<Self> {
+        Ok(Self {
+            average: Average::Ema(Ema::from_state(period, current)?),
+            current,
+        })
+    }
+
+    pub(super) fn smoothing(&self) -> Smoothing {
+        match self.average {
+            Average::Ema(_) => Smoothing::Ema,
+            Average::Wilder(_) => Smoothing::Wilder,
+            Average::Sma(_) => Smoothing::Sma,
+        }
+    }
+
+    /// Returns the latest value, 0 before the first input.
+    pub(super) fn current(&self) -> f64 {
+        self.current
+    }
+}
+
+impl Period for Smoother {
+    fn period(&self) -> usize {
+        match &self.average {
+            Average::Ema(ema) => ema.period(),
+            Average::Wilder(rma) => rma.period(),
+            Average::Sma(sma) => sma.period(),
+        }
+    }
+}
+
+impl IsReady for Smoother {
+    fn bars_remaining(&self) -> usize {
+        match &self.average {
+            Average::Ema(ema) => ema.bars_remaining(),
+            Average::Wilder(rma) => rma.bars_remaining(),
+            Average::Sma(sma) => sma.bars_remaining(),
+        }
+    }
+}
+
+impl Next<f64> for Smoother {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.current = match &mut self.average {
+            Average::Ema(ema) => ema.next(input),
+            Average::Wilder(rma) => rma.next(input),
+            Average::Sma(sma) => sma.next(input),
+        };
+        self.current
+    }
+}
+
+impl Reset for Smoother {
+    fn reset(&mut self) {
+        self.current = 0.0;
+        match &mut self.average {
+            Average::Ema(ema) => ema.reset(),
+            Average::Wilder(rma) => rma.reset(),
+            Average::Sma(sma) => sma.reset(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("wilder".parse::<Smoothing>().unwrap(), Smoothing::Wilder);
+        assert_eq!("SMA".parse::<Smoothing>().unwrap(), Smoothing::Sma);
+        assert!("RMA".parse::<Smoothing>().is_err());
+        for smoothing in Smoothing::ALL.iter() {
+            assert_eq!(
+                smoothing.to_string().parse::<Smoothing>().unwrap(),
+                *smoothing
+            );
+        }
+    }
+
+    #[test]
+    fn test_smoother() {
+        for &smoothing in Smoothing::ALL.iter() {
+            let mut smoother = Smoother::new(smoothing, 3).unwrap();
+            assert_eq!(smoother.smoothing(), smoothing);
+            assert_eq!(smoother.period(), 3);
+            assert_eq!(smoother.next(2.0), 2.0);
+            let value = smoother.next(5.0);
+            assert_eq!(smoother.current(), value);
+
+            smoother.reset();
+            assert_eq!(smoother.current(), 0.0);
+        }
+        assert!(Smoother::new(Smoothing::Wilder, 0).is_err());
+    }
+}
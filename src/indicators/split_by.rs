@@ -0,0 +1,173 @@
+use core::fmt;
+
+use crate::{Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Split by condition.
+///
+/// Routes every input into one of two wrapped indicators depending on a condition, e.g. the
+/// volume of up days into one moving average and the volume of down days into another. The
+/// inputs are pairs `(input, condition)`. The output holds the latest output of both indicators,
+/// or `None` for an indicator which has not received any input yet.
+///
+/// This is the general mechanism behind indicators comparing up and down moves, like the volume
+/// ratio or the up/down volume ratio.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{SimpleMovingAverage as Sma, SplitBy};
+/// use ta_panther::Next;
+///
+/// // average volume of up days and of down days
+/// let mut volume = SplitBy::new(Sma::new(2).unwrap(), Sma::new(2).unwrap());
+/// volume.next((1000.0, true));
+/// volume.next((400.0, false));
+/// let output = volume.next((2000.0, true));
+/// assert_eq!(output.if_true, Some(1500.0));
+/// assert_eq!(output.if_false, Some(400.0));
+/// ```
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SplitBy<A, B = A, O = f64> {
+    if_true: A,
+    if_false: B,
+    latest: SplitByOutput<O>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SplitByOutput<O> {
+    pub if_true: Option<O>,
+    pub if_false: Option<O>,
+}
+
+impl<A, B, O> SplitBy<A, B, O> {
+    /// Creates the split, routing inputs with a true condition into `if_true` and the others
+    /// into `if_false`.
+    pub fn new(if_true: A, if_false: B) -> Self {
+        Self {
+            if_true,
+            if_false,
+            latest: SplitByOutput {
+                if_true: None,
+                if_false: None,
+            },
+        }
+    }
+
+    /// Returns the indicator receiving the inputs with a true condition.
+    pub fn if_true(&self) -> &A {
+        &self.if_true
+    }
+
+    /// Returns the indicator receiving the inputs with a false condition.
+    pub fn if_false(&self) -> &B {
+        &self.if_false
+    }
+}
+
+impl<A, B, O, T> Next<(T, bool)> for SplitBy<A, B, O>
+where
+    A: Next<T, Output = O>,
+    B: Next<T, Output = O>,
+    O: Clone,
+{
+    type Output = SplitByOutput<O>;
+
+    fn next(&mut self, (input, condition): (T, bool)) -> Self::Output {
+        if condition {
+            self.latest.if_true = Some(self.if_true.next(input));
+        } else {
+            self.latest.if_false = Some(self.if_false.next(input));
+        }
+        self.latest.clone()
+    }
+}
+
+impl<A: Reset, B: Reset, O> Reset for SplitBy<A, B, O> {
+    fn reset(&mut self) {
+        self.if_true.reset();
+        self.if_false.reset();
+        self.latest = SplitByOutput {
+            if_true: None,
+            if_false: None,
+        };
+    }
+}
+
+impl<A: Default, B: Default, O> Default for SplitBy<A, B, O> {
+    fn default() -> Self {
+        Self::new(A::default(), B::default())
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display, O> fmt::Display for SplitBy<A, B, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SPLIT({}, {})", self.if_true, self.if_false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage as Ema, Maximum};
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut split = SplitBy::new(Ema::new(3).unwrap(), Ema::new(3).unwrap());
+
+        let output = split.next((4.0, false));
+        assert_eq!(output.if_true, None);
+        assert_eq!(output.if_false, Some(4.0));
+
+        let output = split.next((2.0, true));
+        assert_eq!(output.if_true, Some(2.0));
+        assert_eq!(output.if_false, Some(4.0));
+
+        let output = split.next((6.0, true));
+        assert_eq!(output.if_true, Some(4.0));
+        assert_eq!(output.if_false, Some(4.0));
+
+        let output = split.next((8.0, false));
+        assert_eq!(output.if_true, Some(4.0));
+        assert_eq!(output.if_false, Some(6.0));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut split = SplitBy::new(Maximum::new(3).unwrap(), Ema::new(3).unwrap());
+        let bar = Bar::new().high(5).close(4);
+        let output = split.next((&bar, true));
+        assert_eq!(output.if_true, Some(5.0));
+        let output = split.next((&bar, false));
+        assert_eq!(output.if_false, Some(4.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut split = SplitBy::new(Ema::new(3).unwrap(), Ema::new(3).unwrap());
+        split.next((4.0, true));
+        split.next((8.0, false));
+
+        split.reset();
+        let output = split.next((1.0, true));
+        assert_eq!(output.if_true, Some(1.0));
+        assert_eq!(output.if_false, None);
+    }
+
+    #[test]
+    fn test_default() {
+        SplitBy::<Ema>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let split = SplitBy::<_, _>::new(Ema::new(5).unwrap(), Maximum::new(3).unwrap());
+        assert_eq!(format!("{}", split), "SPLIT(EMA(5), MAX(3))");
+    }
+}
@@ -1,9 +1,15 @@
-use std::fmt;
+use core::fmt;
 
-use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::errors::Result;
+use crate::rolling::RollingMoments;
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset, Validate};
 
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use crate::state::ring_values;
+#[cfg(any(feature = "serde", feature = "simd"))]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
 
 /// Standard deviation (SD).
 ///
@@ -39,73 +45,88 @@ use serde::{Deserialize, Serialize};
 /// * [Standard Deviation, Wikipedia](https://en.wikipedia.org/wiki/Standard_deviation)
 ///
 #[doc(alias = "SD")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StandardDeviation {
-    period: usize,
-    index: usize,
-    count: usize,
-    m: f64,
-    m2: f64,
-    deque: Box<[f64]>,
+    moments: RollingMoments,
 }
 
 impl StandardDeviation {
     pub fn new(period: usize) -> Result<Self> {
-        match period {
-            0 => Err(TaError::InvalidParameter),
-            _ => Ok(Self {
-                period,
-                index: 0,
-                count: 0,
-                m: 0.0,
-                m2: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
-            }),
-        }
+        Ok(Self {
+            moments: RollingMoments::new(period)?,
+        })
     }
 
     pub(super) fn mean(&self) -> f64 {
-        self.m
+        self.moments.mean()
+    }
+
+    /// Sample standard deviation of the window, with Bessel's correction. 0 for a single value.
+    pub(super) fn sample(&self) -> f64 {
+        self.moments.sample_std_dev()
     }
 }
 
 impl Period for StandardDeviation {
     fn period(&self) -> usize {
-        self.period
+        self.moments.capacity()
     }
 }
 
-impl Next<f64> for StandardDeviation {
-    type Output = f64;
+impl Validate for StandardDeviation {
+    /// The window is stored as [RollingMoments] since version 2.
+    const STATE_VERSION: u32 = 2;
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        let old_val = self.deque[self.index];
-        self.deque[self.index] = input;
-
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
-
-        if self.count < self.period {
-            self.count += 1;
-            let delta = input - self.m;
-            self.m += delta / self.count as f64;
-            let delta2 = input - self.m;
-            self.m2 += delta * delta2;
-        } else {
-            let delta = input - old_val;
-            let old_m = self.m;
-            self.m += delta / self.period as f64;
-            let delta2 = input - self.m + old_val - old_m;
-            self.m2 += delta * delta2;
+    fn validate(&self) -> Result<()> {
+        self.moments.validate()
+    }
+
+    #[cfg(feature = "serde")]
+    fn deserialize_version<'de, D: Deserializer<'de>>(
+        version: u32,
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        if version >= 2 {
+            return Self::deserialize(deserializer);
         }
-        if self.m2 < 0.0 {
-            self.m2 = 0.0;
+        let state = StateV1::deserialize(deserializer)?;
+        let values = ring_values(state.period, state.index, state.count, &state.deque)
+            .map_err(D::Error::custom)?;
+        let mut sd = Self::new(state.period).map_err(D::Error::custom)?;
+        for value in values {
+            sd.moments.push(value);
         }
+        Ok(sd)
+    }
+}
+
+/// State of version 1, a ring buffer of the inputs. The moments are recomputed from the inputs.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StateV1 {
+    period: usize,
+    index: usize,
+    count: usize,
+    #[serde(rename = "m")]
+    _mean: f64,
+    #[serde(rename = "m2")]
+    _m2: f64,
+    deque: Vec<f64>,
+}
 
-        (self.m2 / self.count as f64).sqrt()
+impl IsReady for StandardDeviation {
+    fn bars_remaining(&self) -> usize {
+        self.period() - self.moments.len()
+    }
+}
+
+impl Next<f64> for StandardDeviation {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.moments.push(input);
+        self.moments.std_dev()
     }
 }
 
@@ -117,15 +138,17 @@ impl<T: Close> Next<&T> for StandardDeviation {
     }
 }
 
+impl NextBatch for StandardDeviation {
+    /// Runs the [std_dev](crate::simd::std_dev) kernel if no input was seen yet.
+    #[cfg(feature = "simd")]
+    fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+        crate::simd::next_batch(self, input, crate::simd::std_dev)
+    }
+}
+
 impl Reset for StandardDeviation {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
-        self.m = 0.0;
-        self.m2 = 0.0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.moments.clear();
     }
 }
 
@@ -137,7 +160,7 @@ impl Default for StandardDeviation {
 
 impl fmt::Display for StandardDeviation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "SD({})", self.period)
+        write!(f, "SD({})", self.period())
     }
 }
 
@@ -222,4 +245,20 @@ mod tests {
         let sd = StandardDeviation::new(5).unwrap();
         assert_eq!(format!("{}", sd), "SD(5)");
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_migrate() {
+        use crate::state::Versioned;
+
+        let json = r#"{"version":1,"state":{"period":3,"index":1,"count":3,"m":30.0,"m2":200.0,"deque":[40.0,20.0,30.0]}}"#;
+        let Versioned(mut sd) = serde_json::from_str::<Versioned<StandardDeviation>>(json).unwrap();
+        assert!(sd.is_ready());
+        assert_eq!(round(sd.next(50.0)), 8.165);
+
+        let json = serde_json::to_string(&Versioned(sd.clone())).unwrap();
+        let Versioned(mut restored) =
+            serde_json::from_str::<Versioned<StandardDeviation>>(&json).unwrap();
+        assert_eq!(restored.next(10.0), sd.next(10.0));
+    }
 }
@@ -0,0 +1,178 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{deque, linear_regression, regression_standard_error};
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Standard Error (STDERR).
+///
+/// Measures how far the values of the last `period` inputs deviate from the linear regression
+/// line fitted through them (the same line used by
+/// [LinearRegressionPrediction](crate::indicators::LinearRegressionPrediction)).
+/// The smaller the standard error, the better the line describes the trend.
+///
+/// # Formula
+///
+/// STDERR = sqrt(Σ(p<sub>i</sub> - ŷ<sub>i</sub>)<sup>2</sup> / (n - 2))
+///
+/// Where:
+///
+/// * _p<sub>i</sub>_ - i-th input value in the window
+/// * _ŷ<sub>i</sub>_ - value of the regression line at the i-th position
+/// * _n_ - number of values in the window
+///
+/// Until at least 3 values are available, the output is `0.0`.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::StandardError;
+/// use ta_panther::Next;
+///
+/// let mut se = StandardError::new(3).unwrap();
+/// assert_eq!(se.next(1.0), 0.0);
+/// assert_eq!(se.next(2.0), 0.0);
+/// assert_eq!(se.next(3.0), 0.0);
+/// assert!(se.next(1.0) > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Standard Error, Investopedia](https://www.investopedia.com/terms/s/standard-error.asp)
+///
+#[doc(alias = "STDERR")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StandardError {
+    period: usize,
+    deque: VecDeque<f64>,
+}
+
+impl StandardError {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                deque: deque(period)?,
+            }),
+        }
+    }
+}
+
+impl Period for StandardError {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for StandardError {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.deque.len())
+    }
+}
+
+impl Next<f64> for StandardError {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.deque.len() == self.period {
+            self.deque.pop_front();
+        }
+        self.deque.push_back(input);
+
+        let (slope, intercept) = linear_regression(self.deque.iter().copied());
+        regression_standard_error(self.deque.iter().copied(), slope, intercept)
+    }
+}
+
+impl<T: Close> Next<&T> for StandardError {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for StandardError {
+    fn reset(&mut self) {
+        self.deque.clear();
+    }
+}
+
+impl Default for StandardError {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for StandardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "STDERR({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(StandardError);
+
+    #[test]
+    fn test_new() {
+        assert!(StandardError::new(0).is_err());
+        assert!(StandardError::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut se = StandardError::new(4).unwrap();
+        assert_eq!(se.next(1.0), 0.0);
+        assert_eq!(se.next(3.0), 0.0);
+        assert_eq!(round(se.next(2.0)), 1.225);
+        assert_eq!(round(se.next(4.0)), 0.949);
+        assert_eq!(round(se.next(5.0)), 0.949);
+        assert_eq!(round(se.next(6.0)), 0.387);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut se = StandardError::new(3).unwrap();
+        se.next(&Bar::new().close(2));
+        se.next(&Bar::new().close(4));
+        assert_eq!(se.next(&Bar::new().close(6)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut se = StandardError::new(3).unwrap();
+        se.next(1.0);
+        se.next(5.0);
+        assert!(se.next(2.0) > 0.0);
+
+        se.reset();
+        se.next(1.0);
+        se.next(2.0);
+        assert_eq!(se.next(3.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        StandardError::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let se = StandardError::new(10).unwrap();
+        assert_eq!(format!("{}", se), "STDERR(10)");
+    }
+}
@@ -0,0 +1,277 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{FastStochastic, MaType, MovingAverage};
+use crate::{Close, High, IsReady, Low, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Full stochastic oscillator.
+///
+/// Returns both lines of the stochastic oscillator: %K, the raw
+/// [fast stochastic](crate::indicators::FastStochastic) smoothed over _k_smoothing_ periods, and
+/// %D, the moving average of %K over _d_smoothing_ periods. Covers the common variants:
+///
+/// * fast stochastic - %K is not smoothed, see [fast](Self::fast)
+/// * slow stochastic - %K is smoothed over 3 periods, see [slow](Self::slow)
+/// * full stochastic - every length is chosen, see [new](Self::new)
+///
+/// # Formula
+///
+/// Raw %K = (Close - Lowest Low) / (Highest High - Lowest Low) x 100, over _k_period_
+///
+/// %K = MA<sub>k_smoothing</sub>(Raw %K)
+///
+/// %D = MA<sub>d_smoothing</sub>(%K)
+///
+/// # Parameters
+///
+/// * _k_period_ - number of periods of the raw %K (integer greater than 0). Default is 14.
+/// * _k_smoothing_ - smoothing of %K (integer greater than 0), 1 for no smoothing. Default is 3.
+/// * _d_smoothing_ - smoothing of %D (integer greater than 0). Default is 3.
+/// * _ma_type_ - type of the moving averages. Default is [MaType::Sma].
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::StochasticOscillator;
+/// use ta_panther::Next;
+///
+/// let mut stoch = StochasticOscillator::fast(3, 2).unwrap();
+/// assert_eq!(stoch.next(10.0).k, 50.0);
+/// let out = stoch.next(20.0);
+/// assert_eq!((out.k, out.d), (100.0, 75.0));
+/// let out = stoch.next(15.0);
+/// assert_eq!((out.k, out.d), (50.0, 75.0));
+/// ```
+///
+/// # Links
+///
+/// * [Stochastic oscillator, Wikipedia](https://en.wikipedia.org/wiki/Stochastic_oscillator)
+///
+#[doc(alias = "STOCH")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StochasticOscillator {
+    stochastic: FastStochastic,
+    k_ma: MovingAverage,
+    d_ma: MovingAverage,
+    count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StochasticOscillatorOutput {
+    pub k: f64,
+    pub d: f64,
+}
+
+impl From<StochasticOscillatorOutput> for (f64, f64) {
+    fn from(so: StochasticOscillatorOutput) -> Self {
+        (so.k, so.d)
+    }
+}
+
+impl StochasticOscillator {
+    /// Creates a full stochastic oscillator with simple moving averages.
+    pub fn new(k_period: usize, k_smoothing: usize, d_smoothing: usize) -> Result<Self> {
+        Self::with_ma_type(k_period, k_smoothing, d_smoothing, MaType::Sma)
+    }
+
+    /// Creates a fast stochastic oscillator, whose %K is not smoothed.
+    pub fn fast(k_period: usize, d_smoothing: usize) -> Result<Self> {
+        Self::new(k_period, 1, d_smoothing)
+    }
+
+    /// Creates a slow stochastic oscillator, whose %K is smoothed over 3 periods.
+    pub fn slow(k_period: usize, d_smoothing: usize) -> Result<Self> {
+        Self::new(k_period, 3, d_smoothing)
+    }
+
+    /// Creates a full stochastic oscillator with moving averages of the given type.
+    pub fn with_ma_type(
+        k_period: usize,
+        k_smoothing: usize,
+        d_smoothing: usize,
+        ma_type: MaType,
+    ) -> Result<Self> {
+        Ok(Self {
+            stochastic: FastStochastic::new(k_period)?,
+            k_ma: MovingAverage::new(ma_type, k_smoothing)?,
+            d_ma: MovingAverage::new(ma_type, d_smoothing)?,
+            count: 0,
+        })
+    }
+
+    pub fn k_smoothing(&self) -> usize {
+        self.k_ma.period()
+    }
+
+    pub fn d_smoothing(&self) -> usize {
+        self.d_ma.period()
+    }
+
+    pub fn ma_type(&self) -> MaType {
+        self.k_ma.ma_type()
+    }
+
+    fn smooth(&mut self, raw_k: f64) -> StochasticOscillatorOutput {
+        self.count = self.count.saturating_add(1);
+        let k = self.k_ma.next(raw_k);
+        let d = self.d_ma.next(k);
+        StochasticOscillatorOutput { k, d }
+    }
+}
+
+impl Period for StochasticOscillator {
+    fn period(&self) -> usize {
+        self.stochastic.period()
+    }
+}
+
+impl IsReady for StochasticOscillator {
+    fn bars_remaining(&self) -> usize {
+        (self.period() + self.k_smoothing() + self.d_smoothing() - 2).saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for StochasticOscillator {
+    type Output = StochasticOscillatorOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let raw_k = self.stochastic.next(input);
+        self.smooth(raw_k)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for StochasticOscillator {
+    type Output = StochasticOscillatorOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let raw_k = self.stochastic.next(input);
+        self.smooth(raw_k)
+    }
+}
+
+impl Reset for StochasticOscillator {
+    fn reset(&mut self) {
+        self.stochastic.reset();
+        self.k_ma.reset();
+        self.d_ma.reset();
+        self.count = 0;
+    }
+}
+
+impl Default for StochasticOscillator {
+    fn default() -> Self {
+        Self::new(14, 3, 3).unwrap()
+    }
+}
+
+impl fmt::Display for StochasticOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "STOCH({}, {}, {}",
+            self.period(),
+            self.k_smoothing(),
+            self.d_smoothing()
+        )?;
+        if self.ma_type() != MaType::Sma {
+            write!(f, ", {}", self.ma_type())?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(StochasticOscillator);
+
+    fn lines(stoch: &mut StochasticOscillator, input: f64) -> (f64, f64) {
+        stoch.next(input).into()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(StochasticOscillator::new(0, 1, 1).is_err());
+        assert!(StochasticOscillator::new(1, 0, 1).is_err());
+        assert!(StochasticOscillator::new(1, 1, 0).is_err());
+        assert!(StochasticOscillator::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_fast() {
+        let mut stoch = StochasticOscillator::fast(3, 3).unwrap();
+        let mut fast = FastStochastic::new(3).unwrap();
+        for &input in [10.0, 20.0, 15.0, 30.0, 12.0].iter() {
+            assert_eq!(stoch.next(input).k, fast.next(input));
+        }
+    }
+
+    #[test]
+    fn test_full() {
+        let mut stoch = StochasticOscillator::new(3, 2, 2).unwrap();
+        assert_eq!(
+            stoch.next(10.0),
+            StochasticOscillatorOutput { k: 50.0, d: 50.0 }
+        );
+        // raw %K: 100, 50, 100, 0
+        assert_eq!(lines(&mut stoch, 20.0), (75.0, 62.5));
+        assert_eq!(lines(&mut stoch, 15.0), (75.0, 75.0));
+        assert_eq!(lines(&mut stoch, 30.0), (75.0, 75.0));
+        assert_eq!(lines(&mut stoch, 12.0), (50.0, 62.5));
+        assert!(stoch.is_ready());
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut stoch = StochasticOscillator::slow(2, 1).unwrap();
+        // raw %K: 75, 0, 75
+        stoch.next(&Bar::new().high(30).low(10).close(25));
+        stoch.next(&Bar::new().high(20).low(20).close(10));
+        let out = stoch.next(&Bar::new().high(20).low(12).close(18));
+        assert_eq!(out.k, 50.0);
+        assert_eq!(out.d, 50.0);
+    }
+
+    #[test]
+    fn test_ma_type() {
+        let mut stoch = StochasticOscillator::with_ma_type(3, 2, 2, MaType::Ema).unwrap();
+        assert_eq!(stoch.ma_type(), MaType::Ema);
+        stoch.next(10.0);
+        let out = stoch.next(20.0);
+        assert_eq!(round(out.k), 83.333);
+        assert_eq!(round(out.d), 72.222);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut stoch = StochasticOscillator::new(3, 2, 2).unwrap();
+        assert_eq!(stoch.bars_remaining(), 5);
+        stoch.next(1.0);
+        assert_eq!(stoch.bars_remaining(), 4);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut stoch = StochasticOscillator::new(3, 2, 2).unwrap();
+        stoch.next(10.0);
+        stoch.next(20.0);
+
+        stoch.reset();
+        assert_eq!(lines(&mut stoch, 30.0), (50.0, 50.0));
+    }
+
+    #[test]
+    fn test_display() {
+        let stoch = StochasticOscillator::new(14, 3, 3).unwrap();
+        assert_eq!(format!("{}", stoch), "STOCH(14, 3, 3)");
+        let stoch = StochasticOscillator::with_ma_type(14, 1, 3, MaType::Ema).unwrap();
+        assert_eq!(format!("{}", stoch), "STOCH(14, 1, 3, EMA)");
+    }
+}
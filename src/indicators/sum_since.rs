@@ -0,0 +1,119 @@
+use core::fmt;
+
+use crate::{Close, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Sum since.
+///
+/// Sums the values of the inputs since a condition was last true, e.g. the volume traded since
+/// the session open or the gain since a signal. The inputs are pairs `(value, condition)`. Like
+/// for [BarsSince](crate::indicators::BarsSince), an input where the condition is true starts
+/// a new sum without its own value, so the output is 0 on it. The output is NaN until the
+/// condition has been true once.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::SumSince;
+/// use ta_panther::Next;
+///
+/// let mut volume_since_open = SumSince::new();
+/// assert!(volume_since_open.next((100.0, false)).is_nan());
+/// assert_eq!(volume_since_open.next((200.0, true)), 0.0);
+/// assert_eq!(volume_since_open.next((300.0, false)), 300.0);
+/// assert_eq!(volume_since_open.next((150.0, false)), 450.0);
+/// ```
+///
+#[doc(alias = "SUMSINCE")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SumSince {
+    sum: f64,
+}
+
+impl SumSince {
+    pub fn new() -> Self {
+        Self { sum: f64::NAN }
+    }
+}
+
+impl Next<(f64, bool)> for SumSince {
+    type Output = f64;
+
+    fn next(&mut self, (value, condition): (f64, bool)) -> Self::Output {
+        self.sum = if condition { 0.0 } else { self.sum + value };
+        self.sum
+    }
+}
+
+impl<T: Close> Next<(&T, bool)> for SumSince {
+    type Output = f64;
+
+    fn next(&mut self, (input, condition): (&T, bool)) -> Self::Output {
+        self.next((input.close(), condition))
+    }
+}
+
+impl Reset for SumSince {
+    fn reset(&mut self) {
+        self.sum = f64::NAN;
+    }
+}
+
+impl Default for SumSince {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SumSince {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SUM_SINCE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut sum = SumSince::new();
+        assert!(sum.next((1.0, false)).is_nan());
+        assert_eq!(sum.next((2.0, true)), 0.0);
+        assert_eq!(sum.next((3.0, false)), 3.0);
+        assert_eq!(sum.next((4.0, false)), 7.0);
+        assert_eq!(sum.next((5.0, true)), 0.0);
+        assert_eq!(sum.next((6.0, false)), 6.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut sum = SumSince::new();
+        sum.next((&Bar::new().close(2), true));
+        assert_eq!(sum.next((&Bar::new().close(3), false)), 3.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sum = SumSince::new();
+        sum.next((1.0, true));
+        sum.next((2.0, false));
+
+        sum.reset();
+        assert!(sum.next((3.0, false)).is_nan());
+    }
+
+    #[test]
+    fn test_default() {
+        SumSince::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", SumSince::new()), "SUM_SINCE");
+    }
+}
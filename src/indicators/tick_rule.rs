@@ -0,0 +1,140 @@
+use core::fmt;
+
+use crate::{Close, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Tick rule.
+///
+/// Classifies trades as buyer or seller initiated from their prices alone. A trade at a higher
+/// price than the previous trade (uptick) is a buy and returns 1, a trade at a lower price
+/// (downtick) is a sell and returns -1. A trade at the same price (zero tick) keeps the sign of
+/// the previous trade. The first trade, and zero ticks before any price change, return 0.
+///
+/// The input is the price of a trade, or the close of a bar.
+///
+/// # Formula
+///
+/// _b<sub>t</sub> = sign(p<sub>t</sub> - p<sub>t-1</sub>)_ if _p<sub>t</sub> ≠ p<sub>t-1</sub>_,
+/// otherwise _b<sub>t</sub> = b<sub>t-1</sub>_
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::TickRule;
+/// use ta_panther::Next;
+///
+/// let mut tick_rule = TickRule::new();
+/// assert_eq!(tick_rule.next(10.0), 0.0);
+/// assert_eq!(tick_rule.next(10.5), 1.0);
+/// assert_eq!(tick_rule.next(10.5), 1.0);
+/// assert_eq!(tick_rule.next(10.0), -1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Tick rule, Wikipedia](https://en.wikipedia.org/wiki/Tick_test)
+///
+#[doc(alias = "TickTest")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TickRule {
+    previous: Option<f64>,
+    sign: f64,
+}
+
+impl TickRule {
+    pub fn new() -> Self {
+        Self {
+            previous: None,
+            sign: 0.0,
+        }
+    }
+}
+
+impl Next<f64> for TickRule {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if let Some(previous) = self.previous {
+            if input > previous {
+                self.sign = 1.0;
+            } else if input < previous {
+                self.sign = -1.0;
+            }
+        }
+        self.previous = Some(input);
+        self.sign
+    }
+}
+
+impl<T: Close> Next<&T> for TickRule {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for TickRule {
+    fn reset(&mut self) {
+        self.previous = None;
+        self.sign = 0.0;
+    }
+}
+
+impl Default for TickRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for TickRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TICK_RULE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(TickRule);
+
+    #[test]
+    fn test_next() {
+        let mut tick_rule = TickRule::new();
+        assert_eq!(tick_rule.next(5.0), 0.0);
+        assert_eq!(tick_rule.next(5.0), 0.0);
+        assert_eq!(tick_rule.next(4.0), -1.0);
+        assert_eq!(tick_rule.next(4.0), -1.0);
+        assert_eq!(tick_rule.next(4.5), 1.0);
+        assert_eq!(tick_rule.next(4.5), 1.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut tick_rule = TickRule::new();
+        assert_eq!(tick_rule.next(&Bar::new().close(3)), 0.0);
+        assert_eq!(tick_rule.next(&Bar::new().close(2)), -1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tick_rule = TickRule::new();
+        tick_rule.next(3.0);
+        tick_rule.next(4.0);
+
+        tick_rule.reset();
+        assert_eq!(tick_rule.next(2.0), 0.0);
+        assert_eq!(tick_rule.next(2.0), 0.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let tick_rule = TickRule::new();
+        assert_eq!(format!("{}", tick_rule), "TICK_RULE");
+    }
+}
@@ -0,0 +1,214 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{kernel_average, ring, validate_ring};
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Triangular moving average (TMA).
+///
+/// A moving average whose weights increase linearly towards the middle of the window and
+/// decrease linearly towards its end, which is equivalent to a simple moving average of a simple
+/// moving average. It is smoother than the [SMA](crate::indicators::SimpleMovingAverage) but
+/// lags by half of the period.
+///
+/// The weights are computed once on construction. Until _period_ inputs have been seen, the
+/// weights of the newest inputs are used and renormalized.
+///
+/// # Formula
+///
+/// _TMA = Σ w<sub>i</sub> p<sub>i</sub> / Σ w<sub>i</sub>_, with _w<sub>i</sub> = min(i, n + 1 - i)_
+/// for _i = 1 ... n_
+///
+/// Where:
+///
+/// * _n_ - the period
+/// * _p<sub>i</sub>_ - the i-th input of the window, from the oldest to the newest
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::TriangularMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut tma = TriangularMovingAverage::new(4).unwrap();
+/// tma.next(10.0);
+/// tma.next(11.0);
+/// tma.next(12.0);
+/// assert_eq!(tma.next(13.0), 11.5);
+/// assert_eq!(tma.next(14.0), 12.5);
+/// ```
+///
+/// # Links
+///
+/// * [Triangular moving average, Fidelity](https://www.fidelity.com/learning-center/trading-investing/technical-analysis/technical-indicator-guide/tma)
+///
+#[doc(alias = "TMA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TriangularMovingAverage {
+    period: usize,
+    index: usize,
+    count: usize,
+    kernel: Box<[f64]>,
+    deque: Box<[f64]>,
+}
+
+impl TriangularMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                deque: ring(0.0, period)?,
+                kernel: (0..period)
+                    .map(|i| (i + 1).min(period - i) as f64)
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl Period for TriangularMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for TriangularMovingAverage {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for TriangularMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for TriangularMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        kernel_average(&self.kernel, &self.deque, self.index, self.count)
+    }
+}
+
+impl<T: Close> Next<&T> for TriangularMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl NextBatch for TriangularMovingAverage {}
+
+impl Reset for TriangularMovingAverage {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for TriangularMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for TriangularMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(TriangularMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(TriangularMovingAverage::new(0).is_err());
+        assert!(TriangularMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tma = TriangularMovingAverage::new(4).unwrap();
+        assert_eq!(tma.next(1.0), 1.0);
+        assert_eq!(round(tma.next(5.0)), 2.333);
+        assert_eq!(round(tma.next(2.0)), 2.8);
+        assert_eq!(round(tma.next(8.0)), 3.833);
+        assert_eq!(round(tma.next(4.0)), 4.833);
+        assert_eq!(round(tma.next(6.0)), 5.333);
+
+        let mut tma = TriangularMovingAverage::new(5).unwrap();
+        assert_eq!(tma.next(10.0), 10.0);
+        assert_eq!(round(tma.next(11.0)), 10.333);
+        assert_eq!(round(tma.next(12.0)), 10.667);
+        assert_eq!(round(tma.next(13.0)), 11.25);
+        assert_eq!(round(tma.next(14.0)), 12.0);
+        assert_eq!(round(tma.next(15.0)), 13.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut tma = TriangularMovingAverage::new(2).unwrap();
+        assert_eq!(tma.next(&Bar::new().close(4)), 4.0);
+        assert_eq!(tma.next(&Bar::new().close(8)), 6.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tma = TriangularMovingAverage::new(3).unwrap();
+        tma.next(4.0);
+        tma.next(10.0);
+
+        tma.reset();
+        assert_eq!(tma.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        TriangularMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tma = TriangularMovingAverage::new(10).unwrap();
+        assert_eq!(format!("{}", tma), "TMA(10)");
+    }
+}
@@ -1,8 +1,9 @@
-use std::fmt;
+use core::fmt;
 
 use crate::helpers::max3;
 use crate::{Close, High, Low, Next, Reset};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// The range of a day's trading is simply _high_ - _low_.
@@ -49,7 +50,8 @@ use serde::{Deserialize, Serialize};
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TrueRange {
     prev_close: Option<f64>,
 }
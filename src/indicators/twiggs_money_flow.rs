@@ -0,0 +1,219 @@
+use core::fmt;
+
+use super::chaikin_money_flow::money_flow_multiplier;
+use crate::errors::Result;
+use crate::indicators::ExponentialMovingAverage;
+use crate::{Close, High, IsReady, Low, Next, Period, Reset, Volume};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Twiggs Money Flow (TMF).
+///
+/// Variant of the [Chaikin Money Flow](crate::indicators::ChaikinMoneyFlow) by Colin Twiggs.
+/// The range of a bar is extended to the previous close, like the [true
+/// range](crate::indicators::TrueRange), so gaps are taken into account, and the money flow
+/// volume and the volume are smoothed with Wilder's moving average rather than summed over a
+/// window. Ranges from -1 to 1 like the CMF.
+///
+/// # Formula
+///
+/// True Range High(TRH) = max(High, Previous Close)
+///
+/// True Range Low(TRL) = min(Low, Previous Close)
+///
+/// Money Flow Volume(MFV) = ((Close - TRL) - (TRH - Close)) / (TRH - TRL) x Volume
+///
+/// TMF = EMA<sub>2n-1</sub>(MFV) / EMA<sub>2n-1</sub>(Volume)
+///
+/// Wilder's moving average of period _n_ is an [EMA](crate::indicators::ExponentialMovingAverage)
+/// of period _2n - 1_.
+///
+/// # Parameters
+///
+/// * _period_ - smoothing period of Wilder's moving average (integer greater than 0). Default
+///   is 21.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::TwiggsMoneyFlow;
+/// use ta_panther::{DataItem, Next};
+///
+/// let mut tmf = TwiggsMoneyFlow::new(21).unwrap();
+/// let di = DataItem::builder()
+///             .high(4.0)
+///             .low(2.0)
+///             .close(3.5)
+///             .open(3.0)
+///             .volume(1000.0)
+///             .build().unwrap();
+/// assert_eq!(tmf.next(&di), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Twiggs Money Flow, Incredible Charts](https://www.incrediblecharts.com/indicators/twiggs_money_flow.php)
+///
+#[doc(alias = "TMF")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TwiggsMoneyFlow {
+    period: usize,
+    previous_close: Option<f64>,
+    money_flow_volume: ExponentialMovingAverage,
+    volume: ExponentialMovingAverage,
+}
+
+impl TwiggsMoneyFlow {
+    pub fn new(period: usize) -> Result<Self> {
+        let wilder_period = period.saturating_mul(2).saturating_sub(1);
+        Ok(Self {
+            period,
+            previous_close: None,
+            money_flow_volume: ExponentialMovingAverage::new(wilder_period)?,
+            volume: ExponentialMovingAverage::new(wilder_period)?,
+        })
+    }
+}
+
+impl Period for TwiggsMoneyFlow {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for TwiggsMoneyFlow {
+    fn bars_remaining(&self) -> usize {
+        self.volume.bars_remaining()
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for TwiggsMoneyFlow {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> f64 {
+        let (high, low) = match self.previous_close {
+            Some(previous_close) => (
+                input.high().max(previous_close),
+                input.low().min(previous_close),
+            ),
+            None => (input.high(), input.low()),
+        };
+        self.previous_close = Some(input.close());
+
+        let money_flow_volume = self
+            .money_flow_volume
+            .next(money_flow_multiplier(high, low, input.close()) * input.volume());
+        let volume = self.volume.next(input.volume());
+
+        if volume > 0.0 {
+            money_flow_volume / volume
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for TwiggsMoneyFlow {
+    fn default() -> Self {
+        Self::new(21).unwrap()
+    }
+}
+
+impl fmt::Display for TwiggsMoneyFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TMF({})", self.period)
+    }
+}
+
+impl Reset for TwiggsMoneyFlow {
+    fn reset(&mut self) {
+        self.previous_close = None;
+        self.money_flow_volume.reset();
+        self.volume.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(TwiggsMoneyFlow::new(0).is_err());
+        assert!(TwiggsMoneyFlow::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_bar() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        let bar1 = Bar::new().high(4).low(2).close(4).volume(100.0);
+        assert_eq!(tmf.next(&bar1), 1.0);
+
+        // the gap down extends the range up to the previous close
+        let bar2 = Bar::new().high(3).low(2).close(3).volume(100.0);
+        assert_eq!(tmf.next(&bar2), 0.5);
+
+        let bar3 = Bar::new().high(3).low(3).close(3).volume(0.0);
+        assert_eq!(tmf.next(&bar3), 0.5);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(4).low(2).close(3).volume(100.0);
+        let mut tmf = TwiggsMoneyFlow::new(3).unwrap();
+        assert_eq!(tmf.bars_remaining(), 5);
+        for _ in 0..4 {
+            tmf.next(&bar);
+        }
+        assert_eq!(tmf.bars_remaining(), 1);
+        tmf.next(&bar);
+        assert!(tmf.is_ready());
+
+        tmf.reset();
+        assert_eq!(tmf.bars_remaining(), 5);
+    }
+
+    #[test]
+    fn test_period_of_one() {
+        let mut tmf = TwiggsMoneyFlow::new(1).unwrap();
+        tmf.next(&Bar::new().high(4).low(2).close(4).volume(100.0));
+        assert_eq!(
+            tmf.next(&Bar::new().high(4).low(2).close(2).volume(1.0)),
+            -1.0
+        );
+    }
+
+    #[test]
+    fn test_no_volume() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+        assert_eq!(tmf.next(&Bar::new().high(4).low(2).close(4)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+        let bar1 = Bar::new().high(4).low(2).close(4).volume(100.0);
+        let bar2 = Bar::new().high(3).low(2).close(2).volume(300.0);
+
+        tmf.next(&bar1);
+        tmf.next(&bar2);
+
+        tmf.reset();
+        assert_eq!(tmf.next(&bar2), -1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        TwiggsMoneyFlow::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tmf = TwiggsMoneyFlow::new(21).unwrap();
+        assert_eq!(format!("{}", tmf), "TMF(21)");
+    }
+}
@@ -0,0 +1,102 @@
+use core::fmt;
+
+use crate::{Close, High, Low, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Typical price (TYPPRICE).
+///
+/// Returns the average of the high, low and close prices of a bar, the price most
+/// volume-based indicators are computed from. Stateless, it transforms bars into the single price consumed by
+/// the indicators of close prices, in front of which it is chained.
+///
+/// # Formula
+///
+/// TP = (High + Low + Close) / 3
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{TypicalPrice, SimpleMovingAverage};
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64, close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut tp = TypicalPrice::new();
+/// assert_eq!(tp.next(&bar(13.0, 8.0, 12.0)), 11.0);
+///
+/// // chained in front of an SMA
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// sma.next(tp.next(&bar(13.0, 8.0, 12.0)));
+/// assert_eq!(sma.next(tp.next(&bar(15.0, 9.0, 15.0))), 12.0);
+/// ```
+///
+/// # Links
+///
+/// * [Typical price, Wikipedia](https://en.wikipedia.org/wiki/Typical_price)
+///
+#[doc(alias = "TYPPRICE")]
+#[doc(alias = "HLC3")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypicalPrice {}
+
+impl TypicalPrice {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for TypicalPrice {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        (input.high() + input.low() + input.close()) / 3.0
+    }
+}
+
+impl Reset for TypicalPrice {
+    fn reset(&mut self) {}
+}
+
+impl fmt::Display for TypicalPrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("TYPPRICE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut tp = TypicalPrice::new();
+        assert_eq!(tp.next(&Bar::new().high(13).low(8).close(12)), 11.0);
+        assert_eq!(tp.next(&Bar::new().high(4).low(4).close(4)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tp = TypicalPrice::default();
+        tp.next(&Bar::new().high(13).low(8).close(12));
+        tp.reset();
+        assert_eq!(tp.next(&Bar::new().high(13).low(8).close(12)), 11.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", TypicalPrice::new()), "TYPPRICE");
+    }
+}
@@ -0,0 +1,212 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{ring, validate_ring};
+use crate::indicators::Maximum;
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ulcer index (UI).
+///
+/// A volatility measure which only takes downside moves into account: the root mean square of
+/// the percentage drawdowns of the last _period_ inputs, each measured from the highest input of
+/// the _period_ inputs preceding it. Unlike the standard deviation, it is not increased by rises,
+/// and deep or long drawdowns increase it more than short dips.
+///
+/// # Formula
+///
+/// _R<sub>t</sub> = 100 × (C<sub>t</sub> - max(C<sub>t-n+1</sub> ... C<sub>t</sub>)) / max(C<sub>t-n+1</sub> ... C<sub>t</sub>)_
+///
+/// _UI = √(Σ R<sub>i</sub><sup>2</sup> / n)_
+///
+/// Where _n_ is the period.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::UlcerIndex;
+/// use ta_panther::Next;
+///
+/// let mut ui = UlcerIndex::new(2).unwrap();
+/// assert_eq!(ui.next(10.0), 0.0);
+/// assert_eq!(ui.next(11.0), 0.0);
+/// assert_eq!(ui.next(11.0), 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Ulcer index, Wikipedia](https://en.wikipedia.org/wiki/Ulcer_index)
+///
+#[doc(alias = "UI")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UlcerIndex {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum: f64,
+    max: Maximum,
+    deque: Box<[f64]>,
+}
+
+impl UlcerIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                sum: 0.0,
+                max: Maximum::new(period)?,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for UlcerIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for UlcerIndex {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for UlcerIndex {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for UlcerIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let max = self.max.next(input);
+        let drawdown = 100.0 * (input - max) / max;
+        let squared = drawdown * drawdown;
+
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            self.sum -= self.deque[self.index];
+        }
+        self.sum += squared;
+
+        self.deque[self.index] = squared;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        // the sum of squares can drift slightly below 0
+        (self.sum / self.count as f64).max(0.0).sqrt()
+    }
+}
+
+impl<T: Close> Next<&T> for UlcerIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for UlcerIndex {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        self.max.reset();
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for UlcerIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for UlcerIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UI({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(UlcerIndex);
+
+    #[test]
+    fn test_new() {
+        assert!(UlcerIndex::new(0).is_err());
+        assert!(UlcerIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ui = UlcerIndex::new(3).unwrap();
+        assert_eq!(ui.next(10.0), 0.0);
+        assert_eq!(round(ui.next(9.0)), 7.071);
+        assert_eq!(round(ui.next(11.0)), 5.774);
+        assert_eq!(round(ui.next(8.0)), 16.771);
+        assert_eq!(round(ui.next(12.0)), 15.746);
+        assert_eq!(round(ui.next(11.0)), 16.465);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ui = UlcerIndex::new(2).unwrap();
+        ui.next(&Bar::new().close(10));
+        assert_eq!(round(ui.next(&Bar::new().close(8))), 14.142);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ui = UlcerIndex::new(3).unwrap();
+        ui.next(10.0);
+        ui.next(5.0);
+
+        ui.reset();
+        assert_eq!(ui.next(4.0), 0.0);
+        assert_eq!(ui.next(4.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        UlcerIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ui = UlcerIndex::new(10).unwrap();
+        assert_eq!(format!("{}", ui), "UI(10)");
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, validate_ring};
+use crate::indicators::UlcerIndex;
+use crate::{Close, IsReady, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ulcer performance index (UPI), also known as the Martin ratio.
+///
+/// A risk-adjusted return like the Sharpe ratio, which uses the [UlcerIndex] instead of the
+/// standard deviation as the measure of risk, so only drawdowns are penalized. The return is the
+/// percentage change from the oldest to the newest of the last _period_ inputs.
+///
+/// Returns 0 while the window has no drawdown, where the ratio is undefined.
+///
+/// # Formula
+///
+/// _UPI = 100 × (C<sub>t</sub> / C<sub>t-n+1</sub> - 1) / UI(n)_
+///
+/// Where _n_ is the period.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::UlcerPerformanceIndex;
+/// use ta_panther::Next;
+///
+/// let mut upi = UlcerPerformanceIndex::new(2).unwrap();
+/// assert_eq!(upi.next(10.0), 0.0);
+/// assert_eq!(upi.next(12.0), 0.0);
+/// assert!(upi.next(9.0) < 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Ulcer index, Wikipedia](https://en.wikipedia.org/wiki/Ulcer_index)
+///
+#[doc(alias = "UPI")]
+#[doc(alias = "MARTIN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UlcerPerformanceIndex {
+    period: usize,
+    index: usize,
+    count: usize,
+    ui: UlcerIndex,
+    deque: Box<[f64]>,
+}
+
+impl UlcerPerformanceIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                ui: UlcerIndex::new(period)?,
+                deque: ring(0.0, period)?,
+            }),
+        }
+    }
+}
+
+impl Period for UlcerPerformanceIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for UlcerPerformanceIndex {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for UlcerPerformanceIndex {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for UlcerPerformanceIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let ui = self.ui.next(input);
+
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        let oldest = self.deque[(self.index + self.period - self.count) % self.period];
+        if ui == 0.0 {
+            0.0
+        } else {
+            100.0 * (input / oldest - 1.0) / ui
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for UlcerPerformanceIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for UlcerPerformanceIndex {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.ui.reset();
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for UlcerPerformanceIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for UlcerPerformanceIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UPI({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(UlcerPerformanceIndex);
+
+    #[test]
+    fn test_new() {
+        assert!(UlcerPerformanceIndex::new(0).is_err());
+        assert!(UlcerPerformanceIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut upi = UlcerPerformanceIndex::new(3).unwrap();
+        assert_eq!(upi.next(10.0), 0.0);
+        assert_eq!(round(upi.next(9.0)), -1.414);
+        assert_eq!(round(upi.next(11.0)), 1.732);
+        assert_eq!(round(upi.next(8.0)), -0.663);
+        assert_eq!(round(upi.next(12.0)), 0.577);
+        assert_eq!(round(upi.next(11.0)), 2.278);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut upi = UlcerPerformanceIndex::new(2).unwrap();
+        upi.next(&Bar::new().close(10));
+        assert_eq!(round(upi.next(&Bar::new().close(8))), -1.414);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut upi = UlcerPerformanceIndex::new(3).unwrap();
+        upi.next(10.0);
+        upi.next(5.0);
+
+        upi.reset();
+        assert_eq!(upi.next(4.0), 0.0);
+        assert_eq!(upi.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        UlcerPerformanceIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let upi = UlcerPerformanceIndex::new(10).unwrap();
+        assert_eq!(format!("{}", upi), "UPI(10)");
+    }
+}
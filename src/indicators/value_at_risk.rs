@@ -0,0 +1,170 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::RollingPercentile;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Historical value at risk (VaR).
+///
+/// Returns the loss which the last _period_ returns only exceeded with a probability of
+/// _1 - confidence_, estimated by historical simulation: it is the negated
+/// _(1 - confidence)_-percentile of the window, interpolated like the [RollingPercentile]. Losses
+/// are positive, so a VaR of 0.02 means a loss of 2%. A negative VaR means that even the worst
+/// returns of the window are gains.
+///
+/// The inputs are returns, e.g. `0.01` for a gain of 1%. Every update takes _O(log period)_ time.
+///
+/// # Formula
+///
+/// _VaR = -P<sub>1 - confidence</sub>(r<sub>t-n+1</sub> ... r<sub>t</sub>)_
+///
+/// # Parameters
+///
+/// * _confidence_ - confidence level (between 0.0 and 1.0, exclusive). Default is 0.95.
+/// * _period_ - number of returns (integer greater than 0). Default is 100.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ValueAtRisk;
+/// use ta_panther::Next;
+///
+/// let mut var = ValueAtRisk::new(0.75, 5).unwrap();
+/// for &r in [0.01, -0.02, 0.03, -0.04].iter() {
+///     var.next(r);
+/// }
+/// assert_eq!(var.next(0.0), 0.02);
+/// ```
+///
+/// # Links
+///
+/// * [Value at risk, Wikipedia](https://en.wikipedia.org/wiki/Value_at_risk)
+///
+#[doc(alias = "VAR")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ValueAtRisk {
+    confidence: f64,
+    percentile: RollingPercentile,
+}
+
+impl ValueAtRisk {
+    pub fn new(confidence: f64, period: usize) -> Result<Self> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            confidence,
+            percentile: RollingPercentile::new(period, 1.0 - confidence)?,
+        })
+    }
+
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+}
+
+impl Period for ValueAtRisk {
+    fn period(&self) -> usize {
+        self.percentile.period()
+    }
+}
+
+impl IsReady for ValueAtRisk {
+    fn bars_remaining(&self) -> usize {
+        self.percentile.bars_remaining()
+    }
+}
+
+impl Next<f64> for ValueAtRisk {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        -self.percentile.next(input)
+    }
+}
+
+impl<T: Close> Next<&T> for ValueAtRisk {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ValueAtRisk {
+    fn reset(&mut self) {
+        self.percentile.reset();
+    }
+}
+
+impl Default for ValueAtRisk {
+    fn default() -> Self {
+        Self::new(0.95, 100).unwrap()
+    }
+}
+
+impl fmt::Display for ValueAtRisk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VAR({}, {})", self.confidence, self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ValueAtRisk);
+
+    #[test]
+    fn test_new() {
+        assert!(ValueAtRisk::new(0.95, 0).is_err());
+        assert!(ValueAtRisk::new(0.0, 10).is_err());
+        assert!(ValueAtRisk::new(1.0, 10).is_err());
+        assert!(ValueAtRisk::new(f64::NAN, 10).is_err());
+        assert!(ValueAtRisk::new(0.99, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut var = ValueAtRisk::new(0.8, 5).unwrap();
+        assert_eq!(round(var.next(0.01)), -0.01);
+        assert_eq!(round(var.next(-0.02)), 0.014);
+        assert_eq!(round(var.next(0.03)), 0.008);
+        assert_eq!(round(var.next(-0.05)), 0.032);
+        assert_eq!(round(var.next(0.0)), 0.026);
+        assert_eq!(round(var.next(0.02)), 0.026);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut var = ValueAtRisk::new(0.5, 2).unwrap();
+        var.next(&Bar::new().close(-0.01));
+        assert_eq!(round(var.next(&Bar::new().close(-0.03))), 0.02);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut var = ValueAtRisk::new(0.9, 3).unwrap();
+        var.next(-0.1);
+        var.next(0.1);
+
+        var.reset();
+        assert_eq!(var.next(0.05), -0.05);
+    }
+
+    #[test]
+    fn test_default() {
+        ValueAtRisk::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let var = ValueAtRisk::new(0.99, 250).unwrap();
+        assert_eq!(format!("{}", var), "VAR(0.99, 250)");
+    }
+}
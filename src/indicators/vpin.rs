@@ -0,0 +1,254 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, validate_ring};
+use crate::indicators::TickRule;
+use crate::{Close, IsReady, Next, Period, Reset, Validate, Volume};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume-synchronized probability of informed trading (VPIN).
+///
+/// Measures the toxicity of the order flow in volume time rather than clock time. Trades are
+/// collected into buckets of equal volume, their volume is classified as bought or sold by the
+/// [TickRule], and the VPIN is the average absolute imbalance between bought and sold volume
+/// over the last _period_ buckets, relative to the bucket volume. Ranges from 0, when buys and
+/// sells balance in every bucket, to 1, when every bucket is one-sided.
+///
+/// Trades larger than the space left in a bucket are split over the following buckets. The
+/// output changes only when a bucket is completed and is 0 before the first one.
+///
+/// The input is a `(price, volume)` tuple of a trade, or a bar with its close and volume.
+///
+/// # Formula
+///
+/// _VPIN = Σ |V<sup>buy</sup><sub>τ</sub> - V<sup>sell</sup><sub>τ</sub>| / (n * V)_
+///
+/// Where:
+///
+/// * _V<sup>buy</sup><sub>τ</sub>_, _V<sup>sell</sup><sub>τ</sub>_ - volume bought and sold
+///   in bucket _τ_
+/// * _n_ - number of buckets
+/// * _V_ - volume of a bucket
+///
+/// # Parameters
+///
+/// * _period_ - number of buckets (integer greater than 0). Default is 50.
+/// * _bucket_volume_ - volume of a bucket (greater than 0). Default is 1000.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::Vpin;
+/// use ta_panther::Next;
+///
+/// let mut vpin = Vpin::new(2, 100.0).unwrap();
+/// assert_eq!(vpin.next((10.0, 50.0)), 0.0);
+/// // the buy completes the first bucket with 50 bought and 50 unclassified
+/// assert_eq!(vpin.next((11.0, 100.0)), 0.5);
+/// assert_eq!(vpin.next((10.0, 100.0)), 0.25);
+/// assert_eq!(vpin.next((9.0, 50.0)), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Flow Toxicity and Liquidity in a High-frequency World, Easley, López de Prado and O'Hara](https://doi.org/10.1093/rfs/hhs053)
+///
+#[doc(alias = "VPIN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vpin {
+    period: usize,
+    bucket_volume: f64,
+    tick_rule: TickRule,
+    filled: f64,
+    imbalance: f64,
+    index: usize,
+    count: usize,
+    sum: f64,
+    deque: Box<[f64]>,
+}
+
+impl Vpin {
+    pub fn new(period: usize, bucket_volume: f64) -> Result<Self> {
+        if period == 0 || !bucket_volume.is_finite() || bucket_volume <= 0.0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            bucket_volume,
+            tick_rule: TickRule::new(),
+            filled: 0.0,
+            imbalance: 0.0,
+            index: 0,
+            count: 0,
+            sum: 0.0,
+            deque: ring(0.0, period)?,
+        })
+    }
+
+    fn complete_bucket(&mut self) {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            self.sum -= self.deque[self.index];
+        }
+
+        let imbalance = self.imbalance.abs();
+        self.sum += imbalance;
+        self.deque[self.index] = imbalance;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.filled = 0.0;
+        self.imbalance = 0.0;
+    }
+}
+
+impl Period for Vpin {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Validate for Vpin {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for Vpin {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<(f64, f64)> for Vpin {
+    type Output = f64;
+
+    fn next(&mut self, (price, volume): (f64, f64)) -> Self::Output {
+        let sign = self.tick_rule.next(price);
+
+        let mut remaining = volume;
+        while remaining > 0.0 {
+            let space = self.bucket_volume - self.filled;
+            if remaining < space {
+                self.filled += remaining;
+                self.imbalance += sign * remaining;
+                break;
+            }
+            self.imbalance += sign * space;
+            remaining -= space;
+            self.complete_bucket();
+        }
+
+        if self.count > 0 {
+            self.sum / (self.count as f64 * self.bucket_volume)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for Vpin {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.close(), input.volume()))
+    }
+}
+
+impl Reset for Vpin {
+    fn reset(&mut self) {
+        self.tick_rule.reset();
+        self.filled = 0.0;
+        self.imbalance = 0.0;
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for Vpin {
+    fn default() -> Self {
+        Self::new(50, 1000.0).unwrap()
+    }
+}
+
+impl fmt::Display for Vpin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VPIN({}, {})", self.period, self.bucket_volume)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Vpin::new(0, 100.0).is_err());
+        assert!(Vpin::new(1, 0.0).is_err());
+        assert!(Vpin::new(1, f64::NAN).is_err());
+        assert!(Vpin::new(1, 100.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vpin = Vpin::new(2, 10.0).unwrap();
+        assert_eq!(vpin.next((5.0, 4.0)), 0.0);
+        assert_eq!(vpin.next((6.0, 4.0)), 0.0);
+        assert!(!vpin.is_ready());
+
+        // completes the first bucket with 6 bought and 4 unclassified
+        assert_eq!(vpin.next((6.0, 2.0)), 0.6);
+        // large trades fill several buckets
+        assert_eq!(vpin.next((5.0, 25.0)), 1.0);
+        assert!(vpin.is_ready());
+        assert_eq!(vpin.next((6.0, 5.0)), 0.5);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut vpin = Vpin::new(2, 10.0).unwrap();
+        vpin.next(&Bar::new().close(4).volume(5.0));
+        assert_eq!(vpin.next(&Bar::new().close(3).volume(5.0)), 0.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vpin = Vpin::new(2, 10.0).unwrap();
+        vpin.next((5.0, 5.0));
+        vpin.next((6.0, 10.0));
+
+        vpin.reset();
+        assert_eq!(vpin.next((4.0, 5.0)), 0.0);
+        assert_eq!(vpin.next((3.0, 5.0)), 0.5);
+    }
+
+    #[test]
+    fn test_default() {
+        Vpin::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vpin = Vpin::new(50, 1500.0).unwrap();
+        assert_eq!(format!("{}", vpin), "VPIN(50, 1500)");
+    }
+}
@@ -0,0 +1,100 @@
+use core::fmt;
+
+use crate::{Close, High, Low, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Weighted close price (WCLPRICE).
+///
+/// Returns the average of the high, low and close prices of a bar, the close being weighted
+/// twice. Stateless, it transforms bars into the single price consumed by
+/// the indicators of close prices, in front of which it is chained.
+///
+/// # Formula
+///
+/// WCL = (High + Low + 2 x Close) / 4
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{WeightedClose, SimpleMovingAverage};
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64, close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut wcl = WeightedClose::new();
+/// assert_eq!(wcl.next(&bar(13.0, 8.0, 12.0)), 11.25);
+///
+/// // chained in front of an SMA
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// sma.next(wcl.next(&bar(13.0, 8.0, 12.0)));
+/// assert_eq!(sma.next(wcl.next(&bar(15.0, 9.0, 15.0))), 12.375);
+/// ```
+///
+/// # Links
+///
+#[doc(alias = "WCLPRICE")]
+#[doc(alias = "HLCC4")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedClose {}
+
+impl WeightedClose {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for WeightedClose {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        (input.high() + input.low() + 2.0 * input.close()) / 4.0
+    }
+}
+
+impl Reset for WeightedClose {
+    fn reset(&mut self) {}
+}
+
+impl fmt::Display for WeightedClose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("WCLPRICE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut wcl = WeightedClose::new();
+        assert_eq!(wcl.next(&Bar::new().high(13).low(8).close(12)), 11.25);
+        assert_eq!(wcl.next(&Bar::new().high(4).low(4).close(4)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut wcl = WeightedClose::default();
+        wcl.next(&Bar::new().high(13).low(8).close(12));
+        wcl.reset();
+        assert_eq!(wcl.next(&Bar::new().high(13).low(8).close(12)), 11.25);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", WeightedClose::new()), "WCLPRICE");
+    }
+}
@@ -0,0 +1,189 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, WeightedMoments};
+use crate::{Close, IsReady, Next, Period, Reset, Volume};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling weighted mean.
+///
+/// Returns the mean of the last _period_ values, each weighted by its own weight. The inputs are
+/// pairs `(value, weight)`, which allows for volume weighted, liquidity weighted or any other
+/// weighting supplied by the user. Weights should not be negative. Returns NaN while the weights
+/// of the window sum up to zero.
+///
+/// For bars the close price is weighted by the volume, which gives a rolling volume weighted
+/// average price.
+///
+/// # Formula
+///
+/// _WMEAN = Σ w<sub>i</sub> x<sub>i</sub> / Σ w<sub>i</sub>_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::WeightedMean;
+/// use ta_panther::Next;
+///
+/// let mut mean = WeightedMean::new(2).unwrap();
+/// assert_eq!(mean.next((10.0, 100.0)), 10.0);
+/// assert_eq!(mean.next((20.0, 300.0)), 17.5);
+/// assert_eq!(mean.next((30.0, 100.0)), 22.5);
+/// ```
+///
+/// # Links
+///
+/// * [Weighted arithmetic mean, Wikipedia](https://en.wikipedia.org/wiki/Weighted_arithmetic_mean)
+///
+#[doc(alias = "WMEAN")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedMean {
+    period: usize,
+    index: usize,
+    count: usize,
+    moments: WeightedMoments,
+    deque: Box<[(f64, f64)]>,
+}
+
+impl WeightedMean {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                moments: WeightedMoments::default(),
+                deque: ring((0.0, 0.0), period)?,
+            }),
+        }
+    }
+}
+
+impl Period for WeightedMean {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for WeightedMean {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<(f64, f64)> for WeightedMean {
+    type Output = f64;
+
+    fn next(&mut self, (value, weight): (f64, f64)) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let (old_value, old_weight) = self.deque[self.index];
+            self.moments.pop(old_value, old_weight);
+        }
+        self.moments.push(value, weight);
+
+        self.deque[self.index] = (value, weight);
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.moments.mean()
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for WeightedMean {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.close(), input.volume()))
+    }
+}
+
+impl Reset for WeightedMean {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.moments = WeightedMoments::default();
+        for i in 0..self.period {
+            self.deque[i] = (0.0, 0.0);
+        }
+    }
+}
+
+impl Default for WeightedMean {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for WeightedMean {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WMEAN({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(WeightedMean::new(0).is_err());
+        assert!(WeightedMean::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mean = WeightedMean::new(3).unwrap();
+        assert_eq!(mean.next((2.0, 1.0)), 2.0);
+        assert_eq!(mean.next((4.0, 3.0)), 3.5);
+        assert_eq!(mean.next((6.0, 0.0)), 3.5);
+        assert_eq!(round(mean.next((1.0, 2.0))), 2.8);
+        assert_eq!(mean.next((5.0, 0.0)), 1.0);
+        assert_eq!(mean.next((3.0, 0.0)), 1.0);
+        assert!(mean.next((7.0, 0.0)).is_nan());
+        assert_eq!(mean.next((7.0, 1.0)), 7.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut mean = WeightedMean::new(2).unwrap();
+        mean.next(&Bar::new().close(10).volume(1.0));
+        let output = mean.next(&Bar::new().close(20).volume(4.0));
+        assert_eq!(output, 18.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mean = WeightedMean::new(3).unwrap();
+        mean.next((4.0, 1.0));
+        mean.next((8.0, 1.0));
+
+        mean.reset();
+        assert_eq!(mean.next((1.0, 1.0)), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        WeightedMean::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let mean = WeightedMean::new(10).unwrap();
+        assert_eq!(format!("{}", mean), "WMEAN(10)");
+    }
+}
@@ -1,8 +1,13 @@
-use std::fmt;
+use alloc::boxed::Box;
+#[cfg(feature = "simd")]
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::{ring, validate_ring};
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset, Validate};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Weighted moving average (WMA).
@@ -37,9 +42,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Weighted moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Weighted_moving_average)
 ///
-
 #[doc(alias = "WMA")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WeightedMovingAverage {
     period: usize,
     index: usize,
@@ -61,7 +66,7 @@ impl WeightedMovingAverage {
                 weight: 0.0,
                 sum: 0.0,
                 sum_flat: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                deque: ring(0.0, period)?,
             }),
         }
     }
@@ -73,6 +78,24 @@ impl Period for WeightedMovingAverage {
     }
 }
 
+impl Validate for WeightedMovingAverage {
+    fn validate(&self) -> Result<()> {
+        validate_ring(
+            self.period,
+            &self.deque,
+            self.index,
+            self.count,
+            self.period,
+        )
+    }
+}
+
+impl IsReady for WeightedMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
 impl Next<f64> for WeightedMovingAverage {
     type Output = f64;
 
@@ -106,6 +129,14 @@ impl<T: Close> Next<&T> for WeightedMovingAverage {
     }
 }
 
+impl NextBatch for WeightedMovingAverage {
+    /// Runs the [wma](crate::simd::wma) kernel if no input was seen yet.
+    #[cfg(feature = "simd")]
+    fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+        crate::simd::next_batch(self, input, crate::simd::wma)
+    }
+}
+
 impl Reset for WeightedMovingAverage {
     fn reset(&mut self) {
         self.index = 0;
@@ -182,7 +213,6 @@ mod tests {
     #[test]
     fn test_display() {
         let wma = WeightedMovingAverage::new(7).unwrap();
-        println!("{}", wma); // 输出: WMA(7)
         assert_eq!(format!("{}", wma), "WMA(7)");
     }
 }
@@ -0,0 +1,188 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, WeightedMoments};
+use crate::{Close, IsReady, Next, Period, Reset, Volume};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling weighted standard deviation.
+///
+/// Returns the population standard deviation of the last _period_ values around their
+/// [WeightedMean](crate::indicators::WeightedMean), each value weighted by its own weight. The
+/// inputs are pairs `(value, weight)`. Weights should not be negative. Returns NaN while the
+/// weights of the window sum up to zero.
+///
+/// For bars the close price is weighted by the volume.
+///
+/// # Formula
+///
+/// _WSTD = √(Σ w<sub>i</sub> (x<sub>i</sub> - WMEAN)<sup>2</sup> / Σ w<sub>i</sub>)_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::WeightedStd;
+/// use ta_panther::Next;
+///
+/// let mut std = WeightedStd::new(2).unwrap();
+/// assert_eq!(std.next((10.0, 100.0)), 0.0);
+/// assert_eq!(std.next((20.0, 100.0)), 5.0);
+/// assert_eq!(std.next((40.0, 100.0)), 10.0);
+/// ```
+///
+/// # Links
+///
+/// * [Weighted sample variance, Wikipedia](https://en.wikipedia.org/wiki/Weighted_arithmetic_mean#Weighted_sample_variance)
+///
+#[doc(alias = "WSTD")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedStd {
+    period: usize,
+    index: usize,
+    count: usize,
+    moments: WeightedMoments,
+    deque: Box<[(f64, f64)]>,
+}
+
+impl WeightedStd {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                moments: WeightedMoments::default(),
+                deque: ring((0.0, 0.0), period)?,
+            }),
+        }
+    }
+}
+
+impl Period for WeightedStd {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for WeightedStd {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<(f64, f64)> for WeightedStd {
+    type Output = f64;
+
+    fn next(&mut self, (value, weight): (f64, f64)) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let (old_value, old_weight) = self.deque[self.index];
+            self.moments.pop(old_value, old_weight);
+        }
+        self.moments.push(value, weight);
+
+        self.deque[self.index] = (value, weight);
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.moments.std_dev()
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for WeightedStd {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.close(), input.volume()))
+    }
+}
+
+impl Reset for WeightedStd {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.moments = WeightedMoments::default();
+        for i in 0..self.period {
+            self.deque[i] = (0.0, 0.0);
+        }
+    }
+}
+
+impl Default for WeightedStd {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for WeightedStd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WSTD({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(WeightedStd::new(0).is_err());
+        assert!(WeightedStd::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut std = WeightedStd::new(3).unwrap();
+        assert_eq!(std.next((2.0, 1.0)), 0.0);
+        assert_eq!(round(std.next((4.0, 3.0))), 0.866);
+        assert_eq!(round(std.next((6.0, 0.0))), 0.866);
+        assert_eq!(round(std.next((1.0, 2.0))), 1.47);
+        assert_eq!(std.next((5.0, 0.0)), 0.0);
+        assert_eq!(std.next((3.0, 0.0)), 0.0);
+        assert!(std.next((7.0, 0.0)).is_nan());
+        assert_eq!(std.next((7.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut std = WeightedStd::new(2).unwrap();
+        std.next(&Bar::new().close(10).volume(1.0));
+        let output = std.next(&Bar::new().close(20).volume(4.0));
+        assert_eq!(output, 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut std = WeightedStd::new(3).unwrap();
+        std.next((4.0, 1.0));
+        std.next((8.0, 1.0));
+
+        std.reset();
+        assert_eq!(std.next((1.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        WeightedStd::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let std = WeightedStd::new(10).unwrap();
+        assert_eq!(format!("{}", std), "WSTD(10)");
+    }
+}
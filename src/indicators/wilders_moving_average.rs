@@ -0,0 +1,172 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Wilder's moving average (RMA), also known as the smoothed moving average (SMMA).
+///
+/// The smoothing introduced by J. Welles Wilder for the [RSI](crate::indicators::RelativeStrengthIndex),
+/// the [ATR](crate::indicators::AverageTrueRange) and the
+/// [ADX](crate::indicators::AverageDirectionalIndex). It is an exponential moving average with a
+/// smoothing factor of _1 / period_, seeded with the simple average of the first _period_
+/// inputs, which is returned during the warm-up.
+///
+/// # Formula
+///
+/// RMA<sub>t</sub> = RMA<sub>t-1</sub> + (p<sub>t</sub> - RMA<sub>t-1</sub>) / period
+///
+/// Where:
+///
+/// * _RMA<sub>period</sub>_ - the simple average of the first _period_ inputs
+/// * _p<sub>t</sub>_ - input value at a time period _t_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::WildersMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut rma = WildersMovingAverage::new(2).unwrap();
+/// assert_eq!(rma.next(2.0), 2.0);
+/// assert_eq!(rma.next(4.0), 3.0);
+/// assert_eq!(rma.next(7.0), 5.0);
+/// assert_eq!(rma.next(1.0), 3.0);
+/// ```
+///
+/// # Links
+///
+/// * [Moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Modified_moving_average)
+///
+#[doc(alias = "RMA")]
+#[doc(alias = "SMMA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WildersMovingAverage {
+    period: usize,
+    count: usize,
+    current: f64,
+}
+
+impl WildersMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                count: 0,
+                current: 0.0,
+            }),
+        }
+    }
+
+    /// Returns the latest value, 0 before the first input.
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+}
+
+impl Period for WildersMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for WildersMovingAverage {
+    fn bars_remaining(&self) -> usize {
+        self.period.saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for WildersMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        }
+        self.current += (input - self.current) / self.count as f64;
+        self.current
+    }
+}
+
+impl<T: Close> Next<&T> for WildersMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl NextBatch for WildersMovingAverage {}
+
+impl Reset for WildersMovingAverage {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.current = 0.0;
+    }
+}
+
+impl Default for WildersMovingAverage {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for WildersMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(WildersMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(WildersMovingAverage::new(0).is_err());
+        assert!(WildersMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rma = WildersMovingAverage::new(3).unwrap();
+        assert_eq!(rma.next(3.0), 3.0);
+        assert_eq!(rma.next(6.0), 4.5);
+        assert_eq!(rma.next(9.0), 6.0);
+        assert!(rma.is_ready());
+        assert_eq!(rma.next(12.0), 8.0);
+        assert_eq!(rma.current(), 8.0);
+
+        let mut rma = WildersMovingAverage::new(2).unwrap();
+        assert_eq!(rma.next(&Bar::new().close(2)), 2.0);
+        assert_eq!(rma.next(&Bar::new().close(4)), 3.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rma = WildersMovingAverage::new(3).unwrap();
+        rma.next(3.0);
+        rma.next(6.0);
+
+        rma.reset();
+        assert_eq!(rma.next(1.0), 1.0);
+        assert_eq!(rma.bars_remaining(), 2);
+    }
+
+    #[test]
+    fn test_display() {
+        let rma = WildersMovingAverage::new(14).unwrap();
+        assert_eq!(format!("{}", rma), "RMA(14)");
+    }
+}
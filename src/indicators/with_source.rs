@@ -0,0 +1,152 @@
+use core::fmt;
+
+use crate::indicators::PriceSource;
+use crate::{Close, High, IsReady, Low, Next, Open, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Price source selection.
+///
+/// Wraps an indicator of a single price and feeds it the given [PriceSource] of bars instead of
+/// their close, like the "source" parameter of charting platforms. Plain numbers are passed
+/// through untouched. Works with every indicator implementing `Next<f64>`, including the ones
+/// with several outputs.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::{PriceSource, SimpleMovingAverage, WithSource};
+/// use ta_panther::{DataItem, Next};
+///
+/// let mut sma = WithSource::new(SimpleMovingAverage::new(2).unwrap(), PriceSource::Hl2);
+/// let bar = |high, low| {
+///     DataItem::builder()
+///         .open(low)
+///         .high(high)
+///         .low(low)
+///         .close(high)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+/// assert_eq!(sma.next(&bar(12.0, 8.0)), 10.0);
+/// assert_eq!(sma.next(&bar(16.0, 12.0)), 12.0);
+/// assert_eq!(sma.to_string(), "SMA(2) ON HL2");
+/// ```
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WithSource<N> {
+    indicator: N,
+    source: PriceSource,
+}
+
+impl<N> WithSource<N> {
+    pub fn new(indicator: N, source: PriceSource) -> Self {
+        Self { indicator, source }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn inner(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn source(&self) -> PriceSource {
+        self.source
+    }
+}
+
+impl<N: Period> Period for WithSource<N> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<N: IsReady> IsReady for WithSource<N> {
+    fn bars_remaining(&self) -> usize {
+        self.indicator.bars_remaining()
+    }
+}
+
+impl<N: Next<f64>> Next<f64> for WithSource<N> {
+    type Output = N::Output;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.indicator.next(input)
+    }
+}
+
+impl<N: Next<f64>, T: Open + High + Low + Close> Next<&T> for WithSource<N> {
+    type Output = N::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.indicator.next(self.source.price(input))
+    }
+}
+
+impl<N: Reset> Reset for WithSource<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<N: Default> Default for WithSource<N> {
+    fn default() -> Self {
+        Self::new(N::default(), PriceSource::default())
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for WithSource<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ON {}", self.indicator, self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{BollingerBands, ExponentialMovingAverage as Ema, Maximum};
+    use crate::test_helper::*;
+
+    type EmaWithSource = WithSource<Ema>;
+
+    test_indicator!(EmaWithSource);
+
+    #[test]
+    fn test_next() {
+        let mut max = WithSource::new(Maximum::new(2).unwrap(), PriceSource::Open);
+        assert_eq!(max.next(&Bar::new().open(3).close(1)), 3.0);
+        assert_eq!(max.next(&Bar::new().open(2).close(5)), 3.0);
+        assert_eq!(max.next(7.0), 7.0);
+
+        let mut ema = WithSource::new(Ema::new(3).unwrap(), PriceSource::Weighted);
+        assert_eq!(ema.next(&Bar::new().high(6).low(2).close(4)), 4.0);
+        assert_eq!(ema.next(&Bar::new().high(8).low(4).close(8)), 5.5);
+    }
+
+    #[test]
+    fn test_multiple_outputs() {
+        let mut bb = WithSource::new(BollingerBands::new(2, 1.0).unwrap(), PriceSource::Hlc3);
+        bb.next(&Bar::new().high(4).low(1).close(1));
+        let out = bb.next(&Bar::new().high(6).low(3).close(3));
+        assert_eq!((out.average, out.upper, out.lower), (3.0, 4.0, 2.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut max = WithSource::new(Maximum::new(2).unwrap(), PriceSource::Hl2);
+        max.next(&Bar::new().high(10).low(8));
+
+        max.reset();
+        assert_eq!(max.next(&Bar::new().high(4).low(2)), 3.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let ema = WithSource::new(Ema::new(7).unwrap(), PriceSource::Ohlc4);
+        assert_eq!(format!("{}", ema), "EMA(7) ON OHLC4");
+        assert_eq!(ema.source(), PriceSource::Ohlc4);
+        assert_eq!(ema.inner().period(), 7);
+    }
+}
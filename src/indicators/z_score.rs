@@ -0,0 +1,180 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::StandardDeviation;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling z-score.
+///
+/// Returns how many standard deviations the current input lies above (positive) or below
+/// (negative) the mean of the last _period_ inputs, including the current one. Commonly used
+/// for mean reversion signals and to normalize series with different scales.
+///
+/// The mean and the variance are updated incrementally with Welford's method by
+/// [RollingMoments](crate::rolling::RollingMoments), shared with [StandardDeviation], so every
+/// update is O(1). If all the inputs in the window are equal, the z-score is 0.
+///
+/// # Formula
+///
+/// _Z = (p - μ) / σ_
+///
+/// Where:
+///
+/// * _p_ - current input
+/// * _μ_ - mean of the last _period_ inputs
+/// * _σ_ - population standard deviation of the last _period_ inputs
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ZScore;
+/// use ta_panther::Next;
+///
+/// let mut z = ZScore::new(3).unwrap();
+/// assert_eq!(z.next(10.0), 0.0);
+/// assert_eq!(z.next(20.0), 1.0);
+/// assert_eq!(z.next(15.0), 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Standard score, Wikipedia](https://en.wikipedia.org/wiki/Standard_score)
+///
+#[doc(alias = "ZSCORE")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZScore {
+    sd: StandardDeviation,
+}
+
+impl ZScore {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            sd: StandardDeviation::new(period)?,
+        })
+    }
+}
+
+impl Period for ZScore {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl IsReady for ZScore {
+    fn bars_remaining(&self) -> usize {
+        self.sd.bars_remaining()
+    }
+}
+
+impl Next<f64> for ZScore {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let sd = self.sd.next(input);
+        let mean = self.sd.mean();
+
+        // deviations at the level of rounding errors are treated as no deviation
+        if sd <= 1e-6 * mean.abs() || sd == 0.0 {
+            0.0
+        } else {
+            (input - mean) / sd
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for ZScore {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ZScore {
+    fn reset(&mut self) {
+        self.sd.reset();
+    }
+}
+
+impl Default for ZScore {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for ZScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZSCORE({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ZScore);
+
+    #[test]
+    fn test_new() {
+        assert!(ZScore::new(0).is_err());
+        assert!(ZScore::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut z = ZScore::new(4).unwrap();
+        assert_eq!(z.next(2.0), 0.0);
+        assert_eq!(z.next(4.0), 1.0);
+        assert_eq!(round(z.next(9.0)), 1.359);
+        assert_eq!(round(z.next(1.0)), -0.973);
+        assert_eq!(round(z.next(4.0)), -0.174);
+    }
+
+    #[test]
+    fn test_constant_input() {
+        let mut z = ZScore::new(3).unwrap();
+        for &x in &[0.1, 7.3, 2.9, 4.7, 4.7, 4.7] {
+            z.next(x);
+        }
+        assert_eq!(z.next(4.7), 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut z = ZScore::new(2).unwrap();
+        assert_eq!(z.next(&Bar::new().close(3)), 0.0);
+        assert_eq!(z.next(&Bar::new().close(5)), 1.0);
+        assert_eq!(z.next(&Bar::new().close(1)), -1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut z = ZScore::new(3).unwrap();
+        z.next(1.0);
+        z.next(9.0);
+
+        z.reset();
+        assert_eq!(z.next(5.0), 0.0);
+        assert_eq!(z.next(7.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ZScore::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let z = ZScore::new(10).unwrap();
+        assert_eq!(format!("{}", z), "ZSCORE(10)");
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::Result;
+use crate::helpers::deque;
+use crate::indicators::ExponentialMovingAverage;
+use crate::{Close, IsReady, Next, NextBatch, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Zero lag exponential moving average (ZLEMA).
+///
+/// An [EMA](crate::indicators::ExponentialMovingAverage) of the input plus its momentum over
+/// half the period, which cancels most of the lag of the EMA. The momentum is taken from the
+/// first input while fewer inputs have been seen.
+///
+/// # Formula
+///
+/// _ZLEMA<sub>t</sub> = EMA(2 * p<sub>t</sub> - p<sub>t-lag</sub>)_
+///
+/// Where:
+///
+/// * _lag = (period - 1) / 2_, rounded down
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ZeroLagExponentialMovingAverage;
+/// use ta_panther::Next;
+///
+/// let mut zlema = ZeroLagExponentialMovingAverage::new(3).unwrap();
+/// assert_eq!(zlema.next(2.0), 2.0);
+/// assert_eq!(zlema.next(4.0), 4.0);
+/// assert_eq!(zlema.next(5.0), 5.0);
+/// assert_eq!(zlema.next(5.0), 5.0);
+/// ```
+///
+/// # Links
+///
+/// * [Moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average)
+///
+#[doc(alias = "ZLEMA")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZeroLagExponentialMovingAverage {
+    lag: usize,
+    ema: ExponentialMovingAverage,
+    window: VecDeque<f64>,
+    // inputs seen, up to the warm-up
+    #[cfg_attr(feature = "serde", serde(default))]
+    count: usize,
+}
+
+impl ZeroLagExponentialMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        let lag = period.saturating_sub(1) / 2;
+        Ok(Self {
+            lag,
+            ema: ExponentialMovingAverage::new(period)?,
+            window: deque(lag + 1)?,
+            count: 0,
+        })
+    }
+}
+
+impl Period for ZeroLagExponentialMovingAverage {
+    fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+impl IsReady for ZeroLagExponentialMovingAverage {
+    /// The lag window fills after _lag_ inputs and the EMA warms up over the next _period_.
+    fn bars_remaining(&self) -> usize {
+        (self.lag + self.ema.period()).saturating_sub(self.count)
+    }
+}
+
+impl Next<f64> for ZeroLagExponentialMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !self.is_ready() {
+            self.count += 1;
+        }
+        if self.window.len() > self.lag {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        let lagged = self.window.front().copied().unwrap_or(input);
+        self.ema.next(2.0 * input - lagged)
+    }
+}
+
+impl<T: Close> Next<&T> for ZeroLagExponentialMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl NextBatch for ZeroLagExponentialMovingAverage {}
+
+impl Reset for ZeroLagExponentialMovingAverage {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.window.clear();
+        self.count = 0;
+    }
+}
+
+impl Default for ZeroLagExponentialMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for ZeroLagExponentialMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZLEMA({})", self.ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    type Zlema = ZeroLagExponentialMovingAverage;
+
+    test_indicator!(Zlema);
+
+    #[test]
+    fn test_new() {
+        assert!(Zlema::new(0).is_err());
+        assert!(Zlema::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut zlema = Zlema::new(5).unwrap();
+        // lag of 2, the momentum is taken from the first input until the fourth one
+        assert_eq!(zlema.next(3.0), 3.0);
+        assert_eq!(zlema.next(6.0), 5.0);
+        assert_eq!(round(zlema.next(6.0)), 6.333);
+        assert_eq!(round(zlema.next(12.0)), 10.222);
+    }
+
+    #[test]
+    fn test_period_of_one() {
+        let mut zlema = Zlema::new(1).unwrap();
+        assert_eq!(zlema.next(3.0), 3.0);
+        assert_eq!(zlema.next(7.0), 7.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        // lag of 2
+        let mut zlema = Zlema::new(5).unwrap();
+        assert_eq!(zlema.bars_remaining(), 7);
+        for input in 0..6 {
+            zlema.next(input as f64);
+        }
+        assert_eq!(zlema.bars_remaining(), 1);
+        zlema.next(6.0);
+        assert!(zlema.is_ready());
+        zlema.next(7.0);
+        assert!(zlema.is_ready());
+
+        zlema.reset();
+        assert_eq!(zlema.bars_remaining(), 7);
+        assert_eq!(Zlema::new(1).unwrap().bars_remaining(), 1);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut zlema = Zlema::new(3).unwrap();
+        zlema.next(&Bar::new().close(2));
+        assert_eq!(zlema.next(&Bar::new().close(4)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut zlema = Zlema::new(3).unwrap();
+        zlema.next(4.0);
+        zlema.next(8.0);
+
+        zlema.reset();
+        assert_eq!(zlema.next(2.0), 2.0);
+        assert_eq!(zlema.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let zlema = Zlema::new(7).unwrap();
+        assert_eq!(format!("{}", zlema), "ZLEMA(7)");
+    }
+}
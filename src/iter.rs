@@ -0,0 +1,210 @@
+//! Iterator adaptors feeding indicators.
+//!
+//! [IndicatorIteratorExt] adds [indicator](IndicatorIteratorExt::indicator) to every iterator,
+//! which lazily yields the outputs of an indicator fed with the items of the iterator, so
+//! series from readers can be processed without writing loops.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::indicators::{RateOfChange, SimpleMovingAverage};
+//! use ta_panther::iter::IndicatorIteratorExt;
+//!
+//! let prices = vec![2.0, 4.0, 8.0, 10.0];
+//! let sma: Vec<f64> = prices
+//!     .iter()
+//!     .copied()
+//!     .indicator(SimpleMovingAverage::new(2).unwrap())
+//!     .collect();
+//! assert_eq!(sma, vec![2.0, 3.0, 6.0, 9.0]);
+//!
+//! // adaptors can be chained, each indicator feeds the next one
+//! let roc_of_sma: Vec<f64> = prices
+//!     .into_iter()
+//!     .indicator(SimpleMovingAverage::new(2).unwrap())
+//!     .indicator(RateOfChange::new(1).unwrap())
+//!     .collect();
+//! assert_eq!(roc_of_sma, vec![0.0, 50.0, 100.0, 50.0]);
+//! ```
+
+use crate::Next;
+
+/// Extension of iterators with adaptors feeding indicators.
+///
+/// Implemented for every iterator.
+pub trait IndicatorIteratorExt: Iterator + Sized {
+    /// Feeds every item into `indicator`, yielding its outputs.
+    ///
+    /// Works for iterators of inputs accepted by the indicator, e.g. `f64` or references to
+    /// [DataItem](crate::DataItem)s.
+    fn indicator<N: Next<Self::Item>>(self, indicator: N) -> IndicatorIter<Self, N> {
+        IndicatorIter {
+            iter: self,
+            indicator,
+        }
+    }
+
+    /// Feeds a reference to every item into `indicator`, yielding its outputs.
+    ///
+    /// Works for iterators of owned items, e.g. [DataItem](crate::DataItem)s read from a file,
+    /// for indicators accepting references to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ta_panther::indicators::TrueRange;
+    /// use ta_panther::iter::IndicatorIteratorExt;
+    /// use ta_panther::DataItem;
+    ///
+    /// let bars = (1..4).map(|i| {
+    ///     let close = i as f64;
+    ///     DataItem::builder()
+    ///         .open(close)
+    ///         .high(close + 1.0)
+    ///         .low(close - 1.0)
+    ///         .close(close)
+    ///         .volume(100.0)
+    ///         .build()
+    ///         .unwrap()
+    /// });
+    /// let ranges: Vec<f64> = bars.indicator_by_ref(TrueRange::new()).collect();
+    /// assert_eq!(ranges, vec![2.0, 2.0, 2.0]);
+    /// ```
+    fn indicator_by_ref<N>(self, indicator: N) -> IndicatorRefIter<Self, N>
+    where
+        N: for<'a> Next<&'a Self::Item>,
+    {
+        IndicatorRefIter {
+            iter: self,
+            indicator,
+        }
+    }
+}
+
+impl<I: Iterator> IndicatorIteratorExt for I {}
+
+/// Iterator yielding the outputs of an indicator, created by
+/// [indicator](IndicatorIteratorExt::indicator).
+#[derive(Debug, Clone)]
+pub struct IndicatorIter<I, N> {
+    iter: I,
+    indicator: N,
+}
+
+impl<I, N> IndicatorIter<I, N> {
+    /// Returns the indicator, e.g. to read its state after the iteration.
+    pub fn into_indicator(self) -> N {
+        self.indicator
+    }
+}
+
+impl<I: Iterator, N: Next<I::Item>> Iterator for IndicatorIter<I, N> {
+    type Item = N::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.iter.next()?;
+        Some(Next::next(&mut self.indicator, input))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator, N: Next<I::Item>> ExactSizeIterator for IndicatorIter<I, N> {}
+
+/// Iterator yielding the outputs of an indicator fed with references, created by
+/// [indicator_by_ref](IndicatorIteratorExt::indicator_by_ref).
+#[derive(Debug, Clone)]
+pub struct IndicatorRefIter<I, N> {
+    iter: I,
+    indicator: N,
+}
+
+impl<I, N> IndicatorRefIter<I, N> {
+    /// Returns the indicator, e.g. to read its state after the iteration.
+    pub fn into_indicator(self) -> N {
+        self.indicator
+    }
+}
+
+impl<I, N, O> Iterator for IndicatorRefIter<I, N>
+where
+    I: Iterator,
+    N: for<'a> Next<&'a I::Item, Output = O>,
+{
+    type Item = O;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.iter.next()?;
+        Some(Next::next(&mut self.indicator, &input))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, N, O> ExactSizeIterator for IndicatorRefIter<I, N>
+where
+    I: ExactSizeIterator,
+    N: for<'a> Next<&'a I::Item, Output = O>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, Maximum, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_indicator() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        let inputs = [2.0, 5.0, 1.0, 6.25];
+        let expected: Vec<f64> = inputs.iter().map(|&x| ema.next(x)).collect();
+
+        let outputs = inputs
+            .iter()
+            .copied()
+            .indicator(ExponentialMovingAverage::new(3).unwrap());
+        assert_eq!(outputs.len(), 4);
+        assert_eq!(outputs.collect::<Vec<f64>>(), expected);
+    }
+
+    #[test]
+    fn test_indicator_is_lazy() {
+        let mut outputs = (1..)
+            .map(|i| i as f64)
+            .indicator(SimpleMovingAverage::new(2).unwrap());
+        assert_eq!(outputs.next(), Some(1.0));
+        assert_eq!(outputs.next(), Some(1.5));
+        assert_eq!(outputs.nth(1), Some(3.5));
+    }
+
+    #[test]
+    fn test_bars() {
+        let bars = vec![Bar::new().close(4), Bar::new().close(8)];
+        let by_ref: Vec<f64> = bars
+            .iter()
+            .indicator(SimpleMovingAverage::new(2).unwrap())
+            .collect();
+        let owned: Vec<f64> = bars
+            .into_iter()
+            .indicator_by_ref(SimpleMovingAverage::new(2).unwrap())
+            .collect();
+        assert_eq!(by_ref, vec![4.0, 6.0]);
+        assert_eq!(owned, by_ref);
+    }
+
+    #[test]
+    fn test_into_indicator() {
+        let mut outputs = [3.0, 7.0, 5.0]
+            .iter()
+            .copied()
+            .indicator(Maximum::new(3).unwrap());
+        for _ in outputs.by_ref() {}
+        let mut max = outputs.into_indicator();
+        assert_eq!(max.next(1.0), 7.0);
+    }
+}
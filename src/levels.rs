@@ -0,0 +1,772 @@
+//! Support and resistance levels.
+//!
+//! [Levels] clusters the confirmed [swing points](crate::patterns::SwingPoints) of the price
+//! into support and resistance zones: swing points within a relative tolerance of an existing
+//! level are new touches of that level, which moves it to the average price of its touches, and
+//! other swing points start new levels.
+//!
+//! Every level has a strength, the number of its touches decaying with a half-life, so recent
+//! and frequently tested levels are the strongest ones. A support is broken by a close below it,
+//! and a resistance by a close above it, beyond the tolerance. A broken level touched again by a
+//! swing point becomes active again in its new role, e.g. a broken resistance retested from
+//! above becomes a support.
+//!
+//! [FibonacciLevels] draws the Fibonacci retracement and extension levels of the latest swing,
+//! and flags the bars tagging them.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::levels::{LevelKind, Levels};
+//! use ta_panther::patterns::{SwingKind, SwingPoint};
+//!
+//! let swing = |kind, price| SwingPoint { kind, price, offset: 2 };
+//!
+//! let mut levels = Levels::new(2, 2, 0.01).unwrap();
+//! levels.add_swing(&swing(SwingKind::High, 110.0));
+//! levels.add_swing(&swing(SwingKind::Low, 100.0));
+//! levels.add_swing(&swing(SwingKind::High, 109.0));
+//!
+//! let resistance = levels.nearest_level(108.0).unwrap();
+//! assert_eq!(resistance.kind, LevelKind::Resistance);
+//! assert_eq!((resistance.price, resistance.touches), (109.5, 2));
+//! assert_eq!(levels.nearest_level(101.0).unwrap().price, 100.0);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::patterns::{SwingKind, SwingPoint, SwingPoints};
+use crate::{Close, High, Low, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Levels whose strength decays below this threshold are forgotten.
+const MIN_STRENGTH: f64 = 0.1;
+
+/// Role of a [Level].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LevelKind {
+    /// Level formed by swing lows, below the price.
+    Support,
+    /// Level formed by swing highs, above the price.
+    Resistance,
+}
+
+/// Status of a [Level].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LevelStatus {
+    /// The price hasn't closed through the level since its last touch.
+    Active,
+    /// The price closed through the level.
+    Broken,
+}
+
+/// Support or resistance zone of a [Levels] engine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Level {
+    /// Average price of the touches.
+    pub price: f64,
+    /// Role of the level, given by the kind of its last touch.
+    pub kind: LevelKind,
+    /// Number of swing points clustered into the level.
+    pub touches: usize,
+    /// Number of touches, each decaying with the half-life of the engine.
+    pub strength: f64,
+    pub status: LevelStatus,
+}
+
+impl Level {
+    fn is_active(&self) -> bool {
+        self.status == LevelStatus::Active
+    }
+}
+
+/// Nearest active levels around the close of a bar, see [Levels].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LevelsOutput {
+    /// Nearest active level at or below the close.
+    pub support: Option<Level>,
+    /// Nearest active level above the close.
+    pub resistance: Option<Level>,
+}
+
+/// Support and resistance levels clustered from swing points, see the [module](self)
+/// documentation.
+///
+/// Fed with bars, the engine detects the swing points itself and returns the nearest active
+/// support and resistance around every close. Swing points detected elsewhere are added with
+/// [add_swing](Levels::add_swing).
+///
+/// # Parameters
+///
+/// * _left_, _right_ - strength of the [SwingPoints]. Default is 2 and 2.
+/// * _tolerance_ - relative distance within which swing points touch a level (greater than
+///   0.0), e.g. 0.01 for 1%. Default is 0.005.
+/// * _half_life_ - number of bars after which the strength of a touch is halved (greater than
+///   0.0). Default is 100, see [with_half_life](Levels::with_half_life).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::levels::Levels;
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64, close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut levels = Levels::new(1, 1, 0.01).unwrap();
+/// levels.next(&bar(101.0, 99.0, 100.0));
+/// levels.next(&bar(105.0, 100.0, 104.0));
+/// let output = levels.next(&bar(103.0, 101.0, 102.0));
+/// assert_eq!(output.resistance.unwrap().price, 105.0);
+/// assert_eq!(output.support, None);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Levels {
+    swings: SwingPoints,
+    tolerance: f64,
+    half_life: f64,
+    levels: Vec<Level>,
+}
+
+impl Levels {
+    pub fn new(left: usize, right: usize, tolerance: f64) -> Result<Self> {
+        Self::with_half_life(left, right, tolerance, 100.0)
+    }
+
+    pub fn with_half_life(
+        left: usize,
+        right: usize,
+        tolerance: f64,
+        half_life: f64,
+    ) -> Result<Self> {
+        if !(tolerance > 0.0 && tolerance.is_finite() && half_life > 0.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            swings: SwingPoints::new(left, right)?,
+            tolerance,
+            half_life,
+            levels: Vec::new(),
+        })
+    }
+
+    /// Adds a confirmed swing point, as a touch of the nearest level within the tolerance or as
+    /// a new level.
+    pub fn add_swing(&mut self, point: &SwingPoint) {
+        let kind = match point.kind {
+            SwingKind::High => LevelKind::Resistance,
+            SwingKind::Low => LevelKind::Support,
+        };
+        let tolerance = self.tolerance;
+        let touched = self
+            .levels
+            .iter_mut()
+            .filter(|level| (point.price - level.price).abs() <= tolerance * level.price.abs())
+            .min_by(|a, b| {
+                let distance = |level: &Level| (point.price - level.price).abs();
+                distance(a).total_cmp(&distance(b))
+            });
+        match touched {
+            Some(level) => {
+                let touches = level.touches as f64;
+                level.price = (level.price * touches + point.price) / (touches + 1.0);
+                level.touches += 1;
+                level.strength += 1.0;
+                level.kind = kind;
+                level.status = LevelStatus::Active;
+            }
+            None => self.levels.push(Level {
+                price: point.price,
+                kind,
+                touches: 1,
+                strength: 1.0,
+                status: LevelStatus::Active,
+            }),
+        }
+    }
+
+    /// Returns all the levels, active and broken, in the order they were created.
+    pub fn levels(&self) -> &[Level] {
+        &self.levels
+    }
+
+    /// Returns the active level nearest to `price`, on either side.
+    pub fn nearest_level(&self, price: f64) -> Option<&Level> {
+        self.nearest(price, |_| true)
+    }
+
+    /// Returns the nearest active level at or below `price`.
+    pub fn nearest_support(&self, price: f64) -> Option<&Level> {
+        self.nearest(price, |level| level.price <= price)
+    }
+
+    /// Returns the nearest active level above `price`.
+    pub fn nearest_resistance(&self, price: f64) -> Option<&Level> {
+        self.nearest(price, |level| level.price > price)
+    }
+
+    fn nearest(&self, price: f64, side: impl Fn(&Level) -> bool) -> Option<&Level> {
+        self.levels
+            .iter()
+            .filter(|level| level.is_active() && side(level))
+            .min_by(|a, b| (a.price - price).abs().total_cmp(&(b.price - price).abs()))
+    }
+
+    /// Breaks the levels the close went through.
+    fn update_status(&mut self, close: f64) {
+        let tolerance = self.tolerance;
+        for level in self.levels.iter_mut().filter(|level| level.is_active()) {
+            let margin = tolerance * level.price.abs();
+            let broken = match level.kind {
+                LevelKind::Support => close < level.price - margin,
+                LevelKind::Resistance => close > level.price + margin,
+            };
+            if broken {
+                level.status = LevelStatus::Broken;
+            }
+        }
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for Levels {
+    type Output = LevelsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let decay = 0.5_f64.powf(1.0 / self.half_life);
+        for level in self.levels.iter_mut() {
+            level.strength *= decay;
+        }
+        self.levels.retain(|level| level.strength >= MIN_STRENGTH);
+
+        let swings = self.swings.next(input);
+        for point in swings.iter() {
+            self.add_swing(&point);
+        }
+
+        let close = input.close();
+        self.update_status(close);
+        LevelsOutput {
+            support: self.nearest_support(close).copied(),
+            resistance: self.nearest_resistance(close).copied(),
+        }
+    }
+}
+
+impl Reset for Levels {
+    fn reset(&mut self) {
+        self.swings.reset();
+        self.levels.clear();
+    }
+}
+
+impl Default for Levels {
+    fn default() -> Self {
+        Self::new(2, 2, 0.005).unwrap()
+    }
+}
+
+impl fmt::Display for Levels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LEVELS({}, {}, {})",
+            self.swings.left(),
+            self.swings.right(),
+            self.tolerance
+        )
+    }
+}
+
+/// Fibonacci retracement ratios of [FibonacciSwing::retracement].
+pub const FIBONACCI_RETRACEMENTS: [f64; 5] = [0.236, 0.382, 0.5, 0.618, 0.786];
+
+/// Fibonacci extension ratios of [FibonacciSwing::extension].
+pub const FIBONACCI_EXTENSIONS: [f64; 3] = [1.272, 1.618, 2.618];
+
+/// Price leg the Fibonacci levels are drawn on, from a swing point to the following swing
+/// point of the opposite kind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FibonacciSwing {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl FibonacciSwing {
+    /// Returns the price retracing `ratio` of the leg back from its end.
+    pub fn retracement(&self, ratio: f64) -> f64 {
+        self.end - ratio * (self.end - self.start)
+    }
+
+    /// Returns the price extending the leg to `ratio` of its length from its start.
+    pub fn extension(&self, ratio: f64) -> f64 {
+        self.start + ratio * (self.end - self.start)
+    }
+}
+
+/// Fibonacci levels tagged by a bar, see [FibonacciLevels].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FibonacciOutput {
+    /// Latest swing, if any.
+    pub swing: Option<FibonacciSwing>,
+    /// Whether the range of the bar contains the level of each [FIBONACCI_RETRACEMENTS] ratio.
+    pub retracements: [bool; 5],
+    /// Whether the range of the bar contains the level of each [FIBONACCI_EXTENSIONS] ratio.
+    pub extensions: [bool; 3],
+}
+
+impl FibonacciOutput {
+    /// Returns whether the bar tagged any level.
+    pub fn is_tagged(&self) -> bool {
+        self.retracements
+            .iter()
+            .chain(&self.extensions)
+            .any(|&tagged| tagged)
+    }
+}
+
+/// Fibonacci retracement and extension levels of the latest swing.
+///
+/// Fed with bars, the latest swing runs from the latest confirmed [swing point](SwingPoints)
+/// to the latest one of the opposite kind before it, and every bar is checked against the
+/// levels of that swing: a level is tagged when it lies within the range of the bar. A swing
+/// given with [set_swing](FibonacciLevels::set_swing) is kept until the next swing point is
+/// detected.
+///
+/// Use [FibonacciTag] to consume the tags of a single level as a boolean indicator.
+///
+/// # Formula
+///
+/// For a swing from _start_ to _end_:
+///
+/// retracement(r) = end - r * (end - start)
+///
+/// extension(e) = start + e * (end - start)
+///
+/// Where the ratios _r_ are [FIBONACCI_RETRACEMENTS] and _e_ are [FIBONACCI_EXTENSIONS].
+///
+/// # Parameters
+///
+/// * _left_, _right_ - strength of the [SwingPoints]. Default is 2 and 2.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::levels::FibonacciLevels;
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64| {
+///     DataItem::builder()
+///         .open(low)
+///         .high(high)
+///         .low(low)
+///         .close(low)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut fib = FibonacciLevels::new(1, 1).unwrap();
+/// fib.set_swing(100.0, 200.0);
+/// assert_eq!(fib.swing().unwrap().retracement(0.5), 150.0);
+///
+/// // the bar tags the 38.2% and 50% retracements
+/// let output = fib.next(&bar(165.0, 148.0));
+/// assert_eq!(output.retracements, [false, true, true, false, false]);
+/// assert!(output.is_tagged());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FibonacciLevels {
+    swings: SwingPoints,
+    bars: u64,
+    high: Option<(u64, f64)>,
+    low: Option<(u64, f64)>,
+    swing: Option<FibonacciSwing>,
+}
+
+impl FibonacciLevels {
+    pub fn new(left: usize, right: usize) -> Result<Self> {
+        Ok(Self {
+            swings: SwingPoints::new(left, right)?,
+            bars: 0,
+            high: None,
+            low: None,
+            swing: None,
+        })
+    }
+
+    /// Sets the swing from `start` to `end`, replaced by the next detected swing.
+    pub fn set_swing(&mut self, start: f64, end: f64) {
+        self.swing = Some(FibonacciSwing { start, end });
+    }
+
+    /// Returns the latest swing, if any.
+    pub fn swing(&self) -> Option<FibonacciSwing> {
+        self.swing
+    }
+
+    fn add_swing(&mut self, point: &SwingPoint) {
+        let pivot = Some((self.bars - point.offset as u64, point.price));
+        let (latest, previous) = match point.kind {
+            SwingKind::High => {
+                self.high = pivot;
+                (self.high, self.low)
+            }
+            SwingKind::Low => {
+                self.low = pivot;
+                (self.low, self.high)
+            }
+        };
+        if let (Some((end_bar, end)), Some((start_bar, start))) = (latest, previous) {
+            if start_bar < end_bar {
+                self.swing = Some(FibonacciSwing { start, end });
+            }
+        }
+    }
+}
+
+impl<T: High + Low> Next<&T> for FibonacciLevels {
+    type Output = FibonacciOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.bars += 1;
+        let swings = self.swings.next(input);
+        for point in swings.iter() {
+            self.add_swing(&point);
+        }
+
+        let mut output = FibonacciOutput {
+            swing: self.swing,
+            ..Default::default()
+        };
+        if let Some(swing) = self.swing {
+            let tagged = |price: f64| input.low() <= price && price <= input.high();
+            for (flag, &ratio) in output.retracements.iter_mut().zip(&FIBONACCI_RETRACEMENTS) {
+                *flag = tagged(swing.retracement(ratio));
+            }
+            for (flag, &ratio) in output.extensions.iter_mut().zip(&FIBONACCI_EXTENSIONS) {
+                *flag = tagged(swing.extension(ratio));
+            }
+        }
+        output
+    }
+}
+
+impl Reset for FibonacciLevels {
+    fn reset(&mut self) {
+        self.swings.reset();
+        self.bars = 0;
+        self.high = None;
+        self.low = None;
+        self.swing = None;
+    }
+}
+
+impl Default for FibonacciLevels {
+    fn default() -> Self {
+        Self::new(2, 2).unwrap()
+    }
+}
+
+impl fmt::Display for FibonacciLevels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FIB({}, {})", self.swings.left(), self.swings.right())
+    }
+}
+
+/// Whether a bar tags a single Fibonacci level of the latest swing, see [FibonacciLevels].
+///
+/// Ratios below 1.0 are retracements and ratios above 1.0 are extensions.
+///
+/// # Parameters
+///
+/// * _left_, _right_ - strength of the [SwingPoints]. Default is 2 and 2.
+/// * _ratio_ - ratio of the level (greater than 0.0 and different from 1.0). Default is 0.618.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::levels::FibonacciTag;
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64| {
+///     DataItem::builder()
+///         .open(low)
+///         .high(high)
+///         .low(low)
+///         .close(low)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut tag = FibonacciTag::new(1, 1, 0.5).unwrap();
+/// let bars = [(102.0, 100.0), (101.0, 99.0), (110.0, 105.0), (109.0, 106.0), (108.0, 104.0)];
+/// let tags: Vec<bool> = bars.iter().map(|&(high, low)| tag.next(&bar(high, low))).collect();
+/// // the swing from 99 to 110 is confirmed on the fourth bar, the fifth bar tags 104.5
+/// assert_eq!(tags, [false, false, false, false, true]);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FibonacciTag {
+    levels: FibonacciLevels,
+    ratio: f64,
+}
+
+impl FibonacciTag {
+    pub fn new(left: usize, right: usize, ratio: f64) -> Result<Self> {
+        if !(ratio > 0.0 && ratio.is_finite()) || ratio == 1.0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            levels: FibonacciLevels::new(left, right)?,
+            ratio,
+        })
+    }
+
+    /// Sets the swing from `start` to `end`, replaced by the next detected swing.
+    pub fn set_swing(&mut self, start: f64, end: f64) {
+        self.levels.set_swing(start, end);
+    }
+}
+
+impl<T: High + Low> Next<&T> for FibonacciTag {
+    type Output = bool;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let swing = self.levels.next(input).swing;
+        swing.is_some_and(|swing| {
+            let price = if self.ratio < 1.0 {
+                swing.retracement(self.ratio)
+            } else {
+                swing.extension(self.ratio)
+            };
+            input.low() <= price && price <= input.high()
+        })
+    }
+}
+
+impl Reset for FibonacciTag {
+    fn reset(&mut self) {
+        self.levels.reset();
+    }
+}
+
+impl Default for FibonacciTag {
+    fn default() -> Self {
+        Self::new(2, 2, 0.618).unwrap()
+    }
+}
+
+impl fmt::Display for FibonacciTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FIBTAG({}, {}, {})",
+            self.levels.swings.left(),
+            self.levels.swings.right(),
+            self.ratio
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn swing(kind: SwingKind, price: f64) -> SwingPoint {
+        SwingPoint {
+            kind,
+            price,
+            offset: 1,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Levels::new(0, 2, 0.01).is_err());
+        assert!(Levels::new(2, 2, 0.0).is_err());
+        assert!(Levels::new(2, 2, f64::INFINITY).is_err());
+        assert!(Levels::with_half_life(2, 2, 0.01, 0.0).is_err());
+        assert!(Levels::with_half_life(2, 2, 0.01, f64::NAN).is_err());
+        assert!(Levels::new(2, 0, 0.01).is_ok());
+    }
+
+    #[test]
+    fn test_clustering() {
+        let mut levels = Levels::new(2, 2, 0.01).unwrap();
+        levels.add_swing(&swing(SwingKind::Low, 100.0));
+        levels.add_swing(&swing(SwingKind::Low, 100.5));
+        levels.add_swing(&swing(SwingKind::Low, 99.6));
+        levels.add_swing(&swing(SwingKind::High, 120.0));
+        // within the tolerance of 120.0 only
+        levels.add_swing(&swing(SwingKind::High, 119.0));
+
+        assert_eq!(levels.levels().len(), 2);
+        let support = levels.nearest_support(110.0).unwrap();
+        assert_eq!((support.touches, support.strength), (3, 3.0));
+        assert!((support.price - 100.033).abs() < 1e-3);
+        assert_eq!(support.kind, LevelKind::Support);
+        let resistance = levels.nearest_resistance(110.0).unwrap();
+        assert_eq!((resistance.price, resistance.touches), (119.5, 2));
+        assert_eq!(levels.nearest_level(108.0), Some(support));
+        assert_eq!(levels.nearest_level(115.0), Some(resistance));
+        assert_eq!(levels.nearest_resistance(130.0), None);
+    }
+
+    #[test]
+    fn test_broken_levels() {
+        let mut levels = Levels::with_half_life(1, 1, 0.01, 1e9).unwrap();
+        levels.add_swing(&swing(SwingKind::High, 100.0));
+        levels.add_swing(&swing(SwingKind::Low, 90.0));
+
+        let output = levels.next(&Bar::new().high(100).low(98).close(99.5));
+        assert_eq!(output.resistance.unwrap().price, 100.0);
+        assert_eq!(output.support.unwrap().price, 90.0);
+
+        // closes within the tolerance don't break the resistance
+        levels.next(&Bar::new().high(101).low(99).close(100.9));
+        assert_eq!(levels.levels()[0].status, LevelStatus::Active);
+
+        let output = levels.next(&Bar::new().high(103).low(100).close(102));
+        assert_eq!(output.resistance, None);
+        assert_eq!(output.support.unwrap().price, 90.0);
+        assert_eq!(levels.levels()[0].status, LevelStatus::Broken);
+        assert_eq!(levels.nearest_level(100.0).unwrap().price, 90.0);
+
+        // retested from above, the broken resistance becomes a support
+        levels.add_swing(&swing(SwingKind::Low, 100.5));
+        let level = levels.levels()[0];
+        assert_eq!(
+            (level.kind, level.status),
+            (LevelKind::Support, LevelStatus::Active)
+        );
+        assert_eq!(level.touches, 2);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut levels = Levels::with_half_life(1, 1, 0.01, 2.0).unwrap();
+        let bars = [(10.0, 9.0), (12.0, 10.0), (11.0, 10.5), (11.5, 10.6)];
+        let outputs: Vec<_> = bars
+            .iter()
+            .map(|&(high, low)| levels.next(&Bar::new().high(high).low(low).close(low)))
+            .collect();
+        assert_eq!(outputs[1], LevelsOutput::default());
+        let resistance = outputs[2].resistance.unwrap();
+        assert_eq!((resistance.price, resistance.strength), (12.0, 1.0));
+        // halved every two bars
+        assert!((outputs[3].resistance.unwrap().strength - 0.5_f64.sqrt()).abs() < 1e-12);
+
+        // forgotten once the strength decays below the threshold
+        for _ in 0..10 {
+            levels.next(&Bar::new().high(11.5).low(10.6).close(11.0));
+        }
+        assert!(levels.levels().is_empty());
+
+        levels.add_swing(&swing(SwingKind::Low, 10.0));
+        levels.reset();
+        assert!(levels.levels().is_empty());
+    }
+
+    #[test]
+    fn test_fibonacci_swing() {
+        let up = FibonacciSwing {
+            start: 100.0,
+            end: 200.0,
+        };
+        assert_eq!(up.retracement(0.5), 150.0);
+        assert_eq!(up.retracement(0.25), 175.0);
+        assert_eq!(up.extension(1.5), 250.0);
+
+        let down = FibonacciSwing {
+            start: 200.0,
+            end: 100.0,
+        };
+        assert_eq!(down.retracement(0.25), 125.0);
+        assert_eq!(down.extension(1.5), 50.0);
+    }
+
+    #[test]
+    fn test_fibonacci_levels() {
+        let mut fib = FibonacciLevels::new(1, 1).unwrap();
+        assert!(FibonacciLevels::new(0, 1).is_err());
+
+        let bars = [
+            (13.0, 11.0),
+            (12.0, 10.0),
+            (20.0, 11.0),
+            (19.0, 14.0),
+            (18.0, 8.0),
+        ];
+        let outputs: Vec<_> = bars
+            .iter()
+            .map(|&(high, low)| fib.next(&Bar::new().high(high).low(low)))
+            .collect();
+        assert_eq!(outputs[2], FibonacciOutput::default());
+        // swing from the low of 10 to the high of 20, the deepest retracements are not tagged
+        let swing = outputs[3].swing.unwrap();
+        assert_eq!((swing.start, swing.end), (10.0, 20.0));
+        assert_eq!(outputs[3].retracements, [true, true, true, false, false]);
+        assert_eq!(outputs[4].retracements, [true; 5]);
+        assert_eq!(outputs[4].extensions, [false; 3]);
+
+        // the next swing low starts a down swing from the high
+        fib.next(&Bar::new().high(16.0).low(9.0));
+        let output = fib.next(&Bar::new().high(35.0).low(34.0));
+        let swing = output.swing.unwrap();
+        assert_eq!((swing.start, swing.end), (20.0, 8.0));
+        assert!(!output.is_tagged());
+        let output = fib.next(&Bar::new().high(36.0).low(-12.0));
+        assert_eq!(output.extensions, [true, true, true]);
+
+        fib.reset();
+        assert_eq!(fib.swing(), None);
+        fib.set_swing(1.0, 2.0);
+        assert!(fib.next(&Bar::new().high(1.5).low(1.5)).retracements[2]);
+    }
+
+    #[test]
+    fn test_fibonacci_tag() {
+        assert!(FibonacciTag::new(2, 2, 0.0).is_err());
+        assert!(FibonacciTag::new(2, 2, 1.0).is_err());
+        assert!(FibonacciTag::new(2, 2, f64::NAN).is_err());
+
+        let mut tag = FibonacciTag::new(2, 2, 1.618).unwrap();
+        assert!(!tag.next(&Bar::new().high(200).low(0)));
+        tag.set_swing(100.0, 110.0);
+        assert!(tag.next(&Bar::new().high(117).low(116)));
+        assert!(!tag.next(&Bar::new().high(117).low(116.5)));
+        tag.reset();
+        assert!(!tag.next(&Bar::new().high(117).low(116)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Levels::default().to_string(), "LEVELS(2, 2, 0.005)");
+        assert_eq!(FibonacciLevels::default().to_string(), "FIB(2, 2)");
+        assert_eq!(FibonacciTag::default().to_string(), "FIBTAG(2, 2, 0.618)");
+    }
+}
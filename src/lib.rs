@@ -30,39 +30,246 @@
 //!   * [Exponential Moving Average (EMA)](crate::indicators::ExponentialMovingAverage)
 //!   * [Simple Moving Average (SMA)](crate::indicators::SimpleMovingAverage)
 //!   * [Weighted Moving Average (WMA)](crate::indicators::WeightedMovingAverage)
+//!   * [Triangular Moving Average (TMA)](crate::indicators::TriangularMovingAverage)
+//!   * [Sine Weighted Moving Average (SWMA)](crate::indicators::SineWeightedMovingAverage)
+//!   * [Hull Moving Average (HMA)](crate::indicators::HullMovingAverage)
+//!   * [Kaufman's Adaptive Moving Average (KAMA)](crate::indicators::KaufmanAdaptiveMovingAverage)
+//!   * [Zero Lag Exponential Moving Average (ZLEMA)](crate::indicators::ZeroLagExponentialMovingAverage)
+//!   * [Wilder's Moving Average (RMA)](crate::indicators::WildersMovingAverage)
+//!   * [Moving Average of a runtime selected type](crate::indicators::MovingAverage)
+//!   * [Kalman Filter](crate::indicators::KalmanFilter)
+//!   * [MESA Adaptive Moving Average (MAMA)](crate::dsp::MesaAdaptiveMovingAverage)
+//!   * [Super Smoother (SSF)](crate::dsp::SuperSmoother)
+//!   * [Decycler](crate::dsp::Decycler)
 //! * Oscillators
 //!   * [Relative Strength Index (RSI)](indicators/struct.RelativeStrengthIndex.html)
 //!   * [Fast Stochastic](indicators/struct.FastStochastic.html)
 //!   * [Slow Stochastic](indicators/struct.SlowStochastic.html)
+//!   * [Full Stochastic Oscillator (STOCH)](indicators/struct.StochasticOscillator.html)
 //!   * [Moving Average Convergence Divergence (MACD)](indicators/struct.MovingAverageConvergenceDivergence.html)
 //!   * [Percentage Price Oscillator (PPO)](indicators/struct.PercentagePriceOscillator.html)
 //!   * [Commodity Channel Index (CCI)](indicators/struct.CommodityChannelIndex.html)
 //!   * [Money Flow Index (MFI)](indicators/struct.MoneyFlowIndex.html)
+//!   * [Chaikin Money Flow (CMF)](indicators/struct.ChaikinMoneyFlow.html)
+//!   * [Twiggs Money Flow (TMF)](indicators/struct.TwiggsMoneyFlow.html)
 //! * Other
 //!   * [Standard Deviation (SD)](indicators/struct.StandardDeviation.html)
 //!   * [Mean Absolute Deviation (MAD)](indicators/struct.MeanAbsoluteDeviation.html)
 //!   * [Bollinger Bands (BB)](indicators/struct.BollingerBands.html)
+//!   * [Bollinger Band %B (BBP)](indicators/struct.BollingerPercentB.html)
+//!   * [Bollinger Bandwidth (BBW)](indicators/struct.BollingerBandwidth.html)
 //!   * [Chandelier Exit (CE)](indicators/struct.ChandelierExit.html)
 //!   * [Keltner Channel (KC)](indicators/struct.KeltnerChannel.html)
 //!   * [Maximum](indicators/struct.Maximum.html)
 //!   * [Minimum](indicators/struct.Minimum.html)
 //!   * [True Range](indicators/struct.TrueRange.html)
+//!   * [Typical Price (TYPPRICE)](indicators/struct.TypicalPrice.html)
+//!   * [Median Price (MEDPRICE)](indicators/struct.MedianPrice.html)
+//!   * [Weighted Close (WCLPRICE)](indicators/struct.WeightedClose.html)
 //!   * [Average True Range (ATR)](indicators/struct.AverageTrueRange.html)
+//!   * [Average Directional Index (ADX)](indicators/struct.AverageDirectionalIndex.html)
+//!   * [Average Directional Index Rating (ADXR)](indicators/struct.Adxr.html)
+//!   * [Regime Classifier (REGIME)](indicators/struct.RegimeClassifier.html)
+//!   * [Hilbert Transform Dominant Cycle (HT_DCPERIOD, HT_DCPHASE)](dsp/struct.DominantCycle.html)
+//!   * [Roofing Filter (ROOF)](dsp/struct.RoofingFilter.html)
+//!   * [Second Order Low-Pass, High-Pass and Band-Pass Filter](dsp/struct.SecondOrderFilter.html)
 //!   * [Efficiency Ratio (ER)](indicators/struct.EfficiencyRatio.html)
 //!   * [Rate of Change (ROC)](indicators/struct.RateOfChange.html)
+//!   * [Rate of Change Ratio (ROCR)](indicators/struct.RateOfChangeRatio.html)
+//!   * [Momentum (MOM)](indicators/struct.Momentum.html)
+//!   * [Lag (REF)](indicators/struct.Lag.html)
+//!   * [Displace](indicators/struct.Displace.html)
 //!   * [On Balance Volume (OBV)](indicators/struct.OnBalanceVolume.html)
+//!   * [Anchored VWAP (AVWAP)](indicators/struct.AnchoredVwap.html)
+//!   * [Standard Error (STDERR)](indicators/struct.StandardError.html)
+//!   * [Forecast Oscillator (FOSC)](indicators/struct.ForecastOscillator.html)
+//!   * [Linear Regression Channel (LRC)](indicators/struct.RegressionChannel.html)
+//!   * [Polynomial Regression Forecast](indicators/struct.PolynomialRegressionForecast.html)
+//!   * [Exponential Regression Forecast](indicators/struct.ExponentialRegressionForecast.html)
+//!   * [Rolling Median](indicators/struct.RollingMedian.html)
+//!   * [Rolling Percentile](indicators/struct.RollingPercentile.html)
+//!   * [Skewness](indicators/struct.Skewness.html)
+//!   * [Kurtosis](indicators/struct.Kurtosis.html)
+//!   * [Z-Score](indicators/struct.ZScore.html)
+//!   * [Rolling Correlation](indicators/struct.RollingCorrelation.html)
+//!   * [Beta](indicators/struct.Beta.html)
+//!   * [Conditional Mean](indicators/struct.ConditionalMean.html)
+//!   * [Split By](indicators/struct.SplitBy.html)
+//!   * [Binary Op](indicators/struct.BinaryOp.html)
+//!   * [Compare](indicators/struct.Compare.html)
+//!   * [If Then Else (IF)](indicators/struct.IfThenElse.html)
+//!   * [Bars Since](indicators/struct.BarsSince.html)
+//!   * [Count True](indicators/struct.CountTrue.html)
+//!   * [Sum Since](indicators/struct.SumSince.html)
+//!   * [Debounce](indicators/struct.Debounce.html)
+//!   * [Hold](indicators/struct.Hold.html)
+//!   * [Checked warm-up](indicators/struct.Checked.html)
+//!   * [NaN Guard](indicators/struct.NanGuard.html)
+//!   * [Price Source selection](indicators/struct.WithSource.html)
+//!   * [Shannon Entropy](indicators/struct.ShannonEntropy.html)
+//!   * [Approximate Entropy](indicators/struct.ApproximateEntropy.html)
+//!   * [Weighted Mean](indicators/struct.WeightedMean.html)
+//!   * [Weighted Standard Deviation](indicators/struct.WeightedStd.html)
+//!   * [Autocorrelation](indicators/struct.Autocorrelation.html)
+//!   * [Geometric Mean](indicators/struct.GeometricMean.html)
+//!   * [Harmonic Mean](indicators/struct.HarmonicMean.html)
+//!   * [Rolling Max Drawup](indicators/struct.RollingMaxDrawup.html)
+//!   * [Rolling Log-Sum-Exp](indicators/struct.RollingLogSumExp.html)
+//!   * [Ulcer Index (UI)](indicators/struct.UlcerIndex.html)
+//!   * [Ulcer Performance Index (UPI)](indicators/struct.UlcerPerformanceIndex.html)
+//!   * [Max Drawdown (MDD)](indicators/struct.MaxDrawdown.html)
+//!   * [Value at Risk (VaR)](indicators/struct.ValueAtRisk.html)
+//!   * [Expected Shortfall (ES)](indicators/struct.ExpectedShortfall.html)
+//!   * [Tick Rule](indicators/struct.TickRule.html)
+//!   * [Order Flow Imbalance (OFI)](indicators/struct.OrderFlowImbalance.html)
+//!   * [Volume-Synchronized Probability of Informed Trading (VPIN)](indicators/struct.Vpin.html)
 //!
+//! ## `no_std`
+//!
+//! The indicators only need `alloc`. Disabling the default `std` feature and enabling `libm`,
+//! which provides the floating point functions, builds the crate for `no_std` targets. The
+//! modules keeping hash maps of indicators and the features talking to the outside world
+//! require `std`.
+//!
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled");
+
 #[cfg(test)]
 #[macro_use]
 mod test_helper;
 
+// tests link `std`, whose methods of `f64` take precedence over the ones of `Float`
+#[cfg(not(any(feature = "std", test)))]
+mod float;
 mod helpers;
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "backtest")]
+pub mod backtest;
+pub mod batch;
+#[cfg(any(feature = "csv", feature = "json"))]
+pub mod data;
+pub mod dsp;
+pub mod dynamic;
+#[cfg(feature = "std")]
+pub mod engine;
 pub mod errors;
+#[cfg(feature = "expr")]
+pub mod expr;
+#[cfg(feature = "std")]
+pub mod factory;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fixed;
 pub mod indicators;
+pub mod iter;
+pub mod levels;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+#[cfg(feature = "ml")]
+pub mod ml;
+pub mod optimize;
+pub mod pairs;
+pub mod patterns;
+pub mod performance;
+#[cfg(feature = "persistence")]
+pub mod persistence;
+#[cfg(feature = "plugin")]
+pub mod plugin;
+#[cfg(feature = "std")]
+pub mod pool;
+pub mod profile;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod resample;
+pub mod returns;
+pub mod rolling;
+#[cfg(feature = "rhai")]
+pub mod script;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(target_has_atomic = "64")]
+pub mod shared;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod sizing;
+#[cfg(feature = "serde")]
+pub mod state;
+pub mod stops;
+pub mod strategy;
+#[cfg(feature = "tokio")]
+pub mod stream;
+pub mod testing;
+pub mod volatility;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 mod traits;
 pub use crate::traits::*;
 
+/// Derives [Period], [Reset], `Display` and optionally [FromParams] for custom indicators.
+///
+/// Available with the `derive` feature.
+///
+/// The macro doesn't derive `Serialize` and `Deserialize`, which custom indicators derive from
+/// serde themselves, e.g. behind their own `serde` feature like the built-in indicators.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::errors::Result;
+/// use ta_panther::indicators::SimpleMovingAverage;
+/// use ta_panther::{FromParams, Indicator, Next, Period, Reset};
+///
+/// #[derive(Indicator)]
+/// #[indicator(name = "SCALED_SMA", from_params)]
+/// struct ScaledSma {
+///     #[indicator(period)]
+///     period: usize,
+///     #[indicator(param)]
+///     factor: f64,
+///     #[indicator(reset)]
+///     sma: SimpleMovingAverage,
+///     #[indicator(reset = 0)]
+///     count: usize,
+/// }
+///
+/// impl ScaledSma {
+///     fn new(period: usize, factor: f64) -> Result<Self> {
+///         Ok(Self {
+///             period,
+///             factor,
+///             sma: SimpleMovingAverage::new(period)?,
+///             count: 0,
+///         })
+///     }
+/// }
+///
+/// impl Next<f64> for ScaledSma {
+///     type Output = f64;
+///
+///     fn next(&mut self, input: f64) -> f64 {
+///         self.count += 1;
+///         self.sma.next(input) * self.factor
+///     }
+/// }
+///
+/// let mut indicator = ScaledSma::from_params(&[2.0, 10.0]).unwrap();
+/// assert_eq!(indicator.period(), 2);
+/// assert_eq!(indicator.to_string(), "SCALED_SMA(2, 10)");
+/// assert_eq!(indicator.next(1.0), 10.0);
+/// assert_eq!(indicator.next(2.0), 15.0);
+///
+/// indicator.reset();
+/// assert_eq!(indicator.next(4.0), 40.0);
+/// ```
+#[cfg(feature = "derive")]
+pub use ta_panther_derive::Indicator;
+
 mod data_item;
 pub use crate::data_item::DataItem;
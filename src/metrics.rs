@@ -0,0 +1,325 @@
+//! Prometheus metrics for live indicator values.
+//!
+//! Available with the `prometheus` feature.
+//!
+//! [MetricsExporter] owns the following metrics:
+//!
+//! * `ta_indicator_value{symbol, indicator}` - gauge with the latest output of an indicator
+//! * `ta_bars_processed_total{symbol}` - counter of inputs processed per symbol, counting the
+//!   inputs fed to several indicators of a symbol once
+//! * `ta_last_update_timestamp_seconds{symbol}` - gauge with the unix time of the latest input,
+//!   so staleness can be alerted on with `time() - ta_last_update_timestamp_seconds`
+//! * `ta_indicator_latency_seconds{indicator}` - histogram of the time spent in `next`
+//!
+//! The metrics are registered in a [Registry] chosen by the caller, which is then exposed by
+//! the application's HTTP endpoint as usual.
+//!
+//! # Example
+//!
+//! ```
+//! use prometheus::Registry;
+//! use ta_panther::indicators::ExponentialMovingAverage;
+//! use ta_panther::metrics::{Instrumented, MetricsExporter};
+//! use ta_panther::Next;
+//!
+//! let registry = Registry::new();
+//! let exporter = MetricsExporter::new().unwrap();
+//! exporter.register(&registry).unwrap();
+//!
+//! let ema = ExponentialMovingAverage::new(3).unwrap();
+//! let mut ema = Instrumented::new(ema, "AAPL", &exporter);
+//! ema.next(2.0);
+//! ema.next(5.0);
+//!
+//! assert_eq!(exporter.value("AAPL", "EMA(3)"), 3.5);
+//! assert_eq!(exporter.bars_processed("AAPL"), 2);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+
+use crate::pool::IndicatorPool;
+use crate::{Next, Reset};
+
+/// Set of Prometheus metrics describing live indicators.
+///
+/// Cloning the exporter is cheap and the clones update the same metrics.
+#[derive(Clone, Debug)]
+pub struct MetricsExporter {
+    values: GaugeVec,
+    bars: IntCounterVec,
+    last_update: GaugeVec,
+    latency: HistogramVec,
+    // most inputs processed by an indicator of each symbol
+    inputs: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl MetricsExporter {
+    /// Creates the metrics. They have to be [registered](MetricsExporter::register) to be
+    /// exported.
+    pub fn new() -> prometheus::Result<Self> {
+        Ok(Self {
+            values: GaugeVec::new(
+                Opts::new("ta_indicator_value", "Latest output of an indicator"),
+                &["symbol", "indicator"],
+            )?,
+            bars: IntCounterVec::new(
+                Opts::new("ta_bars_processed_total", "Number of processed inputs"),
+                &["symbol"],
+            )?,
+            last_update: GaugeVec::new(
+                Opts::new(
+                    "ta_last_update_timestamp_seconds",
+                    "Unix time of the latest processed input",
+                ),
+                &["symbol"],
+            )?,
+            latency: HistogramVec::new(
+                HistogramOpts::new(
+                    "ta_indicator_latency_seconds",
+                    "Time spent calculating an indicator",
+                )
+                .buckets(prometheus::exponential_buckets(1e-7, 4.0, 10)?),
+                &["indicator"],
+            )?,
+            inputs: Arc::default(),
+        })
+    }
+
+    /// Registers all the metrics in `registry`.
+    pub fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.values.clone()))?;
+        registry.register(Box::new(self.bars.clone()))?;
+        registry.register(Box::new(self.last_update.clone()))?;
+        registry.register(Box::new(self.latency.clone()))?;
+        Ok(())
+    }
+
+    /// Records the latest `value` of `indicator` for `symbol`.
+    pub fn observe_value(&self, symbol: &str, indicator: &str, value: f64) {
+        self.values
+            .with_label_values(&[symbol, indicator])
+            .set(value);
+    }
+
+    /// Records that an input of `symbol` has been processed now.
+    pub fn observe_bar(&self, symbol: &str) {
+        let mut inputs = self.inputs.lock().unwrap_or_else(|e| e.into_inner());
+        *inputs.entry(symbol.to_string()).or_default() += 1;
+        self.bars.with_label_values(&[symbol]).inc();
+        self.touch(symbol);
+    }
+
+    /// Records that an indicator of `symbol` has processed `count` inputs in total, the latest
+    /// one now.
+    ///
+    /// The counter of processed inputs of the symbol follows the indicator which processed the
+    /// most inputs, so the inputs fed to every indicator of a symbol are counted once.
+    pub fn observe_inputs(&self, symbol: &str, count: u64) {
+        let mut inputs = self.inputs.lock().unwrap_or_else(|e| e.into_inner());
+        let processed = inputs.entry(symbol.to_string()).or_default();
+        if count > *processed {
+            self.bars
+                .with_label_values(&[symbol])
+                .inc_by(count - *processed);
+            *processed = count;
+        }
+        self.touch(symbol);
+    }
+
+    fn touch(&self, symbol: &str) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+        self.last_update.with_label_values(&[symbol]).set(now);
+    }
+
+    /// Runs `f` and records its duration as the latency of `indicator`.
+    pub fn time<T, F: FnOnce() -> T>(&self, indicator: &str, f: F) -> T {
+        let timer = self.latency.with_label_values(&[indicator]).start_timer();
+        let result = f();
+        timer.observe_duration();
+        result
+    }
+
+    /// Records the latest outputs of all symbols of `pool`, using the pool's prototype as
+    /// indicator name.
+    pub fn observe_pool<K, I>(&self, pool: &IndicatorPool<K, I, f64>)
+    where
+        K: Eq + Hash + fmt::Display,
+        I: Clone + fmt::Display,
+    {
+        let indicator = pool.prototype().to_string();
+        for (symbol, &value) in pool.iter() {
+            self.observe_value(&symbol.to_string(), &indicator, value);
+        }
+    }
+
+    /// Returns the recorded value of `indicator` for `symbol`.
+    pub fn value(&self, symbol: &str, indicator: &str) -> f64 {
+        self.values.with_label_values(&[symbol, indicator]).get()
+    }
+
+    /// Returns the number of inputs processed for `symbol`.
+    pub fn bars_processed(&self, symbol: &str) -> u64 {
+        self.bars.with_label_values(&[symbol]).get()
+    }
+}
+
+/// Encodes all the metrics of `registry` in the Prometheus text format.
+pub fn encode_text(registry: &Registry) -> prometheus::Result<String> {
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&registry.gather(), &mut buffer)?;
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+/// Wraps an indicator, reporting its outputs, processed inputs and latency to a
+/// [MetricsExporter].
+///
+/// The indicator is labeled with its `Display` representation (e.g. `EMA(9)`). Several
+/// indicators of a symbol count their inputs once, see [MetricsExporter::observe_inputs].
+#[derive(Clone, Debug)]
+pub struct Instrumented<I> {
+    indicator: I,
+    symbol: String,
+    name: String,
+    exporter: MetricsExporter,
+    inputs: u64,
+}
+
+impl<I: fmt::Display> Instrumented<I> {
+    pub fn new(indicator: I, symbol: &str, exporter: &MetricsExporter) -> Self {
+        Self {
+            name: indicator.to_string(),
+            indicator,
+            symbol: symbol.to_string(),
+            exporter: exporter.clone(),
+            inputs: 0,
+        }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I, T> Next<T> for Instrumented<I>
+where
+    I: Next<T, Output = f64>,
+{
+    type Output = f64;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let indicator = &mut self.indicator;
+        let value = self.exporter.time(&self.name, || indicator.next(input));
+        self.inputs += 1;
+        self.exporter.observe_inputs(&self.symbol, self.inputs);
+        self.exporter.observe_value(&self.symbol, &self.name, value);
+        value
+    }
+}
+
+impl<I: Reset> Reset for Instrumented<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for Instrumented<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+
+    #[test]
+    fn test_instrumented() {
+        let exporter = MetricsExporter::new().unwrap();
+        let mut sma = Instrumented::new(SimpleMovingAverage::new(2).unwrap(), "A", &exporter);
+
+        assert_eq!(sma.next(2.0), 2.0);
+        assert_eq!(sma.next(4.0), 3.0);
+        assert_eq!(exporter.value("A", "SMA(2)"), 3.0);
+        assert_eq!(exporter.bars_processed("A"), 2);
+        assert_eq!(format!("{}", sma), "SMA(2)");
+
+        sma.reset();
+        assert_eq!(sma.next(10.0), 10.0);
+    }
+
+    #[test]
+    fn test_bars_of_several_indicators() {
+        let exporter = MetricsExporter::new().unwrap();
+        let mut sma = Instrumented::new(SimpleMovingAverage::new(2).unwrap(), "A", &exporter);
+        let mut ema = Instrumented::new(ExponentialMovingAverage::new(2).unwrap(), "A", &exporter);
+        let mut other = Instrumented::new(SimpleMovingAverage::new(2).unwrap(), "B", &exporter);
+
+        for input in [1.0, 2.0, 3.0] {
+            sma.next(input);
+            ema.next(input);
+        }
+        other.next(1.0);
+        assert_eq!(exporter.bars_processed("A"), 3);
+        assert_eq!(exporter.bars_processed("B"), 1);
+
+        // an indicator added later catches up
+        let mut late = Instrumented::new(SimpleMovingAverage::new(3).unwrap(), "A", &exporter);
+        late.next(3.0);
+        assert_eq!(exporter.bars_processed("A"), 3);
+        sma.next(4.0);
+        assert_eq!(exporter.bars_processed("A"), 4);
+
+        exporter.observe_bar("B");
+        other.next(2.0);
+        assert_eq!(exporter.bars_processed("B"), 2);
+    }
+
+    #[test]
+    fn test_observe_pool() {
+        let exporter = MetricsExporter::new().unwrap();
+        let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+        pool.next(("A", 1.0));
+        pool.next(("B", 5.0));
+
+        exporter.observe_pool(&pool);
+        assert_eq!(exporter.value("A", "SMA(2)"), 1.0);
+        assert_eq!(exporter.value("B", "SMA(2)"), 5.0);
+    }
+
+    #[test]
+    fn test_encode_text() {
+        let registry = Registry::new();
+        let exporter = MetricsExporter::new().unwrap();
+        exporter.register(&registry).unwrap();
+
+        let mut sma = Instrumented::new(SimpleMovingAverage::new(2).unwrap(), "A", &exporter);
+        sma.next(2.0);
+
+        let text = encode_text(&registry).unwrap();
+        assert!(text.contains("ta_indicator_value{indicator=\"SMA(2)\",symbol=\"A\"} 2"));
+        assert!(text.contains("ta_bars_processed_total{symbol=\"A\"} 1"));
+        assert!(text.contains("ta_last_update_timestamp_seconds"));
+        assert!(text.contains("ta_indicator_latency_seconds_count{indicator=\"SMA(2)\"} 1"));
+    }
+
+    #[test]
+    fn test_register_twice() {
+        let registry = Registry::new();
+        let exporter = MetricsExporter::new().unwrap();
+        exporter.register(&registry).unwrap();
+        assert!(exporter.register(&registry).is_err());
+    }
+}
@@ -0,0 +1,394 @@
+//! Statistical models of market regimes.
+//!
+//! [GaussianHmm] is a hidden Markov model whose hidden states are market regimes, each one
+//! emitting normally distributed logarithmic returns with its own mean and volatility. Unlike
+//! the threshold rules of [RegimeClassifier](crate::indicators::RegimeClassifier), the regimes
+//! are learned from the returns themselves, and every bar updates the probability of each
+//! regime given all the returns seen so far.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::ml::GaussianHmm;
+//! use ta_panther::Next;
+//!
+//! let mut hmm = GaussianHmm::new(2, 20).unwrap();
+//! let mut price = 100.0;
+//! for i in 0..40 {
+//!     price *= if i % 2 == 0 { 1.001 } else { 0.999 };
+//!     hmm.next(price);
+//! }
+//! // a crash is far more likely in the volatile regime
+//! let output = hmm.next(price * 0.9);
+//! assert_eq!(output.state, Some(1));
+//! assert!(output.probabilities[1] > 0.99);
+//! ```
+
+use core::f64::consts::PI;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::Moments;
+use crate::{Close, IsReady, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of states of a [GaussianHmm].
+pub const MAX_STATES: usize = 3;
+
+const MIN_VARIANCE: f64 = 1e-12;
+
+/// Initial volatilities of the states relative to the volatility of the warm-up, by number of
+/// states.
+const SCALES: [[f64; MAX_STATES]; 2] = [[0.5, 2.0, 0.0], [0.5, 1.0, 2.0]];
+
+/// Regime probabilities of a bar, see [GaussianHmm].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HmmOutput {
+    /// Most likely state, or `None` during the warm-up.
+    pub state: Option<usize>,
+    /// Probability of every state, 0.0 for the states beyond the number of states.
+    pub probabilities: [f64; MAX_STATES],
+}
+
+/// Gaussian hidden Markov model of the returns, see the [module](self) documentation.
+///
+/// The model is initialized from the returns of the warm-up: every state starts with their
+/// mean, and with a multiple of their volatility, so the states are ordered from the calmest
+/// to the most volatile. The probabilities of the states are then updated by the forward
+/// algorithm on every return, and the mean and variance of every state follow the returns with
+/// exponential forgetting, weighted by the probability of the state (online EM).
+///
+/// The transition probabilities are fixed: the model stays in its state with probability
+/// _persistence_ and switches to any other state with equal probabilities.
+///
+/// Inputs are prices. Prices which aren't positive and finite are ignored.
+///
+/// # Formula
+///
+/// P(s<sub>t</sub> = j | r<sub>1..t</sub>) ∝ N(r<sub>t</sub>; μ<sub>j</sub>, σ<sub>j</sub>²)
+/// Σ<sub>i</sub> P(s<sub>t-1</sub> = i | r<sub>1..t-1</sub>) A<sub>ij</sub>
+///
+/// μ<sub>j</sub> += w<sub>j</sub> (r<sub>t</sub> - μ<sub>j</sub>),
+/// σ<sub>j</sub>² = (1 - w<sub>j</sub>) (σ<sub>j</sub>² + w<sub>j</sub> (r<sub>t</sub> -
+/// μ<sub>j</sub>)²)
+///
+/// Where _r<sub>t</sub> = ln(P<sub>t</sub> / P<sub>t-1</sub>)_, _A_ is the transition matrix
+/// and _w<sub>j</sub> = P(s<sub>t</sub> = j | r<sub>1..t</sub>) / period_.
+///
+/// # Parameters
+///
+/// * _states_ - number of states (2 or 3). Default is 2.
+/// * _period_ - number of returns of the warm-up, and memory of the updates (integer greater
+///   than 1). Default is 100.
+/// * _persistence_ - probability of staying in a state (between 0.0 and 1.0 exclusive).
+///   Default is 0.95, see [with_persistence](Self::with_persistence).
+///
+/// # Links
+///
+/// * [Hidden Markov model, Wikipedia](https://en.wikipedia.org/wiki/Hidden_Markov_model)
+///
+#[doc(alias = "HMM")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GaussianHmm {
+    states: usize,
+    period: usize,
+    persistence: f64,
+    previous: Option<f64>,
+    warmup: Moments,
+    initialized: bool,
+    means: [f64; MAX_STATES],
+    variances: [f64; MAX_STATES],
+    probabilities: [f64; MAX_STATES],
+}
+
+impl GaussianHmm {
+    pub fn new(states: usize, period: usize) -> Result<Self> {
+        Self::with_persistence(states, period, 0.95)
+    }
+
+    pub fn with_persistence(states: usize, period: usize, persistence: f64) -> Result<Self> {
+        if !(2..=MAX_STATES).contains(&states)
+            || period < 2
+            || !(persistence > 0.0 && persistence < 1.0)
+        {
+            return Err(TaError::InvalidParameter);
+        }
+        let mut hmm = Self {
+            states,
+            period,
+            persistence,
+            previous: None,
+            warmup: Moments::default(),
+            initialized: false,
+            means: [0.0; MAX_STATES],
+            variances: [0.0; MAX_STATES],
+            probabilities: [0.0; MAX_STATES],
+        };
+        hmm.reset();
+        Ok(hmm)
+    }
+
+    pub fn states(&self) -> usize {
+        self.states
+    }
+
+    pub fn persistence(&self) -> f64 {
+        self.persistence
+    }
+
+    /// Returns the mean return of every state, all 0.0 during the warm-up.
+    pub fn means(&self) -> &[f64] {
+        &self.means[..self.states]
+    }
+
+    /// Returns the variance of the returns of every state, all 0.0 during the warm-up.
+    pub fn variances(&self) -> &[f64] {
+        &self.variances[..self.states]
+    }
+
+    fn initialize(&mut self) {
+        let mean = self.warmup.mean();
+        let variance = self.warmup.sample_variance().max(MIN_VARIANCE);
+        for (j, scale) in SCALES[self.states - 2][..self.states].iter().enumerate() {
+            self.means[j] = mean;
+            self.variances[j] = variance * scale * scale;
+        }
+        self.initialized = true;
+    }
+
+    fn update(&mut self, value: f64) {
+        let states = self.states;
+        let switch = (1.0 - self.persistence) / (states - 1) as f64;
+
+        // forward step in log space, so outliers don't underflow every likelihood
+        let mut log_weights = [f64::NEG_INFINITY; MAX_STATES];
+        for (j, log_weight) in log_weights[..states].iter_mut().enumerate() {
+            let prior = switch + (self.persistence - switch) * self.probabilities[j];
+            let variance = self.variances[j];
+            let delta = value - self.means[j];
+            *log_weight =
+                prior.ln() - 0.5 * ((2.0 * PI * variance).ln() + delta * delta / variance);
+        }
+        let max = log_weights[..states]
+            .iter()
+            .fold(f64::NEG_INFINITY, |max, &w| max.max(w));
+        let mut sum = 0.0;
+        for (probability, log_weight) in self.probabilities.iter_mut().zip(&log_weights[..states]) {
+            *probability = (log_weight - max).exp();
+            sum += *probability;
+        }
+
+        let rate = 1.0 / self.period as f64;
+        for j in 0..states {
+            self.probabilities[j] /= sum;
+            let weight = rate * self.probabilities[j];
+            let delta = value - self.means[j];
+            self.means[j] += weight * delta;
+            self.variances[j] =
+                ((1.0 - weight) * (self.variances[j] + weight * delta * delta)).max(MIN_VARIANCE);
+        }
+    }
+
+    fn output(&self) -> HmmOutput {
+        let state = self.initialized.then(|| {
+            (1..self.states).fold(0, |best, j| {
+                if self.probabilities[j] > self.probabilities[best] {
+                    j
+                } else {
+                    best
+                }
+            })
+        });
+        HmmOutput {
+            state,
+            probabilities: self.probabilities,
+        }
+    }
+}
+
+impl Period for GaussianHmm {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for GaussianHmm {
+    fn bars_remaining(&self) -> usize {
+        if self.initialized {
+            0
+        } else {
+            self.period + 1 - self.warmup.count() - self.previous.is_some() as usize
+        }
+    }
+}
+
+impl Next<f64> for GaussianHmm {
+    type Output = HmmOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if !(input > 0.0 && input.is_finite()) {
+            return self.output();
+        }
+        let previous = match self.previous.replace(input) {
+            Some(previous) => previous,
+            None => return self.output(),
+        };
+        let value = (input / previous).ln();
+
+        if !self.initialized {
+            self.warmup.push(value);
+            if self.warmup.count() < self.period {
+                return self.output();
+            }
+            self.initialize();
+        }
+        self.update(value);
+        self.output()
+    }
+}
+
+impl<T: Close> Next<&T> for GaussianHmm {
+    type Output = HmmOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for GaussianHmm {
+    fn reset(&mut self) {
+        self.previous = None;
+        self.warmup = Moments::default();
+        self.initialized = false;
+        self.means = [0.0; MAX_STATES];
+        self.variances = [0.0; MAX_STATES];
+        self.probabilities = [0.0; MAX_STATES];
+        for probability in &mut self.probabilities[..self.states] {
+            *probability = 1.0 / self.states as f64;
+        }
+    }
+}
+
+impl Default for GaussianHmm {
+    fn default() -> Self {
+        Self::new(2, 100).unwrap()
+    }
+}
+
+impl fmt::Display for GaussianHmm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HMM({}, {})", self.states, self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    /// Deterministic returns alternating in sign, with magnitudes up to `scale`.
+    fn returns(count: usize, scale: f64) -> impl Iterator<Item = f64> {
+        (0..count).map(move |i| {
+            let magnitude = ((i * 7919) % 11) as f64 / 10.0;
+            if i % 2 == 0 {
+                scale * magnitude
+            } else {
+                -scale * magnitude
+            }
+        })
+    }
+
+    fn feed(
+        hmm: &mut GaussianHmm,
+        price: &mut f64,
+        returns: impl Iterator<Item = f64>,
+    ) -> Vec<HmmOutput> {
+        returns
+            .map(|r| {
+                *price *= r.exp();
+                hmm.next(*price)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(GaussianHmm::new(1, 100).is_err());
+        assert!(GaussianHmm::new(4, 100).is_err());
+        assert!(GaussianHmm::new(2, 1).is_err());
+        assert!(GaussianHmm::with_persistence(2, 100, 1.0).is_err());
+        assert!(GaussianHmm::with_persistence(2, 100, f64::NAN).is_err());
+
+        let hmm = GaussianHmm::with_persistence(3, 50, 0.9).unwrap();
+        assert_eq!((hmm.states(), hmm.persistence()), (3, 0.9));
+        assert_eq!(hmm.means(), &[0.0; 3]);
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut hmm = GaussianHmm::new(2, 3).unwrap();
+        assert_eq!(hmm.bars_remaining(), 4);
+        let output = hmm.next(100.0);
+        assert_eq!(output.state, None);
+        assert_eq!(output.probabilities, [0.5, 0.5, 0.0]);
+
+        hmm.next(&Bar::new().close(101.0));
+        hmm.next(f64::NAN);
+        hmm.next(99.0);
+        assert_eq!(hmm.bars_remaining(), 1);
+        assert!(hmm.next(100.0).state.is_some());
+        assert!(hmm.is_ready());
+        assert!(hmm.variances()[0] < hmm.variances()[1]);
+    }
+
+    #[test]
+    fn test_regimes() {
+        let mut hmm = GaussianHmm::with_persistence(3, 50, 0.9).unwrap();
+        let mut price = 100.0;
+        let calm = feed(&mut hmm, &mut price, returns(200, 0.002));
+        assert!(calm[100..].iter().all(|output| output.state != Some(2)));
+
+        // the most volatile state, except for the returns close to 0
+        let volatile = feed(&mut hmm, &mut price, returns(30, 0.05));
+        let count = volatile
+            .iter()
+            .filter(|output| output.state == Some(2))
+            .count();
+        assert!(count >= 25);
+        let sum: f64 = volatile[29].probabilities.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+
+        let calm = feed(&mut hmm, &mut price, returns(30, 0.002));
+        assert!(calm[5..].iter().all(|output| output.state != Some(2)));
+        assert!(hmm.variances()[2] > 100.0 * hmm.variances()[0]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hmm = GaussianHmm::new(2, 10).unwrap();
+        let mut price = 100.0;
+        let outputs = feed(&mut hmm, &mut price, returns(30, 0.01));
+        hmm.reset();
+        assert!(!hmm.is_ready());
+        let mut price = 100.0;
+        assert_eq!(feed(&mut hmm, &mut price, returns(30, 0.01)), outputs);
+    }
+
+    #[test]
+    fn test_default() {
+        GaussianHmm::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(GaussianHmm::default().to_string(), "HMM(2, 100)");
+    }
+}
@@ -0,0 +1,391 @@
+//! Parameter optimization.
+//!
+//! [grid_search] evaluates an indicator with every combination of parameters of a
+//! [ParameterGrid] over a series, and keeps the combination with the best score of a
+//! user-provided objective. [walk_forward] repeats the search on rolling training windows and
+//! scores the winner on the window that follows each of them, which estimates how the chosen
+//! parameters perform on data they were not fitted to.
+//!
+//! Indicators are created from the parameters by a constructor and evaluated over whole slices
+//! with [NextBatch], so indicators with fast batch paths are searched faster. Parameter
+//! combinations the constructor rejects are skipped.
+//!
+//! # Example
+//!
+//! Choosing the period of an EMA that tracks a series most closely:
+//!
+//! ```
+//! use ta_panther::indicators::ExponentialMovingAverage;
+//! use ta_panther::optimize::{grid_search, ParameterGrid};
+//!
+//! let input: Vec<f64> = (0..100).map(|i| (i as f64 / 10.0).sin()).collect();
+//! let grid = ParameterGrid::new().axis((5..=50).map(f64::from));
+//!
+//! let best = grid_search(
+//!     &input,
+//!     &grid,
+//!     |params| ExponentialMovingAverage::new(params[0] as usize),
+//!     |input, outputs| {
+//!         let error: f64 = input.iter().zip(outputs).map(|(x, y)| (x - y).abs()).sum();
+//!         -error
+//!     },
+//! )
+//! .unwrap();
+//! assert_eq!(best.params, vec![5.0]);
+//! ```
+
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::NextBatch;
+
+/// Maximum number of values of an axis added by [ParameterGrid::range].
+pub const MAX_RANGE_LEN: usize = 100_000;
+
+/// Combinations of parameter values to search.
+///
+/// Every axis holds the values of one parameter, the grid is their cartesian product. The
+/// parameters of a combination are in the order the axes were added.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterGrid {
+    axes: Vec<Vec<f64>>,
+}
+
+impl ParameterGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an axis with the given values.
+    pub fn axis<I: IntoIterator<Item = f64>>(mut self, values: I) -> Self {
+        self.axes.push(values.into_iter().collect());
+        self
+    }
+
+    /// Adds an axis with the values from `start` to `end`, inclusive, every `step`.
+    ///
+    /// Returns an error if the axis would have more than [MAX_RANGE_LEN] values.
+    pub fn range(self, start: f64, end: f64, step: f64) -> Result<Self> {
+        if !(start.is_finite() && end >= start && end.is_finite() && step > 0.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        let steps = ((end - start) / step + 1e-9).floor();
+        if steps >= MAX_RANGE_LEN as f64 {
+            return Err(TaError::InvalidParameter);
+        }
+        let count = steps as usize + 1;
+        Ok(self.axis((0..count).map(|i| start + i as f64 * step)))
+    }
+
+    /// Returns the number of combinations, 0 without axes, saturating at `usize::MAX`.
+    pub fn len(&self) -> usize {
+        if self.axes.is_empty() || self.axes.iter().any(Vec::is_empty) {
+            0
+        } else {
+            self.axes
+                .iter()
+                .try_fold(1usize, |len, axis| len.checked_mul(axis.len()))
+                .unwrap_or(usize::MAX)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns every combination, the last axis changing fastest.
+    pub fn iter(&self) -> impl Iterator<Item = Vec<f64>> + '_ {
+        (0..self.len()).map(move |mut index| {
+            let mut params = vec![0.0; self.axes.len()];
+            for (param, axis) in params.iter_mut().zip(&self.axes).rev() {
+                *param = axis[index % axis.len()];
+                index /= axis.len();
+            }
+            params
+        })
+    }
+}
+
+/// Parameters of an indicator with their score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub params: Vec<f64>,
+    pub score: f64,
+}
+
+/// Searches the parameters with the highest score.
+///
+/// Creates an indicator with `constructor` for every combination of `grid`, computes its outputs
+/// over `input` and scores them with `objective`, which gets the inputs and the outputs. Returns
+/// the first combination with the highest score; scores which are NaN are ignored.
+///
+/// Returns an error if the input is empty or no combination has a score.
+pub fn grid_search<N, C, F>(
+    input: &[f64],
+    grid: &ParameterGrid,
+    mut constructor: C,
+    mut objective: F,
+) -> Result<Evaluation>
+where
+    N: NextBatch,
+    C: FnMut(&[f64]) -> Result<N>,
+    F: FnMut(&[f64], &[N::Output]) -> f64,
+{
+    search(input, 0, grid, &mut constructor, &mut objective)
+}
+
+/// Grid search scoring the outputs from `skip` on only, the earlier inputs warming up the
+/// indicator.
+fn search<N, C, F>(
+    input: &[f64],
+    skip: usize,
+    grid: &ParameterGrid,
+    constructor: &mut C,
+    objective: &mut F,
+) -> Result<Evaluation>
+where
+    N: NextBatch,
+    C: FnMut(&[f64]) -> Result<N>,
+    F: FnMut(&[f64], &[N::Output]) -> f64,
+{
+    if input.len() <= skip {
+        return Err(TaError::InvalidParameter);
+    }
+    let mut best: Option<Evaluation> = None;
+    for params in grid.iter() {
+        let score = match constructor(&params) {
+            Ok(mut indicator) => {
+                let outputs = indicator.next_batch(input);
+                objective(&input[skip..], &outputs[skip..])
+            }
+            Err(_) => continue,
+        };
+        if !score.is_nan() && best.as_ref().is_none_or(|best| score > best.score) {
+            best = Some(Evaluation { params, score });
+        }
+    }
+    best.ok_or(TaError::InvalidParameter)
+}
+
+/// Training and test windows of a walk-forward step, as ranges of input indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Split {
+    pub train: Range<usize>,
+    pub test: Range<usize>,
+}
+
+/// Splits `len` inputs into rolling windows of `train` inputs, each followed by a test window
+/// of `test` inputs. The windows move forward by `test` inputs, so the test windows follow each
+/// other without overlapping. A last test window shorter than `test` is dropped.
+///
+/// Returns an error if either window is empty.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::optimize::{walk_forward_splits, Split};
+///
+/// let splits = walk_forward_splits(10, 4, 3).unwrap();
+/// assert_eq!(
+///     splits,
+///     vec![
+///         Split { train: 0..4, test: 4..7 },
+///         Split { train: 3..7, test: 7..10 },
+///     ]
+/// );
+/// ```
+pub fn walk_forward_splits(len: usize, train: usize, test: usize) -> Result<Vec<Split>> {
+    if train == 0 || test == 0 {
+        return Err(TaError::InvalidParameter);
+    }
+    Ok((0..)
+        .map(|i| i * test)
+        .take_while(|start| start + train + test <= len)
+        .map(|start| Split {
+            train: start..start + train,
+            test: start + train..start + train + test,
+        })
+        .collect())
+}
+
+/// Result of a walk-forward step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalkForwardStep {
+    pub split: Split,
+    /// Best parameters on the training window.
+    pub params: Vec<f64>,
+    /// Score of the parameters on the training window.
+    pub train_score: f64,
+    /// Score of the parameters on the test window.
+    pub test_score: f64,
+}
+
+/// Walk-forward optimization.
+///
+/// Runs a [grid_search] on every training window of [walk_forward_splits] and scores the best
+/// parameters on the test window that follows. The indicator scored on a test window is fed
+/// with the training window first, so it is warmed up, but only the outputs of the test window
+/// are passed to `objective`.
+///
+/// Returns an error if there is no split or a training window has no scored combination.
+pub fn walk_forward<N, C, F>(
+    input: &[f64],
+    grid: &ParameterGrid,
+    train: usize,
+    test: usize,
+    mut constructor: C,
+    mut objective: F,
+) -> Result<Vec<WalkForwardStep>>
+where
+    N: NextBatch,
+    C: FnMut(&[f64]) -> Result<N>,
+    F: FnMut(&[f64], &[N::Output]) -> f64,
+{
+    let splits = walk_forward_splits(input.len(), train, test)?;
+    if splits.is_empty() {
+        return Err(TaError::InvalidParameter);
+    }
+    splits
+        .into_iter()
+        .map(|split| {
+            let best = search(
+                &input[split.train.clone()],
+                0,
+                grid,
+                &mut constructor,
+                &mut objective,
+            )?;
+            let fixed = ParameterGrid {
+                axes: best.params.iter().map(|&param| vec![param]).collect(),
+            };
+            let tested = search(
+                &input[split.train.start..split.test.end],
+                train,
+                &fixed,
+                &mut constructor,
+                &mut objective,
+            )?;
+            Ok(WalkForwardStep {
+                split,
+                params: best.params,
+                train_score: best.score,
+                test_score: tested.score,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage as Sma;
+
+    fn sma(params: &[f64]) -> Result<Sma> {
+        Sma::new(params[0] as usize)
+    }
+
+    /// Negative sum of the absolute errors of the outputs as forecasts of the next input.
+    fn forecast_error(input: &[f64], outputs: &[f64]) -> f64 {
+        -input[1..]
+            .iter()
+            .zip(outputs)
+            .map(|(x, y)| (x - y).abs())
+            .sum::<f64>()
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = ParameterGrid::new()
+            .axis(vec![1.0, 2.0])
+            .range(10.0, 30.0, 10.0)
+            .unwrap();
+        assert_eq!(grid.len(), 6);
+        let combinations: Vec<Vec<f64>> = grid.iter().collect();
+        assert_eq!(combinations[0], vec![1.0, 10.0]);
+        assert_eq!(combinations[1], vec![1.0, 20.0]);
+        assert_eq!(combinations[5], vec![2.0, 30.0]);
+
+        assert!(ParameterGrid::new().is_empty());
+        assert!(ParameterGrid::new().axis(vec![]).is_empty());
+        assert_eq!(ParameterGrid::new().range(0.1, 0.3, 0.1).unwrap().len(), 3);
+        assert!(ParameterGrid::new().range(2.0, 1.0, 1.0).is_err());
+        assert!(ParameterGrid::new().range(1.0, 2.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_grid_limits() {
+        assert!(ParameterGrid::new().range(0.0, 1.0, 1e-20).is_err());
+        assert!(ParameterGrid::new()
+            .range(0.0, f64::MAX, f64::MIN_POSITIVE)
+            .is_err());
+        let max = (MAX_RANGE_LEN - 1) as f64;
+        let grid = ParameterGrid::new().range(0.0, max, 1.0).unwrap();
+        assert_eq!(grid.len(), MAX_RANGE_LEN);
+        assert!(ParameterGrid::new().range(0.0, max + 1.0, 1.0).is_err());
+
+        let grid = (0..usize::BITS).fold(ParameterGrid::new(), |grid, _| grid.axis(vec![0.0, 1.0]));
+        assert_eq!(grid.len(), usize::MAX);
+        let grid = grid.axis(vec![]);
+        assert_eq!(grid.len(), 0);
+    }
+
+    #[test]
+    fn test_grid_search() {
+        // a step is followed best by the shortest average
+        let input = [1.0, 1.0, 5.0, 5.0, 5.0, 5.0];
+        let grid = ParameterGrid::new().axis(vec![0.0, 3.0, 1.0, 2.0]);
+        let best = grid_search(&input, &grid, sma, forecast_error).unwrap();
+        assert_eq!(best.params, vec![1.0]);
+        assert_eq!(best.score, -4.0);
+    }
+
+    #[test]
+    fn test_grid_search_errors() {
+        let grid = ParameterGrid::new().axis(vec![0.0]);
+        assert!(grid_search(&[1.0, 2.0], &grid, sma, forecast_error).is_err());
+        let grid = ParameterGrid::new().axis(vec![1.0]);
+        assert!(grid_search(&[], &grid, sma, forecast_error).is_err());
+        assert!(grid_search(&[1.0], &grid, sma, |_, _| f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_walk_forward_splits() {
+        assert!(walk_forward_splits(10, 0, 2).is_err());
+        assert!(walk_forward_splits(10, 2, 0).is_err());
+        assert_eq!(walk_forward_splits(5, 4, 2).unwrap(), vec![]);
+        let splits = walk_forward_splits(9, 3, 2).unwrap();
+        assert_eq!(splits.len(), 3);
+        assert_eq!(
+            splits[2],
+            Split {
+                train: 4..7,
+                test: 7..9
+            }
+        );
+    }
+
+    #[test]
+    fn test_walk_forward() {
+        let input = [1.0, 2.0, 3.0, 4.0, 3.0, 2.0, 1.0, 2.0];
+        let grid = ParameterGrid::new().axis(vec![1.0, 2.0]);
+        let steps = walk_forward(&input, &grid, 4, 2, sma, forecast_error).unwrap();
+        assert_eq!(steps.len(), 2);
+
+        // the trend is followed best by the last input, and the warm-up is not scored
+        assert_eq!(
+            steps[0].split,
+            Split {
+                train: 0..4,
+                test: 4..6
+            }
+        );
+        assert_eq!(steps[0].params, vec![1.0]);
+        assert_eq!(steps[0].train_score, -3.0);
+        assert_eq!(steps[0].test_score, -1.0);
+
+        assert!(walk_forward(&input, &grid, 8, 2, sma, forecast_error).is_err());
+    }
+}
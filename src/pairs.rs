@@ -0,0 +1,412 @@
+//! Indicators over pairs of series for pairs trading and statistical arbitrage.
+//!
+//! All the indicators of this module take pairs `(a, b)` of inputs, e.g. the prices of two
+//! cointegrated assets. Together with [RollingCorrelation](crate::indicators::RollingCorrelation),
+//! [Beta] and [ZScore](crate::indicators::ZScore) they cover the usual building blocks of a
+//! pairs trading strategy.
+//!
+//! # Example
+//!
+//! Z-score of the spread between two assets, hedged with the rolling beta:
+//!
+//! ```
+//! use ta_panther::indicators::ZScore;
+//! use ta_panther::pairs::Spread;
+//! use ta_panther::Next;
+//!
+//! let mut spread = Spread::rolling(20).unwrap();
+//! let mut zscore = ZScore::new(20).unwrap();
+//!
+//! let prices = [(10.0, 5.0), (10.4, 5.1), (10.2, 5.2), (10.8, 5.3), (11.6, 5.4)];
+//! for &(a, b) in prices.iter() {
+//!     let signal = zscore.next(spread.next((a, b)));
+//!     assert!(signal.is_finite());
+//! }
+//! ```
+
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{ring, CoMoments};
+use crate::indicators::Beta;
+use crate::{Close, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling covariance.
+///
+/// Returns the population covariance of two series over the last _period_ pairs of inputs. The
+/// co-moments are updated incrementally as pairs enter and leave the window, so every update is
+/// O(1).
+///
+/// # Formula
+///
+/// _COV = Σ (a<sub>i</sub> - mean(a)) (b<sub>i</sub> - mean(b)) / n_
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::pairs::RollingCovariance;
+/// use ta_panther::Next;
+///
+/// let mut cov = RollingCovariance::new(2).unwrap();
+/// assert_eq!(cov.next((1.0, 10.0)), 0.0);
+/// assert_eq!(cov.next((3.0, 20.0)), 5.0);
+/// assert_eq!(cov.next((4.0, 10.0)), -2.5);
+/// ```
+///
+/// # Links
+///
+/// * [Covariance, Wikipedia](https://en.wikipedia.org/wiki/Covariance)
+///
+#[doc(alias = "COV")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingCovariance {
+    period: usize,
+    index: usize,
+    count: usize,
+    moments: CoMoments,
+    deque: Box<[(f64, f64)]>,
+}
+
+impl RollingCovariance {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                index: 0,
+                count: 0,
+                moments: CoMoments::default(),
+                deque: ring((0.0, 0.0), period)?,
+            }),
+        }
+    }
+}
+
+impl Period for RollingCovariance {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<(f64, f64)> for RollingCovariance {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let (old_a, old_b) = self.deque[self.index];
+            self.moments.pop(old_a, old_b);
+        }
+        self.moments.push(a, b);
+
+        self.deque[self.index] = (a, b);
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.moments.covariance()
+    }
+}
+
+impl<T: Close> Next<(&T, &T)> for RollingCovariance {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (&T, &T)) -> Self::Output {
+        self.next((a.close(), b.close()))
+    }
+}
+
+impl Reset for RollingCovariance {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.moments = CoMoments::default();
+        for i in 0..self.period {
+            self.deque[i] = (0.0, 0.0);
+        }
+    }
+}
+
+impl Default for RollingCovariance {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RollingCovariance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "COV({})", self.period)
+    }
+}
+
+/// Spread.
+///
+/// Returns the difference between the first series and the second one scaled by a hedge ratio.
+/// The hedge ratio is either fixed, e.g. estimated offline by a cointegration test, or the
+/// rolling [Beta] of the first series against the second one over the last _period_ pairs.
+///
+/// # Formula
+///
+/// _SPREAD = a - hedge · b_
+///
+/// # Parameters
+///
+/// * _hedge_ - fixed hedge ratio, see [new](Spread::new). Default is 1.0.
+/// * _period_ - number of periods of the rolling hedge ratio (integer greater than 0), see
+///   [rolling](Spread::rolling).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::pairs::Spread;
+/// use ta_panther::Next;
+///
+/// let mut spread = Spread::new(2.0).unwrap();
+/// assert_eq!(spread.next((10.0, 4.0)), 2.0);
+/// assert_eq!(spread.next((9.0, 5.0)), -1.0);
+/// ```
+///
+#[doc(alias = "SPREAD")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spread {
+    hedge: Hedge,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Hedge {
+    Fixed(f64),
+    Rolling(Beta),
+}
+
+impl Spread {
+    /// Creates a spread with a fixed hedge ratio.
+    pub fn new(hedge: f64) -> Result<Self> {
+        if !hedge.is_finite() {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            hedge: Hedge::Fixed(hedge),
+        })
+    }
+
+    /// Creates a spread hedged with the rolling beta over the last `period` pairs.
+    pub fn rolling(period: usize) -> Result<Self> {
+        Ok(Self {
+            hedge: Hedge::Rolling(Beta::new(period)?),
+        })
+    }
+}
+
+impl Next<(f64, f64)> for Spread {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        let hedge = match &mut self.hedge {
+            Hedge::Fixed(hedge) => *hedge,
+            Hedge::Rolling(beta) => beta.next((a, b)),
+        };
+        a - hedge * b
+    }
+}
+
+impl<T: Close> Next<(&T, &T)> for Spread {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (&T, &T)) -> Self::Output {
+        self.next((a.close(), b.close()))
+    }
+}
+
+impl Reset for Spread {
+    fn reset(&mut self) {
+        if let Hedge::Rolling(beta) = &mut self.hedge {
+            beta.reset();
+        }
+    }
+}
+
+impl Default for Spread {
+    fn default() -> Self {
+        Self::new(1.0).unwrap()
+    }
+}
+
+impl fmt::Display for Spread {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.hedge {
+            Hedge::Fixed(hedge) => write!(f, "SPREAD({})", hedge),
+            Hedge::Rolling(beta) => write!(f, "SPREAD({})", beta),
+        }
+    }
+}
+
+/// Ratio.
+///
+/// Returns the ratio of the first series to the second one, the simplest relative value measure
+/// of a pair. The output is infinite or NaN if the second series is 0.
+///
+/// # Formula
+///
+/// _RATIO = a / b_
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::pairs::Ratio;
+/// use ta_panther::Next;
+///
+/// let mut ratio = Ratio::new();
+/// assert_eq!(ratio.next((10.0, 4.0)), 2.5);
+/// ```
+///
+#[doc(alias = "RATIO")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ratio {}
+
+impl Ratio {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Next<(f64, f64)> for Ratio {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        a / b
+    }
+}
+
+impl<T: Close> Next<(&T, &T)> for Ratio {
+    type Output = f64;
+
+    fn next(&mut self, (a, b): (&T, &T)) -> Self::Output {
+        self.next((a.close(), b.close()))
+    }
+}
+
+impl Reset for Ratio {
+    fn reset(&mut self) {}
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RATIO")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_covariance_new() {
+        assert!(RollingCovariance::new(0).is_err());
+        assert!(RollingCovariance::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_covariance_next() {
+        let mut cov = RollingCovariance::new(3).unwrap();
+        assert_eq!(cov.next((1.0, 5.0)), 0.0);
+        assert_eq!(round(cov.next((2.0, 3.0))), -0.5);
+        assert_eq!(round(cov.next((4.0, 4.0))), -0.333);
+        assert_eq!(round(cov.next((3.0, 6.0))), 0.333);
+        assert_eq!(round(cov.next((3.0, 6.0))), -0.444);
+    }
+
+    #[test]
+    fn test_covariance_reset() {
+        let mut cov = RollingCovariance::new(3).unwrap();
+        cov.next((1.0, 2.0));
+        cov.next((2.0, 4.0));
+
+        cov.reset();
+        assert_eq!(cov.next((1.0, 1.0)), 0.0);
+        assert_eq!(cov.next((3.0, 3.0)), 1.0);
+    }
+
+    #[test]
+    fn test_spread_new() {
+        assert!(Spread::new(f64::NAN).is_err());
+        assert!(Spread::new(-0.5).is_ok());
+        assert!(Spread::rolling(0).is_err());
+        assert!(Spread::rolling(1).is_ok());
+    }
+
+    #[test]
+    fn test_spread_next() {
+        let mut spread = Spread::new(0.5).unwrap();
+        assert_eq!(spread.next((10.0, 4.0)), 8.0);
+        assert_eq!(spread.next((3.0, 8.0)), -1.0);
+
+        let mut spread = Spread::rolling(3).unwrap();
+        assert_eq!(spread.next((1.0, 5.0)), 1.0);
+        assert_eq!(round(spread.next((2.0, 3.0))), 3.5);
+        assert_eq!(round(spread.next((4.0, 4.0))), 6.0);
+        assert_eq!(round(spread.next((3.0, 6.0))), 1.714);
+        assert_eq!(round(spread.next((3.0, 6.0))), 6.0);
+    }
+
+    #[test]
+    fn test_spread_reset() {
+        let mut spread = Spread::rolling(3).unwrap();
+        spread.next((1.0, 5.0));
+        spread.next((2.0, 3.0));
+
+        spread.reset();
+        assert_eq!(spread.next((2.0, 3.0)), 2.0);
+    }
+
+    #[test]
+    fn test_ratio_next() {
+        let mut ratio = Ratio::new();
+        assert_eq!(ratio.next((3.0, 4.0)), 0.75);
+        assert_eq!(ratio.next((5.0, 2.0)), 2.5);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let (a, b) = (Bar::new().close(6), Bar::new().close(4));
+        assert_eq!(RollingCovariance::new(2).unwrap().next((&a, &b)), 0.0);
+        assert_eq!(Spread::new(1.0).unwrap().next((&a, &b)), 2.0);
+        assert_eq!(Ratio::new().next((&a, &b)), 1.5);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingCovariance::default();
+        Spread::default();
+        Ratio::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", RollingCovariance::new(10).unwrap()),
+            "COV(10)"
+        );
+        assert_eq!(format!("{}", Spread::new(1.5).unwrap()), "SPREAD(1.5)");
+        assert_eq!(
+            format!("{}", Spread::rolling(20).unwrap()),
+            "SPREAD(BETA(20))"
+        );
+        assert_eq!(format!("{}", Ratio::new()), "RATIO");
+    }
+}
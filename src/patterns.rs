@@ -0,0 +1,618 @@
+//! Bar patterns of breakout setups and swing points of the market structure.
+//!
+//! The detectors compare the range of every bar, from its low to its high, with the ranges of
+//! the previous bars, and return `true` on the bars completing a pattern:
+//!
+//! * [InsideBar] - the range of the bar lies within the range of the previous bar
+//! * [OutsideBar] - the range of the bar engulfs the range of the previous bar
+//! * [NarrowRange] - the range of the bar is the narrowest of the last _n_ bars, e.g. NR4 or NR7
+//!
+//! All these patterns signal a contraction or an expansion of the volatility, so they are
+//! usually combined, e.g. an inside bar which is also an NR4 bar, and traded on the breakout of
+//! the range of the pattern bar.
+//!
+//! [SwingPoints] detects the pivot highs and lows of the price, which delimit the swings of the
+//! market and are the foundation of support and resistance levels.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::patterns::{InsideBar, NarrowRange};
+//! use ta_panther::{DataItem, Next};
+//!
+//! let bar = |high: f64, low: f64| {
+//!     DataItem::builder()
+//!         .open(low)
+//!         .high(high)
+//!         .low(low)
+//!         .close(high)
+//!         .volume(1000.0)
+//!         .build()
+//!         .unwrap()
+//! };
+//!
+//! let mut inside = InsideBar::new();
+//! let mut nr4 = NarrowRange::new(4).unwrap();
+//! let mut setups = Vec::new();
+//! for bar in [bar(12.0, 8.0), bar(13.0, 9.0), bar(12.0, 7.0), bar(11.0, 9.5)] {
+//!     let (is_inside, is_nr4) = (inside.next(&bar), nr4.next(&bar));
+//!     setups.push(is_inside && is_nr4);
+//! }
+//! assert_eq!(setups, [false, false, false, true]);
+//! ```
+
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::rolling::RollingWindow;
+use crate::{High, IsReady, Low, Next, Period, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Inside bar.
+///
+/// Returns `true` if the high of the bar is lower than the previous high and its low is higher
+/// than the previous low, i.e. the bar is contained in the previous one. Returns `false` on the
+/// first bar.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::patterns::InsideBar;
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64| {
+///     DataItem::builder()
+///         .open(low)
+///         .high(high)
+///         .low(low)
+///         .close(high)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut inside = InsideBar::new();
+/// assert!(!inside.next(&bar(12.0, 8.0)));
+/// assert!(inside.next(&bar(11.0, 9.0)));
+/// assert!(!inside.next(&bar(11.0, 8.0)));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InsideBar {
+    previous: Option<(f64, f64)>,
+}
+
+impl InsideBar {
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+}
+
+impl<T: High + Low> Next<&T> for InsideBar {
+    type Output = bool;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let (high, low) = (input.high(), input.low());
+        match self.previous.replace((high, low)) {
+            Some((prev_high, prev_low)) => high < prev_high && low > prev_low,
+            None => false,
+        }
+    }
+}
+
+impl Reset for InsideBar {
+    fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+impl Default for InsideBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for InsideBar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "INSIDE_BAR")
+    }
+}
+
+/// Outside bar.
+///
+/// Returns `true` if the high of the bar is higher than the previous high and its low is lower
+/// than the previous low, i.e. the bar engulfs the previous one. Returns `false` on the first
+/// bar.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::patterns::OutsideBar;
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64| {
+///     DataItem::builder()
+///         .open(low)
+///         .high(high)
+///         .low(low)
+///         .close(high)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut outside = OutsideBar::new();
+/// assert!(!outside.next(&bar(11.0, 9.0)));
+/// assert!(outside.next(&bar(12.0, 8.0)));
+/// assert!(!outside.next(&bar(13.0, 8.0)));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OutsideBar {
+    previous: Option<(f64, f64)>,
+}
+
+impl OutsideBar {
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+}
+
+impl<T: High + Low> Next<&T> for OutsideBar {
+    type Output = bool;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let (high, low) = (input.high(), input.low());
+        match self.previous.replace((high, low)) {
+            Some((prev_high, prev_low)) => high > prev_high && low < prev_low,
+            None => false,
+        }
+    }
+}
+
+impl Reset for OutsideBar {
+    fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+impl Default for OutsideBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for OutsideBar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OUTSIDE_BAR")
+    }
+}
+
+/// Narrow range bar (NR<sub>n</sub>).
+///
+/// Returns `true` if the range of the bar, _High - Low_, is narrower than the ranges of each of
+/// the previous _period_ - 1 bars. NR4 and NR7 bars, narrower than the 3 or 6 previous bars,
+/// are the usual setups. Returns `false` until _period_ bars have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of bars including the current one (integer greater than 1). Default is 7.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::patterns::NarrowRange;
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64| {
+///     DataItem::builder()
+///         .open(low)
+///         .high(high)
+///         .low(low)
+///         .close(high)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut nr4 = NarrowRange::new(4).unwrap();
+/// assert!(!nr4.next(&bar(12.0, 8.0)));
+/// assert!(!nr4.next(&bar(12.0, 9.0)));
+/// assert!(!nr4.next(&bar(13.0, 11.0)));
+/// assert!(nr4.next(&bar(12.0, 11.0)));
+/// ```
+#[doc(alias = "NR4")]
+#[doc(alias = "NR7")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NarrowRange {
+    ranges: RollingWindow<f64>,
+}
+
+impl NarrowRange {
+    pub fn new(period: usize) -> Result<Self> {
+        if period < 2 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            ranges: RollingWindow::new(period)?,
+        })
+    }
+}
+
+impl Period for NarrowRange {
+    fn period(&self) -> usize {
+        self.ranges.capacity()
+    }
+}
+
+impl IsReady for NarrowRange {
+    fn bars_remaining(&self) -> usize {
+        self.ranges.capacity() - self.ranges.len()
+    }
+}
+
+impl<T: High + Low> Next<&T> for NarrowRange {
+    type Output = bool;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = input.high() - input.low();
+        self.ranges.push(range);
+        self.ranges.is_full()
+            && self
+                .ranges
+                .iter()
+                .take(self.ranges.len() - 1)
+                .all(|&other| range < other)
+    }
+}
+
+impl Reset for NarrowRange {
+    fn reset(&mut self) {
+        self.ranges.clear();
+    }
+}
+
+impl Default for NarrowRange {
+    fn default() -> Self {
+        Self::new(7).unwrap()
+    }
+}
+
+impl fmt::Display for NarrowRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NR{}", self.period())
+    }
+}
+
+/// Kind of a [SwingPoint].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SwingKind {
+    /// Pivot high, a local maximum of the highs.
+    High,
+    /// Pivot low, a local minimum of the lows.
+    Low,
+}
+
+/// Confirmed pivot of a [SwingPoints] detector.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwingPoint {
+    pub kind: SwingKind,
+    /// High of a pivot high or low of a pivot low.
+    pub price: f64,
+    /// Number of bars between the pivot and the bar confirming it, which is _right_.
+    pub offset: usize,
+}
+
+/// Swing points confirmed by a bar, see [SwingPoints].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwingPointsOutput {
+    pub high: Option<SwingPoint>,
+    pub low: Option<SwingPoint>,
+}
+
+impl SwingPointsOutput {
+    /// Returns the confirmed pivots, the high first.
+    pub fn iter(&self) -> impl Iterator<Item = SwingPoint> {
+        self.high.into_iter().chain(self.low)
+    }
+}
+
+/// Swing highs and lows, also known as pivot points or fractals.
+///
+/// A bar is a pivot high if its high is higher than the highs of the _left_ bars before it and
+/// not lower than the highs of the _right_ bars after it, and symmetrically for pivot lows. The
+/// pivot is thus confirmed _right_ bars after it occurred, by the bar which returns it with its
+/// price and its offset. Of equal highs, only the first one can be a pivot. A single bar can be
+/// both a pivot high and a pivot low.
+///
+/// The strength of the detected swings grows with _left_ and _right_: Bill Williams' fractals
+/// are swing points with a strength of 2 on both sides.
+///
+/// # Parameters
+///
+/// * _left_ - number of bars before the pivot (integer greater than 0). Default is 2.
+/// * _right_ - number of bars after the pivot, i.e. the confirmation delay (integer). Default
+///   is 2.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::patterns::{SwingKind, SwingPoints};
+/// use ta_panther::{DataItem, Next};
+///
+/// let bar = |high: f64, low: f64| {
+///     DataItem::builder()
+///         .open(low)
+///         .high(high)
+///         .low(low)
+///         .close(high)
+///         .volume(1000.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut swings = SwingPoints::new(1, 1).unwrap();
+/// assert_eq!(swings.next(&bar(10.0, 8.0)).high, None);
+/// assert_eq!(swings.next(&bar(12.0, 9.0)).high, None);
+/// let pivot = swings.next(&bar(11.0, 7.0)).high.unwrap();
+/// assert_eq!((pivot.kind, pivot.price, pivot.offset), (SwingKind::High, 12.0, 1));
+/// ```
+///
+/// # Links
+///
+/// * [Pivot point, Wikipedia](https://en.wikipedia.org/wiki/Pivot_point_(technical_analysis))
+///
+#[doc(alias = "PIVOTS")]
+#[doc(alias = "FRACTALS")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwingPoints {
+    left: usize,
+    right: usize,
+    /// Highs and lows of the last _left_ + _right_ + 1 bars.
+    bars: RollingWindow<(f64, f64)>,
+}
+
+impl SwingPoints {
+    pub fn new(left: usize, right: usize) -> Result<Self> {
+        if left == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        let len = left
+            .checked_add(right)
+            .and_then(|len| len.checked_add(1))
+            .ok_or(TaError::InvalidParameter)?;
+        Ok(Self {
+            left,
+            right,
+            bars: RollingWindow::new(len)?,
+        })
+    }
+
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    fn pivot(&self, kind: SwingKind) -> Option<SwingPoint> {
+        let price = |(high, low): (f64, f64)| match kind {
+            SwingKind::High => high,
+            SwingKind::Low => -low,
+        };
+        let candidate = price(self.bars[self.left]);
+        let bars = self.bars.iter().map(|&bar| price(bar));
+        let (mut before, mut after) = (bars.clone().take(self.left), bars.skip(self.left + 1));
+        let is_pivot =
+            before.all(|other| candidate > other) && after.all(|other| candidate >= other);
+        is_pivot.then(|| SwingPoint {
+            kind,
+            price: match kind {
+                SwingKind::High => candidate,
+                SwingKind::Low => -candidate,
+            },
+            offset: self.right,
+        })
+    }
+}
+
+impl Period for SwingPoints {
+    fn period(&self) -> usize {
+        self.bars.capacity()
+    }
+}
+
+impl IsReady for SwingPoints {
+    fn bars_remaining(&self) -> usize {
+        self.bars.capacity() - self.bars.len()
+    }
+}
+
+impl Validate for SwingPoints {
+    fn validate(&self) -> Result<()> {
+        self.bars.validate()?;
+        if self.left > 0 && self.bars.capacity() == self.left + self.right + 1 {
+            Ok(())
+        } else {
+            Err(TaError::InvalidState)
+        }
+    }
+}
+
+impl<T: High + Low> Next<&T> for SwingPoints {
+    type Output = SwingPointsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.bars.push((input.high(), input.low()));
+        if !self.bars.is_full() {
+            return SwingPointsOutput::default();
+        }
+        SwingPointsOutput {
+            high: self.pivot(SwingKind::High),
+            low: self.pivot(SwingKind::Low),
+        }
+    }
+}
+
+impl Reset for SwingPoints {
+    fn reset(&mut self) {
+        self.bars.clear();
+    }
+}
+
+impl Default for SwingPoints {
+    fn default() -> Self {
+        Self::new(2, 2).unwrap()
+    }
+}
+
+impl fmt::Display for SwingPoints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SWINGS({}, {})", self.left, self.right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64) -> Bar {
+        Bar::new().high(high).low(low)
+    }
+
+    #[test]
+    fn test_inside_bar() {
+        let mut inside = InsideBar::new();
+        assert!(!inside.next(&bar(12.0, 8.0)));
+        assert!(inside.next(&bar(11.0, 9.0)));
+        // equal highs are not inside
+        assert!(!inside.next(&bar(11.0, 9.5)));
+        assert!(inside.next(&bar(10.5, 9.6)));
+
+        inside.reset();
+        assert!(!inside.next(&bar(10.0, 9.8)));
+        assert_eq!(InsideBar::default().to_string(), "INSIDE_BAR");
+    }
+
+    #[test]
+    fn test_outside_bar() {
+        let mut outside = OutsideBar::new();
+        assert!(!outside.next(&bar(11.0, 9.0)));
+        assert!(outside.next(&bar(12.0, 8.0)));
+        // equal lows are not outside
+        assert!(!outside.next(&bar(13.0, 8.0)));
+        assert!(!outside.next(&bar(12.5, 8.5)));
+
+        outside.reset();
+        assert!(!outside.next(&bar(20.0, 1.0)));
+        assert_eq!(OutsideBar::default().to_string(), "OUTSIDE_BAR");
+    }
+
+    #[test]
+    fn test_narrow_range() {
+        assert!(NarrowRange::new(0).is_err());
+        assert!(NarrowRange::new(1).is_err());
+
+        let mut nr4 = NarrowRange::new(4).unwrap();
+        let ranges = [4.0, 3.0, 2.0, 1.0, 1.0, 0.5, 3.0, 2.0, 2.0, 0.4];
+        let expected = [
+            false, false, false, true, false, true, false, false, false, true,
+        ];
+        for (&range, &expected) in ranges.iter().zip(expected.iter()) {
+            assert_eq!(nr4.next(&bar(10.0 + range, 10.0)), expected);
+        }
+        assert!(nr4.is_ready());
+
+        nr4.reset();
+        assert_eq!(nr4.bars_remaining(), 4);
+        assert!(!nr4.next(&bar(10.0, 9.9)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NarrowRange::default().to_string(), "NR7");
+        assert_eq!(NarrowRange::new(4).unwrap().period(), 4);
+    }
+
+    #[test]
+    fn test_swing_points() {
+        assert!(SwingPoints::new(0, 2).is_err());
+        assert!(SwingPoints::new(usize::MAX, 1).is_err());
+        assert!(SwingPoints::new(1, 0).is_ok());
+
+        let mut swings = SwingPoints::new(2, 1).unwrap();
+        let bars = [
+            (10.0, 9.0),
+            (11.0, 10.0),
+            (13.0, 11.0),
+            (12.0, 8.0),
+            (12.5, 9.0),
+            (13.0, 9.5),
+            (14.0, 10.0),
+        ];
+        let outputs: Vec<_> = bars
+            .iter()
+            .map(|&(high, low)| swings.next(&bar(high, low)))
+            .collect();
+        assert!(outputs[..3].iter().all(|output| output.iter().count() == 0));
+        let high = SwingPoint {
+            kind: SwingKind::High,
+            price: 13.0,
+            offset: 1,
+        };
+        assert_eq!(
+            outputs[3],
+            SwingPointsOutput {
+                high: Some(high),
+                low: None
+            }
+        );
+        let low = SwingPoint {
+            kind: SwingKind::Low,
+            price: 8.0,
+            offset: 1,
+        };
+        assert_eq!(
+            outputs[4],
+            SwingPointsOutput {
+                high: None,
+                low: Some(low)
+            }
+        );
+        // the high of 13.0 is exceeded by the next bar
+        assert_eq!(outputs[6], SwingPointsOutput::default());
+        assert!(swings.is_ready());
+        assert_eq!(swings.validate(), Ok(()));
+
+        swings.reset();
+        assert_eq!(swings.bars_remaining(), 4);
+    }
+
+    #[test]
+    fn test_swing_points_plateau() {
+        // without bars after the pivot, every new extreme is confirmed at once
+        let mut swings = SwingPoints::new(1, 0).unwrap();
+        swings.next(&bar(10.0, 9.0));
+        assert!(swings.next(&bar(11.0, 9.5)).high.is_some());
+        assert!(swings.next(&bar(11.0, 9.5)).high.is_none());
+
+        let mut swings = SwingPoints::new(1, 1).unwrap();
+        swings.next(&bar(10.0, 9.0));
+        swings.next(&bar(11.0, 9.5));
+        assert!(swings.next(&bar(11.0, 9.5)).high.is_some());
+        // both a pivot high and a pivot low
+        swings.next(&bar(12.0, 9.0));
+        let output = swings.next(&bar(11.5, 10.0));
+        assert_eq!(output.iter().count(), 2);
+        assert_eq!(SwingPoints::default().to_string(), "SWINGS(2, 2)");
+    }
+}
@@ -0,0 +1,490 @@
+//! Risk-adjusted performance ratios over rolling windows of returns.
+//!
+//! All the indicators of this module take the simple returns of a strategy or an asset per
+//! period, e.g. `0.01` for a gain of 1%, rather than prices. The ratios are annualized with the
+//! number of periods per year, e.g. 252 for daily returns of stocks or 12 for monthly returns,
+//! and the risk-free rate is an annual rate.
+//!
+//! # Example
+//!
+//! Sharpe ratio of the daily returns of the last quarter:
+//!
+//! ```
+//! use ta_panther::performance::SharpeRatio;
+//! use ta_panther::Next;
+//!
+//! let mut sharpe = SharpeRatio::new(63, 0.02, 252.0).unwrap();
+//! let returns = [0.004, -0.002, 0.003, 0.001, -0.001];
+//! for &r in returns.iter() {
+//!     assert!(sharpe.next(r).is_finite());
+//! }
+//! ```
+
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{ring, Moments};
+use crate::{Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+fn validate(period: usize, risk_free: f64, periods_per_year: f64) -> Result<()> {
+    if period == 0
+        || !risk_free.is_finite()
+        || !(periods_per_year > 0.0 && periods_per_year.is_finite())
+    {
+        Err(TaError::InvalidParameter)
+    } else {
+        Ok(())
+    }
+}
+
+/// Sharpe ratio.
+///
+/// Returns the mean excess return over the risk-free rate of the last _period_ returns, divided
+/// by their sample standard deviation and annualized. Returns 0 until two returns have been seen,
+/// or while the returns are constant.
+///
+/// # Formula
+///
+/// _Sharpe = √periods_per_year × mean(r<sub>i</sub> - r<sub>f</sub>) / stddev(r<sub>i</sub>)_
+///
+/// Where _r<sub>f</sub>_ is the risk-free rate per period, _risk_free / periods_per_year_.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0). Default is 252.
+/// * _risk_free_ - annual risk-free rate. Default is 0.0.
+/// * _periods_per_year_ - annualization factor (greater than 0.0). Default is 252.0.
+///
+/// # Links
+///
+/// * [Sharpe ratio, Wikipedia](https://en.wikipedia.org/wiki/Sharpe_ratio)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SharpeRatio {
+    period: usize,
+    risk_free: f64,
+    periods_per_year: f64,
+    index: usize,
+    count: usize,
+    moments: Moments,
+    deque: Box<[f64]>,
+}
+
+impl SharpeRatio {
+    pub fn new(period: usize, risk_free: f64, periods_per_year: f64) -> Result<Self> {
+        validate(period, risk_free, periods_per_year)?;
+        Ok(Self {
+            period,
+            risk_free,
+            periods_per_year,
+            index: 0,
+            count: 0,
+            moments: Moments::default(),
+            deque: ring(0.0, period)?,
+        })
+    }
+}
+
+impl Period for SharpeRatio {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for SharpeRatio {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let excess = input - self.risk_free / self.periods_per_year;
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            self.moments.pop(self.deque[self.index]);
+        }
+        self.moments.push(excess);
+
+        self.deque[self.index] = excess;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let mean = self.moments.mean();
+        let sd = self.moments.sample_variance().sqrt();
+
+        // deviations at the level of rounding errors are treated as no deviation
+        if sd <= 1e-9 * mean.abs() || sd == 0.0 {
+            0.0
+        } else {
+            self.periods_per_year.sqrt() * mean / sd
+        }
+    }
+}
+
+impl Reset for SharpeRatio {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.moments = Moments::default();
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for SharpeRatio {
+    fn default() -> Self {
+        Self::new(252, 0.0, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for SharpeRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SHARPE({}, {}, {})",
+            self.period, self.risk_free, self.periods_per_year
+        )
+    }
+}
+
+/// Sortino ratio.
+///
+/// Like the [SharpeRatio], but divides by the downside deviation, so only returns below the
+/// risk-free rate count as risk. Returns 0 while none of the last _period_ returns is below the
+/// risk-free rate.
+///
+/// # Formula
+///
+/// _Sortino = √periods_per_year × mean(r<sub>i</sub> - r<sub>f</sub>) / DD_, with
+/// _DD = √(Σ min(0, r<sub>i</sub> - r<sub>f</sub>)<sup>2</sup> / n)_
+///
+/// Where _r<sub>f</sub>_ is the risk-free rate per period, _risk_free / periods_per_year_.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0). Default is 252.
+/// * _risk_free_ - annual risk-free rate, also the target return. Default is 0.0.
+/// * _periods_per_year_ - annualization factor (greater than 0.0). Default is 252.0.
+///
+/// # Links
+///
+/// * [Sortino ratio, Wikipedia](https://en.wikipedia.org/wiki/Sortino_ratio)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SortinoRatio {
+    period: usize,
+    risk_free: f64,
+    periods_per_year: f64,
+    index: usize,
+    count: usize,
+    sum: f64,
+    downside_sum: f64,
+    deque: Box<[f64]>,
+}
+
+impl SortinoRatio {
+    pub fn new(period: usize, risk_free: f64, periods_per_year: f64) -> Result<Self> {
+        validate(period, risk_free, periods_per_year)?;
+        Ok(Self {
+            period,
+            risk_free,
+            periods_per_year,
+            index: 0,
+            count: 0,
+            sum: 0.0,
+            downside_sum: 0.0,
+            deque: ring(0.0, period)?,
+        })
+    }
+}
+
+impl Period for SortinoRatio {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for SortinoRatio {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let excess = input - self.risk_free / self.periods_per_year;
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            let old = self.deque[self.index];
+            self.sum -= old;
+            self.downside_sum -= old.min(0.0).powi(2);
+        }
+        self.sum += excess;
+        self.downside_sum += excess.min(0.0).powi(2);
+
+        self.deque[self.index] = excess;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        let n = self.count as f64;
+        // the rolling sum of squares can drift slightly below 0
+        let downside = (self.downside_sum / n).max(0.0).sqrt();
+        if downside <= 1e-12 {
+            0.0
+        } else {
+            self.periods_per_year.sqrt() * self.sum / n / downside
+        }
+    }
+}
+
+impl Reset for SortinoRatio {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        self.downside_sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for SortinoRatio {
+    fn default() -> Self {
+        Self::new(252, 0.0, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for SortinoRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SORTINO({}, {}, {})",
+            self.period, self.risk_free, self.periods_per_year
+        )
+    }
+}
+
+/// Calmar ratio.
+///
+/// Returns the annualized compound return of the last _period_ returns divided by the maximum
+/// drawdown of the equity curve they form. Returns 0 while the window has no drawdown. Every
+/// update replays the window, so it is O(period).
+///
+/// Traditionally the Calmar ratio is computed over 36 months, e.g. `CalmarRatio::new(36, 12.0)`.
+/// Unlike the [SharpeRatio] and the [SortinoRatio] it takes no risk-free rate.
+///
+/// # Formula
+///
+/// _Calmar = ((Π (1 + r<sub>i</sub>))<sup>periods_per_year / n</sup> - 1) / MDD_
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0). Default is 36.
+/// * _periods_per_year_ - annualization factor (greater than 0.0). Default is 12.0.
+///
+/// # Links
+///
+/// * [Calmar ratio, Wikipedia](https://en.wikipedia.org/wiki/Calmar_ratio)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CalmarRatio {
+    period: usize,
+    periods_per_year: f64,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+}
+
+impl CalmarRatio {
+    pub fn new(period: usize, periods_per_year: f64) -> Result<Self> {
+        validate(period, 0.0, periods_per_year)?;
+        Ok(Self {
+            period,
+            periods_per_year,
+            index: 0,
+            count: 0,
+            deque: ring(0.0, period)?,
+        })
+    }
+}
+
+impl Period for CalmarRatio {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for CalmarRatio {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.deque[self.index] = input;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        // replay the equity curve of the window, starting from 1
+        let oldest = (self.index + self.period - self.count) % self.period;
+        let mut equity = 1.0;
+        let mut peak: f64 = 1.0;
+        let mut max_drawdown: f64 = 0.0;
+        for i in 0..self.count {
+            equity *= 1.0 + self.deque[(oldest + i) % self.period];
+            peak = peak.max(equity);
+            max_drawdown = max_drawdown.max(1.0 - equity / peak);
+        }
+
+        if max_drawdown <= 0.0 {
+            0.0
+        } else {
+            let annual = equity.powf(self.periods_per_year / self.count as f64) - 1.0;
+            annual / max_drawdown
+        }
+    }
+}
+
+impl Reset for CalmarRatio {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for CalmarRatio {
+    fn default() -> Self {
+        Self::new(36, 12.0).unwrap()
+    }
+}
+
+impl fmt::Display for CalmarRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CALMAR({}, {})", self.period, self.periods_per_year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    const RETURNS: [f64; 6] = [0.01, -0.02, 0.015, 0.005, -0.01, 0.02];
+
+    fn outputs<I: Next<f64, Output = f64>>(indicator: &mut I) -> Vec<f64> {
+        RETURNS.iter().map(|&r| round(indicator.next(r))).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SharpeRatio::new(0, 0.0, 252.0).is_err());
+        assert!(SharpeRatio::new(20, f64::NAN, 252.0).is_err());
+        assert!(SortinoRatio::new(20, 0.0, 0.0).is_err());
+        assert!(CalmarRatio::new(0, 12.0).is_err());
+        assert!(CalmarRatio::new(36, -12.0).is_err());
+        assert!(SharpeRatio::new(1, -0.01, 1.0).is_ok());
+        assert!(SortinoRatio::new(1, 0.05, 1.0).is_ok());
+        assert!(CalmarRatio::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_sharpe_next() {
+        let mut sharpe = SharpeRatio::new(4, 0.0, 252.0).unwrap();
+        assert_eq!(
+            outputs(&mut sharpe),
+            vec![0.0, -3.742, 1.398, 2.553, -2.553, 9.0]
+        );
+
+        let mut sharpe = SharpeRatio::new(4, 0.0252, 252.0).unwrap();
+        assert_eq!(
+            outputs(&mut sharpe),
+            vec![0.0, -3.816, 1.314, 2.451, -2.655, 8.88]
+        );
+
+        let mut sharpe = SharpeRatio::new(3, 0.0, 252.0).unwrap();
+        assert_eq!(sharpe.next(0.01), 0.0);
+        assert_eq!(sharpe.next(0.01), 0.0);
+    }
+
+    #[test]
+    fn test_sortino_next() {
+        let mut sortino = SortinoRatio::new(4, 0.0, 252.0).unwrap();
+        assert_eq!(
+            outputs(&mut sortino),
+            vec![0.0, -5.612, 2.291, 3.969, -3.55, 23.812]
+        );
+
+        let mut sortino = SortinoRatio::new(4, 0.0252, 252.0).unwrap();
+        assert_eq!(
+            outputs(&mut sortino),
+            vec![0.0, -5.696, 2.143, 3.791, -3.67, 23.262]
+        );
+    }
+
+    #[test]
+    fn test_calmar_next() {
+        let mut calmar = CalmarRatio::new(4, 12.0).unwrap();
+        assert_eq!(
+            outputs(&mut calmar),
+            vec![0.0, -2.983, 0.936, 1.465, -1.533, 9.296]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sharpe = SharpeRatio::new(4, 0.0, 252.0).unwrap();
+        let mut sortino = SortinoRatio::new(4, 0.0, 252.0).unwrap();
+        let mut calmar = CalmarRatio::new(4, 12.0).unwrap();
+        outputs(&mut sharpe);
+        outputs(&mut sortino);
+        outputs(&mut calmar);
+
+        sharpe.reset();
+        sortino.reset();
+        calmar.reset();
+        assert_eq!(outputs(&mut sharpe)[1], -3.742);
+        assert_eq!(outputs(&mut sortino)[1], -5.612);
+        assert_eq!(outputs(&mut calmar)[1], -2.983);
+    }
+
+    #[test]
+    fn test_default() {
+        SharpeRatio::default();
+        SortinoRatio::default();
+        CalmarRatio::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", SharpeRatio::new(252, 0.02, 252.0).unwrap()),
+            "SHARPE(252, 0.02, 252)"
+        );
+        assert_eq!(
+            format!("{}", SortinoRatio::new(20, 0.0, 12.0).unwrap()),
+            "SORTINO(20, 0, 12)"
+        );
+        assert_eq!(
+            format!("{}", CalmarRatio::new(36, 12.0).unwrap()),
+            "CALMAR(36, 12)"
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! Persistence of indicator state snapshots.
+//!
+//! Available with the `persistence` feature. The `redis` feature additionally enables
+//! [RedisStore].
+//!
+//! A [Checkpointer] periodically serializes the state of an indicator (or a whole stack of
+//! indicators, or an [IndicatorPool](crate::pool::IndicatorPool)) into a versioned [Snapshot]
+//! and writes it to a [StateStore]. On startup the latest snapshot is restored, so a long
+//! running process does not have to warm up its indicators again after a restart.
+//!
+//! The version of a snapshot is chosen by the application. Snapshots written with a different
+//! version are rejected on restore, which protects against loading state saved by an
+//! incompatible build. The state itself is stored as a [Versioned] state, so on restore it is
+//! [validated](Validate) and a corrupt or tampered state is rejected as well.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::indicators::ExponentialMovingAverage;
+//! use ta_panther::persistence::{Checkpointer, MemoryStore};
+//! use ta_panther::Next;
+//!
+//! let mut checkpointer = Checkpointer::new(MemoryStore::new(), "ema", 1).every(2);
+//!
+//! let mut ema = ExponentialMovingAverage::new(3).unwrap();
+//! for &price in &[2.0, 5.0, 1.0] {
+//!     ema.next(price);
+//!     checkpointer.tick(&ema).unwrap();
+//! }
+//!
+//! // snapshot written after the second input
+//! let mut restored: ExponentialMovingAverage = checkpointer.restore().unwrap().unwrap();
+//! assert_eq!(restored.next(1.0), 2.25);
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::state::Versioned;
+use crate::Validate;
+
+/// Error returned by persistence operations.
+#[derive(Debug)]
+pub enum PersistenceError {
+    /// The state store failed.
+    Store(Box<dyn Error + Send + Sync>),
+    /// The state could not be serialized or deserialized, or it failed the validation.
+    Serialization(serde_json::Error),
+    /// The stored snapshot has a different version than expected.
+    VersionMismatch { expected: u32, found: u32 },
+}
+
+impl fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PersistenceError::Store(err) => write!(f, "state store error: {}", err),
+            PersistenceError::Serialization(err) => write!(f, "serialization error: {}", err),
+            PersistenceError::VersionMismatch { expected, found } => write!(
+                f,
+                "snapshot version mismatch: expected {}, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl Error for PersistenceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PersistenceError::Store(err) => Some(err.as_ref()),
+            PersistenceError::Serialization(err) => Some(err),
+            PersistenceError::VersionMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for PersistenceError {
+    fn from(err: serde_json::Error) -> Self {
+        PersistenceError::Serialization(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, PersistenceError>;
+
+/// Key-value storage for serialized snapshots.
+pub trait StateStore {
+    /// Stores `data` under `key`, replacing the previous value.
+    fn save(&mut self, key: &str, data: &[u8]) -> Result<()>;
+
+    /// Returns the value stored under `key`, if any.
+    fn load(&mut self, key: &str) -> Result<Option<Vec<u8>>>;
+}
+
+/// In-memory [StateStore], mainly useful for tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    entries: HashMap<String, Vec<u8>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn save(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        self.entries.insert(key.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    fn load(&mut self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.get(key).cloned())
+    }
+}
+
+/// [StateStore] keeping the snapshots in Redis.
+///
+/// Available with the `redis` feature. Every key is prefixed with the configured prefix,
+/// so several applications can share a Redis database.
+#[cfg(feature = "redis")]
+pub struct RedisStore {
+    connection: redis::Connection,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisStore {
+    /// Connects to the Redis server at `url` (e.g. `redis://127.0.0.1/`).
+    pub fn open(url: &str, prefix: &str) -> Result<Self> {
+        let client = redis::Client::open(url).map_err(store_error)?;
+        let connection = client.get_connection().map_err(store_error)?;
+        Ok(Self::with_connection(connection, prefix))
+    }
+
+    /// Uses an already established connection.
+    pub fn with_connection(connection: redis::Connection, prefix: &str) -> Self {
+        Self {
+            connection,
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "redis")]
+impl StateStore for RedisStore {
+    fn save(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        use redis::Commands;
+        let key = format!("{}{}", self.prefix, key);
+        self.connection
+            .set::<_, _, ()>(key, data)
+            .map_err(store_error)
+    }
+
+    fn load(&mut self, key: &str) -> Result<Option<Vec<u8>>> {
+        use redis::Commands;
+        let key = format!("{}{}", self.prefix, key);
+        self.connection.get(key).map_err(store_error)
+    }
+}
+
+#[cfg(feature = "redis")]
+fn store_error(err: redis::RedisError) -> PersistenceError {
+    PersistenceError::Store(Box::new(err))
+}
+
+/// Serialized state together with its metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot<T> {
+    /// Version of the state format, chosen by the application.
+    pub version: u32,
+    /// Number of the snapshot, growing with every save.
+    pub sequence: u64,
+    /// Unix time in seconds when the snapshot was taken.
+    pub timestamp: u64,
+    pub state: T,
+}
+
+/// Periodically writes versioned snapshots of a state to a [StateStore].
+#[derive(Debug, Clone)]
+pub struct Checkpointer<S> {
+    store: S,
+    key: String,
+    version: u32,
+    interval: usize,
+    pending: usize,
+    sequence: u64,
+}
+
+impl<S: StateStore> Checkpointer<S> {
+    /// Creates a checkpointer saving snapshots of the given `version` under `key`.
+    ///
+    /// By default a snapshot is written on every [tick](Checkpointer::tick).
+    pub fn new(store: S, key: &str, version: u32) -> Self {
+        Self {
+            store,
+            key: key.to_string(),
+            version,
+            interval: 1,
+            pending: 0,
+            sequence: 0,
+        }
+    }
+
+    /// Writes a snapshot on every `interval`-th tick. An interval of 0 is treated as 1.
+    pub fn every(mut self, interval: usize) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Counts an update of `state`, writing a snapshot if the interval has elapsed.
+    ///
+    /// Returns `true` if a snapshot has been written.
+    pub fn tick<T: Serialize + Validate>(&mut self, state: &T) -> Result<bool> {
+        self.pending += 1;
+        if self.pending < self.interval {
+            return Ok(false);
+        }
+        self.save(state)?;
+        Ok(true)
+    }
+
+    /// Writes a snapshot of `state` immediately.
+    pub fn save<T: Serialize + Validate>(&mut self, state: &T) -> Result<()> {
+        let snapshot = Snapshot {
+            version: self.version,
+            sequence: self.sequence + 1,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            state: Versioned(state),
+        };
+        let data = serde_json::to_vec(&snapshot)?;
+        self.store.save(&self.key, &data)?;
+        self.sequence = snapshot.sequence;
+        self.pending = 0;
+        Ok(())
+    }
+
+    /// Loads the latest snapshot, returning `None` if nothing has been saved yet.
+    ///
+    /// Following snapshots continue the sequence of the restored one. Returns an error if the
+    /// state fails its [validation](Validate::validate).
+    pub fn restore_snapshot<T>(&mut self) -> Result<Option<Snapshot<T>>>
+    where
+        T: DeserializeOwned + Validate,
+    {
+        let data = match self.store.load(&self.key)? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let snapshot: Snapshot<serde_json::Value> = serde_json::from_slice(&data)?;
+        if snapshot.version != self.version {
+            return Err(PersistenceError::VersionMismatch {
+                expected: self.version,
+                found: snapshot.version,
+            });
+        }
+        self.sequence = snapshot.sequence;
+        Ok(Some(Snapshot {
+            version: snapshot.version,
+            sequence: snapshot.sequence,
+            timestamp: snapshot.timestamp,
+            state: serde_json::from_value::<Versioned<T>>(snapshot.state)?.into_inner(),
+        }))
+    }
+
+    /// Loads the state of the latest snapshot, returning `None` if nothing has been saved yet.
+    pub fn restore<T: DeserializeOwned + Validate>(&mut self) -> Result<Option<T>> {
+        Ok(self.restore_snapshot()?.map(|snapshot| snapshot.state))
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Result as TaResult;
+    use crate::indicators::SimpleMovingAverage;
+    use crate::pool::IndicatorPool;
+    use crate::Next;
+
+    #[test]
+    fn test_every() {
+        let mut checkpointer = Checkpointer::new(MemoryStore::new(), "sma", 1).every(3);
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+
+        let written: Vec<bool> = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+            .iter()
+            .map(|&x| {
+                sma.next(x);
+                checkpointer.tick(&sma).unwrap()
+            })
+            .collect();
+        assert_eq!(written, vec![false, false, true, false, false, true]);
+
+        let snapshot = checkpointer
+            .restore_snapshot::<SimpleMovingAverage>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.version, 1);
+        assert_eq!(snapshot.sequence, 2);
+        let mut restored = snapshot.state;
+        assert_eq!(restored.next(8.0), sma.next(8.0));
+    }
+
+    #[test]
+    fn test_restore_empty() {
+        let mut checkpointer = Checkpointer::new(MemoryStore::new(), "sma", 1);
+        assert!(checkpointer
+            .restore::<SimpleMovingAverage>()
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let mut old = Checkpointer::new(MemoryStore::new(), "sma", 1);
+        old.save(&SimpleMovingAverage::new(2).unwrap()).unwrap();
+
+        let mut new = Checkpointer::new(old.store().clone(), "sma", 2);
+        match new.restore::<SimpleMovingAverage>() {
+            Err(PersistenceError::VersionMismatch { expected, found }) => {
+                assert_eq!((expected, found), (2, 1));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sequence_continues_after_restore() {
+        let mut first = Checkpointer::new(MemoryStore::new(), "sma", 1);
+        let sma = SimpleMovingAverage::new(2).unwrap();
+        first.save(&sma).unwrap();
+        first.save(&sma).unwrap();
+
+        let mut second = Checkpointer::new(first.store().clone(), "sma", 1);
+        second.restore::<SimpleMovingAverage>().unwrap();
+        second.save(&sma).unwrap();
+        let snapshot = second
+            .restore_snapshot::<SimpleMovingAverage>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.sequence, 3);
+    }
+
+    #[test]
+    fn test_stack() {
+        #[derive(Serialize, Deserialize)]
+        struct Stack {
+            fast: SimpleMovingAverage,
+            slow: SimpleMovingAverage,
+        }
+
+        impl Validate for Stack {
+            fn validate(&self) -> TaResult<()> {
+                self.fast.validate()?;
+                self.slow.validate()
+            }
+        }
+
+        let mut checkpointer = Checkpointer::new(MemoryStore::new(), "stack", 1);
+        let mut stack = Stack {
+            fast: SimpleMovingAverage::new(2).unwrap(),
+            slow: SimpleMovingAverage::new(4).unwrap(),
+        };
+        stack.fast.next(4.0);
+        stack.slow.next(4.0);
+        checkpointer.save(&stack).unwrap();
+
+        let mut restored: Stack = checkpointer.restore().unwrap().unwrap();
+        assert_eq!(restored.fast.next(6.0), 5.0);
+        assert_eq!(restored.slow.next(8.0), 6.0);
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        let mut store = MemoryStore::new();
+        store.save("sma", b"not json").unwrap();
+        let mut checkpointer = Checkpointer::new(store, "sma", 1);
+        assert!(matches!(
+            checkpointer.restore::<SimpleMovingAverage>(),
+            Err(PersistenceError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_tampered_state() {
+        let mut checkpointer = Checkpointer::new(MemoryStore::new(), "sma", 1);
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.next(4.0);
+        checkpointer.save(&sma).unwrap();
+
+        let data = checkpointer.store().entries["sma"].clone();
+        let tampered = String::from_utf8(data)
+            .unwrap()
+            .replace("\"capacity\":2", "\"capacity\":0");
+        let mut store = MemoryStore::new();
+        store.save("sma", tampered.as_bytes()).unwrap();
+        let mut checkpointer = Checkpointer::new(store, "sma", 1);
+        assert!(matches!(
+            checkpointer.restore::<SimpleMovingAverage>(),
+            Err(PersistenceError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_pool() {
+        let mut checkpointer = Checkpointer::new(MemoryStore::new(), "pool", 1);
+        let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+        pool.next(("A".to_string(), 2.0));
+        pool.next(("B".to_string(), 10.0));
+        checkpointer.save(&pool).unwrap();
+
+        let mut restored: IndicatorPool<String, SimpleMovingAverage> =
+            checkpointer.restore().unwrap().unwrap();
+        assert_eq!(restored.latest(&"B".to_string()), Some(&10.0));
+        assert_eq!(restored.next(("A".to_string(), 4.0)), 3.0);
+    }
+}
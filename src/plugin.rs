@@ -0,0 +1,597 @@
+//! Indicators loaded at runtime from separately compiled libraries.
+//!
+//! Available with the `plugin` feature.
+//!
+//! A plugin is a dynamic library (crate type `cdylib`) exporting the function
+//! `ta_panther_plugin`, which describes its indicators through a small C ABI: a
+//! [PluginDeclaration] pointing to one [IndicatorVTable] per indicator. The ABI depends neither
+//! on the Rust compiler nor on the version of this crate used to build the plugin, only on
+//! [ABI_VERSION], which is checked when the plugin is loaded. This allows proprietary indicators
+//! to be distributed as binaries and plugged into an application, e.g. into an
+//! [IndicatorFactory](crate::factory::IndicatorFactory) or into the
+//! [IndicatorRegistry](crate::server::IndicatorRegistry) of the JSON-RPC service.
+//!
+//! # Writing a plugin
+//!
+//! Plugin crates export their indicators with [export_plugin](crate::export_plugin). Every
+//! indicator has to implement [FromParams], `Next<f64, Output = f64>`, [Reset], `Display` and
+//! `Send`:
+//!
+//! ```
+//! use std::fmt;
+//!
+//! use ta_panther::errors::{Result, TaError};
+//! use ta_panther::{FromParams, Next, Reset};
+//!
+//! struct Scale {
+//!     factor: f64,
+//! }
+//!
+//! impl FromParams for Scale {
+//!     const NAME: &'static str = "SCALE";
+//!
+//!     fn from_params(params: &[f64]) -> Result<Self> {
+//!         match params {
+//!             [factor] => Ok(Self { factor: *factor }),
+//!             _ => Err(TaError::InvalidParameter),
+//!         }
+//!     }
+//! }
+//!
+//! impl Next<f64> for Scale {
+//!     type Output = f64;
+//!
+//!     fn next(&mut self, input: f64) -> f64 {
+//!         input * self.factor
+//!     }
+//! }
+//!
+//! impl Reset for Scale {
+//!     fn reset(&mut self) {}
+//! }
+//!
+//! impl fmt::Display for Scale {
+//!     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+//!         write!(f, "SCALE({})", self.factor)
+//!     }
+//! }
+//!
+//! ta_panther::export_plugin!(Scale);
+//! ```
+//!
+//! # Loading a plugin
+//!
+//! ```no_run
+//! use ta_panther::plugin::Plugin;
+//! use ta_panther::Next;
+//!
+//! let plugin = unsafe { Plugin::load("libmy_indicators.so") }.unwrap();
+//! let mut scale = plugin.create("SCALE", &[2.0]).unwrap();
+//! assert_eq!(scale.next(1.5), 3.0);
+//! ```
+
+use std::error::Error;
+use std::ffi::{c_void, OsStr};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr::{self, NonNull};
+use std::slice;
+use std::sync::Arc;
+
+use libloading::Library;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, FromParams, Next, Reset};
+
+/// Version of the plugin ABI, increased on every incompatible change of [PluginDeclaration] or
+/// [IndicatorVTable].
+pub const ABI_VERSION: u32 = 1;
+
+/// Name of the function exported by plugins, with the signature
+/// `extern "C" fn() -> PluginDeclaration`.
+pub const ENTRY_POINT: &str = "ta_panther_plugin";
+
+/// Description of a plugin, returned by its entry point.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PluginDeclaration {
+    /// [ABI_VERSION] the plugin was built with.
+    pub abi_version: u32,
+    /// Pointer to `len` indicator descriptions, valid as long as the library is loaded.
+    pub indicators: *const IndicatorVTable,
+    /// Number of indicators.
+    pub len: usize,
+}
+
+/// Description of an indicator type of a plugin.
+///
+/// Instances are opaque pointers created by `create` and owned by the caller until they are
+/// passed to `destroy`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct IndicatorVTable {
+    /// Pointer to the UTF-8 encoded name of the indicator, without a terminating zero.
+    pub name: *const u8,
+    /// Length of the name in bytes.
+    pub name_len: usize,
+    /// Creates an instance from `len` parameters, returns null if the parameters are invalid.
+    pub create: unsafe extern "C" fn(params: *const f64, len: usize) -> *mut c_void,
+    /// Feeds the next value into the instance, returning the output.
+    pub next: unsafe extern "C" fn(instance: *mut c_void, input: f64) -> f64,
+    /// Resets the instance to its initial state.
+    pub reset: unsafe extern "C" fn(instance: *mut c_void),
+    /// Writes at most `len` bytes of the UTF-8 encoded display name of the instance (e.g.
+    /// `SMA(14)`) into `buf`, returning the full length of the name, or 0 if formatting failed.
+    pub display: unsafe extern "C" fn(instance: *const c_void, buf: *mut u8, len: usize) -> usize,
+    /// Destroys the instance.
+    pub destroy: unsafe extern "C" fn(instance: *mut c_void),
+}
+
+// The pointers of a declaration refer to immutable data of the plugin.
+unsafe impl Send for PluginDeclaration {}
+unsafe impl Sync for PluginDeclaration {}
+unsafe impl Send for IndicatorVTable {}
+unsafe impl Sync for IndicatorVTable {}
+
+/// Returns the [IndicatorVTable] of an indicator type, used by
+/// [export_plugin](crate::export_plugin).
+#[doc(hidden)]
+pub const fn vtable<T>() -> IndicatorVTable
+where
+    T: FromParams + Next<f64, Output = f64> + Reset + fmt::Display + Send + 'static,
+{
+    IndicatorVTable {
+        name: T::NAME.as_ptr(),
+        name_len: T::NAME.len(),
+        create: create::<T>,
+        next: next::<T>,
+        reset: reset::<T>,
+        display: display::<T>,
+        destroy: destroy::<T>,
+    }
+}
+
+unsafe extern "C" fn create<T: FromParams>(params: *const f64, len: usize) -> *mut c_void {
+    let params = if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(params, len)
+    };
+    match panic::catch_unwind(|| T::from_params(params)) {
+        Ok(Ok(indicator)) => Box::into_raw(Box::new(indicator)).cast(),
+        _ => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn next<T: Next<f64, Output = f64>>(instance: *mut c_void, input: f64) -> f64 {
+    let indicator = &mut *instance.cast::<T>();
+    panic::catch_unwind(AssertUnwindSafe(|| indicator.next(input))).unwrap_or(f64::NAN)
+}
+
+unsafe extern "C" fn reset<T: Reset>(instance: *mut c_void) {
+    let indicator = &mut *instance.cast::<T>();
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| indicator.reset()));
+}
+
+unsafe extern "C" fn display<T: fmt::Display>(
+    instance: *const c_void,
+    buf: *mut u8,
+    len: usize,
+) -> usize {
+    let indicator = &*instance.cast::<T>();
+    match panic::catch_unwind(AssertUnwindSafe(|| indicator.to_string())) {
+        Ok(name) => {
+            ptr::copy_nonoverlapping(name.as_ptr(), buf, name.len().min(len));
+            name.len()
+        }
+        Err(_) => 0,
+    }
+}
+
+unsafe extern "C" fn destroy<T>(instance: *mut c_void) {
+    let indicator = Box::from_raw(instance.cast::<T>());
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(indicator)));
+}
+
+/// Exports indicator types as a plugin.
+///
+/// Defines the entry point `ta_panther_plugin` of a plugin providing the given indicators, see
+/// the [plugin](crate::plugin) module. The crate of the plugin has to be built with the crate
+/// type `cdylib`.
+#[macro_export]
+macro_rules! export_plugin {
+    ($($indicator:ty),* $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn ta_panther_plugin() -> $crate::plugin::PluginDeclaration {
+            static INDICATORS: &[$crate::plugin::IndicatorVTable] =
+                &[$($crate::plugin::vtable::<$indicator>()),*];
+            $crate::plugin::PluginDeclaration {
+                abi_version: $crate::plugin::ABI_VERSION,
+                indicators: INDICATORS.as_ptr(),
+                len: INDICATORS.len(),
+            }
+        }
+    };
+}
+
+/// Error returned when a plugin can not be loaded.
+#[derive(Debug)]
+pub enum PluginError {
+    /// The library could not be loaded or has no entry point.
+    Library(libloading::Error),
+    /// The plugin was built for a different version of the ABI.
+    VersionMismatch { expected: u32, found: u32 },
+    /// The name of an indicator is not valid UTF-8.
+    InvalidName,
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PluginError::Library(err) => write!(f, "plugin library error: {}", err),
+            PluginError::VersionMismatch { expected, found } => write!(
+                f,
+                "plugin ABI version mismatch: expected {}, found {}",
+                expected, found
+            ),
+            PluginError::InvalidName => write!(f, "plugin indicator name is not valid UTF-8"),
+        }
+    }
+}
+
+impl Error for PluginError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PluginError::Library(err) => Some(err),
+            PluginError::VersionMismatch { .. } | PluginError::InvalidName => None,
+        }
+    }
+}
+
+impl From<libloading::Error> for PluginError {
+    fn from(err: libloading::Error) -> Self {
+        PluginError::Library(err)
+    }
+}
+
+/// Loaded plugin, creating instances of its indicators.
+///
+/// The library stays loaded as long as the plugin or any indicator created by it is alive.
+#[derive(Clone)]
+pub struct Plugin {
+    indicators: Vec<(String, IndicatorVTable)>,
+    library: Option<Arc<Library>>,
+}
+
+impl Plugin {
+    /// Loads the plugin at `path`.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialization code, and the library must implement the
+    /// plugin ABI correctly. Only load trusted plugins.
+    pub unsafe fn load<P: AsRef<OsStr>>(path: P) -> std::result::Result<Self, PluginError> {
+        let library = Library::new(path)?;
+        let entry: libloading::Symbol<extern "C" fn() -> PluginDeclaration> =
+            library.get(ENTRY_POINT.as_bytes())?;
+        let mut plugin = Self::from_declaration(entry())?;
+        plugin.library = Some(Arc::new(library));
+        Ok(plugin)
+    }
+
+    /// Creates a plugin from a declaration, e.g. of a plugin linked statically.
+    ///
+    /// # Safety
+    ///
+    /// All the pointers of the declaration must be valid as long as the plugin and its
+    /// indicators are alive.
+    pub unsafe fn from_declaration(
+        declaration: PluginDeclaration,
+    ) -> std::result::Result<Self, PluginError> {
+        if declaration.abi_version != ABI_VERSION {
+            return Err(PluginError::VersionMismatch {
+                expected: ABI_VERSION,
+                found: declaration.abi_version,
+            });
+        }
+
+        let vtables = if declaration.len == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(declaration.indicators, declaration.len)
+        };
+        let indicators = vtables
+            .iter()
+            .map(|vtable| {
+                let name = slice::from_raw_parts(vtable.name, vtable.name_len);
+                match std::str::from_utf8(name) {
+                    Ok(name) => Ok((name.to_string(), *vtable)),
+                    Err(_) => Err(PluginError::InvalidName),
+                }
+            })
+            .collect::<std::result::Result<_, _>>()?;
+
+        Ok(Self {
+            indicators,
+            library: None,
+        })
+    }
+
+    /// Returns the names of the indicators of the plugin.
+    pub fn names(&self) -> Vec<&str> {
+        self.indicators
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Creates an indicator by its case-insensitive name.
+    ///
+    /// Returns an error if the name is unknown or the parameters are invalid.
+    pub fn create(&self, name: &str, params: &[f64]) -> Result<PluginIndicator> {
+        let (_, vtable) = self
+            .indicators
+            .iter()
+            .find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+            .ok_or(TaError::InvalidParameter)?;
+        let instance = unsafe { (vtable.create)(params.as_ptr(), params.len()) };
+        match NonNull::new(instance) {
+            Some(instance) => Ok(PluginIndicator {
+                instance,
+                vtable: *vtable,
+                _library: self.library.clone(),
+            }),
+            None => Err(TaError::InvalidParameter),
+        }
+    }
+}
+
+impl fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Plugin")
+            .field("indicators", &self.names())
+            .finish()
+    }
+}
+
+/// Instance of an indicator provided by a [Plugin].
+pub struct PluginIndicator {
+    instance: NonNull<c_void>,
+    vtable: IndicatorVTable,
+    // Dropped after the instance, which is destroyed by the library.
+    _library: Option<Arc<Library>>,
+}
+
+// Exported indicator types are required to be `Send`.
+unsafe impl Send for PluginIndicator {}
+
+impl Next<f64> for PluginIndicator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        unsafe { (self.vtable.next)(self.instance.as_ptr(), input) }
+    }
+}
+
+impl<T: Close> Next<&T> for PluginIndicator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for PluginIndicator {
+    fn reset(&mut self) {
+        unsafe { (self.vtable.reset)(self.instance.as_ptr()) }
+    }
+}
+
+impl fmt::Display for PluginIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = vec![0; 32];
+        loop {
+            let len = unsafe {
+                (self.vtable.display)(self.instance.as_ptr(), buf.as_mut_ptr(), buf.len())
+            };
+            if len <= buf.len() {
+                buf.truncate(len);
+                return write!(f, "{}", String::from_utf8_lossy(&buf));
+            }
+            buf.resize(len, 0);
+        }
+    }
+}
+
+impl fmt::Debug for PluginIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PluginIndicator")
+            .field("name", &self.to_string())
+            .finish()
+    }
+}
+
+impl Drop for PluginIndicator {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.destroy)(self.instance.as_ptr()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+    use crate::test_helper::*;
+    use crate::Period;
+
+    struct Sma(SimpleMovingAverage);
+
+    impl FromParams for Sma {
+        const NAME: &'static str = "PLUGIN_SMA";
+
+        fn from_params(params: &[f64]) -> Result<Self> {
+            match params {
+                [period] => Ok(Self(SimpleMovingAverage::new(*period as usize)?)),
+                _ => Err(TaError::InvalidParameter),
+            }
+        }
+    }
+
+    impl Next<f64> for Sma {
+        type Output = f64;
+
+        fn next(&mut self, input: f64) -> f64 {
+            self.0.next(input)
+        }
+    }
+
+    impl Reset for Sma {
+        fn reset(&mut self) {
+            self.0.reset();
+        }
+    }
+
+    impl fmt::Display for Sma {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "PLUGIN_SMA_WITH_A_LONG_NAME({})", self.0.period())
+        }
+    }
+
+    crate::export_plugin!(Sma);
+
+    fn plugin() -> Plugin {
+        unsafe { Plugin::from_declaration(ta_panther_plugin()) }.unwrap()
+    }
+
+    #[test]
+    fn test_from_declaration() {
+        assert_eq!(plugin().names(), vec!["PLUGIN_SMA"]);
+
+        let mut declaration = ta_panther_plugin();
+        declaration.abi_version = ABI_VERSION + 1;
+        assert!(matches!(
+            unsafe { Plugin::from_declaration(declaration) },
+            Err(PluginError::VersionMismatch { expected, found })
+                if expected == ABI_VERSION && found == ABI_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_create() {
+        let plugin = plugin();
+        assert!(plugin.create("PLUGIN_SMA", &[0.0]).is_err());
+        assert!(plugin.create("PLUGIN_SMA", &[]).is_err());
+        assert!(plugin.create("UNKNOWN", &[3.0]).is_err());
+        assert!(plugin.create("plugin_sma", &[3.0]).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sma = plugin().create("PLUGIN_SMA", &[2.0]).unwrap();
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next(6.0), 5.0);
+        assert_eq!(sma.next(&Bar::new().close(2)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = plugin().create("PLUGIN_SMA", &[2.0]).unwrap();
+        sma.next(4.0);
+        sma.next(6.0);
+
+        sma.reset();
+        assert_eq!(sma.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = plugin().create("PLUGIN_SMA", &[14.0]).unwrap();
+        assert_eq!(format!("{}", sma), "PLUGIN_SMA_WITH_A_LONG_NAME(14)");
+    }
+
+    struct Panicking;
+
+    impl FromParams for Panicking {
+        const NAME: &'static str = "PANICKING";
+
+        fn from_params(_params: &[f64]) -> Result<Self> {
+            Ok(Self)
+        }
+    }
+
+    impl Next<f64> for Panicking {
+        type Output = f64;
+
+        fn next(&mut self, _input: f64) -> f64 {
+            panic!("next")
+        }
+    }
+
+    impl Reset for Panicking {
+        fn reset(&mut self) {
+            panic!("reset")
+        }
+    }
+
+    impl fmt::Display for Panicking {
+        fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+            panic!("display")
+        }
+    }
+
+    impl Drop for Panicking {
+        fn drop(&mut self) {
+            panic!("destroy")
+        }
+    }
+
+    #[test]
+    fn test_panics() {
+        let vtable = vtable::<Panicking>();
+        unsafe {
+            let instance = (vtable.create)(ptr::null(), 0);
+            assert!((vtable.next)(instance, 1.0).is_nan());
+            (vtable.reset)(instance);
+            let mut buf = [0; 8];
+            assert_eq!((vtable.display)(instance, buf.as_mut_ptr(), buf.len()), 0);
+            (vtable.destroy)(instance);
+        }
+    }
+
+    #[test]
+    fn test_factory_register_plugin() {
+        use crate::factory::IndicatorFactory;
+
+        let mut factory = IndicatorFactory::empty();
+        factory.register_plugin(&plugin());
+        assert_eq!(factory.names(), vec!["PLUGIN_SMA"]);
+
+        let mut sma = factory.create("plugin_sma", &[2.0]).unwrap();
+        sma.next(4.0);
+        assert_eq!(sma.next(6.0), 5.0);
+        assert_eq!(sma.to_string(), "PLUGIN_SMA_WITH_A_LONG_NAME(2)");
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn test_register_plugin() {
+        use crate::server::IndicatorRegistry;
+
+        let mut registry = IndicatorRegistry::empty();
+        registry.register_plugin(&plugin());
+        assert_eq!(registry.names(), vec!["PLUGIN_SMA"]);
+
+        let mut sma = registry.create("plugin_sma", &[2.0]).unwrap();
+        sma.push(4.0);
+        assert_eq!(sma.push(6.0), serde_json::json!(5.0));
+        assert!(sma.snapshot().is_err());
+    }
+
+    #[test]
+    fn test_load_missing_library() {
+        assert!(matches!(
+            unsafe { Plugin::load("/nonexistent/libplugin.so") },
+            Err(PluginError::Library(_))
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::hash_map::{self, HashMap};
+use std::fmt;
+use std::hash::Hash;
+
+use crate::errors::Result;
+use crate::{Next, Reset, Validate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Per-symbol pool of indicators.
+///
+/// The pool is configured with a prototype indicator (or a struct holding a whole stack of
+/// indicators). Every time an input arrives for a symbol which has not been seen before, the
+/// prototype is cloned for it. Inputs are routed to the indicator of their symbol and the latest
+/// output of every symbol is kept, so it can be queried or iterated over later.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::SimpleMovingAverage;
+/// use ta_panther::pool::IndicatorPool;
+/// use ta_panther::Next;
+///
+/// let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+///
+/// assert_eq!(pool.next(("AAPL", 10.0)), 10.0);
+/// assert_eq!(pool.next(("MSFT", 20.0)), 20.0);
+/// assert_eq!(pool.next(("AAPL", 12.0)), 11.0);
+///
+/// assert_eq!(pool.len(), 2);
+/// assert_eq!(pool.latest(&"AAPL"), Some(&11.0));
+/// assert_eq!(pool.latest(&"MSFT"), Some(&20.0));
+/// assert_eq!(pool.latest(&"GOOG"), None);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        deserialize = "K: Eq + Hash + Deserialize<'de>, I: Deserialize<'de>, O: Deserialize<'de>"
+    ))
+)]
+pub struct IndicatorPool<K, I, O = f64> {
+    prototype: I,
+    entries: HashMap<K, Entry<I, O>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Entry<I, O> {
+    indicator: I,
+    latest: Option<O>,
+}
+
+impl<K: Eq + Hash, I: Clone, O> IndicatorPool<K, I, O> {
+    /// Creates an empty pool, which instantiates new indicators by cloning `prototype`.
+    pub fn new(prototype: I) -> Self {
+        Self {
+            prototype,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the indicator of the given symbol, instantiating it if the symbol is new.
+    pub fn get_or_insert(&mut self, symbol: K) -> &mut I {
+        let prototype = &self.prototype;
+        &mut self
+            .entries
+            .entry(symbol)
+            .or_insert_with(|| Entry {
+                indicator: prototype.clone(),
+                latest: None,
+            })
+            .indicator
+    }
+
+    /// Returns the indicator of the given symbol, if the symbol has been seen.
+    pub fn get(&self, symbol: &K) -> Option<&I> {
+        self.entries.get(symbol).map(|entry| &entry.indicator)
+    }
+
+    /// Returns the latest output produced for the given symbol.
+    pub fn latest(&self, symbol: &K) -> Option<&O> {
+        self.entries
+            .get(symbol)
+            .and_then(|entry| entry.latest.as_ref())
+    }
+
+    /// Removes the symbol from the pool, returning its indicator.
+    pub fn remove(&mut self, symbol: &K) -> Option<I> {
+        self.entries.remove(symbol).map(|entry| entry.indicator)
+    }
+
+    /// Iterates over the symbols and their latest outputs.
+    ///
+    /// Symbols which have an indicator, but have not produced an output yet, are skipped.
+    pub fn iter(&self) -> Iter<'_, K, I, O> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Iterates over the symbols seen so far.
+    pub fn symbols(&self) -> impl Iterator<Item = &K> {
+        self.entries.keys()
+    }
+
+    /// Returns the prototype used to instantiate indicators for new symbols.
+    pub fn prototype(&self) -> &I {
+        &self.prototype
+    }
+
+    /// Returns the number of symbols in the pool.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no symbol has been seen yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K, I, O, T> Next<(K, T)> for IndicatorPool<K, I, O>
+where
+    K: Eq + Hash,
+    I: Clone + Next<T, Output = O>,
+    O: Clone,
+{
+    type Output = O;
+
+    fn next(&mut self, (symbol, input): (K, T)) -> Self::Output {
+        let prototype = &self.prototype;
+        let entry = self.entries.entry(symbol).or_insert_with(|| Entry {
+            indicator: prototype.clone(),
+            latest: None,
+        });
+        let output = entry.indicator.next(input);
+        entry.latest = Some(output.clone());
+        output
+    }
+}
+
+impl<K, I: Validate, O> Validate for IndicatorPool<K, I, O> {
+    /// The pool is stored with the layout of its indicators.
+    const STATE_VERSION: u32 = I::STATE_VERSION;
+
+    fn validate(&self) -> Result<()> {
+        self.prototype.validate()?;
+        self.entries
+            .values()
+            .try_for_each(|entry| entry.indicator.validate())
+    }
+
+    fn migrate(&mut self, version: u32) -> Result<()> {
+        self.prototype.migrate(version)?;
+        self.entries
+            .values_mut()
+            .try_for_each(|entry| entry.indicator.migrate(version))
+    }
+}
+
+impl<K, I: Reset, O> Reset for IndicatorPool<K, I, O> {
+    fn reset(&mut self) {
+        for entry in self.entries.values_mut() {
+            entry.indicator.reset();
+            entry.latest = None;
+        }
+    }
+}
+
+impl<K: Eq + Hash, I: Clone + Default, O> Default for IndicatorPool<K, I, O> {
+    fn default() -> Self {
+        Self::new(I::default())
+    }
+}
+
+impl<K, I: fmt::Display, O> fmt::Display for IndicatorPool<K, I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "POOL({})", self.prototype)
+    }
+}
+
+/// Iterator over the symbols of an [IndicatorPool] and their latest outputs.
+pub struct Iter<'a, K, I, O> {
+    inner: hash_map::Iter<'a, K, Entry<I, O>>,
+}
+
+impl<'a, K, I, O> Iterator for Iter<'a, K, I, O> {
+    type Item = (&'a K, &'a O);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (symbol, entry) in self.inner.by_ref() {
+            if let Some(latest) = entry.latest.as_ref() {
+                return Some((symbol, latest));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, K: Eq + Hash, I: Clone, O> IntoIterator for &'a IndicatorPool<K, I, O> {
+    type Item = (&'a K, &'a O);
+    type IntoIter = Iter<'a, K, I, O>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+
+        assert_eq!(pool.next(("A", 4.0)), 4.0);
+        assert_eq!(pool.next(("B", 10.0)), 10.0);
+        assert_eq!(pool.next(("A", 6.0)), 5.0);
+        assert_eq!(pool.next(("B", 20.0)), 15.0);
+        assert_eq!(pool.next(("A", 8.0)), 7.0);
+
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.latest(&"A"), Some(&7.0));
+        assert_eq!(pool.latest(&"B"), Some(&15.0));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut pool = IndicatorPool::new(ExponentialMovingAverage::new(3).unwrap());
+
+        assert_eq!(pool.next(("A", &Bar::new().close(2))), 2.0);
+        assert_eq!(pool.next(("B", &Bar::new().close(7))), 7.0);
+        assert_eq!(pool.next(("A", &Bar::new().close(5))), 3.5);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+        pool.next(("A".to_string(), 1.0));
+        pool.next(("B".to_string(), 2.0));
+        pool.get_or_insert("C".to_string());
+
+        let mut outputs: Vec<(String, f64)> = pool.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        outputs.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            outputs,
+            vec![("A".to_string(), 1.0), ("B".to_string(), 2.0)]
+        );
+        assert_eq!(pool.symbols().count(), 3);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+        pool.next(("A", 1.0));
+        assert!(pool.remove(&"A").is_some());
+        assert!(pool.remove(&"A").is_none());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pool = IndicatorPool::new(SimpleMovingAverage::new(2).unwrap());
+        pool.next(("A", 4.0));
+        pool.next(("A", 6.0));
+
+        pool.reset();
+        assert_eq!(pool.latest(&"A"), None);
+        assert_eq!(pool.next(("A", 10.0)), 10.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let pool: IndicatorPool<&str, _> = IndicatorPool::new(SimpleMovingAverage::new(5).unwrap());
+        assert_eq!(format!("{}", pool), "POOL(SMA(5))");
+    }
+}
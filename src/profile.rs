@@ -0,0 +1,369 @@
+//! Distribution of the traded volume over price levels.
+//!
+//! Unlike the indicators, which map a series of inputs to a series of outputs, a
+//! [VolumeProfile] is a histogram of the volume traded at each price level, either over a
+//! session or over a rolling window of inputs. The levels where most of the volume was traded,
+//! the point of control and the value area, are commonly used as support and resistance.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::profile::VolumeProfile;
+//!
+//! let mut profile = VolumeProfile::new(1.0, 0.7).unwrap();
+//! for &(price, volume) in [(10.5, 100.0), (11.5, 300.0), (12.5, 200.0), (13.5, 50.0)].iter() {
+//!     profile.add(price, volume);
+//! }
+//! assert_eq!(profile.poc(), Some(11.5));
+//!
+//! let value_area = profile.value_area().unwrap();
+//! assert_eq!(value_area.low, 11.0);
+//! assert_eq!(value_area.high, 13.0);
+//! assert_eq!(value_area.volume, 500.0);
+//! ```
+
+use alloc::collections::{BTreeMap, VecDeque};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::{High, Low, Reset, Volume};
+
+/// Value area of a [VolumeProfile].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ValueArea {
+    /// Point of control, the center of the bin with the most volume.
+    pub poc: f64,
+    /// Upper bound of the value area.
+    pub high: f64,
+    /// Lower bound of the value area.
+    pub low: f64,
+    /// Volume traded within the value area.
+    pub volume: f64,
+}
+
+/// Volume profile.
+///
+/// Accumulates the traded volume into bins of a fixed price range. The volume of a trade goes
+/// into the bin of its price, the volume of a bar is spread evenly over the bins from its low to
+/// its high.
+///
+/// The point of control (POC) is the center of the bin with the most volume, the lowest one on
+/// ties. The value area is the range of bins around the POC holding the given share of the
+/// total volume. It is grown from the POC one bin at a time, towards the side with more volume.
+///
+/// A session profile accumulates inputs until it is reset, a rolling profile created by
+/// [rolling](VolumeProfile::rolling) holds the last _period_ inputs only.
+///
+/// # Parameters
+///
+/// * _bin_size_ - price range of a bin (greater than 0)
+/// * _value_area_ - share of the volume in the value area (greater than 0, at most 1).
+///   Commonly 0.7.
+///
+/// # Links
+///
+/// * [Volume profile, Wikipedia](https://en.wikipedia.org/wiki/Market_profile)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VolumeProfile {
+    bin_size: f64,
+    value_area: f64,
+    period: Option<usize>,
+    total: f64,
+    bins: BTreeMap<i64, Bin>,
+    inputs: VecDeque<(f64, f64, f64)>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Bin {
+    volume: f64,
+    count: usize,
+}
+
+impl VolumeProfile {
+    /// Creates a session profile.
+    pub fn new(bin_size: f64, value_area: f64) -> Result<Self> {
+        if !(bin_size.is_finite() && bin_size > 0.0 && value_area > 0.0 && value_area <= 1.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            bin_size,
+            value_area,
+            period: None,
+            total: 0.0,
+            bins: BTreeMap::new(),
+            inputs: VecDeque::new(),
+        })
+    }
+
+    /// Creates a profile of the last `period` inputs.
+    pub fn rolling(period: usize, bin_size: f64, value_area: f64) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        let mut profile = Self::new(bin_size, value_area)?;
+        profile.period = Some(period);
+        profile.inputs.reserve(period);
+        Ok(profile)
+    }
+
+    pub fn bin_size(&self) -> f64 {
+        self.bin_size
+    }
+
+    /// Returns the number of inputs of a rolling profile.
+    pub fn period(&self) -> Option<usize> {
+        self.period
+    }
+
+    /// Adds a trade of `volume` at `price`.
+    pub fn add(&mut self, price: f64, volume: f64) {
+        self.add_range(price, price, volume);
+    }
+
+    /// Adds a bar, spreading its volume evenly from its low to its high.
+    pub fn add_bar<T: High + Low + Volume>(&mut self, bar: &T) {
+        self.add_range(bar.low(), bar.high(), bar.volume());
+    }
+
+    /// Returns the total volume of the profile.
+    pub fn total_volume(&self) -> f64 {
+        self.total
+    }
+
+    /// Returns the lower bound and the volume of each bin holding volume, by price.
+    pub fn bins(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.bins
+            .iter()
+            .map(move |(&index, bin)| (index as f64 * self.bin_size, bin.volume))
+    }
+
+    /// Returns the point of control, or `None` if the profile is empty.
+    pub fn poc(&self) -> Option<f64> {
+        self.poc_index().map(|index| self.center(index))
+    }
+
+    /// Returns the value area, or `None` if the profile is empty.
+    pub fn value_area(&self) -> Option<ValueArea> {
+        let poc = self.poc_index()?;
+        let (&first, _) = self.bins.iter().next()?;
+        let (&last, _) = self.bins.iter().next_back()?;
+        let volume = |index: i64| self.bins.get(&index).map_or(0.0, |bin| bin.volume);
+
+        let target = self.total * self.value_area;
+        let (mut low, mut high) = (poc, poc);
+        let mut area = volume(poc);
+        while area < target && (low > first || high < last) {
+            let below = if low > first { volume(low - 1) } else { -1.0 };
+            let above = if high < last { volume(high + 1) } else { -1.0 };
+            if above >= below {
+                high += 1;
+                area += above;
+            } else {
+                low -= 1;
+                area += below;
+            }
+        }
+
+        Some(ValueArea {
+            poc: self.center(poc),
+            high: (high + 1) as f64 * self.bin_size,
+            low: low as f64 * self.bin_size,
+            volume: area,
+        })
+    }
+
+    fn add_range(&mut self, low: f64, high: f64, volume: f64) {
+        if !(low.is_finite() && high.is_finite() && volume.is_finite()) {
+            return;
+        }
+        if let Some(period) = self.period {
+            if self.inputs.len() == period {
+                if let Some((low, high, volume)) = self.inputs.pop_front() {
+                    self.update(low, high, volume, false);
+                }
+            }
+            self.inputs.push_back((low, high, volume));
+        }
+        self.update(low, high, volume, true);
+    }
+
+    fn update(&mut self, low: f64, high: f64, volume: f64, add: bool) {
+        let first = self.index(low.min(high));
+        let last = self.index(low.max(high));
+        let share = volume / (last - first + 1) as f64;
+        for index in first..=last {
+            if add {
+                let bin = self.bins.entry(index).or_default();
+                bin.volume += share;
+                bin.count += 1;
+            } else if let Some(bin) = self.bins.get_mut(&index) {
+                bin.volume -= share;
+                bin.count -= 1;
+                if bin.count == 0 {
+                    self.bins.remove(&index);
+                }
+            }
+        }
+        if add {
+            self.total += volume;
+        } else if self.bins.is_empty() {
+            self.total = 0.0;
+        } else {
+            self.total -= volume;
+        }
+    }
+
+    fn poc_index(&self) -> Option<i64> {
+        let mut poc: Option<(i64, f64)> = None;
+        for (&index, bin) in self.bins.iter() {
+            if poc.is_none_or(|(_, volume)| bin.volume > volume) {
+                poc = Some((index, bin.volume));
+            }
+        }
+        poc.map(|(index, _)| index)
+    }
+
+    fn index(&self, price: f64) -> i64 {
+        (price / self.bin_size).floor() as i64
+    }
+
+    fn center(&self, index: i64) -> f64 {
+        (index as f64 + 0.5) * self.bin_size
+    }
+}
+
+impl Reset for VolumeProfile {
+    fn reset(&mut self) {
+        self.total = 0.0;
+        self.bins.clear();
+        self.inputs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeProfile::new(0.0, 0.7).is_err());
+        assert!(VolumeProfile::new(f64::INFINITY, 0.7).is_err());
+        assert!(VolumeProfile::new(1.0, 0.0).is_err());
+        assert!(VolumeProfile::new(1.0, 1.5).is_err());
+        assert!(VolumeProfile::new(1.0, 1.0).is_ok());
+        assert!(VolumeProfile::rolling(0, 1.0, 0.7).is_err());
+        assert_eq!(
+            VolumeProfile::rolling(5, 1.0, 0.7).unwrap().period(),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let profile = VolumeProfile::new(1.0, 0.7).unwrap();
+        assert_eq!(profile.poc(), None);
+        assert_eq!(profile.value_area(), None);
+        assert_eq!(profile.total_volume(), 0.0);
+    }
+
+    #[test]
+    fn test_value_area() {
+        let mut profile = VolumeProfile::new(0.5, 0.7).unwrap();
+        for &(price, volume) in [
+            (10.0, 50.0),
+            (10.5, 100.0),
+            (11.0, 400.0),
+            (11.5, 150.0),
+            (13.0, 300.0),
+        ]
+        .iter()
+        {
+            profile.add(price, volume);
+        }
+        assert_eq!(profile.poc(), Some(11.25));
+        assert_eq!(
+            profile.value_area(),
+            Some(ValueArea {
+                poc: 11.25,
+                high: 12.0,
+                low: 10.0,
+                volume: 700.0,
+            })
+        );
+
+        // empty bins are part of the value area
+        profile.add(11.0, 100.0);
+        profile.add(13.0, 500.0);
+        assert_eq!(profile.poc(), Some(13.25));
+        let value_area = profile.value_area().unwrap();
+        assert_eq!(value_area.low, 11.0);
+        assert_eq!(value_area.high, 13.5);
+        assert_eq!(value_area.volume, 1450.0);
+    }
+
+    #[test]
+    fn test_whole_profile() {
+        let mut profile = VolumeProfile::new(1.0, 1.0).unwrap();
+        profile.add(1.5, 10.0);
+        profile.add(3.5, 20.0);
+        let value_area = profile.value_area().unwrap();
+        assert_eq!((value_area.low, value_area.high), (1.0, 4.0));
+    }
+
+    #[test]
+    fn test_bars() {
+        let mut profile = VolumeProfile::new(1.0, 0.7).unwrap();
+        profile.add_bar(&Bar::new().low(1.5).high(3.5).volume(300.0));
+        profile.add_bar(&Bar::new().low(2.5).high(2.5).volume(50.0));
+        let bins: Vec<(f64, f64)> = profile.bins().collect();
+        assert_eq!(bins, vec![(1.0, 100.0), (2.0, 150.0), (3.0, 100.0)]);
+        assert_eq!(profile.total_volume(), 350.0);
+    }
+
+    #[test]
+    fn test_rolling() {
+        let mut profile = VolumeProfile::rolling(2, 1.0, 0.7).unwrap();
+        profile.add(1.5, 10.0);
+        profile.add(2.5, 20.0);
+        assert_eq!(profile.poc(), Some(2.5));
+
+        profile.add(1.5, 30.0);
+        assert_eq!(profile.poc(), Some(1.5));
+        assert_eq!(profile.total_volume(), 50.0);
+
+        profile.add(3.5, 5.0);
+        let bins: Vec<(f64, f64)> = profile.bins().collect();
+        assert_eq!(bins, vec![(1.0, 30.0), (3.0, 5.0)]);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let mut profile = VolumeProfile::new(1.0, 0.7).unwrap();
+        profile.add(f64::NAN, 10.0);
+        profile.add(1.0, f64::INFINITY);
+        assert_eq!(profile.poc(), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut profile = VolumeProfile::rolling(2, 1.0, 0.7).unwrap();
+        profile.add(1.5, 10.0);
+        profile.add(2.5, 20.0);
+
+        profile.reset();
+        assert_eq!(profile.poc(), None);
+        profile.add(4.5, 1.0);
+        profile.add(5.5, 2.0);
+        profile.add(6.5, 3.0);
+        assert_eq!(profile.total_volume(), 5.0);
+    }
+}
@@ -0,0 +1,221 @@
+//! Python bindings.
+//!
+//! Available with the `python` feature.
+//!
+//! Exposes the indicators to Python through [PyO3](https://pyo3.rs), so research done in
+//! Python computes exactly the same values as the Rust code running in production. The main
+//! indicators have their own classes, and any indicator of the [factory] can be created by name
+//! with `Indicator`. Besides `next` and `reset`, every class has a vectorized `next_batch`,
+//! taking and returning a numpy array:
+//!
+//! ```python
+//! import numpy as np
+//! from ta_panther import Indicator, RelativeStrengthIndex
+//!
+//! rsi = RelativeStrengthIndex(14)
+//! values = rsi.next_batch(np.asarray(closes, dtype=np.float64))
+//! last = rsi.next(101.5)
+//!
+//! kama = Indicator("KAMA", [10, 2, 30])
+//! print(kama)  # KAMA(10, 2, 30)
+//! ```
+//!
+//! The extension module is built with [maturin](https://www.maturin.rs), which compiles the
+//! crate with the `python` feature and exports the classes registered by [ta_panther].
+
+use std::string::ToString;
+use std::sync::Mutex;
+
+use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::errors::TaError;
+use crate::factory::{self, NextF64};
+use crate::indicators;
+use crate::{Next, Period, Reset};
+
+fn value_error(error: TaError) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+fn next_batch<'py, N: Next<f64, Output = f64> + ?Sized>(
+    indicator: &mut N,
+    py: Python<'py>,
+    input: PyReadonlyArray1<'py, f64>,
+) -> Bound<'py, PyArray1<f64>> {
+    let output = input
+        .as_array()
+        .iter()
+        .map(|&value| indicator.next(value))
+        .collect();
+    PyArray1::from_vec(py, output)
+}
+
+/// Indicator consuming and returning floats, created by name.
+#[pyclass(module = "ta_panther")]
+pub struct Indicator {
+    // Python classes have to be `Sync`, which boxed indicators are not. Methods take
+    // `&mut self`, so the mutex is never locked.
+    inner: Mutex<Box<dyn NextF64>>,
+}
+
+impl Indicator {
+    fn inner(&mut self) -> &mut dyn NextF64 {
+        match self.inner.get_mut() {
+            Ok(inner) => &mut **inner,
+            Err(poisoned) => &mut **poisoned.into_inner(),
+        }
+    }
+}
+
+#[pymethods]
+impl Indicator {
+    /// Creates a built-in indicator by its case-insensitive name, e.g. `Indicator("EMA", [9])`.
+    ///
+    /// Raises `ValueError` if the name is unknown or the parameters are invalid.
+    #[new]
+    #[pyo3(signature = (name, params = Vec::new()))]
+    fn new(name: &str, params: Vec<f64>) -> PyResult<Self> {
+        let inner = factory::indicator(name, &params).map_err(value_error)?;
+        Ok(Self {
+            inner: Mutex::new(inner),
+        })
+    }
+
+    /// Feeds a value and returns the output.
+    fn next(&mut self, input: f64) -> f64 {
+        self.inner().next(input)
+    }
+
+    /// Feeds all the values of a numpy array and returns their outputs.
+    fn next_batch<'py>(
+        &mut self,
+        py: Python<'py>,
+        input: PyReadonlyArray1<'py, f64>,
+    ) -> Bound<'py, PyArray1<f64>> {
+        next_batch(self.inner(), py, input)
+    }
+
+    /// Resets the indicator to its initial state.
+    fn reset(&mut self) {
+        self.inner().reset();
+    }
+
+    fn __repr__(&mut self) -> String {
+        self.inner().to_string()
+    }
+
+    /// Returns the sorted names of the built-in indicators.
+    #[staticmethod]
+    fn names() -> Vec<String> {
+        factory::IndicatorFactory::default()
+            .names()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
+macro_rules! py_indicators {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("Python class of [", stringify!($name), "](indicators::", stringify!($name), ").")]
+            #[pyclass(module = "ta_panther")]
+            pub struct $name {
+                inner: indicators::$name,
+            }
+
+            #[pymethods]
+            impl $name {
+                /// Creates the indicator, raising `ValueError` if the period is invalid.
+                #[new]
+                fn new(period: usize) -> PyResult<Self> {
+                    let inner = indicators::$name::new(period).map_err(value_error)?;
+                    Ok(Self { inner })
+                }
+
+                /// Feeds a value and returns the output.
+                fn next(&mut self, input: f64) -> f64 {
+                    self.inner.next(input)
+                }
+
+                /// Feeds all the values of a numpy array and returns their outputs.
+                fn next_batch<'py>(
+                    &mut self,
+                    py: Python<'py>,
+                    input: PyReadonlyArray1<'py, f64>,
+                ) -> Bound<'py, PyArray1<f64>> {
+                    next_batch(&mut self.inner, py, input)
+                }
+
+                /// Resets the indicator to its initial state.
+                fn reset(&mut self) {
+                    self.inner.reset();
+                }
+
+                #[getter]
+                fn period(&self) -> usize {
+                    self.inner.period()
+                }
+
+                fn __repr__(&self) -> String {
+                    self.inner.to_string()
+                }
+            }
+        )*
+
+        fn add_indicators(module: &Bound<'_, PyModule>) -> PyResult<()> {
+            $(module.add_class::<$name>()?;)*
+            Ok(())
+        }
+    };
+}
+
+py_indicators!(
+    SimpleMovingAverage,
+    ExponentialMovingAverage,
+    WeightedMovingAverage,
+    HullMovingAverage,
+    RelativeStrengthIndex,
+    StandardDeviation,
+    MeanAbsoluteDeviation,
+    Maximum,
+    Minimum,
+    RateOfChange,
+    EfficiencyRatio,
+);
+
+/// The `ta_panther` Python module.
+#[pymodule]
+pub fn ta_panther(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Indicator>()?;
+    add_indicators(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicator() {
+        let mut sma = Indicator::new("sma", vec![2.0]).unwrap();
+        assert_eq!(sma.__repr__(), "SMA(2)");
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next(8.0), 6.0);
+        sma.reset();
+        assert_eq!(sma.next(10.0), 10.0);
+        assert!(Indicator::names().iter().any(|name| name == "RSI"));
+    }
+
+    #[test]
+    fn test_classes() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        assert_eq!(ema.period(), 3);
+        assert_eq!(ema.__repr__(), "EMA(3)");
+        assert_eq!(ema.next(2.0), 2.0);
+        assert_eq!(ema.next(4.0), 3.0);
+        ema.reset();
+        assert_eq!(ema.next(4.0), 4.0);
+    }
+}
@@ -0,0 +1,834 @@
+//! Aggregation of ticks and bars into bars of a longer timeframe.
+//!
+//! A [BarAggregator] merges timestamped [DataItem]s, e.g. 1-minute bars, or ticks into bars of
+//! a fixed interval, e.g. 5 minutes, an hour or a day. Each completed bar is returned as soon as
+//! the first input of the next interval arrives, so indicators on several timeframes can be
+//! driven from a single feed.
+//!
+//! Intervals are aligned to the Unix epoch, i.e. hourly bars start at full hours and daily bars
+//! at midnight UTC. Intervals without inputs produce no bars.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use ta_panther::resample::BarAggregator;
+//! use ta_panther::{Close, High, Low, Open, Timestamp, Volume};
+//!
+//! let mut aggregator = BarAggregator::new(Duration::from_secs(60)).unwrap();
+//! assert_eq!(aggregator.push_tick(0, 10.0, 1.0).unwrap(), None);
+//! assert_eq!(aggregator.push_tick(20_000, 12.0, 2.0).unwrap(), None);
+//! assert_eq!(aggregator.push_tick(40_000, 9.0, 1.0).unwrap(), None);
+//!
+//! // the first tick of the next minute completes the bar
+//! let bar = aggregator.push_tick(60_000, 11.0, 3.0).unwrap().unwrap();
+//! assert_eq!(bar.timestamp(), Some(0));
+//! assert_eq!(bar.open(), 10.0);
+//! assert_eq!(bar.high(), 12.0);
+//! assert_eq!(bar.low(), 9.0);
+//! assert_eq!(bar.close(), 9.0);
+//! assert_eq!(bar.volume(), 4.0);
+//! assert_eq!(bar.trade_count(), Some(3));
+//!
+//! // the bar in progress can be taken at the end of the feed
+//! let bar = aggregator.flush().unwrap();
+//! assert_eq!(bar.timestamp(), Some(60_000));
+//! assert_eq!(bar.close(), 11.0);
+//! ```
+//!
+//! [VolumeBarBuilder] and [DollarBarBuilder] close bars by the volume or the notional value
+//! traded instead of time.
+//!
+//! [MultiTimeframe] wraps an indicator with an aggregator, to run it on the higher timeframe
+//! while being fed with the bars of the lower one.
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, TaError};
+use crate::{
+    Close, DataItem, High, Low, Next, Open, OpenInterest, Period, Reset, Timestamp, Volume,
+};
+
+/// Merges timestamped bars or ticks into bars of a fixed interval.
+///
+/// The timestamp of a merged bar is the start of its interval. Volumes and trade counts are
+/// summed up, the open interest is the latest one known.
+///
+/// Inputs must have a timestamp and arrive in chronological order, inputs of an earlier
+/// interval than the bar in progress are rejected with [TaError::InvalidInput].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BarAggregator {
+    interval: i64,
+    current: Option<DataItem>,
+}
+
+impl BarAggregator {
+    /// Creates an aggregator of bars of the given interval, which must be at least a
+    /// millisecond.
+    pub fn new(interval: Duration) -> Result<Self> {
+        match i64::try_from(interval.as_millis()) {
+            Ok(interval) if interval > 0 => Ok(Self {
+                interval,
+                current: None,
+            }),
+            _ => Err(TaError::InvalidParameter),
+        }
+    }
+
+    /// Returns the interval of the bars.
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval as u64)
+    }
+
+    /// Returns the bar in progress, if any.
+    pub fn current(&self) -> Option<&DataItem> {
+        self.current.as_ref()
+    }
+
+    /// Adds a bar, returning the previous bar if the input starts a new interval.
+    pub fn push(&mut self, item: &DataItem) -> Result<Option<DataItem>> {
+        let timestamp = item.timestamp().ok_or(TaError::InvalidInput)?;
+        let start = timestamp - timestamp.rem_euclid(self.interval);
+
+        let current = match self.current.take() {
+            None => {
+                self.current = Some(merge(item, None, Some(start))?);
+                return Ok(None);
+            }
+            Some(current) => current,
+        };
+
+        let current_start = current.timestamp().unwrap_or(start);
+        if start < current_start {
+            self.current = Some(current);
+            Err(TaError::InvalidInput)
+        } else if start == current_start {
+            self.current = Some(merge(item, Some(&current), Some(start))?);
+            Ok(None)
+        } else {
+            self.current = Some(merge(item, None, Some(start))?);
+            Ok(Some(current))
+        }
+    }
+
+    /// Adds a trade of `volume` at `price`, returning the previous bar if the trade starts a new
+    /// interval. `timestamp` is in milliseconds since the Unix epoch.
+    pub fn push_tick(
+        &mut self,
+        timestamp: i64,
+        price: f64,
+        volume: f64,
+    ) -> Result<Option<DataItem>> {
+        self.push(&tick(timestamp, price, volume)?)
+    }
+
+    /// Returns the bar in progress and starts over, e.g. at the end of a feed or a session.
+    pub fn flush(&mut self) -> Option<DataItem> {
+        self.current.take()
+    }
+}
+
+impl Reset for BarAggregator {
+    fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+/// Merges `item` into the bar in progress, or starts a new bar if there is none.
+fn merge(item: &DataItem, current: Option<&DataItem>, timestamp: Option<i64>) -> Result<DataItem> {
+    let mut builder = DataItem::builder().close(item.close());
+    if let Some(timestamp) = timestamp {
+        builder = builder.timestamp(timestamp);
+    }
+    let trade_count = match current {
+        None => {
+            builder = builder
+                .open(item.open())
+                .high(item.high())
+                .low(item.low())
+                .volume(item.volume());
+            item.trade_count()
+        }
+        Some(current) => {
+            builder = builder
+                .open(current.open())
+                .high(current.high().max(item.high()))
+                .low(current.low().min(item.low()))
+                .volume(current.volume() + item.volume());
+            match (current.trade_count(), item.trade_count()) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            }
+        }
+    };
+    if let Some(trade_count) = trade_count {
+        builder = builder.trade_count(trade_count);
+    }
+    let open_interest = item
+        .open_interest()
+        .or_else(|| current.and_then(|c| c.open_interest()));
+    if let Some(open_interest) = open_interest {
+        builder = builder.open_interest(open_interest);
+    }
+    builder.build()
+}
+
+/// Returns a bar of a single trade.
+fn tick(timestamp: i64, price: f64, volume: f64) -> Result<DataItem> {
+    DataItem::builder()
+        .open(price)
+        .high(price)
+        .low(price)
+        .close(price)
+        .volume(volume)
+        .timestamp(timestamp)
+        .trade_count(1)
+        .build()
+        .map_err(|_| TaError::InvalidInput)
+}
+
+/// Bars closing once an amount traded reaches a threshold.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct ThresholdBars {
+    threshold: f64,
+    accumulated: f64,
+    current: Option<DataItem>,
+}
+
+impl ThresholdBars {
+    fn new(threshold: f64) -> Result<Self> {
+        if threshold.is_finite() && threshold > 0.0 {
+            Ok(Self {
+                threshold,
+                accumulated: 0.0,
+                current: None,
+            })
+        } else {
+            Err(TaError::InvalidParameter)
+        }
+    }
+
+    fn push(&mut self, item: &DataItem, amount: f64) -> Result<Option<DataItem>> {
+        let current = self.current.as_ref();
+        let timestamp = current.map_or(item.timestamp(), |c| c.timestamp());
+        let merged = merge(item, current, timestamp)?;
+        self.accumulated += amount;
+        if self.accumulated >= self.threshold {
+            self.reset();
+            Ok(Some(merged))
+        } else {
+            self.current = Some(merged);
+            Ok(None)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.accumulated = 0.0;
+        self.current = None;
+    }
+}
+
+/// Merges bars or ticks into bars of a fixed volume.
+///
+/// A bar closes as soon as the volume traded since the previous bar reaches the threshold, so
+/// that bars sample the market by activity instead of time. Inputs are not split, i.e. the
+/// volume of a bar may exceed the threshold by the volume of its last input.
+///
+/// The timestamp of a bar is the one of its first input, if any. Volumes and trade counts are
+/// summed up, the open interest is the latest one known.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::resample::VolumeBarBuilder;
+/// use ta_panther::{Close, Volume};
+///
+/// let mut bars = VolumeBarBuilder::new(100.0).unwrap();
+/// assert_eq!(bars.push_tick(0, 10.0, 60.0).unwrap(), None);
+///
+/// let bar = bars.push_tick(1000, 11.0, 50.0).unwrap().unwrap();
+/// assert_eq!(bar.close(), 11.0);
+/// assert_eq!(bar.volume(), 110.0);
+/// ```
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VolumeBarBuilder {
+    bars: ThresholdBars,
+}
+
+impl VolumeBarBuilder {
+    /// Creates a builder of bars of `volume` contracts, which must be positive.
+    pub fn new(volume: f64) -> Result<Self> {
+        Ok(Self {
+            bars: ThresholdBars::new(volume)?,
+        })
+    }
+
+    /// Returns the volume of the bars.
+    pub fn threshold(&self) -> f64 {
+        self.bars.threshold
+    }
+
+    /// Returns the bar in progress, if any.
+    pub fn current(&self) -> Option<&DataItem> {
+        self.bars.current.as_ref()
+    }
+
+    /// Adds a bar, returning the merged bar if the threshold is reached.
+    pub fn push(&mut self, item: &DataItem) -> Result<Option<DataItem>> {
+        self.bars.push(item, item.volume())
+    }
+
+    /// Adds a trade of `volume` at `price`, returning the merged bar if the threshold is
+    /// reached. `timestamp` is in milliseconds since the Unix epoch.
+    pub fn push_tick(
+        &mut self,
+        timestamp: i64,
+        price: f64,
+        volume: f64,
+    ) -> Result<Option<DataItem>> {
+        self.push(&tick(timestamp, price, volume)?)
+    }
+
+    /// Returns the bar in progress and starts over.
+    pub fn flush(&mut self) -> Option<DataItem> {
+        let current = self.bars.current.take();
+        self.bars.reset();
+        current
+    }
+}
+
+impl Reset for VolumeBarBuilder {
+    fn reset(&mut self) {
+        self.bars.reset();
+    }
+}
+
+/// Merges bars or ticks into bars of a fixed notional value.
+///
+/// Like [VolumeBarBuilder], but a bar closes as soon as the notional value traded since the
+/// previous bar, i.e. the sum of _close * volume_ of its inputs, reaches the threshold. Unlike
+/// volume bars, dollar bars are not distorted by large changes of the price over time.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::resample::DollarBarBuilder;
+/// use ta_panther::{Open, Volume};
+///
+/// let mut bars = DollarBarBuilder::new(1000.0).unwrap();
+/// assert_eq!(bars.push_tick(0, 10.0, 60.0).unwrap(), None);
+///
+/// let bar = bars.push_tick(1000, 20.0, 20.0).unwrap().unwrap();
+/// assert_eq!(bar.open(), 10.0);
+/// assert_eq!(bar.volume(), 80.0);
+/// ```
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DollarBarBuilder {
+    bars: ThresholdBars,
+}
+
+impl DollarBarBuilder {
+    /// Creates a builder of bars of `notional` value traded, which must be positive.
+    pub fn new(notional: f64) -> Result<Self> {
+        Ok(Self {
+            bars: ThresholdBars::new(notional)?,
+        })
+    }
+
+    /// Returns the notional value of the bars.
+    pub fn threshold(&self) -> f64 {
+        self.bars.threshold
+    }
+
+    /// Returns the bar in progress, if any.
+    pub fn current(&self) -> Option<&DataItem> {
+        self.bars.current.as_ref()
+    }
+
+    /// Adds a bar, returning the merged bar if the threshold is reached.
+    pub fn push(&mut self, item: &DataItem) -> Result<Option<DataItem>> {
+        self.bars.push(item, item.close() * item.volume())
+    }
+
+    /// Adds a trade of `volume` at `price`, returning the merged bar if the threshold is
+    /// reached. `timestamp` is in milliseconds since the Unix epoch.
+    pub fn push_tick(
+        &mut self,
+        timestamp: i64,
+        price: f64,
+        volume: f64,
+    ) -> Result<Option<DataItem>> {
+        self.push(&tick(timestamp, price, volume)?)
+    }
+
+    /// Returns the bar in progress and starts over.
+    pub fn flush(&mut self) -> Option<DataItem> {
+        let current = self.bars.current.take();
+        self.bars.reset();
+        current
+    }
+}
+
+impl Reset for DollarBarBuilder {
+    fn reset(&mut self) {
+        self.bars.reset();
+    }
+}
+
+/// Values reported by a [MultiTimeframe] while a higher timeframe bar is forming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MultiTimeframeMode {
+    /// Reports the value of the latest closed bar, which never changes afterwards.
+    #[default]
+    NonRepainting,
+    /// Reports the value of the bar in progress, as if it closed with the current input. The
+    /// value changes with every input until the bar closes.
+    Repainting,
+}
+
+/// Indicator on a higher timeframe.
+///
+/// Aggregates the incoming bars into bars of a longer interval with a [BarAggregator], feeds the
+/// indicator with them and reports its latest value on every incoming bar. Depending on the
+/// [MultiTimeframeMode] this is the value of the latest closed bar, or the value of the bar in
+/// progress. The latter is computed on a copy of the indicator, which is fed only with closed
+/// bars.
+///
+/// Returns `None` until a value is available, i.e. until the first bar closes in the
+/// non-repainting mode. Incoming bars without a timestamp or out of order are ignored by [Next],
+/// [try_next](MultiTimeframe::try_next) reports them as errors.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use ta_panther::indicators::SimpleMovingAverage;
+/// use ta_panther::resample::{MultiTimeframe, MultiTimeframeMode};
+/// use ta_panther::{DataItem, Next};
+///
+/// let minute = |minute: i64, close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(close)
+///         .low(close)
+///         .close(close)
+///         .volume(1.0)
+///         .timestamp(minute * 60_000)
+///         .build()
+///         .unwrap()
+/// };
+/// let sma = SimpleMovingAverage::new(2).unwrap();
+/// let five_minutes = Duration::from_secs(300);
+///
+/// let mut closed =
+///     MultiTimeframe::new(sma.clone(), five_minutes, MultiTimeframeMode::NonRepainting).unwrap();
+/// let mut forming =
+///     MultiTimeframe::new(sma, five_minutes, MultiTimeframeMode::Repainting).unwrap();
+///
+/// assert_eq!(closed.next(&minute(0, 10.0)), None);
+/// assert_eq!(forming.next(&minute(0, 10.0)), Some(10.0));
+/// assert_eq!(closed.next(&minute(4, 12.0)), None);
+/// assert_eq!(forming.next(&minute(4, 12.0)), Some(12.0));
+///
+/// // the first 5-minute bar closed with 12
+/// assert_eq!(closed.next(&minute(5, 14.0)), Some(12.0));
+/// assert_eq!(forming.next(&minute(5, 14.0)), Some(13.0));
+/// assert_eq!(closed.next(&minute(6, 16.0)), Some(12.0));
+/// assert_eq!(forming.next(&minute(6, 16.0)), Some(14.0));
+/// ```
+///
+#[doc(alias = "MTF")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiTimeframe<N> {
+    indicator: N,
+    aggregator: BarAggregator,
+    mode: MultiTimeframeMode,
+    latest: Option<f64>,
+}
+
+impl<N> MultiTimeframe<N> {
+    /// Creates the wrapper, returning an error if the interval is less than a millisecond.
+    pub fn new(indicator: N, interval: Duration, mode: MultiTimeframeMode) -> Result<Self> {
+        Ok(Self {
+            indicator,
+            aggregator: BarAggregator::new(interval)?,
+            mode,
+            latest: None,
+        })
+    }
+
+    /// Returns the wrapped indicator, which has been fed with the closed bars.
+    pub fn inner(&self) -> &N {
+        &self.indicator
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.aggregator.interval()
+    }
+
+    pub fn mode(&self) -> MultiTimeframeMode {
+        self.mode
+    }
+}
+
+impl<N> MultiTimeframe<N>
+where
+    N: for<'a> Next<&'a DataItem, Output = f64> + Clone,
+{
+    /// Feeds the next bar, returning an error if it has no timestamp or belongs to an earlier
+    /// interval than the bar in progress. The state is left untouched in that case.
+    pub fn try_next(&mut self, input: &DataItem) -> Result<Option<f64>> {
+        if let Some(closed) = self.aggregator.push(input)? {
+            self.latest = Some(self.indicator.next(&closed));
+        }
+        Ok(self.output())
+    }
+
+    fn output(&self) -> Option<f64> {
+        match (self.mode, self.aggregator.current()) {
+            (MultiTimeframeMode::Repainting, Some(forming)) => {
+                Some(self.indicator.clone().next(forming))
+            }
+            _ => self.latest,
+        }
+    }
+}
+
+impl<N> Next<&DataItem> for MultiTimeframe<N>
+where
+    N: for<'a> Next<&'a DataItem, Output = f64> + Clone,
+{
+    type Output = Option<f64>;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        self.try_next(input).unwrap_or_else(|_| self.output())
+    }
+}
+
+impl<N: Period> Period for MultiTimeframe<N> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<N: Reset> Reset for MultiTimeframe<N> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.aggregator.reset();
+        self.latest = None;
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for MultiTimeframe<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MTF({}, {:?})", self.indicator, self.interval())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Maximum;
+    use alloc::format;
+    use alloc::{vec, vec::Vec};
+
+    const MINUTE: i64 = 60_000;
+
+    fn bar(timestamp: i64, open: f64, high: f64, low: f64, close: f64, volume: f64) -> DataItem {
+        DataItem::builder()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(volume)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(BarAggregator::new(Duration::from_secs(0)).is_err());
+        assert!(BarAggregator::new(Duration::from_micros(999)).is_err());
+        assert!(BarAggregator::new(Duration::from_secs(u64::MAX)).is_err());
+        let aggregator = BarAggregator::new(Duration::from_secs(300)).unwrap();
+        assert_eq!(aggregator.interval(), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_push() {
+        let mut aggregator = BarAggregator::new(Duration::from_secs(300)).unwrap();
+        assert_eq!(
+            aggregator.push(&bar(MINUTE, 10.0, 12.0, 9.0, 11.0, 100.0)),
+            Ok(None)
+        );
+        assert_eq!(
+            aggregator.push(&bar(2 * MINUTE, 11.0, 15.0, 10.0, 14.0, 50.0)),
+            Ok(None)
+        );
+        assert_eq!(
+            aggregator.push(&bar(4 * MINUTE, 14.0, 14.0, 8.0, 9.0, 25.0)),
+            Ok(None)
+        );
+        assert_eq!(aggregator.current().unwrap().close(), 9.0);
+
+        let merged = aggregator
+            .push(&bar(5 * MINUTE, 9.0, 10.0, 9.0, 10.0, 10.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged, bar(0, 10.0, 15.0, 8.0, 9.0, 175.0));
+
+        // gaps produce no bars
+        let merged = aggregator
+            .push(&bar(21 * MINUTE, 10.0, 11.0, 10.0, 11.0, 5.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged, bar(5 * MINUTE, 9.0, 10.0, 9.0, 10.0, 10.0));
+        assert_eq!(
+            aggregator.flush(),
+            Some(bar(20 * MINUTE, 10.0, 11.0, 10.0, 11.0, 5.0))
+        );
+        assert_eq!(aggregator.flush(), None);
+    }
+
+    #[test]
+    fn test_negative_timestamps() {
+        let mut aggregator = BarAggregator::new(Duration::from_secs(60)).unwrap();
+        aggregator.push_tick(-1, 2.0, 1.0).unwrap();
+        let merged = aggregator.push_tick(0, 3.0, 1.0).unwrap().unwrap();
+        assert_eq!(merged.timestamp(), Some(-MINUTE));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let mut aggregator = BarAggregator::new(Duration::from_secs(60)).unwrap();
+        let untimed = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(aggregator.push(&untimed), Err(TaError::InvalidInput));
+        assert_eq!(
+            aggregator.push_tick(0, 1.0, -1.0),
+            Err(TaError::InvalidInput)
+        );
+
+        aggregator.push_tick(2 * MINUTE, 1.0, 1.0).unwrap();
+        assert_eq!(
+            aggregator.push_tick(MINUTE, 1.0, 1.0),
+            Err(TaError::InvalidInput)
+        );
+        assert_eq!(aggregator.push_tick(3 * MINUTE - 1, 2.0, 1.0), Ok(None));
+        assert_eq!(aggregator.current().unwrap().volume(), 2.0);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut aggregator = BarAggregator::new(Duration::from_secs(60)).unwrap();
+        let with_oi = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(1.0)
+            .timestamp(0)
+            .open_interest(300.0)
+            .trade_count(4)
+            .build()
+            .unwrap();
+        aggregator.push(&with_oi).unwrap();
+        aggregator.push_tick(1000, 1.0, 1.0).unwrap();
+
+        let merged = aggregator.flush().unwrap();
+        assert_eq!(merged.open_interest(), Some(300.0));
+        assert_eq!(merged.trade_count(), Some(5));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut aggregator = BarAggregator::new(Duration::from_secs(60)).unwrap();
+        aggregator.push_tick(MINUTE, 1.0, 1.0).unwrap();
+
+        aggregator.reset();
+        assert_eq!(aggregator.current(), None);
+        assert_eq!(aggregator.push_tick(0, 1.0, 1.0), Ok(None));
+    }
+    fn mtf(mode: MultiTimeframeMode) -> MultiTimeframe<Maximum> {
+        MultiTimeframe::new(Maximum::new(2).unwrap(), Duration::from_secs(120), mode).unwrap()
+    }
+
+    fn feed(indicator: &mut MultiTimeframe<Maximum>) -> Vec<Option<f64>> {
+        [(0, 1.0), (1, 3.0), (2, 4.0), (3, 2.0), (4, 1.0), (6, 0.5)]
+            .iter()
+            .map(|&(minute, price)| {
+                indicator.next(&bar(minute * MINUTE, price, price, price, price, 1.0))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_non_repainting() {
+        let mut max = mtf(MultiTimeframeMode::NonRepainting);
+        assert_eq!(
+            feed(&mut max),
+            vec![None, None, Some(3.0), Some(3.0), Some(4.0), Some(4.0)]
+        );
+    }
+
+    #[test]
+    fn test_repainting() {
+        let mut max = mtf(MultiTimeframeMode::Repainting);
+        assert_eq!(
+            feed(&mut max),
+            vec![
+                Some(1.0),
+                Some(3.0),
+                Some(4.0),
+                Some(4.0),
+                Some(4.0),
+                Some(1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_timeframe_invalid_inputs() {
+        let mut max = mtf(MultiTimeframeMode::NonRepainting);
+        max.next(&bar(0, 2.0, 2.0, 2.0, 2.0, 1.0));
+        assert_eq!(
+            max.next(&bar(2 * MINUTE, 1.0, 1.0, 1.0, 1.0, 1.0)),
+            Some(2.0)
+        );
+        assert_eq!(
+            max.try_next(&bar(0, 5.0, 5.0, 5.0, 5.0, 1.0)),
+            Err(TaError::InvalidInput)
+        );
+        assert_eq!(max.next(&bar(0, 5.0, 5.0, 5.0, 5.0, 1.0)), Some(2.0));
+        assert_eq!(
+            max.next(&bar(4 * MINUTE, 1.0, 1.0, 1.0, 1.0, 1.0)),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn test_multi_timeframe_reset() {
+        let mut max = mtf(MultiTimeframeMode::NonRepainting);
+        feed(&mut max);
+
+        max.reset();
+        assert_eq!(max.next(&bar(0, 2.0, 2.0, 2.0, 2.0, 1.0)), None);
+        assert_eq!(
+            max.next(&bar(2 * MINUTE, 1.0, 1.0, 1.0, 1.0, 1.0)),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn test_multi_timeframe_display() {
+        let max = mtf(MultiTimeframeMode::Repainting);
+        assert_eq!(format!("{}", max), "MTF(MAX(2), 120s)");
+        assert_eq!(max.mode(), MultiTimeframeMode::Repainting);
+        assert_eq!(max.period(), 2);
+    }
+    #[test]
+    fn test_volume_bars() {
+        assert!(VolumeBarBuilder::new(0.0).is_err());
+        assert!(VolumeBarBuilder::new(f64::INFINITY).is_err());
+
+        let mut bars = VolumeBarBuilder::new(100.0).unwrap();
+        assert_eq!(bars.threshold(), 100.0);
+        assert_eq!(
+            bars.push(&bar(MINUTE, 10.0, 12.0, 9.0, 11.0, 40.0)),
+            Ok(None)
+        );
+        assert_eq!(
+            bars.push(&bar(2 * MINUTE, 11.0, 13.0, 10.0, 12.0, 60.0)),
+            Ok(Some(bar(MINUTE, 10.0, 13.0, 9.0, 12.0, 100.0)))
+        );
+        assert_eq!(bars.current(), None);
+
+        assert_eq!(
+            bars.push_tick(3 * MINUTE, 12.0, 250.0)
+                .unwrap()
+                .unwrap()
+                .volume(),
+            250.0
+        );
+        assert_eq!(bars.push_tick(4 * MINUTE, 13.0, 1.0), Ok(None));
+        assert_eq!(
+            bars.push_tick(5 * MINUTE, 13.0, -1.0),
+            Err(TaError::InvalidInput)
+        );
+        assert_eq!(bars.flush().unwrap().timestamp(), Some(4 * MINUTE));
+        assert_eq!(bars.flush(), None);
+    }
+
+    #[test]
+    fn test_volume_bars_without_timestamps() {
+        let untimed = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(50.0)
+            .build()
+            .unwrap();
+        let mut bars = VolumeBarBuilder::new(100.0).unwrap();
+        bars.push(&untimed).unwrap();
+        let merged = bars.push(&untimed).unwrap().unwrap();
+        assert_eq!(merged.timestamp(), None);
+        assert_eq!(merged.volume(), 100.0);
+    }
+
+    #[test]
+    fn test_dollar_bars() {
+        assert!(DollarBarBuilder::new(-1.0).is_err());
+
+        let mut bars = DollarBarBuilder::new(1000.0).unwrap();
+        assert_eq!(bars.push_tick(0, 10.0, 50.0), Ok(None));
+        assert_eq!(bars.push_tick(1, 20.0, 20.0), Ok(None));
+        let merged = bars.push_tick(2, 5.0, 20.0).unwrap().unwrap();
+        assert_eq!(
+            merged,
+            DataItem::builder()
+                .open(10.0)
+                .high(20.0)
+                .low(5.0)
+                .close(5.0)
+                .volume(90.0)
+                .timestamp(0)
+                .trade_count(3)
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_threshold_bars_reset() {
+        let mut volume_bars = VolumeBarBuilder::new(10.0).unwrap();
+        let mut dollar_bars = DollarBarBuilder::new(10.0).unwrap();
+        volume_bars.push_tick(0, 1.0, 6.0).unwrap();
+        dollar_bars.push_tick(0, 1.0, 6.0).unwrap();
+
+        volume_bars.reset();
+        dollar_bars.reset();
+        assert_eq!(volume_bars.push_tick(1, 1.0, 6.0), Ok(None));
+        assert_eq!(dollar_bars.push_tick(1, 1.0, 6.0), Ok(None));
+    }
+}
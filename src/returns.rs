@@ -0,0 +1,283 @@
+//! Transformers from prices to returns.
+//!
+//! The risk and [performance](crate::performance) indicators take returns rather than prices.
+//! The transformers of this module compute them from a stream of prices, so the indicators can be
+//! chained onto it directly. All of them return 0 for the first input, which has no predecessor.
+//!
+//! # Example
+//!
+//! Sharpe ratio of a stream of daily closes:
+//!
+//! ```
+//! use ta_panther::performance::SharpeRatio;
+//! use ta_panther::returns::SimpleReturns;
+//! use ta_panther::Next;
+//!
+//! let mut returns = SimpleReturns::new();
+//! let mut sharpe = SharpeRatio::new(20, 0.0, 252.0).unwrap();
+//!
+//! for &close in [100.0, 101.0, 100.5, 102.0, 103.5].iter() {
+//!     let ratio = sharpe.next(returns.next(close));
+//!     assert!(ratio.is_finite());
+//! }
+//! ```
+
+use core::fmt;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::{Close, Next, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Simple returns.
+///
+/// Returns the relative change of the input since the previous input, e.g. `0.01` for a rise of
+/// 1%. Returns 0 for the first input.
+///
+/// # Formula
+///
+/// _r<sub>t</sub> = x<sub>t</sub> / x<sub>t-1</sub> - 1_
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::returns::SimpleReturns;
+/// use ta_panther::Next;
+///
+/// let mut returns = SimpleReturns::new();
+/// assert_eq!(returns.next(10.0), 0.0);
+/// assert_eq!(returns.next(15.0), 0.5);
+/// assert_eq!(returns.next(7.5), -0.5);
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimpleReturns {
+    previous: Option<f64>,
+}
+
+impl SimpleReturns {
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+}
+
+impl Next<f64> for SimpleReturns {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self.previous.replace(input) {
+            Some(previous) => input / previous - 1.0,
+            None => 0.0,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for SimpleReturns {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for SimpleReturns {
+    fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+impl fmt::Display for SimpleReturns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RETURNS")
+    }
+}
+
+/// Logarithmic returns.
+///
+/// Returns the natural logarithm of the ratio of the input to the previous input. Unlike simple
+/// returns, log returns add up over time, which makes them the usual input of volatility
+/// estimates. Returns 0 for the first input.
+///
+/// # Formula
+///
+/// _r<sub>t</sub> = ln(x<sub>t</sub> / x<sub>t-1</sub>)_
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::returns::LogReturns;
+/// use ta_panther::Next;
+///
+/// let mut returns = LogReturns::new();
+/// assert_eq!(returns.next(1.0), 0.0);
+/// assert_eq!(returns.next(1.0_f64.exp()), 1.0);
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LogReturns {
+    previous: Option<f64>,
+}
+
+impl LogReturns {
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+}
+
+impl Next<f64> for LogReturns {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self.previous.replace(input) {
+            Some(previous) => (input / previous).ln(),
+            None => 0.0,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for LogReturns {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for LogReturns {
+    fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+impl fmt::Display for LogReturns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LOG_RETURNS")
+    }
+}
+
+/// Cumulative returns.
+///
+/// Returns the relative change of the input since the first input, e.g. the total return of an
+/// equity curve since inception. Returns 0 for the first input.
+///
+/// # Formula
+///
+/// _R<sub>t</sub> = x<sub>t</sub> / x<sub>0</sub> - 1_
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::returns::CumulativeReturns;
+/// use ta_panther::Next;
+///
+/// let mut returns = CumulativeReturns::new();
+/// assert_eq!(returns.next(10.0), 0.0);
+/// assert_eq!(returns.next(15.0), 0.5);
+/// assert_eq!(returns.next(20.0), 1.0);
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CumulativeReturns {
+    first: Option<f64>,
+}
+
+impl CumulativeReturns {
+    pub fn new() -> Self {
+        Self { first: None }
+    }
+}
+
+impl Next<f64> for CumulativeReturns {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        input / *self.first.get_or_insert(input) - 1.0
+    }
+}
+
+impl<T: Close> Next<&T> for CumulativeReturns {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for CumulativeReturns {
+    fn reset(&mut self) {
+        self.first = None;
+    }
+}
+
+impl fmt::Display for CumulativeReturns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CUM_RETURNS")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SimpleReturns);
+
+    #[test]
+    fn test_simple_next() {
+        let mut returns = SimpleReturns::new();
+        assert_eq!(returns.next(100.0), 0.0);
+        assert_eq!(round(returns.next(102.0)), 0.02);
+        assert_eq!(round(returns.next(96.9)), -0.05);
+        assert_eq!(returns.next(&Bar::new().close(96.9)), 0.0);
+    }
+
+    #[test]
+    fn test_log_next() {
+        let mut returns = LogReturns::new();
+        assert_eq!(returns.next(100.0), 0.0);
+        assert_eq!(round(returns.next(200.0)), 0.693);
+        assert_eq!(round(returns.next(100.0)), -0.693);
+        assert_eq!(returns.next(&Bar::new().close(100)), 0.0);
+    }
+
+    #[test]
+    fn test_cumulative_next() {
+        let mut returns = CumulativeReturns::new();
+        assert_eq!(returns.next(100.0), 0.0);
+        assert_eq!(round(returns.next(102.0)), 0.02);
+        assert_eq!(round(returns.next(96.9)), -0.031);
+        assert_eq!(returns.next(&Bar::new().close(150)), 0.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut simple = SimpleReturns::new();
+        let mut log = LogReturns::new();
+        let mut cumulative = CumulativeReturns::new();
+        for &x in [10.0, 20.0].iter() {
+            simple.next(x);
+            log.next(x);
+            cumulative.next(x);
+        }
+
+        simple.reset();
+        log.reset();
+        cumulative.reset();
+        assert_eq!(simple.next(5.0), 0.0);
+        assert_eq!(log.next(5.0), 0.0);
+        assert_eq!(cumulative.next(5.0), 0.0);
+        assert_eq!(cumulative.next(10.0), 1.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", SimpleReturns::new()), "RETURNS");
+        assert_eq!(format!("{}", LogReturns::new()), "LOG_RETURNS");
+        assert_eq!(format!("{}", CumulativeReturns::new()), "CUM_RETURNS");
+    }
+}
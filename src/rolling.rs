@@ -0,0 +1,877 @@
+//! Building blocks of rolling window indicators.
+//!
+//! Most indicators compute their output over the last _period_ inputs. [RollingWindow] is the
+//! ring buffer holding these inputs in the built-in indicators, exposed so custom indicators
+//! don't have to reimplement its bookkeeping. [RollingMinMax] tracks the minimum and the maximum
+//! of the last inputs in O(1) amortized time per input, whatever the period. [RollingMoments]
+//! and [RollingCoMoments] update the statistical moments of the last inputs, or pairs of inputs,
+//! in O(1) as they enter and leave the window.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::errors::Result;
+//! use ta_panther::rolling::RollingWindow;
+//! use ta_panther::Next;
+//!
+//! /// Range between the oldest and the newest input of the window.
+//! struct Drift {
+//!     window: RollingWindow<f64>,
+//! }
+//!
+//! impl Drift {
+//!     fn new(period: usize) -> Result<Self> {
+//!         Ok(Self {
+//!             window: RollingWindow::new(period)?,
+//!         })
+//!     }
+//! }
+//!
+//! impl Next<f64> for Drift {
+//!     type Output = f64;
+//!
+//!     fn next(&mut self, input: f64) -> f64 {
+//!         self.window.push(input);
+//!         self.window.newest().unwrap() - self.window.oldest().unwrap()
+//!     }
+//! }
+//!
+//! let mut drift = Drift::new(3).unwrap();
+//! assert_eq!(drift.next(2.0), 0.0);
+//! assert_eq!(drift.next(5.0), 3.0);
+//! assert_eq!(drift.next(3.0), 1.0);
+//! assert_eq!(drift.next(4.0), -1.0);
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::iter::Chain;
+use core::ops::Index;
+use core::slice::Iter;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{deque, CoMoments, Moments};
+use crate::Validate;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ring buffer of the last `capacity` values pushed into it.
+///
+/// Values are indexed and iterated from the oldest to the newest. Once the window is full,
+/// every [push](RollingWindow::push) evicts and returns the oldest value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingWindow<T> {
+    capacity: usize,
+    /// Slot written next, which holds the oldest value once the window is full.
+    index: usize,
+    values: Vec<T>,
+}
+
+impl<T> RollingWindow<T> {
+    /// Creates an empty window of `capacity` values.
+    ///
+    /// Returns [InvalidParameter](TaError::InvalidParameter) if the capacity is 0 or can't be
+    /// allocated.
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        let mut values = Vec::new();
+        values
+            .try_reserve_exact(capacity)
+            .map_err(|_| TaError::InvalidParameter)?;
+        Ok(Self {
+            capacity,
+            index: 0,
+            values,
+        })
+    }
+
+    /// Pushes a value, returning the oldest value if it was evicted because the window was full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let evicted = if self.values.len() < self.capacity {
+            self.values.push(value);
+            None
+        } else {
+            Some(core::mem::replace(&mut self.values[self.index], value))
+        };
+        self.index = if self.index + 1 < self.capacity {
+            self.index + 1
+        } else {
+            0
+        };
+        evicted
+    }
+
+    /// Returns the maximum number of values.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of values, which is the capacity once the window is full.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns whether the window holds `capacity` values, so pushing evicts the oldest one.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    /// Returns the oldest value, which is evicted by the next push once the window is full.
+    pub fn oldest(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the value pushed last.
+    pub fn newest(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|age| self.get(age))
+    }
+
+    /// Returns the `i`-th value from the oldest one.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i < self.values.len() {
+            let (older, newer) = self.as_slices();
+            Some(older.get(i).unwrap_or_else(|| &newer[i - older.len()]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the values as two slices, the values of the first one being older than the ones
+    /// of the second one.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        // until the window is full, `index` is the number of values and the first slice is empty
+        let (newer, older) = self.values.split_at(self.index);
+        (older, newer)
+    }
+
+    /// Returns an iterator over the values from the oldest to the newest.
+    pub fn iter(&self) -> Chain<Iter<'_, T>, Iter<'_, T>> {
+        let (older, newer) = self.as_slices();
+        older.iter().chain(newer)
+    }
+
+    /// Removes all the values, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.index = 0;
+    }
+}
+
+impl<T: Clone> RollingWindow<T> {
+    /// Pushes the values of a slice, as if they were pushed one by one.
+    pub fn extend_from_slice(&mut self, values: &[T]) {
+        let skip = values.len().saturating_sub(self.capacity);
+        for value in &values[skip..] {
+            self.push(value.clone());
+        }
+    }
+
+    /// Returns the values from the oldest to the newest.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Index<usize> for RollingWindow<T> {
+    type Output = T;
+
+    /// Returns the `i`-th value from the oldest one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than the number of values.
+    fn index(&self, i: usize) -> &T {
+        match self.get(i) {
+            Some(value) => value,
+            None => panic!(
+                "index {} out of range for a window of {} values",
+                i,
+                self.len()
+            ),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RollingWindow<T> {
+    type Item = &'a T;
+    type IntoIter = Chain<Iter<'a, T>, Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Extend<T> for RollingWindow<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> Validate for RollingWindow<T> {
+    fn validate(&self) -> Result<()> {
+        let len = self.values.len();
+        let valid = self.capacity > 0
+            && len <= self.capacity
+            && self.index < self.capacity
+            && (len == self.capacity || self.index == len);
+        if valid {
+            Ok(())
+        } else {
+            Err(TaError::InvalidState)
+        }
+    }
+}
+
+/// Minimum and maximum of the last `capacity` values pushed into it.
+///
+/// Both extremums are kept in monotonic deques: a pushed value evicts the older values which
+/// can't be an extremum anymore, so every value is inserted and removed at most once and a push
+/// takes O(1) amortized time. NaN values take a slot of the window but are otherwise ignored.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::rolling::RollingMinMax;
+///
+/// let mut range = RollingMinMax::new(3).unwrap();
+/// assert_eq!(range.push(4.0), (4.0, 4.0));
+/// assert_eq!(range.push(7.0), (4.0, 7.0));
+/// assert_eq!(range.push(5.0), (4.0, 7.0));
+/// assert_eq!(range.push(6.0), (5.0, 7.0));
+/// assert_eq!(range.push(5.5), (5.0, 6.0));
+///
+/// // lows and highs of bars
+/// assert_eq!(range.push_range(3.0, 8.0), (3.0, 8.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingMinMax {
+    capacity: usize,
+    len: usize,
+    /// Number of pushes, wrapping, which is the position of the next value.
+    tick: u64,
+    /// Positions and values of the candidate minimums, increasing from the front.
+    min: VecDeque<(u64, f64)>,
+    /// Positions and values of the candidate maximums, decreasing from the front.
+    max: VecDeque<(u64, f64)>,
+}
+
+impl RollingMinMax {
+    /// Creates an empty window of `capacity` values.
+    ///
+    /// Returns [InvalidParameter](TaError::InvalidParameter) if the capacity is 0 or can't be
+    /// allocated.
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            capacity,
+            len: 0,
+            tick: 0,
+            min: deque(capacity)?,
+            max: deque(capacity)?,
+        })
+    }
+
+    /// Pushes a value, returning the minimum and the maximum of the window.
+    pub fn push(&mut self, value: f64) -> (f64, f64) {
+        self.push_range(value, value)
+    }
+
+    /// Pushes the low and the high of a bar, returning the lowest low and the highest high of
+    /// the window.
+    pub fn push_range(&mut self, low: f64, high: f64) -> (f64, f64) {
+        let tick = self.tick;
+        self.tick = tick.wrapping_add(1);
+        self.len = (self.len + 1).min(self.capacity);
+
+        let expired =
+            |&(position, _): &(u64, f64)| tick.wrapping_sub(position) >= self.capacity as u64;
+        if self.min.front().is_some_and(expired) {
+            self.min.pop_front();
+        }
+        if self.max.front().is_some_and(expired) {
+            self.max.pop_front();
+        }
+        if !low.is_nan() {
+            while self.min.back().is_some_and(|&(_, value)| value >= low) {
+                self.min.pop_back();
+            }
+            self.min.push_back((tick, low));
+        }
+        if !high.is_nan() {
+            while self.max.back().is_some_and(|&(_, value)| value <= high) {
+                self.max.pop_back();
+            }
+            self.max.push_back((tick, high));
+        }
+        (self.min_or_nan(), self.max_or_nan())
+    }
+
+    /// Returns the minimum of the window, or `None` if it holds no value other than NaN.
+    pub fn min(&self) -> Option<f64> {
+        self.min.front().map(|&(_, value)| value)
+    }
+
+    /// Returns the maximum of the window, or `None` if it holds no value other than NaN.
+    pub fn max(&self) -> Option<f64> {
+        self.max.front().map(|&(_, value)| value)
+    }
+
+    fn min_or_nan(&self) -> f64 {
+        self.min().unwrap_or(f64::NAN)
+    }
+
+    fn max_or_nan(&self) -> f64 {
+        self.max().unwrap_or(f64::NAN)
+    }
+
+    /// Returns the maximum number of values.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of values, which is the capacity once the window is full.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the window holds `capacity` values, so pushing evicts the oldest one.
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    /// Removes all the values, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.tick = 0;
+        self.min.clear();
+        self.max.clear();
+    }
+}
+
+impl Validate for RollingMinMax {
+    fn validate(&self) -> Result<()> {
+        let age = |position: u64| self.tick.wrapping_sub(position);
+        let monotonic = |deque: &VecDeque<(u64, f64)>, ordered: fn(f64, f64) -> bool| {
+            deque.len() <= self.len
+                && deque.iter().all(|&(position, value)| {
+                    (1..=self.len as u64).contains(&age(position)) && !value.is_nan()
+                })
+                && deque
+                    .iter()
+                    .zip(deque.iter().skip(1))
+                    .all(|(a, b)| age(a.0) > age(b.0) && ordered(a.1, b.1))
+        };
+        let valid = self.capacity > 0
+            && self.len <= self.capacity
+            && monotonic(&self.min, |a, b| a < b)
+            && monotonic(&self.max, |a, b| a > b);
+        if valid {
+            Ok(())
+        } else {
+            Err(TaError::InvalidState)
+        }
+    }
+}
+
+/// Mean and variance, and optionally skewness and kurtosis, of the last `capacity` values
+/// pushed into it.
+///
+/// The mean and the variance are updated in O(1) per push with Welford's method, replacing the
+/// evicted value by the pushed one, which avoids both rescans of the window and the cancellation
+/// of naive sums of squares. Windows created [with_higher_moments](Self::with_higher_moments)
+/// also update the third and fourth moments with the formulas of Terriberry.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::rolling::RollingMoments;
+///
+/// let mut moments = RollingMoments::with_higher_moments(3).unwrap();
+/// for value in [1.0, 4.0, 2.0, 6.0] {
+///     moments.push(value);
+/// }
+/// assert_eq!(moments.mean(), 4.0);
+/// assert_eq!(moments.sample_variance(), 4.0);
+/// // the window 4, 2, 6 is symmetric
+/// assert!(moments.skewness().unwrap().abs() < 1e-12);
+///
+/// let moments = RollingMoments::new(3).unwrap();
+/// assert_eq!(moments.skewness(), None);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingMoments {
+    window: RollingWindow<f64>,
+    mean: f64,
+    m2: f64,
+    higher: Option<Moments>,
+}
+
+impl RollingMoments {
+    /// Creates an empty window of `capacity` values, tracking the mean and the variance.
+    ///
+    /// Returns [InvalidParameter](TaError::InvalidParameter) if the capacity is 0 or can't be
+    /// allocated.
+    pub fn new(capacity: usize) -> Result<Self> {
+        Ok(Self {
+            window: RollingWindow::new(capacity)?,
+            mean: 0.0,
+            m2: 0.0,
+            higher: None,
+        })
+    }
+
+    /// Creates an empty window of `capacity` values, also tracking the skewness and the
+    /// kurtosis.
+    pub fn with_higher_moments(capacity: usize) -> Result<Self> {
+        Ok(Self {
+            higher: Some(Moments::default()),
+            ..Self::new(capacity)?
+        })
+    }
+
+    /// Pushes a value, replacing the oldest one in the moments if the window was full.
+    pub fn push(&mut self, value: f64) {
+        match self.window.push(value) {
+            None => {
+                let delta = value - self.mean;
+                self.mean += delta / self.window.len() as f64;
+                self.m2 += delta * (value - self.mean);
+            }
+            Some(evicted) => {
+                let delta = value - evicted;
+                let old_mean = self.mean;
+                self.mean += delta / self.window.len() as f64;
+                self.m2 += delta * (value - self.mean + evicted - old_mean);
+                if let Some(higher) = self.higher.as_mut() {
+                    higher.pop(evicted);
+                }
+            }
+        }
+        if self.m2 < 0.0 {
+            self.m2 = 0.0;
+        }
+        if let Some(higher) = self.higher.as_mut() {
+            higher.push(value);
+        }
+    }
+
+    /// Returns the mean, or `0.0` if the window is empty.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the population variance, or `0.0` if the window is empty.
+    pub fn variance(&self) -> f64 {
+        match self.window.len() {
+            0 => 0.0,
+            len => self.m2 / len as f64,
+        }
+    }
+
+    /// Returns the sample variance, with Bessel's correction, or `0.0` for less than two values.
+    pub fn sample_variance(&self) -> f64 {
+        match self.window.len() {
+            0 | 1 => 0.0,
+            len => self.m2 / (len - 1) as f64,
+        }
+    }
+
+    /// Returns the population standard deviation, or `0.0` if the window is empty.
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Returns the sample standard deviation, or `0.0` for less than two values.
+    pub fn sample_std_dev(&self) -> f64 {
+        self.sample_variance().sqrt()
+    }
+
+    /// Returns the population skewness, `0.0` if all the values are equal, or `None` if the
+    /// window doesn't track [higher moments](Self::with_higher_moments).
+    pub fn skewness(&self) -> Option<f64> {
+        self.higher.as_ref().map(Moments::skewness)
+    }
+
+    /// Returns the population excess kurtosis, `0.0` if all the values are equal, or `None` if
+    /// the window doesn't track [higher moments](Self::with_higher_moments).
+    pub fn kurtosis(&self) -> Option<f64> {
+        self.higher.as_ref().map(Moments::kurtosis)
+    }
+
+    /// Returns the values of the window.
+    pub fn window(&self) -> &RollingWindow<f64> {
+        &self.window
+    }
+
+    /// Returns the maximum number of values.
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+
+    /// Returns the number of values, which is the capacity once the window is full.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Returns whether the window holds `capacity` values, so pushing evicts the oldest one.
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    /// Removes all the values, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        if let Some(higher) = self.higher.as_mut() {
+            *higher = Moments::default();
+        }
+    }
+}
+
+impl Validate for RollingMoments {
+    fn validate(&self) -> Result<()> {
+        self.window.validate()?;
+        let consistent = self.m2 >= 0.0
+            && self
+                .higher
+                .as_ref()
+                .is_none_or(|higher| higher.count() == self.window.len());
+        if consistent {
+            Ok(())
+        } else {
+            Err(TaError::InvalidState)
+        }
+    }
+}
+
+/// Covariance, correlation and beta of the last `capacity` pairs of values pushed into it.
+///
+/// The co-moments are updated in O(1) per push, like the moments of [RollingMoments].
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::rolling::RollingCoMoments;
+///
+/// let mut moments = RollingCoMoments::new(3).unwrap();
+/// for pair in [(5.0, 1.0), (1.0, 2.0), (2.0, 4.0), (3.0, 6.0)] {
+///     moments.push(pair);
+/// }
+/// assert_eq!(moments.correlation(), 1.0);
+/// assert!((moments.beta() - 0.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingCoMoments {
+    window: RollingWindow<(f64, f64)>,
+    moments: CoMoments,
+}
+
+impl RollingCoMoments {
+    /// Creates an empty window of `capacity` pairs.
+    ///
+    /// Returns [InvalidParameter](TaError::InvalidParameter) if the capacity is 0 or can't be
+    /// allocated.
+    pub fn new(capacity: usize) -> Result<Self> {
+        Ok(Self {
+            window: RollingWindow::new(capacity)?,
+            moments: CoMoments::default(),
+        })
+    }
+
+    /// Pushes a pair `(x, y)`, removing the oldest one from the co-moments if the window was
+    /// full.
+    pub fn push(&mut self, (x, y): (f64, f64)) {
+        if let Some((old_x, old_y)) = self.window.push((x, y)) {
+            self.moments.pop(old_x, old_y);
+        }
+        self.moments.push(x, y);
+    }
+
+    /// Returns the population covariance, or `0.0` if the window is empty.
+    pub fn covariance(&self) -> f64 {
+        self.moments.covariance()
+    }
+
+    /// Returns the Pearson correlation, or `0.0` if either of the series is constant.
+    pub fn correlation(&self) -> f64 {
+        self.moments.correlation()
+    }
+
+    /// Returns the slope of the regression of `x` on `y`, or `0.0` if `y` is constant.
+    pub fn beta(&self) -> f64 {
+        self.moments.beta()
+    }
+
+    /// Returns the pairs of the window.
+    pub fn window(&self) -> &RollingWindow<(f64, f64)> {
+        &self.window
+    }
+
+    /// Returns the maximum number of pairs.
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+
+    /// Returns the number of pairs, which is the capacity once the window is full.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Returns whether the window holds `capacity` pairs, so pushing evicts the oldest one.
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    /// Removes all the pairs, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.moments = CoMoments::default();
+    }
+}
+
+impl Validate for RollingCoMoments {
+    fn validate(&self) -> Result<()> {
+        self.window.validate()?;
+        if self.moments.count() == self.window.len() {
+            Ok(())
+        } else {
+            Err(TaError::InvalidState)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_new() {
+        assert!(RollingWindow::<f64>::new(0).is_err());
+        assert!(RollingWindow::<f64>::new(usize::MAX).is_err());
+
+        let window = RollingWindow::<f64>::new(3).unwrap();
+        assert!(window.is_empty());
+        assert_eq!(window.capacity(), 3);
+        assert_eq!(window.oldest(), None);
+        assert_eq!(window.newest(), None);
+    }
+
+    #[test]
+    fn test_push() {
+        let mut window = RollingWindow::new(3).unwrap();
+        assert_eq!(window.push(1.0), None);
+        assert_eq!(window.push(2.0), None);
+        assert!(!window.is_full());
+        assert_eq!(window.to_vec(), vec![1.0, 2.0]);
+        assert_eq!(window.push(3.0), None);
+        assert!(window.is_full());
+        assert_eq!(window.push(4.0), Some(1.0));
+        assert_eq!(window.push(5.0), Some(2.0));
+
+        assert_eq!(window.len(), 3);
+        assert_eq!(window.to_vec(), vec![3.0, 4.0, 5.0]);
+        assert_eq!(
+            window.iter().rev().copied().collect::<Vec<_>>(),
+            vec![5.0, 4.0, 3.0]
+        );
+        assert_eq!(window.as_slices(), (&[3.0][..], &[4.0, 5.0][..]));
+        assert_eq!(window.oldest(), Some(&3.0));
+        assert_eq!(window.newest(), Some(&5.0));
+        assert_eq!((window[0], window[1], window[2]), (3.0, 4.0, 5.0));
+        assert_eq!(window.get(3), None);
+        assert_eq!(window.validate(), Ok(()));
+
+        window.clear();
+        assert!(window.is_empty());
+        assert_eq!(window.push(6.0), None);
+        assert_eq!(window.to_vec(), vec![6.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_range() {
+        let mut window = RollingWindow::new(2).unwrap();
+        window.push(1);
+        let _ = window[1];
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut pushed = RollingWindow::new(4).unwrap();
+        let mut extended = pushed.clone();
+        for value in 0..10 {
+            pushed.push(value);
+        }
+        extended.push(-1);
+        extended.extend_from_slice(&(0..10).collect::<Vec<_>>());
+        assert_eq!(pushed.to_vec(), extended.to_vec());
+        assert_eq!(pushed.to_vec(), vec![6, 7, 8, 9]);
+
+        extended.extend(10..12);
+        assert_eq!(extended.to_vec(), vec![8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn test_min_max() {
+        assert!(RollingMinMax::new(0).is_err());
+        assert!(RollingMinMax::new(usize::MAX).is_err());
+
+        let inputs: Vec<f64> = (0..50u64).map(|i| (i * 7919 % 31) as f64).collect();
+        for capacity in [1, 2, 3, 7, 60] {
+            let mut range = RollingMinMax::new(capacity).unwrap();
+            for (i, &input) in inputs.iter().enumerate() {
+                let window = &inputs[(i + 1).saturating_sub(capacity)..=i];
+                let min = window.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                assert_eq!(range.push(input), (min, max));
+                assert_eq!(range.len(), window.len());
+                assert_eq!(range.validate(), Ok(()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_max_range_and_nan() {
+        let mut range = RollingMinMax::new(2).unwrap();
+        assert!(range.is_empty());
+        assert_eq!(range.push_range(2.0, 5.0), (2.0, 5.0));
+        assert_eq!(range.push_range(3.0, 4.0), (2.0, 5.0));
+        assert!(range.is_full());
+        assert_eq!(range.push_range(f64::NAN, 6.0), (3.0, 6.0));
+        let (min, max) = range.push(f64::NAN);
+        assert!(min.is_nan());
+        assert_eq!(max, 6.0);
+        assert_eq!(range.min(), None);
+        assert_eq!(range.validate(), Ok(()));
+
+        range.clear();
+        assert!(range.is_empty());
+        assert_eq!(range.max(), None);
+        assert_eq!(range.push(1.0), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        assert!(RollingMoments::new(0).is_err());
+
+        let inputs: Vec<f64> = (0..40u64).map(|i| (i * 7919 % 31) as f64 / 4.0).collect();
+        let mut moments = RollingMoments::with_higher_moments(5).unwrap();
+        for (i, &input) in inputs.iter().enumerate() {
+            moments.push(input);
+            let window = &inputs[(i + 1).saturating_sub(5)..=i];
+            let n = window.len() as f64;
+            let mean = window.iter().sum::<f64>() / n;
+            let m2 = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+            let m3 = window.iter().map(|x| (x - mean).powi(3)).sum::<f64>();
+            assert!((moments.mean() - mean).abs() < 1e-9);
+            assert!((moments.variance() - m2 / n).abs() < 1e-9);
+            assert!((moments.std_dev() - (m2 / n).sqrt()).abs() < 1e-9);
+            if n > 1.0 {
+                assert!((moments.sample_variance() - m2 / (n - 1.0)).abs() < 1e-9);
+                let skewness = n.sqrt() * m3 / m2.powf(1.5);
+                assert!((moments.skewness().unwrap() - skewness).abs() < 1e-9);
+            }
+            assert_eq!(moments.validate(), Ok(()));
+        }
+        assert_eq!(moments.window().len(), 5);
+
+        moments.clear();
+        assert!(moments.is_empty());
+        moments.push(4.2);
+        moments.push(4.2);
+        assert_eq!((moments.mean(), moments.sample_std_dev()), (4.2, 0.0));
+        assert_eq!(moments.kurtosis(), Some(0.0));
+        assert_eq!(RollingMoments::new(2).unwrap().kurtosis(), None);
+    }
+
+    #[test]
+    fn test_co_moments() {
+        assert!(RollingCoMoments::new(0).is_err());
+
+        let mut moments = RollingCoMoments::new(3).unwrap();
+        assert_eq!(moments.correlation(), 0.0);
+        for pair in [(9.0, 9.0), (1.0, 3.0), (2.0, 1.0), (3.0, 2.0)] {
+            moments.push(pair);
+        }
+        // the window (1, 3), (2, 1), (3, 2)
+        assert!((moments.covariance() + 1.0 / 3.0).abs() < 1e-12);
+        assert!((moments.correlation() + 0.5).abs() < 1e-12);
+        assert!((moments.beta() + 0.5).abs() < 1e-12);
+        assert!(moments.is_full());
+        assert_eq!(moments.validate(), Ok(()));
+
+        moments.clear();
+        assert_eq!(moments.len(), 0);
+        assert_eq!(moments.covariance(), 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_validate() {
+        let mut window = RollingWindow::new(3).unwrap();
+        window.push(1.0);
+        let json = serde_json::to_string(&window).unwrap();
+        assert_eq!(json, r#"{"capacity":3,"index":1,"values":[1.0]}"#);
+
+        let parse = |json: &str| serde_json::from_str::<RollingWindow<f64>>(json).unwrap();
+        assert_eq!(parse(&json).validate(), Ok(()));
+        for invalid in [
+            r#"{"capacity":0,"index":0,"values":[]}"#,
+            r#"{"capacity":1,"index":0,"values":[1.0,2.0]}"#,
+            r#"{"capacity":3,"index":3,"values":[1.0,2.0,3.0]}"#,
+            r#"{"capacity":3,"index":0,"values":[1.0]}"#,
+        ] {
+            assert_eq!(parse(invalid).validate(), Err(TaError::InvalidState));
+        }
+
+        let mut range = RollingMinMax::new(3).unwrap();
+        range.push(1.0);
+        range.push(2.0);
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(
+            json,
+            r#"{"capacity":3,"len":2,"tick":2,"min":[[0,1.0],[1,2.0]],"max":[[1,2.0]]}"#
+        );
+        let parse = |json: &str| serde_json::from_str::<RollingMinMax>(json).unwrap();
+        assert_eq!(parse(&json).validate(), Ok(()));
+        for invalid in [
+            r#"{"capacity":3,"len":4,"tick":4,"min":[],"max":[]}"#,
+            r#"{"capacity":3,"len":2,"tick":2,"min":[[1,2.0],[0,1.0]],"max":[]}"#,
+            r#"{"capacity":3,"len":2,"tick":2,"min":[[0,2.0],[1,1.0]],"max":[]}"#,
+            r#"{"capacity":3,"len":2,"tick":2,"min":[[2,1.0]],"max":[]}"#,
+        ] {
+            assert_eq!(parse(invalid).validate(), Err(TaError::InvalidState));
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! Custom indicators written in the [Rhai](https://rhai.rs) scripting language.
+//!
+//! Available with the `rhai` feature.
+//!
+//! A [ScriptIndicator] is defined by a script containing a function `next(value, state)` which
+//! returns an array `[output, new_state]`. The optional function `init()` returns the initial
+//! state, otherwise the state starts as `()`. The state can be any Rhai value (e.g. a number,
+//! an array or an object map).
+//!
+//! Script indicators implement [Next], [Reset] and `Display`, so they can be used anywhere a
+//! built-in indicator can, and they serialize together with their source and current state, so
+//! snapshots restore them completely.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::script::ScriptIndicator;
+//! use ta_panther::Next;
+//!
+//! let script = r#"
+//!     fn init() { #{ sum: 0.0, count: 0 } }
+//!
+//!     fn next(value, state) {
+//!         state.sum += value;
+//!         state.count += 1;
+//!         [state.sum / state.count, state]
+//!     }
+//! "#;
+//!
+//! let mut mean = ScriptIndicator::new("MEAN", script).unwrap();
+//! assert_eq!(mean.next(2.0), 2.0);
+//! assert_eq!(mean.next(4.0), 3.0);
+//! assert_eq!(mean.next(9.0), 5.0);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, ParseError, Scope, AST};
+use serde::{Deserialize, Serialize};
+
+use crate::{Close, Next, Reset};
+
+/// Maximum number of operations a single call of a script may perform, which stops runaway
+/// scripts such as infinite loops.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Error returned when a script can not be used as an indicator.
+#[derive(Debug)]
+pub enum ScriptError {
+    /// The script does not compile.
+    Parse(ParseError),
+    /// Evaluation of the script failed.
+    Eval(Box<EvalAltResult>),
+    /// The script has no `next(value, state)` function.
+    MissingNext,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScriptError::Parse(err) => write!(f, "script does not compile: {}", err),
+            ScriptError::Eval(err) => write!(f, "script failed: {}", err),
+            ScriptError::MissingNext => write!(f, "script has no `next(value, state)` function"),
+        }
+    }
+}
+
+impl Error for ScriptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScriptError::Parse(err) => Some(err),
+            ScriptError::Eval(err) => Some(err.as_ref()),
+            ScriptError::MissingNext => None,
+        }
+    }
+}
+
+/// Indicator implemented by a Rhai script.
+///
+/// If a call of `next` fails (e.g. the script returns something else than a two element array
+/// with a numeric output), the output is NaN, the state is left unchanged and the error is
+/// available through [last_error](ScriptIndicator::last_error).
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "ScriptSnapshot", into = "ScriptSnapshot")]
+pub struct ScriptIndicator {
+    name: String,
+    source: String,
+    initial_state: Dynamic,
+    state: Dynamic,
+    last_error: Option<String>,
+    compiled: Arc<Compiled>,
+}
+
+struct Compiled {
+    engine: Engine,
+    ast: AST,
+}
+
+impl ScriptIndicator {
+    /// Compiles `source` into an indicator displayed as `name`.
+    pub fn new(name: &str, source: &str) -> Result<Self, ScriptError> {
+        let compiled = Compiled::new(source)?;
+        let initial_state = compiled.init()?;
+        Ok(Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            state: initial_state.clone(),
+            initial_state,
+            last_error: None,
+            compiled: Arc::new(compiled),
+        })
+    }
+
+    /// Returns the source of the script.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the current state of the script.
+    pub fn state(&self) -> &Dynamic {
+        &self.state
+    }
+
+    /// Returns the error of the latest call of `next`, if it failed.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+impl Compiled {
+    fn new(source: &str) -> Result<Self, ScriptError> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let ast = engine.compile(source).map_err(ScriptError::Parse)?;
+        if !ast
+            .iter_functions()
+            .any(|f| f.name == "next" && f.params.len() == 2)
+        {
+            return Err(ScriptError::MissingNext);
+        }
+        Ok(Self { engine, ast })
+    }
+
+    fn init(&self) -> Result<Dynamic, ScriptError> {
+        if !self
+            .ast
+            .iter_functions()
+            .any(|f| f.name == "init" && f.params.is_empty())
+        {
+            return Ok(Dynamic::UNIT);
+        }
+        self.engine
+            .call_fn(&mut Scope::new(), &self.ast, "init", ())
+            .map_err(ScriptError::Eval)
+    }
+
+    fn next(&self, input: f64, state: Dynamic) -> Result<(f64, Dynamic), String> {
+        let result: Array = self
+            .engine
+            .call_fn(&mut Scope::new(), &self.ast, "next", (input, state))
+            .map_err(|err| err.to_string())?;
+        let [output, state]: [Dynamic; 2] = result
+            .try_into()
+            .map_err(|_| "`next` must return an array of [output, state]".to_string())?;
+        let output = output
+            .as_float()
+            .or_else(|_| output.as_int().map(|int| int as f64))
+            .map_err(|type_name| format!("output must be a number, got {}", type_name))?;
+        Ok((output, state))
+    }
+}
+
+impl Next<f64> for ScriptIndicator {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self.compiled.next(input, self.state.clone()) {
+            Ok((output, state)) => {
+                self.state = state;
+                self.last_error = None;
+                output
+            }
+            Err(err) => {
+                self.last_error = Some(err);
+                f64::NAN
+            }
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for ScriptIndicator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for ScriptIndicator {
+    fn reset(&mut self) {
+        self.state = self.initial_state.clone();
+        self.last_error = None;
+    }
+}
+
+impl fmt::Display for ScriptIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl fmt::Debug for ScriptIndicator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScriptIndicator")
+            .field("name", &self.name)
+            .field("state", &self.state)
+            .field("last_error", &self.last_error)
+            .finish()
+    }
+}
+
+/// Serialized form of a [ScriptIndicator], which is compiled again on deserialization.
+#[derive(Serialize, Deserialize)]
+struct ScriptSnapshot {
+    name: String,
+    source: String,
+    initial_state: Dynamic,
+    state: Dynamic,
+}
+
+impl From<ScriptIndicator> for ScriptSnapshot {
+    fn from(indicator: ScriptIndicator) -> Self {
+        Self {
+            name: indicator.name,
+            source: indicator.source,
+            initial_state: indicator.initial_state,
+            state: indicator.state,
+        }
+    }
+}
+
+impl TryFrom<ScriptSnapshot> for ScriptIndicator {
+    type Error = ScriptError;
+
+    fn try_from(snapshot: ScriptSnapshot) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: snapshot.name,
+            compiled: Arc::new(Compiled::new(&snapshot.source)?),
+            source: snapshot.source,
+            initial_state: snapshot.initial_state,
+            state: snapshot.state,
+            last_error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::ExponentialMovingAverage;
+    use crate::test_helper::*;
+
+    const EMA: &str = r#"
+        fn init() { () }
+
+        fn next(value, state) {
+            let k = 2.0 / (3 + 1);
+            let ema = if state == () { value } else { k * value + (1.0 - k) * state };
+            [ema, ema]
+        }
+    "#;
+
+    #[test]
+    fn test_new() {
+        assert!(matches!(
+            ScriptIndicator::new("X", "fn next(value) { value }"),
+            Err(ScriptError::MissingNext)
+        ));
+        assert!(matches!(
+            ScriptIndicator::new("X", "fn next(value, state) {"),
+            Err(ScriptError::Parse(_))
+        ));
+        assert!(matches!(
+            ScriptIndicator::new("X", "fn init() { 1 / 0 } fn next(v, s) { [v, s] }"),
+            Err(ScriptError::Eval(_))
+        ));
+        assert!(ScriptIndicator::new("X", EMA).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut script = ScriptIndicator::new("EMA3", EMA).unwrap();
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        for &value in &[2.0, 5.0, 1.0, 6.25, 3.0] {
+            assert_eq!(round(script.next(value)), round(ema.next(value)));
+        }
+    }
+
+    #[test]
+    fn test_integer_output() {
+        let mut count = ScriptIndicator::new(
+            "COUNT",
+            "fn init() { 0 } fn next(value, state) { [state + 1, state + 1] }",
+        )
+        .unwrap();
+        assert_eq!(count.next(5.0), 1.0);
+        assert_eq!(count.next(5.0), 2.0);
+    }
+
+    #[test]
+    fn test_runtime_error() {
+        let mut broken = ScriptIndicator::new("BROKEN", "fn next(value, state) { value }").unwrap();
+        assert!(broken.next(1.0).is_nan());
+        assert!(broken.last_error().is_some());
+
+        let mut endless =
+            ScriptIndicator::new("ENDLESS", "fn next(value, state) { loop {} }").unwrap();
+        assert!(endless.next(1.0).is_nan());
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut script = ScriptIndicator::new("EMA3", EMA).unwrap();
+        assert_eq!(script.next(&Bar::new().close(4)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut script = ScriptIndicator::new("EMA3", EMA).unwrap();
+        script.next(4.0);
+        script.next(8.0);
+
+        script.reset();
+        assert_eq!(script.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_serde() {
+        let mut script = ScriptIndicator::new("EMA3", EMA).unwrap();
+        script.next(2.0);
+        script.next(5.0);
+
+        let json = serde_json::to_string(&script).unwrap();
+        let mut restored: ScriptIndicator = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.next(1.0), script.next(1.0));
+        assert_eq!(format!("{}", restored), "EMA3");
+    }
+
+    #[test]
+    fn test_display() {
+        let script = ScriptIndicator::new("MY_EMA", EMA).unwrap();
+        assert_eq!(format!("{}", script), "MY_EMA");
+    }
+}
@@ -0,0 +1,760 @@
+//! JSON-RPC service exposing indicators to other processes.
+//!
+//! Available with the `server` feature.
+//!
+//! The service lets programs written in any language use the indicators of this crate as a
+//! sidecar: they create indicator instances by name, push values into them, read the outputs
+//! and take snapshots of the state. Requests and responses follow
+//! [JSON-RPC 2.0](https://www.jsonrpc.org/specification) and are exchanged as one JSON document
+//! per line over TCP, see [serve].
+//!
+//! # Methods
+//!
+//! * `indicators` - returns the names of all indicators known to the [IndicatorRegistry]
+//! * `create` - `{"indicator": "SMA", "params": [14], "id": "optional id"}`, creates an instance
+//!   and returns its `id` and `name`. The `id` must not be in use, and parameters are limited
+//!   to the [max_period](Service::with_max_period) of the service
+//! * `next` - `{"id": ..., "value": 1.0}` returns the output for a single value,
+//!   `{"id": ..., "values": [1.0, 2.0]}` returns an array of outputs
+//! * `get` - `{"id": ...}` returns the latest output, or `null` before the first value
+//! * `reset` - `{"id": ...}` resets the instance
+//! * `snapshot` - `{"id": ...}` returns the name and the serialized state of the instance
+//! * `remove` - `{"id": ...}` removes the instance, returning `true` if it existed
+//! * `list` - returns the `id` and `name` of all instances
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::server::{IndicatorRegistry, Service};
+//!
+//! let mut service = Service::new(IndicatorRegistry::default());
+//!
+//! let response = service.handle(
+//!     r#"{"jsonrpc": "2.0", "id": 1, "method": "create", "params": {"indicator": "SMA", "params": [2], "id": "sma"}}"#,
+//! );
+//! assert_eq!(
+//!     response.unwrap(),
+//!     r#"{"id":1,"jsonrpc":"2.0","result":{"id":"sma","name":"SMA(2)"}}"#
+//! );
+//!
+//! let response = service.handle(
+//!     r#"{"jsonrpc": "2.0", "id": 2, "method": "next", "params": {"id": "sma", "values": [1, 2, 3]}}"#,
+//! );
+//! assert_eq!(
+//!     response.unwrap(),
+//!     r#"{"id":2,"jsonrpc":"2.0","result":[1.0,1.5,2.5]}"#
+//! );
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::errors::{Result, TaError};
+use crate::factory::{register_multi_output_indicators, register_scalar_indicators};
+#[cfg(feature = "plugin")]
+use crate::plugin::{Plugin, PluginIndicator};
+use crate::{FromParams, Next, Reset};
+
+/// Indicator which can be driven by the [Service].
+///
+/// Implemented for every indicator accepting `f64` inputs whose output and state can be
+/// serialized.
+pub trait ServiceIndicator: Send {
+    /// Feeds the next value, returning the serialized output.
+    fn push(&mut self, input: f64) -> Value;
+
+    /// Resets the indicator to its initial state.
+    fn reset_state(&mut self);
+
+    /// Returns the serialized state of the indicator.
+    fn snapshot(&self) -> serde_json::Result<Value>;
+
+    /// Returns the name of the indicator with its parameters, e.g. `SMA(14)`.
+    fn name(&self) -> String;
+}
+
+impl<T> ServiceIndicator for T
+where
+    T: Next<f64> + Reset + Serialize + fmt::Display + Send,
+    T::Output: Serialize,
+{
+    fn push(&mut self, input: f64) -> Value {
+        serde_json::to_value(Next::next(self, input)).unwrap_or(Value::Null)
+    }
+
+    fn reset_state(&mut self) {
+        Reset::reset(self);
+    }
+
+    fn snapshot(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
+    }
+
+    fn name(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(feature = "plugin")]
+impl ServiceIndicator for PluginIndicator {
+    fn push(&mut self, input: f64) -> Value {
+        json!(Next::next(self, input))
+    }
+
+    fn reset_state(&mut self) {
+        Reset::reset(self);
+    }
+
+    /// Plugin indicators are opaque, so their state can not be serialized.
+    fn snapshot(&self) -> serde_json::Result<Value> {
+        Err(serde::ser::Error::custom(
+            "state of plugin indicators can not be serialized",
+        ))
+    }
+
+    fn name(&self) -> String {
+        self.to_string()
+    }
+}
+
+type Constructor = Box<dyn Fn(&[f64]) -> Result<Box<dyn ServiceIndicator>> + Send + Sync>;
+
+/// Constructors of indicators by name.
+///
+/// The default registry knows all the built-in indicators accepting `f64` inputs, named after
+/// their abbreviation (e.g. `SMA`, `RSI`, `BB`). Custom indicators can be added with
+/// [register](IndicatorRegistry::register).
+pub struct IndicatorRegistry {
+    constructors: HashMap<String, Constructor>,
+}
+
+impl IndicatorRegistry {
+    /// Creates a registry without any indicators.
+    pub fn empty() -> Self {
+        Self {
+            constructors: HashMap::new(),
+        }
+    }
+
+    /// Registers a constructor of an indicator under `name`, replacing a previous one.
+    pub fn register<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&[f64]) -> Result<Box<dyn ServiceIndicator>> + Send + Sync + 'static,
+    {
+        self.constructors
+            .insert(name.to_uppercase(), Box::new(constructor));
+    }
+
+    /// Registers an indicator type under its [FromParams::NAME].
+    pub fn register_type<T>(&mut self)
+    where
+        T: FromParams + ServiceIndicator + 'static,
+    {
+        self.register(T::NAME, |params| Ok(Box::new(T::from_params(params)?)));
+    }
+
+    /// Registers all the indicators of a plugin under their names.
+    ///
+    /// Available with the `plugin` feature.
+    #[cfg(feature = "plugin")]
+    pub fn register_plugin(&mut self, plugin: &Plugin) {
+        for name in plugin.names() {
+            let plugin = plugin.clone();
+            let indicator = name.to_string();
+            self.register(name, move |params| {
+                Ok(Box::new(plugin.create(&indicator, params)?))
+            });
+        }
+    }
+
+    /// Creates an indicator by its case-insensitive name.
+    ///
+    /// Returns an error if the name is unknown or the parameters are invalid.
+    pub fn create(&self, name: &str, params: &[f64]) -> Result<Box<dyn ServiceIndicator>> {
+        match self.constructors.get(&name.to_uppercase()) {
+            Some(constructor) => constructor(params),
+            None => Err(TaError::InvalidParameter),
+        }
+    }
+
+    /// Returns the sorted names of all registered indicators.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.constructors.keys().map(|name| name.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl Default for IndicatorRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+
+        register_scalar_indicators!(registry);
+        register_multi_output_indicators!(registry);
+
+        registry
+    }
+}
+
+impl fmt::Debug for IndicatorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IndicatorRegistry")
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const UNKNOWN_INSTANCE: i64 = -32000;
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl fmt::Display) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateParams {
+    indicator: String,
+    #[serde(default)]
+    params: Vec<f64>,
+    id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NextParams {
+    id: String,
+    value: Option<f64>,
+    values: Option<Vec<f64>>,
+}
+
+#[derive(Deserialize)]
+struct InstanceParams {
+    id: String,
+}
+
+struct Instance {
+    indicator: Box<dyn ServiceIndicator>,
+    latest: Value,
+}
+
+/// Default limit of the parameters of the indicators created by a [Service].
+pub const DEFAULT_MAX_PERIOD: usize = 10_000;
+
+/// State of the JSON-RPC service: the registry and all created indicator instances.
+pub struct Service {
+    registry: IndicatorRegistry,
+    instances: BTreeMap<String, Instance>,
+    next_id: u64,
+    max_period: usize,
+}
+
+impl Service {
+    pub fn new(registry: IndicatorRegistry) -> Self {
+        Self {
+            registry,
+            instances: BTreeMap::new(),
+            next_id: 1,
+            max_period: DEFAULT_MAX_PERIOD,
+        }
+    }
+
+    /// Sets the largest parameter accepted by `create`, [DEFAULT_MAX_PERIOD] by default.
+    ///
+    /// Indicators allocate buffers as large as their periods, so the limit keeps remote clients
+    /// from exhausting the memory of the process. It applies to all the parameters, which are
+    /// periods or much smaller numbers like multipliers.
+    pub fn with_max_period(mut self, max_period: usize) -> Self {
+        self.max_period = max_period;
+        self
+    }
+
+    pub fn max_period(&self) -> usize {
+        self.max_period
+    }
+
+    /// Handles a single JSON-RPC request, returning the serialized response.
+    ///
+    /// Notifications (requests without an `id`) are executed, but produce no response.
+    pub fn handle(&mut self, request: &str) -> Option<String> {
+        let request: Value = match serde_json::from_str(request) {
+            Ok(request) => request,
+            Err(err) => return Some(response(Value::Null, Err(RpcError::new(PARSE_ERROR, err)))),
+        };
+
+        let id = request.get("id").cloned();
+        let result = match request.get("method").and_then(Value::as_str) {
+            Some(method) => {
+                let params = request.get("params").cloned().unwrap_or(Value::Null);
+                self.call(method, params)
+            }
+            None => Err(RpcError::new(INVALID_REQUEST, "missing method")),
+        };
+
+        id.map(|id| response(id, result))
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        match method {
+            "indicators" => Ok(json!(self.registry.names())),
+            "create" => {
+                let params: CreateParams = parse(params)?;
+                if let Some(param) = params
+                    .params
+                    .iter()
+                    .find(|&&param| param > self.max_period as f64)
+                {
+                    return Err(RpcError::new(
+                        INVALID_PARAMS,
+                        format!(
+                            "parameter {} exceeds the maximum period {}",
+                            param, self.max_period
+                        ),
+                    ));
+                }
+                let id = match params.id {
+                    Some(id) if self.instances.contains_key(&id) => {
+                        return Err(RpcError::new(
+                            INVALID_PARAMS,
+                            format!("instance `{}` already exists", id),
+                        ));
+                    }
+                    Some(id) => id,
+                    None => self.generate_id(),
+                };
+                let indicator = self
+                    .registry
+                    .create(&params.indicator, &params.params)
+                    .map_err(|err| RpcError::new(INVALID_PARAMS, err))?;
+                let name = indicator.name();
+                self.instances.insert(
+                    id.clone(),
+                    Instance {
+                        indicator,
+                        latest: Value::Null,
+                    },
+                );
+                Ok(json!({ "id": id, "name": name }))
+            }
+            "next" => {
+                let params: NextParams = parse(params)?;
+                let instance = self.instance(&params.id)?;
+                match (params.value, params.values) {
+                    (Some(value), None) => {
+                        instance.latest = instance.indicator.push(value);
+                        Ok(instance.latest.clone())
+                    }
+                    (None, Some(values)) => {
+                        let outputs: Vec<Value> = values
+                            .into_iter()
+                            .map(|value| instance.indicator.push(value))
+                            .collect();
+                        if let Some(latest) = outputs.last() {
+                            instance.latest = latest.clone();
+                        }
+                        Ok(Value::Array(outputs))
+                    }
+                    _ => Err(RpcError::new(
+                        INVALID_PARAMS,
+                        "expected either `value` or `values`",
+                    )),
+                }
+            }
+            "get" => {
+                let params: InstanceParams = parse(params)?;
+                Ok(self.instance(&params.id)?.latest.clone())
+            }
+            "reset" => {
+                let params: InstanceParams = parse(params)?;
+                let instance = self.instance(&params.id)?;
+                instance.indicator.reset_state();
+                instance.latest = Value::Null;
+                Ok(Value::Null)
+            }
+            "snapshot" => {
+                let params: InstanceParams = parse(params)?;
+                let instance = self.instance(&params.id)?;
+                let state = instance
+                    .indicator
+                    .snapshot()
+                    .map_err(|err| RpcError::new(INVALID_PARAMS, err))?;
+                Ok(json!({ "name": instance.indicator.name(), "state": state }))
+            }
+            "remove" => {
+                let params: InstanceParams = parse(params)?;
+                Ok(json!(self.instances.remove(&params.id).is_some()))
+            }
+            "list" => Ok(Value::Array(
+                self.instances
+                    .iter()
+                    .map(|(id, instance)| json!({ "id": id, "name": instance.indicator.name() }))
+                    .collect(),
+            )),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method `{}`", method),
+            )),
+        }
+    }
+
+    /// Returns the next numeric id which isn't used by an instance.
+    fn generate_id(&mut self) -> String {
+        loop {
+            let id = self.next_id.to_string();
+            self.next_id += 1;
+            if !self.instances.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    fn instance(&mut self, id: &str) -> std::result::Result<&mut Instance, RpcError> {
+        self.instances
+            .get_mut(id)
+            .ok_or_else(|| RpcError::new(UNKNOWN_INSTANCE, format!("unknown instance `{}`", id)))
+    }
+}
+
+impl fmt::Debug for Service {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Service")
+            .field("registry", &self.registry)
+            .field("instances", &self.instances.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(params: Value) -> std::result::Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|err| RpcError::new(INVALID_PARAMS, err))
+}
+
+fn response(id: Value, result: std::result::Result<Value, RpcError>) -> String {
+    let response = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+        Err(err) => json!({
+            "jsonrpc": "2.0",
+            "error": { "code": err.code, "message": err.message },
+            "id": id,
+        }),
+    };
+    response.to_string()
+}
+
+/// Serves a shared [Service] over TCP.
+///
+/// Every connection is handled by its own thread. Clients send one JSON-RPC request per line
+/// and receive one response per line, in the same order. The function blocks and only returns
+/// if accepting a connection fails.
+pub fn serve(listener: TcpListener, service: Arc<Mutex<Service>>) -> io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept()?;
+        let service = Arc::clone(&service);
+        thread::spawn(move || -> io::Result<()> {
+            let mut writer = stream.try_clone()?;
+            for line in BufReader::new(stream).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = match service.lock() {
+                    Ok(mut service) => service.handle(&line),
+                    Err(poisoned) => poisoned.into_inner().handle(&line),
+                };
+                if let Some(response) = response {
+                    writeln!(writer, "{}", response)?;
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factory::periods;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+
+    fn call(service: &mut Service, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params });
+        let response = service.handle(&request.to_string()).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = IndicatorRegistry::default();
+        assert!(registry.names().contains(&"SMA"));
+        assert_eq!(registry.create("sma", &[3.0]).unwrap().name(), "SMA(3)");
+        assert_eq!(
+            registry.create("BB", &[20.0, 2.0]).unwrap().name(),
+            "BB(20, 2)"
+        );
+        assert!(registry.create("SMA", &[]).is_err());
+        assert!(registry.create("SMA", &[2.5]).is_err());
+        assert!(registry.create("SMA", &[0.0]).is_err());
+        assert!(registry.create("NOPE", &[1.0]).is_err());
+        assert_eq!(registry.create("TR", &[]).unwrap().name(), "TRUE_RANGE()");
+    }
+
+    #[test]
+    fn test_register() {
+        let mut registry = IndicatorRegistry::empty();
+        registry.register("double_ema", |params| {
+            let [period] = periods(params)?;
+            Ok(Box::new(ExponentialMovingAverage::new(period * 2)?))
+        });
+        assert_eq!(registry.names(), vec!["DOUBLE_EMA"]);
+        assert_eq!(
+            registry.create("double_ema", &[5.0]).unwrap().name(),
+            "EMA(10)"
+        );
+    }
+
+    #[test]
+    fn test_register_type() {
+        #[derive(Serialize)]
+        struct Double(SimpleMovingAverage);
+
+        impl Next<f64> for Double {
+            type Output = f64;
+
+            fn next(&mut self, input: f64) -> f64 {
+                self.0.next(input) * 2.0
+            }
+        }
+
+        impl Reset for Double {
+            fn reset(&mut self) {
+                self.0.reset();
+            }
+        }
+
+        impl fmt::Display for Double {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "DOUBLE({})", self.0)
+            }
+        }
+
+        impl FromParams for Double {
+            const NAME: &'static str = "DOUBLE";
+
+            fn from_params(params: &[f64]) -> Result<Self> {
+                let [period] = periods(params)?;
+                Ok(Double(SimpleMovingAverage::new(period)?))
+            }
+        }
+
+        let mut registry = IndicatorRegistry::empty();
+        registry.register_type::<Double>();
+        let mut double = registry.create("double", &[2.0]).unwrap();
+        assert_eq!(double.name(), "DOUBLE(SMA(2))");
+        assert_eq!(double.push(3.0), json!(6.0));
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut service = Service::new(IndicatorRegistry::default());
+
+        let created = call(
+            &mut service,
+            "create",
+            json!({"indicator": "SMA", "params": [2]}),
+        );
+        assert_eq!(created["result"], json!({"id": "1", "name": "SMA(2)"}));
+
+        assert_eq!(
+            call(&mut service, "get", json!({"id": "1"}))["result"],
+            Value::Null
+        );
+        assert_eq!(
+            call(&mut service, "next", json!({"id": "1", "value": 4.0}))["result"],
+            json!(4.0)
+        );
+        assert_eq!(
+            call(
+                &mut service,
+                "next",
+                json!({"id": "1", "values": [6.0, 10.0]})
+            )["result"],
+            json!([5.0, 8.0])
+        );
+        assert_eq!(
+            call(&mut service, "get", json!({"id": "1"}))["result"],
+            json!(8.0)
+        );
+
+        let snapshot = call(&mut service, "snapshot", json!({"id": "1"}))["result"].clone();
+        assert_eq!(snapshot["name"], json!("SMA(2)"));
+        let restored: SimpleMovingAverage =
+            serde_json::from_value(snapshot["state"].clone()).unwrap();
+        assert_eq!(format!("{}", restored), "SMA(2)");
+
+        call(&mut service, "reset", json!({"id": "1"}));
+        assert_eq!(
+            call(&mut service, "next", json!({"id": "1", "value": 1.0}))["result"],
+            json!(1.0)
+        );
+
+        assert_eq!(
+            call(&mut service, "list", Value::Null)["result"],
+            json!([{"id": "1", "name": "SMA(2)"}])
+        );
+        assert_eq!(
+            call(&mut service, "remove", json!({"id": "1"}))["result"],
+            json!(true)
+        );
+        assert_eq!(call(&mut service, "list", Value::Null)["result"], json!([]));
+    }
+
+    #[test]
+    fn test_multi_output() {
+        let mut service = Service::new(IndicatorRegistry::default());
+        call(
+            &mut service,
+            "create",
+            json!({"indicator": "KALMAN", "params": [0.01, 1.0], "id": "k"}),
+        );
+        let output = call(&mut service, "next", json!({"id": "k", "value": 3.0}))["result"].clone();
+        assert_eq!(output, json!({"value": 3.0, "velocity": 0.0}));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut service = Service::new(IndicatorRegistry::default());
+
+        let response: Value = serde_json::from_str(&service.handle("{").unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], json!(PARSE_ERROR));
+
+        let code = |response: Value| response["error"]["code"].clone();
+        assert_eq!(
+            code(call(&mut service, "unknown", Value::Null)),
+            json!(METHOD_NOT_FOUND)
+        );
+        assert_eq!(
+            code(call(
+                &mut service,
+                "create",
+                json!({"indicator": "SMA", "params": [0]})
+            )),
+            json!(INVALID_PARAMS)
+        );
+        assert_eq!(
+            code(call(&mut service, "get", json!({"id": "missing"}))),
+            json!(UNKNOWN_INSTANCE)
+        );
+        assert_eq!(
+            code(call(&mut service, "get", json!({}))),
+            json!(INVALID_PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_max_period() {
+        let mut service = Service::new(IndicatorRegistry::default()).with_max_period(100);
+        assert_eq!(service.max_period(), 100);
+        let create = |service: &mut Service, period: f64| {
+            call(
+                service,
+                "create",
+                json!({"indicator": "SMA", "params": [period]}),
+            )
+        };
+        assert_eq!(
+            create(&mut service, 100.0)["result"]["name"],
+            json!("SMA(100)")
+        );
+        let response = create(&mut service, 101.0);
+        assert_eq!(response["error"]["code"], json!(INVALID_PARAMS));
+        assert_eq!(
+            response["error"]["message"],
+            json!("parameter 101 exceeds the maximum period 100")
+        );
+
+        let mut service = Service::new(IndicatorRegistry::default());
+        assert_eq!(
+            create(&mut service, u32::MAX as f64)["error"]["code"],
+            json!(INVALID_PARAMS)
+        );
+        assert_eq!(call(&mut service, "list", Value::Null)["result"], json!([]));
+    }
+
+    #[test]
+    fn test_ids() {
+        let mut service = Service::new(IndicatorRegistry::default());
+        let create = |service: &mut Service, id: Option<&str>| {
+            let params = match id {
+                Some(id) => json!({"indicator": "EMA", "params": [3], "id": id}),
+                None => json!({"indicator": "EMA", "params": [3]}),
+            };
+            call(service, "create", params)
+        };
+
+        assert_eq!(create(&mut service, Some("2"))["result"]["id"], json!("2"));
+        assert_eq!(
+            create(&mut service, Some("2"))["error"]["code"],
+            json!(INVALID_PARAMS)
+        );
+        // generated ids skip the ones taken by clients
+        assert_eq!(create(&mut service, None)["result"]["id"], json!("1"));
+        assert_eq!(create(&mut service, None)["result"]["id"], json!("3"));
+
+        // the existing instance is left untouched
+        call(&mut service, "next", json!({"id": "2", "value": 4.0}));
+        create(&mut service, Some("2"));
+        assert_eq!(
+            call(&mut service, "get", json!({"id": "2"}))["result"],
+            json!(4.0)
+        );
+    }
+
+    #[test]
+    fn test_notification() {
+        let mut service = Service::new(IndicatorRegistry::default());
+        let request = r#"{"jsonrpc": "2.0", "method": "create", "params": {"indicator": "EMA", "params": [3]}}"#;
+        assert!(service.handle(request).is_none());
+        assert_eq!(
+            call(&mut service, "list", Value::Null)["result"][0]["name"],
+            json!("EMA(3)")
+        );
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service = Arc::new(Mutex::new(Service::new(IndicatorRegistry::default())));
+        thread::spawn(move || serve(listener, service));
+
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"indicators\"}\n")
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        let response: Value = serde_json::from_str(&line).unwrap();
+        assert!(response["result"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("RSI")));
+    }
+}
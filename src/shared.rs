@@ -0,0 +1,188 @@
+//! Indicators whose latest output is read from other threads.
+//!
+//! An [AtomicIndicator] wraps an indicator fed by a single writer and publishes every output
+//! into an atomic, which any number of [SharedOutput] readers load without locking. A UI
+//! thread can thus read live values on every frame, without ever blocking the thread feeding
+//! the market data.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::indicators::SimpleMovingAverage;
+//! use ta_panther::shared::AtomicIndicator;
+//! use ta_panther::Next;
+//!
+//! let mut sma = AtomicIndicator::new(SimpleMovingAverage::new(2).unwrap());
+//! let output = sma.reader();
+//! assert_eq!(output.latest(), None);
+//!
+//! sma.next(4.0);
+//! sma.next(8.0);
+//!
+//! let ui = std::thread::spawn(move || output.latest());
+//! assert_eq!(ui.join().unwrap(), Some(6.0));
+//! ```
+
+use alloc::sync::Arc;
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{Next, Period, Reset};
+
+#[derive(Debug, Default)]
+struct Slot {
+    value: AtomicU64,
+    updates: AtomicU64,
+}
+
+/// Indicator publishing its outputs to [SharedOutput] readers.
+///
+/// Outputs are returned by [next](Next::next) as usual, and additionally stored so readers
+/// see the latest one. Resetting the indicator clears the latest output.
+#[derive(Debug)]
+pub struct AtomicIndicator<I> {
+    indicator: I,
+    slot: Arc<Slot>,
+}
+
+impl<I> AtomicIndicator<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            indicator,
+            slot: Arc::default(),
+        }
+    }
+
+    /// Returns a reader of the latest output, which can be sent to other threads.
+    pub fn reader(&self) -> SharedOutput {
+        SharedOutput {
+            slot: Arc::clone(&self.slot),
+        }
+    }
+
+    /// Returns the wrapped indicator.
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    /// Returns the wrapped indicator. Readers keep the latest output published.
+    pub fn into_inner(self) -> I {
+        self.indicator
+    }
+}
+
+impl<I, T> Next<T> for AtomicIndicator<I>
+where
+    I: Next<T, Output = f64>,
+{
+    type Output = f64;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let output = self.indicator.next(input);
+        self.slot.value.store(output.to_bits(), Ordering::Relaxed);
+        self.slot.updates.fetch_add(1, Ordering::Release);
+        output
+    }
+}
+
+impl<I: Reset> Reset for AtomicIndicator<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.slot.updates.store(0, Ordering::Release);
+    }
+}
+
+impl<I: Period> Period for AtomicIndicator<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for AtomicIndicator<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+/// Lock-free reader of the latest output of an [AtomicIndicator].
+#[derive(Debug, Clone)]
+pub struct SharedOutput {
+    slot: Arc<Slot>,
+}
+
+impl SharedOutput {
+    /// Returns the latest output, or `None` if no input has been fed since the indicator was
+    /// created or reset.
+    pub fn latest(&self) -> Option<f64> {
+        if self.slot.updates.load(Ordering::Acquire) == 0 {
+            None
+        } else {
+            Some(f64::from_bits(self.slot.value.load(Ordering::Relaxed)))
+        }
+    }
+
+    /// Returns the number of outputs published since the indicator was created or reset, which
+    /// tells readers polling the output whether it changed.
+    pub fn updates(&self) -> u64 {
+        self.slot.updates.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+    use crate::test_helper::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_next() {
+        let mut sma = AtomicIndicator::new(SimpleMovingAverage::new(2).unwrap());
+        let output = sma.reader();
+        assert_eq!(output.latest(), None);
+        assert_eq!(output.updates(), 0);
+
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next(&Bar::new().close(8.0)), 6.0);
+        assert_eq!(output.latest(), Some(6.0));
+        assert_eq!(output.clone().updates(), 2);
+
+        sma.reset();
+        assert_eq!(output.latest(), None);
+        assert_eq!(sma.next(2.0), 2.0);
+        assert_eq!(output.latest(), Some(2.0));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_readers_on_threads() {
+        let mut sma = AtomicIndicator::new(SimpleMovingAverage::new(3).unwrap());
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let output = sma.reader();
+                std::thread::spawn(move || {
+                    while output.updates() < 100 {
+                        if let Some(value) = output.latest() {
+                            assert!((0.0..100.0).contains(&value));
+                        }
+                    }
+                    output.latest()
+                })
+            })
+            .collect();
+        for input in 0..100 {
+            sma.next(input as f64);
+        }
+        for reader in readers {
+            assert_eq!(reader.join().unwrap(), Some(98.0));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = AtomicIndicator::new(SimpleMovingAverage::new(5).unwrap());
+        assert_eq!(sma.to_string(), "SMA(5)");
+        assert_eq!(sma.period(), 5);
+        assert_eq!(sma.into_inner().period(), 5);
+    }
+}
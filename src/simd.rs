@@ -0,0 +1,356 @@
+//! Vectorized kernels over whole series.
+//!
+//! The functions of this module compute some of the heaviest indicators over a slice in one go,
+//! without the ring buffers and branches of the streaming implementations. Their inner loops
+//! step [LANES] independent lanes side by side, so that no running sum waits for the previous
+//! one and the processor can overlap them, on every target and without `unsafe` or nightly
+//! features.
+//!
+//! Every kernel returns the same outputs as a freshly created indicator fed with the slice:
+//!
+//! | Kernel         | Indicator                                                      |
+//! |----------------|----------------------------------------------------------------|
+//! | [sma]          | [SimpleMovingAverage](crate::indicators::SimpleMovingAverage)  |
+//! | [wma]          | [WeightedMovingAverage](crate::indicators::WeightedMovingAverage) |
+//! | [std_dev]      | [StandardDeviation](crate::indicators::StandardDeviation)      |
+//! | [rolling_max]  | [HighestHighValue](crate::indicators::HighestHighValue)        |
+//! | [rolling_min]  | [LowestLowValue](crate::indicators::LowestLowValue)            |
+//!
+//! The sums are accumulated in a different order, so the averages and the standard deviation
+//! may differ from the streaming outputs by rounding errors; the extremums are exact. Unlike
+//! [NextBatch](crate::NextBatch), the kernels keep no state between calls, but with the `simd`
+//! feature the `next_batch` of these indicators runs them when the indicator has not seen any
+//! input yet. `cargo bench --bench simd --features simd` compares them with the scalar paths.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::simd;
+//!
+//! let input = [1.0, 2.0, 3.0, 4.0, 5.0];
+//! assert_eq!(simd::sma(&input, 2).unwrap(), vec![1.0, 1.5, 2.5, 3.5, 4.5]);
+//! assert_eq!(simd::rolling_max(&input, 3).unwrap(), vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+//! assert!(simd::sma(&input, 0).is_err());
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::{IsReady, Next, Period};
+
+/// Number of values processed together by the inner loops.
+pub const LANES: usize = 4;
+
+/// Simple moving average over the last `period` values, or fewer during the warm-up.
+///
+/// Returns an error if the period is 0.
+pub fn sma(input: &[f64], period: usize) -> Result<Vec<f64>> {
+    check(period)?;
+    let mut sum = 0.0;
+    let mut output = warm_up(input, period, |i, x| {
+        sum += x;
+        sum / (i + 1) as f64
+    });
+    let scale = 1.0 / period as f64;
+    rolling(
+        input,
+        period,
+        &mut output,
+        |window| window.iter().sum::<f64>(),
+        |sum, x, old| *sum += x - old,
+        |sum| sum * scale,
+    );
+    Ok(output)
+}
+
+/// Weighted moving average over the last `period` values, the newest value having the weight
+/// `period`, or the number of values during the warm-up.
+///
+/// Returns an error if the period is 0.
+pub fn wma(input: &[f64], period: usize) -> Result<Vec<f64>> {
+    check(period)?;
+    let (mut sum, mut sum_flat) = (0.0, 0.0);
+    let mut output = warm_up(input, period, |i, x| {
+        let weight = (i + 1) as f64;
+        sum += x * weight;
+        sum_flat += x;
+        sum / (weight * (weight + 1.0) / 2.0)
+    });
+    let n = period as f64;
+    let scale = 2.0 / (n * (n + 1.0));
+    rolling(
+        input,
+        period,
+        &mut output,
+        |window| {
+            let sum = window
+                .iter()
+                .enumerate()
+                .map(|(i, x)| x * (i + 1) as f64)
+                .sum::<f64>();
+            (sum, window.iter().sum::<f64>())
+        },
+        |(sum, sum_flat), x, old| {
+            *sum += x * n - *sum_flat;
+            *sum_flat += x - old;
+        },
+        |(sum, _)| sum * scale,
+    );
+    Ok(output)
+}
+
+/// Population standard deviation over the last `period` values, or fewer during the warm-up.
+///
+/// The values are shifted by the first one before their squares are summed, which keeps the
+/// cancellation of the sums of powers low for prices far from 0.
+///
+/// Returns an error if the period is 0.
+pub fn std_dev(input: &[f64], period: usize) -> Result<Vec<f64>> {
+    check(period)?;
+    let shift = input.first().copied().unwrap_or(0.0);
+    let (mut sum, mut squares) = (0.0, 0.0);
+    let mut output = warm_up(input, period, |i, x| {
+        let y = x - shift;
+        sum += y;
+        squares += y * y;
+        let count = (i + 1) as f64;
+        (squares - sum * sum / count) / count
+    });
+    let scale = 1.0 / period as f64;
+    rolling(
+        input,
+        period,
+        &mut output,
+        |window| {
+            window.iter().fold((0.0, 0.0), |(sum, squares), x| {
+                let y = x - shift;
+                (sum + y, squares + y * y)
+            })
+        },
+        |(sum, squares), x, old| {
+            let (y, old) = (x - shift, old - shift);
+            *sum += y - old;
+            *squares += y * y - old * old;
+        },
+        |(sum, squares)| (squares - sum * sum * scale) * scale,
+    );
+    // the variances are contiguous, so the square roots are vectorized too
+    lanes_map(&mut output, |variance| variance.max(0.0).sqrt());
+    Ok(output)
+}
+
+/// Highest value over the last `period` values. NaN values are ignored.
+///
+/// Returns an error if the period is 0.
+pub fn rolling_max(input: &[f64], period: usize) -> Result<Vec<f64>> {
+    check(period)?;
+    Ok(rolling_extremum(input, period, f64::NEG_INFINITY, f64::max))
+}
+
+/// Lowest value over the last `period` values. NaN values are ignored.
+///
+/// Returns an error if the period is 0.
+pub fn rolling_min(input: &[f64], period: usize) -> Result<Vec<f64>> {
+    check(period)?;
+    Ok(rolling_extremum(input, period, f64::INFINITY, f64::min))
+}
+
+/// [NextBatch::next_batch](crate::NextBatch::next_batch) of the indicators computed by `kernel`.
+///
+/// An indicator which has not seen any input yet takes the outputs of the kernel and is then fed
+/// the last `period` inputs, which leaves its window as streaming would. Otherwise the inputs are
+/// streamed, as the kernels cannot start from a partial window.
+pub(crate) fn next_batch<N>(
+    indicator: &mut N,
+    input: &[f64],
+    kernel: fn(&[f64], usize) -> Result<Vec<f64>>,
+) -> Vec<f64>
+where
+    N: Next<f64, Output = f64> + IsReady + Period,
+{
+    let period = indicator.period();
+    if indicator.bars_remaining() < period {
+        return input.iter().map(|&value| indicator.next(value)).collect();
+    }
+    let output = kernel(input, period).unwrap_or_default();
+    for &value in &input[input.len().saturating_sub(period)..] {
+        indicator.next(value);
+    }
+    output
+}
+
+fn check(period: usize) -> Result<()> {
+    match period {
+        0 => Err(TaError::InvalidParameter),
+        _ => Ok(()),
+    }
+}
+
+/// Outputs of the windows which are not full yet, `f` getting their index and newest value.
+fn warm_up(input: &[f64], period: usize, mut f: impl FnMut(usize, f64) -> f64) -> Vec<f64> {
+    let mut output = Vec::with_capacity(input.len());
+    let len = input.len().min(period - 1);
+    output.extend(input[..len].iter().enumerate().map(|(i, &x)| f(i, x)));
+    output
+}
+
+/// Appends the outputs of the full windows of `period` values to `output`.
+///
+/// The windows are split into [LANES] consecutive segments which are stepped side by side, so
+/// the running state of a lane never waits for the state of another one. Every lane starts with
+/// `init` of its first window, then `step` adds the entering value and removes the leaving one.
+fn rolling<S: Copy>(
+    input: &[f64],
+    period: usize,
+    output: &mut Vec<f64>,
+    init: impl Fn(&[f64]) -> S,
+    step: impl Fn(&mut S, f64, f64),
+    value: impl Fn(S) -> f64,
+) {
+    if input.len() < period {
+        return;
+    }
+    let start = output.len();
+    output.resize(start + input.len() - period + 1, 0.0);
+    let output = &mut output[start..];
+    let segment = output.len() / LANES;
+
+    // window `w` ends at input `w + period - 1`, the value leaving it is input `w - 1`
+    let mut states = [init(&input[..period]); LANES];
+    for (lane, state) in states.iter_mut().enumerate() {
+        let first = lane * segment;
+        *state = init(&input[first..first + period]);
+        output[first] = value(*state);
+    }
+    for offset in 1..segment {
+        for (lane, state) in states.iter_mut().enumerate() {
+            let w = lane * segment + offset;
+            step(state, input[w + period - 1], input[w - 1]);
+            output[w] = value(*state);
+        }
+    }
+
+    // the windows left over by the segments continue the last lane
+    let mut state = states[LANES - 1];
+    for w in (LANES * segment).max(1)..output.len() {
+        step(&mut state, input[w + period - 1], input[w - 1]);
+        output[w] = value(state);
+    }
+}
+
+/// `values[i] = f(values[i])`, lane by lane.
+fn lanes_map(values: &mut [f64], f: impl Fn(f64) -> f64) {
+    let mut chunks = values.chunks_exact_mut(LANES);
+    for chunk in chunks.by_ref() {
+        for value in chunk.iter_mut() {
+            *value = f(*value);
+        }
+    }
+    for value in chunks.into_remainder() {
+        *value = f(*value);
+    }
+}
+
+/// Sliding extremum by the van Herk/Gil-Werman algorithm.
+///
+/// The input is split into blocks of `period` values. The extremum of a window is the extremum
+/// of the suffix of the block where it starts and of the prefix of the block where it ends, so
+/// every output takes two lane-wise comparisons, whatever the period.
+fn rolling_extremum(input: &[f64], period: usize, init: f64, f: fn(f64, f64) -> f64) -> Vec<f64> {
+    let mut prefix = input.to_vec();
+    let mut suffix = input.to_vec();
+    for block in prefix.chunks_mut(period) {
+        let mut acc = init;
+        for value in block.iter_mut() {
+            acc = f(acc, *value);
+            *value = acc;
+        }
+    }
+    for block in suffix.chunks_mut(period) {
+        let mut acc = init;
+        for value in block.iter_mut().rev() {
+            acc = f(acc, *value);
+            *value = acc;
+        }
+    }
+
+    // the windows ending in the first block start at the beginning of the input
+    let mut output = prefix;
+    if input.len() >= period {
+        lanes_map2(&mut output[period - 1..], &suffix, f);
+    }
+    output
+}
+
+/// `values[i] = f(values[i], other[i])`, lane by lane, up to the shorter length.
+fn lanes_map2(values: &mut [f64], other: &[f64], f: impl Fn(f64, f64) -> f64) {
+    let len = values.len().min(other.len());
+    let (values, other) = (&mut values[..len], &other[..len]);
+    let mut chunks = values.chunks_exact_mut(LANES);
+    let mut other_chunks = other.chunks_exact(LANES);
+    for (chunk, other) in chunks.by_ref().zip(other_chunks.by_ref()) {
+        for (value, &other) in chunk.iter_mut().zip(other) {
+            *value = f(*value, other);
+        }
+    }
+    for (value, &other) in chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(other_chunks.remainder())
+    {
+        *value = f(*value, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        HighestHighValue, LowestLowValue, SimpleMovingAverage, StandardDeviation,
+        WeightedMovingAverage,
+    };
+    use crate::testing::{assert_batch_consistent, compare, random_series};
+    use crate::NextBatch;
+
+    #[test]
+    fn test_invalid_period() {
+        assert!(sma(&[1.0], 0).is_err());
+        assert!(wma(&[1.0], 0).is_err());
+        assert!(std_dev(&[1.0], 0).is_err());
+        assert!(rolling_max(&[1.0], 0).is_err());
+        assert!(rolling_min(&[1.0], 0).is_err());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert!(sma(&[], 3).unwrap().is_empty());
+        assert!(wma(&[], 3).unwrap().is_empty());
+        assert!(std_dev(&[], 3).unwrap().is_empty());
+        assert!(rolling_max(&[], 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_against_streaming() {
+        let input = random_series(11, 1_003);
+        // the batch path of a fresh indicator is the kernel
+        let mut sma_indicator = SimpleMovingAverage::new(14).unwrap();
+        assert_eq!(sma_indicator.next_batch(&input), sma(&input, 14).unwrap());
+
+        for period in [1, 2, 3, 4, 7, 14, 50, 2_000] {
+            assert_batch_consistent(&SimpleMovingAverage::new(period).unwrap(), &input, 1e-9);
+            assert_batch_consistent(&WeightedMovingAverage::new(period).unwrap(), &input, 1e-9);
+            assert_batch_consistent(&StandardDeviation::new(period).unwrap(), &input, 1e-6);
+            assert_batch_consistent(&HighestHighValue::new(period).unwrap(), &input, 0.0);
+            assert_batch_consistent(&LowestLowValue::new(period).unwrap(), &input, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_wma() {
+        let output = wma(&[1.0, 2.0, 3.0, 4.0], 2).unwrap();
+        let expected = [1.0, 5.0 / 3.0, 8.0 / 3.0, 11.0 / 3.0];
+        assert_eq!(compare(&expected, &output, 1e-12), Ok(()));
+    }
+}
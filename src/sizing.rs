@@ -0,0 +1,480 @@
+//! Position sizing.
+//!
+//! Calculators turning the account equity and a measure of the risk of a trade into the size of
+//! the position to take. [FixedFractional] risks a fixed share of the equity on the distance to
+//! the stop, [KellyCriterion] derives the share of the equity to risk from the results of the
+//! last trades, and [VolatilityTarget] scales the position so its volatility matches a target.
+//!
+//! The sizes are not rounded to lots or contracts, and the calculators do not check that the
+//! account can afford the position.
+//!
+//! # Example
+//!
+//! Risking 1% of an equity of 100,000 on a trade with a stop 2.5 below the entry:
+//!
+//! ```
+//! use ta_panther::sizing::FixedFractional;
+//! use ta_panther::Next;
+//!
+//! let mut sizing = FixedFractional::new(0.01).unwrap();
+//! assert_eq!(sizing.next((100_000.0, 2.5)), 400.0);
+//! ```
+
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::ring;
+use crate::volatility::HistoricalVolatility;
+use crate::{Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Fixed fractional position sizing.
+///
+/// Sizes a position so that hitting the stop loses the given share of the equity. The inputs
+/// are pairs `(equity, stop_distance)`, the stop distance being the loss per unit between the
+/// entry and the stop. Returns 0 if the stop distance is not positive.
+///
+/// # Formula
+///
+/// _size = equity × risk / stop_distance_
+///
+/// # Parameters
+///
+/// * _risk_ - share of the equity to risk per trade (greater than 0, at most 1). Default is
+///   0.01.
+///
+/// # Links
+///
+/// * [Fixed fractional position sizing, Investopedia](https://www.investopedia.com/terms/p/positionsizing.asp)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FixedFractional {
+    risk: f64,
+}
+
+impl FixedFractional {
+    pub fn new(risk: f64) -> Result<Self> {
+        if !(risk > 0.0 && risk <= 1.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self { risk })
+    }
+
+    pub fn risk(&self) -> f64 {
+        self.risk
+    }
+}
+
+impl Next<(f64, f64)> for FixedFractional {
+    type Output = f64;
+
+    fn next(&mut self, (equity, stop_distance): (f64, f64)) -> Self::Output {
+        if stop_distance > 0.0 {
+            equity * self.risk / stop_distance
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Reset for FixedFractional {
+    fn reset(&mut self) {}
+}
+
+impl Default for FixedFractional {
+    fn default() -> Self {
+        Self::new(0.01).unwrap()
+    }
+}
+
+impl fmt::Display for FixedFractional {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FIXED_FRACTIONAL({})", self.risk)
+    }
+}
+
+/// Kelly criterion.
+///
+/// Returns the share of the equity to risk on the next trade from the win rate and the payoff
+/// ratio of the last _period_ trades. The inputs are the returns of closed trades, e.g. `0.02`
+/// for a gain of 2% and `-0.01` for a loss of 1%. A return of 0 is neither a win nor a loss but
+/// still lowers the win rate, NaN and infinite returns are ignored.
+///
+/// The full Kelly fraction maximizes the long run growth of the equity but is very volatile and
+/// sensitive to estimation errors, so it is scaled by _fraction_, commonly a half. The result is
+/// clamped to between 0, while the trades have no edge, and _fraction_, while they had no
+/// losses.
+///
+/// # Formula
+///
+/// _Kelly = fraction × (W - (1 - W) / R)_
+///
+/// Where:
+///
+/// * _W_ - share of winning trades
+/// * _R_ - average gain of the winning trades divided by the average loss of the losing ones
+///
+/// # Parameters
+///
+/// * _period_ - number of trades (integer greater than 0). Default is 50.
+/// * _fraction_ - share of the full Kelly fraction (greater than 0, at most 1). Default is 0.5.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::sizing::KellyCriterion;
+/// use ta_panther::Next;
+///
+/// let mut kelly = KellyCriterion::new(4, 1.0).unwrap();
+/// kelly.next(0.02);
+/// kelly.next(-0.01);
+/// kelly.next(0.02);
+/// assert_eq!(kelly.next(-0.01), 0.25);
+/// ```
+///
+/// # Links
+///
+/// * [Kelly criterion, Wikipedia](https://en.wikipedia.org/wiki/Kelly_criterion)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KellyCriterion {
+    period: usize,
+    fraction: f64,
+    index: usize,
+    count: usize,
+    wins: usize,
+    losses: usize,
+    win_sum: f64,
+    loss_sum: f64,
+    deque: Box<[f64]>,
+}
+
+impl KellyCriterion {
+    pub fn new(period: usize, fraction: f64) -> Result<Self> {
+        if period == 0 || !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            fraction,
+            index: 0,
+            count: 0,
+            wins: 0,
+            losses: 0,
+            win_sum: 0.0,
+            loss_sum: 0.0,
+            deque: ring(0.0, period)?,
+        })
+    }
+
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    fn update(&mut self, trade: f64, add: bool) {
+        let sign = if add { 1.0 } else { -1.0 };
+        if trade > 0.0 {
+            self.win_sum += sign * trade;
+            self.wins = if add { self.wins + 1 } else { self.wins - 1 };
+        } else if trade < 0.0 {
+            self.loss_sum -= sign * trade;
+            self.losses = if add {
+                self.losses + 1
+            } else {
+                self.losses - 1
+            };
+        }
+    }
+
+    fn fraction_of_equity(&self) -> f64 {
+        if self.wins == 0 {
+            return 0.0;
+        }
+        if self.losses == 0 {
+            return self.fraction;
+        }
+        let win_rate = self.wins as f64 / self.count as f64;
+        let payoff = (self.win_sum / self.wins as f64) / (self.loss_sum / self.losses as f64);
+        let kelly = win_rate - (1.0 - win_rate) / payoff;
+        (self.fraction * kelly).clamp(0.0, self.fraction)
+    }
+}
+
+impl Period for KellyCriterion {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for KellyCriterion {
+    type Output = f64;
+
+    fn next(&mut self, trade: f64) -> Self::Output {
+        if !trade.is_finite() {
+            return self.fraction_of_equity();
+        }
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            self.update(self.deque[self.index], false);
+        }
+        self.update(trade, true);
+
+        self.deque[self.index] = trade;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        self.fraction_of_equity()
+    }
+}
+
+impl Reset for KellyCriterion {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.wins = 0;
+        self.losses = 0;
+        self.win_sum = 0.0;
+        self.loss_sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for KellyCriterion {
+    fn default() -> Self {
+        Self::new(50, 0.5).unwrap()
+    }
+}
+
+impl fmt::Display for KellyCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KELLY({}, {})", self.period, self.fraction)
+    }
+}
+
+/// Volatility targeting.
+///
+/// Sizes a position so that its annualized volatility matches a target, using the
+/// [HistoricalVolatility] of the last _period_ prices. The inputs are pairs `(equity, price)`,
+/// the output is the number of units to hold. The exposure, the value of the position relative
+/// to the equity, is capped at _max_leverage_ for calm markets where the realized volatility is
+/// close to 0. Returns 0 until the volatility is known, after three prices.
+///
+/// # Formula
+///
+/// _size = equity × min(target / HV, max_leverage) / price_
+///
+/// # Parameters
+///
+/// * _period_ - number of returns of the volatility (integer greater than 0). Default is 20.
+/// * _target_ - annualized volatility to target (greater than 0). Default is 0.1.
+/// * _max_leverage_ - highest exposure (greater than 0). Default is 1.0.
+/// * _periods_per_year_ - annualization factor (greater than 0.0). Default is 252.0.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::sizing::VolatilityTarget;
+/// use ta_panther::Next;
+///
+/// let mut sizing = VolatilityTarget::new(20, 0.1, 2.0, 252.0).unwrap();
+/// assert_eq!(sizing.next((10_000.0, 100.0)), 0.0);
+/// assert_eq!(sizing.next((10_000.0, 101.0)), 0.0);
+/// assert!(sizing.next((10_000.0, 100.0)) > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volatility targeting, Wikipedia](https://en.wikipedia.org/wiki/Volatility_(finance))
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VolatilityTarget {
+    target: f64,
+    max_leverage: f64,
+    volatility: HistoricalVolatility,
+}
+
+impl VolatilityTarget {
+    pub fn new(
+        period: usize,
+        target: f64,
+        max_leverage: f64,
+        periods_per_year: f64,
+    ) -> Result<Self> {
+        if !(target > 0.0 && target.is_finite() && max_leverage > 0.0 && max_leverage.is_finite()) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            target,
+            max_leverage,
+            volatility: HistoricalVolatility::new(period, periods_per_year)?,
+        })
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    pub fn max_leverage(&self) -> f64 {
+        self.max_leverage
+    }
+}
+
+impl Period for VolatilityTarget {
+    fn period(&self) -> usize {
+        self.volatility.period()
+    }
+}
+
+impl Next<(f64, f64)> for VolatilityTarget {
+    type Output = f64;
+
+    fn next(&mut self, (equity, price): (f64, f64)) -> Self::Output {
+        let volatility = self.volatility.next(price);
+        if !(volatility > 0.0 && price > 0.0) {
+            return 0.0;
+        }
+        equity * (self.target / volatility).min(self.max_leverage) / price
+    }
+}
+
+impl Reset for VolatilityTarget {
+    fn reset(&mut self) {
+        self.volatility.reset();
+    }
+}
+
+impl Default for VolatilityTarget {
+    fn default() -> Self {
+        Self::new(20, 0.1, 1.0, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for VolatilityTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "VOL_TARGET({}, {}, {})",
+            self.volatility.period(),
+            self.target,
+            self.max_leverage
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(FixedFractional::new(0.0).is_err());
+        assert!(FixedFractional::new(1.5).is_err());
+        assert!(FixedFractional::new(1.0).is_ok());
+        assert!(KellyCriterion::new(0, 0.5).is_err());
+        assert!(KellyCriterion::new(10, 0.0).is_err());
+        assert!(KellyCriterion::new(1, 1.0).is_ok());
+        assert!(VolatilityTarget::new(0, 0.1, 1.0, 252.0).is_err());
+        assert!(VolatilityTarget::new(20, 0.0, 1.0, 252.0).is_err());
+        assert!(VolatilityTarget::new(20, 0.1, f64::INFINITY, 252.0).is_err());
+        assert!(VolatilityTarget::new(20, 0.1, 1.0, 0.0).is_err());
+        assert!(VolatilityTarget::new(1, 0.1, 1.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_fixed_fractional() {
+        let mut sizing = FixedFractional::new(0.02).unwrap();
+        assert_eq!(sizing.next((50_000.0, 4.0)), 250.0);
+        assert_eq!(sizing.next((50_000.0, 0.0)), 0.0);
+        assert_eq!(sizing.next((50_000.0, -1.0)), 0.0);
+    }
+
+    #[test]
+    fn test_kelly() {
+        let mut kelly = KellyCriterion::new(4, 0.5).unwrap();
+        assert_eq!(kelly.next(-0.01), 0.0);
+        // W = 1 / 2, R = 3
+        assert_eq!(round(kelly.next(0.03)), 0.167);
+        // W = 2 / 3, R = 2.5
+        assert_eq!(round(kelly.next(0.02)), 0.267);
+        // W = 1 / 2, R = 2.5 / 1.5
+        assert_eq!(round(kelly.next(-0.02)), 0.1);
+        // the first loss leaves the window, the flat trade counts in the win rate only
+        assert_eq!(round(kelly.next(0.0)), 0.05);
+        // no edge
+        let mut kelly = KellyCriterion::new(2, 1.0).unwrap();
+        kelly.next(0.01);
+        assert_eq!(kelly.next(-0.02), 0.0);
+    }
+
+    #[test]
+    fn test_kelly_ignores_nan() {
+        let mut kelly = KellyCriterion::new(2, 1.0).unwrap();
+        kelly.next(0.01);
+        assert_eq!(kelly.next(f64::NAN), 1.0);
+        assert_eq!(kelly.next(-0.01), 0.0);
+    }
+
+    #[test]
+    fn test_volatility_target() {
+        let mut sizing = VolatilityTarget::new(2, 0.1, 3.0, 1.0).unwrap();
+        assert_eq!(sizing.next((1000.0, 100.0)), 0.0);
+        assert_eq!(sizing.next((1000.0, 110.0)), 0.0);
+        // HV = 0.134 per period
+        assert_eq!(round(sizing.next((1000.0, 100.0))), 7.419);
+        // the exposure is capped while the price is calm
+        let mut sizing = VolatilityTarget::new(2, 0.1, 3.0, 1.0).unwrap();
+        sizing.next((1000.0, 100.0));
+        sizing.next((1000.0, 100.1));
+        assert_eq!(round(sizing.next((1000.0, 100.0))), 30.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kelly = KellyCriterion::new(4, 1.0).unwrap();
+        kelly.next(-0.01);
+        kelly.reset();
+        assert_eq!(kelly.next(0.01), 1.0);
+
+        let mut sizing = VolatilityTarget::new(2, 0.1, 3.0, 1.0).unwrap();
+        sizing.next((1000.0, 100.0));
+        sizing.next((1000.0, 110.0));
+        sizing.reset();
+        assert_eq!(sizing.next((1000.0, 100.0)), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        FixedFractional::default();
+        KellyCriterion::default();
+        VolatilityTarget::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", FixedFractional::new(0.01).unwrap()),
+            "FIXED_FRACTIONAL(0.01)"
+        );
+        assert_eq!(
+            format!("{}", KellyCriterion::new(50, 0.5).unwrap()),
+            "KELLY(50, 0.5)"
+        );
+        assert_eq!(
+            format!("{}", VolatilityTarget::new(20, 0.1, 1.0, 252.0).unwrap()),
+            "VOL_TARGET(20, 0.1, 1)"
+        );
+    }
+}
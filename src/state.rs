@@ -0,0 +1,340 @@
+//! Versioned and validated serialization of indicator states.
+//!
+//! Indicators serialize their internal state with serde, so they can be saved and restored.
+//! Deserializing a state written by an older version of the crate, or edited by hand, can
+//! however produce an indicator whose buffers don't match its period, which silently computes
+//! wrong outputs or panics later.
+//!
+//! [Versioned] wraps the state of an indicator implementing [Validate] together with its
+//! [STATE_VERSION](Validate::STATE_VERSION). On deserialization, states of older versions are
+//! [migrated](Validate::migrate), and inconsistent states or states of newer versions are
+//! rejected with an error.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::indicators::SimpleMovingAverage;
+//! use ta_panther::state::Versioned;
+//! use ta_panther::Next;
+//!
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! sma.next(4.0);
+//!
+//! let json = serde_json::to_string(&Versioned(sma)).unwrap();
+//! let Versioned(mut restored): Versioned<SimpleMovingAverage> =
+//!     serde_json::from_str(&json).unwrap();
+//! assert_eq!(restored.next(8.0), 6.0);
+//!
+//! // the buffer doesn't match its capacity
+//! let corrupt = json.replace("\"capacity\":2", "\"capacity\":0");
+//! assert!(serde_json::from_str::<Versioned<SimpleMovingAverage>>(&corrupt).is_err());
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{
+    Deserialize, DeserializeSeed, Deserializer, Error, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use serde::ser::{Serialize, Serializer};
+
+use crate::errors::{Result, TaError};
+use crate::helpers::validate_ring;
+use crate::Validate;
+
+/// State of an indicator serialized together with its version, see the [module](self)
+/// documentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Versioned<T>(pub T);
+
+impl<T> Versioned<T> {
+    /// Returns the wrapped state.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Versioned")]
+struct VersionedRef<'a, T> {
+    version: u32,
+    state: &'a T,
+}
+
+impl<T: Serialize + Validate> Serialize for Versioned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        VersionedRef {
+            version: T::STATE_VERSION,
+            state: &self.0,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Version,
+    State,
+    #[serde(other)]
+    Other,
+}
+
+/// Deserializes a state with the layout of its version.
+struct StateSeed<T> {
+    version: u32,
+    state: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de> + Validate> DeserializeSeed<'de> for StateSeed<T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> core::result::Result<T, D::Error> {
+        T::deserialize_version(self.version, deserializer)
+    }
+}
+
+struct VersionedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de> + Validate> Visitor<'de> for VersionedVisitor<T> {
+    type Value = (u32, T);
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a versioned state")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        let version: u32 = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let seed = StateSeed {
+            version,
+            state: PhantomData,
+        };
+        let state = seq
+            .next_element_seed(seed)?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok((version, state))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> core::result::Result<Self::Value, A::Error> {
+        let mut version = None;
+        let mut state = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Version => version = Some(map.next_value()?),
+                // a state before its version can only have the current layout
+                Field::State => {
+                    let seed = StateSeed {
+                        version: version.unwrap_or(T::STATE_VERSION),
+                        state: PhantomData,
+                    };
+                    state = Some(map.next_value_seed(seed)?);
+                }
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let version = version.ok_or_else(|| A::Error::missing_field("version"))?;
+        let state = state.ok_or_else(|| A::Error::missing_field("state"))?;
+        Ok((version, state))
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Validate> Deserialize<'de> for Versioned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let (version, state) = deserializer.deserialize_struct(
+            "Versioned",
+            &["version", "state"],
+            VersionedVisitor(PhantomData),
+        )?;
+        upgrade(state, version)
+            .map(Versioned)
+            .map_err(|err| D::Error::custom(format_args!("{} of version {}", err, version)))
+    }
+}
+
+/// State of the indicators holding only a ring buffer of their inputs, before they moved to
+/// [RollingWindow](crate::rolling::RollingWindow).
+#[derive(serde::Deserialize)]
+pub(crate) struct Ring {
+    pub period: usize,
+    index: usize,
+    count: usize,
+    deque: Vec<f64>,
+}
+
+impl Ring {
+    /// Returns the values of the ring from the oldest to the newest.
+    pub(crate) fn values(&self) -> Result<Vec<f64>> {
+        ring_values(self.period, self.index, self.count, &self.deque)
+    }
+}
+
+/// Returns the values of a ring buffer from the oldest to the newest, e.g. to replay the states
+/// of rolling window indicators written before [RollingWindow](crate::rolling::RollingWindow).
+///
+/// The ring of `period` values is full once `count` reaches the period, and `index` is the
+/// position of the next value.
+pub(crate) fn ring_values(
+    period: usize,
+    index: usize,
+    count: usize,
+    ring: &[f64],
+) -> Result<Vec<f64>> {
+    validate_ring(period, ring, index, count, period)?;
+    if count == period {
+        Ok(ring[index..]
+            .iter()
+            .chain(&ring[..index])
+            .copied()
+            .collect())
+    } else if index == count {
+        Ok(ring[..count].to_vec())
+    } else {
+        Err(TaError::InvalidState)
+    }
+}
+
+/// Migrates a state deserialized from `version` to the current version and validates it.
+///
+/// Useful for states which were serialized without [Versioned], e.g. by older versions of the
+/// crate, whose version is known by the application.
+///
+/// Returns [InvalidState](TaError::InvalidState) if the version is 0 or newer than the
+/// current one, or if the state is inconsistent.
+pub fn upgrade<T: Validate>(mut state: T, version: u32) -> Result<T> {
+    if version == 0 || version > T::STATE_VERSION {
+        return Err(TaError::InvalidState);
+    }
+    if version < T::STATE_VERSION {
+        state.migrate(version)?;
+    }
+    state.validate()?;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        HighestHighValue, LowestLowValue, Maximum, Momentum, SimpleMovingAverage,
+    };
+    use crate::{IsReady, Next};
+
+    #[test]
+    fn test_round_trip() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        sma.next(3.0);
+        sma.next(6.0);
+
+        let json = serde_json::to_string(&Versioned(sma.clone())).unwrap();
+        assert!(json.starts_with("{\"version\":2,\"state\":{"));
+        let mut restored = serde_json::from_str::<Versioned<SimpleMovingAverage>>(&json)
+            .unwrap()
+            .into_inner();
+        assert_eq!(restored.next(9.0), sma.next(9.0));
+
+        let bytes = bincode::serialize(&Versioned(sma.clone())).unwrap();
+        let mut restored: Versioned<SimpleMovingAverage> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored.0.next(1.0), sma.next(1.0));
+    }
+
+    #[test]
+    fn test_invalid_states() {
+        let json = |version: u32, capacity: usize, index: usize, len: usize| {
+            let state = format!(
+                r#"{{"sum":0.0,"window":{{"capacity":{},"index":{},"values":{:?}}}}}"#,
+                capacity,
+                index,
+                vec![0.0; len]
+            );
+            format!(r#"{{"version":{},"state":{}}}"#, version, state)
+        };
+        let parse = |json: String| serde_json::from_str::<Versioned<SimpleMovingAverage>>(&json);
+
+        assert!(parse(json(2, 3, 2, 2)).is_ok());
+        assert!(parse(json(2, 3, 1, 3)).is_ok());
+        assert!(parse(json(2, 3, 1, 2)).is_err());
+        assert!(parse(json(2, 0, 0, 0)).is_err());
+        assert!(parse(json(2, 3, 3, 3)).is_err());
+        assert!(parse(json(2, 3, 1, 4)).is_err());
+        assert!(parse(json(0, 3, 2, 2)).is_err());
+        assert!(parse(json(3, 3, 2, 2)).is_err());
+
+        // fields in any order
+        let state = r#"{"sum":1.0,"window":{"capacity":2,"index":1,"values":[1.0]}}"#;
+        let reordered = format!(r#"{{"state":{},"extra":null,"version":2}}"#, state);
+        assert!(parse(reordered).is_ok());
+        assert!(parse(format!(r#"{{"state":{}}}"#, state)).is_err());
+
+        let mut momentum = Momentum::new(2).unwrap();
+        for input in [1.0, 2.0, 3.0] {
+            momentum.next(input);
+        }
+        assert_eq!(momentum.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_migrate() {
+        // state written before the number of inputs was stored
+        let legacy = r#"{"period":3,"max_index":2,"cur_index":0,"deque":[5.0,4.0,6.0]}"#;
+        let max: Maximum = serde_json::from_str(legacy).unwrap();
+        assert_eq!(max.bars_remaining(), 3);
+
+        let mut max = upgrade(max, 1).unwrap();
+        assert!(max.is_ready());
+        assert_eq!(max.next(1.0), 6.0);
+
+        let versioned = format!(r#"{{"version":1,"state":{}}}"#, legacy);
+        let max: Versioned<Maximum> = serde_json::from_str(&versioned).unwrap();
+        assert!(max.0.is_ready());
+        assert_eq!(upgrade(max.0, 3).unwrap_err(), TaError::InvalidState);
+    }
+
+    #[test]
+    fn test_migrate_extremums() {
+        let v1 = r#"{"version":1,"state":{"period":3,"index":1,"count":3,"deque":[7.0,9.0,8.0]}}"#;
+        let v2 =
+            r#"{"version":2,"state":{"window":{"capacity":3,"index":1,"values":[7.0,9.0,8.0]}}}"#;
+        for json in [v1, v2] {
+            let Versioned(mut hhv) =
+                serde_json::from_str::<Versioned<HighestHighValue>>(json).unwrap();
+            assert!(hhv.is_ready());
+            assert_eq!(hhv.next(1.0), 8.0);
+            assert_eq!(hhv.next(2.0), 7.0);
+
+            let Versioned(mut llv) =
+                serde_json::from_str::<Versioned<LowestLowValue>>(json).unwrap();
+            assert_eq!(llv.next(10.0), 7.0);
+            assert_eq!(llv.next(10.0), 7.0);
+            assert_eq!(llv.next(10.0), 10.0);
+
+            let json = serde_json::to_string(&Versioned(llv.clone())).unwrap();
+            let Versioned(mut restored) =
+                serde_json::from_str::<Versioned<LowestLowValue>>(&json).unwrap();
+            assert_eq!(restored.next(9.0), llv.next(9.0));
+        }
+
+        let partial =
+            r#"{"version":1,"state":{"period":3,"index":2,"count":2,"deque":[7.0,9.0,0.0]}}"#;
+        let Versioned(mut hhv) =
+            serde_json::from_str::<Versioned<HighestHighValue>>(partial).unwrap();
+        assert_eq!(hhv.bars_remaining(), 1);
+        assert_eq!(hhv.next(1.0), 9.0);
+
+        let corrupt = v2.replace("\"index\":1", "\"index\":3");
+        assert!(serde_json::from_str::<Versioned<HighestHighValue>>(&corrupt).is_err());
+    }
+}
@@ -0,0 +1,546 @@
+//! Trailing stops.
+//!
+//! Unlike the [Chandelier Exit](crate::indicators::ChandelierExit) indicator, which returns the
+//! stop levels of both a long and a short position on every bar, the stops of this module track
+//! a position. A position is opened with `enter()` at the close of the latest bar, which also
+//! sets the initial stop. On every following bar the stop is checked against the range of the
+//! bar and then moved in the direction of the position only, never back.
+//!
+//! Every stop returns a [StopOutput] with the stop level in force and whether the bar hit it.
+//! A stopped out position is closed, and the stop level is NaN until the next `enter()`.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::stops::{Direction, PercentTrailingStop};
+//! use ta_panther::{DataItem, Next};
+//!
+//! let bar = |high: f64, low: f64, close: f64| {
+//!     DataItem::builder()
+//!         .open(close)
+//!         .high(high)
+//!         .low(low)
+//!         .close(close)
+//!         .volume(1000.0)
+//!         .build()
+//!         .unwrap()
+//! };
+//!
+//! let mut stop = PercentTrailingStop::new(10.0).unwrap();
+//! stop.next(&bar(101.0, 99.0, 100.0));
+//! stop.enter(Direction::Long);
+//! assert_eq!(stop.stop(), 90.0);
+//!
+//! let output = stop.next(&bar(121.0, 110.0, 120.0));
+//! assert_eq!((output.stop, output.stopped), (108.0, false));
+//!
+//! let output = stop.next(&bar(112.0, 105.0, 106.0));
+//! assert_eq!((output.stop, output.stopped), (108.0, true));
+//! assert_eq!(stop.direction(), None);
+//! ```
+
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{self, AverageTrueRange};
+use crate::{Close, High, Low, Next, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Direction of a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    /// Bought, stopped out when the price falls to the stop below it.
+    #[default]
+    Long,
+    /// Sold short, stopped out when the price rises to the stop above it.
+    Short,
+}
+
+/// Output of a trailing stop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StopOutput {
+    /// Stop level, the one hit if stopped out. NaN while there is no position.
+    pub stop: f64,
+    /// Whether the bar reached the stop, closing the position.
+    pub stopped: bool,
+}
+
+impl From<StopOutput> for (f64, bool) {
+    fn from(output: StopOutput) -> Self {
+        (output.stop, output.stopped)
+    }
+}
+
+/// Position and stop level shared by the stops, with the stop levels of the latest bar for a
+/// long and a short position.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Trail {
+    direction: Option<Direction>,
+    stop: f64,
+    long: f64,
+    short: f64,
+}
+
+impl Trail {
+    fn new() -> Self {
+        Self {
+            direction: None,
+            stop: f64::NAN,
+            long: f64::NAN,
+            short: f64::NAN,
+        }
+    }
+
+    fn enter(&mut self, direction: Direction) {
+        self.direction = Some(direction);
+        self.stop = match direction {
+            Direction::Long => self.long,
+            Direction::Short => self.short,
+        };
+    }
+
+    fn exit(&mut self) {
+        self.direction = None;
+        self.stop = f64::NAN;
+    }
+
+    fn next(&mut self, high: f64, low: f64, long: f64, short: f64) -> StopOutput {
+        self.long = long;
+        self.short = short;
+        let stopped = match self.direction {
+            Some(Direction::Long) if low <= self.stop => true,
+            Some(Direction::Short) if high >= self.stop => true,
+            Some(Direction::Long) => {
+                self.stop = self.stop.max(long);
+                false
+            }
+            Some(Direction::Short) => {
+                self.stop = self.stop.min(short);
+                false
+            }
+            None => false,
+        };
+        let output = StopOutput {
+            stop: self.stop,
+            stopped,
+        };
+        if stopped {
+            self.exit();
+        }
+        output
+    }
+}
+
+macro_rules! position_methods {
+    () => {
+        /// Opens a position at the close of the latest bar, replacing the current one.
+        pub fn enter(&mut self, direction: Direction) {
+            self.trail.enter(direction);
+        }
+
+        /// Closes the position without a stop out.
+        pub fn exit(&mut self) {
+            self.trail.exit();
+        }
+
+        /// Returns the direction of the open position, or `None` if there is none.
+        pub fn direction(&self) -> Option<Direction> {
+            self.trail.direction
+        }
+
+        /// Returns the stop level of the open position, or NaN if there is none.
+        pub fn stop(&self) -> f64 {
+            self.trail.stop
+        }
+    };
+}
+
+/// Percentage trailing stop.
+///
+/// Trails the close by a fixed percentage: the stop of a long position is the highest close
+/// since the entry less _percent_, the one of a short position the lowest close since the entry
+/// plus _percent_.
+///
+/// # Parameters
+///
+/// * _percent_ - distance of the stop in percent of the close (greater than 0, less than 100).
+///   Default is 5.
+///
+#[doc(alias = "PTS")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PercentTrailingStop {
+    percent: f64,
+    trail: Trail,
+}
+
+impl PercentTrailingStop {
+    pub fn new(percent: f64) -> Result<Self> {
+        if !(percent > 0.0 && percent < 100.0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            percent,
+            trail: Trail::new(),
+        })
+    }
+
+    pub fn percent(&self) -> f64 {
+        self.percent
+    }
+
+    position_methods!();
+}
+
+impl<T: High + Low + Close> Next<&T> for PercentTrailingStop {
+    type Output = StopOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let distance = input.close() * self.percent / 100.0;
+        self.trail.next(
+            input.high(),
+            input.low(),
+            input.close() - distance,
+            input.close() + distance,
+        )
+    }
+}
+
+impl Reset for PercentTrailingStop {
+    fn reset(&mut self) {
+        self.trail = Trail::new();
+    }
+}
+
+impl Default for PercentTrailingStop {
+    fn default() -> Self {
+        Self::new(5.0).unwrap()
+    }
+}
+
+impl fmt::Display for PercentTrailingStop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PERCENT_STOP({})", self.percent)
+    }
+}
+
+/// ATR trailing stop.
+///
+/// Trails the close by a multiple of the [ATR](crate::indicators::AverageTrueRange), so the
+/// stop is further away in volatile markets: the stop of a long position is the highest level
+/// of _close - multiplier × ATR_ since the entry, the one of a short position the lowest level
+/// of _close + multiplier × ATR_.
+///
+/// # Parameters
+///
+/// * _period_ - period of the ATR (integer greater than 0). Default is 14.
+/// * _multiplier_ - ATR factor (greater than 0). Default is 3.
+///
+#[doc(alias = "ATRTS")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AtrTrailingStop {
+    atr: AverageTrueRange,
+    multiplier: f64,
+    trail: Trail,
+}
+
+impl AtrTrailingStop {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        if !(multiplier > 0.0 && multiplier.is_finite()) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            atr: AverageTrueRange::new(period)?,
+            multiplier,
+            trail: Trail::new(),
+        })
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    position_methods!();
+}
+
+impl Period for AtrTrailingStop {
+    fn period(&self) -> usize {
+        self.atr.period()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for AtrTrailingStop {
+    type Output = StopOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let distance = self.atr.next(input) * self.multiplier;
+        self.trail.next(
+            input.high(),
+            input.low(),
+            input.close() - distance,
+            input.close() + distance,
+        )
+    }
+}
+
+impl Reset for AtrTrailingStop {
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.trail = Trail::new();
+    }
+}
+
+impl Default for AtrTrailingStop {
+    fn default() -> Self {
+        Self::new(14, 3.0).unwrap()
+    }
+}
+
+impl fmt::Display for AtrTrailingStop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ATR_STOP({}, {})", self.atr.period(), self.multiplier)
+    }
+}
+
+/// Chandelier Exit stop.
+///
+/// Trails the levels of the [Chandelier Exit](crate::indicators::ChandelierExit): the stop of
+/// a long position follows the highest high of the last _period_ bars less a multiple of the
+/// ATR, the one of a short position the lowest low plus a multiple of the ATR.
+///
+/// # Parameters
+///
+/// * _period_ - period of the highest high, lowest low and ATR (integer greater than 0).
+///   Default is 22.
+/// * _multiplier_ - ATR factor (greater than 0). Default is 3.
+///
+/// # Links
+///
+/// * [Chandelier Exit, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:chandelier_exit)
+///
+#[doc(alias = "CE")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChandelierExit {
+    exit: indicators::ChandelierExit,
+    trail: Trail,
+}
+
+impl ChandelierExit {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        if !(multiplier > 0.0 && multiplier.is_finite()) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            exit: indicators::ChandelierExit::new(period, multiplier)?,
+            trail: Trail::new(),
+        })
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.exit.multiplier()
+    }
+
+    position_methods!();
+}
+
+impl Period for ChandelierExit {
+    fn period(&self) -> usize {
+        self.exit.period()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for ChandelierExit {
+    type Output = StopOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let levels = self.exit.next(input);
+        self.trail
+            .next(input.high(), input.low(), levels.long, levels.short)
+    }
+}
+
+impl Reset for ChandelierExit {
+    fn reset(&mut self) {
+        self.exit.reset();
+        self.trail = Trail::new();
+    }
+}
+
+impl Default for ChandelierExit {
+    fn default() -> Self {
+        Self::new(22, 3.0).unwrap()
+    }
+}
+
+impl fmt::Display for ChandelierExit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "CHANDELIER_STOP({}, {})",
+            self.exit.period(),
+            self.exit.multiplier()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PercentTrailingStop::new(0.0).is_err());
+        assert!(PercentTrailingStop::new(100.0).is_err());
+        assert!(PercentTrailingStop::new(f64::NAN).is_err());
+        assert!(PercentTrailingStop::new(5.0).is_ok());
+        assert!(AtrTrailingStop::new(0, 3.0).is_err());
+        assert!(AtrTrailingStop::new(14, 0.0).is_err());
+        assert!(AtrTrailingStop::new(14, 3.0).is_ok());
+        assert!(ChandelierExit::new(0, 3.0).is_err());
+        assert!(ChandelierExit::new(22, -1.0).is_err());
+        assert!(ChandelierExit::new(22, 3.0).is_ok());
+    }
+
+    #[test]
+    fn test_no_position() {
+        let mut stop = PercentTrailingStop::new(10.0).unwrap();
+        let output = stop.next(&bar(12.0, 8.0, 10.0));
+        assert!(output.stop.is_nan());
+        assert!(!output.stopped);
+        assert_eq!(stop.direction(), None);
+    }
+
+    #[test]
+    fn test_percent_long() {
+        let mut stop = PercentTrailingStop::new(10.0).unwrap();
+        stop.next(&bar(101.0, 99.0, 100.0));
+        stop.enter(Direction::Long);
+        assert_eq!(stop.direction(), Some(Direction::Long));
+        assert_eq!(stop.stop(), 90.0);
+
+        // the stop only moves up
+        assert_eq!(stop.next(&bar(111.0, 100.0, 110.0)).stop, 99.0);
+        assert_eq!(stop.next(&bar(106.0, 100.0, 105.0)).stop, 99.0);
+
+        let output = stop.next(&bar(104.0, 98.0, 103.0));
+        assert_eq!(
+            output,
+            StopOutput {
+                stop: 99.0,
+                stopped: true
+            }
+        );
+        assert_eq!(stop.direction(), None);
+        assert!(stop.next(&bar(104.0, 98.0, 103.0)).stop.is_nan());
+    }
+
+    #[test]
+    fn test_percent_short() {
+        let mut stop = PercentTrailingStop::new(10.0).unwrap();
+        stop.next(&bar(101.0, 99.0, 100.0));
+        stop.enter(Direction::Short);
+        assert_eq!(stop.stop(), 110.0);
+
+        assert_eq!(
+            stop.next(&bar(95.0, 85.0, 90.0)),
+            StopOutput {
+                stop: 99.0,
+                stopped: false
+            }
+        );
+        assert_eq!(stop.next(&bar(98.0, 92.0, 95.0)).stop, 99.0);
+        assert!(stop.next(&bar(99.0, 92.0, 95.0)).stopped);
+    }
+
+    #[test]
+    fn test_exit() {
+        let mut stop = PercentTrailingStop::new(10.0).unwrap();
+        stop.next(&bar(101.0, 99.0, 100.0));
+        stop.enter(Direction::Long);
+        stop.exit();
+        assert_eq!(stop.direction(), None);
+        assert!(!stop.next(&bar(50.0, 40.0, 45.0)).stopped);
+    }
+
+    #[test]
+    fn test_enter_before_first_bar() {
+        let mut stop = PercentTrailingStop::new(10.0).unwrap();
+        stop.enter(Direction::Long);
+        assert!(stop.stop().is_nan());
+        assert_eq!(stop.next(&bar(101.0, 99.0, 100.0)).stop, 90.0);
+    }
+
+    #[test]
+    fn test_atr_long() {
+        let mut stop = AtrTrailingStop::new(3, 2.0).unwrap();
+        stop.next(&bar(11.0, 9.0, 10.0));
+        stop.enter(Direction::Long);
+        assert_eq!(stop.stop(), 6.0);
+
+        // ATR = 2 + (5 - 2) / 2 = 3.5
+        let output = stop.next(&bar(15.0, 10.0, 14.0));
+        assert_eq!(
+            output,
+            StopOutput {
+                stop: 7.0,
+                stopped: false
+            }
+        );
+        assert!(stop.next(&bar(14.0, 7.0, 8.0)).stopped);
+    }
+
+    #[test]
+    fn test_chandelier_short() {
+        let mut stop = ChandelierExit::new(5, 2.0).unwrap();
+        stop.next(&bar(2.0, 1.0, 1.5));
+        stop.enter(Direction::Short);
+        assert_eq!(stop.stop(), 3.0);
+
+        let output = stop.next(&bar(2.5, 1.0, 2.0));
+        assert!(!output.stopped);
+        assert_eq!(round(output.stop), 3.0);
+        assert!(stop.next(&bar(5.0, 3.0, 4.0)).stopped);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut stop = AtrTrailingStop::new(3, 2.0).unwrap();
+        stop.next(&bar(11.0, 9.0, 10.0));
+        stop.enter(Direction::Long);
+
+        stop.reset();
+        assert_eq!(stop.direction(), None);
+        stop.next(&bar(11.0, 9.0, 10.0));
+        stop.enter(Direction::Short);
+        assert_eq!(stop.stop(), 14.0);
+    }
+
+    #[test]
+    fn test_default() {
+        PercentTrailingStop::default();
+        AtrTrailingStop::default();
+        ChandelierExit::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let stop = PercentTrailingStop::new(5.0).unwrap();
+        assert_eq!(format!("{}", stop), "PERCENT_STOP(5)");
+        let stop = AtrTrailingStop::new(14, 3.0).unwrap();
+        assert_eq!(format!("{}", stop), "ATR_STOP(14, 3)");
+        let stop = ChandelierExit::new(22, 3.0).unwrap();
+        assert_eq!(format!("{}", stop), "CHANDELIER_STOP(22, 3)");
+    }
+}
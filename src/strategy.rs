@@ -0,0 +1,388 @@
+//! Rule based strategies.
+//!
+//! A [Strategy] turns every bar into a [Signal]. Strategies are usually assembled from
+//! conditions, closures that feed indicators with the bar and test their outputs, combined by
+//! a [Rule]: all of them, any of them, or a weighted vote. A [RuleStrategy] then maps the rules
+//! for going long, going short and going flat to a signal.
+//!
+//! # Example
+//!
+//! Going long when the RSI is oversold and the close is above its moving average, and flat when
+//! the RSI is overbought:
+//!
+//! ```
+//! use ta_panther::indicators::{RelativeStrengthIndex, SimpleMovingAverage};
+//! use ta_panther::strategy::{Rule, RuleStrategy, Signal, Strategy};
+//! use ta_panther::{Close, DataItem, Next};
+//!
+//! let mut rsi = RelativeStrengthIndex::new(14).unwrap();
+//! let mut exit_rsi = rsi.clone();
+//! let mut sma = SimpleMovingAverage::new(50).unwrap();
+//!
+//! let mut strategy = RuleStrategy::new()
+//!     .long(
+//!         Rule::all()
+//!             .with(move |bar: &DataItem| rsi.next(bar) < 30.0)
+//!             .with(move |bar: &DataItem| bar.close() > sma.next(bar)),
+//!     )
+//!     .flat(Rule::all().with(move |bar: &DataItem| exit_rsi.next(bar) > 70.0));
+//!
+//! let bar = DataItem::builder()
+//!     .open(10.0)
+//!     .high(10.0)
+//!     .low(10.0)
+//!     .close(10.0)
+//!     .volume(100.0)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(strategy.on_bar(&bar), Signal::Hold);
+//! ```
+
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::DataItem;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Signal of a [Strategy] for a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Signal {
+    /// Hold a long position, opening it if needed.
+    Long,
+    /// Hold a short position, opening it if needed.
+    Short,
+    /// Close any position.
+    Flat,
+    /// Keep the current position, whatever it is.
+    #[default]
+    Hold,
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Signal::Long => "LONG",
+            Signal::Short => "SHORT",
+            Signal::Flat => "FLAT",
+            Signal::Hold => "HOLD",
+        })
+    }
+}
+
+/// Trading strategy, returning a [Signal] for every bar.
+///
+/// Implemented by [RuleStrategy] and by closures taking a bar.
+pub trait Strategy {
+    fn on_bar(&mut self, bar: &DataItem) -> Signal;
+}
+
+impl<F: FnMut(&DataItem) -> Signal> Strategy for F {
+    fn on_bar(&mut self, bar: &DataItem) -> Signal {
+        self(bar)
+    }
+}
+
+/// Condition evaluated on every bar.
+type Condition = Box<dyn FnMut(&DataItem) -> bool + Send>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combination {
+    All,
+    Any,
+    Weighted(f64),
+}
+
+/// Combination of conditions.
+///
+/// * [all](Rule::all) holds when all the conditions hold, like AND.
+/// * [any](Rule::any) holds when at least one condition holds, like OR.
+/// * [weighted](Rule::weighted) holds when the weights of the conditions that hold add up to at
+///   least a threshold, e.g. two of three equally weighted conditions.
+///
+/// Every condition is evaluated on every bar, even when the result is known before, so the
+/// indicators they feed see all the bars. A rule without conditions never holds. Rules are
+/// conditions themselves and can be nested with [with_rule](Rule::with_rule).
+pub struct Rule {
+    combination: Combination,
+    conditions: Vec<(f64, Condition)>,
+}
+
+impl Rule {
+    /// Creates a rule holding when all the conditions hold.
+    pub fn all() -> Self {
+        Self::with_combination(Combination::All)
+    }
+
+    /// Creates a rule holding when any condition holds.
+    pub fn any() -> Self {
+        Self::with_combination(Combination::Any)
+    }
+
+    /// Creates a rule holding when the weights of the conditions that hold add up to at least
+    /// `threshold`.
+    pub fn weighted(threshold: f64) -> Result<Self> {
+        if !threshold.is_finite() {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self::with_combination(Combination::Weighted(threshold)))
+    }
+
+    fn with_combination(combination: Combination) -> Self {
+        Self {
+            combination,
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Adds a condition with a weight of 1.
+    pub fn with<F>(self, condition: F) -> Self
+    where
+        F: FnMut(&DataItem) -> bool + Send + 'static,
+    {
+        self.with_weight(1.0, condition)
+    }
+
+    /// Adds a condition with a weight, only used by weighted rules.
+    pub fn with_weight<F>(mut self, weight: f64, condition: F) -> Self
+    where
+        F: FnMut(&DataItem) -> bool + Send + 'static,
+    {
+        self.conditions.push((weight, Box::new(condition)));
+        self
+    }
+
+    /// Adds a nested rule as a condition with a weight of 1.
+    pub fn with_rule(self, mut rule: Rule) -> Self {
+        self.with(move |bar: &DataItem| rule.check(bar))
+    }
+
+    /// Returns the number of conditions.
+    pub fn len(&self) -> usize {
+        self.conditions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Evaluates the conditions on a bar and returns whether the rule holds.
+    pub fn check(&mut self, bar: &DataItem) -> bool {
+        if self.conditions.is_empty() {
+            return false;
+        }
+        let mut count = 0;
+        let mut weight = 0.0;
+        for (condition_weight, condition) in self.conditions.iter_mut() {
+            if condition(bar) {
+                count += 1;
+                weight += *condition_weight;
+            }
+        }
+        match self.combination {
+            Combination::All => count == self.conditions.len(),
+            Combination::Any => count > 0,
+            Combination::Weighted(threshold) => weight >= threshold,
+        }
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rule")
+            .field("combination", &self.combination)
+            .field("conditions", &self.conditions.len())
+            .finish()
+    }
+}
+
+/// Strategy of rules.
+///
+/// Checks the rules for going long, going short and going flat on every bar, and returns the
+/// [Signal] of the first one holding in that order, or [Hold](Signal::Hold) if none holds. All
+/// the rules are checked on every bar. A missing rule never holds.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::strategy::{Rule, RuleStrategy, Signal, Strategy};
+/// use ta_panther::{Close, DataItem, Open};
+///
+/// let mut strategy = RuleStrategy::new()
+///     .long(Rule::all().with(|bar: &DataItem| bar.close() > bar.open()))
+///     .flat(Rule::any().with(|bar: &DataItem| bar.close() < bar.open()));
+///
+/// let bar = DataItem::builder()
+///     .open(10.0)
+///     .high(12.0)
+///     .low(9.0)
+///     .close(11.0)
+///     .volume(100.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(strategy.on_bar(&bar), Signal::Long);
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct RuleStrategy {
+    long: Option<Rule>,
+    short: Option<Rule>,
+    flat: Option<Rule>,
+}
+
+impl RuleStrategy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the rule for going long.
+    pub fn long(mut self, rule: Rule) -> Self {
+        self.long = Some(rule);
+        self
+    }
+
+    /// Sets the rule for going short.
+    pub fn short(mut self, rule: Rule) -> Self {
+        self.short = Some(rule);
+        self
+    }
+
+    /// Sets the rule for going flat.
+    pub fn flat(mut self, rule: Rule) -> Self {
+        self.flat = Some(rule);
+        self
+    }
+}
+
+impl Strategy for RuleStrategy {
+    fn on_bar(&mut self, bar: &DataItem) -> Signal {
+        let check = |rule: &mut Option<Rule>| rule.as_mut().is_some_and(|rule| rule.check(bar));
+        let long = check(&mut self.long);
+        let short = check(&mut self.short);
+        let flat = check(&mut self.flat);
+        if long {
+            Signal::Long
+        } else if short {
+            Signal::Short
+        } else if flat {
+            Signal::Flat
+        } else {
+            Signal::Hold
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Close;
+    use alloc::format;
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder()
+            .open(10.0)
+            .high(close.max(10.0))
+            .low(close.min(10.0))
+            .close(close)
+            .volume(100.0)
+            .build()
+            .unwrap()
+    }
+
+    fn above(level: f64) -> impl FnMut(&DataItem) -> bool + Send {
+        move |bar: &DataItem| bar.close() > level
+    }
+
+    #[test]
+    fn test_all_and_any() {
+        let mut all = Rule::all().with(above(5.0)).with(above(10.0));
+        let mut any = Rule::any().with(above(5.0)).with(above(10.0));
+        assert!(!all.check(&bar(8.0)));
+        assert!(any.check(&bar(8.0)));
+        assert!(all.check(&bar(12.0)));
+        assert!(!any.check(&bar(4.0)));
+    }
+
+    #[test]
+    fn test_weighted() {
+        assert!(Rule::weighted(f64::NAN).is_err());
+        let mut rule = Rule::weighted(2.0)
+            .unwrap()
+            .with_weight(1.5, above(5.0))
+            .with_weight(0.5, above(10.0))
+            .with(above(15.0));
+        assert_eq!(rule.len(), 3);
+        assert!(!rule.check(&bar(8.0)));
+        assert!(rule.check(&bar(12.0)));
+    }
+
+    #[test]
+    fn test_empty_rule() {
+        assert!(Rule::all().is_empty());
+        assert!(!Rule::all().check(&bar(8.0)));
+        assert!(!Rule::any().check(&bar(8.0)));
+    }
+
+    #[test]
+    fn test_nested_rules() {
+        let mut rule = Rule::any()
+            .with(above(20.0))
+            .with_rule(Rule::all().with(above(5.0)).with(above(10.0)));
+        assert!(!rule.check(&bar(8.0)));
+        assert!(rule.check(&bar(12.0)));
+    }
+
+    #[test]
+    fn test_every_condition_is_evaluated() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut rule = Rule::all().with(above(100.0)).with(move |_: &DataItem| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            true
+        });
+        rule.check(&bar(8.0));
+        rule.check(&bar(8.0));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_rule_strategy() {
+        let mut strategy = RuleStrategy::new()
+            .long(Rule::all().with(above(12.0)))
+            .short(Rule::all().with(move |bar: &DataItem| bar.close() < 8.0))
+            .flat(
+                Rule::any()
+                    .with(above(11.0))
+                    .with(move |bar: &DataItem| bar.close() < 9.0),
+            );
+        assert_eq!(strategy.on_bar(&bar(13.0)), Signal::Long);
+        assert_eq!(strategy.on_bar(&bar(7.0)), Signal::Short);
+        assert_eq!(strategy.on_bar(&bar(11.5)), Signal::Flat);
+        assert_eq!(strategy.on_bar(&bar(10.0)), Signal::Hold);
+        assert_eq!(RuleStrategy::new().on_bar(&bar(10.0)), Signal::Hold);
+    }
+
+    #[test]
+    fn test_closure_strategy() {
+        let mut strategy = |bar: &DataItem| {
+            if bar.close() > 10.0 {
+                Signal::Long
+            } else {
+                Signal::Flat
+            }
+        };
+        assert_eq!(strategy.on_bar(&bar(11.0)), Signal::Long);
+        assert_eq!(strategy.on_bar(&bar(9.0)), Signal::Flat);
+    }
+
+    #[test]
+    fn test_signal_display() {
+        assert_eq!(format!("{}", Signal::Long), "LONG");
+        assert_eq!(format!("{}", Signal::default()), "HOLD");
+    }
+}
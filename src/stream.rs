@@ -0,0 +1,179 @@
+//! Indicators over asynchronous streams.
+//!
+//! Available with the `tokio` feature.
+//!
+//! [IndicatorStreamExt] adapts any [Stream], e.g. the bars decoded from a websocket, into a
+//! stream of the outputs of an indicator, which owns the state of the indicator, so async
+//! consumers don't have to share it mutably between tasks. The adapters work with any async
+//! runtime implementing the [Stream] trait of `futures`, including tokio and `tokio-stream`.
+//!
+//! # Example
+//!
+//! ```
+//! use futures_core::Stream;
+//! use ta_panther::indicators::RelativeStrengthIndex;
+//! use ta_panther::stream::IndicatorStreamExt;
+//! use ta_panther::DataItem;
+//!
+//! fn rsi<S>(bars: S) -> impl Stream<Item = f64>
+//! where
+//!     S: Stream<Item = DataItem>,
+//! {
+//!     bars.indicator(RelativeStrengthIndex::new(14).unwrap())
+//! }
+//! ```
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::Next;
+
+pin_project! {
+    /// Stream of the outputs of an indicator fed with references to the items of a stream,
+    /// created by [IndicatorStreamExt::indicator].
+    #[derive(Debug, Clone)]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct IndicatorStream<S, I> {
+        #[pin]
+        stream: S,
+        indicator: I,
+    }
+}
+
+pin_project! {
+    /// Stream of the outputs of an indicator fed with the items of a stream, created by
+    /// [IndicatorStreamExt::indicator_by_value].
+    #[derive(Debug, Clone)]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct IndicatorValueStream<S, I> {
+        #[pin]
+        stream: S,
+        indicator: I,
+    }
+}
+
+impl<S, I> IndicatorStream<S, I> {
+    /// Returns the indicator.
+    pub fn indicator_ref(&self) -> &I {
+        &self.indicator
+    }
+
+    /// Returns the underlying stream and the indicator.
+    pub fn into_inner(self) -> (S, I) {
+        (self.stream, self.indicator)
+    }
+}
+
+impl<S, I> IndicatorValueStream<S, I> {
+    /// Returns the indicator.
+    pub fn indicator_ref(&self) -> &I {
+        &self.indicator
+    }
+
+    /// Returns the underlying stream and the indicator.
+    pub fn into_inner(self) -> (S, I) {
+        (self.stream, self.indicator)
+    }
+}
+
+impl<S, I, O> Stream for IndicatorStream<S, I>
+where
+    S: Stream,
+    I: for<'a> Next<&'a S::Item, Output = O>,
+{
+    type Item = O;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.stream
+            .poll_next(cx)
+            .map(|item| item.map(|item| this.indicator.next(&item)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S, I> Stream for IndicatorValueStream<S, I>
+where
+    S: Stream,
+    I: Next<S::Item>,
+{
+    type Item = I::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        this.stream
+            .poll_next(cx)
+            .map(|item| item.map(|item| this.indicator.next(item)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+/// Adapters feeding the items of a [Stream] into an indicator.
+pub trait IndicatorStreamExt: Stream {
+    /// Feeds references to the items, e.g. [DataItem](crate::DataItem)s, into `indicator` and
+    /// yields its outputs.
+    fn indicator<I>(self, indicator: I) -> IndicatorStream<Self, I>
+    where
+        Self: Sized,
+        I: for<'a> Next<&'a Self::Item>,
+    {
+        IndicatorStream {
+            stream: self,
+            indicator,
+        }
+    }
+
+    /// Feeds the items, e.g. `f64` prices, into `indicator` and yields its outputs.
+    fn indicator_by_value<I>(self, indicator: I) -> IndicatorValueStream<Self, I>
+    where
+        Self: Sized,
+        I: Next<Self::Item>,
+    {
+        IndicatorValueStream {
+            stream: self,
+            indicator,
+        }
+    }
+}
+
+impl<S: Stream> IndicatorStreamExt for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::test_helper::*;
+    use tokio_stream::{self as stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_indicator() {
+        let bars = stream::iter(vec![
+            Bar::new().close(2.0),
+            Bar::new().close(4.0),
+            Bar::new().close(9.0),
+        ]);
+        let outputs = bars.indicator(SimpleMovingAverage::new(2).unwrap());
+        assert_eq!(outputs.size_hint(), (3, Some(3)));
+        assert_eq!(outputs.collect::<Vec<f64>>().await, vec![2.0, 3.0, 6.5]);
+    }
+
+    #[tokio::test]
+    async fn test_indicator_by_value() {
+        let mut outputs = stream::iter(vec![2.0, 5.0, 1.0])
+            .indicator_by_value(ExponentialMovingAverage::new(3).unwrap());
+        assert_eq!(outputs.next().await, Some(2.0));
+        assert_eq!(outputs.next().await, Some(3.5));
+        assert_eq!(outputs.indicator_ref().to_string(), "EMA(3)");
+        assert_eq!(outputs.next().await, Some(2.25));
+        assert_eq!(outputs.next().await, None);
+    }
+}
@@ -1,5 +1,11 @@
 use super::{Close, High, Low, Open, Volume};
 
+// the tests of `no_std` builds take the collections from `alloc`
+pub use alloc::boxed::Box;
+pub use alloc::string::ToString;
+pub use alloc::vec::Vec;
+pub use alloc::{format, vec};
+
 #[derive(Debug, PartialEq)]
 pub struct Bar {
     open: f64,
@@ -20,10 +26,10 @@ impl Bar {
         }
     }
 
-    //pub fn open<T: Into<f64>>(mut self, val :T ) -> Self {
-    //    self.open = val.into();
-    //    self
-    //}
+    pub fn open<T: Into<f64>>(mut self, val: T) -> Self {
+        self.open = val.into();
+        self
+    }
 
     pub fn high<T: Into<f64>>(mut self, val: T) -> Self {
         self.high = val.into();
@@ -100,7 +106,7 @@ macro_rules! test_indicator {
             assert_eq!(indicator.next(12.3), first_output);
 
             // ensure Display is implemented
-            format!("{}", indicator);
+            let _ = format!("{}", indicator);
         }
     };
 }
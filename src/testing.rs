@@ -0,0 +1,260 @@
+//! Helpers to verify that batch and streaming implementations of an indicator agree.
+//!
+//! Indicators which override [NextBatch::next_batch] with an
+//! optimized implementation must behave exactly like calling [Next::next](crate::Next::next)
+//! for every input. The functions in this module compare both paths on the same data and are
+//! public, so custom indicators can be checked the same way.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::indicators::HighestHighValue;
+//! use ta_panther::testing::{assert_batch_consistent, random_series};
+//!
+//! let hhv = HighestHighValue::new(10).unwrap();
+//! assert_batch_consistent(&hhv, &random_series(42, 1_000), 1e-9);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::NextBatch;
+
+/// Describes the first output on which the batch and streaming paths disagree.
+///
+/// If one path returned fewer outputs than the other, its missing output is NaN.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    /// Position of the output in the whole sequence of outputs.
+    pub index: usize,
+    pub streaming: f64,
+    pub batch: f64,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "output {} differs: streaming = {}, batch = {}",
+            self.index, self.streaming, self.batch
+        )
+    }
+}
+
+/// Compares the batch and the streaming path of `indicator` on `input`.
+///
+/// A copy of the indicator consumes `input` one value at a time, while other copies consume it
+/// as a single batch and split into chunks of varying size. Afterwards all copies are fed a few
+/// more values one by one, to verify that the batch path left the indicator in the same state.
+///
+/// Two outputs agree if both are NaN or if their absolute difference does not exceed
+/// `tolerance`.
+pub fn check_batch_consistency<I>(
+    indicator: &I,
+    input: &[f64],
+    tolerance: f64,
+) -> Result<(), Mismatch>
+where
+    I: NextBatch<Output = f64> + Clone,
+{
+    let tail = random_series(input.len() as u64, 16);
+
+    let mut streaming = indicator.clone();
+    let expected: Vec<f64> = input
+        .iter()
+        .chain(tail.iter())
+        .map(|&value| streaming.next(value))
+        .collect();
+
+    for chunk_size in [input.len().max(1), 1, 2, 7, 64] {
+        let mut batch = indicator.clone();
+        let mut actual = Vec::with_capacity(expected.len());
+        for chunk in input.chunks(chunk_size) {
+            actual.extend(batch.next_batch(chunk));
+        }
+        actual.extend(tail.iter().map(|&value| batch.next(value)));
+
+        compare(&expected, &actual, tolerance)?;
+    }
+
+    Ok(())
+}
+
+/// Same as [check_batch_consistency], but panics with a descriptive message on mismatch.
+pub fn assert_batch_consistent<I>(indicator: &I, input: &[f64], tolerance: f64)
+where
+    I: NextBatch<Output = f64> + Clone + fmt::Display,
+{
+    if let Err(mismatch) = check_batch_consistency(indicator, input, tolerance) {
+        panic!("{}: {}", indicator, mismatch);
+    }
+}
+
+/// Generates a deterministic pseudo-random walk of positive prices.
+///
+/// The same `seed` always produces the same series, which keeps failures reproducible.
+pub fn random_series(seed: u64, len: usize) -> Vec<f64> {
+    // xorshift64*, seeded through splitmix64 so that small seeds are fine
+    let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    state ^= state >> 31;
+    if state == 0 {
+        state = 1;
+    }
+
+    let mut price = 100.0;
+    (0..len)
+        .map(|_| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            let uniform =
+                (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64;
+            price *= 1.0 + (uniform - 0.5) * 0.04;
+            price
+        })
+        .collect()
+}
+
+pub(crate) fn compare(expected: &[f64], actual: &[f64], tolerance: f64) -> Result<(), Mismatch> {
+    if expected.len() != actual.len() {
+        let index = expected.len().min(actual.len());
+        return Err(Mismatch {
+            index,
+            streaming: expected.get(index).copied().unwrap_or(f64::NAN),
+            batch: actual.get(index).copied().unwrap_or(f64::NAN),
+        });
+    }
+    for (index, (&streaming, &batch)) in expected.iter().zip(actual.iter()).enumerate() {
+        let agree =
+            (streaming.is_nan() && batch.is_nan()) || (streaming - batch).abs() <= tolerance;
+        if !agree {
+            return Err(Mismatch {
+                index,
+                streaming,
+                batch,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage, HighestHighValue, LowestLowValue, SimpleMovingAverage,
+        StandardDeviation, WeightedMovingAverage,
+    };
+    use crate::Next;
+
+    #[derive(Debug, Clone)]
+    struct Broken {
+        count: usize,
+    }
+
+    impl Next<f64> for Broken {
+        type Output = f64;
+
+        fn next(&mut self, input: f64) -> f64 {
+            self.count += 1;
+            input * self.count as f64
+        }
+    }
+
+    impl NextBatch for Broken {
+        fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+            input.to_vec()
+        }
+    }
+
+    /// Returns the right outputs but forgets to advance the window.
+    #[derive(Debug, Clone)]
+    struct Stateless {
+        sma: SimpleMovingAverage,
+    }
+
+    impl Next<f64> for Stateless {
+        type Output = f64;
+
+        fn next(&mut self, input: f64) -> f64 {
+            self.sma.next(input)
+        }
+    }
+
+    impl NextBatch for Stateless {
+        fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+            self.sma.clone().next_batch(input)
+        }
+    }
+
+    // the kernels of the `simd` feature sum in another order
+    const SUM_TOLERANCE: f64 = if cfg!(feature = "simd") { 1e-9 } else { 0.0 };
+    const SD_TOLERANCE: f64 = if cfg!(feature = "simd") { 1e-6 } else { 0.0 };
+
+    #[test]
+    fn test_random_series() {
+        assert_eq!(random_series(1, 100), random_series(1, 100));
+        assert_ne!(random_series(1, 100), random_series(2, 100));
+        assert!(random_series(0, 1000).iter().all(|&x| x > 0.0));
+    }
+
+    #[test]
+    fn test_indicators() {
+        let data = random_series(7, 500);
+        for period in [1, 2, 3, 14, 50] {
+            assert_batch_consistent(&HighestHighValue::new(period).unwrap(), &data, 0.0);
+            assert_batch_consistent(&LowestLowValue::new(period).unwrap(), &data, 0.0);
+            let sma = SimpleMovingAverage::new(period).unwrap();
+            assert_batch_consistent(&sma, &data, SUM_TOLERANCE);
+            assert_batch_consistent(&ExponentialMovingAverage::new(period).unwrap(), &data, 0.0);
+            let wma = WeightedMovingAverage::new(period).unwrap();
+            assert_batch_consistent(&wma, &data, SUM_TOLERANCE);
+            let sd = StandardDeviation::new(period).unwrap();
+            assert_batch_consistent(&sd, &data, SD_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let result = check_batch_consistency(&Broken { count: 0 }, &[1.0, 2.0], 1e-9);
+        assert_eq!(
+            result,
+            Err(Mismatch {
+                index: 1,
+                streaming: 4.0,
+                batch: 2.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_stale_state() {
+        let stateless = Stateless {
+            sma: SimpleMovingAverage::new(2).unwrap(),
+        };
+        let mismatch = check_batch_consistency(&stateless, &[1.0, 3.0, 5.0], 1e-9).unwrap_err();
+        // the one-by-one chunks only disagree once the tail is streamed
+        assert_eq!(mismatch.index, 3);
+        assert_eq!(mismatch.batch, random_series(3, 1)[0]);
+    }
+
+    #[test]
+    fn test_missing_outputs() {
+        let result = compare(&[1.0, 2.0, 3.0], &[1.0, 2.0], 0.0);
+        let mismatch = result.unwrap_err();
+        assert_eq!((mismatch.index, mismatch.streaming), (2, 3.0));
+        assert!(mismatch.batch.is_nan());
+
+        let mismatch = compare(&[1.0], &[1.0, 2.0], 0.0).unwrap_err();
+        assert_eq!((mismatch.index, mismatch.batch), (1, 2.0));
+        assert!(mismatch.streaming.is_nan());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let hhv = HighestHighValue::new(3).unwrap();
+        assert!(check_batch_consistency(&hhv, &[], 0.0).is_ok());
+    }
+}
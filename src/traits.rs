@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 // Indicator traits
 //
 
@@ -11,6 +12,53 @@ pub trait Period {
     fn period(&self) -> usize;
 }
 
+/// Reports the warm-up progress of windowed indicators.
+///
+/// An indicator is ready once its window is full, so its outputs no longer depend on how many
+/// inputs it has seen. Strategies can wait until all their indicators are ready before they
+/// generate signals.
+pub trait IsReady {
+    /// Returns the number of inputs needed until the indicator is ready, 0 if it is ready.
+    fn bars_remaining(&self) -> usize;
+
+    /// Returns whether the window of the indicator is full.
+    fn is_ready(&self) -> bool {
+        self.bars_remaining() == 0
+    }
+}
+
+/// Returns the output of an indicator only once it is ready.
+///
+/// Many indicators return values over a partial window during their warm-up, e.g. the maximum
+/// of the inputs seen so far, which are easily mistaken for real signals.
+/// [next_checked](NextChecked::next_checked) feeds every input to the indicator, and returns
+/// `None` until its [IsReady::bars_remaining] reaches 0. Implemented by all the indicators
+/// implementing [Next] and [IsReady], see also [Checked](indicators/struct.Checked.html).
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::RateOfChange;
+/// use ta_panther::NextChecked;
+///
+/// let mut roc = RateOfChange::new(2).unwrap();
+/// assert_eq!(roc.next_checked(10.0), None);
+/// assert_eq!(roc.next_checked(12.0), None);
+/// assert_eq!(roc.next_checked(15.0), Some(50.0));
+/// ```
+pub trait NextChecked<T>: Next<T> + IsReady {
+    fn next_checked(&mut self, input: T) -> Option<Self::Output> {
+        let output = self.next(input);
+        if self.is_ready() {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
+
+impl<N: Next<T> + IsReady, T> NextChecked<T> for N {}
+
 /// Consumes a data item of type `T` and returns `Output`.
 ///
 /// Typically `T` can be `f64` or a struct similar to [DataItem](struct.DataItem.html), that implements
@@ -25,6 +73,126 @@ pub trait Next<T> {
     fn next(&mut self, input: T) -> Self::Output;
 }
 
+/// Consumes a slice of `f64` inputs at once and returns the outputs for every one of them.
+///
+/// The default implementation calls [Next::next] for every input. Indicators may override it
+/// with an optimized implementation, which must produce the same outputs and leave the
+/// indicator in the same state as feeding the inputs one by one. The
+/// [testing](crate::testing) module provides helpers to verify this.
+pub trait NextBatch: Next<f64> {
+    fn next_batch(&mut self, input: &[f64]) -> Vec<Self::Output> {
+        input.iter().map(|&value| self.next(value)).collect()
+    }
+}
+
+/// Primes an indicator from recent history in one call, e.g. when reconnecting after an outage
+/// of the feed.
+///
+/// Implemented for every indicator which can be [Reset], with `T = f64` for indicators consuming
+/// `f64` inputs and `T = DataItem` for indicators consuming references to
+/// [DataItem](crate::DataItem)s.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::indicators::ExponentialMovingAverage;
+/// use ta_panther::{Next, WarmUp};
+///
+/// let mut ema = ExponentialMovingAverage::new(3).unwrap();
+/// ema.next(100.0);
+///
+/// // the feed was down, restart from the latest prices
+/// ema.warm_up(&[2.0, 5.0]);
+/// assert_eq!(ema.next(1.0), 2.25);
+/// ```
+pub trait WarmUp<T> {
+    /// Resets the indicator and feeds the `history`, oldest first, discarding the outputs.
+    fn warm_up(&mut self, history: &[T]);
+}
+
+impl<I: Next<f64> + Reset> WarmUp<f64> for I {
+    fn warm_up(&mut self, history: &[f64]) {
+        self.reset();
+        for &input in history {
+            self.next(input);
+        }
+    }
+}
+
+impl<I> WarmUp<crate::DataItem> for I
+where
+    I: for<'a> Next<&'a crate::DataItem> + Reset,
+{
+    fn warm_up(&mut self, history: &[crate::DataItem]) {
+        self.reset();
+        for input in history {
+            self.next(input);
+        }
+    }
+}
+
+/// Creates an indicator from a list of numeric parameters.
+///
+/// Allows registries to construct indicators by name, e.g. from configuration files. For
+/// custom indicators it can be derived with [Indicator](derive.Indicator.html) (requires the
+/// `derive` feature).
+pub trait FromParams: Sized {
+    /// Name of the indicator, e.g. `SMA`.
+    const NAME: &'static str;
+
+    /// Creates the indicator, returning an error if the number or the values of the parameters
+    /// are invalid.
+    fn from_params(params: &[f64]) -> crate::errors::Result<Self>;
+}
+
+/// Invariants and format version of the serialized state of an indicator.
+///
+/// Deserializing a state through [Versioned](crate::state::Versioned) migrates states written
+/// by older versions and rejects inconsistent ones, e.g. hand-edited states whose buffer doesn't
+/// match their period, which would otherwise produce wrong outputs or panics.
+pub trait Validate {
+    /// Version of the serialized state, increased whenever its format changes.
+    const STATE_VERSION: u32 = 1;
+
+    /// Returns [InvalidState](crate::errors::TaError::InvalidState) if the state is
+    /// inconsistent.
+    fn validate(&self) -> crate::errors::Result<()>;
+
+    /// Upgrades a state deserialized from an older `version`, before it is validated.
+    ///
+    /// States of older versions have to deserialize into the current type, e.g. by giving new
+    /// fields a `#[serde(default)]`, and are fixed up here. States whose fields were replaced
+    /// are converted by [deserialize_version](Self::deserialize_version) instead.
+    fn migrate(&mut self, version: u32) -> crate::errors::Result<()> {
+        let _ = version;
+        Ok(())
+    }
+
+    /// Deserializes a state written by `version`, through
+    /// [Versioned](crate::state::Versioned).
+    ///
+    /// Overridden when the layout of the state changed, to deserialize the states of older
+    /// versions into their own layout and convert them to the current type.
+    #[cfg(feature = "serde")]
+    fn deserialize_version<'de, D>(version: u32, deserializer: D) -> Result<Self, D::Error>
+    where
+        Self: Sized + serde::Deserialize<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        let _ = version;
+        Self::deserialize(deserializer)
+    }
+}
+
+/// Lets a borrowed state be serialized as [Versioned](crate::state::Versioned).
+impl<T: Validate> Validate for &T {
+    const STATE_VERSION: u32 = T::STATE_VERSION;
+
+    fn validate(&self) -> crate::errors::Result<()> {
+        (**self).validate()
+    }
+}
+
 pub trait State {
     type Output;
     fn state(&self) -> Self::Output;
@@ -54,3 +222,65 @@ pub trait High {
 pub trait Volume {
     fn volume(&self) -> f64;
 }
+
+/// Start time of a particular period, in milliseconds since the Unix epoch, if known.
+pub trait Timestamp {
+    fn timestamp(&self) -> Option<i64>;
+}
+
+/// Number of open contracts at the end of a particular period, if known.
+pub trait OpenInterest {
+    fn open_interest(&self) -> Option<f64>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{AverageTrueRange, RelativeStrengthIndex, SimpleMovingAverage};
+    use crate::DataItem;
+
+    #[test]
+    fn test_warm_up() {
+        let history = [10.0, 10.5, 10.0, 9.5, 11.0];
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+        let mut replayed = RelativeStrengthIndex::new(3).unwrap();
+        rsi.next(50.0);
+        rsi.warm_up(&history);
+        for &input in &history {
+            replayed.next(input);
+        }
+        assert_eq!(rsi.next(10.2), replayed.next(10.2));
+
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.warm_up(&[1.0, 2.0, 3.0]);
+        assert!(sma.is_ready());
+        assert_eq!(sma.next(5.0), 4.0);
+        sma.warm_up(&[] as &[f64]);
+        assert_eq!(sma.next(5.0), 5.0);
+    }
+
+    #[test]
+    fn test_warm_up_bars() {
+        let bar = |high: f64, low: f64, close: f64| {
+            DataItem::builder()
+                .open(close)
+                .high(high)
+                .low(low)
+                .close(close)
+                .volume(1.0)
+                .build()
+                .unwrap()
+        };
+        let history = [bar(10.0, 8.0, 9.0), bar(11.0, 9.0, 10.0)];
+        let mut atr = AverageTrueRange::new(3).unwrap();
+        atr.next(&bar(20.0, 1.0, 5.0));
+        atr.warm_up(&history);
+
+        let mut replayed = AverageTrueRange::new(3).unwrap();
+        for input in &history {
+            replayed.next(input);
+        }
+        let next = bar(12.0, 9.5, 11.0);
+        assert_eq!(atr.next(&next), replayed.next(&next));
+    }
+}
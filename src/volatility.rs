@@ -0,0 +1,482 @@
+//! Volatility estimators.
+//!
+//! [HistoricalVolatility] estimates the volatility from close prices only, while
+//! [ParkinsonVolatility] and [GarmanKlassVolatility] also use the range of every bar, which
+//! makes them considerably more efficient: they need fewer bars for an estimate of the same
+//! precision.
+//!
+//! All the estimators are annualized by multiplying the per-bar variance by the number of bars
+//! per year, e.g. 252 for daily bars of stocks or 365 for daily bars of cryptocurrencies. Pass
+//! 1.0 to get the volatility per bar.
+//!
+//! # Example
+//!
+//! ```
+//! use ta_panther::volatility::ParkinsonVolatility;
+//! use ta_panther::{DataItem, Next};
+//!
+//! let mut volatility = ParkinsonVolatility::new(20, 252.0).unwrap();
+//! let bar = DataItem::builder()
+//!     .open(100.0)
+//!     .high(102.0)
+//!     .low(99.0)
+//!     .close(101.0)
+//!     .volume(1000.0)
+//!     .build()
+//!     .unwrap();
+//! assert!(volatility.next(&bar) > 0.0);
+//! ```
+
+use alloc::boxed::Box;
+use core::f64::consts::LN_2;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use crate::helpers::{ring, Moments};
+use crate::{Close, High, Low, Next, Open, Period, Reset};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+fn validate(period: usize, periods_per_year: f64) -> Result<()> {
+    if period == 0 || !(periods_per_year > 0.0 && periods_per_year.is_finite()) {
+        Err(TaError::InvalidParameter)
+    } else {
+        Ok(())
+    }
+}
+
+/// Historical volatility (HV).
+///
+/// Returns the annualized sample standard deviation of the logarithmic returns of the last
+/// _period_ inputs, also known as close-to-close volatility. Returns 0 until two returns have
+/// been seen.
+///
+/// # Formula
+///
+/// _HV = √(periods_per_year × var(ln(C<sub>t</sub> / C<sub>t-1</sub>)))_
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0). Default is 20.
+/// * _periods_per_year_ - annualization factor (greater than 0.0). Default is 252.0.
+///
+/// # Example
+///
+/// ```
+/// use ta_panther::volatility::HistoricalVolatility;
+/// use ta_panther::Next;
+///
+/// let mut hv = HistoricalVolatility::new(20, 1.0).unwrap();
+/// hv.next(100.0);
+/// hv.next(110.0);
+/// assert_eq!(hv.next(121.0), 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volatility (finance), Wikipedia](https://en.wikipedia.org/wiki/Volatility_(finance))
+///
+#[doc(alias = "HV")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HistoricalVolatility {
+    period: usize,
+    periods_per_year: f64,
+    index: usize,
+    count: usize,
+    previous: Option<f64>,
+    moments: Moments,
+    deque: Box<[f64]>,
+}
+
+impl HistoricalVolatility {
+    pub fn new(period: usize, periods_per_year: f64) -> Result<Self> {
+        validate(period, periods_per_year)?;
+        Ok(Self {
+            period,
+            periods_per_year,
+            index: 0,
+            count: 0,
+            previous: None,
+            moments: Moments::default(),
+            deque: ring(0.0, period)?,
+        })
+    }
+}
+
+impl Period for HistoricalVolatility {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<f64> for HistoricalVolatility {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if let Some(previous) = self.previous.replace(input) {
+            let log_return = (input / previous).ln();
+            if self.count < self.period {
+                self.count += 1;
+            } else {
+                self.moments.pop(self.deque[self.index]);
+            }
+            self.moments.push(log_return);
+
+            self.deque[self.index] = log_return;
+            self.index = if self.index + 1 < self.period {
+                self.index + 1
+            } else {
+                0
+            };
+        }
+
+        (self.moments.sample_variance() * self.periods_per_year).sqrt()
+    }
+}
+
+impl<T: Close> Next<&T> for HistoricalVolatility {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Reset for HistoricalVolatility {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.previous = None;
+        self.moments = Moments::default();
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+impl Default for HistoricalVolatility {
+    fn default() -> Self {
+        Self::new(20, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for HistoricalVolatility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HV({}, {})", self.period, self.periods_per_year)
+    }
+}
+
+/// Rolling mean of a per-bar variance term, shared by the range based estimators.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct RollingVariance {
+    period: usize,
+    periods_per_year: f64,
+    index: usize,
+    count: usize,
+    sum: f64,
+    deque: Box<[f64]>,
+}
+
+impl RollingVariance {
+    fn new(period: usize, periods_per_year: f64) -> Result<Self> {
+        validate(period, periods_per_year)?;
+        Ok(Self {
+            period,
+            periods_per_year,
+            index: 0,
+            count: 0,
+            sum: 0.0,
+            deque: ring(0.0, period)?,
+        })
+    }
+
+    /// Adds the variance term of a bar, returning the annualized volatility.
+    fn next(&mut self, term: f64) -> f64 {
+        if self.count < self.period {
+            self.count += 1;
+        } else {
+            self.sum -= self.deque[self.index];
+        }
+        self.sum += term;
+
+        self.deque[self.index] = term;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        (self.sum / self.count as f64 * self.periods_per_year)
+            .max(0.0)
+            .sqrt()
+    }
+
+    fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.sum = 0.0;
+        for i in 0..self.period {
+            self.deque[i] = 0.0;
+        }
+    }
+}
+
+/// Parkinson volatility.
+///
+/// Estimates the annualized volatility of the last _period_ bars from their high-low ranges. It
+/// is about five times more efficient than the close-to-close
+/// [HistoricalVolatility], but it assumes that the price has no drift and no opening jumps, so
+/// it underestimates the volatility of markets with gaps.
+///
+/// # Formula
+///
+/// _σ = √(periods_per_year × mean(ln(H / L)<sup>2</sup>) / (4 ln 2))_
+///
+/// # Parameters
+///
+/// * _period_ - number of bars (integer greater than 0). Default is 20.
+/// * _periods_per_year_ - annualization factor (greater than 0.0). Default is 252.0.
+///
+/// # Links
+///
+/// * [Parkinson, M. (1980), The Extreme Value Method for Estimating the Variance of the Rate of Return](https://doi.org/10.1086/296071)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParkinsonVolatility {
+    variance: RollingVariance,
+}
+
+impl ParkinsonVolatility {
+    pub fn new(period: usize, periods_per_year: f64) -> Result<Self> {
+        Ok(Self {
+            variance: RollingVariance::new(period, periods_per_year)?,
+        })
+    }
+}
+
+impl Period for ParkinsonVolatility {
+    fn period(&self) -> usize {
+        self.variance.period
+    }
+}
+
+impl<T: High + Low> Next<&T> for ParkinsonVolatility {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = (input.high() / input.low()).ln();
+        self.variance.next(range * range / (4.0 * LN_2))
+    }
+}
+
+impl Reset for ParkinsonVolatility {
+    fn reset(&mut self) {
+        self.variance.reset();
+    }
+}
+
+impl Default for ParkinsonVolatility {
+    fn default() -> Self {
+        Self::new(20, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for ParkinsonVolatility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "PARKINSON({}, {})",
+            self.variance.period, self.variance.periods_per_year
+        )
+    }
+}
+
+/// Garman-Klass volatility.
+///
+/// Estimates the annualized volatility of the last _period_ bars from their open, high, low and
+/// close prices. It is about seven times more efficient than the close-to-close
+/// [HistoricalVolatility]. Like the [ParkinsonVolatility] it assumes that the price has no drift
+/// and no opening jumps.
+///
+/// # Formula
+///
+/// _σ = √(periods_per_year × mean(½ ln(H / L)<sup>2</sup> - (2 ln 2 - 1) ln(C / O)<sup>2</sup>))_
+///
+/// # Parameters
+///
+/// * _period_ - number of bars (integer greater than 0). Default is 20.
+/// * _periods_per_year_ - annualization factor (greater than 0.0). Default is 252.0.
+///
+/// # Links
+///
+/// * [Garman, M. B., Klass, M. J. (1980), On the Estimation of Security Price Volatilities from Historical Data](https://doi.org/10.1086/296072)
+///
+#[doc(alias = "GK")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GarmanKlassVolatility {
+    variance: RollingVariance,
+}
+
+impl GarmanKlassVolatility {
+    pub fn new(period: usize, periods_per_year: f64) -> Result<Self> {
+        Ok(Self {
+            variance: RollingVariance::new(period, periods_per_year)?,
+        })
+    }
+}
+
+impl Period for GarmanKlassVolatility {
+    fn period(&self) -> usize {
+        self.variance.period
+    }
+}
+
+impl<T: Open + High + Low + Close> Next<&T> for GarmanKlassVolatility {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = (input.high() / input.low()).ln();
+        let body = (input.close() / input.open()).ln();
+        self.variance
+            .next(0.5 * range * range - (2.0 * LN_2 - 1.0) * body * body)
+    }
+}
+
+impl Reset for GarmanKlassVolatility {
+    fn reset(&mut self) {
+        self.variance.reset();
+    }
+}
+
+impl Default for GarmanKlassVolatility {
+    fn default() -> Self {
+        Self::new(20, 252.0).unwrap()
+    }
+}
+
+impl fmt::Display for GarmanKlassVolatility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "GK({}, {})",
+            self.variance.period, self.variance.periods_per_year
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::DataItem;
+
+    fn bars() -> Vec<DataItem> {
+        [
+            (10.0, 11.0, 9.0, 10.5),
+            (10.5, 12.0, 10.0, 11.5),
+            (11.5, 11.8, 10.8, 11.0),
+            (11.0, 12.5, 10.9, 12.2),
+        ]
+        .iter()
+        .map(|&(open, high, low, close)| {
+            DataItem::builder()
+                .open(open)
+                .high(high)
+                .low(low)
+                .close(close)
+                .volume(0.0)
+                .build()
+                .unwrap()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(HistoricalVolatility::new(0, 252.0).is_err());
+        assert!(HistoricalVolatility::new(20, 0.0).is_err());
+        assert!(ParkinsonVolatility::new(20, f64::NAN).is_err());
+        assert!(GarmanKlassVolatility::new(0, 252.0).is_err());
+        assert!(HistoricalVolatility::new(1, 1.0).is_ok());
+        assert!(ParkinsonVolatility::new(1, 1.0).is_ok());
+        assert!(GarmanKlassVolatility::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_historical_next() {
+        let mut hv = HistoricalVolatility::new(3, 252.0).unwrap();
+        assert_eq!(hv.next(100.0), 0.0);
+        assert_eq!(hv.next(102.0), 0.0);
+        assert_eq!(round(hv.next(101.0)), 0.333);
+        assert_eq!(round(hv.next(105.0)), 0.39);
+        assert_eq!(round(hv.next(103.0)), 0.495);
+        assert_eq!(round(hv.next(&Bar::new().close(103))), 0.47);
+    }
+
+    #[test]
+    fn test_parkinson_next() {
+        let mut parkinson = ParkinsonVolatility::new(2, 252.0).unwrap();
+        let outputs: Vec<f64> = bars()
+            .iter()
+            .map(|bar| round(parkinson.next(bar)))
+            .collect();
+        assert_eq!(outputs, vec![1.913, 1.828, 1.366, 1.099]);
+    }
+
+    #[test]
+    fn test_garman_klass_next() {
+        let mut gk = GarmanKlassVolatility::new(2, 252.0).unwrap();
+        let outputs: Vec<f64> = bars().iter().map(|bar| round(gk.next(bar))).collect();
+        assert_eq!(outputs, vec![2.2, 2.028, 1.445, 1.029]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hv = HistoricalVolatility::new(3, 252.0).unwrap();
+        hv.next(100.0);
+        hv.next(120.0);
+        hv.reset();
+        assert_eq!(hv.next(80.0), 0.0);
+        assert_eq!(hv.next(81.0), 0.0);
+
+        let bars = bars();
+        let mut parkinson = ParkinsonVolatility::new(2, 252.0).unwrap();
+        let mut gk = GarmanKlassVolatility::new(2, 252.0).unwrap();
+        parkinson.next(&bars[0]);
+        gk.next(&bars[0]);
+        parkinson.reset();
+        gk.reset();
+        assert_eq!(round(parkinson.next(&bars[2])), 0.844);
+        assert_eq!(round(gk.next(&bars[1])), 1.839);
+    }
+
+    #[test]
+    fn test_default() {
+        HistoricalVolatility::default();
+        ParkinsonVolatility::default();
+        GarmanKlassVolatility::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", HistoricalVolatility::new(20, 252.0).unwrap()),
+            "HV(20, 252)"
+        );
+        assert_eq!(
+            format!("{}", ParkinsonVolatility::new(10, 365.0).unwrap()),
+            "PARKINSON(10, 365)"
+        );
+        assert_eq!(
+            format!("{}", GarmanKlassVolatility::new(5, 1.0).unwrap()),
+            "GK(5, 1)"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! WebAssembly bindings.
+//!
+//! Available with the `wasm` feature.
+//!
+//! Exposes the indicators of the [factory] to JavaScript through
+//! [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/), so charting applications
+//! running in a browser compute exactly the same values as the Rust code. Every indicator is an
+//! [Indicator] created by its name and parameters:
+//!
+//! ```js
+//! import { Indicator } from "ta-panther";
+//!
+//! const rsi = new Indicator("RSI", [14]);
+//! const values = rsi.nextBatch(new Float64Array(closes));
+//! const last = rsi.next(101.5);
+//! console.log(rsi.toString()); // "RSI(14)"
+//! rsi.free();
+//! ```
+//!
+//! Indicators live in the WebAssembly memory and have to be released with `free()`.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::factory::{self, IndicatorFactory, NextF64};
+
+/// Indicator consuming and returning numbers, created by name.
+#[wasm_bindgen]
+pub struct Indicator {
+    inner: Box<dyn NextF64>,
+}
+
+#[wasm_bindgen]
+impl Indicator {
+    /// Creates a built-in indicator by its case-insensitive name, e.g. `new Indicator("EMA", [9])`.
+    ///
+    /// Throws if the name is unknown or the parameters are invalid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(name: &str, params: &[f64]) -> Result<Indicator, JsError> {
+        Ok(Self {
+            inner: factory::indicator(name, params)?,
+        })
+    }
+
+    /// Feeds a value and returns the output.
+    pub fn next(&mut self, input: f64) -> f64 {
+        self.inner.next(input)
+    }
+
+    /// Feeds all the values of an array and returns their outputs.
+    #[wasm_bindgen(js_name = nextBatch)]
+    pub fn next_batch(&mut self, input: &[f64]) -> Vec<f64> {
+        input.iter().map(|&value| self.inner.next(value)).collect()
+    }
+
+    /// Resets the indicator to its initial state.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Returns the name and parameters of the indicator, e.g. `RSI(14)`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn display(&self) -> String {
+        self.inner.to_string()
+    }
+
+    /// Returns the sorted names of the built-in indicators.
+    pub fn names() -> Vec<String> {
+        IndicatorFactory::default()
+            .names()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indicator() {
+        let mut sma = Indicator::new("sma", &[2.0]).unwrap();
+        assert_eq!(sma.display(), "SMA(2)");
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next_batch(&[8.0, 10.0]), vec![6.0, 9.0]);
+
+        sma.reset();
+        assert_eq!(sma.next(10.0), 10.0);
+    }
+
+    #[test]
+    fn test_names() {
+        let names = Indicator::names();
+        assert!(names.iter().any(|name| name == "RSI"));
+    }
+}
@@ -0,0 +1,268 @@
+//! Derive macro for custom indicators of the [ta-panther](https://docs.rs/ta-panther) crate.
+//!
+//! The macro is re-exported by `ta-panther` with the `derive` feature, see the documentation of
+//! `ta_panther::Indicator` for usage.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Ident, LitStr, Type};
+
+/// Generates `Period`, `Reset`, `Display` and optionally `FromParams` implementations for an
+/// indicator struct.
+///
+/// Container attributes (`#[indicator(...)]` on the struct):
+///
+/// * `name = "NAME"` - name used by `Display` and `FromParams`, defaults to the struct name in
+///   upper snake case
+/// * `from_params` - implement `FromParams` by calling `Self::new` with the parameters in the
+///   order of declaration
+///
+/// Field attributes:
+///
+/// * `period` - the field returned by `Period::period`, also a parameter
+/// * `param` - a parameter, shown by `Display` and passed to `new` by `FromParams`
+/// * `reset` - reset the field by calling `Reset::reset` on it
+/// * `reset = expr` - reset the field by assigning `expr` to it
+///
+/// Fields without a `reset` attribute keep their value on reset.
+///
+/// Serialization is not derived: a derive macro can only add items next to the struct, not
+/// attributes to it, and a `cfg(feature = "serde")` in the generated code would test the
+/// features of the crate using the macro. Add `Serialize` and `Deserialize` to the struct
+/// like any other derive.
+#[proc_macro_derive(Indicator, attributes(indicator))]
+pub fn derive_indicator(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum ResetKind {
+    Keep,
+    Reset,
+    Assign(Expr),
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    period: bool,
+    param: bool,
+    reset: ResetKind,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let mut name = None;
+    let mut from_params = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("indicator"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("from_params") {
+                from_params = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"...\"` or `from_params`"))
+            }
+        })?;
+    }
+    let name = name.unwrap_or_else(|| upper_snake_case(&input.ident.to_string()));
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`Indicator` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "`Indicator` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut parsed = Vec::new();
+    for field in fields {
+        let mut parsed_field = Field {
+            ident: field.ident.clone().expect("named field"),
+            ty: field.ty.clone(),
+            period: false,
+            param: false,
+            reset: ResetKind::Keep,
+        };
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("indicator"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("period") {
+                    parsed_field.period = true;
+                    Ok(())
+                } else if meta.path.is_ident("param") {
+                    parsed_field.param = true;
+                    Ok(())
+                } else if meta.path.is_ident("reset") {
+                    parsed_field.reset = if meta.input.peek(syn::Token![=]) {
+                        ResetKind::Assign(meta.value()?.parse()?)
+                    } else {
+                        ResetKind::Reset
+                    };
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `period`, `param`, `reset` or `reset = ...`"))
+                }
+            })?;
+        }
+        parsed.push(parsed_field);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let periods: Vec<&Field> = parsed.iter().filter(|f| f.period).collect();
+    if periods.len() > 1 {
+        return Err(syn::Error::new(
+            periods[1].ident.span(),
+            "only one field can be marked as `period`",
+        ));
+    }
+    let period_impl = periods.first().map(|field| {
+        let field = &field.ident;
+        quote! {
+            impl #impl_generics ::ta_panther::Period for #ident #ty_generics #where_clause {
+                fn period(&self) -> usize {
+                    self.#field as usize
+                }
+            }
+        }
+    });
+
+    let resets = parsed.iter().map(|field| {
+        let field_ident = &field.ident;
+        match &field.reset {
+            ResetKind::Keep => quote! {},
+            ResetKind::Reset => quote! { ::ta_panther::Reset::reset(&mut self.#field_ident); },
+            ResetKind::Assign(expr) => quote! { self.#field_ident = #expr; },
+        }
+    });
+
+    let params: Vec<&Field> = parsed.iter().filter(|f| f.period || f.param).collect();
+    let format = format!(
+        "{}({})",
+        name.replace('{', "{{").replace('}', "}}"),
+        vec!["{}"; params.len()].join(", ")
+    );
+    let param_idents: Vec<&Ident> = params.iter().map(|f| &f.ident).collect();
+
+    let from_params_impl = if from_params {
+        Some(from_params_impl(&input, &name, &params)?)
+    } else {
+        None
+    };
+
+    Ok(quote! {
+        #period_impl
+
+        impl #impl_generics ::ta_panther::Reset for #ident #ty_generics #where_clause {
+            fn reset(&mut self) {
+                #(#resets)*
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                write!(f, #format, #(self.#param_idents),*)
+            }
+        }
+
+        #from_params_impl
+    })
+}
+
+fn from_params_impl(
+    input: &DeriveInput,
+    name: &str,
+    params: &[&Field],
+) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let count = params.len();
+    let vars: Vec<Ident> = (0..count)
+        .map(|i| Ident::new(&format!("param{}", i), proc_macro2::Span::call_site()))
+        .collect();
+
+    let conversions = params
+        .iter()
+        .zip(&vars)
+        .map(|(field, var)| {
+            let ty = &field.ty;
+            match primitive(ty).as_deref() {
+                Some("f64" | "f32") => Ok(quote! { let #var = #var as #ty; }),
+                // `MAX as f64` rounds up for 64 bit types, so the range is checked on an i128,
+                // to which every integral f64 in the range of the type converts exactly
+                Some(
+                    "usize" | "u8" | "u16" | "u32" | "u64" | "isize" | "i8" | "i16" | "i32" | "i64",
+                ) => Ok(quote! {
+                    if #var.fract() != 0.0 {
+                        return Err(::ta_panther::errors::TaError::InvalidParameter);
+                    }
+                    let #var = <#ty as ::core::convert::TryFrom<i128>>::try_from(#var as i128)
+                        .map_err(|_| ::ta_panther::errors::TaError::InvalidParameter)?;
+                }),
+                _ => Err(syn::Error::new(
+                    ty.span(),
+                    "`from_params` supports only integer and floating point parameters",
+                )),
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics ::ta_panther::FromParams for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+
+            fn from_params(params: &[f64]) -> ::ta_panther::errors::Result<Self> {
+                let [#(#vars),*]: [f64; #count] = params
+                    .try_into()
+                    .map_err(|_| ::ta_panther::errors::TaError::InvalidParameter)?;
+                #(#conversions)*
+                Self::new(#(#vars),*)
+            }
+        }
+    })
+}
+
+/// Returns the name of a primitive type, e.g. `f64`.
+fn primitive(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+            Some(path.path.segments[0].ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn upper_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            result.push('_');
+        }
+        result.extend(c.to_uppercase());
+    }
+    result
+}
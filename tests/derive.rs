@@ -0,0 +1,113 @@
+#![cfg(feature = "derive")]
+
+use ta_panther::errors::{Result, TaError};
+use ta_panther::indicators::ExponentialMovingAverage;
+use ta_panther::{FromParams, Indicator, Next, Period, Reset};
+
+#[derive(Indicator)]
+#[indicator(from_params)]
+struct EmaSpread {
+    #[indicator(period)]
+    period: usize,
+    #[indicator(param)]
+    slow_period: u32,
+    #[indicator(reset)]
+    fast: ExponentialMovingAverage,
+    #[indicator(reset)]
+    slow: ExponentialMovingAverage,
+    #[indicator(reset = None)]
+    last: Option<f64>,
+}
+
+impl EmaSpread {
+    fn new(period: usize, slow_period: u32) -> Result<Self> {
+        if slow_period as usize <= period {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            slow_period,
+            fast: ExponentialMovingAverage::new(period)?,
+            slow: ExponentialMovingAverage::new(slow_period as usize)?,
+            last: None,
+        })
+    }
+}
+
+impl Next<f64> for EmaSpread {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> f64 {
+        let spread = self.fast.next(input) - self.slow.next(input);
+        self.last = Some(spread);
+        spread
+    }
+}
+
+#[derive(Indicator)]
+#[indicator(name = "SAMPLE", from_params)]
+struct Sample {
+    #[indicator(param)]
+    size: u64,
+    #[indicator(param)]
+    offset: i8,
+}
+
+impl Sample {
+    fn new(size: u64, offset: i8) -> Result<Self> {
+        Ok(Self { size, offset })
+    }
+}
+
+#[derive(Indicator)]
+#[indicator(name = "COUNTER")]
+struct Counter {
+    #[indicator(reset = 0)]
+    count: usize,
+}
+
+#[test]
+fn test_derive() {
+    let mut spread = EmaSpread::new(2, 4).unwrap();
+    assert_eq!(spread.period(), 2);
+    assert_eq!(spread.to_string(), "EMA_SPREAD(2, 4)");
+
+    assert_eq!(spread.next(10.0), 0.0);
+    assert!(spread.next(20.0) > 0.0);
+
+    spread.reset();
+    assert_eq!(spread.last, None);
+    assert_eq!(spread.next(5.0), 0.0);
+}
+
+#[test]
+fn test_from_params() {
+    assert_eq!(EmaSpread::NAME, "EMA_SPREAD");
+    assert!(EmaSpread::from_params(&[2.0, 4.0]).is_ok());
+    assert!(EmaSpread::from_params(&[2.0]).is_err());
+    assert!(EmaSpread::from_params(&[2.5, 4.0]).is_err());
+    assert!(EmaSpread::from_params(&[2.0, -4.0]).is_err());
+    assert!(EmaSpread::from_params(&[4.0, 2.0]).is_err());
+}
+
+#[test]
+fn test_from_params_integers() {
+    let sample = Sample::from_params(&[9007199254740992.0, -128.0]).unwrap();
+    assert_eq!((sample.size, sample.offset), (1 << 53, -128));
+
+    // u64::MAX as f64 rounds up to 2^64
+    assert!(Sample::from_params(&[u64::MAX as f64, 0.0]).is_err());
+    assert!(Sample::from_params(&[1e30, 0.0]).is_err());
+    assert!(Sample::from_params(&[-1.0, 0.0]).is_err());
+    assert!(Sample::from_params(&[1.0, 128.0]).is_err());
+    assert!(Sample::from_params(&[f64::NAN, 0.0]).is_err());
+    assert!(Sample::from_params(&[f64::INFINITY, 0.0]).is_err());
+}
+
+#[test]
+fn test_without_params() {
+    let mut counter = Counter { count: 5 };
+    assert_eq!(counter.to_string(), "COUNTER()");
+    counter.reset();
+    assert_eq!(counter.count, 0);
+}
@@ -6,9 +6,24 @@ extern crate ta_panther;
 #[cfg(test)]
 mod test {
 
+    #[cfg(feature = "serde")]
     mod serde {
-        use ta_panther::indicators::SimpleMovingAverage;
-        use ta_panther::Next;
+        use serde::de::DeserializeOwned;
+        use serde::Serialize;
+        use ta_panther::dsp::{
+            Decycler, DominantCycle, FilterType, MesaAdaptiveMovingAverage, RoofingFilter,
+            SecondOrderFilter, SuperSmoother,
+        };
+        use ta_panther::fixed;
+        use ta_panther::indicators::*;
+        use ta_panther::levels::{FibonacciLevels, FibonacciTag, Levels};
+        use ta_panther::patterns::{InsideBar, NarrowRange, OutsideBar, SwingPoints};
+        use ta_panther::performance::{CalmarRatio, SharpeRatio, SortinoRatio};
+        use ta_panther::returns::{CumulativeReturns, LogReturns, SimpleReturns};
+        use ta_panther::volatility::{
+            GarmanKlassVolatility, HistoricalVolatility, ParkinsonVolatility,
+        };
+        use ta_panther::{Close, DataItem, Next};
 
         // Simple smoke test that serde works (not sure if this is really necessary)
         #[test]
@@ -19,5 +34,380 @@ mod test {
 
             assert_eq!(deserialized.next(2.0), macd.next(2.0));
         }
+
+        /// Random walk of bars, deterministic so failures are reproducible.
+        fn bars() -> Vec<DataItem> {
+            let mut seed: u64 = 42;
+            let mut random = move || {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (seed >> 11) as f64 / (1u64 << 53) as f64
+            };
+            let mut close = 100.0;
+            (0..200)
+                .map(|_| {
+                    let open = close;
+                    close = open * (1.0 + (random() - 0.5) * 0.04);
+                    DataItem::builder()
+                        .open(open)
+                        .high(open.max(close) * (1.0 + random() * 0.01))
+                        .low(open.min(close) * (1.0 - random() * 0.01))
+                        .close(close)
+                        .volume(500.0 + random() * 1000.0)
+                        .build()
+                        .unwrap()
+                })
+                .collect()
+        }
+
+        fn restore_json<T: Serialize + DeserializeOwned>(indicator: &T) -> T {
+            let json = serde_json::to_string(indicator).unwrap();
+            serde_json::from_str(&json).unwrap()
+        }
+
+        fn restore_bincode<T: Serialize + DeserializeOwned>(indicator: &T) -> T {
+            let bytes = bincode::serialize(indicator).unwrap();
+            bincode::deserialize(&bytes).unwrap()
+        }
+
+        /// Feeds the first half of the inputs into every indicator, restores it from JSON and
+        /// bincode, and checks the restored indicators return the same outputs on the second half.
+        macro_rules! assert_round_trip {
+            ($inputs:expr; $($indicator:expr),* $(,)?) => {$({
+                let inputs = &$inputs;
+                let (history, rest) = inputs.split_at(inputs.len() / 2);
+                let mut indicator = $indicator;
+                for &input in history {
+                    indicator.next(input);
+                }
+                let mut from_json = restore_json(&indicator);
+                let mut from_bincode = restore_bincode(&indicator);
+                for &input in rest {
+                    let expected = format!("{:?}", indicator.next(input));
+                    let name = stringify!($indicator);
+                    assert_eq!(format!("{:?}", from_json.next(input)), expected, "{}", name);
+                    assert_eq!(format!("{:?}", from_bincode.next(input)), expected, "{}", name);
+                }
+            })*};
+        }
+
+        #[test]
+        fn test_round_trip_all_indicators() {
+            let bars = bars();
+            let bars: Vec<&DataItem> = bars.iter().collect();
+            let closes: Vec<f64> = bars.iter().map(|bar| bar.close()).collect();
+            let pairs: Vec<(f64, f64)> = closes.windows(2).map(|w| (w[1], w[0])).collect();
+            let bar_pairs: Vec<(&DataItem, &DataItem)> =
+                bars.windows(2).map(|w| (w[1], w[0])).collect();
+            let returns: Vec<f64> = pairs.iter().map(|&(a, b)| a / b - 1.0).collect();
+            let rising: Vec<bool> = pairs.iter().map(|&(a, b)| a > b).collect();
+            let conditional: Vec<(f64, bool)> =
+                closes.iter().copied().zip(rising.clone()).collect();
+            let branches: Vec<(bool, f64, f64)> = rising
+                .iter()
+                .zip(&pairs)
+                .map(|(&condition, &(a, b))| (condition, a, b))
+                .collect();
+
+            assert_round_trip!(bars;
+                Adxr::new(14).unwrap(),
+                Adxr::with_smoothing(14, Smoothing::Sma).unwrap(),
+                AnchoredVwap::new(),
+                ApproximateEntropy::new(20, 2, 0.2).unwrap(),
+                Autocorrelation::new(2, 20).unwrap(),
+                AverageDirectionalIndex::new(14).unwrap(),
+                RegimeClassifier::new(14, 50, 100).unwrap(),
+                AverageTrueRange::with_smoothing(14, Smoothing::Wilder).unwrap(),
+                BollingerBands::new(20, 2.0).unwrap(),
+                BollingerBands::with_deviation(20, 2.0, DeviationType::MeanAbsolute).unwrap(),
+                BollingerBandwidth::new(20, 2.0).unwrap(),
+                BollingerPercentB::new(20, 2.0).unwrap(),
+                ChaikinMoneyFlow::new(20).unwrap(),
+                ChandelierExit::new(22, 3.0).unwrap(),
+                Checked::new(SimpleMovingAverage::new(9).unwrap()),
+                CommodityChannelIndex::new(20).unwrap(),
+                CommodityChannelIndex::with_params(20, 0.015, 10, PriceSource::Hl2).unwrap(),
+                CrossWithHysteresis::new(101.0, 99.0).unwrap(),
+                Displace::new(SimpleMovingAverage::new(5).unwrap(), 3).unwrap(),
+                EfficiencyRatio::new(10).unwrap(),
+                ExpectedShortfall::new(0.95, 30).unwrap(),
+                ExponentialMovingAverage::new(9).unwrap(),
+                ExponentialRegressionForecast::new(10).unwrap(),
+                FastStochastic::new(14).unwrap(),
+                ForecastOscillator::new(14).unwrap(),
+                GeometricMean::new(10).unwrap(),
+                HarmonicMean::new(10).unwrap(),
+                HullMovingAverage::new(16).unwrap(),
+                KalmanFilter::new(0.01, 1.0).unwrap(),
+                KaufmanAdaptiveMovingAverage::new(10, 2, 30).unwrap(),
+                KeltnerChannel::new(20, 2.0).unwrap(),
+                Kurtosis::new(20).unwrap(),
+                Lag::new(3).unwrap(),
+                FibonacciLevels::new(2, 2).unwrap(),
+                FibonacciTag::new(2, 2, 0.618).unwrap(),
+                Levels::new(2, 2, 0.005).unwrap(),
+                MesaAdaptiveMovingAverage::new(0.5, 0.05).unwrap(),
+                DominantCycle::new(),
+                SuperSmoother::new(10).unwrap(),
+                RoofingFilter::new(48, 10).unwrap(),
+                Decycler::new(60).unwrap(),
+                SecondOrderFilter::new(FilterType::BandPass, 20).unwrap(),
+                MaxDrawdown::new(),
+                Maximum::new(14).unwrap(),
+                MeanAbsoluteDeviation::new(14).unwrap(),
+                Minimum::new(14).unwrap(),
+                Momentum::new(10).unwrap(),
+                MoneyFlowIndex::new(14).unwrap(),
+                MovingAverage::new(MaType::Kama, 10).unwrap(),
+                MovingAverageConvergenceDivergence::new(12, 26, 9).unwrap(),
+                InsideBar::new(),
+                OutsideBar::new(),
+                NarrowRange::new(7).unwrap(),
+                SwingPoints::new(2, 2).unwrap(),
+                NanGuard::new(RelativeStrengthIndex::new(14).unwrap(), NanPolicy::Skip),
+                OnBalanceVolume::new(),
+                OrderFlowImbalance::new(20).unwrap(),
+                PercentagePriceOscillator::new(12, 26, 9).unwrap(),
+                PolynomialRegressionForecast::new(20, 2).unwrap(),
+                RateOfChange::new(10).unwrap(),
+                RateOfChangeRatio::new(10).unwrap(),
+                RegressionChannel::new(20, 2.0).unwrap(),
+                RelativeStrengthIndex::new(14).unwrap(),
+                RelativeStrengthIndex::with_smoothing(14, Smoothing::Wilder).unwrap(),
+                RollingLogSumExp::new(10).unwrap(),
+                RollingMaxDrawup::new(20).unwrap(),
+                RollingMedian::new(15).unwrap(),
+                RollingPercentile::new(20, 0.9).unwrap(),
+                ShannonEntropy::new(30, 5).unwrap(),
+                SimpleMovingAverage::new(9).unwrap(),
+                SineWeightedMovingAverage::new(9).unwrap(),
+                Skewness::new(20).unwrap(),
+                SlowStochastic::new(14, 3).unwrap(),
+                StandardDeviation::new(20).unwrap(),
+                StandardError::new(20).unwrap(),
+                StochasticOscillator::new(14, 3, 3).unwrap(),
+                TickRule::new(),
+                TriangularMovingAverage::new(9).unwrap(),
+                TrueRange::new(),
+                TypicalPrice::new(),
+                MedianPrice::new(),
+                WeightedClose::new(),
+                TwiggsMoneyFlow::new(21).unwrap(),
+                UlcerIndex::new(14).unwrap(),
+                UlcerPerformanceIndex::new(14).unwrap(),
+                ValueAtRisk::new(0.95, 30).unwrap(),
+                Vpin::new(10, 5000.0).unwrap(),
+                WeightedMean::new(10).unwrap(),
+                WeightedMovingAverage::new(9).unwrap(),
+                WeightedStd::new(10).unwrap(),
+                WildersMovingAverage::new(14).unwrap(),
+                WithSource::new(ExponentialMovingAverage::new(9).unwrap(), PriceSource::Hl2),
+                ZScore::new(20).unwrap(),
+                ZeroLagExponentialMovingAverage::new(9).unwrap(),
+                SimpleReturns::new(),
+                LogReturns::new(),
+                CumulativeReturns::new(),
+                HistoricalVolatility::new(20, 252.0).unwrap(),
+                ParkinsonVolatility::new(20, 252.0).unwrap(),
+                GarmanKlassVolatility::new(20, 252.0).unwrap(),
+                fixed::SimpleMovingAverage::<9>::new(),
+                fixed::StandardDeviation::<20>::new(),
+            );
+            assert_round_trip!(closes;
+                CrossAbove::new(100.0).unwrap(),
+                CrossBelow::new(100.0).unwrap(),
+                Debounce::new(CrossAbove::new(100.0).unwrap(), 3).unwrap(),
+                Hold::new(CrossBelow::new(100.0).unwrap(), 3).unwrap(),
+                HighestHighValue::new(14).unwrap(),
+                LowestLowValue::new(14).unwrap(),
+                LinearRegressionPrediction::with_horizon(14, 2).unwrap(),
+                fixed::HighestHighValue::<14>::new(),
+                fixed::LowestLowValue::<14>::new(),
+            );
+            assert_round_trip!(returns;
+                SharpeRatio::new(30, 0.0, 252.0).unwrap(),
+                SortinoRatio::new(30, 0.0, 252.0).unwrap(),
+                CalmarRatio::new(30, 252.0).unwrap(),
+            );
+            assert_round_trip!(pairs;
+                BinaryOp::new(Operator::Div),
+                Compare::new(Comparison::Greater),
+                RollingCorrelation::new(20).unwrap(),
+                Beta::new(20).unwrap(),
+            );
+            assert_round_trip!(bar_pairs; RollingCorrelation::new(20).unwrap());
+            assert_round_trip!(rising;
+                BarsSince::new(),
+                CountTrue::new(10).unwrap(),
+            );
+            assert_round_trip!(conditional;
+                ConditionalMean::new(20).unwrap(),
+                SumSince::new(),
+                SplitBy::<_, _, f64>::new(
+                    SimpleMovingAverage::new(5).unwrap(),
+                    ExponentialMovingAverage::new(5).unwrap(),
+                ),
+            );
+            assert_round_trip!(branches; IfThenElse::new());
+        }
+    }
+
+    mod batch {
+        use ta_panther::indicators::{
+            ExponentialMovingAverage, HighestHighValue, LowestLowValue, SimpleMovingAverage,
+            StandardDeviation, WeightedMovingAverage,
+        };
+        use ta_panther::testing::assert_batch_consistent;
+
+        fn amzn_closes() -> Vec<f64> {
+            let mut reader = csv::Reader::from_path("./examples/data/AMZN.csv").unwrap();
+            reader
+                .deserialize()
+                .map(|record| {
+                    let (_date, _open, _high, _low, close, _volume): (
+                        String,
+                        f64,
+                        f64,
+                        f64,
+                        f64,
+                        f64,
+                    ) = record.unwrap();
+                    close
+                })
+                .collect()
+        }
+
+        // the kernels of the `simd` feature sum in another order
+        const SUM_TOLERANCE: f64 = if cfg!(feature = "simd") { 1e-9 } else { 0.0 };
+        const SD_TOLERANCE: f64 = if cfg!(feature = "simd") { 1e-6 } else { 0.0 };
+
+        #[test]
+        fn test_batch_matches_streaming_on_fixture() {
+            let closes = amzn_closes();
+            for period in [1, 5, 20] {
+                assert_batch_consistent(&HighestHighValue::new(period).unwrap(), &closes, 0.0);
+                assert_batch_consistent(&LowestLowValue::new(period).unwrap(), &closes, 0.0);
+                let sma = SimpleMovingAverage::new(period).unwrap();
+                assert_batch_consistent(&sma, &closes, SUM_TOLERANCE);
+                assert_batch_consistent(
+                    &ExponentialMovingAverage::new(period).unwrap(),
+                    &closes,
+                    0.0,
+                );
+                let wma = WeightedMovingAverage::new(period).unwrap();
+                assert_batch_consistent(&wma, &closes, SUM_TOLERANCE);
+                let sd = StandardDeviation::new(period).unwrap();
+                assert_batch_consistent(&sd, &closes, SD_TOLERANCE);
+            }
+        }
+    }
+    #[cfg(feature = "csv")]
+    mod data {
+        use ta_panther::data::csv::CsvReader;
+        use ta_panther::indicators::{AverageTrueRange, RelativeStrengthIndex};
+        use ta_panther::{Close, Next};
+
+        #[test]
+        fn test_indicators_on_fixture() {
+            let bars = CsvReader::new()
+                .open("./examples/data/AMZN.csv")
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            let mut rsi = RelativeStrengthIndex::new(14).unwrap();
+            let mut atr = AverageTrueRange::new(14).unwrap();
+            for bar in &bars {
+                let value = rsi.next(bar.close());
+                assert!((0.0..=100.0).contains(&value));
+                assert!(atr.next(bar) >= 0.0);
+            }
+        }
+    }
+
+    mod periods {
+        use ta_panther::indicators::*;
+        use ta_panther::Next;
+
+        const HUGE: usize = usize::MAX;
+
+        /// Indicators either reject periods they can't allocate, or compute with them like with
+        /// any other period, but never panic on overflows.
+        macro_rules! assert_no_panic {
+            ($($indicator:expr),* $(,)?) => {$(
+                if let Ok(mut indicator) = $indicator {
+                    for input in [1.0, 2.0, 3.0] {
+                        indicator.next(input);
+                    }
+                }
+            )*};
+        }
+
+        #[test]
+        fn test_huge_periods() {
+            assert!(SimpleMovingAverage::new(HUGE).is_err());
+            assert!(ExponentialMovingAverage::new(HUGE).is_ok());
+
+            assert_no_panic!(
+                ApproximateEntropy::new(HUGE, 2, 0.2),
+                ApproximateEntropy::new(10, HUGE, 0.2),
+                Autocorrelation::new(HUGE, 10),
+                Autocorrelation::new(1, HUGE),
+                AverageTrueRange::new(HUGE),
+                BollingerBands::new(HUGE, 2.0),
+                Debounce::new(CrossAbove::new(1.0).unwrap(), HUGE),
+                Displace::new(SimpleMovingAverage::new(2).unwrap(), HUGE),
+                EfficiencyRatio::new(HUGE),
+                ExpectedShortfall::new(0.95, HUGE),
+                ExponentialMovingAverage::new(HUGE),
+                ExponentialRegressionForecast::new(HUGE),
+                FastStochastic::new(HUGE),
+                ForecastOscillator::new(HUGE),
+                GeometricMean::new(HUGE),
+                HarmonicMean::new(HUGE),
+                HighestHighValue::new(HUGE),
+                HullMovingAverage::new(HUGE),
+                KaufmanAdaptiveMovingAverage::new(HUGE, 2, 30),
+                KaufmanAdaptiveMovingAverage::new(10, HUGE, HUGE),
+                Kurtosis::new(HUGE),
+                Lag::new(HUGE),
+                LinearRegressionPrediction::with_horizon(HUGE, 1),
+                LinearRegressionPrediction::with_horizon(10, HUGE),
+                LowestLowValue::new(HUGE),
+                Maximum::new(HUGE),
+                MeanAbsoluteDeviation::new(HUGE),
+                Minimum::new(HUGE),
+                Momentum::new(HUGE),
+                MovingAverageConvergenceDivergence::new(HUGE, HUGE, HUGE),
+                PercentagePriceOscillator::new(HUGE, HUGE, HUGE),
+                PolynomialRegressionForecast::new(HUGE, 2),
+                PolynomialRegressionForecast::new(10, HUGE),
+                RateOfChange::new(HUGE),
+                RateOfChangeRatio::new(HUGE),
+                RegressionChannel::new(HUGE, 2.0),
+                RelativeStrengthIndex::new(HUGE),
+                RollingLogSumExp::new(HUGE),
+                RollingMaxDrawup::new(HUGE),
+                RollingMedian::new(HUGE),
+                RollingPercentile::new(HUGE, 0.5),
+                ShannonEntropy::new(HUGE, 10),
+                ShannonEntropy::new(10, HUGE),
+                SimpleMovingAverage::new(HUGE),
+                SineWeightedMovingAverage::new(HUGE),
+                Skewness::new(HUGE),
+                SlowStochastic::new(HUGE, HUGE),
+                StandardDeviation::new(HUGE),
+                StandardError::new(HUGE),
+                TriangularMovingAverage::new(HUGE),
+                UlcerIndex::new(HUGE),
+                UlcerPerformanceIndex::new(HUGE),
+                ValueAtRisk::new(0.95, HUGE),
+                WeightedMovingAverage::new(HUGE),
+                WildersMovingAverage::new(HUGE),
+                ZScore::new(HUGE),
+                ZeroLagExponentialMovingAverage::new(HUGE),
+            );
+        }
     }
 }